@@ -8,6 +8,7 @@ use tape_core::types::StorageUnits;
 use tape_crypto::ed25519::Keypair as CryptoKeypair;
 use tape_sdk::keys::helpers::load_solana_keypair;
 use tape_sdk::keys::tape_key::TapeKey;
+use tape_sdk::stream::compression::Compression;
 use tape_sdk::stream::manifest::MAX_TRACK_SIZE;
 use tape_sdk::tapedrive::Tapedrive;
 use tokio::io::AsyncReadExt;
@@ -34,6 +35,10 @@ struct Cli {
 
     #[arg(long, default_value_t = DEFAULT_FILL_BYTE)]
     fill_byte: u8,
+
+    /// Compress stream chunks with zstd before encoding.
+    #[arg(long)]
+    compress: bool,
 }
 
 #[tokio::main]
@@ -50,7 +55,10 @@ async fn main() -> Result<()> {
     .context("create rpc client")?;
     let admin = CryptoKeypair::from_solana_keypair(&admin)
         .context("convert admin keypair")?;
-    let sdk = Tapedrive::new(rpc, admin);
+    let mut sdk = Tapedrive::new(rpc, admin);
+    if cli.compress {
+        sdk = sdk.with_compression(Compression::default());
+    }
 
     let tape_key = TapeKey::generate();
     let size = StorageUnits::from_bytes(cli.size_bytes as u64);
@@ -60,6 +68,7 @@ async fn main() -> Result<()> {
     println!("preparing upload stream");
     println!("  size_bytes: {}", cli.size_bytes);
     println!("  chunk_count: {}", chunk_count);
+    println!("  compress: {}", cli.compress);
     println!("  tape_address: {}", tape_key.address());
 
     println!("reserving tape");
@@ -81,6 +90,8 @@ async fn main() -> Result<()> {
     println!("manifest address: {}", receipt.manifest);
     println!("manifest track number: {}", receipt.manifest_track_number.as_u64());
     println!("tape address: {}", receipt.tape);
+    println!("logical bytes: {}", receipt.logical_size.to_bytes());
+    println!("stored bytes: {}", receipt.stored_size.to_bytes());
 
     Ok(())
 }
@@ -55,6 +55,8 @@ pub struct SimnetConfig {
     pub file_log: bool,
     /// Arbitrary serialized accounts to inject into LiteSVM before nodes start.
     pub seed_accounts: Vec<SeededAccount>,
+    /// Seed for the random draws of the network fault plan.
+    pub fault_seed: u64,
 }
 
 impl Default for SimnetConfig {
@@ -67,6 +69,7 @@ impl Default for SimnetConfig {
             slot_advance_per_tx: 1,
             file_log: false,
             seed_accounts: Vec::new(),
+            fault_seed: 0,
         }
     }
 }
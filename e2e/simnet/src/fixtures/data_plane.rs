@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use anyhow::{Context, Result};
 use peer_http::HttpApi;
 use rpc_litesvm::LiteSvmRpc;
@@ -8,6 +10,8 @@ use tape_core::spooler::GroupIndex;
 use tape_core::track::types::CompressedTrack;
 use tape_crypto::address::Address;
 use tape_crypto::ed25519::Keypair as CryptoKeypair;
+use tape_protocol::api::{GetSliceReq, GetSlicesReq};
+use tape_protocol::Api;
 use tape_sdk::keys::tape_key::TapeKey;
 use tape_sdk::tapedrive::Tapedrive;
 use tape_store::ops::{SliceOps, SpoolOps};
@@ -130,4 +134,73 @@ impl SimnetScenario<'_> {
 
         Ok(count)
     }
+
+    /// Fetch the group's slices from their current owners through the batch
+    /// route, one request per owner, and check each against the owner's store.
+    /// Returns how many slices came back matching.
+    pub async fn fetch_current_owner_slices_batched(
+        &self,
+        track: &Address,
+        group: GroupIndex,
+    ) -> Result<usize> {
+        let system = self.read_system().await?;
+        let group_account = self.read_group(system.current_epoch, group).await?;
+        let client = self
+            .harness
+            .nodes()
+            .iter()
+            .find(|node| node.is_running())
+            .context("no running node to fetch slices with")?
+            .context()
+            .api
+            .clone();
+
+        let mut by_owner: HashMap<Address, Vec<GetSliceReq>> = HashMap::new();
+        for (position, spool) in group_account.spools.iter().enumerate() {
+            if spool.node == Address::default() {
+                continue;
+            }
+            by_owner.entry(spool.node).or_default().push(GetSliceReq {
+                track: *track,
+                spool: group.spool_at(position),
+            });
+        }
+
+        let mut matched = 0usize;
+        for (owner, items) in by_owner {
+            let Some(node) = self.harness.nodes().iter().find(|node| {
+                node.is_running() && Address::from(self.node_address(node.id())) == owner
+            }) else {
+                continue;
+            };
+
+            let response = client
+                .get_slices(owner, &GetSlicesReq { items: items.clone() })
+                .await
+                .with_context(|| format!("get_slices from node {}", node.id()))?;
+            anyhow::ensure!(
+                response.results.len() == items.len(),
+                "node {} answered {} of {} batch items",
+                node.id(),
+                response.results.len(),
+                items.len()
+            );
+
+            for (item, result) in items.iter().zip(response.results) {
+                let Ok(fetched) = result else {
+                    continue;
+                };
+                let stored = node
+                    .context()
+                    .store
+                    .get_slice(item.spool, *track)
+                    .with_context(|| format!("get_slice node {} spool {}", node.id(), item.spool))?;
+                if stored.as_deref() == Some(fetched.data.as_slice()) {
+                    matched += 1;
+                }
+            }
+        }
+
+        Ok(matched)
+    }
 }
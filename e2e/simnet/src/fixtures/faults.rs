@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use peer_memory::{FaultChange, FaultScript, LinkFaults};
+use tape_crypto::address::Address;
+use tokio::task::JoinHandle;
+
+use crate::log::append_log;
+use crate::scenario::SimnetScenario;
+
+/// Fault plan controls keyed by node index. Every node's peer calls go
+/// through the harness plan, so changes take effect on running nodes.
+impl SimnetScenario<'_> {
+    /// The address peers use to reach node `index`.
+    pub fn fault_address(&self, index: usize) -> Address {
+        Address::from(self.node_address(index))
+    }
+
+    pub fn fault_addresses(&self, indices: &[usize]) -> Vec<Address> {
+        indices.iter().map(|&index| self.fault_address(index)).collect()
+    }
+
+    /// Cut every link between nodes in `left` and nodes in `right`.
+    pub fn partition_nodes(&self, left: &[usize], right: &[usize]) {
+        append_log(&format!("faults: partition {left:?} from {right:?}"));
+        self.apply_fault(FaultChange::Partition(
+            self.fault_addresses(left),
+            self.fault_addresses(right),
+        ));
+    }
+
+    pub fn heal_partitions(&self) {
+        append_log("faults: heal partitions");
+        self.apply_fault(FaultChange::Heal);
+    }
+
+    /// Faults for every call made to node `index`, e.g. a byzantine node.
+    pub fn set_node_faults(&self, index: usize, faults: LinkFaults) {
+        append_log(&format!("faults: node {index} {faults:?}"));
+        self.apply_fault(FaultChange::Node {
+            node: self.fault_address(index),
+            faults,
+        });
+    }
+
+    /// Faults for calls from node `from` to node `to` only.
+    pub fn set_link_faults(&self, from: usize, to: usize, faults: LinkFaults) {
+        append_log(&format!("faults: link {from}->{to} {faults:?}"));
+        self.apply_fault(FaultChange::Link {
+            from: self.fault_address(from),
+            to: self.fault_address(to),
+            faults,
+        });
+    }
+
+    /// Faults for every link without a node or link rule.
+    pub fn set_default_faults(&self, faults: LinkFaults) {
+        append_log(&format!("faults: default {faults:?}"));
+        self.apply_fault(FaultChange::Default(faults));
+    }
+
+    pub fn clear_faults(&self) {
+        append_log("faults: clear");
+        self.apply_fault(FaultChange::Clear);
+    }
+
+    pub fn apply_fault(&self, change: FaultChange) {
+        self.harness.faults().apply(change);
+    }
+
+    /// Run `script` against the harness fault plan in the background. Offsets
+    /// count from this call.
+    pub fn run_fault_script(&self, script: FaultScript) -> JoinHandle<()> {
+        script.spawn(self.harness.faults().clone())
+    }
+
+    /// Cut `minority` off from every other node for `duration`, then heal.
+    pub fn partition_for(&self, minority: &[usize], duration: Duration) -> JoinHandle<()> {
+        let rest: Vec<usize> = (0..self.harness.nodes().len())
+            .filter(|index| !minority.contains(index))
+            .collect();
+        append_log(&format!("faults: partition {minority:?} for {duration:?}"));
+        let script = FaultScript::new()
+            .at(
+                Duration::ZERO,
+                FaultChange::Partition(self.fault_addresses(minority), self.fault_addresses(&rest)),
+            )
+            .at(duration, FaultChange::Heal);
+        self.run_fault_script(script)
+    }
+}
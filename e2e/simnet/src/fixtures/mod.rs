@@ -5,4 +5,5 @@ pub mod data_plane;
 pub mod diagnostics;
 pub mod epoch;
 pub mod err;
+pub mod faults;
 pub mod state;
@@ -229,6 +229,14 @@ impl TestGateway {
         Ok(path)
     }
 
+    /// Join a fleet of gateways sharing slice cache misses. `peers` is every
+    /// member's base URL, this gateway's included. Must be called before the
+    /// first [`start`](Self::start).
+    pub fn join_fleet(&mut self, peers: Vec<String>) {
+        self.app_config.gateway.fleet.self_url = Some(self.base_url());
+        self.app_config.gateway.fleet.peers = peers;
+    }
+
     /// Inject the admission gate the gateway runtime starts with; without one
     /// the runtime admits every write. Must be called before start.
     pub fn set_admission(&mut self, admission: Arc<dyn Admission>) {
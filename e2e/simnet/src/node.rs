@@ -5,15 +5,18 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use peer_http::HttpApi;
 use peer_manager::PeerManager;
+use peer_memory::{FaultApi, FaultPlan};
 use rpc_client::RpcClient;
 use rpc_litesvm::LiteSvmRpc;
 use solana_keypair::Keypair;
 use solana_pubkey::Pubkey;
 use solana_signer::Signer;
 use store_memory::MemoryStore;
+use tape_api::program::tapedrive::node_pda;
 use tape_core::bls::BlsPrivateKey;
 use tape_core::types::network::NetworkAddress;
 use tape_core::types::tls::NetworkTlsPubkey;
+use tape_crypto::address::Address;
 use tape_crypto::ed25519::Keypair as CryptoKeypair;
 use tape_node::config::node::NodeConfig;
 use tape_node::context::{NodeContext, NodeContextBuilder};
@@ -24,7 +27,10 @@ use tracing::Instrument;
 
 use crate::config::NodeRuntimeMode;
 
-type TestNodeContext = Arc<NodeContext<MemoryStore, HttpApi, LiteSvmRpc>>;
+/// Peer transport for a node: real HTTP, behind the harness fault plan.
+type TestNodeApi = FaultApi<HttpApi>;
+
+type TestNodeContext = Arc<NodeContext<MemoryStore, TestNodeApi, LiteSvmRpc>>;
 
 struct TestConfig {
     mode: NodeRuntimeMode,
@@ -52,6 +58,7 @@ pub struct TestNode {
     tls_keypair: CryptoKeypair,
     rpc: LiteSvmRpc,
     app_config: NodeConfig,
+    faults: Arc<FaultPlan>,
     context: Option<TestNodeContext>,
     test_config: TestConfig,
     runtime: Option<NodeRuntimeHandle>,
@@ -65,6 +72,7 @@ impl TestNode {
         bind_addr: SocketAddr,
         public_port: u16,
         stop_timeout: Duration,
+        faults: Arc<FaultPlan>,
     ) -> Result<Self> {
         let keypair = Keypair::new();
         let bls_keypair = BlsPrivateKey::from_random();
@@ -87,6 +95,7 @@ impl TestNode {
             tls_keypair,
             rpc,
             app_config,
+            faults,
             context: None,
             test_config: TestConfig::new(mode, stop_timeout),
             runtime: None,
@@ -212,14 +221,14 @@ impl TestNode {
 
         let tls_identity = Arc::new(clone_ed25519_keypair(&self.tls_keypair));
 
-        let api = Arc::new(
-            peer_http::HttpApiBuilder::new()
-                .local_identity(tls_identity.clone())
-                .build(peer_manager.clone())
-                .context("build HttpApi")?,
-        );
+        let http = peer_http::HttpApiBuilder::new()
+            .local_identity(tls_identity.clone())
+            .build(peer_manager.clone())
+            .context("build HttpApi")?;
+        let (local, _) = node_pda(Address::from(self.authority()));
+        let api = Arc::new(FaultApi::new(http, local, self.faults.clone()));
 
-        let context = NodeContextBuilder::<MemoryStore, HttpApi, LiteSvmRpc>::new(
+        let context = NodeContextBuilder::<MemoryStore, TestNodeApi, LiteSvmRpc>::new(
             self.app_config.clone(),
             clone_keypair(&self.keypair),
             self.bls_keypair.clone(),
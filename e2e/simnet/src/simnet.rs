@@ -1,7 +1,9 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{anyhow, bail, Context, Result};
+use peer_memory::FaultPlan;
 use solana_keypair::Keypair;
 use solana_pubkey::Pubkey;
 use tokio::task::JoinHandle;
@@ -56,6 +58,11 @@ impl SimnetBuilder {
         self
     }
 
+    pub fn fault_seed(mut self, seed: u64) -> Self {
+        self.config.fault_seed = seed;
+        self
+    }
+
     pub fn seed_account(
         mut self,
         address: impl Into<Pubkey>,
@@ -85,9 +92,10 @@ impl SimnetBuilder {
                 })?;
         }
 
+        let faults = Arc::new(FaultPlan::new(self.config.fault_seed));
         let mut nodes = Vec::with_capacity(self.config.node_count);
         for i in 0..self.config.node_count {
-            nodes.push(make_node(&self.config, &chain, i)?);
+            nodes.push(make_node(&self.config, &chain, &faults, i)?);
         }
 
         let admin = Keypair::new();
@@ -97,6 +105,7 @@ impl SimnetBuilder {
             chain,
             admin,
             nodes,
+            faults,
             block_producer: None,
         })
     }
@@ -117,6 +126,9 @@ pub struct SimnetHarness {
     chain: ChainFixture,
     admin: Keypair,
     nodes: Vec<TestNode>,
+    /// Faults every node's peer calls pass through; empty until a test sets
+    /// some.
+    faults: Arc<FaultPlan>,
     block_producer: Option<JoinHandle<()>>,
 }
 
@@ -149,13 +161,17 @@ impl SimnetHarness {
         self.nodes.get_mut(index)
     }
 
+    pub fn faults(&self) -> &Arc<FaultPlan> {
+        &self.faults
+    }
+
     pub fn scenario(&self) -> SimnetScenario<'_> {
         SimnetScenario::new(self)
     }
 
     pub fn add_node(&mut self) -> Result<usize> {
         let id = self.nodes.len();
-        let node = make_node(&self.config, &self.chain, id)?;
+        let node = make_node(&self.config, &self.chain, &self.faults, id)?;
         self.nodes.push(node);
         self.config.node_count = self.nodes.len();
         Ok(id)
@@ -262,7 +278,12 @@ impl SimnetHarness {
     }
 }
 
-fn make_node(config: &SimnetConfig, chain: &ChainFixture, id: usize) -> Result<TestNode> {
+fn make_node(
+    config: &SimnetConfig,
+    chain: &ChainFixture,
+    faults: &Arc<FaultPlan>,
+    id: usize,
+) -> Result<TestNode> {
     let bind_addr = if config.base_port == 0 {
         tls::pick_bind(id as u64)?
     } else {
@@ -280,5 +301,6 @@ fn make_node(config: &SimnetConfig, chain: &ChainFixture, id: usize) -> Result<T
         bind_addr,
         port,
         config.stop_timeout,
+        faults.clone(),
     )
 }
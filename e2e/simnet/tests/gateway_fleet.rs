@@ -0,0 +1,263 @@
+//! Two gateways in one fleet split slice cache misses by rendezvous owner,
+//! and a gateway whose peer goes down still serves by falling back to the
+//! storage nodes.
+//!
+//! Storage nodes keep no access threshold here, so the gateways read slices
+//! without being registered or staked (see `gateway_read.rs` for that flow).
+
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use tape_api::program::tapedrive::track_pda;
+use tape_chain_harness::TEST_MAX_EPOCH_DURATION;
+use tape_core::erasure::GROUP_SIZE;
+use tape_core::spooler::GroupIndex;
+use tape_core::types::{BasisPoints, StorageUnits};
+use tape_crypto::address::Address;
+use tape_e2e_simnet::{
+    NodeRuntimeMode, SimnetBuilder, SimnetScenario, TestGateway, run_simnet_test,
+};
+use tape_sdk::keys::tape_key::TapeKey;
+use tape_store::ops::SliceOps;
+
+const NODE_COUNT: usize = GROUP_SIZE;
+const TARGET_GROUPS: u64 = 5;
+const STORAGE_NODE_STAKE: u64 = 1_000;
+
+#[test]
+fn fleet_shares_slices() {
+    run_simnet_test(fleet_shares_slices_inner);
+}
+
+async fn fleet_shares_slices_inner() {
+    peer_tls::install_default_provider();
+
+    let mut harness = SimnetBuilder::new()
+        .node_count(NODE_COUNT)
+        .runtime_mode(NodeRuntimeMode::Full)
+        .file_log(true)
+        .build()
+        .expect("build harness");
+    let mut gateways = vec![
+        TestGateway::new(0, harness.chain().rpc().clone()).expect("build gateway 0"),
+        TestGateway::new(1, harness.chain().rpc().clone()).expect("build gateway 1"),
+    ];
+    let peers: Vec<String> = gateways.iter().map(TestGateway::base_url).collect();
+    for gateway in &mut gateways {
+        gateway.join_fleet(peers.clone());
+    }
+
+    let all: Vec<usize> = (0..NODE_COUNT).collect();
+    let health_timeout = Duration::from_secs(30);
+    let active_timeout = Duration::from_secs(60);
+    let slice_timeout = Duration::from_secs(120);
+    let epoch_timeout = Duration::from_secs(TEST_MAX_EPOCH_DURATION.0 * 5);
+
+    {
+        let scenario = harness.scenario();
+        scenario.init_system().await.expect("init system");
+        scenario
+            .register_nodes(BasisPoints(100))
+            .await
+            .expect("register storage nodes");
+        scenario
+            .stake_all(STORAGE_NODE_STAKE)
+            .await
+            .expect("stake storage nodes");
+        scenario
+            .set_spool_groups_many(&all, TARGET_GROUPS)
+            .await
+            .expect("set spool group preferences");
+        scenario.start_network().await.expect("start network");
+    }
+
+    harness
+        .start_all_with_retry(3, Duration::from_millis(200))
+        .await
+        .expect("start storage nodes");
+
+    {
+        let scenario = harness.scenario();
+        scenario
+            .wait_nodes_healthy(health_timeout)
+            .await
+            .expect("storage nodes healthy");
+        scenario
+            .wait_nodes_active(&all, active_timeout)
+            .await
+            .expect("storage nodes active");
+        for expected in [2, 3] {
+            let epoch = scenario
+                .self_advance_epoch(epoch_timeout)
+                .await
+                .expect("advance epoch");
+            assert_eq!(epoch, expected, "unexpected epoch");
+        }
+        scenario
+            .wait_nodes_active(&all, active_timeout)
+            .await
+            .expect("storage nodes active at epoch 3");
+        eprintln!("gateway_fleet: storage nodes active at epoch 3");
+    }
+
+    let scenario = harness.scenario();
+    let first_data = deterministic_bytes(64 * 1024, 0);
+    let second_data = deterministic_bytes(64 * 1024, 7);
+    let writer = scenario.sdk(harness.admin());
+    let tape_key = TapeKey::generate();
+    writer
+        .reserve(
+            &tape_key,
+            StorageUnits::from_bytes((first_data.len() + second_data.len()) as u64)
+                + StorageUnits::mb(4),
+            4,
+        )
+        .await
+        .expect("reserve tape");
+
+    let mut tracks = Vec::new();
+    for data in [&first_data, &second_data] {
+        let track = writer
+            .write_track(&tape_key, data)
+            .await
+            .expect("write coded track");
+        assert!(track.is_coded(), "fleet tracks must use coded slice reads");
+        let address = track_pda(track.tape, track.track_number).0;
+        wait_current_owner_slices(&scenario, &address, track.group, GROUP_SIZE, slice_timeout)
+            .await
+            .expect("all current owners should have their slice");
+        tracks.push((address, track.group));
+    }
+    eprintln!("gateway_fleet: tracks written");
+
+    for gateway in &mut gateways {
+        gateway.start().await.expect("start gateway");
+        wait_gateway_healthy(&gateway.base_url(), Duration::from_secs(180))
+            .await
+            .expect("gateway healthy");
+    }
+    eprintln!("gateway_fleet: gateways healthy");
+
+    // Both members serve the first track, and every slice read for it lives
+    // in exactly one member's cache.
+    let (first, first_group) = tracks[0];
+    for gateway in &gateways {
+        assert_track_bytes(&gateway.base_url(), &first, &first_data)
+            .await
+            .expect("fleet gateway should serve the first track");
+    }
+    let cached: Vec<Vec<usize>> = gateways
+        .iter()
+        .map(|gateway| cached_positions(gateway, &first, first_group))
+        .collect();
+    for position in &cached[0] {
+        assert!(
+            !cached[1].contains(position),
+            "slice {position} cached by both fleet members"
+        );
+    }
+    assert!(
+        cached.iter().all(|positions| !positions.is_empty()),
+        "each member should own part of the track, cached {cached:?}"
+    );
+    eprintln!("gateway_fleet: first track split across members {cached:?}");
+
+    // With its peer down, gateway 0 reads the second track from the storage
+    // nodes on its own.
+    gateways[1].stop().await.expect("stop gateway 1");
+    let (second, second_group) = tracks[1];
+    assert_track_bytes(&gateways[0].base_url(), &second, &second_data)
+        .await
+        .expect("gateway should fall back when its peer is down");
+    assert!(
+        cached_positions(&gateways[1], &second, second_group).is_empty(),
+        "stopped peer should not have cached the second track"
+    );
+    eprintln!("gateway_fleet: fallback read with peer down succeeded");
+
+    gateways[0].stop().await.expect("stop gateway 0");
+}
+
+async fn assert_track_bytes(
+    gateway_base: &str,
+    track: &Address,
+    expected: &[u8],
+) -> anyhow::Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(180))
+        .build()?;
+    let response = client
+        .get(format!("{gateway_base}/track/{track}"))
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK, "track read should return 200");
+    let bytes = response.bytes().await?;
+    assert_eq!(bytes.as_ref(), expected, "track bytes should match written data");
+    Ok(())
+}
+
+fn cached_positions(gateway: &TestGateway, track: &Address, group: GroupIndex) -> Vec<usize> {
+    let context = gateway.context();
+    (0..GROUP_SIZE)
+        .filter(|position| {
+            context
+                .store
+                .get_slice(group.spool_at(*position), *track)
+                .expect("read gateway slice store")
+                .is_some()
+        })
+        .collect()
+}
+
+async fn wait_current_owner_slices(
+    scenario: &SimnetScenario<'_>,
+    track: &Address,
+    group: GroupIndex,
+    expected: usize,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let start = Instant::now();
+
+    loop {
+        let observed = scenario.count_current_owner_slices(track, group).await?;
+        if observed == expected {
+            return Ok(());
+        }
+
+        if start.elapsed() >= timeout {
+            anyhow::bail!(
+                "timed out waiting for current group owners to hold {expected} slices, observed {observed}"
+            );
+        }
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+async fn wait_gateway_healthy(base: &str, timeout: Duration) -> anyhow::Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()?;
+    let start = Instant::now();
+    loop {
+        if let Ok(response) = client.get(format!("{base}/v1/health")).send().await {
+            if response.status() == StatusCode::OK {
+                return Ok(());
+            }
+        }
+        if start.elapsed() >= timeout {
+            anyhow::bail!("timed out waiting for gateway health");
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+fn deterministic_bytes(len: usize, seed: usize) -> Vec<u8> {
+    (0..len)
+        .map(|i| {
+            let mixed = (i + seed).wrapping_mul(31) ^ i.rotate_left(5);
+            mixed as u8
+        })
+        .collect()
+}
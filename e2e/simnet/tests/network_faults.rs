@@ -0,0 +1,108 @@
+//! A minority partition and a lossy, slow network do not stall epoch
+//! transitions, and the partitioned node is active again once the partition
+//! heals.
+
+use std::time::Duration;
+
+use peer_memory::{Latency, LinkFaults};
+use tape_chain_harness::TEST_MAX_EPOCH_DURATION;
+use tape_core::erasure::GROUP_SIZE;
+use tape_core::types::BasisPoints;
+use tape_e2e_simnet::{NodeRuntimeMode, SimnetBuilder, run_simnet_test};
+
+const NODE_COUNT: usize = GROUP_SIZE;
+const TARGET_GROUPS: u64 = 1;
+const PARTITIONED_NODE: usize = 0;
+const NODE_STAKE: u64 = 1_000;
+
+#[test]
+fn network_faults() {
+    run_simnet_test(network_faults_inner);
+}
+
+async fn network_faults_inner() {
+    let mut harness = SimnetBuilder::new()
+        .node_count(NODE_COUNT)
+        .runtime_mode(NodeRuntimeMode::Full)
+        .file_log(true)
+        .fault_seed(7)
+        .build()
+        .expect("build harness");
+
+    let all: Vec<usize> = (0..NODE_COUNT).collect();
+    let health_timeout = Duration::from_secs(30);
+    let active_timeout = Duration::from_secs(60);
+    let epoch_timeout = Duration::from_secs(TEST_MAX_EPOCH_DURATION.0 * 5);
+
+    {
+        let scenario = harness.scenario();
+        scenario.init_system().await.expect("init system");
+        scenario
+            .register_nodes(BasisPoints(100))
+            .await
+            .expect("register nodes");
+        scenario.stake_all(NODE_STAKE).await.expect("stake nodes");
+        scenario
+            .set_spool_groups_many(&all, TARGET_GROUPS)
+            .await
+            .expect("set spool group preferences");
+        scenario.start_network().await.expect("start network");
+    }
+
+    harness
+        .start_all_with_retry(3, Duration::from_millis(200))
+        .await
+        .expect("start runtimes");
+
+    let scenario = harness.scenario();
+    scenario
+        .wait_nodes_healthy(health_timeout)
+        .await
+        .expect("nodes healthy");
+    scenario
+        .wait_nodes_active(&all, active_timeout)
+        .await
+        .expect("nodes active");
+    assert_eq!(
+        scenario.self_advance_epoch(epoch_timeout).await.expect("advance epoch"),
+        2,
+        "unexpected epoch"
+    );
+
+    // Every peer call is slow and a few are lost, while one node is cut off
+    // from the rest for most of an epoch.
+    scenario.set_default_faults(LinkFaults {
+        latency: Latency::Uniform {
+            min: Duration::from_millis(5),
+            max: Duration::from_millis(50),
+        },
+        drop_rate: 0.05,
+        ..LinkFaults::default()
+    });
+    let partition = scenario.partition_for(
+        &[PARTITIONED_NODE],
+        Duration::from_secs(TEST_MAX_EPOCH_DURATION.0 / 2),
+    );
+    assert_eq!(
+        scenario
+            .self_advance_epoch(epoch_timeout)
+            .await
+            .expect("advance epoch with a minority partition"),
+        3,
+        "unexpected epoch"
+    );
+    partition.await.expect("fault script");
+    eprintln!("network_faults: epoch 3 reached with node {PARTITIONED_NODE} partitioned");
+
+    scenario.clear_faults();
+    assert_eq!(
+        scenario.self_advance_epoch(epoch_timeout).await.expect("advance epoch"),
+        4,
+        "unexpected epoch"
+    );
+    scenario
+        .wait_nodes_active(&all, active_timeout)
+        .await
+        .expect("all nodes active after the partition heals");
+    eprintln!("network_faults: all nodes active at epoch 4");
+}
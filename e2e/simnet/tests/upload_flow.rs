@@ -209,6 +209,15 @@ async fn upload_flow_inner() {
             slice_count, GROUP_SIZE,
             "blob track should be stored across the full group"
         );
+
+        let fetched = scenario
+            .fetch_current_owner_slices_batched(&track_address, track.group)
+            .await
+            .expect("batch fetch blob slices");
+        assert_eq!(
+            fetched, GROUP_SIZE,
+            "batched reads should return every stored slice"
+        );
     }
 
     harness.stop_all().await.expect("stop runtimes");
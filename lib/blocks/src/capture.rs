@@ -1,7 +1,9 @@
 //! Capture parsed Tapedrive instructions into replayable events.
 
 use bytemuck::bytes_of;
-use tape_api::event::{TapeDestroyed, TapeExtended, TapeReserved, TrackDeleted, TrackWritten};
+use tape_api::event::{
+    TapeDestroyed, TapeExtended, TapeReserved, TapeTransferred, TrackDeleted, TrackWritten,
+};
 use tape_api::program::tapedrive::track_pda;
 use tape_core::snapshot::replay::{ReplayRecord, ReplayTrack, ReplayTrackObject, ReplayableEvent};
 use tape_core::spooler::GroupIndex;
@@ -292,6 +294,47 @@ fn capture_instruction(
         ParsedInstruction::ExtendTape { event, .. } => {
             capture_extend(*current_epoch, tx_id, actor, event)
         }
+        ParsedInstruction::TransferTape { event, .. } => {
+            capture_transfer(*current_epoch, tx_id, actor, event)
+        }
+        ParsedInstruction::SetTapeVersioning { event, .. } => Captured {
+            event: captured_event(
+                *current_epoch,
+                tx_id,
+                actor,
+                ReplayableEvent::SetTapeVersioning {
+                    tape: event.tape,
+                    status: event.status,
+                },
+            ),
+            raw_track: None,
+        },
+        ParsedInstruction::PlaceDeleteMarker { name, event, .. } => Captured {
+            event: captured_event(
+                *current_epoch,
+                tx_id,
+                actor,
+                ReplayableEvent::PlaceDeleteMarker {
+                    tape: event.tape,
+                    name: name.clone(),
+                },
+            ),
+            raw_track: None,
+        },
+        ParsedInstruction::RemoveDeleteMarker { name, event, .. } => Captured {
+            event: captured_event(
+                *current_epoch,
+                tx_id,
+                actor,
+                ReplayableEvent::RemoveDeleteMarker {
+                    tape: event.tape,
+                    name: name.clone(),
+                    track_number: event.track_number,
+                    marker: event.marker as u32,
+                },
+            ),
+            raw_track: None,
+        },
         ParsedInstruction::RegisterNode { authority, event, .. } => Captured {
             event: captured_event(
                 *current_epoch,
@@ -332,6 +375,19 @@ fn capture_instruction(
             ),
             raw_track: None,
         },
+        ParsedInstruction::StakeSlashed { event } => Captured {
+            event: captured_event(
+                *current_epoch,
+                tx_id,
+                actor,
+                ReplayableEvent::StakeSlashed {
+                    pool: event.pool,
+                    reason: event.reason,
+                    amount: event.amount,
+                },
+            ),
+            raw_track: None,
+        },
         ParsedInstruction::AddToBlacklist { entry, event, .. } => capture_track(
             *current_epoch,
             tx_id,
@@ -523,6 +579,27 @@ fn capture_extend(
     }
 }
 
+fn capture_transfer(
+    epoch: EpochNumber,
+    tx_id: Txid,
+    actor: Option<Address>,
+    event: &TapeTransferred,
+) -> Captured {
+    Captured {
+        event: captured_event(
+            epoch,
+            tx_id,
+            actor,
+            ReplayableEvent::TransferTape {
+                tape: event.tape,
+                previous_authority: event.previous_authority,
+                authority: event.authority,
+            },
+        ),
+        raw_track: None,
+    }
+}
+
 fn actor_for(instruction: &ParsedInstruction) -> Option<Address> {
     match instruction {
         ParsedInstruction::SyncSpool { node, .. }
@@ -544,6 +621,11 @@ fn actor_for(instruction: &ParsedInstruction) -> Option<Address> {
 
         ParsedInstruction::ExtendTape { payer, .. } => Some((*payer).into()),
 
+        ParsedInstruction::TransferTape { authority, .. }
+        | ParsedInstruction::SetTapeVersioning { authority, .. }
+        | ParsedInstruction::PlaceDeleteMarker { authority, .. }
+        | ParsedInstruction::RemoveDeleteMarker { authority, .. } => Some(*authority),
+
         ParsedInstruction::ProposeSnapshot { proposer, .. }
         | ParsedInstruction::ProposeAssignment { proposer, .. }
         | ParsedInstruction::ProposeEviction { proposer, .. } => Some(*proposer),
@@ -558,6 +640,7 @@ fn actor_for(instruction: &ParsedInstruction) -> Option<Address> {
         | ParsedInstruction::CommitEpoch { .. }
         | ParsedInstruction::AdvanceEpoch { .. }
         | ParsedInstruction::NodeEvicted { .. }
+        | ParsedInstruction::StakeSlashed { .. }
         | ParsedInstruction::FinalizeSnapshot { .. }
         | ParsedInstruction::FinalizeGroup { .. }
         | ParsedInstruction::CertifyTrack { .. }
@@ -570,8 +653,8 @@ fn actor_for(instruction: &ParsedInstruction) -> Option<Address> {
 mod tests {
     use bytemuck::{bytes_of, Zeroable};
     use tape_api::event::{
-        EpochAdvanced, SnapshotFinalized, TapeExtended, TapeReserved, TrackCertified,
-        TrackWritten,
+        DeleteMarkerRemoved, EpochAdvanced, SnapshotFinalized, TapeExtended, TapeReserved,
+        TapeTransferred, TrackCertified, TrackWritten,
     };
     use tape_api::program::tapedrive::{snapshot_tape_pda, track_pda};
     use tape_core::encoding::EncodingProfile;
@@ -965,6 +1048,74 @@ mod tests {
         }
     }
 
+    // A transfer captures both authorities and records the new one as actor.
+    #[test]
+    fn captures_transfer_tape() {
+        let tape = Address::new_unique();
+        let previous = Address::new_unique();
+        let authority = Address::new_unique();
+
+        let captured = capture(
+            EpochNumber(7),
+            SlotNumber(42),
+            vec![ParsedInstruction::TransferTape {
+                authority,
+                tape,
+                event: TapeTransferred {
+                    tape,
+                    previous_authority: previous,
+                    authority,
+                },
+            }],
+        );
+
+        assert_eq!(captured.events.len(), 1);
+        assert_eq!(captured.events[0].record.actor, Some(authority));
+        assert_eq!(
+            captured.events[0].record.event,
+            ReplayableEvent::TransferTape {
+                tape,
+                previous_authority: previous,
+                authority,
+            }
+        );
+    }
+
+    // A marker removal captures the name and marker, with the operator as actor.
+    #[test]
+    fn captures_remove_delete_marker() {
+        let tape = Address::new_unique();
+        let authority = Address::new_unique();
+
+        let captured = capture(
+            EpochNumber(7),
+            SlotNumber(42),
+            vec![ParsedInstruction::RemoveDeleteMarker {
+                authority,
+                tape,
+                name: b"k".to_vec(),
+                event: DeleteMarkerRemoved {
+                    tape,
+                    key: Hash::new_unique(),
+                    track_number: TrackNumber(4),
+                    marker: 2,
+                },
+            }],
+        );
+
+        assert_eq!(captured.events.len(), 1);
+        assert_eq!(captured.events[0].record.actor, Some(authority));
+        assert_eq!(
+            captured.events[0].record.event,
+            ReplayableEvent::RemoveDeleteMarker {
+                tape,
+                name: b"k".to_vec(),
+                track_number: TrackNumber(4),
+                marker: 2,
+            }
+        );
+    }
+
     #[test]
     fn captures_snapshot_finalization() {
         let snapshot_epoch = EpochNumber(7);
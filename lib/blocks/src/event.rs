@@ -1,11 +1,13 @@
 //! Tapedrive event parsing from transaction logs.
 
 use tape_api::event::{
-    AssignmentFinalized, CommitteeCreated, CommitteeResized, EpochAdvanced, EpochCommitted,
-    EpochCreated, EventType, CommissionClaimed, NodeEvicted, NodeJoinedCommittee, NodeRegistered,
-    PeerSetResized, PoolAdvanced, SnapshotFinalized, SpoolSynced, StakeDeposited,
+    AssignmentFinalized, CommitteeCreated, CommitteeResized, DeleteMarkerPlaced,
+    DeleteMarkerRemoved, EpochAdvanced, EpochCommitted, EpochCreated, EventType,
+    CommissionClaimed, NodeEvicted, NodeJoinedCommittee, NodeRegistered, PeerSetResized,
+    PoolAdvanced, SnapshotFinalized, SpoolSynced, StakeDeposited, StakeSlashed,
     StakeUnlockRequested, StakeWithdrawn, TapeDestroyed, TapeExtended, TapeReserved,
-    TrackCertified, TrackDeleted, TrackInvalidated, TrackWritten, VoteProposed, VoteRecorded,
+    TapeTransferred, TapeVersioningSet, TrackCertified, TrackDeleted, TrackInvalidated,
+    TrackWritten, VoteProposed, VoteRecorded,
 };
 
 use crate::error::ParseError;
@@ -34,6 +36,10 @@ pub enum TapedriveEvent {
     TapeReserved(TapeReserved),
     TapeDestroyed(TapeDestroyed),
     TapeExtended(TapeExtended),
+    TapeTransferred(TapeTransferred),
+    TapeVersioningSet(TapeVersioningSet),
+    DeleteMarkerPlaced(DeleteMarkerPlaced),
+    DeleteMarkerRemoved(DeleteMarkerRemoved),
     NodeRegistered(NodeRegistered),
     NodeJoinedCommittee(NodeJoinedCommittee),
     NodeEvicted(NodeEvicted),
@@ -42,6 +48,7 @@ pub enum TapedriveEvent {
     StakeDeposited(StakeDeposited),
     StakeUnlockRequested(StakeUnlockRequested),
     StakeWithdrawn(StakeWithdrawn),
+    StakeSlashed(StakeSlashed),
     CommissionClaimed(CommissionClaimed),
 }
 
@@ -154,6 +161,31 @@ pub fn parse_event_data(log: &str) -> Result<Option<TapedriveEvent>, ParseError>
                 .map_err(|_| ParseError::InvalidEvent)?;
             Ok(Some(TapedriveEvent::TapeExtended(*event)))
         }
+        EventType::TapeTransferred => {
+            let event = bytemuck::try_from_bytes::<TapeTransferred>(event_data)
+                .map_err(|_| ParseError::InvalidEvent)?;
+            Ok(Some(TapedriveEvent::TapeTransferred(*event)))
+        }
+        EventType::TapeVersioningSet => {
+            let event = bytemuck::try_from_bytes::<TapeVersioningSet>(event_data)
+                .map_err(|_| ParseError::InvalidEvent)?;
+            Ok(Some(TapedriveEvent::TapeVersioningSet(*event)))
+        }
+        EventType::DeleteMarkerPlaced => {
+            let event = bytemuck::try_from_bytes::<DeleteMarkerPlaced>(event_data)
+                .map_err(|_| ParseError::InvalidEvent)?;
+            Ok(Some(TapedriveEvent::DeleteMarkerPlaced(*event)))
+        }
+        EventType::DeleteMarkerRemoved => {
+            let event = bytemuck::try_from_bytes::<DeleteMarkerRemoved>(event_data)
+                .map_err(|_| ParseError::InvalidEvent)?;
+            Ok(Some(TapedriveEvent::DeleteMarkerRemoved(*event)))
+        }
+        // Escrow bookkeeping has no effect on stored data. Renewals that
+        // move a tape's expiry surface through TapeExtended instead.
+        EventType::TapeEscrowFunded
+        | EventType::TapeEscrowWithdrawn
+        | EventType::TapeEscrowLow => Ok(None),
         EventType::NodeRegistered => {
             let event = bytemuck::try_from_bytes::<NodeRegistered>(event_data)
                 .map_err(|_| ParseError::InvalidEvent)?;
@@ -194,6 +226,11 @@ pub fn parse_event_data(log: &str) -> Result<Option<TapedriveEvent>, ParseError>
                 .map_err(|_| ParseError::InvalidEvent)?;
             Ok(Some(TapedriveEvent::StakeWithdrawn(*event)))
         }
+        EventType::StakeSlashed => {
+            let event = bytemuck::try_from_bytes::<StakeSlashed>(event_data)
+                .map_err(|_| ParseError::InvalidEvent)?;
+            Ok(Some(TapedriveEvent::StakeSlashed(*event)))
+        }
         EventType::CommissionClaimed => {
             let event = bytemuck::try_from_bytes::<CommissionClaimed>(event_data)
                 .map_err(|_| ParseError::InvalidEvent)?;
@@ -495,6 +532,56 @@ mod tests {
         }
     }
 
+    // A tape transferred event decodes from an encoded program data log line.
+    #[test]
+    fn parse_tape_transferred_event() {
+        let event = TapeTransferred {
+            tape: Address::new_unique(),
+            previous_authority: Address::new_unique(),
+            authority: Address::new_unique(),
+        };
+
+        let log = encode_event(EventType::TapeTransferred, &event);
+        let parsed = parse_event_data(&log)
+            .expect("parse succeeds")
+            .expect("event present");
+
+        match parsed {
+            TapedriveEvent::TapeTransferred(e) => {
+                assert_eq!(e.tape, event.tape);
+                assert_eq!(e.previous_authority, event.previous_authority);
+                assert_eq!(e.authority, event.authority);
+            }
+            _ => panic!("Expected TapeTransferred event"),
+        }
+    }
+
+    // A delete marker removal decodes with the marker it names.
+    #[test]
+    fn parse_delete_marker_removed_event() {
+        let event = DeleteMarkerRemoved {
+            tape: Address::new_unique(),
+            key: Hash::new_unique(),
+            track_number: TrackNumber(9),
+            marker: 2,
+        };
+
+        let log = encode_event(EventType::DeleteMarkerRemoved, &event);
+        let parsed = parse_event_data(&log)
+            .expect("parse succeeds")
+            .expect("event present");
+
+        match parsed {
+            TapedriveEvent::DeleteMarkerRemoved(e) => {
+                assert_eq!(e.tape, event.tape);
+                assert_eq!(e.key, event.key);
+                assert_eq!(e.track_number, TrackNumber(9));
+                assert_eq!(e.marker, 2);
+            }
+            _ => panic!("Expected DeleteMarkerRemoved event"),
+        }
+    }
+
     #[test]
     fn parse_stake_and_commission_events() {
         let stake = Address::new_unique();
@@ -550,6 +637,23 @@ mod tests {
             other => panic!("Expected StakeWithdrawn event, got {other:?}"),
         }
 
+        let slashed = StakeSlashed {
+            pool,
+            epoch: EpochNumber(11),
+            reason: 1,
+            evidence: Hash::new_unique(),
+            amount: TAPE(50),
+            fronted: TAPE(40),
+        };
+        let log = encode_event(EventType::StakeSlashed, &slashed);
+        match parse_event_data(&log).unwrap().unwrap() {
+            TapedriveEvent::StakeSlashed(event) => {
+                assert_eq!(event.pool, pool);
+                assert_eq!(event.fronted, TAPE(40));
+            }
+            other => panic!("Expected StakeSlashed event, got {other:?}"),
+        }
+
         let commission = CommissionClaimed {
             node,
             authority,
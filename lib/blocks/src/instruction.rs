@@ -1,10 +1,12 @@
 use solana_transaction_status::UiCompiledInstruction;
 use tape_api::event::{
     AssignmentFinalized, CommissionClaimed, CommitteeCreated, CommitteeResized,
-    EpochAdvanced, EpochCommitted, EpochCreated, NodeEvicted, NodeJoinedCommittee, NodeRegistered,
-    PeerSetResized, PoolAdvanced, SnapshotFinalized, SpoolSynced, StakeDeposited,
-    StakeUnlockRequested, StakeWithdrawn, TapeDestroyed, TapeExtended, TapeReserved,
-    TrackCertified, TrackDeleted, TrackInvalidated, TrackWritten, VoteProposed, VoteRecorded,
+    DeleteMarkerPlaced, DeleteMarkerRemoved, EpochAdvanced, EpochCommitted, EpochCreated,
+    NodeEvicted, NodeJoinedCommittee, NodeRegistered, PeerSetResized, PoolAdvanced,
+    SnapshotFinalized, SpoolSynced, StakeDeposited, StakeSlashed, StakeUnlockRequested,
+    StakeWithdrawn, TapeDestroyed, TapeExtended, TapeReserved, TapeTransferred,
+    TapeVersioningSet, TrackCertified, TrackDeleted, TrackInvalidated, TrackWritten,
+    VoteProposed, VoteRecorded,
 };
 use tape_api::instruction::{self as ix, TapeInstruction};
 use tape_api::program::tapedrive::{track_pda, ID as TAPE_PROGRAM_ID};
@@ -16,7 +18,7 @@ use tape_core::system::BlacklistEntry;
 use tape_core::track::data::{track_key, BlobData};
 use tape_core::track::types::CompressedTrackProof;
 use tape_core::types::coin::{Coin, TAPE};
-use tape_core::types::EpochNumber;
+use tape_core::types::{EpochNumber, TrackNumber};
 use tape_crypto::address::Address;
 use tape_crypto::Hash;
 
@@ -93,6 +95,9 @@ pub enum RawInstruction {
         pool: Address,
         stake: Address,
     },
+    SlashPool {
+        node: Address,
+    },
     ClaimCommission {
         authority: Address,
         node: Address,
@@ -125,6 +130,30 @@ pub enum RawInstruction {
         payer: Address,
         tape: Address,
     },
+    TransferTape {
+        authority: Address,
+        tape: Address,
+    },
+    SetTapeVersioning {
+        authority: Address,
+        tape: Address,
+        status: u64,
+    },
+    PlaceDeleteMarker {
+        authority: Address,
+        tape: Address,
+        name: Vec<u8>,
+    },
+    RemoveDeleteMarker {
+        authority: Address,
+        tape: Address,
+        name: Vec<u8>,
+        track_number: TrackNumber,
+        marker: u32,
+    },
+    RenewTape {
+        tape: Address,
+    },
     RegisterNode {
         authority: Address,
         node: Address,
@@ -277,6 +306,28 @@ pub enum ParsedInstruction {
         tape: Address,
         event: TapeExtended,
     },
+    TransferTape {
+        authority: Address,
+        tape: Address,
+        event: TapeTransferred,
+    },
+    SetTapeVersioning {
+        authority: Address,
+        tape: Address,
+        event: TapeVersioningSet,
+    },
+    PlaceDeleteMarker {
+        authority: Address,
+        tape: Address,
+        name: Vec<u8>,
+        event: DeleteMarkerPlaced,
+    },
+    RemoveDeleteMarker {
+        authority: Address,
+        tape: Address,
+        name: Vec<u8>,
+        event: DeleteMarkerRemoved,
+    },
 
     // Node management
     RegisterNode {
@@ -301,6 +352,11 @@ pub enum ParsedInstruction {
     NodeEvicted {
         event: NodeEvicted,
     },
+    // A node's pool cut, either by a landing eviction vote or by a group
+    // certifying an inconsistency.
+    StakeSlashed {
+        event: StakeSlashed,
+    },
     AddToBlacklist {
         node: Address,
         entry: BlacklistEntry,
@@ -478,6 +534,7 @@ pub fn parse_raw_instruction(
                 name: object.name.to_vec(),
                 content_type: object.content_type,
                 logical_size: object.logical_size,
+                headers: object.decode_headers(),
             });
             let value = blob.data.to_owned();
             value
@@ -531,6 +588,58 @@ pub fn parse_raw_instruction(
             Ok(Some(RawInstruction::ExtendTape { payer, tape }))
         }
 
+        TapeInstruction::RenewTape => {
+            // Account layout from build_renew_tape_ix: [fee_payer, tape, escrow, ...]
+            let tape = get_account(1)?;
+            Ok(Some(RawInstruction::RenewTape { tape }))
+        }
+
+        TapeInstruction::AcceptTapeTransfer => {
+            // Account layout from build_accept_tape_transfer_ix: [fee_payer, new_authority, tape, transfer]
+            let authority = get_account(1)?;
+            let tape = get_account(2)?;
+            Ok(Some(RawInstruction::TransferTape { authority, tape }))
+        }
+
+        TapeInstruction::SetTapeVersioning => {
+            // Account layout from build_set_tape_versioning_ix: [fee_payer, signer, tape]
+            let args = ix::SetTapeVersioning::try_from_bytes(&ix_data[1..])
+                .map_err(|e| ParseError::Deserialization(format!("set_tape_versioning: {e:?}")))?;
+            let authority = get_account(1)?;
+            let tape = get_account(2)?;
+            Ok(Some(RawInstruction::SetTapeVersioning {
+                authority,
+                tape,
+                status: args.status,
+            }))
+        }
+
+        TapeInstruction::PlaceDeleteMarker => {
+            let (_args, name) = ix::parse_place_delete_marker(&ix_data[1..])
+                .map_err(|e| ParseError::Deserialization(e.to_string()))?;
+            let authority = get_account(1)?;
+            let tape = get_account(2)?;
+            Ok(Some(RawInstruction::PlaceDeleteMarker {
+                authority,
+                tape,
+                name: name.to_vec(),
+            }))
+        }
+
+        TapeInstruction::RemoveDeleteMarker => {
+            let (args, name) = ix::parse_remove_delete_marker(&ix_data[1..])
+                .map_err(|e| ParseError::Deserialization(e.to_string()))?;
+            let authority = get_account(1)?;
+            let tape = get_account(2)?;
+            Ok(Some(RawInstruction::RemoveDeleteMarker {
+                authority,
+                tape,
+                name: name.to_vec(),
+                track_number: args.track_number(),
+                marker: args.marker(),
+            }))
+        }
+
         TapeInstruction::RegisterNode => {
             let authority = get_account(1)?;
             let node = get_account(5)?;
@@ -602,6 +711,12 @@ pub fn parse_raw_instruction(
             pool: get_account(8)?,
         })),
 
+        TapeInstruction::SlashPool => {
+            let args = ix::SlashPool::try_from_bytes(&ix_data[1..])
+                .map_err(|e| ParseError::Deserialization(format!("slash_pool: {e:?}")))?;
+            Ok(Some(RawInstruction::SlashPool { node: args.node }))
+        }
+
         TapeInstruction::ClaimCommission => Ok(Some(RawInstruction::ClaimCommission {
             authority: get_account(1)?,
             node: get_account(5)?,
@@ -627,8 +742,13 @@ pub fn parse_raw_instruction(
         | TapeInstruction::SetEpochDuration
         | TapeInstruction::SplitPoolStake
         | TapeInstruction::MergePoolStake
+        | TapeInstruction::UpgradeArchive
+        | TapeInstruction::UpgradeNode
         | TapeInstruction::SetTapeDelegate
-        | TapeInstruction::RevokeTapeDelegate => Ok(None),
+        | TapeInstruction::RevokeTapeDelegate
+        | TapeInstruction::ProposeTapeTransfer
+        | TapeInstruction::FundTapeEscrow
+        | TapeInstruction::WithdrawTapeEscrow => Ok(None),
     }
 }
 
@@ -641,7 +761,8 @@ mod tests {
     use solana_instruction::Instruction;
     use solana_transaction_status::UiCompiledInstruction;
     use tape_api::instruction::{
-        build_finalize_group_ix, build_vote_assignment_ix, build_vote_snapshot_ix,
+        build_accept_tape_transfer_ix, build_finalize_group_ix, build_propose_tape_transfer_ix,
+        build_remove_delete_marker_ix, build_vote_assignment_ix, build_vote_snapshot_ix,
     };
     use tape_api::program::tapedrive::ID as TAPE_PROGRAM_ID;
     use tape_core::bls::BlsSignature;
@@ -738,6 +859,67 @@ mod tests {
         }
     }
 
+    // Only the accepting half of a transfer surfaces, naming the new authority.
+    #[test]
+    fn parses_tape_transfer() {
+        let authority = Address::new_unique();
+        let new_authority = Address::new_unique();
+        let tape = Address::new_unique();
+
+        let (ix, keys) = compiled_instruction(&build_propose_tape_transfer_ix(
+            Address::new_unique(),
+            authority,
+            tape,
+            new_authority,
+        ));
+        assert!(parse_raw_instruction(&ix, &keys).unwrap().is_none());
+
+        let (ix, keys) = compiled_instruction(&build_accept_tape_transfer_ix(
+            Address::new_unique(),
+            new_authority,
+            tape,
+        ));
+        match parse_raw_instruction(&ix, &keys).unwrap() {
+            Some(RawInstruction::TransferTape { authority: parsed, tape: parsed_tape }) => {
+                assert_eq!(parsed, new_authority);
+                assert_eq!(parsed_tape, tape);
+            }
+            other => panic!("expected RawInstruction::TransferTape, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_remove_delete_marker() {
+        let authority = Address::new_unique();
+        let tape = Address::new_unique();
+
+        let (ix, keys) = compiled_instruction(
+            &build_remove_delete_marker_ix(
+                Address::new_unique(),
+                authority,
+                tape,
+                b"photos/a.jpg",
+                TrackNumber(12),
+                3,
+            )
+            .unwrap(),
+        );
+        match parse_raw_instruction(&ix, &keys).unwrap() {
+            Some(RawInstruction::RemoveDeleteMarker {
+                authority: parsed,
+                tape: parsed_tape,
+                name,
+                track_number,
+                marker,
+            }) => {
+                assert_eq!((parsed, parsed_tape), (authority, tape));
+                assert_eq!(name, b"photos/a.jpg");
+                assert_eq!((track_number, marker), (TrackNumber(12), 3));
+            }
+            other => panic!("expected RawInstruction::RemoveDeleteMarker, got {other:?}"),
+        }
+    }
+
     #[test]
     fn parses_snapshot_events() {
         let voted = VoteRecorded {
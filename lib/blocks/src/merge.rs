@@ -6,6 +6,7 @@ use crate::instruction::{ParsedInstruction, RawInstruction};
 use std::collections::VecDeque;
 use tape_core::cert::eviction_vote_hash;
 use tape_core::system::VoteKind;
+use tape_crypto::hash::hash;
 
 /// Merge raw instructions with their corresponding events.
 ///
@@ -180,14 +181,18 @@ pub fn merge(
                 ParsedInstruction::ProposeEviction { node, proposer, event }
             }
 
-            // A vote reaching supermajority logs the removal before the vote
-            // record. Emit the removal so the committee drop replays, then drain
-            // the vote record.
+            // A vote reaching supermajority logs the removal, and the slash of
+            // the node's pool when there was stake to cut, before the vote
+            // record. Emit both so they replay, then drain the vote record.
             RawInstruction::VoteEviction => {
                 if let Some(TapedriveEvent::NodeEvicted(evicted)) = events.front() {
                     result.push(ParsedInstruction::NodeEvicted { event: *evicted });
                     events.pop_front();
                 }
+                if let Some(TapedriveEvent::StakeSlashed(slashed)) = events.front() {
+                    result.push(ParsedInstruction::StakeSlashed { event: *slashed });
+                    events.pop_front();
+                }
                 let event = match events.pop_front() {
                     Some(TapedriveEvent::VoteRecorded(e)) => e,
                     _ => return Err(ParseError::EventMismatch("expected VoteRecorded event")),
@@ -312,6 +317,109 @@ pub fn merge(
                 }
             }
 
+            // A renewal the escrow could not cover leaves the tape as it was
+            // and logs nothing this parser keeps.
+            RawInstruction::RenewTape { tape } => {
+                let event = match events.front() {
+                    Some(TapedriveEvent::TapeExtended(e)) if e.tape == tape => *e,
+                    _ => continue,
+                };
+                events.pop_front();
+                ParsedInstruction::ExtendTape {
+                    payer: event.payer,
+                    tape,
+                    event,
+                }
+            }
+
+            RawInstruction::TransferTape { authority, tape } => {
+                let event = match events.pop_front() {
+                    Some(TapedriveEvent::TapeTransferred(e)) => e,
+                    _ => {
+                        return Err(ParseError::EventMismatch(
+                            "expected TapeTransferred event",
+                        ))
+                    }
+                };
+                if event.tape != tape || event.authority != authority {
+                    return Err(ParseError::EventMismatch("unexpected TapeTransferred event"));
+                }
+                ParsedInstruction::TransferTape {
+                    authority,
+                    tape,
+                    event,
+                }
+            }
+
+            RawInstruction::SetTapeVersioning { authority, tape, status } => {
+                let event = match events.pop_front() {
+                    Some(TapedriveEvent::TapeVersioningSet(e)) => e,
+                    _ => {
+                        return Err(ParseError::EventMismatch(
+                            "expected TapeVersioningSet event",
+                        ))
+                    }
+                };
+                if event.tape != tape || event.status != status {
+                    return Err(ParseError::EventMismatch("unexpected TapeVersioningSet event"));
+                }
+                ParsedInstruction::SetTapeVersioning {
+                    authority,
+                    tape,
+                    event,
+                }
+            }
+
+            RawInstruction::PlaceDeleteMarker { authority, tape, name } => {
+                let event = match events.pop_front() {
+                    Some(TapedriveEvent::DeleteMarkerPlaced(e)) => e,
+                    _ => {
+                        return Err(ParseError::EventMismatch(
+                            "expected DeleteMarkerPlaced event",
+                        ))
+                    }
+                };
+                if event.tape != tape || event.key != hash(&name) {
+                    return Err(ParseError::EventMismatch("unexpected DeleteMarkerPlaced event"));
+                }
+                ParsedInstruction::PlaceDeleteMarker {
+                    authority,
+                    tape,
+                    name,
+                    event,
+                }
+            }
+
+            RawInstruction::RemoveDeleteMarker {
+                authority,
+                tape,
+                name,
+                track_number,
+                marker,
+            } => {
+                let event = match events.pop_front() {
+                    Some(TapedriveEvent::DeleteMarkerRemoved(e)) => e,
+                    _ => {
+                        return Err(ParseError::EventMismatch(
+                            "expected DeleteMarkerRemoved event",
+                        ))
+                    }
+                };
+                if event.tape != tape
+                    || event.key != hash(&name)
+                    || event.track_number != track_number
+                    || event.marker != marker as u64
+                {
+                    return Err(ParseError::EventMismatch("unexpected DeleteMarkerRemoved event"));
+                }
+                ParsedInstruction::RemoveDeleteMarker {
+                    authority,
+                    tape,
+                    name,
+                    event,
+                }
+            }
+
             RawInstruction::RegisterNode { authority, node } => {
                 let event = match events.pop_front() {
                     Some(TapedriveEvent::NodeRegistered(e)) => e,
@@ -454,6 +562,17 @@ pub fn merge(
                 }
             }
 
+            RawInstruction::SlashPool { node } => {
+                let event = match events.pop_front() {
+                    Some(TapedriveEvent::StakeSlashed(e)) => e,
+                    _ => return Err(ParseError::EventMismatch("expected StakeSlashed event")),
+                };
+                if event.pool != node {
+                    return Err(ParseError::EventMismatch("unexpected StakeSlashed event"));
+                }
+                ParsedInstruction::StakeSlashed { event }
+            }
+
             RawInstruction::ClaimCommission { authority, node } => {
                 let event = match events.pop_front() {
                     Some(TapedriveEvent::CommissionClaimed(e)) => e,
@@ -485,10 +604,10 @@ mod tests {
     use super::*;
     use bytemuck::Zeroable;
     use tape_api::event::{
-        AssignmentFinalized, EpochAdvanced, EpochCommitted, NodeEvicted, NodeJoinedCommittee,
-        NodeRegistered, PoolAdvanced, SnapshotFinalized, SpoolSynced, TapeDestroyed,
-        TapeExtended, TapeReserved, TrackCertified, TrackDeleted, TrackInvalidated,
-        TrackWritten, VoteProposed, VoteRecorded,
+        AssignmentFinalized, DeleteMarkerPlaced, EpochAdvanced, EpochCommitted, NodeEvicted,
+        NodeJoinedCommittee, NodeRegistered, PoolAdvanced, SnapshotFinalized, SpoolSynced,
+        StakeSlashed, TapeDestroyed, TapeExtended, TapeReserved, TapeTransferred, TrackCertified,
+        TrackDeleted, TrackInvalidated, TrackWritten, VoteProposed, VoteRecorded,
     };
     use tape_core::bls::BlsPubkey;
     use tape_core::erasure::GROUP_SIZE;
@@ -700,6 +819,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn merge_eviction_landing_emits_stake_slashed() {
+        let node = Address::new_unique();
+        let evicted = NodeEvicted {
+            node,
+            target_epoch: EpochNumber(9),
+        };
+        let slashed = StakeSlashed {
+            pool: node,
+            epoch: EpochNumber(8),
+            amount: TAPE(50),
+            fronted: TAPE(50),
+            ..StakeSlashed::zeroed()
+        };
+
+        let merged = merge(
+            vec![RawInstruction::VoteEviction],
+            vec![
+                TapedriveEvent::NodeEvicted(evicted),
+                TapedriveEvent::StakeSlashed(slashed),
+                TapedriveEvent::VoteRecorded(eviction_vote_recorded(node, 1, 1)),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(merged.len(), 2);
+        assert!(matches!(merged[0], ParsedInstruction::NodeEvicted { .. }));
+        match &merged[1] {
+            ParsedInstruction::StakeSlashed { event } => {
+                assert_eq!(event.pool, node);
+                assert_eq!(event.amount, TAPE(50));
+            }
+            other => panic!("expected StakeSlashed, got {other:?}"),
+        }
+    }
+
     #[test]
     fn merge_eviction_non_landing_vote_produces_nothing() {
         let node = Address::new_unique();
@@ -1150,6 +1305,30 @@ mod tests {
         }
     }
 
+    // A renewal surfaces as an extend paid by the escrow; one the escrow
+    // could not cover produces nothing.
+    #[test]
+    fn merge_renew_tape() {
+        let escrow = Address::new_unique();
+        let tape = Address::new_unique();
+        let event = tape_extended_event(escrow, tape);
+
+        let merged = merge(
+            vec![RawInstruction::RenewTape { tape }, RawInstruction::RenewTape { tape }],
+            vec![TapedriveEvent::TapeExtended(event)],
+        )
+        .expect("merge succeeds");
+
+        assert_eq!(merged.len(), 1);
+        match &merged[0] {
+            ParsedInstruction::ExtendTape { payer, tape: t, .. } => {
+                assert_eq!(*payer, escrow);
+                assert_eq!(*t, tape);
+            }
+            _ => panic!("Expected ExtendTape"),
+        }
+    }
+
     // Pairing an extend with a different event type fails.
     #[test]
     fn merge_extend_wrong_event() {
@@ -1181,6 +1360,91 @@ mod tests {
         assert!(matches!(result, Err(ParseError::EventMismatch(_))));
     }
 
+    // Pairing an accepted transfer with its event keeps the new authority.
+    #[test]
+    fn merge_transfer_tape() {
+        let previous = Address::new_unique();
+        let authority = Address::new_unique();
+        let tape = Address::new_unique();
+        let event = TapeTransferred {
+            tape,
+            previous_authority: previous,
+            authority,
+        };
+
+        let merged = merge(
+            vec![RawInstruction::TransferTape { authority, tape }],
+            vec![TapedriveEvent::TapeTransferred(event)],
+        )
+        .expect("merge succeeds");
+
+        assert_eq!(merged.len(), 1);
+        match &merged[0] {
+            ParsedInstruction::TransferTape { authority: a, tape: t, event } => {
+                assert_eq!(*a, authority);
+                assert_eq!(*t, tape);
+                assert_eq!(event.previous_authority, previous);
+            }
+            _ => panic!("Expected TransferTape"),
+        }
+    }
+
+    // Pairing a transfer with an event naming another tape fails.
+    #[test]
+    fn merge_transfer_tape_mismatch() {
+        let authority = Address::new_unique();
+        let event = TapeTransferred {
+            tape: Address::new_unique(),
+            previous_authority: Address::new_unique(),
+            authority,
+        };
+
+        let result = merge(
+            vec![RawInstruction::TransferTape {
+                authority,
+                tape: Address::new_unique(),
+            }],
+            vec![TapedriveEvent::TapeTransferred(event)],
+        );
+
+        assert!(matches!(result, Err(ParseError::EventMismatch(_))));
+    }
+
+    // A delete marker pairs with the event only when the name hashes to its key.
+    #[test]
+    fn merge_place_delete_marker() {
+        let authority = Address::new_unique();
+        let tape = Address::new_unique();
+        let event = DeleteMarkerPlaced {
+            tape,
+            key: hash(b"photos/a.jpg"),
+        };
+
+        let merged = merge(
+            vec![RawInstruction::PlaceDeleteMarker {
+                authority,
+                tape,
+                name: b"photos/a.jpg".to_vec(),
+            }],
+            vec![TapedriveEvent::DeleteMarkerPlaced(event)],
+        )
+        .expect("merge succeeds");
+        assert!(matches!(
+            &merged[0],
+            ParsedInstruction::PlaceDeleteMarker { name, .. } if name == b"photos/a.jpg"
+        ));
+
+        let result = merge(
+            vec![RawInstruction::PlaceDeleteMarker {
+                authority,
+                tape,
+                name: b"photos/b.jpg".to_vec(),
+            }],
+            vec![TapedriveEvent::DeleteMarkerPlaced(event)],
+        );
+        assert!(matches!(result, Err(ParseError::EventMismatch(_))));
+    }
+
     #[test]
     fn merge_sync_spool_with_event() {
         let node = Address::new_unique();
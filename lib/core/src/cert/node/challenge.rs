@@ -0,0 +1,94 @@
+use bytemuck::{Pod, Zeroable, bytes_of, try_from_bytes};
+use tape_crypto::{Address, Hash};
+
+use crate::types::EpochNumber;
+
+/// Domain separation tag for failed storage challenges.
+pub const CHALLENGE_DOMAIN_TAG: &[u8; 8] = b"CHALFAIL";
+
+/// Size of the challenge failure message in bytes.
+/// 8 (domain) + 8 (epoch) + 32 (nonce) + 32 (node) = 80 bytes
+pub const CHALLENGE_MESSAGE_SIZE: usize = 80;
+
+/// Message format for challenge failure BLS signatures.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+pub struct ChallengeFailMessage {
+    /// Epoch the challenges were drawn in.
+    pub epoch: EpochNumber,
+    /// Epoch nonce the challenges were seeded from.
+    pub nonce: Hash,
+    /// Node that failed to answer.
+    pub node: Address,
+}
+
+/// Encode the challenged node's address as the challenge vote hash.
+pub fn challenge_vote_hash(node: Address) -> Hash {
+    Hash(node.to_bytes())
+}
+
+/// Recover the challenged node's address from a challenge vote hash.
+pub fn challenge_vote_node(hash: Hash) -> Address {
+    Address::new(hash.0)
+}
+
+impl ChallengeFailMessage {
+    pub const fn new(epoch: EpochNumber, nonce: Hash, node: Address) -> Self {
+        Self { epoch, nonce, node }
+    }
+
+    pub fn to_bytes(&self) -> [u8; CHALLENGE_MESSAGE_SIZE] {
+        let mut buf = [0u8; CHALLENGE_MESSAGE_SIZE];
+        buf[0..8].copy_from_slice(CHALLENGE_DOMAIN_TAG);
+        buf[8..].copy_from_slice(bytes_of(self));
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != CHALLENGE_MESSAGE_SIZE {
+            return None;
+        }
+
+        if &bytes[0..8] != CHALLENGE_DOMAIN_TAG {
+            return None;
+        }
+
+        try_from_bytes::<Self>(&bytes[8..]).copied().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cert::NodeEvictMessage;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let epoch = EpochNumber(12345);
+        let nonce = Hash([0xAB; 32]);
+        let node = Address::new([0xCD; 32]);
+
+        let msg = ChallengeFailMessage::new(epoch, nonce, node);
+        let bytes = msg.to_bytes();
+
+        assert_eq!(bytes.len(), CHALLENGE_MESSAGE_SIZE);
+        assert_eq!(&bytes[0..8], b"CHALFAIL");
+
+        let recovered = ChallengeFailMessage::from_bytes(&bytes).expect("should parse");
+        assert_eq!(recovered, msg);
+    }
+
+    #[test]
+    fn test_evict_message_rejected() {
+        let bytes = NodeEvictMessage::new(EpochNumber(1), Hash([0x11; 32]), Address::new([0x22; 32]))
+            .to_bytes();
+
+        assert!(ChallengeFailMessage::from_bytes(&bytes).is_none());
+    }
+
+    #[test]
+    fn test_vote_hash_roundtrip() {
+        let node = Address::new([0x99; 32]);
+        assert_eq!(challenge_vote_node(challenge_vote_hash(node)), node);
+    }
+}
@@ -1,3 +1,5 @@
+mod challenge;
 mod evict;
 
+pub use challenge::*;
 pub use evict::*;
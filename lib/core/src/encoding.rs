@@ -25,6 +25,29 @@ pub enum EncodingType {
     Clay = 2,
 }
 
+/// How a blob's slices are bound to its commitment leaves.
+///
+/// Carried in the top byte of [`EncodingProfile::params`], which the coder
+/// parameters leave unused, so blobs certified before segment leaves existed
+/// read back as [`LeafScheme::Whole`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum LeafScheme {
+
+    /// Each leaf hashes the whole slice.
+    #[default]
+    Whole = 0,
+
+    /// Each leaf is the root over the slice's stripe segments.
+    Segmented = 1,
+}
+
+/// Bit offset of the leaf scheme in the profile params.
+const LEAF_SCHEME_SHIFT: u32 = 56;
+
+/// Params bits that belong to the coder.
+const CODER_PARAMS_MASK: u64 = (1 << LEAF_SCHEME_SHIFT) - 1;
+
 /// Encoding configuration: type + params.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
@@ -75,7 +98,7 @@ impl EncodingProfile {
     /// Get the Clay parameters (only valid if is_clay()).
     #[inline]
     pub const fn clay_params(&self) -> ClayParams {
-        ClayParams::from_u64(self.params)
+        ClayParams::from_u64(self.params & CODER_PARAMS_MASK)
     }
 
     /// Create a Basic (RS) encoding profile with given parameters.
@@ -95,7 +118,22 @@ impl EncodingProfile {
     /// Get the RS parameters (only valid if is_basic()).
     #[inline]
     pub const fn rs_params(&self) -> RSParams {
-        RSParams::from_u64(self.params)
+        RSParams::from_u64(self.params & CODER_PARAMS_MASK)
+    }
+
+    /// Get the leaf scheme, or None if this build does not know it.
+    #[inline]
+    pub fn leaf_scheme(&self) -> Option<LeafScheme> {
+        LeafScheme::try_from((self.params >> LEAF_SCHEME_SHIFT) as u8).ok()
+    }
+
+    /// The same profile with its slices committed under `scheme`.
+    #[inline]
+    pub const fn with_leaf_scheme(self, scheme: LeafScheme) -> Self {
+        Self {
+            encoding: self.encoding,
+            params: (self.params & CODER_PARAMS_MASK) | ((scheme as u64) << LEAF_SCHEME_SHIFT),
+        }
     }
 
     /// Get k (data slices) for any encoding type.
@@ -429,6 +467,31 @@ mod tests {
         let _ = profile.k();
     }
 
+    #[test]
+    fn test_leaf_scheme_default_whole() {
+        assert_eq!(EncodingProfile::clay_default().leaf_scheme(), Some(LeafScheme::Whole));
+        assert_eq!(EncodingProfile::zeroed().leaf_scheme(), Some(LeafScheme::Whole));
+    }
+
+    #[test]
+    fn test_leaf_scheme_keeps_params() {
+        let clay = EncodingProfile::clay_default().with_leaf_scheme(LeafScheme::Segmented);
+        assert_eq!(clay.leaf_scheme(), Some(LeafScheme::Segmented));
+        assert_eq!(clay.clay_params(), ClayParams::default());
+        assert_eq!(clay.k(), 7);
+
+        let plain = EncodingProfile::basic(RSParams::new(8, 4));
+        let basic = plain.with_leaf_scheme(LeafScheme::Segmented);
+        assert_eq!(basic.rs_params(), RSParams::new(8, 4));
+        assert_eq!(basic.with_leaf_scheme(LeafScheme::Whole), plain);
+    }
+
+    #[test]
+    fn test_leaf_scheme_unknown() {
+        let profile = EncodingProfile { params: 0xff << 56, ..EncodingProfile::clay_default() };
+        assert_eq!(profile.leaf_scheme(), None);
+    }
+
     #[test]
     fn test_encoding_profile_unknown() {
         let profile = EncodingProfile::unknown();
@@ -0,0 +1,563 @@
+//! Snapshot records as logged by v1 snapshots.
+//!
+//! v1 predates object headers on [`ReplayTrackObject`] and every event after
+//! [`ReplayableEvent::VoteRecorded`]. Its records decode into the mirror types
+//! below and map onto the current ones, so published v1 snapshots still
+//! bootstrap a node.
+
+use serde::{Deserialize, Serialize};
+use wincode_derive::{SchemaRead, SchemaWrite};
+
+use crate::bls::BlsPubkey;
+use crate::snapshot::error::SnapshotError;
+use crate::snapshot::replay::{
+    ReplayRecord, ReplayTrack, ReplayTrackObject, ReplayableEvent, SnapshotEntry,
+};
+use crate::spooler::GroupIndex;
+use crate::system::NodePreferences;
+use crate::track::blob::BlobEncoding;
+use crate::track::types::CompressedTrack;
+use crate::types::coin::{Coin, TAPE};
+use crate::types::{
+    ContentType, EpochNumber, NodeId, ObjectHeaders, SlotNumber, SpoolIndex, StorageUnits,
+    TapeNumber,
+};
+use tape_crypto::address::Address;
+use tape_crypto::hash::Hash;
+use tape_crypto::tx::Txid;
+
+/// Wire-format version of snapshots logged before object headers
+pub const LEGACY_SNAPSHOT_VERSION: u8 = 1;
+
+/// Snapshot entry layout of v1 snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+struct LegacySnapshotEntry {
+    slot: SlotNumber,
+    block_time: Option<i64>,
+    records: Vec<LegacyReplayRecord>,
+}
+
+/// Record layout of v1 snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+struct LegacyReplayRecord {
+    tx_id: Txid,
+    actor: Option<Address>,
+    event: LegacyReplayableEvent,
+}
+
+/// Event layout of v1 snapshots, before tape transfers, slashing, versioning
+/// and delete markers were logged
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+enum LegacyReplayableEvent {
+    Track(LegacyReplayTrack),
+    CertifyTrack {
+        track: Address,
+        epoch: EpochNumber,
+    },
+    DeleteTrack {
+        track: Address,
+        epoch: EpochNumber,
+    },
+    InvalidateTrack {
+        track: Address,
+        epoch: EpochNumber,
+    },
+    AdvanceEpoch {
+        old_epoch: EpochNumber,
+        new_epoch: EpochNumber,
+        timestamp: i64,
+        total_stake: Coin<TAPE>,
+        committee_count: u64,
+        preferences: NodePreferences,
+        subsidy: Coin<TAPE>,
+        nonce: Hash,
+    },
+    SyncSpool {
+        node: Address,
+        epoch: EpochNumber,
+        group: GroupIndex,
+        spool: SpoolIndex,
+    },
+    ReserveTape {
+        tape: Address,
+        id: TapeNumber,
+        flags: u64,
+        authority: Address,
+        capacity: StorageUnits,
+        active_epoch: EpochNumber,
+        expiry_epoch: EpochNumber,
+        cost: Coin<TAPE>,
+        burned: Coin<TAPE>,
+        scheduled: Coin<TAPE>,
+    },
+    DestroyTape {
+        tape: Address,
+        epoch: EpochNumber,
+    },
+    ExtendTape {
+        tape: Address,
+        capacity: StorageUnits,
+        expiry_epoch: EpochNumber,
+        cost: Coin<TAPE>,
+        burned: Coin<TAPE>,
+        scheduled: Coin<TAPE>,
+    },
+    RegisterNode {
+        authority: Address,
+        node: Address,
+        id: NodeId,
+    },
+    JoinCommittee {
+        node: Address,
+        stake: Coin<TAPE>,
+        key: BlsPubkey,
+        preferences: NodePreferences,
+        activation_epoch: EpochNumber,
+    },
+    NodeEvicted {
+        node: Address,
+        target_epoch: EpochNumber,
+    },
+    SnapshotFinalized {
+        epoch: EpochNumber,
+        hash: Hash,
+        snapshot_tape: Address,
+    },
+    AssignmentFinalized {
+        epoch: EpochNumber,
+        hash: Hash,
+        group: GroupIndex,
+        group_account: Address,
+        size: StorageUnits,
+        total_groups: u64,
+        total_assigned: StorageUnits,
+    },
+    StakeDeposited {
+        stake: Address,
+        authority: Address,
+        pool: Address,
+        amount: Coin<TAPE>,
+        activation_epoch: EpochNumber,
+    },
+    StakeUnlockRequested {
+        stake: Address,
+        authority: Address,
+        pool: Address,
+        amount: Coin<TAPE>,
+        withdraw_epoch: EpochNumber,
+    },
+    StakeWithdrawn {
+        stake: Address,
+        authority: Address,
+        pool: Address,
+        principal: Coin<TAPE>,
+        rewards: Coin<TAPE>,
+    },
+    VoteProposed {
+        kind: u64,
+        vote: Address,
+        voting_epoch: EpochNumber,
+        target_epoch: EpochNumber,
+        hash: Hash,
+        total_groups: u64,
+    },
+    VoteRecorded {
+        kind: u64,
+        vote: Address,
+        voting_epoch: EpochNumber,
+        target_epoch: EpochNumber,
+        hash: Hash,
+        group: GroupIndex,
+        signer_count: u64,
+        signed_groups: u64,
+        total_groups: u64,
+        signers: [u8; 8],
+    },
+}
+
+/// Track layout of v1 snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+struct LegacyReplayTrack {
+    state: CompressedTrack,
+    epoch: EpochNumber,
+    blob: Option<BlobEncoding>,
+    object: Option<LegacyReplayTrackObject>,
+}
+
+/// Object layout of v1 snapshots, before writes carried headers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+struct LegacyReplayTrackObject {
+    name: Vec<u8>,
+    content_type: ContentType,
+    logical_size: StorageUnits,
+}
+
+/// Decode one v1 entry frame into the current entry layout
+///
+/// Objects written before headers existed replay with empty headers.
+pub fn decode_legacy_entry(data: &[u8]) -> Result<SnapshotEntry, SnapshotError> {
+    let legacy: LegacySnapshotEntry = wincode::deserialize(data)?;
+    Ok(SnapshotEntry {
+        slot: legacy.slot,
+        block_time: legacy.block_time,
+        records: legacy.records.into_iter().map(ReplayRecord::from).collect(),
+    })
+}
+
+impl From<LegacyReplayRecord> for ReplayRecord {
+    fn from(legacy: LegacyReplayRecord) -> Self {
+        Self {
+            tx_id: legacy.tx_id,
+            actor: legacy.actor,
+            event: legacy.event.into(),
+        }
+    }
+}
+
+impl From<LegacyReplayTrack> for ReplayTrack {
+    fn from(legacy: LegacyReplayTrack) -> Self {
+        Self {
+            state: legacy.state,
+            epoch: legacy.epoch,
+            blob: legacy.blob,
+            object: legacy.object.map(|object| ReplayTrackObject {
+                name: object.name,
+                content_type: object.content_type,
+                logical_size: object.logical_size,
+                headers: ObjectHeaders::default(),
+            }),
+        }
+    }
+}
+
+impl From<LegacyReplayableEvent> for ReplayableEvent {
+    fn from(legacy: LegacyReplayableEvent) -> Self {
+        match legacy {
+            LegacyReplayableEvent::Track(track) => ReplayableEvent::Track(track.into()),
+            LegacyReplayableEvent::CertifyTrack {
+                track,
+                epoch,
+            } => ReplayableEvent::CertifyTrack {
+                track,
+                epoch,
+            },
+            LegacyReplayableEvent::DeleteTrack {
+                track,
+                epoch,
+            } => ReplayableEvent::DeleteTrack {
+                track,
+                epoch,
+            },
+            LegacyReplayableEvent::InvalidateTrack {
+                track,
+                epoch,
+            } => ReplayableEvent::InvalidateTrack {
+                track,
+                epoch,
+            },
+            LegacyReplayableEvent::AdvanceEpoch {
+                old_epoch,
+                new_epoch,
+                timestamp,
+                total_stake,
+                committee_count,
+                preferences,
+                subsidy,
+                nonce,
+            } => ReplayableEvent::AdvanceEpoch {
+                old_epoch,
+                new_epoch,
+                timestamp,
+                total_stake,
+                committee_count,
+                preferences,
+                subsidy,
+                nonce,
+            },
+            LegacyReplayableEvent::SyncSpool {
+                node,
+                epoch,
+                group,
+                spool,
+            } => ReplayableEvent::SyncSpool {
+                node,
+                epoch,
+                group,
+                spool,
+            },
+            LegacyReplayableEvent::ReserveTape {
+                tape,
+                id,
+                flags,
+                authority,
+                capacity,
+                active_epoch,
+                expiry_epoch,
+                cost,
+                burned,
+                scheduled,
+            } => ReplayableEvent::ReserveTape {
+                tape,
+                id,
+                flags,
+                authority,
+                capacity,
+                active_epoch,
+                expiry_epoch,
+                cost,
+                burned,
+                scheduled,
+            },
+            LegacyReplayableEvent::DestroyTape {
+                tape,
+                epoch,
+            } => ReplayableEvent::DestroyTape {
+                tape,
+                epoch,
+            },
+            LegacyReplayableEvent::ExtendTape {
+                tape,
+                capacity,
+                expiry_epoch,
+                cost,
+                burned,
+                scheduled,
+            } => ReplayableEvent::ExtendTape {
+                tape,
+                capacity,
+                expiry_epoch,
+                cost,
+                burned,
+                scheduled,
+            },
+            LegacyReplayableEvent::RegisterNode {
+                authority,
+                node,
+                id,
+            } => ReplayableEvent::RegisterNode {
+                authority,
+                node,
+                id,
+            },
+            LegacyReplayableEvent::JoinCommittee {
+                node,
+                stake,
+                key,
+                preferences,
+                activation_epoch,
+            } => ReplayableEvent::JoinCommittee {
+                node,
+                stake,
+                key,
+                preferences,
+                activation_epoch,
+            },
+            LegacyReplayableEvent::NodeEvicted {
+                node,
+                target_epoch,
+            } => ReplayableEvent::NodeEvicted {
+                node,
+                target_epoch,
+            },
+            LegacyReplayableEvent::SnapshotFinalized {
+                epoch,
+                hash,
+                snapshot_tape,
+            } => ReplayableEvent::SnapshotFinalized {
+                epoch,
+                hash,
+                snapshot_tape,
+            },
+            LegacyReplayableEvent::AssignmentFinalized {
+                epoch,
+                hash,
+                group,
+                group_account,
+                size,
+                total_groups,
+                total_assigned,
+            } => ReplayableEvent::AssignmentFinalized {
+                epoch,
+                hash,
+                group,
+                group_account,
+                size,
+                total_groups,
+                total_assigned,
+            },
+            LegacyReplayableEvent::StakeDeposited {
+                stake,
+                authority,
+                pool,
+                amount,
+                activation_epoch,
+            } => ReplayableEvent::StakeDeposited {
+                stake,
+                authority,
+                pool,
+                amount,
+                activation_epoch,
+            },
+            LegacyReplayableEvent::StakeUnlockRequested {
+                stake,
+                authority,
+                pool,
+                amount,
+                withdraw_epoch,
+            } => ReplayableEvent::StakeUnlockRequested {
+                stake,
+                authority,
+                pool,
+                amount,
+                withdraw_epoch,
+            },
+            LegacyReplayableEvent::StakeWithdrawn {
+                stake,
+                authority,
+                pool,
+                principal,
+                rewards,
+            } => ReplayableEvent::StakeWithdrawn {
+                stake,
+                authority,
+                pool,
+                principal,
+                rewards,
+            },
+            LegacyReplayableEvent::VoteProposed {
+                kind,
+                vote,
+                voting_epoch,
+                target_epoch,
+                hash,
+                total_groups,
+            } => ReplayableEvent::VoteProposed {
+                kind,
+                vote,
+                voting_epoch,
+                target_epoch,
+                hash,
+                total_groups,
+            },
+            LegacyReplayableEvent::VoteRecorded {
+                kind,
+                vote,
+                voting_epoch,
+                target_epoch,
+                hash,
+                group,
+                signer_count,
+                signed_groups,
+                total_groups,
+                signers,
+            } => ReplayableEvent::VoteRecorded {
+                kind,
+                vote,
+                voting_epoch,
+                target_epoch,
+                hash,
+                group,
+                signer_count,
+                signed_groups,
+                total_groups,
+                signers,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::replay::{SnapshotEntryFrame, SnapshotHeader, SnapshotLog};
+    use crate::track::types::{TrackKind, TrackState};
+
+    fn legacy_track() -> LegacyReplayTrack {
+        LegacyReplayTrack {
+            state: CompressedTrack {
+                tape: Address::from([1u8; 32]),
+                key: Hash::from([2u8; 32]),
+                track_number: 3u64.into(),
+                kind: TrackKind::Inline as u64,
+                state: TrackState::Certified as u64,
+                size: 16u64.into(),
+                group: GroupIndex::from(0),
+                value_hash: Hash::from([4u8; 32]),
+            },
+            epoch: EpochNumber(9),
+            blob: None,
+            object: Some(LegacyReplayTrackObject {
+                name: b"photos/cat.jpg".to_vec(),
+                content_type: ContentType::ImageJpeg,
+                logical_size: StorageUnits::from_bytes(16),
+            }),
+        }
+    }
+
+    fn v1_bytes(version: u8, entries: &[LegacySnapshotEntry]) -> Vec<u8> {
+        let header = SnapshotHeader {
+            version,
+            epoch: EpochNumber(9),
+            start_slot: SlotNumber(100),
+            end_slot: SlotNumber(200),
+            entry_count: entries.len() as u64,
+        };
+        let mut bytes = wincode::serialize(&header).unwrap();
+        for entry in entries {
+            let data = wincode::serialize(entry).unwrap();
+            let frame = SnapshotEntryFrame { len: data.len() as u64, data };
+            bytes.extend(wincode::serialize(&frame).unwrap());
+        }
+        bytes
+    }
+
+    // v1 snapshots decode, with objects replaying under empty headers
+    #[test]
+    fn decodes_v1_snapshot() {
+        let entry = LegacySnapshotEntry {
+            slot: SlotNumber(150),
+            block_time: Some(1_700_000_050),
+            records: vec![
+                LegacyReplayRecord {
+                    tx_id: Txid::default(),
+                    actor: Some(Address::from([5u8; 32])),
+                    event: LegacyReplayableEvent::Track(legacy_track()),
+                },
+                LegacyReplayRecord {
+                    tx_id: Txid::default(),
+                    actor: None,
+                    event: LegacyReplayableEvent::CertifyTrack {
+                        track: Address::from([6u8; 32]),
+                        epoch: EpochNumber(9),
+                    },
+                },
+            ],
+        };
+
+        let log = SnapshotLog::from_bytes(&v1_bytes(LEGACY_SNAPSHOT_VERSION, &[entry]))
+            .expect("v1 snapshot");
+        assert_eq!(log.epoch, EpochNumber(9));
+        let records = &log.entries[0].records;
+        assert_eq!(records.len(), 2);
+
+        let ReplayableEvent::Track(track) = &records[0].event else {
+            panic!("track record");
+        };
+        let object = track.object.as_ref().expect("object");
+        assert_eq!(object.name, b"photos/cat.jpg");
+        assert_eq!(object.headers, ObjectHeaders::default());
+        assert_eq!(
+            records[1].event,
+            ReplayableEvent::CertifyTrack {
+                track: Address::from([6u8; 32]),
+                epoch: EpochNumber(9),
+            }
+        );
+    }
+
+    #[test]
+    fn refuses_unknown_version() {
+        let result = SnapshotLog::from_bytes(&v1_bytes(9, &[]));
+        assert!(matches!(result, Err(SnapshotError::UnsupportedVersion(9))));
+    }
+}
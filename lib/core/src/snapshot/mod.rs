@@ -1,2 +1,4 @@
 pub mod error;
+#[cfg(feature = "wincode")]
+pub mod legacy;
 pub mod replay;
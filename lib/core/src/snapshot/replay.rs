@@ -10,20 +10,23 @@ use wincode_derive::{SchemaRead, SchemaWrite};
 use crate::bls::BlsPubkey;
 #[cfg(feature = "wincode")]
 use crate::snapshot::error::SnapshotError;
+#[cfg(feature = "wincode")]
+use crate::snapshot::legacy::{decode_legacy_entry, LEGACY_SNAPSHOT_VERSION};
 use crate::spooler::GroupIndex;
 use crate::system::NodePreferences;
 use crate::track::blob::BlobEncoding;
 use crate::track::types::CompressedTrack;
 use crate::types::coin::{Coin, TAPE};
 use crate::types::{
-    ContentType, EpochNumber, NodeId, SlotNumber, SpoolIndex, StorageUnits, TapeNumber,
+    ContentType, EpochNumber, NodeId, ObjectHeaders, SlotNumber, SpoolIndex, StorageUnits,
+    TapeNumber, TrackNumber,
 };
 use tape_crypto::address::Address;
 use tape_crypto::hash::Hash;
 use tape_crypto::tx::Txid;
 
 /// Wire-format version for the framed snapshot binary.
-pub const SNAPSHOT_VERSION: u8 = 1;
+pub const SNAPSHOT_VERSION: u8 = 2;
 
 #[cfg(feature = "wincode")]
 const SNAPSHOT_FRAME_LIMIT: usize = 4 * 1024 * 1024;
@@ -199,6 +202,40 @@ pub enum ReplayableEvent {
         total_groups: u64,
         signers: [u8; 8],
     },
+
+    /// Tape ownership moved to a new authority.
+    TransferTape {
+        tape: Address,
+        previous_authority: Address,
+        authority: Address,
+    },
+
+    /// A node's pool was slashed, by eviction or certified inconsistency.
+    StakeSlashed {
+        pool: Address,
+        reason: u64,
+        amount: Coin<TAPE>,
+    },
+
+    /// A tape operator enabled or suspended object versioning.
+    SetTapeVersioning {
+        tape: Address,
+        status: u64,
+    },
+
+    /// A tape operator hid a name behind a delete marker.
+    PlaceDeleteMarker {
+        tape: Address,
+        name: Vec<u8>,
+    },
+
+    /// A tape operator removed one delete marker version of a name.
+    RemoveDeleteMarker {
+        tape: Address,
+        name: Vec<u8>,
+        track_number: TrackNumber,
+        marker: u32,
+    },
 }
 
 /// Replayable track metadata for the track-write flow.
@@ -225,6 +262,8 @@ pub struct ReplayTrackObject {
     pub content_type: ContentType,
     /// Logical user-facing object size, distinct from manifest-track size.
     pub logical_size: StorageUnits,
+    /// Free-form headers carried by the write.
+    pub headers: ObjectHeaders,
 }
 
 /// Single replay event emitted during block processing, with associated metadata.
@@ -270,7 +309,7 @@ pub struct SnapshotLog {
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "wincode", derive(Serialize, Deserialize, SchemaRead, SchemaWrite))]
 pub struct SnapshotHeader {
-    /// Wire-format version; must equal [`SNAPSHOT_VERSION`] on read.
+    /// Wire-format version; [`SNAPSHOT_VERSION`], or the v1 layout on read.
     pub version: u8,
     /// Epoch this snapshot covers.
     pub epoch: EpochNumber,
@@ -324,9 +363,12 @@ impl SnapshotLog {
     }
 
     /// Deserialize from the framed binary format
+    ///
+    /// v1 snapshots, logged before object headers, decode through their
+    /// legacy record layout.
     pub fn from_bytes(data: &[u8]) -> Result<Self, SnapshotError> {
         let header: SnapshotHeader = wincode::deserialize(data)?;
-        if header.version != SNAPSHOT_VERSION {
+        if header.version != SNAPSHOT_VERSION && header.version != LEGACY_SNAPSHOT_VERSION {
             return Err(SnapshotError::UnsupportedVersion(header.version));
         }
         let header_size = wincode::serialized_size(&header)? as usize;
@@ -339,7 +381,11 @@ impl SnapshotLog {
             let frame_size = wincode::serialized_size(&frame)? as usize;
             cursor += frame_size;
 
-            let entry: SnapshotEntry = wincode::deserialize(&frame.data)?;
+            let entry = if header.version == LEGACY_SNAPSHOT_VERSION {
+                decode_legacy_entry(&frame.data)?
+            } else {
+                wincode::deserialize(&frame.data)?
+            };
             entries.push(entry);
         }
 
@@ -1,11 +1,13 @@
 pub mod history;
 pub mod pool;
 pub mod schedule;
+pub mod slash;
 pub mod state;
 pub mod value;
 
 pub use history::*;
 pub use pool::*;
 pub use schedule::*;
+pub use slash::*;
 pub use state::*;
 pub use value::*;
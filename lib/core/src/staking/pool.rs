@@ -2,6 +2,7 @@ use crate::types::*;
 use bytemuck::{Pod, Zeroable};
 
 use super::schedule::*;
+use super::slash::*;
 use super::state::*;
 use crate::system::*;
 
@@ -17,6 +18,7 @@ pub enum PoolError {
     ZeroShares,
     ZeroStake,
     ZeroCommission,
+    AlreadySlashed,
 }
 
 #[repr(C)]
@@ -109,6 +111,94 @@ impl<const N: usize> StakingPool<N> {
         Ok(amount)
     }
 
+    /// Cut `bps` of the pool's stake, at most once per epoch. The cut is
+    /// capped at `available`, what the archive can burn right now.
+    ///
+    /// The cut lowers the exchange rate, so every share in the pool loses the
+    /// same fraction of its value. That includes shares scheduled to unlock,
+    /// which settle at the post-slash rate. Stake still waiting to activate
+    /// holds no shares yet and is left whole.
+    ///
+    /// The caller burns the whole cut from the archive as the slash lands. The
+    /// pool's reward balance covers what it can; the archive fronts the rest,
+    /// which stakers pay back out of their principal through
+    /// [`Self::repay_slash`] as they unstake.
+    pub fn slash(
+        &mut self,
+        slashing: &mut SlashState,
+        current_epoch: EpochNumber,
+        bps: BasisPoints,
+        available: Coin<TAPE>,
+    ) -> Result<PoolSlash, PoolError> {
+        if current_epoch < slashing.slashable_epoch {
+            return Err(PoolError::AlreadySlashed);
+        }
+
+        // A full cut would leave shares with no stake behind them.
+        if bps.as_u128() >= BasisPoints::MAX as u128 {
+            return Err(PoolError::StakeInvalid);
+        }
+
+        let amount: Coin<TAPE> = ((
+            self.stake.as_u128() * bps.as_u128() / BasisPoints::MAX as u128
+        ) as u64).into();
+        let amount = amount.min(available);
+        let from_rewards = amount.min(self.rewards);
+
+        self.stake = self.stake.saturating_sub(amount);
+        self.rewards = self.rewards.saturating_sub(from_rewards);
+        slashing.fronted = slashing.fronted.saturating_add(amount - from_rewards);
+        slashing.slashable_epoch = current_epoch.next();
+
+        Ok(PoolSlash {
+            amount,
+            from_rewards,
+        })
+    }
+
+    /// Pay out rewards a staker of a slashed pool is owed beyond what
+    /// [`Self::unstake_from_pool`] could pay from the pool's balance. The
+    /// slash took the pool's rewards first, so a staker whose rewards covered
+    /// their cut can be owed more than is left while others still have
+    /// principal to pay back. The archive fronts the gap. Returns what the
+    /// archive pays; an unslashed pool fronts nothing.
+    pub fn front_rewards(
+        &mut self,
+        slashing: &mut SlashState,
+        unpaid: Coin<TAPE>,
+    ) -> Coin<TAPE> {
+        if !slashing.is_slashed() {
+            return TAPE::zero();
+        }
+
+        slashing.fronted = slashing.fronted.saturating_add(unpaid);
+        unpaid
+    }
+
+    /// Take back the part of a staker's principal their shares no longer
+    /// cover: `principal` less what the shares `settled` at. It repays what
+    /// the archive fronted first and returns to the pool's rewards after.
+    /// Returns what the staker hands to the archive; in an unslashed pool
+    /// that is nothing, so rounding dust in the settlement never costs a
+    /// staker anything.
+    pub fn repay_slash(
+        &mut self,
+        slashing: &mut SlashState,
+        principal: Coin<TAPE>,
+        settled: Coin<TAPE>,
+    ) -> Coin<TAPE> {
+        if !slashing.is_slashed() {
+            return TAPE::zero();
+        }
+
+        let shortfall = principal.saturating_sub(settled);
+        let to_archive = shortfall.min(slashing.fronted);
+
+        slashing.fronted = slashing.fronted.saturating_sub(to_archive);
+        self.rewards = self.rewards.saturating_add(shortfall - to_archive);
+        shortfall
+    }
+
     /// Advance the pool state to the next epoch, applying any scheduled changes and distributing
     /// rewards.
     pub fn advance_epoch(
@@ -1237,4 +1327,201 @@ mod tests {
             .into();
         assert_eq!(bob_value, tape(109));
     }
+
+    const UNCAPPED: Coin<TAPE> = TAPE(u64::MAX);
+
+    #[test]
+    fn slash_cuts_rate() {
+        let mut p = TestPool::new(BasisPoints(0));
+        let mut slashing = SlashState::default();
+
+        p.schedule.stake(epoch(0), tape(1000)).unwrap();
+        p.advance_epoch(epoch(0), tape(0)).unwrap();
+        p.advance_epoch(epoch(1), tape(200)).unwrap();
+        assert_eq!(p.stake, tape(1200));
+
+        // 10% of 1200 stake, all of it covered by the 200 rewards
+        let cut = p.slash(&mut slashing, epoch(2), BasisPoints(1000), UNCAPPED).unwrap();
+        assert_eq!(cut, PoolSlash { amount: tape(120), from_rewards: tape(120) });
+        assert_eq!(p.stake, tape(1080));
+        assert_eq!(p.rewards, tape(80));
+        assert_eq!(slashing.fronted, tape(0));
+        assert_eq!(p.shares, shares(1000));
+        assert_eq!(p.get_current_rate(), ExchangeRate { tape: 1080, other: 1000 });
+    }
+
+    #[test]
+    fn slash_fronts_principal() {
+        let mut p = TestPool::new(BasisPoints(0));
+        let mut slashing = SlashState::default();
+
+        p.schedule.stake(epoch(0), tape(1000)).unwrap();
+        p.advance_epoch(epoch(0), tape(0)).unwrap();
+        p.advance_epoch(epoch(1), tape(100)).unwrap();
+
+        // 20% of 1100 is more than the 100 rewards; the archive fronts 120
+        let cut = p.slash(&mut slashing, epoch(2), BasisPoints(2000), UNCAPPED).unwrap();
+        assert_eq!(cut, PoolSlash { amount: tape(220), from_rewards: tape(100) });
+        assert_eq!(p.rewards, tape(0));
+        assert_eq!(slashing.fronted, tape(120));
+    }
+
+    #[test]
+    fn slash_capped_by_archive() {
+        let mut p = TestPool::new(BasisPoints(0));
+        let mut slashing = SlashState::default();
+
+        p.schedule.stake(epoch(0), tape(1000)).unwrap();
+        p.advance_epoch(epoch(0), tape(0)).unwrap();
+
+        let cut = p.slash(&mut slashing, epoch(1), BasisPoints(1000), tape(40)).unwrap();
+        assert_eq!(cut.amount, tape(40));
+        assert_eq!(p.stake, tape(960));
+        assert_eq!(slashing.fronted, tape(40));
+    }
+
+    #[test]
+    fn slash_once_per_epoch() {
+        let mut p = TestPool::new(BasisPoints(0));
+        let mut slashing = SlashState::default();
+
+        p.schedule.stake(epoch(0), tape(1000)).unwrap();
+        p.advance_epoch(epoch(0), tape(0)).unwrap();
+
+        p.slash(&mut slashing, epoch(3), BasisPoints(500), UNCAPPED).unwrap();
+        let err = p.slash(&mut slashing, epoch(3), BasisPoints(500), UNCAPPED).unwrap_err();
+        assert!(matches!(err, PoolError::AlreadySlashed));
+
+        p.slash(&mut slashing, epoch(4), BasisPoints(500), UNCAPPED).unwrap();
+        assert_eq!(p.stake, tape(903));
+    }
+
+    #[test]
+    fn slash_full_err() {
+        let mut p = TestPool::new(BasisPoints(0));
+        let mut slashing = SlashState::default();
+
+        p.schedule.stake(epoch(0), tape(1000)).unwrap();
+        p.advance_epoch(epoch(0), tape(0)).unwrap();
+
+        let err = p.slash(&mut slashing, epoch(1), BasisPoints(10_000), UNCAPPED).unwrap_err();
+        assert!(matches!(err, PoolError::StakeInvalid));
+        assert_eq!(p.stake, tape(1000));
+        assert!(!slashing.is_slashed());
+    }
+
+    #[test]
+    fn slash_skips_pending_stake() {
+        let mut p = TestPool::new(BasisPoints(0));
+        let mut slashing = SlashState::default();
+
+        p.schedule.stake(epoch(0), tape(1000)).unwrap();
+        p.advance_epoch(epoch(0), tape(0)).unwrap();
+
+        // Bob's stake is scheduled but holds no shares when the slash lands
+        let bob = p.stake_with_pool(epoch(1), tape(500)).unwrap();
+        p.slash(&mut slashing, epoch(1), BasisPoints(2000), UNCAPPED).unwrap();
+        assert_eq!(p.stake, tape(800));
+
+        p.advance_epoch(epoch(2), tape(0)).unwrap();
+        p.advance_epoch(epoch(3), tape(0)).unwrap();
+
+        // Bob mints at the slashed rate, so his 500 is still worth 500
+        let bob_shares: ShareAmount = ExchangeRate { tape: 800, other: 1000 }
+            .convert_to_other_amount(bob.amount.into())
+            .into();
+        assert_eq!(bob_shares, shares(625));
+        assert_eq!(p.shares, shares(1625));
+        let bob_value: Coin<TAPE> = p.get_current_rate()
+            .convert_to_tape_amount(bob_shares.into())
+            .into();
+        assert_eq!(bob_value, tape(500));
+    }
+
+    #[test]
+    fn slash_hits_pending_unlock() {
+        let mut p = TestPool::new(BasisPoints(0));
+        let mut slashing = SlashState::default();
+
+        let mut s = p.stake_with_pool(epoch(0), tape(1000)).unwrap();
+        p.advance_epoch(epoch(2), tape(0)).unwrap();
+
+        // Unlock is requested before the slash, but settles after it
+        let withdraw_epoch = p.request_withdraw(&mut s, epoch(2), ExchangeRate::flat()).unwrap();
+        p.slash(&mut slashing, epoch(3), BasisPoints(1000), UNCAPPED).unwrap();
+
+        let settle_rate = p.get_current_rate();
+        p.advance_epoch(withdraw_epoch, tape(0)).unwrap();
+        assert_eq!(p.stake, tape(0));
+        assert_eq!(p.shares, shares(0));
+
+        let settled: Coin<TAPE> = settle_rate
+            .convert_to_tape_amount(s.unlock_shares.into())
+            .into();
+        assert_eq!(settled, tape(900));
+        assert_eq!(p.repay_slash(&mut slashing, s.amount, settled), tape(100));
+        assert_eq!(slashing.fronted, tape(0));
+    }
+
+    // Alice's rewards cover her cut, Bob's principal covers his. Whatever
+    // order they leave in, each takes exactly what their shares settled at
+    // and the archive ends up even with what it burned.
+    #[test]
+    fn slash_settles_either_order() {
+        for alice_first in [true, false] {
+            let mut p = TestPool::new(BasisPoints(0));
+            let mut slashing = SlashState::default();
+
+            // Alice holds 500 principal plus all 500 of the pool's rewards,
+            // Bob joins afterwards with 500 at the higher rate.
+            p.schedule.stake(epoch(0), tape(500)).unwrap();
+            p.advance_epoch(epoch(0), tape(0)).unwrap();
+            p.advance_epoch(epoch(1), tape(500)).unwrap();
+            p.schedule.stake(epoch(2), tape(500)).unwrap();
+            p.advance_epoch(epoch(2), tape(0)).unwrap();
+            assert_eq!(p.stake, tape(1500));
+
+            let cut = p.slash(&mut slashing, epoch(3), BasisPoints(2000), UNCAPPED).unwrap();
+            assert_eq!(cut, PoolSlash { amount: tape(300), from_rewards: tape(300) });
+
+            // The archive holds the pool's rewards less the burned cut.
+            let mut archive = 500 - cut.amount.as_u64() as i64;
+            let mut leave = |p: &mut TestPool, principal: u64, settled: u64| {
+                let owed = tape(settled.saturating_sub(principal));
+                let mut stake = StakedTape::new(tape(principal), epoch(0));
+                stake.set_withdrawing(epoch(3));
+                let paid = p.unstake_from_pool(&mut stake, epoch(4), owed).unwrap();
+                let fronted = p.front_rewards(&mut slashing, owed - paid);
+                let repaid = p.repay_slash(&mut slashing, tape(principal), tape(settled));
+                archive += repaid.as_u64() as i64 - (paid + fronted).as_u64() as i64;
+                principal - repaid.as_u64() + (paid + fronted).as_u64()
+            };
+
+            // Alice's 1000 settles at 800, Bob's 500 at 400.
+            let (alice, bob) = if alice_first {
+                let alice = leave(&mut p, 500, 800);
+                (alice, leave(&mut p, 500, 400))
+            } else {
+                let bob = leave(&mut p, 500, 400);
+                (leave(&mut p, 500, 800), bob)
+            };
+
+            assert_eq!((alice, bob), (800, 400));
+            assert_eq!(archive, 0);
+            assert_eq!(p.rewards, tape(0));
+            assert_eq!(slashing.fronted, tape(0));
+        }
+    }
+
+    #[test]
+    fn unslashed_ignores_dust() {
+        let mut p = TestPool::new(BasisPoints(0));
+        let mut slashing = SlashState::default();
+
+        // No slash: a 1-flux rounding shortfall costs the staker nothing,
+        // and nothing is fronted past the pool's rewards.
+        assert_eq!(p.repay_slash(&mut slashing, tape(500), tape(499)), tape(0));
+        assert_eq!(p.front_rewards(&mut slashing, tape(1)), tape(0));
+        assert_eq!(slashing.fronted, tape(0));
+    }
 }
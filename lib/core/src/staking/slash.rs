@@ -0,0 +1,46 @@
+use crate::types::*;
+use bytemuck::{Pod, Zeroable};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Why a pool was slashed.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum SlashReason {
+    /// The committee voted the node out.
+    Eviction = 0,
+    /// A group certified that the node failed its storage challenges.
+    Inconsistency,
+}
+
+/// The outcome of a slash: how much stake was cut, and how much of that cut
+/// the pool's reward balance covered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolSlash {
+    /// Stake removed from the pool, burned from the archive in full.
+    pub amount: Coin<TAPE>,
+
+    /// The part of `amount` taken from the pool's rewards. The archive fronts
+    /// the rest, which is principal still held in staker vaults.
+    pub from_rewards: Coin<TAPE>,
+}
+
+/// Slashing bookkeeping kept alongside a node's staking pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct SlashState {
+    /// The first epoch the pool can be slashed in again. Zero until the pool
+    /// is first slashed.
+    pub slashable_epoch: EpochNumber,
+
+    /// Tokens the archive burned or paid out for the pool beyond its reward
+    /// balance. Stakers whose shares settle below their principal pay it back
+    /// at unstake.
+    pub fronted: Coin<TAPE>,
+}
+
+impl SlashState {
+    /// Whether the pool has ever been slashed.
+    pub fn is_slashed(&self) -> bool {
+        !self.slashable_epoch.is_zero()
+    }
+}
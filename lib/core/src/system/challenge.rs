@@ -0,0 +1,215 @@
+//! Proof-of-storage challenge selection and verification.
+
+use tape_crypto::hash::hashv;
+use tape_crypto::{Address, Hash};
+
+use crate::erasure::GROUP_SIZE;
+use crate::track::blob::BlobEncoding;
+use crate::track::stripe::{stripe_segment, verify_segment};
+use crate::types::{EpochNumber, SpoolIndex};
+
+/// Domain separation tag for challenge seeds.
+pub const CHALLENGE_SEED_TAG: &[u8; 8] = b"CHALLNG\0";
+
+/// Challenges a node issues to each group peer per epoch.
+pub const CHALLENGES_PER_EPOCH: u64 = 2;
+
+/// A single slice position a node must prove it still holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChallengeTarget {
+    /// Track whose slice is being challenged.
+    pub track: Address,
+
+    /// Spool the challenged node holds the slice for.
+    pub spool: SpoolIndex,
+
+    /// Stripe inside the slice the challenge is drawn for.
+    pub stripe: u32,
+}
+
+/// Seed for the challenges one node issues to another during an epoch.
+///
+/// The epoch nonce is only known once the epoch is set up, so a node cannot
+/// tell ahead of time which of its slices will be asked for.
+pub fn challenge_seed(
+    epoch: EpochNumber,
+    nonce: Hash,
+    challenger: Address,
+    target: Address,
+) -> Hash {
+    hashv(&[
+        CHALLENGE_SEED_TAG,
+        &epoch.0.to_le_bytes(),
+        nonce.as_ref(),
+        challenger.as_ref(),
+        target.as_ref(),
+    ])
+}
+
+/// Draw an index in `0..len` for one challenge round.
+///
+/// `salt` separates independent draws taken from the same round.
+pub fn challenge_index(seed: Hash, round: u64, salt: u8, len: u64) -> Option<u64> {
+    if len == 0 {
+        return None;
+    }
+
+    let digest = hashv(&[seed.as_ref(), &round.to_le_bytes(), &[salt]]);
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&digest.as_ref()[..8]);
+
+    Some(u64::from_le_bytes(word) % len)
+}
+
+/// Pick the track to challenge from the tracks stored for the target's group.
+///
+/// The slice must be sorted by address so that every node holding the same
+/// track set draws the same track for the same seed.
+pub fn select_challenge_track(seed: Hash, round: u64, tracks: &[Address]) -> Option<Address> {
+    let index = challenge_index(seed, round, 0, tracks.len() as u64)?;
+    tracks.get(index as usize).copied()
+}
+
+/// Pick the stripe to challenge inside the chosen track.
+pub fn select_challenge_stripe(seed: Hash, round: u64, blob: &BlobEncoding) -> Option<u32> {
+    challenge_index(seed, round, 1, blob.stripe_count.0).map(|stripe| stripe as u32)
+}
+
+/// Verify a challenge answer against the track's blob commitment.
+///
+/// The answer is the segment of the slice holding the challenged stripe,
+/// with its path to the slice leaf, so only blobs with segmented leaves can
+/// be challenged. The blob leaves are only trusted when they rebuild the
+/// commitment.
+pub fn verify_challenge(
+    blob: &BlobEncoding,
+    target: &ChallengeTarget,
+    data: &[u8],
+    segment_proof: &[Hash],
+) -> bool {
+    if !blob.is_segmented() || u64::from(target.stripe) >= blob.stripe_count.0 {
+        return false;
+    }
+    if blob.commitment_root() != blob.commitment {
+        return false;
+    }
+
+    let position = target.spool.as_usize() % GROUP_SIZE;
+    let segment = stripe_segment(u64::from(target.stripe), blob.stripe_count);
+    verify_segment(&blob.leaves[position], segment, data, segment_proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{EncodingProfile, LeafScheme};
+    use crate::erasure::SLICE_TREE_HEIGHT;
+    use crate::track::stripe::{segment_hashes, segment_proof, segment_range, slice_leaf};
+    use crate::types::{StorageUnits, StripeCount};
+    use tape_crypto::merkle::{hash_leaf, root_from_leaf_hashes};
+
+    fn blob_for(slices: &[Vec<u8>]) -> BlobEncoding {
+        let mut leaves = [hash_leaf(&[]); GROUP_SIZE];
+        for (index, slice) in slices.iter().enumerate() {
+            leaves[index] = slice_leaf(slice, StripeCount(4));
+        }
+
+        BlobEncoding {
+            size: StorageUnits::from_bytes(1024),
+            commitment: root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&leaves),
+            profile: EncodingProfile::basic_default().with_leaf_scheme(LeafScheme::Segmented),
+            stripe_size: StorageUnits::from_bytes(256),
+            stripe_count: StripeCount(4),
+            leaves,
+        }
+    }
+
+    fn slices() -> Vec<Vec<u8>> {
+        (0..GROUP_SIZE)
+            .map(|i| (0..64).map(|b| (i * 64 + b) as u8).collect())
+            .collect()
+    }
+
+    #[test]
+    fn seed_is_bound_to_pair() {
+        let nonce = Hash::from([0x11; 32]);
+        let a = Address::new_unique();
+        let b = Address::new_unique();
+
+        let seed = challenge_seed(EpochNumber(4), nonce, a, b);
+
+        assert_eq!(seed, challenge_seed(EpochNumber(4), nonce, a, b));
+        assert_ne!(seed, challenge_seed(EpochNumber(4), nonce, b, a));
+        assert_ne!(seed, challenge_seed(EpochNumber(5), nonce, a, b));
+        assert_ne!(seed, challenge_seed(EpochNumber(4), Hash::from([0x22; 32]), a, b));
+    }
+
+    #[test]
+    fn index_stays_in_range() {
+        let seed = Hash::from([0x42; 32]);
+
+        assert_eq!(challenge_index(seed, 0, 0, 0), None);
+        for round in 0..64 {
+            assert!(challenge_index(seed, round, 0, 7).unwrap() < 7);
+        }
+    }
+
+    #[test]
+    fn selects_track_and_stripe() {
+        let seed = Hash::from([0x42; 32]);
+        let tracks = vec![Address::new_unique(), Address::new_unique()];
+        let blob = blob_for(&slices());
+
+        let track = select_challenge_track(seed, 0, &tracks).unwrap();
+        assert!(tracks.contains(&track));
+        assert!(select_challenge_track(seed, 0, &[]).is_none());
+
+        let stripe = select_challenge_stripe(seed, 0, &blob).unwrap();
+        assert!(u64::from(stripe) < blob.stripe_count.0);
+    }
+
+    #[test]
+    fn verifies_answer() {
+        let slices = slices();
+        let blob = blob_for(&slices);
+        let target = ChallengeTarget {
+            track: Address::new_unique(),
+            spool: SpoolIndex(23),
+            stripe: 1,
+        };
+        let position = 3;
+        let stripes = blob.stripe_count;
+        let hashes = segment_hashes(&slices[position], stripes);
+        let proof = segment_proof(&hashes, 1).unwrap();
+        let chunk = &slices[position][segment_range(64, stripes, 1)];
+
+        assert!(verify_challenge(&blob, &target, chunk, &proof));
+
+        // Corrupted stripe bytes.
+        let mut corrupted = chunk.to_vec();
+        corrupted[0] ^= 0xFF;
+        assert!(!verify_challenge(&blob, &target, &corrupted, &proof));
+
+        // Another stripe of the same slice.
+        let other = ChallengeTarget { stripe: 2, ..target };
+        assert!(!verify_challenge(&blob, &other, chunk, &proof));
+
+        // Another spool's stripe.
+        let neighbour = &slices[position + 1][segment_range(64, stripes, 1)];
+        assert!(!verify_challenge(&blob, &target, neighbour, &proof));
+
+        // Stripe outside the blob.
+        let out_of_range = ChallengeTarget { stripe: 4, ..target };
+        assert!(!verify_challenge(&blob, &out_of_range, chunk, &proof));
+
+        // Leaves that do not rebuild the commitment.
+        let mut forged = blob_for(&slices);
+        forged.commitment = Hash::default();
+        assert!(!verify_challenge(&forged, &target, chunk, &proof));
+
+        // A blob with whole-slice leaves has no segments to answer with.
+        let mut whole = blob_for(&slices);
+        whole.profile = whole.profile.with_leaf_scheme(LeafScheme::Whole);
+        assert!(!verify_challenge(&whole, &target, chunk, &proof));
+    }
+}
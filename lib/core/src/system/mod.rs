@@ -1,5 +1,6 @@
 mod assignment;
 mod blacklist;
+mod challenge;
 mod committee;
 mod epoch;
 mod exchange;
@@ -12,6 +13,7 @@ mod vote;
 
 pub use assignment::*;
 pub use blacklist::*;
+pub use challenge::*;
 pub use committee::*;
 pub use epoch::*;
 pub use exchange::*;
@@ -22,6 +22,7 @@ pub enum VoteKind {
     Snapshot,
     Assignment,
     Eviction,
+    Challenge,
 }
 
 /// Durable identity for a candidate vote account.
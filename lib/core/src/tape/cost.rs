@@ -1,6 +1,9 @@
 use crate::types::coin::{Coin, TAPE};
 use crate::types::{EpochNumber, StorageUnits};
 
+/// Epochs ahead of expiry in which a tape's escrow may renew it.
+pub const RENEWAL_WINDOW_EPOCHS: u64 = 4;
+
 /// Compute the token cost for reserving `capacity` for `epochs`.
 pub fn tape_reservation_cost(
     price_per_unit: Coin<TAPE>,
@@ -26,6 +29,12 @@ pub fn remaining_tape_epochs(
     }
 }
 
+/// Whether a tape expiring at `expiry` may be renewed from its escrow in
+/// `current`: it is still live and expires within the renewal window.
+pub fn in_renewal_window(current: EpochNumber, expiry: EpochNumber) -> bool {
+    current < expiry && expiry.as_u64() - current.as_u64() <= RENEWAL_WINDOW_EPOCHS
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,4 +75,13 @@ mod tests {
         let remaining = remaining_tape_epochs(EpochNumber(10), EpochNumber(4), EpochNumber(10));
         assert_eq!(remaining, None);
     }
+
+    #[test]
+    fn renewal_window_spans_epochs_before_expiry() {
+        let expiry = EpochNumber(10);
+        assert!(!in_renewal_window(EpochNumber(5), expiry));
+        assert!(in_renewal_window(EpochNumber(6), expiry));
+        assert!(in_renewal_window(EpochNumber(9), expiry));
+        assert!(!in_renewal_window(EpochNumber(10), expiry));
+    }
 }
@@ -4,11 +4,18 @@ pub struct TapeFlags;
 
 impl TapeFlags {
     pub const SYSTEM: u64 = 1;
+    /// Ownership has moved, so the address no longer derives from the authority.
+    pub const TRANSFERRED: u64 = 2;
 
     #[inline(always)]
     pub fn is_system(flags: u64) -> bool {
         flags & Self::SYSTEM != 0
     }
+
+    #[inline(always)]
+    pub fn is_transferred(flags: u64) -> bool {
+        flags & Self::TRANSFERRED != 0
+    }
 }
 
 #[repr(u8)]
@@ -1,5 +1,7 @@
 mod cost;
 mod identity;
+mod versioning;
 
 pub use cost::*;
 pub use identity::*;
+pub use versioning::*;
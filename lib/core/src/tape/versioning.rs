@@ -0,0 +1,13 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Versioning state of a tape's named objects, set by the tape operator.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum TapeVersioning {
+    /// Every write and delete adds a version to the name's history.
+    Enabled = 1,
+
+    /// Earlier history is kept, but new writes and deletes replace the name's
+    /// single null version.
+    Suspended = 2,
+}
@@ -1,15 +1,16 @@
 //! Blob payload metadata and commitment encoding.
 
 use core::mem::size_of;
+use core::ops::Range;
 
 use bytemuck::{Pod, Zeroable};
 use tape_crypto::Hash;
 use tape_crypto::hash::hash;
-use tape_crypto::merkle::root_from_leaf_hashes;
-use tape_crypto::merkle::hash_leaf;
+use tape_crypto::merkle::{hash_leaf, root_from_leaf_hashes};
 
-use crate::encoding::EncodingProfile;
+use crate::encoding::{EncodingProfile, LeafScheme};
 use crate::erasure::{SLICE_TREE_HEIGHT, GROUP_SIZE};
+use crate::track::stripe::{self, scheme_leaf, segments_root};
 use crate::types::{SpoolIndex, StorageUnits, StripeCount};
 
 #[cfg(feature = "wincode")]
@@ -42,7 +43,7 @@ pub struct BlobEncoding {
     /// Number of stripes.
     pub stripe_count: StripeCount,
 
-    /// Per-slice commitment leaves.
+    /// Per-slice commitment leaves, built under the profile's leaf scheme.
     pub leaves: [Hash; GROUP_SIZE],
 }
 
@@ -68,6 +69,57 @@ impl BlobEncoding {
         root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&self.leaves)
     }
 
+    /// Whether each leaf commits to the slice's stripe segments.
+    #[inline]
+    pub fn is_segmented(&self) -> bool {
+        self.profile.leaf_scheme() == Some(LeafScheme::Segmented)
+    }
+
+    /// Number of segments each slice is committed in; a whole-slice leaf
+    /// covers one.
+    pub fn segment_count(&self) -> u64 {
+        if self.is_segmented() {
+            stripe::segment_count(self.stripe_count)
+        } else {
+            1
+        }
+    }
+
+    /// Byte range of `segment` in a slice of `len` bytes.
+    pub fn segment_range(&self, len: usize, segment: u64) -> Range<usize> {
+        if self.is_segmented() {
+            stripe::segment_range(len, self.stripe_count, segment)
+        } else if segment == 0 {
+            0..len
+        } else {
+            len..len
+        }
+    }
+
+    /// Leaf hash of every segment of a slice, in order.
+    pub fn segment_hashes(&self, data: &[u8]) -> Vec<Hash> {
+        (0..self.segment_count())
+            .map(|segment| hash_leaf(&data[self.segment_range(data.len(), segment)]))
+            .collect()
+    }
+
+    /// Slice leaf over segment hashes, or None if they cannot form one.
+    pub fn leaf_from_segments(&self, hashes: &[Hash]) -> Option<Hash> {
+        if hashes.len() as u64 != self.segment_count() {
+            return None;
+        }
+        match self.profile.leaf_scheme()? {
+            LeafScheme::Whole => hashes.first().copied(),
+            LeafScheme::Segmented => segments_root(hashes),
+        }
+    }
+
+    /// The commitment leaf of a slice, or None for an unknown leaf scheme.
+    pub fn slice_leaf(&self, data: &[u8]) -> Option<Hash> {
+        let scheme = self.profile.leaf_scheme()?;
+        Some(scheme_leaf(data, scheme, self.stripe_count))
+    }
+
     /// Verify a single slice against its stored leaf hash.
     pub fn verify_slice(&self, position: SpoolIndex, data: &[u8]) -> bool {
         let position = position.as_usize();
@@ -75,7 +127,7 @@ impl BlobEncoding {
             return false;
         }
 
-        hash_leaf(data) == self.leaves[position]
+        self.slice_leaf(data) == Some(self.leaves[position])
     }
 
     /// Compute the canonical value hash for this blob payload.
@@ -136,6 +188,23 @@ mod tests {
         assert_eq!(recovered, blob);
     }
 
+    #[test]
+    fn verify_slice_by_scheme() {
+        let data: Vec<u8> = (0..600).map(|i| i as u8).collect();
+        let mut blob = sample_blob_encoding();
+
+        blob.leaves[1] = hash_leaf(&data);
+        assert!(blob.verify_slice(SpoolIndex::from(1), &data));
+        assert_eq!(blob.leaf_from_segments(&blob.segment_hashes(&data)), Some(blob.leaves[1]));
+
+        blob.profile = blob.profile.with_leaf_scheme(LeafScheme::Segmented);
+        assert!(!blob.verify_slice(SpoolIndex::from(1), &data));
+        blob.leaves[1] = stripe::slice_leaf(&data, blob.stripe_count);
+        assert!(blob.verify_slice(SpoolIndex::from(1), &data));
+        assert_eq!(blob.segment_count(), 2);
+        assert_eq!(blob.leaf_from_segments(&blob.segment_hashes(&data)), Some(blob.leaves[1]));
+    }
+
     #[test]
     fn encoding_pack() {
         let blob = sample_blob_encoding();
@@ -5,7 +5,7 @@ use tape_crypto::Hash;
 
 use crate::track::blob::BlobEncoding;
 use crate::track::types::{TrackKind, TrackState};
-use crate::types::{ContentType, StorageUnits};
+use crate::types::{ContentType, ObjectHeaders, StorageUnits};
 
 #[cfg(feature = "wincode")]
 use serde::{Deserialize, Serialize};
@@ -112,6 +112,8 @@ pub struct TrackObjectInfo {
     pub name: Vec<u8>,
     pub content_type: ContentType,
     pub logical_size: StorageUnits,
+    /// Trailer half of [`ObjectHeaders::to_wire`]; empty when the write carries none.
+    pub headers: Vec<u8>,
 }
 
 impl TrackObjectInfo {
@@ -121,6 +123,7 @@ impl TrackObjectInfo {
             name: &self.name,
             content_type: self.content_type,
             logical_size: self.logical_size,
+            headers: &self.headers,
         }
     }
 }
@@ -131,6 +134,15 @@ pub struct TrackObjectInfoSlice<'source> {
     pub name: &'source [u8],
     pub content_type: ContentType,
     pub logical_size: StorageUnits,
+    pub headers: &'source [u8],
+}
+
+impl TrackObjectInfoSlice<'_> {
+    /// Decode the free-form headers. Parsing already rejected malformed
+    /// trailers, so a failure here falls back to none.
+    pub fn decode_headers(&self) -> ObjectHeaders {
+        ObjectHeaders::from_wire(self.content_type, self.headers).unwrap_or_default()
+    }
 }
 
 /// Owned track write envelope.
@@ -166,6 +178,7 @@ impl<'source> BlobInfoSlice<'source> {
                 name: object.name.to_vec(),
                 content_type: object.content_type,
                 logical_size: object.logical_size,
+                headers: object.headers.to_vec(),
             }),
             data: self.data.to_owned(),
         }
@@ -223,6 +236,7 @@ mod tests {
                 name: b"photos/cat.jpg".to_vec(),
                 content_type: ContentType::ImageJpeg,
                 logical_size: StorageUnits::from_bytes(1024),
+                headers: ObjectHeaders::with_content_type("image/jpeg").to_bytes().unwrap(),
             }),
             data: BlobData::Coded(sample_blob_encoding()),
         };
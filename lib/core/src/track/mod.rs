@@ -2,6 +2,7 @@ pub mod blob;
 pub mod data;
 pub mod archive;
 pub mod mirror;
+pub mod stripe;
 pub mod types;
 
 pub use archive::TRACK_TREE_HEIGHT;
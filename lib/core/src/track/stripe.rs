@@ -0,0 +1,155 @@
+//! Stripe segments of a slice.
+//!
+//! Under [`LeafScheme::Segmented`] a slice leaf is not a hash of the whole
+//! slice but the root of a small tree over the slice's stripe segments. One
+//! segment can then be checked against the blob commitment on its own:
+//! uploads are verified segment by segment as they arrive, and a storage
+//! challenge is answered with a single stripe. Blobs under
+//! [`LeafScheme::Whole`] keep one leaf hash over the whole slice.
+
+use core::ops::Range;
+
+use tape_crypto::Hash;
+use tape_crypto::merkle::{
+    create_proof_from_leaf_hashes, hash_leaf, root_from_leaf_hashes, verify_proof,
+};
+
+use crate::encoding::LeafScheme;
+use crate::types::StripeCount;
+
+/// Merkle tree height for the segments of one slice.
+pub const STRIPE_TREE_HEIGHT: usize = 8;
+
+/// Most segments a slice is cut into. Blobs with more stripes than this
+/// share segments between neighbouring stripes.
+pub const MAX_SLICE_SEGMENTS: u64 = 1 << STRIPE_TREE_HEIGHT;
+
+/// Number of segments a slice of a blob with `stripe_count` stripes has.
+pub fn segment_count(stripe_count: StripeCount) -> u64 {
+    stripe_count.0.clamp(1, MAX_SLICE_SEGMENTS)
+}
+
+/// Byte range of `segment` in a slice of `len` bytes.
+///
+/// Segments split the slice evenly, so each lines up with the stripe chunk
+/// it holds; a segment past the end is empty.
+pub fn segment_range(len: usize, stripe_count: StripeCount, segment: u64) -> Range<usize> {
+    let count = segment_count(stripe_count);
+    let segment = segment.min(count);
+    let boundary = |index: u64| (index * len as u64 / count) as usize;
+    boundary(segment)..boundary((segment + 1).min(count))
+}
+
+/// The segment holding `stripe`.
+pub fn stripe_segment(stripe: u64, stripe_count: StripeCount) -> u64 {
+    let count = segment_count(stripe_count);
+    if stripe_count.0 <= count {
+        return stripe;
+    }
+    stripe.saturating_mul(count) / stripe_count.0
+}
+
+/// Leaf hash of every segment of a slice, in order.
+pub fn segment_hashes(data: &[u8], stripe_count: StripeCount) -> Vec<Hash> {
+    (0..segment_count(stripe_count))
+        .map(|segment| hash_leaf(&data[segment_range(data.len(), stripe_count, segment)]))
+        .collect()
+}
+
+/// Slice leaf over segment hashes, or None past [`MAX_SLICE_SEGMENTS`].
+pub fn segments_root(hashes: &[Hash]) -> Option<Hash> {
+    if hashes.len() as u64 > MAX_SLICE_SEGMENTS {
+        return None;
+    }
+    Some(root_from_leaf_hashes::<STRIPE_TREE_HEIGHT>(hashes))
+}
+
+/// The segmented commitment leaf of a slice.
+pub fn slice_leaf(data: &[u8], stripe_count: StripeCount) -> Hash {
+    root_from_leaf_hashes::<STRIPE_TREE_HEIGHT>(&segment_hashes(data, stripe_count))
+}
+
+/// The commitment leaf of a slice under `scheme`.
+pub fn scheme_leaf(data: &[u8], scheme: LeafScheme, stripe_count: StripeCount) -> Hash {
+    match scheme {
+        LeafScheme::Whole => hash_leaf(data),
+        LeafScheme::Segmented => slice_leaf(data, stripe_count),
+    }
+}
+
+/// Merkle path from one segment to its slice leaf.
+pub fn segment_proof(hashes: &[Hash], segment: u64) -> Option<Vec<Hash>> {
+    create_proof_from_leaf_hashes::<STRIPE_TREE_HEIGHT>(hashes, segment as usize).ok()
+}
+
+/// Verify a segment against its slice leaf.
+pub fn verify_segment(leaf: &Hash, segment: u64, data: &[u8], proof: &[Hash]) -> bool {
+    verify_proof(data, leaf, proof, segment, STRIPE_TREE_HEIGHT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slice(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn segments_cover_slice() {
+        let stripes = StripeCount(7);
+        for len in [0, 1, 6, 7, 100, 1003] {
+            let mut end = 0;
+            for segment in 0..segment_count(stripes) {
+                let range = segment_range(len, stripes, segment);
+                assert_eq!(range.start, end);
+                end = range.end;
+            }
+            assert_eq!(end, len);
+        }
+    }
+
+    #[test]
+    fn segment_proves_leaf() {
+        let stripes = StripeCount(5);
+        let data = slice(1000);
+        let hashes = segment_hashes(&data, stripes);
+        let leaf = slice_leaf(&data, stripes);
+        assert_eq!(segments_root(&hashes), Some(leaf));
+
+        for segment in 0..segment_count(stripes) {
+            let proof = segment_proof(&hashes, segment).expect("proof");
+            let chunk = &data[segment_range(data.len(), stripes, segment)];
+            assert!(verify_segment(&leaf, segment, chunk, &proof));
+            assert!(!verify_segment(&leaf, (segment + 1) % 5, chunk, &proof));
+        }
+    }
+
+    // stripes past the segment cap fold into shared segments
+    #[test]
+    fn many_stripes_share_segments() {
+        let stripes = StripeCount(MAX_SLICE_SEGMENTS * 3);
+        assert_eq!(segment_count(stripes), MAX_SLICE_SEGMENTS);
+        assert_eq!(stripe_segment(0, stripes), 0);
+        assert_eq!(stripe_segment(5, stripes), 1);
+        assert_eq!(stripe_segment(stripes.0 - 1, stripes), MAX_SLICE_SEGMENTS - 1);
+        assert_eq!(stripe_segment(3, StripeCount(4)), 3);
+    }
+
+    #[test]
+    fn whole_leaf_hashes_slice() {
+        let data = slice(600);
+        assert_eq!(scheme_leaf(&data, LeafScheme::Whole, StripeCount(3)), hash_leaf(&data));
+        assert_eq!(
+            scheme_leaf(&data, LeafScheme::Segmented, StripeCount(3)),
+            slice_leaf(&data, StripeCount(3)),
+        );
+    }
+
+    #[test]
+    fn leaf_binds_stripe_count() {
+        let data = slice(600);
+        assert_ne!(slice_leaf(&data, StripeCount(2)), slice_leaf(&data, StripeCount(3)));
+        assert_eq!(segments_root(&vec![Hash::default(); 300]), None);
+    }
+}
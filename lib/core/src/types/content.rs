@@ -268,6 +268,274 @@ impl core::fmt::Display for ContentType {
     }
 }
 
+/// Upper bound on an encoded [`ObjectHeaders`] record, matching S3's 2 KiB
+/// user-metadata allowance.
+pub const MAX_OBJECT_HEADERS_LEN: usize = 2048;
+
+/// Current [`ObjectHeaders`] wire version.
+const OBJECT_HEADERS_VERSION: u8 = 1;
+
+/// One `x-amz-meta-*` user metadata pair.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "wincode", derive(Serialize, Deserialize, SchemaRead, SchemaWrite))]
+pub struct MetadataEntry {
+    /// Lowercased key with the `x-amz-meta-` prefix stripped.
+    pub key: String,
+    pub value: String,
+}
+
+/// Free-form HTTP representation headers stored with a named object.
+///
+/// The [`ContentType`] enum stays the hot, fixed-size type used for listings
+/// and charging; these carry the writer's exact strings next to it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "wincode", derive(Serialize, Deserialize, SchemaRead, SchemaWrite))]
+pub struct ObjectHeaders {
+    /// Exact `Content-Type` as supplied, parameters included.
+    pub content_type: Option<String>,
+    pub content_encoding: Option<String>,
+    pub content_disposition: Option<String>,
+    pub cache_control: Option<String>,
+    /// User metadata, sorted by key.
+    pub user_metadata: Vec<MetadataEntry>,
+}
+
+impl ObjectHeaders {
+    /// Headers carrying only an exact `Content-Type` string.
+    pub fn with_content_type(content_type: &str) -> Self {
+        Self {
+            content_type: Some(content_type.to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content_type.is_none()
+            && self.content_encoding.is_none()
+            && self.content_disposition.is_none()
+            && self.cache_control.is_none()
+            && self.user_metadata.is_empty()
+    }
+
+    /// The hot enum for the exact content type.
+    pub fn hot_content_type(&self) -> ContentType {
+        self.content_type
+            .as_deref()
+            .map(ContentType::from_str)
+            .unwrap_or(ContentType::Unknown)
+    }
+
+    /// The `Content-Type` to serve, falling back to the hot type's canonical string.
+    pub fn mime(&self, hot: ContentType) -> &str {
+        self.content_type.as_deref().unwrap_or(hot.to_str())
+    }
+
+    /// Add a user metadata pair, keeping keys sorted and unique.
+    pub fn insert_metadata(&mut self, key: &str, value: &str) {
+        let key = key.to_ascii_lowercase();
+        match self.user_metadata.binary_search_by(|entry| entry.key.cmp(&key)) {
+            Ok(index) => self.user_metadata[index].value = value.to_string(),
+            Err(index) => self.user_metadata.insert(
+                index,
+                MetadataEntry {
+                    key,
+                    value: value.to_string(),
+                },
+            ),
+        }
+    }
+
+    /// Length of the encoded trailer, counted without building it.
+    pub fn encoded_len(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+
+        let fields = [
+            &self.content_type,
+            &self.content_encoding,
+            &self.content_disposition,
+            &self.cache_control,
+        ]
+        .iter()
+        .map(|field| 2 + field.as_deref().map_or(0, str::len))
+        .sum::<usize>();
+        let metadata = self
+            .user_metadata
+            .iter()
+            .map(|entry| 4 + entry.key.len() + entry.value.len())
+            .sum::<usize>();
+
+        1 + fields + 2 + metadata
+    }
+
+    /// Encode for the track-write trailer. Empty headers encode to nothing so
+    /// writes without them keep their original layout. Returns `None` when
+    /// the record would exceed [`MAX_OBJECT_HEADERS_LEN`], which also keeps
+    /// every length within its `u16` prefix.
+    ///
+    /// Layout: version byte, then the four optional strings and each metadata
+    /// key/value as little-endian `u16` length-prefixed UTF-8, with the pair
+    /// count as a `u16` ahead of the pairs. An absent string has length zero.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        let len = self.encoded_len();
+        if len > MAX_OBJECT_HEADERS_LEN {
+            return None;
+        }
+        if len == 0 {
+            return Some(Vec::new());
+        }
+
+        let mut out = Vec::with_capacity(len);
+        out.push(OBJECT_HEADERS_VERSION);
+        for field in [
+            &self.content_type,
+            &self.content_encoding,
+            &self.content_disposition,
+            &self.cache_control,
+        ] {
+            push_field(&mut out, field.as_deref().unwrap_or(""));
+        }
+
+        out.extend_from_slice(&(self.user_metadata.len() as u16).to_le_bytes());
+        for entry in &self.user_metadata {
+            push_field(&mut out, &entry.key);
+            push_field(&mut out, &entry.value);
+        }
+
+        Some(out)
+    }
+
+    /// Decode a track-write trailer. Returns `None` for oversized or malformed
+    /// input, or any value that could not be sent back as an HTTP header.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() {
+            return Some(Self::default());
+        }
+
+        if bytes.len() > MAX_OBJECT_HEADERS_LEN || bytes[0] != OBJECT_HEADERS_VERSION {
+            return None;
+        }
+
+        let mut reader = FieldReader { rest: &bytes[1..] };
+        let mut headers = Self {
+            content_type: reader.optional()?,
+            content_encoding: reader.optional()?,
+            content_disposition: reader.optional()?,
+            cache_control: reader.optional()?,
+            user_metadata: Vec::new(),
+        };
+
+        let count = reader.u16()?;
+        for _ in 0..count {
+            let key = reader.field()?;
+            let value = reader.field()?;
+            if key.is_empty() || !is_metadata_key(key) {
+                return None;
+            }
+
+            // Keys must arrive sorted and unique, so a record has one encoding.
+            if headers.user_metadata.last().is_some_and(|last| last.key.as_str() >= key) {
+                return None;
+            }
+
+            headers.user_metadata.push(MetadataEntry {
+                key: key.to_string(),
+                value: value.to_string(),
+            });
+        }
+
+        reader.rest.is_empty().then_some(headers)
+    }
+
+    /// Split into the hot type and the trailer bytes for a track write. A
+    /// content type the hot enum already spells exactly is left off the wire.
+    /// Returns `None` when the trailer would exceed [`MAX_OBJECT_HEADERS_LEN`].
+    pub fn to_wire(&self) -> Option<(ContentType, Vec<u8>)> {
+        let hot = self.hot_content_type();
+        if self.content_type.as_deref() == Some(hot.to_str()) {
+            let trimmed = Self {
+                content_type: None,
+                ..self.clone()
+            };
+            return Some((hot, trimmed.to_bytes()?));
+        }
+
+        Some((hot, self.to_bytes()?))
+    }
+
+    /// Rebuild headers from a track write's hot type and trailer bytes.
+    pub fn from_wire(hot: ContentType, bytes: &[u8]) -> Option<Self> {
+        let mut headers = Self::from_bytes(bytes)?;
+        if headers.content_type.is_none() && hot != ContentType::Unknown {
+            headers.content_type = Some(hot.to_str().to_string());
+        }
+
+        Some(headers)
+    }
+}
+
+impl From<ContentType> for ObjectHeaders {
+    fn from(content_type: ContentType) -> Self {
+        match content_type {
+            ContentType::Unknown => Self::default(),
+            known => Self::with_content_type(known.to_str()),
+        }
+    }
+}
+
+/// Callers bound the record by [`MAX_OBJECT_HEADERS_LEN`] first, so `value`
+/// always fits its `u16` length prefix.
+fn push_field(out: &mut Vec<u8>, value: &str) {
+    debug_assert!(value.len() <= MAX_OBJECT_HEADERS_LEN);
+    out.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Whether `key` is a valid lowercase HTTP header token.
+fn is_metadata_key(key: &str) -> bool {
+    key.bytes().all(|byte| {
+        byte.is_ascii_lowercase()
+            || byte.is_ascii_digit()
+            || b"!#$%&'*+-.^_`|~".contains(&byte)
+    })
+}
+
+/// Whether `value` can be sent back verbatim as an HTTP header value.
+fn is_header_value(value: &str) -> bool {
+    value.bytes().all(|byte| byte == b'\t' || (0x20..0x7f).contains(&byte))
+}
+
+struct FieldReader<'source> {
+    rest: &'source [u8],
+}
+
+impl<'source> FieldReader<'source> {
+    fn u16(&mut self) -> Option<u16> {
+        let (len, rest) = self.rest.split_first_chunk::<2>()?;
+        self.rest = rest;
+        Some(u16::from_le_bytes(*len))
+    }
+
+    fn field(&mut self) -> Option<&'source str> {
+        let len = self.u16()? as usize;
+        if self.rest.len() < len {
+            return None;
+        }
+
+        let (value, rest) = self.rest.split_at(len);
+        self.rest = rest;
+
+        let value = core::str::from_utf8(value).ok()?;
+        is_header_value(value).then_some(value)
+    }
+
+    fn optional(&mut self) -> Option<Option<String>> {
+        let value = self.field()?;
+        Some((!value.is_empty()).then(|| value.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,4 +594,116 @@ mod tests {
         assert_eq!(ContentType::ApplicationGzip.extension(), "gz");
         assert_eq!(ContentType::Unknown.extension(), "bin");
     }
+
+    fn sample_headers() -> ObjectHeaders {
+        let mut headers = ObjectHeaders::with_content_type("application/x-parquet");
+        headers.content_encoding = Some("gzip".into());
+        headers.content_disposition = Some("attachment; filename=\"part-0.parquet\"".into());
+        headers.cache_control = Some("no-cache".into());
+        headers.insert_metadata("Pipeline", "ingest-7");
+        headers.insert_metadata("owner", "data-lake");
+        headers
+    }
+
+    // headers survive the trailer encoding
+    #[test]
+    fn headers_roundtrip() {
+        let headers = sample_headers();
+        let bytes = headers.to_bytes().unwrap();
+
+        assert_eq!(bytes.len(), headers.encoded_len());
+        assert_eq!(ObjectHeaders::from_bytes(&bytes), Some(headers));
+        assert_eq!(
+            ObjectHeaders::from_bytes(&[]),
+            Some(ObjectHeaders::default())
+        );
+        assert_eq!(ObjectHeaders::default().to_bytes(), Some(Vec::new()));
+    }
+
+    // metadata keys are lowercased, sorted and deduplicated
+    #[test]
+    fn metadata_sorted() {
+        let headers = sample_headers();
+        let keys: Vec<_> = headers.user_metadata.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["owner", "pipeline"]);
+
+        let mut headers = headers;
+        headers.insert_metadata("OWNER", "platform");
+        assert_eq!(headers.user_metadata.len(), 2);
+        assert_eq!(headers.user_metadata[0].value, "platform");
+    }
+
+    // truncated, trailing or unsendable input is rejected
+    #[test]
+    fn headers_rejects_malformed() {
+        let bytes = sample_headers().to_bytes().unwrap();
+
+        assert!(ObjectHeaders::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(ObjectHeaders::from_bytes(&trailing).is_none());
+
+        let mut version = bytes.clone();
+        version[0] = 9;
+        assert!(ObjectHeaders::from_bytes(&version).is_none());
+
+        let newline = ObjectHeaders::with_content_type("text/plain\r\nX-Injected: 1");
+        assert!(ObjectHeaders::from_bytes(&newline.to_bytes().unwrap()).is_none());
+    }
+
+    // the hot type follows the exact string and falls back to octet-stream
+    #[test]
+    fn headers_hot_type() {
+        let headers = ObjectHeaders::with_content_type("text/csv; charset=utf-8");
+        assert_eq!(headers.hot_content_type(), ContentType::TextCsv);
+        assert_eq!(headers.mime(ContentType::TextCsv), "text/csv; charset=utf-8");
+
+        let headers = ObjectHeaders::from(ContentType::ImagePng);
+        assert_eq!(headers.content_type.as_deref(), Some("image/png"));
+        assert_eq!(headers.to_wire(), Some((ContentType::ImagePng, Vec::new())));
+        assert_eq!(ObjectHeaders::default().mime(ContentType::Unknown), "application/octet-stream");
+    }
+
+    // canonical content types ride the hot enum instead of the trailer
+    #[test]
+    fn headers_wire() {
+        let (hot, bytes) = ObjectHeaders::from(ContentType::TextCsv).to_wire().unwrap();
+        assert_eq!(hot, ContentType::TextCsv);
+        assert!(bytes.is_empty());
+        assert_eq!(
+            ObjectHeaders::from_wire(hot, &bytes),
+            Some(ObjectHeaders::with_content_type("text/csv"))
+        );
+
+        let headers = sample_headers();
+        let (hot, bytes) = headers.to_wire().unwrap();
+        assert_eq!(hot, ContentType::Unknown);
+        assert_eq!(ObjectHeaders::from_wire(hot, &bytes), Some(headers));
+
+        assert_eq!(
+            ObjectHeaders::from_wire(ContentType::Unknown, &[]),
+            Some(ObjectHeaders::default())
+        );
+    }
+
+    // a record over the limit is refused rather than written with a wrapped
+    // length prefix
+    #[test]
+    fn headers_rejects_oversized() {
+        let mut headers = ObjectHeaders::default();
+        headers.insert_metadata("blob", &"a".repeat(u16::MAX as usize + 1));
+        assert!(headers.encoded_len() > MAX_OBJECT_HEADERS_LEN);
+        assert_eq!(headers.to_bytes(), None);
+        assert_eq!(headers.to_wire(), None);
+
+        let mut headers = ObjectHeaders::default();
+        headers.insert_metadata("blob", "");
+        let room = MAX_OBJECT_HEADERS_LEN - headers.encoded_len();
+        headers.insert_metadata("blob", &"a".repeat(room));
+        assert_eq!(headers.to_bytes().unwrap().len(), MAX_OBJECT_HEADERS_LEN);
+
+        headers.cache_control = Some("a".into());
+        assert_eq!(headers.to_bytes(), None);
+    }
 }
@@ -390,6 +390,31 @@ pub fn compute_path(proof: &[Hash], leaf: Hash, index: u64, height: usize) -> Ve
     computed_path
 }
 
+/// Bring the proof for leaf `index` up to date after another leaf changed.
+///
+/// `changed_path` is the new path of leaf `changed_index` as returned by
+/// `compute_path` for its new value. Only the sibling at the level where the
+/// two paths meet moves, so a batch of updates against one root can be
+/// chained without refetching proofs.
+pub fn advance_proof(
+    proof: &mut [Hash],
+    index: u64,
+    changed_index: u64,
+    changed_path: &[Hash],
+) -> Result<(), MerkleError> {
+    let diverged = index ^ changed_index;
+    if diverged == 0 {
+        return Err(MerkleError::InvalidIndex);
+    }
+
+    let level = (u64::BITS - 1 - diverged.leading_zeros()) as usize;
+    if level >= proof.len() || level >= changed_path.len() {
+        return Err(MerkleError::ProofLength);
+    }
+    proof[level] = changed_path[level];
+    Ok(())
+}
+
 pub fn create_merkle_proof<T: AsRef<[u8]>>(
     leaves: &[T],
     index: usize, 
@@ -465,12 +490,23 @@ pub fn verify_proof(
     proof: &[Hash],
     index: u64,
     height: usize
+) -> bool {
+    verify_leaf_proof(hash_leaf(data), root, proof, index, height)
+}
+
+/// Verify a proof for a leaf that was already hashed with [`hash_leaf`].
+pub fn verify_leaf_proof(
+    leaf: Hash,
+    root: &Hash,
+    proof: &[Hash],
+    index: u64,
+    height: usize
 ) -> bool {
     if proof.len() != height {
         return false;
     }
 
-    let mut node = hash_leaf(data);
+    let mut node = leaf;
     let mut idx = index;
 
     for &sibling in proof.iter() {
@@ -764,6 +800,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn leaf_proof_matches_hashed_leaf() {
+        let leaf = hash_leaf(&[0x5A; 1000]);
+        let leaves = [leaf, hash_leaf(b"other")];
+        let root = root_from_leaf_hashes::<1>(&leaves);
+        let proof = create_proof_from_leaf_hashes::<1>(&leaves, 0).unwrap();
+        assert!(verify_leaf_proof(leaf, &root, &proof, 0, 1));
+        assert!(!verify_leaf_proof(leaf, &root, &proof, 1, 1));
+    }
+
     #[test]
     fn update_leaf_hash_matches_update_leaf() {
         let data = [
@@ -828,6 +874,36 @@ mod tests {
         assert_eq!(raw_tree, hash_tree);
     }
 
+    #[test]
+    fn advance_proof_chains_removals() {
+        let data = [
+            b"hello".to_vec(),
+            b"world".to_vec(),
+            b"data".to_vec(),
+            b"test".to_vec(),
+            b"more".to_vec(),
+        ];
+
+        let mut tree = MerkleTree::<3>::new();
+        for d in &data {
+            tree.add_leaf(d).unwrap();
+        }
+
+        // Both proofs are taken against the same root.
+        let first = tree.create_proof(&data, 1).expect("valid proof");
+        let mut second = tree.create_proof(&data, 4).expect("valid proof");
+
+        let new_path = compute_path(&first, hash_leaf(&[]), 1, 3);
+        tree.remove_leaf(1, &first, &data[1]).unwrap();
+        assert!(!tree.contains(4, &second, &data[4]));
+
+        advance_proof(&mut second, 4, 1, &new_path).unwrap();
+        assert!(tree.contains(4, &second, &data[4]));
+        tree.remove_leaf(4, &second, &data[4]).unwrap();
+
+        assert_eq!(advance_proof(&mut second, 4, 4, &new_path), Err(MerkleError::InvalidIndex));
+    }
+
     // NOTE: This is used for calculating EMPTY_ROOTS.
     #[test]
     fn empty_roots() {
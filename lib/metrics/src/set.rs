@@ -25,6 +25,9 @@ pub struct Metrics {
     pub epoch_transitions_total: IntCounter,
     pub repair_escalations_total: IntCounter,
     pub spool_bytes_total: IntCounterVec,
+    pub scrub_slices_total: IntCounterVec,
+    pub scrub_bytes_total: IntCounter,
+    pub scrub_passes_total: IntCounter,
 
     pub decode_duration: HistogramVec,
     pub decode_output_bytes_total: IntCounter,
@@ -33,6 +36,7 @@ pub struct Metrics {
 
     pub cache_requests_total: IntCounterVec,
     pub cache_evicted_total: IntCounter,
+    pub decoded_cache_total: IntCounterVec,
 
     // Stats endpoint only — intentionally NOT registered, so the HTTP histogram
     // stays the single Prometheus source for request and byte rates.
@@ -91,6 +95,25 @@ impl Metrics {
                 registry
             )
             .expect("register tape_node_spool_bytes_total"),
+            scrub_slices_total: register_int_counter_vec_with_registry!(
+                "tape_node_scrub_slices_total",
+                "Stored slices re-verified by the scrubber, by result",
+                &["result"],
+                registry
+            )
+            .expect("register tape_node_scrub_slices_total"),
+            scrub_bytes_total: register_int_counter_with_registry!(
+                "tape_node_scrub_bytes_total",
+                "Stored slice bytes re-hashed by the scrubber",
+                registry
+            )
+            .expect("register tape_node_scrub_bytes_total"),
+            scrub_passes_total: register_int_counter_with_registry!(
+                "tape_node_scrub_passes_total",
+                "Completed scrub passes over a spool",
+                registry
+            )
+            .expect("register tape_node_scrub_passes_total"),
 
             decode_duration: register_histogram_vec_with_registry!(
                 "tape_gw_decode_duration_seconds",
@@ -134,6 +157,13 @@ impl Metrics {
                 registry
             )
             .expect("register tape_gw_cache_evicted_total"),
+            decoded_cache_total: register_int_counter_vec_with_registry!(
+                "tape_gw_decoded_cache_total",
+                "Decoded chunk cache lookups and read-ahead fills by result",
+                &["result"],
+                registry
+            )
+            .expect("register tape_gw_decoded_cache_total"),
 
             requests_total: IntCounter::new("tape_node_requests_total", "Requests handled")
                 .expect("tape_node_requests_total"),
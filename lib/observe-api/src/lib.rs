@@ -154,8 +154,12 @@ pub const DECODE_SLICE_OUTCOMES: &[&str] = &["used", "rejected_leaf", "rejected_
 /// The slice fetch outcomes that count as wasted work.
 pub const DECODE_SLICES_WASTED: &[&str] = &["rejected_leaf", "rejected_group", "fetch_failed"];
 
-/// All slice cache result labels.
-pub const CACHE_RESULTS: &[&str] = &["hit", "miss", "coalesced"];
+/// All slice cache result labels. `peer` is a miss served by the fleet
+/// gateway owning the slice; `peer_failed` is one that fell back to storage.
+pub const CACHE_RESULTS: &[&str] = &["hit", "miss", "coalesced", "peer", "peer_failed"];
+
+/// All decoded chunk cache result labels. `prefetch` counts read-ahead fills.
+pub const DECODED_CACHE_RESULTS: &[&str] = &["hit", "miss", "coalesced", "prefetch"];
 
 /// All spool pipeline operation labels.
 pub const SPOOL_OPS: &[&str] = &["sync", "repair", "recover"];
@@ -163,6 +167,9 @@ pub const SPOOL_OPS: &[&str] = &["sync", "repair", "recover"];
 /// All spool pipeline stage labels.
 pub const SPOOL_STAGES: &[&str] = &["fetched", "persisted"];
 
+/// All scrub result labels.
+pub const SCRUB_RESULTS: &[&str] = &["verified", "corrupt"];
+
 /// Epoch phase names, in phase-index order.
 pub const EPOCH_PHASES: &[&str] = &["Unknown", "Sync", "Snapshot", "Active", "Closing", "Completed"];
 
@@ -314,6 +321,27 @@ pub struct DecodeStats {
 pub struct CacheStats {
     pub results: Vec<Labeled>,
     pub evicted: u64,
+    /// In-memory decoded chunk cache, including read-ahead fills.
+    #[serde(default)]
+    pub decoded: Vec<Labeled>,
+}
+
+/// Where the scrubber stands in one spool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScrubCursor {
+    pub spool: u64,
+    /// Last track scrubbed in the current pass; none between passes.
+    pub track: Option<String>,
+    pub quarantined: u64,
+}
+
+/// Background slice scrubbing (cumulative counters plus live cursors).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScrubStats {
+    pub results: Vec<Labeled>,
+    pub bytes: u64,
+    pub passes: u64,
+    pub cursors: Vec<ScrubCursor>,
 }
 
 /// Spool pipeline bytes by op and stage (cumulative).
@@ -475,6 +503,8 @@ pub struct Board {
     pub decode: DecodeStats,
     pub cache: CacheStats,
     pub spool: Vec<SpoolStat>,
+    #[serde(default)]
+    pub scrub: ScrubStats,
     pub last_epoch: LastEpoch,
     #[serde(default)]
     pub current_epoch: LastEpoch,
@@ -1,6 +1,9 @@
 //! Merkle tree helpers for blob commitments.
 
+use tape_core::encoding::LeafScheme;
 use tape_core::erasure::{SLICE_TREE_HEIGHT, GROUP_SIZE};
+use tape_core::track::stripe::scheme_leaf;
+use tape_core::types::StripeCount;
 use tape_crypto::Hash;
 use tape_crypto::merkle::MerkleTree;
 
@@ -8,22 +11,33 @@ pub type BlobMerkleTree = MerkleTree<{ SLICE_TREE_HEIGHT }>;
 pub type BlobMerkleRoot = Hash;
 
 /// Build a merkle tree from the slices of an erasure-coded blob.
-/// The tree has SLICE_TREE_HEIGHT levels with GROUP_SIZE leaves.
+/// The tree has SLICE_TREE_HEIGHT levels with GROUP_SIZE leaves, each the
+/// slice's leaf under `scheme` for a blob of `stripe_count` stripes.
 ///
 /// Accepts any slice-like data that can be converted to `&[u8]`.
-pub fn build_blob_merkle_tree<T: AsRef<[u8]>>(slices: &[T]) -> BlobMerkleTree {
+pub fn build_blob_merkle_tree<T: AsRef<[u8]>>(
+    slices: &[T],
+    scheme: LeafScheme,
+    stripe_count: StripeCount,
+) -> BlobMerkleTree {
     assert!(
         slices.len() <= GROUP_SIZE,
         "too many slices for merkle tree"
     );
     let mut tree = BlobMerkleTree::new();
     for s in slices.iter() {
-        tree.add_leaf(s.as_ref()).expect("tree capacity");
+        tree
+            .add_leaf_hash(scheme_leaf(s.as_ref(), scheme, stripe_count))
+            .expect("tree capacity");
     }
     tree
 }
 
 /// Compute the merkle root (commitment hash) for an erasure-coded blob.
-pub fn blob_merkle_root<T: AsRef<[u8]>>(slices: &[T]) -> BlobMerkleRoot {
-    build_blob_merkle_tree(slices).root()
+pub fn blob_merkle_root<T: AsRef<[u8]>>(
+    slices: &[T],
+    scheme: LeafScheme,
+    stripe_count: StripeCount,
+) -> BlobMerkleRoot {
+    build_blob_merkle_tree(slices, scheme, stripe_count).root()
 }
@@ -704,6 +704,8 @@ mod tests {
     #[test]
     fn test_chunk_index_differentiates_commitments() {
         use crate::blob_merkle_root;
+        use tape_core::encoding::LeafScheme;
+        use tape_core::types::StripeCount;
 
         // Encode identical zero data at two different chunk indices
         let zeros = vec![0u8; 1000];
@@ -720,8 +722,8 @@ mod tests {
         slicer_b.set_chunk_index(ChunkNumber(1));
         let slices_b = slicer_b.encode(&zeros).unwrap();
 
-        let root_a = blob_merkle_root(&slices_a);
-        let root_b = blob_merkle_root(&slices_b);
+        let root_a = blob_merkle_root(&slices_a, LeafScheme::Whole, StripeCount(1));
+        let root_b = blob_merkle_root(&slices_b, LeafScheme::Whole, StripeCount(1));
 
         assert_ne!(root_a, root_b, "identical data at different chunk indices must produce different commitments");
     }
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tape_crypto::Hash;
+use tape_crypto::address::Address;
+use tracing::debug;
+
+use super::error::GatewayCacheError;
+use super::inflight::{FetchClaim, InflightFetch, InflightFetches};
+use super::state::{CacheState, CacheStats};
+
+/// A decoded track, addressed by the object it was read for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DecodedChunkKey {
+    pub track_address: Address,
+    /// Manifest index of a stream chunk; `None` for a track read whole.
+    pub chunk: Option<usize>,
+    /// Value hash of the decoded track, so a rewritten track never serves
+    /// the bytes of the one it replaced.
+    pub value_hash: Hash,
+}
+
+impl DecodedChunkKey {
+    pub fn whole(track_address: Address, value_hash: Hash) -> Self {
+        Self {
+            track_address,
+            chunk: None,
+            value_hash,
+        }
+    }
+
+    pub fn chunk(object_address: Address, index: usize, value_hash: Hash) -> Self {
+        Self {
+            track_address: object_address,
+            chunk: Some(index),
+            value_hash,
+        }
+    }
+}
+
+/// Decoded track bytes as they came out of the erasure decoder, before any
+/// stream envelope is opened.
+#[derive(Clone, Debug)]
+pub struct DecodedChunk {
+    pub bytes: Arc<[u8]>,
+    pub etag: Hash,
+}
+
+#[derive(Default)]
+struct DecodedState {
+    chunks: HashMap<DecodedChunkKey, DecodedChunk>,
+    lru: CacheState<DecodedChunkKey>,
+}
+
+/// Bounded in-memory LRU of decoded tracks, in front of the slice cache.
+pub struct DecodedChunkCache {
+    max_bytes: u64,
+    state: Mutex<DecodedState>,
+    inflight: InflightFetches<DecodedChunkKey>,
+}
+
+impl DecodedChunkCache {
+    pub fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            state: Mutex::new(DecodedState::default()),
+            inflight: InflightFetches::default(),
+        }
+    }
+
+    pub fn stats(&self) -> Result<CacheStats, GatewayCacheError> {
+        let state = self.lock_state()?;
+        Ok(CacheStats {
+            entries: state.chunks.len(),
+            bytes: state.lru.total_bytes,
+            inflight: self.inflight.len()?,
+        })
+    }
+
+    pub fn contains(&self, key: DecodedChunkKey) -> bool {
+        self.lock_state()
+            .map(|state| state.chunks.contains_key(&key))
+            .unwrap_or(false)
+    }
+
+    pub async fn get_or_insert_with<F, Fut, E>(
+        &self,
+        key: DecodedChunkKey,
+        fetch: F,
+    ) -> Result<DecodedChunk, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<DecodedChunk, E>>,
+        E: From<GatewayCacheError>,
+    {
+        if self.max_bytes == 0 {
+            return fetch().await;
+        }
+
+        let mut waited = false;
+        loop {
+            if let Some(chunk) = self.get(key).map_err(E::from)? {
+                crate::metrics::inc_decoded_cache(if waited { "coalesced" } else { "hit" });
+                return Ok(chunk);
+            }
+
+            let claim = match self.inflight.join_or_start_fetch(key).map_err(E::from)? {
+                InflightFetch::Wait(wait) => {
+                    waited = true;
+                    wait.await;
+                    continue;
+                }
+                InflightFetch::Fill(claim) => claim,
+            };
+
+            let result = self.fill(key, claim, fetch).await;
+            if result.is_ok() {
+                crate::metrics::inc_decoded_cache("miss");
+            }
+            return result;
+        }
+    }
+
+    /// Decode `key` ahead of a reader. Does nothing when the entry is
+    /// already cached or another caller is filling it; returns whether this
+    /// call did the fill.
+    pub async fn prefetch_with<F, Fut, E>(&self, key: DecodedChunkKey, fetch: F) -> Result<bool, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<DecodedChunk, E>>,
+        E: From<GatewayCacheError>,
+    {
+        if self.max_bytes == 0 || self.contains(key) {
+            return Ok(false);
+        }
+        let InflightFetch::Fill(claim) = self.inflight.join_or_start_fetch(key).map_err(E::from)?
+        else {
+            return Ok(false);
+        };
+        // A fill may have landed between the check and claiming the key.
+        if self.contains(key) {
+            return Ok(false);
+        }
+
+        self.fill(key, claim, fetch).await?;
+        crate::metrics::inc_decoded_cache("prefetch");
+        Ok(true)
+    }
+
+    /// Run the fetch for a key this caller claimed, store the result and
+    /// release the waiters whether or not it succeeded. The claim also
+    /// releases them if this future is dropped mid-fetch.
+    async fn fill<F, Fut, E>(
+        &self,
+        key: DecodedChunkKey,
+        claim: FetchClaim<'_, DecodedChunkKey>,
+        fetch: F,
+    ) -> Result<DecodedChunk, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<DecodedChunk, E>>,
+        E: From<GatewayCacheError>,
+    {
+        let result = fetch().await;
+        let stored = match &result {
+            Ok(chunk) => self.insert(key, chunk.clone()),
+            Err(_) => Ok(()),
+        };
+        drop(claim);
+        stored.map_err(E::from)?;
+        result
+    }
+
+    fn get(&self, key: DecodedChunkKey) -> Result<Option<DecodedChunk>, GatewayCacheError> {
+        let mut state = self.lock_state()?;
+        let Some(chunk) = state.chunks.get(&key).cloned() else {
+            return Ok(None);
+        };
+        state.lru.touch(key, chunk.bytes.len() as u64);
+        Ok(Some(chunk))
+    }
+
+    fn insert(&self, key: DecodedChunkKey, chunk: DecodedChunk) -> Result<(), GatewayCacheError> {
+        let size = chunk.bytes.len() as u64;
+        // Larger than the whole budget: serve it once, keep nothing.
+        if size > self.max_bytes {
+            return Ok(());
+        }
+
+        let mut state = self.lock_state()?;
+        state.lru.upsert(key, size);
+        state.chunks.insert(key, chunk);
+
+        let mut evicted = 0usize;
+        while state.lru.total_bytes > self.max_bytes {
+            let victim = state
+                .lru
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(key, _)| *key);
+            let Some(victim) = victim else {
+                break;
+            };
+            state.lru.remove(victim);
+            state.chunks.remove(&victim);
+            evicted += 1;
+        }
+        if evicted > 0 {
+            debug!(evicted, "gateway decoded cache evicted entries after fill");
+        }
+
+        Ok(())
+    }
+
+    fn lock_state(&self) -> Result<MutexGuard<'_, DecodedState>, GatewayCacheError> {
+        self.state
+            .lock()
+            .map_err(|_| GatewayCacheError::State("decoded cache lock poisoned".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    fn chunk(bytes: &[u8]) -> DecodedChunk {
+        DecodedChunk {
+            bytes: Arc::from(bytes),
+            etag: Hash::default(),
+        }
+    }
+
+    fn key(index: usize) -> DecodedChunkKey {
+        DecodedChunkKey::chunk(Address::default(), index, Hash::default())
+    }
+
+    #[tokio::test]
+    async fn cache_hit_avoids_decode() {
+        let cache = DecodedChunkCache::new(1024);
+        let decodes = AtomicUsize::new(0);
+        for _ in 0..2 {
+            let read = cache
+                .get_or_insert_with(key(0), || async {
+                    decodes.fetch_add(1, Ordering::SeqCst);
+                    Ok::<_, GatewayCacheError>(chunk(&[1, 2, 3]))
+                })
+                .await
+                .unwrap();
+            assert_eq!(&*read.bytes, &[1, 2, 3]);
+        }
+
+        assert_eq!(decodes.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_miss_decodes_once() {
+        let cache = Arc::new(DecodedChunkCache::new(1024));
+        let decodes = Arc::new(AtomicUsize::new(0));
+
+        let mut tasks = Vec::new();
+        for _ in 0..8 {
+            let cache = cache.clone();
+            let decodes = decodes.clone();
+            tasks.push(tokio::spawn(async move {
+                cache
+                    .get_or_insert_with(key(1), || async move {
+                        decodes.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+                        Ok::<_, GatewayCacheError>(chunk(&[4, 5, 6]))
+                    })
+                    .await
+                    .unwrap()
+            }));
+        }
+        for task in tasks {
+            assert_eq!(&*task.await.unwrap().bytes, &[4, 5, 6]);
+        }
+
+        assert_eq!(decodes.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_read_chunk() {
+        let cache = DecodedChunkCache::new(6);
+        for index in 0..2 {
+            cache
+                .get_or_insert_with(key(index), || async {
+                    Ok::<_, GatewayCacheError>(chunk(&[index as u8; 3]))
+                })
+                .await
+                .unwrap();
+        }
+        // Reading chunk 0 again makes chunk 1 the eviction victim.
+        cache
+            .get_or_insert_with(key(0), || async {
+                Err::<DecodedChunk, _>(GatewayCacheError::State("should hit".into()))
+            })
+            .await
+            .unwrap();
+        cache
+            .get_or_insert_with(key(2), || async { Ok::<_, GatewayCacheError>(chunk(&[2; 3])) })
+            .await
+            .unwrap();
+
+        assert!(cache.contains(key(0)));
+        assert!(!cache.contains(key(1)));
+        assert!(cache.contains(key(2)));
+        assert_eq!(cache.stats().unwrap().bytes, 6);
+    }
+
+    #[tokio::test]
+    async fn prefetch_fills_once_and_skips_cached_chunks() {
+        let cache = DecodedChunkCache::new(1024);
+        let fill = || async { Ok::<_, GatewayCacheError>(chunk(&[7, 8])) };
+
+        assert!(cache.prefetch_with(key(3), fill).await.unwrap());
+        assert!(!cache.prefetch_with(key(3), fill).await.unwrap());
+
+        let read = cache
+            .get_or_insert_with(key(3), || async {
+                Err::<DecodedChunk, _>(GatewayCacheError::State("should hit".into()))
+            })
+            .await
+            .unwrap();
+        assert_eq!(&*read.bytes, &[7, 8]);
+    }
+
+    // a reader dropped mid-decode must not strand the readers behind it
+    #[tokio::test]
+    async fn abandoned_fill_releases_waiters() {
+        let cache = Arc::new(DecodedChunkCache::new(1024));
+        let stalled = tokio::spawn({
+            let cache = cache.clone();
+            async move {
+                let decode = std::future::pending::<Result<DecodedChunk, GatewayCacheError>>;
+                cache.get_or_insert_with(key(5), decode).await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let waiter = tokio::spawn({
+            let cache = cache.clone();
+            async move {
+                let decode = || async { Ok::<_, GatewayCacheError>(chunk(&[5])) };
+                cache.get_or_insert_with(key(5), decode).await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        stalled.abort();
+
+        let read = tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("waiter released")
+            .unwrap()
+            .unwrap();
+        assert_eq!(&*read.bytes, &[5]);
+        assert_eq!(cache.stats().unwrap().inflight, 0);
+    }
+
+    #[tokio::test]
+    async fn zero_budget_keeps_nothing() {
+        let cache = DecodedChunkCache::new(0);
+        let read = cache
+            .get_or_insert_with(key(4), || async { Ok::<_, GatewayCacheError>(chunk(&[9])) })
+            .await
+            .unwrap();
+
+        assert_eq!(&*read.bytes, &[9]);
+        assert!(!cache.contains(key(4)));
+        assert!(!cache.prefetch_with(key(4), || async {
+            Ok::<_, GatewayCacheError>(chunk(&[9]))
+        })
+        .await
+        .unwrap());
+    }
+}
@@ -1,35 +1,67 @@
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::sync::{Arc, Mutex, MutexGuard};
 
 use tokio::sync::Notify;
+use tokio::sync::futures::OwnedNotified;
 
 use super::error::GatewayCacheError;
 use super::state::SliceCacheKey;
 
-#[derive(Debug, Default)]
-pub struct InflightFetches {
-    entries: Mutex<HashMap<SliceCacheKey, Arc<Notify>>>,
+/// Singleflight over cache fills: the first caller for a key fetches, the
+/// rest wait on its notify and re-read the cache.
+#[derive(Debug)]
+pub struct InflightFetches<K = SliceCacheKey> {
+    entries: Mutex<HashMap<K, Arc<Notify>>>,
 }
 
-impl InflightFetches {
+impl<K> Default for InflightFetches<K> {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// Outcome of joining a key: wait for the fill in flight, or fill it.
+pub enum InflightFetch<'a, K: Copy + Eq + Hash> {
+    /// Resolves once the current fill is released.
+    Wait(OwnedNotified),
+    /// This caller fills the key.
+    Fill(FetchClaim<'a, K>),
+}
+
+/// The claim on a key's fill. Dropping it releases the key and wakes the
+/// waiters, so a fill abandoned mid-fetch never strands them.
+pub struct FetchClaim<'a, K: Copy + Eq + Hash> {
+    fetches: &'a InflightFetches<K>,
+    key: K,
+}
+
+impl<K: Copy + Eq + Hash> Drop for FetchClaim<'_, K> {
+    fn drop(&mut self) {
+        self.fetches.finish_fetch(self.key);
+    }
+}
+
+impl<K: Copy + Eq + Hash> InflightFetches<K> {
     pub fn len(&self) -> Result<usize, GatewayCacheError> {
         Ok(self.lock()?.len())
     }
 
-    pub fn join_or_start_fetch(
-        &self,
-        key: SliceCacheKey,
-    ) -> Result<Option<Arc<Notify>>, GatewayCacheError> {
+    pub fn join_or_start_fetch(&self, key: K) -> Result<InflightFetch<'_, K>, GatewayCacheError> {
         let mut entries = self.lock()?;
+        // The wait is registered under the lock so a release racing this
+        // call cannot slip past it.
         if let Some(wait) = entries.get(&key) {
-            return Ok(Some(wait.clone()));
+            return Ok(InflightFetch::Wait(wait.clone().notified_owned()));
         }
 
         entries.insert(key, Arc::new(Notify::new()));
-        Ok(None)
+        Ok(InflightFetch::Fill(FetchClaim { fetches: self, key }))
     }
 
-    pub fn finish_fetch(&self, key: SliceCacheKey) {
+    fn finish_fetch(&self, key: K) {
         let notify = self.lock().ok().and_then(|mut entries| entries.remove(&key));
 
         if let Some(notify) = notify {
@@ -39,7 +71,7 @@ impl InflightFetches {
 
     fn lock(
         &self,
-    ) -> Result<MutexGuard<'_, HashMap<SliceCacheKey, Arc<Notify>>>, GatewayCacheError> {
+    ) -> Result<MutexGuard<'_, HashMap<K, Arc<Notify>>>, GatewayCacheError> {
         self.entries
             .lock()
             .map_err(|_| GatewayCacheError::State("in-flight cache lock poisoned".into()))
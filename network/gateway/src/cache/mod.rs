@@ -1,8 +1,10 @@
+mod decoded;
 mod error;
 mod inflight;
 mod slice;
 mod state;
 
+pub use decoded::{DecodedChunk, DecodedChunkCache, DecodedChunkKey};
 pub use error::GatewayCacheError;
 pub use slice::GatewaySliceCache;
 pub use state::{CacheRead, CacheSource, CacheStats, SliceCacheKey};
@@ -14,7 +14,7 @@ use tape_store::types::{SliceKey, SliceValue};
 use tracing::{debug, warn};
 
 use super::error::GatewayCacheError;
-use super::inflight::InflightFetches;
+use super::inflight::{InflightFetch, InflightFetches};
 use super::state::{CacheRead, CacheSource, CacheState, CacheStats, SliceCacheKey};
 
 pub struct GatewaySliceCache<Db: Store> {
@@ -56,6 +56,19 @@ impl<Db: Store> GatewaySliceCache<Db> {
         })
     }
 
+    /// A cached slice, without fetching on a miss.
+    pub fn get(
+        &self,
+        spool_id: SpoolIndex,
+        track_address: Address,
+    ) -> Result<Option<Vec<u8>>, GatewayCacheError> {
+        let data = self.get_cached(SliceCacheKey::new(spool_id, track_address))?;
+        if data.is_some() {
+            crate::metrics::inc_cache("hit");
+        }
+        Ok(data)
+    }
+
     pub async fn get_or_insert_with<F, Fut, E>(
         &self,
         spool_id: SpoolIndex,
@@ -80,11 +93,14 @@ impl<Db: Store> GatewaySliceCache<Db> {
                 });
             }
 
-            if let Some(wait) = self.inflight.join_or_start_fetch(key).map_err(E::from)? {
-                waited = true;
-                wait.notified().await;
-                continue;
-            }
+            let claim = match self.inflight.join_or_start_fetch(key).map_err(E::from)? {
+                InflightFetch::Wait(wait) => {
+                    waited = true;
+                    wait.await;
+                    continue;
+                }
+                InflightFetch::Fill(claim) => claim,
+            };
 
             let result = match fetch.take() {
                 Some(fetch) => {
@@ -103,7 +119,7 @@ impl<Db: Store> GatewaySliceCache<Db> {
                 None => Err(GatewayCacheError::State("missing cache fetcher".into()).into()),
             };
 
-            self.inflight.finish_fetch(key);
+            drop(claim);
             if result.is_ok() {
                 crate::metrics::inc_cache("miss");
             }
@@ -277,6 +293,7 @@ mod tests {
             max_bytes,
             eviction_batch: 16,
             reclaim_after_deleted_slices: 0,
+            ..GatewayCacheConfig::default()
         }
     }
 
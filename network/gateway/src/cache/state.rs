@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::hash::Hash;
 
 use tape_core::types::SpoolIndex;
 use tape_crypto::address::Address;
@@ -29,6 +30,8 @@ impl From<SliceKey> for SliceCacheKey {
 pub enum CacheSource {
     Hit,
     Miss,
+    /// Served by the fleet gateway that owns the slice; not cached here.
+    Peer,
 }
 
 #[derive(Debug)]
@@ -50,20 +53,30 @@ pub struct CacheEntry {
     pub last_access: u64,
 }
 
-#[derive(Debug, Default)]
-pub struct CacheState {
-    pub entries: HashMap<SliceCacheKey, CacheEntry>,
+#[derive(Debug)]
+pub struct CacheState<K = SliceCacheKey> {
+    pub entries: HashMap<K, CacheEntry>,
     pub total_bytes: u64,
     clock: u64,
 }
 
-impl CacheState {
+impl<K> Default for CacheState<K> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            total_bytes: 0,
+            clock: 0,
+        }
+    }
+}
+
+impl<K: Copy + Eq + Hash> CacheState<K> {
     fn next_access(&mut self) -> u64 {
         self.clock = self.clock.saturating_add(1);
         self.clock
     }
 
-    pub fn upsert(&mut self, key: SliceCacheKey, size: u64) {
+    pub fn upsert(&mut self, key: K, size: u64) {
         let last_access = self.next_access();
         if let Some(previous) = self.entries.insert(key, CacheEntry { size, last_access }) {
             self.total_bytes = self.total_bytes.saturating_sub(previous.size);
@@ -71,7 +84,7 @@ impl CacheState {
         self.total_bytes = self.total_bytes.saturating_add(size);
     }
 
-    pub fn touch(&mut self, key: SliceCacheKey, size: u64) {
+    pub fn touch(&mut self, key: K, size: u64) {
         let last_access = self.next_access();
         match self.entries.get_mut(&key) {
             Some(entry) => {
@@ -89,7 +102,7 @@ impl CacheState {
         }
     }
 
-    pub fn remove(&mut self, key: SliceCacheKey) -> Option<CacheEntry> {
+    pub fn remove(&mut self, key: K) -> Option<CacheEntry> {
         let removed = self.entries.remove(&key)?;
         self.total_bytes = self.total_bytes.saturating_sub(removed.size);
         Some(removed)
@@ -0,0 +1,252 @@
+//! Cooperative slice caching across a fleet of gateways.
+//!
+//! Every member lists the same peers, so rendezvous hashing over a
+//! [`SliceCacheKey`] picks the same owner on every gateway. A miss for a
+//! slice owned elsewhere is asked of the owner, which answers from its own
+//! cache or fetches the slice once from the storage nodes; the asking
+//! gateway keeps no copy. A peer that fails is skipped for a while, and the
+//! next-ranked live member owns its slices until it is back.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use peer_http::GatewayApi;
+use tape_core::types::SpoolIndex;
+use tape_crypto::Hash;
+use tape_crypto::address::Address;
+use tape_crypto::hash::hashv;
+use tape_node::config::gateway::GatewayFleetConfig;
+use tape_protocol::Api;
+use tape_protocol::api::{ApiError, GetSliceReq};
+use tracing::{info, warn};
+
+use crate::cache::SliceCacheKey;
+
+/// Marks a slice request forwarded by a fleet peer. The receiver serves it
+/// from its own cache or the storage nodes and never forwards it again.
+pub const FLEET_PEER_HEADER: &str = "x-tape-fleet-peer";
+
+pub struct GatewayFleet {
+    members: Vec<FleetMember>,
+    down_for: Duration,
+}
+
+struct FleetMember {
+    url: String,
+    /// `None` for this gateway.
+    peer: Option<FleetPeer>,
+}
+
+pub struct FleetPeer {
+    url: String,
+    api: GatewayApi,
+    down_until: Mutex<Option<Instant>>,
+}
+
+impl GatewayFleet {
+    /// A fleet of one: every slice is owned locally.
+    pub fn solo() -> Self {
+        Self {
+            members: Vec::new(),
+            down_for: Duration::ZERO,
+        }
+    }
+
+    pub fn new(config: &GatewayFleetConfig) -> Result<Self, ApiError> {
+        let Some(self_url) = config.self_url.as_deref().map(normalize_url) else {
+            return Ok(Self::solo());
+        };
+        if config.peers.is_empty() {
+            return Ok(Self::solo());
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(FLEET_PEER_HEADER, HeaderValue::from_static("1"));
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.peer_timeout_ms))
+            .default_headers(headers)
+            .build()
+            .map_err(|error| ApiError::Other(format!("fleet client build: {error}")))?;
+
+        let mut members = Vec::with_capacity(config.peers.len());
+        for url in &config.peers {
+            let url = normalize_url(url);
+            if members.iter().any(|member: &FleetMember| member.url == url) {
+                continue;
+            }
+            let peer = if url == self_url {
+                None
+            } else {
+                Some(FleetPeer {
+                    api: GatewayApi::with_client(url.clone(), client.clone())?,
+                    url: url.clone(),
+                    down_until: Mutex::new(None),
+                })
+            };
+            members.push(FleetMember { url, peer });
+        }
+
+        info!(self_url = %self_url, members = members.len(), "gateway fleet configured");
+        Ok(Self {
+            members,
+            down_for: Duration::from_secs(config.peer_down_secs),
+        })
+    }
+
+    pub fn is_solo(&self) -> bool {
+        self.members.iter().all(|member| member.peer.is_none())
+    }
+
+    /// The peer that owns `key`, or `None` when this gateway does. Members
+    /// marked down are passed over for the next-highest score.
+    pub fn owner_of(&self, key: SliceCacheKey) -> Option<&FleetPeer> {
+        let mut ranked: Vec<(Hash, &FleetMember)> = self
+            .members
+            .iter()
+            .map(|member| (score(&member.url, key), member))
+            .collect();
+        ranked.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+
+        let now = Instant::now();
+        for (_, member) in ranked {
+            match &member.peer {
+                None => return None,
+                Some(peer) if peer.is_down(now) => continue,
+                Some(peer) => return Some(peer),
+            }
+        }
+        None
+    }
+
+    /// Ask `peer` for a slice. Failures that say the peer itself is
+    /// unhealthy mark it down; a 404 or other refusal does not.
+    pub async fn get_slice(
+        &self,
+        peer: &FleetPeer,
+        track: Address,
+        spool: SpoolIndex,
+    ) -> Result<Vec<u8>, ApiError> {
+        let request = GetSliceReq { track, spool };
+        // The node argument is ignored by the gateway client.
+        match peer.api.get_slice(Address::default(), &request).await {
+            Ok(response) => Ok(response.data),
+            Err(error) => {
+                if peer_unhealthy(&error) {
+                    warn!(peer = %peer.url, %error, "fleet peer marked down");
+                    peer.mark_down(self.down_for);
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+impl FleetPeer {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn is_down(&self, now: Instant) -> bool {
+        match self.down_until.lock() {
+            Ok(until) => until.is_some_and(|until| now < until),
+            Err(_) => false,
+        }
+    }
+
+    fn mark_down(&self, down_for: Duration) {
+        if let Ok(mut until) = self.down_until.lock() {
+            *until = Some(Instant::now() + down_for);
+        }
+    }
+}
+
+fn score(url: &str, key: SliceCacheKey) -> Hash {
+    hashv(&[
+        url.as_bytes(),
+        &key.spool_id.0.to_le_bytes(),
+        key.track_address.as_ref(),
+    ])
+}
+
+fn normalize_url(url: &str) -> String {
+    url.trim_end_matches('/').to_string()
+}
+
+fn peer_unhealthy(error: &ApiError) -> bool {
+    match error {
+        ApiError::ConnectionFailed(_) | ApiError::Timeout | ApiError::Other(_) => true,
+        // A 502 is the peer reporting a storage node failure, not its own.
+        ApiError::ServerError { status, .. } => *status >= 500 && *status != 502,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(self_url: &str) -> GatewayFleetConfig {
+        GatewayFleetConfig {
+            self_url: Some(self_url.into()),
+            peers: vec![
+                "http://gw-a:3430".into(),
+                "http://gw-b:3430/".into(),
+                "http://gw-c:3430".into(),
+            ],
+            ..GatewayFleetConfig::default()
+        }
+    }
+
+    fn owner_url(fleet: &GatewayFleet, self_url: &str, key: SliceCacheKey) -> String {
+        fleet
+            .owner_of(key)
+            .map(|peer| peer.url().to_string())
+            .unwrap_or_else(|| self_url.to_string())
+    }
+
+    #[test]
+    fn members_agree_on_owners() {
+        let urls = ["http://gw-a:3430", "http://gw-b:3430", "http://gw-c:3430"];
+        let fleets: Vec<GatewayFleet> =
+            urls.iter().map(|url| GatewayFleet::new(&config(url)).unwrap()).collect();
+
+        let mut owned = [0usize; 3];
+        for spool in 0..64u64 {
+            let key = SliceCacheKey::new(SpoolIndex(spool), Address::default());
+            let owners: Vec<String> = fleets
+                .iter()
+                .zip(urls)
+                .map(|(fleet, url)| owner_url(fleet, url, key))
+                .collect();
+            assert!(owners.iter().all(|owner| owner == &owners[0]));
+            owned[urls.iter().position(|url| *url == owners[0]).unwrap()] += 1;
+        }
+
+        assert!(owned.iter().all(|count| *count > 0), "{owned:?}");
+    }
+
+    #[test]
+    fn down_peer_hands_its_slices_to_the_next_member() {
+        let fleet = GatewayFleet::new(&config("http://gw-a:3430")).unwrap();
+        let key = (0..64u64)
+            .map(|spool| SliceCacheKey::new(SpoolIndex(spool), Address::default()))
+            .find(|key| fleet.owner_of(*key).is_some())
+            .unwrap();
+
+        let peer = fleet.owner_of(key).unwrap();
+        peer.mark_down(Duration::from_secs(60));
+        let url = peer.url().to_string();
+
+        assert_ne!(owner_url(&fleet, "http://gw-a:3430", key), url);
+    }
+
+    #[test]
+    fn solo_owns_everything() {
+        let fleet = GatewayFleet::new(&GatewayFleetConfig::default()).unwrap();
+        let key = SliceCacheKey::new(SpoolIndex(1), Address::default());
+
+        assert!(fleet.is_solo());
+        assert!(fleet.owner_of(key).is_none());
+    }
+}
@@ -10,11 +10,11 @@ use tape_core::types::SpoolIndex;
 use tape_crypto::Hash;
 use tape_crypto::address::Address;
 use tape_crypto::hash::hash;
-use tape_crypto::merkle::hash_leaf;
 use tape_protocol::Api;
 use tape_sdk::codec::decoder::BlobDecoder;
 use tracing::{debug, warn};
 
+use crate::cache::{DecodedChunk, DecodedChunkKey};
 use crate::http::error::RouteError;
 use crate::http::handlers::track::slice::read_cached_slice;
 use crate::http::handlers::track::track_data_with_pending;
@@ -25,6 +25,37 @@ pub struct DecodedObject {
     pub etag: Hash,
 }
 
+/// [`decode_track_bytes`] through the in-memory decoded cache, so repeat
+/// reads of a popular track skip the slice fetches and the erasure decode.
+pub async fn decode_track_cached<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    key: DecodedChunkKey,
+    track_addr: Address,
+    track: CompressedTrack,
+) -> Result<DecodedObject, RouteError> {
+    let chunk = state
+        .decoded_cache
+        .get_or_insert_with(key, || decode_for_cache(state, track_addr, track))
+        .await?;
+    Ok(DecodedObject {
+        bytes: chunk.bytes.to_vec(),
+        etag: chunk.etag,
+    })
+}
+
+/// Decode a track into the shape the decoded cache keeps.
+pub async fn decode_for_cache<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_addr: Address,
+    track: CompressedTrack,
+) -> Result<DecodedChunk, RouteError> {
+    let decoded = decode_track_bytes(state, track_addr, track).await?;
+    Ok(DecodedChunk {
+        bytes: decoded.bytes.into(),
+        etag: decoded.etag,
+    })
+}
+
 pub async fn decode_track_bytes<
     Db: Store,
     Cluster: Api,
@@ -126,7 +157,7 @@ async fn fetch_decoding_slices<Db: Store, Cluster: Api, Blockchain: Rpc>(
                     warn!(spool = %spool_id, track = %track_addr, "gateway skipped slice outside track group");
                     continue;
                 };
-                if position >= GROUP_SIZE || hash_leaf(&data) != blob.leaves[position] {
+                if !blob.verify_slice(SpoolIndex(position as u64), &data) {
                     rejected_leaf += 1;
                     warn!(spool = %spool_id, track = %track_addr, "gateway skipped slice with mismatched leaf hash");
                     continue;
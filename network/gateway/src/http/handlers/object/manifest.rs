@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use axum::body::{Body, Bytes};
 use axum::response::{IntoResponse, Response};
 use futures::Stream;
@@ -8,10 +10,13 @@ use tape_core::track::types::CompressedTrack;
 use tape_crypto::Hash;
 use tape_crypto::address::Address;
 use tape_protocol::Api;
+use tape_sdk::stream::envelope::{ChunkCipher, KeyProvider};
 use tape_sdk::stream::manifest::ChunkManifest;
+use tracing::debug;
 
-use super::decode::decode_track_bytes;
+use super::decode::{decode_for_cache, decode_track_cached};
 use super::response::{ByteRange, ObjectResponseMetadata, ranged_object_headers};
+use crate::cache::DecodedChunkKey;
 use crate::http::error::RouteError;
 use crate::http::handlers::track::track_with_pending;
 use crate::http::state::AppState;
@@ -62,7 +67,6 @@ struct StreamChunk {
     index: usize,
     track_addr: Address,
     track: CompressedTrack,
-    expected_size: u64,
     skip: u64,
     take: u64,
 }
@@ -77,21 +81,11 @@ fn resolve_planned_chunks<Db: Store, Cluster: Api, Blockchain: Rpc>(
 ) -> Result<Vec<StreamChunk>, RouteError> {
     let mut chunks = Vec::with_capacity(plan.len());
     for planned in plan {
-        let entry = &manifest.chunks[planned.index];
-        let chunk_addr = track_pda(tape, entry.track_number).0.into();
-        let chunk = track_with_pending(state, chunk_addr)?.ok_or(RouteError::NotFound)?;
-        if !chunk.is_certified() {
-            return Err(RouteError::BadGateway(format!(
-                "manifest chunk {} is not certified",
-                planned.index
-            )));
-        }
-
+        let (chunk_addr, chunk) = resolve_chunk_track(state, tape, manifest, planned.index)?;
         chunks.push(StreamChunk {
             index: planned.index,
             track_addr: chunk_addr,
             track: chunk,
-            expected_size: planned.decoded_size,
             skip: planned.skip,
             take: planned.take,
         });
@@ -100,30 +94,103 @@ fn resolve_planned_chunks<Db: Store, Cluster: Api, Blockchain: Rpc>(
     Ok(chunks)
 }
 
+/// The certified track holding manifest chunk `index`.
+fn resolve_chunk_track<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    tape: Address,
+    manifest: &ChunkManifest,
+    index: usize,
+) -> Result<(Address, CompressedTrack), RouteError> {
+    let entry = &manifest.chunks[index];
+    let chunk_addr = track_pda(tape, entry.track_number).0.into();
+    let chunk = track_with_pending(state, chunk_addr)?.ok_or(RouteError::NotFound)?;
+    if !chunk.is_certified() {
+        return Err(RouteError::BadGateway(format!(
+            "manifest chunk {index} is not certified"
+        )));
+    }
+    Ok((chunk_addr, chunk))
+}
+
+/// The cipher for a sealed stream's chunks, unwrapped with `key_provider`;
+/// `None` for a plaintext stream, which refuses a key.
+pub fn stream_cipher(
+    manifest: &ChunkManifest,
+    key_provider: Option<&dyn KeyProvider>,
+) -> Result<Option<Arc<ChunkCipher>>, RouteError> {
+    let Some(envelope) = &manifest.encryption else {
+        if key_provider.is_some() {
+            return Err(plaintext_with_key());
+        }
+        return Ok(None);
+    };
+    let key_provider = key_provider.ok_or_else(|| {
+        RouteError::BadRequest("object is encrypted; the request must supply its key".into())
+    })?;
+    let cipher = envelope
+        .open(key_provider)
+        .map_err(|_| RouteError::Forbidden("the supplied key does not open the object".into()))?;
+    Ok(Some(Arc::new(cipher)))
+}
+
+/// The error for a key supplied to read an object that is not encrypted.
+pub fn plaintext_with_key() -> RouteError {
+    RouteError::BadRequest("object is not encrypted; the request must not supply a key".into())
+}
+
 pub fn object_stream_response<Db, Cluster, Blockchain>(
     state: AppState<Db, Cluster, Blockchain>,
     tape: Address,
-    manifest: &ChunkManifest,
+    object: Address,
+    manifest: Arc<ChunkManifest>,
     plan: &[PlannedChunk],
     metadata: ObjectResponseMetadata,
     etag: Hash,
     total_size: u64,
     range: Option<ByteRange>,
+    key_provider: Option<&dyn KeyProvider>,
 ) -> Result<Response, RouteError>
 where
     Db: Store + 'static,
     Cluster: Api + 'static,
     Blockchain: Rpc + 'static,
 {
-    let chunks = resolve_planned_chunks(&state, tape, manifest, plan)?;
+    let cipher = stream_cipher(&manifest, key_provider)?;
+    let chunks = resolve_planned_chunks(&state, tape, &manifest, plan)?;
     let (status, headers) = ranged_object_headers(range, total_size, &metadata, etag)?;
-    let body = Body::from_stream(manifest_chunk_stream(state, chunks));
+    let stream = manifest_chunk_stream(state, tape, object, manifest, chunks, cipher);
+    let body = Body::from_stream(stream);
     Ok((status, headers, body).into_response())
 }
 
+/// The bytes of `range` within a stream, decoded chunk by chunk, for server-side
+/// copies that feed the write path rather than a response.
+pub fn stream_range_bytes<Db, Cluster, Blockchain>(
+    state: AppState<Db, Cluster, Blockchain>,
+    tape: Address,
+    object: Address,
+    manifest: Arc<ChunkManifest>,
+    range: ByteRange,
+    key_provider: Option<&dyn KeyProvider>,
+) -> Result<impl Stream<Item = Result<Bytes, RouteError>> + Send + 'static, RouteError>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let cipher = stream_cipher(&manifest, key_provider)?;
+    let plan = chunk_range_plan(&manifest, range);
+    let chunks = resolve_planned_chunks(&state, tape, &manifest, &plan)?;
+    Ok(manifest_chunk_stream(state, tape, object, manifest, chunks, cipher))
+}
+
 fn manifest_chunk_stream<Db, Cluster, Blockchain>(
     state: AppState<Db, Cluster, Blockchain>,
+    tape: Address,
+    object: Address,
+    manifest: Arc<ChunkManifest>,
     chunks: Vec<StreamChunk>,
+    cipher: Option<Arc<ChunkCipher>>,
 ) -> impl Stream<Item = Result<Bytes, RouteError>> + Send + 'static
 where
     Db: Store + 'static,
@@ -132,8 +199,13 @@ where
 {
     futures::stream::try_unfold(
         ObjectStreamState {
+            read_ahead: state.context.config.gateway.cache.prefetch_chunks,
             state,
+            tape,
+            object,
+            manifest,
             chunks,
+            cipher,
             next: 0,
         },
         |mut stream| async move {
@@ -142,24 +214,27 @@ where
             };
             stream.next += 1;
 
-            // The whole chunk decodes and verifies against the manifest size
-            // before any slicing; a ranged read changes what is sent, never
-            // what is checked.
-            let decoded = decode_track_bytes(&stream.state, chunk.track_addr, chunk.track).await?;
-            if decoded.bytes.len() as u64 != chunk.expected_size {
-                return Err(RouteError::BadGateway(format!(
-                    "manifest chunk {} size mismatch",
-                    chunk.index
-                )));
-            }
-
+            // The whole chunk decodes, authenticates when sealed, expands when
+            // compressed and verifies against the manifest size before any
+            // slicing; a ranged read changes what is sent, never what is
+            // checked, and only the chunks it touches are ever expanded.
+            spawn_read_ahead(&stream, chunk.index);
+            let key = DecodedChunkKey::chunk(stream.object, chunk.index, chunk.track.value_hash);
+            let decoded =
+                decode_track_cached(&stream.state, key, chunk.track_addr, chunk.track).await?;
             stream
                 .state
                 .context
                 .metrics
                 .add_downloaded(decoded.bytes.len() as u64);
 
-            let bytes = Bytes::from(decoded.bytes)
+            let opened = stream
+                .manifest
+                .open_chunk(chunk.index, decoded.bytes, stream.cipher.as_deref())
+                .map_err(|error| {
+                    RouteError::BadGateway(format!("manifest chunk {}: {error}", chunk.index))
+                })?;
+            let bytes = Bytes::from(opened)
                 .slice(chunk.skip as usize..(chunk.skip + chunk.take) as usize);
             Ok(Some((bytes, stream)))
         },
@@ -168,8 +243,64 @@ where
 
 struct ObjectStreamState<Db: Store, Cluster: Api, Blockchain: Rpc> {
     state: AppState<Db, Cluster, Blockchain>,
+    tape: Address,
+    object: Address,
+    manifest: Arc<ChunkManifest>,
     chunks: Vec<StreamChunk>,
+    cipher: Option<Arc<ChunkCipher>>,
     next: usize,
+    /// Manifest chunks to decode ahead of a sequential reader.
+    read_ahead: usize,
+}
+
+/// Decode the `read_ahead` manifest chunks after `index` in the background
+/// when the reader looks sequential: it asked for more than one chunk, or
+/// the chunk before this one is already cached from an earlier request.
+fn spawn_read_ahead<Db, Cluster, Blockchain>(
+    stream: &ObjectStreamState<Db, Cluster, Blockchain>,
+    index: usize,
+) where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    if stream.read_ahead == 0 {
+        return;
+    }
+
+    let cached = |index: usize| {
+        resolve_chunk_track(&stream.state, stream.tape, &stream.manifest, index)
+            .map(|(_, track)| DecodedChunkKey::chunk(stream.object, index, track.value_hash))
+            .is_ok_and(|key| stream.state.decoded_cache.contains(key))
+    };
+    let sequential = stream.chunks.len() > 1 || index.checked_sub(1).is_some_and(cached);
+    if !sequential {
+        return;
+    }
+
+    let last = (index + stream.read_ahead).min(stream.manifest.chunks.len().saturating_sub(1));
+    for ahead in index + 1..=last {
+        let Ok((track_addr, track)) =
+            resolve_chunk_track(&stream.state, stream.tape, &stream.manifest, ahead)
+        else {
+            continue;
+        };
+        let key = DecodedChunkKey::chunk(stream.object, ahead, track.value_hash);
+        if stream.state.decoded_cache.contains(key) {
+            continue;
+        }
+
+        let state = stream.state.clone();
+        tokio::spawn(async move {
+            let fill = state
+                .decoded_cache
+                .prefetch_with(key, || decode_for_cache(&state, track_addr, track))
+                .await;
+            if let Err(error) = fill {
+                debug!(chunk = ahead, %error, "gateway chunk read-ahead failed");
+            }
+        });
+    }
 }
 
 #[cfg(test)]
@@ -198,6 +329,8 @@ mod tests {
                     size: StorageUnits::from_bytes(size),
                 })
                 .collect(),
+            encryption: None,
+            compression: None,
         }
     }
 
@@ -3,10 +3,13 @@ mod manifest;
 mod response;
 mod routes;
 
-pub use response::ObjectResponseMetadata;
+pub use decode::DecodedObject;
+pub use manifest::stream_range_bytes;
+pub use response::{ByteRange, ObjectResponseMetadata, USER_METADATA_PREFIX};
 pub use response::{
     range_header, ranged_object_headers, resolve_range,
 };
 pub use routes::{
-    OBJECT_PATH, TRACK_BYTES_PATH, get_object, get_track_bytes, read_object_response,
+    OBJECT_PATH, OpenedObject, TRACK_BYTES_PATH, check_object_key, get_object, get_track_bytes,
+    open_object, read_object_response,
 };
@@ -1,9 +1,9 @@
 use axum::body::Bytes;
-use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header};
 use axum::response::{IntoResponse, Response};
 use rpc::Rpc;
 use store::Store;
-use tape_core::types::ContentType;
+use tape_core::types::{ContentType, ObjectHeaders};
 use tape_crypto::Hash;
 use tape_crypto::address::Address;
 use tape_protocol::Api;
@@ -17,8 +17,15 @@ use crate::http::state::AppState;
 pub struct ObjectResponseMetadata {
     pub content_type: ContentType,
     pub filename: Option<Vec<u8>>,
+    pub headers: ObjectHeaders,
 }
 
+/// Prefix of the response headers carrying user metadata.
+pub const USER_METADATA_PREFIX: &str = "x-amz-meta-";
+
+/// Cache-Control served when the object does not set its own.
+const DEFAULT_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
 pub fn object_response_metadata<Db: Store, Cluster: Api, Blockchain: Rpc>(
     state: &AppState<Db, Cluster, Blockchain>,
     track_addr: Address,
@@ -29,16 +36,18 @@ pub fn object_response_metadata<Db: Store, Cluster: Api, Blockchain: Rpc>(
         .get_object_metadata(track_addr)
         .map_err(store_error)?;
 
-    let content_type = metadata
-        .as_ref()
-        .map(|metadata| metadata.content_type)
-        .unwrap_or(ContentType::Unknown);
-    let filename = metadata
-        .and_then(|metadata| (!metadata.name.is_empty()).then_some(metadata.name));
+    let Some(metadata) = metadata else {
+        return Ok(ObjectResponseMetadata {
+            content_type: ContentType::Unknown,
+            filename: None,
+            headers: ObjectHeaders::default(),
+        });
+    };
 
     Ok(ObjectResponseMetadata {
-        content_type,
-        filename,
+        content_type: metadata.content_type,
+        filename: (!metadata.name.is_empty()).then_some(metadata.name),
+        headers: metadata.headers,
     })
 }
 
@@ -195,7 +204,7 @@ pub fn object_headers(
     let mut headers = HeaderMap::new();
     headers.insert(
         header::CONTENT_TYPE,
-        HeaderValue::from_static(metadata.content_type.to_str()),
+        header_value(metadata.headers.mime(metadata.content_type), "content type")?,
     );
     headers.insert(
         header::CONTENT_LENGTH,
@@ -209,21 +218,47 @@ pub fn object_headers(
     );
     headers.insert(
         header::CACHE_CONTROL,
-        HeaderValue::from_static("public, max-age=31536000, immutable"),
+        header_value(
+            metadata
+                .headers
+                .cache_control
+                .as_deref()
+                .unwrap_or(DEFAULT_CACHE_CONTROL),
+            "cache control",
+        )?,
     );
     headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
-    if let Some(filename) = metadata.filename.as_deref() {
+    if let Some(encoding) = metadata.headers.content_encoding.as_deref() {
+        headers.insert(header::CONTENT_ENCODING, header_value(encoding, "content encoding")?);
+    }
+
+    // A stored disposition wins over the one derived from the object name.
+    let disposition = match (&metadata.headers.content_disposition, &metadata.filename) {
+        (Some(disposition), _) => Some(disposition.clone()),
+        (None, Some(filename)) => Some(content_disposition(filename)),
+        (None, None) => None,
+    };
+    if let Some(disposition) = disposition {
         headers.insert(
             header::CONTENT_DISPOSITION,
-            HeaderValue::from_str(&content_disposition(filename)).map_err(|error| {
-                RouteError::Internal(format!("content disposition header: {error}"))
-            })?,
+            header_value(&disposition, "content disposition")?,
         );
     }
 
+    for entry in &metadata.headers.user_metadata {
+        let name = HeaderName::from_bytes(format!("{USER_METADATA_PREFIX}{}", entry.key).as_bytes())
+            .map_err(|error| RouteError::Internal(format!("user metadata header: {error}")))?;
+        headers.insert(name, header_value(&entry.value, "user metadata")?);
+    }
+
     Ok(headers)
 }
 
+fn header_value(value: &str, label: &str) -> Result<HeaderValue, RouteError> {
+    HeaderValue::from_str(value)
+        .map_err(|error| RouteError::Internal(format!("{label} header: {error}")))
+}
+
 fn content_disposition(filename: &[u8]) -> String {
     format!("attachment; filename*=UTF-8''{}", encode_rfc5987(filename))
 }
@@ -262,7 +297,7 @@ fn encode_rfc5987(value: &[u8]) -> String {
 #[cfg(test)]
 mod tests {
     use axum::http::header;
-    use tape_core::types::ContentType;
+    use tape_core::types::{ContentType, ObjectHeaders};
     use tape_crypto::Hash;
 
     use super::{ObjectResponseMetadata, object_headers};
@@ -272,6 +307,7 @@ mod tests {
         let metadata = ObjectResponseMetadata {
             content_type: ContentType::TextPlain,
             filename: Some(b"reports/june final.txt".to_vec()),
+            headers: ObjectHeaders::default(),
         };
 
         let headers = object_headers(42, &metadata, Hash::default()).unwrap();
@@ -289,6 +325,7 @@ mod tests {
         let metadata = ObjectResponseMetadata {
             content_type: ContentType::Unknown,
             filename: None,
+            headers: ObjectHeaders::default(),
         };
 
         let headers = object_headers(42, &metadata, Hash::default()).unwrap();
@@ -296,6 +333,29 @@ mod tests {
         assert!(headers.get(header::CONTENT_DISPOSITION).is_none());
     }
 
+    #[test]
+    fn object_headers_serve_stored_headers() {
+        let mut stored = ObjectHeaders::with_content_type("application/x-parquet");
+        stored.content_encoding = Some("gzip".into());
+        stored.content_disposition = Some("inline".into());
+        stored.cache_control = Some("no-cache".into());
+        stored.insert_metadata("Owner", "data-lake");
+        let metadata = ObjectResponseMetadata {
+            content_type: ContentType::Unknown,
+            filename: Some(b"part-0.parquet".to_vec()),
+            headers: stored,
+        };
+
+        let headers = object_headers(42, &metadata, Hash::default()).unwrap();
+        let get = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+        assert_eq!(get("content-type"), Some("application/x-parquet"));
+        assert_eq!(get("content-encoding"), Some("gzip"));
+        assert_eq!(get("content-disposition"), Some("inline"));
+        assert_eq!(get("cache-control"), Some("no-cache"));
+        assert_eq!(get("x-amz-meta-owner"), Some("data-lake"));
+    }
+
     #[test]
     fn parse_range_variants() {
         use super::{ByteRange, RangeOutcome, parse_range};
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use axum::Extension;
@@ -7,18 +8,24 @@ use axum::response::Response;
 use rpc::Rpc;
 use store::Store;
 use tape_core::track::types::CompressedTrack;
+use tape_crypto::Hash;
 use tape_crypto::address::Address;
 use tape_protocol::Api;
+use tape_sdk::stream::envelope::KeyProvider;
 use tape_sdk::stream::manifest::ChunkManifest;
 
-use super::decode::decode_track_bytes;
-use super::manifest::{chunk_range_plan, object_stream_response};
+use super::decode::{DecodedObject, decode_track_cached};
+use super::manifest::{
+    chunk_range_plan, object_stream_response, plaintext_with_key, stream_cipher,
+};
 use super::response::{
     ByteRange, ObjectResponseMetadata, object_response_metadata, object_response_ranged,
     range_header, resolve_range,
 };
+use crate::cache::DecodedChunkKey;
 use crate::http::error::RouteError;
-use crate::http::handlers::track::{parse_address, track_with_pending};
+use crate::http::handlers::s3::authz::ReadOp;
+use crate::http::handlers::track::{authorize_native_read, parse_address, track_with_pending};
 use crate::http::state::AppState;
 use crate::meter::{GatewayMeterDecision, MeterCaller, rate_limited_response};
 
@@ -40,6 +47,7 @@ pub async fn get_object<
     if !track.is_certified() {
         return Err(RouteError::BadRequest("track is not certified".into()));
     }
+    authorize_native_read(&state, track.tape, ReadOp::Get)?;
 
     let metadata = object_response_metadata(&state, track_addr)?;
     read_object_response(
@@ -49,6 +57,7 @@ pub async fn get_object<
         metadata,
         &caller,
         range_header(&headers).map(str::to_string),
+        None,
         rate_limited_response,
     )
     .await
@@ -60,7 +69,8 @@ pub async fn get_object<
 /// `range` is the raw `Range` header value (`bytes=...`). Single-track objects
 /// slice the decoded bytes in memory; multi-track streams decode only the
 /// chunks the range touches. Either way a satisfied range answers `206` and an
-/// unsatisfiable one `416`.
+/// unsatisfiable one `416`. A sealed stream opens with `key_provider` and is
+/// refused without one.
 pub async fn read_object_response<
     Db: Store + 'static,
     Cluster: Api + 'static,
@@ -72,6 +82,7 @@ pub async fn read_object_response<
     metadata: ObjectResponseMetadata,
     caller: &MeterCaller,
     range: Option<String>,
+    key_provider: Option<&dyn KeyProvider>,
     rate_limited: impl Fn(Duration) -> Response,
 ) -> Result<Response, RouteError> {
     match state.meter.check_object_bytes(caller, track.size.to_bytes()) {
@@ -81,14 +92,18 @@ pub async fn read_object_response<
         }
     }
 
-    let decoded = decode_track_bytes(&state, track_addr, track).await?;
-    let Ok(manifest) = ChunkManifest::from_bytes(&decoded.bytes) else {
-        state
-            .context
-            .metrics
-            .add_downloaded(decoded.bytes.len() as u64);
-        // Single-track object: bytes are in memory, so honor a Range slice here.
-        return object_response_ranged(decoded.bytes, &metadata, decoded.etag, range.as_deref());
+    let opened = open_object(&state, track_addr, track).await?;
+    check_object_key(&opened, key_provider)?;
+    let (manifest, etag) = match opened {
+        OpenedObject::Single(decoded) => {
+            state
+                .context
+                .metrics
+                .add_downloaded(decoded.bytes.len() as u64);
+            // Single-track object: bytes are in memory, so honor a Range slice here.
+            return object_response_ranged(decoded.bytes, &metadata, decoded.etag, range.as_deref());
+        }
+        OpenedObject::Stream { manifest, etag } => (manifest, etag),
     };
 
     let total_size = manifest.total_size.to_bytes();
@@ -117,15 +132,58 @@ pub async fn read_object_response<
     object_stream_response(
         state,
         track.tape,
-        &manifest,
+        track_addr,
+        Arc::new(manifest),
         &plan,
         metadata,
-        decoded.etag,
+        etag,
         total_size,
         range,
+        key_provider,
     )
 }
 
+/// An object's decoded top track: the whole object, or the manifest of a
+/// multi-track stream whose chunks have not been read yet.
+pub enum OpenedObject {
+    Single(DecodedObject),
+    Stream { manifest: ChunkManifest, etag: Hash },
+}
+
+/// Decode a certified object track and tell a single-track object from a
+/// stream manifest.
+pub async fn open_object<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_addr: Address,
+    track: CompressedTrack,
+) -> Result<OpenedObject, RouteError> {
+    let key = DecodedChunkKey::whole(track_addr, track.value_hash);
+    let decoded = decode_track_cached(state, key, track_addr, track).await?;
+    Ok(match ChunkManifest::from_bytes(&decoded.bytes) {
+        Ok(manifest) => OpenedObject::Stream {
+            manifest,
+            etag: decoded.etag,
+        },
+        Err(_) => OpenedObject::Single(decoded),
+    })
+}
+
+/// Check a request's key against an opened object without reading its
+/// chunks: a sealed stream needs a key that opens it, and a plaintext object
+/// refuses one. Empty objects are always written plaintext, so they take either.
+pub fn check_object_key(
+    opened: &OpenedObject,
+    key_provider: Option<&dyn KeyProvider>,
+) -> Result<(), RouteError> {
+    match opened {
+        OpenedObject::Single(decoded) if key_provider.is_some() && !decoded.bytes.is_empty() => {
+            Err(plaintext_with_key())
+        }
+        OpenedObject::Single(_) => Ok(()),
+        OpenedObject::Stream { manifest, .. } => stream_cipher(manifest, key_provider).map(drop),
+    }
+}
+
 pub async fn get_track_bytes<Db: Store, Cluster: Api, Blockchain: Rpc>(
     State(state): State<AppState<Db, Cluster, Blockchain>>,
     Extension(caller): Extension<MeterCaller>,
@@ -137,6 +195,7 @@ pub async fn get_track_bytes<Db: Store, Cluster: Api, Blockchain: Rpc>(
     if !track.is_certified() {
         return Err(RouteError::BadRequest("track is not certified".into()));
     }
+    authorize_native_read(&state, track.tape, ReadOp::Get)?;
 
     match state
         .meter
@@ -149,7 +208,8 @@ pub async fn get_track_bytes<Db: Store, Cluster: Api, Blockchain: Rpc>(
     }
 
     let metadata = object_response_metadata(&state, track_addr)?;
-    let decoded = decode_track_bytes(&state, track_addr, track).await?;
+    let key = DecodedChunkKey::whole(track_addr, track.value_hash);
+    let decoded = decode_track_cached(&state, key, track_addr, track).await?;
     state
         .context
         .metrics
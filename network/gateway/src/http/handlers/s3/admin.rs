@@ -1,5 +1,6 @@
 //! S3 write-authorization admin control plane.
 
+use std::net::IpAddr;
 use std::sync::Arc;
 
 use axum::Router;
@@ -16,16 +17,21 @@ use store::Store;
 use tape_crypto::address::Address;
 use tape_node::context::NodeContext;
 use tape_protocol::Api;
-use tape_store::ops::{AuditOps, AuthStateOps, CredentialOps, LedgerOps, PolicyOps};
+use tape_store::ops::{
+    AuditOps, AuthStateOps, BucketAccessOps, CredentialOps, LedgerOps, LifecycleOps, PolicyOps,
+};
 use tape_store::types::{
-    AuditDecision, AuditEntry, AuditOp, BudgetLimits, Credential, CredentialCaps, CredentialScope,
-    CredentialStatus, LedgerEntry, PolicyAction, PolicyEffect, PolicyRule, PolicyRuleKey,
+    AuditDecision, AuditEntry, AuditOp, BucketAccess, BudgetLimits, CidrBlock, Credential,
+    CredentialCaps, CredentialScope, CredentialStatus, KeyPattern, LedgerEntry, PolicyAction,
+    PolicyConditions, PolicyEffect, PolicyRule, PolicyRuleKey, TimeWindow,
 };
 use tape_store::TapeStore;
 
 use super::accounting::{with_ledger_lock, Accounting};
 use super::authz::peppered_secret_hmac;
 use super::clock::now_unix;
+use super::lifecycle::{plan_bucket, Expiration};
+use super::resolve::format_version_id;
 use super::sigv4::constant_time_eq;
 
 /// Shared state for the admin control-plane router.
@@ -98,6 +104,19 @@ where
             put(set_principal_budget::<Db, Cluster, Blockchain>)
                 .delete(clear_principal_budget::<Db, Cluster, Blockchain>),
         )
+        .route(
+            "/lifecycle/dry-run",
+            get(lifecycle_dry_run::<Db, Cluster, Blockchain>),
+        )
+        .route(
+            "/lifecycle/{bucket}/dry-run",
+            get(bucket_lifecycle_dry_run::<Db, Cluster, Blockchain>),
+        )
+        .route(
+            "/buckets/{bucket}/access",
+            get(get_bucket_access::<Db, Cluster, Blockchain>)
+                .put(set_bucket_access::<Db, Cluster, Blockchain>),
+        )
         .with_state(state.clone())
         .layer(from_fn_with_state(
             state,
@@ -196,6 +215,8 @@ where
         can_put: request.caps.can_put,
         can_delete: request.caps.can_delete,
         can_multipart: request.caps.can_multipart,
+        can_get: request.caps.can_get,
+        can_list: request.caps.can_list,
     };
     if let Some(grade) = request.grade.as_deref() {
         require_known_grade(&state, grade)?;
@@ -357,6 +378,7 @@ where
         action: request.action.into(),
         effect: request.effect.into(),
         reason: request.reason,
+        conditions: request.conditions.try_into_conditions()?,
     };
     let key = PolicyRuleKey::new(request.priority, request.id);
 
@@ -623,6 +645,109 @@ where
     Ok(Json(LedgerView::from_entry(&principal, &entry)))
 }
 
+// Lifecycle
+
+/// `GET /lifecycle/dry-run` — report the expirations the next lifecycle pass
+/// would carry out, across every bucket with rules, without acting on them
+async fn lifecycle_dry_run<Db, Cluster, Blockchain>(
+    State(state): State<AdminState<Db, Cluster, Blockchain>>,
+) -> Result<Json<Vec<ExpirationView>>, AdminError>
+where
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+{
+    let store = state.context.store.as_ref();
+    let limit = state.context.config.gateway.s3.lifecycle.max_actions_per_pass;
+    let now = now_unix();
+    let lifecycles = store
+        .list_bucket_lifecycles()
+        .map_err(|error| AdminError::internal(format!("lifecycle store: {error}")))?;
+    let mut views: Vec<ExpirationView> = Vec::new();
+    for (bucket, lifecycle) in &lifecycles {
+        let remaining = limit.saturating_sub(views.len());
+        if remaining == 0 {
+            break;
+        }
+        let planned = plan_bucket(store, *bucket, lifecycle, now, remaining)
+            .map_err(|error| AdminError::internal(format!("lifecycle plan: {error}")))?;
+        views.extend(planned.iter().map(|expiration| view_expiration(bucket, expiration)));
+    }
+    Ok(Json(views))
+}
+
+/// `GET /lifecycle/{bucket}/dry-run` — report the expirations due in one bucket
+async fn bucket_lifecycle_dry_run<Db, Cluster, Blockchain>(
+    State(state): State<AdminState<Db, Cluster, Blockchain>>,
+    Path(bucket): Path<String>,
+) -> Result<Json<Vec<ExpirationView>>, AdminError>
+where
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+{
+    let bucket = parse_address(&bucket, "bucket")?;
+    let store = state.context.store.as_ref();
+    let lifecycle = store
+        .get_bucket_lifecycle(bucket)
+        .map_err(|error| AdminError::internal(format!("lifecycle store: {error}")))?
+        .ok_or_else(|| AdminError::not_found("bucket has no lifecycle configuration"))?;
+    let limit = state.context.config.gateway.s3.lifecycle.max_actions_per_pass;
+    let planned = plan_bucket(store, bucket, &lifecycle, now_unix(), limit)
+        .map_err(|error| AdminError::internal(format!("lifecycle plan: {error}")))?;
+    Ok(Json(planned.iter().map(|expiration| view_expiration(&bucket, expiration)).collect()))
+}
+
+// Bucket access
+
+/// `GET /buckets/{bucket}/access` — whether a bucket is public or private
+async fn get_bucket_access<Db, Cluster, Blockchain>(
+    State(state): State<AdminState<Db, Cluster, Blockchain>>,
+    Path(bucket): Path<String>,
+) -> Result<Json<BucketAccessView>, AdminError>
+where
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+{
+    let bucket = parse_address(&bucket, "bucket")?;
+    let access = state
+        .context
+        .store
+        .get_bucket_access(bucket)
+        .map_err(|error| AdminError::internal(format!("bucket access store: {error}")))?
+        .unwrap_or(BucketAccess::Public);
+    Ok(Json(BucketAccessView {
+        access: access.into(),
+    }))
+}
+
+/// `PUT /buckets/{bucket}/access` — make a bucket public or private. Applies to
+/// the next read; no restart
+async fn set_bucket_access<Db, Cluster, Blockchain>(
+    State(state): State<AdminState<Db, Cluster, Blockchain>>,
+    Path(bucket): Path<String>,
+    Json(request): Json<BucketAccessView>,
+) -> Result<Json<BucketAccessView>, AdminError>
+where
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+{
+    let bucket = parse_address(&bucket, "bucket")?;
+    let store = state.context.store.as_ref();
+    store
+        .put_bucket_access(bucket, request.access.into())
+        .map_err(|error| AdminError::internal(format!("bucket access store: {error}")))?;
+    audit_admin(
+        store,
+        &state.accounting,
+        Address::default(),
+        format!("set_bucket_access bucket={bucket} access={}", request.access.as_str()),
+    )?;
+    Ok(Json(request))
+}
+
 // Request / response bodies
 #[derive(Deserialize)]
 struct CreateCredentialRequest {
@@ -635,7 +760,7 @@ struct CreateCredentialRequest {
     /// Buckets this credential may write to; omitted allows any owned bucket
     #[serde(default)]
     scope: ScopeSpec,
-    /// Write operations this credential may perform
+    /// Operations this credential may perform
     caps: CapsSpec,
     /// Optional expiry as a unix timestamp; omitted never expires
     #[serde(default)]
@@ -684,6 +809,12 @@ struct CapsSpec {
     can_delete: bool,
     #[serde(default)]
     can_multipart: bool,
+    /// Object reads on private buckets
+    #[serde(default)]
+    can_get: bool,
+    /// Listings on private buckets
+    #[serde(default)]
+    can_list: bool,
 }
 
 #[derive(Serialize)]
@@ -702,6 +833,8 @@ struct CapsView {
     can_put: bool,
     can_delete: bool,
     can_multipart: bool,
+    can_get: bool,
+    can_list: bool,
 }
 
 #[derive(Serialize)]
@@ -728,6 +861,123 @@ struct CreatePolicyRuleRequest {
     action: PolicyActionSpec,
     effect: PolicyEffectSpec,
     reason: String,
+    /// Further constraints; omitted conditions constrain nothing
+    #[serde(default)]
+    conditions: ConditionsSpec,
+}
+
+/// Policy rule conditions as the admin API reads and reports them
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+struct ConditionsSpec {
+    /// Keys starting with this prefix; exclusive with `key_glob`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_prefix: Option<String>,
+    /// Keys matching this glob, where `*` spans any run and `?` one character
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_glob: Option<String>,
+    /// Caller networks as `address/prefix`, or a bare address
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    source_cidrs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time_window: Option<TimeWindowSpec>,
+    /// Largest object size in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_object_size: Option<u64>,
+}
+
+/// A UTC window given as `HH:MM` bounds; the end is exclusive and may fall
+/// before the start to run past midnight
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct TimeWindowSpec {
+    start: String,
+    end: String,
+    /// Lowercase three-letter day names; empty means every day
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    weekdays: Vec<String>,
+}
+
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+impl ConditionsSpec {
+    fn try_into_conditions(self) -> Result<PolicyConditions, AdminError> {
+        let key = match (self.key_prefix, self.key_glob) {
+            (Some(_), Some(_)) => {
+                return Err(AdminError::bad_request(
+                    "key_prefix and key_glob are mutually exclusive",
+                ));
+            }
+            (Some(prefix), None) => Some(KeyPattern::Prefix(prefix)),
+            (None, Some(glob)) => Some(KeyPattern::Glob(glob)),
+            (None, None) => None,
+        };
+        let mut source_cidrs: Vec<CidrBlock> = Vec::new();
+        for cidr in &self.source_cidrs {
+            source_cidrs.push(parse_cidr(cidr)?);
+        }
+        let time_window = match self.time_window {
+            Some(window) => Some(window.try_into_window()?),
+            None => None,
+        };
+        Ok(PolicyConditions {
+            key,
+            source_cidrs,
+            time_window,
+            max_object_size: self.max_object_size,
+        })
+    }
+
+    fn view(conditions: &PolicyConditions) -> Self {
+        let (key_prefix, key_glob) = match &conditions.key {
+            Some(KeyPattern::Prefix(prefix)) => (Some(prefix.clone()), None),
+            Some(KeyPattern::Glob(glob)) => (None, Some(glob.clone())),
+            None => (None, None),
+        };
+        Self {
+            key_prefix,
+            key_glob,
+            source_cidrs: conditions.source_cidrs.iter().map(format_cidr).collect(),
+            time_window: conditions.time_window.map(TimeWindowSpec::view),
+            max_object_size: conditions.max_object_size,
+        }
+    }
+}
+
+impl TimeWindowSpec {
+    fn try_into_window(self) -> Result<TimeWindow, AdminError> {
+        let mut weekdays = 0u8;
+        for day in &self.weekdays {
+            let bit = WEEKDAYS.iter().position(|name| *name == day.as_str()).ok_or_else(|| {
+                AdminError::bad_request(format!("time_window: unknown weekday `{day}`"))
+            })?;
+            weekdays |= 1 << bit;
+        }
+        if weekdays == 0 {
+            weekdays = TimeWindow::ALL_DAYS;
+        }
+        Ok(TimeWindow {
+            start_minute: parse_minute(&self.start)?,
+            end_minute: parse_minute(&self.end)?,
+            weekdays,
+        })
+    }
+
+    fn view(window: TimeWindow) -> Self {
+        let weekdays = if window.weekdays == TimeWindow::ALL_DAYS {
+            Vec::new()
+        } else {
+            WEEKDAYS
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| window.weekdays & (1 << bit) != 0)
+                .map(|(_, name)| name.to_string())
+                .collect()
+        };
+        Self {
+            start: format_minute(window.start_minute),
+            end: format_minute(window.end_minute),
+            weekdays,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -737,6 +987,9 @@ enum PolicyActionSpec {
     Put,
     Delete,
     Multipart,
+    Configure,
+    Get,
+    List,
 }
 
 impl From<PolicyActionSpec> for PolicyAction {
@@ -746,6 +999,9 @@ impl From<PolicyActionSpec> for PolicyAction {
             PolicyActionSpec::Put => PolicyAction::Put,
             PolicyActionSpec::Delete => PolicyAction::Delete,
             PolicyActionSpec::Multipart => PolicyAction::Multipart,
+            PolicyActionSpec::Configure => PolicyAction::Configure,
+            PolicyActionSpec::Get => PolicyAction::Get,
+            PolicyActionSpec::List => PolicyAction::List,
         }
     }
 }
@@ -775,6 +1031,12 @@ struct PolicyRuleView {
     action: String,
     effect: String,
     reason: String,
+    #[serde(skip_serializing_if = "is_unconditioned")]
+    conditions: ConditionsSpec,
+}
+
+fn is_unconditioned(conditions: &ConditionsSpec) -> bool {
+    *conditions == ConditionsSpec::default()
 }
 
 #[derive(Serialize)]
@@ -784,6 +1046,45 @@ struct DeleteRuleResponse {
     is_deleted: bool,
 }
 
+#[derive(Deserialize, Serialize)]
+struct BucketAccessView {
+    access: BucketAccessSpec,
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum BucketAccessSpec {
+    Public,
+    Private,
+}
+
+impl BucketAccessSpec {
+    fn as_str(self) -> &'static str {
+        match self {
+            BucketAccessSpec::Public => "public",
+            BucketAccessSpec::Private => "private",
+        }
+    }
+}
+
+impl From<BucketAccessSpec> for BucketAccess {
+    fn from(spec: BucketAccessSpec) -> Self {
+        match spec {
+            BucketAccessSpec::Public => BucketAccess::Public,
+            BucketAccessSpec::Private => BucketAccess::Private,
+        }
+    }
+}
+
+impl From<BucketAccess> for BucketAccessSpec {
+    fn from(access: BucketAccess) -> Self {
+        match access {
+            BucketAccess::Public => BucketAccessSpec::Public,
+            BucketAccess::Private => BucketAccessSpec::Private,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 struct KillSwitchView {
     is_kill_switch_engaged: bool,
@@ -849,8 +1150,32 @@ impl LedgerView {
     }
 }
 
+/// One expiration a lifecycle pass would carry out
+#[derive(Serialize)]
+struct ExpirationView {
+    bucket: String,
+    /// Object key, lossily decoded as UTF-8
+    key: String,
+    version_id: String,
+    track: String,
+    rule: String,
+    action: String,
+}
+
 // Helpers
 
+/// Build an ExpirationView from a planned expiration in `bucket`
+fn view_expiration(bucket: &Address, expiration: &Expiration) -> ExpirationView {
+    ExpirationView {
+        bucket: bucket.to_string(),
+        key: String::from_utf8_lossy(&expiration.key).into_owned(),
+        version_id: format_version_id(expiration.version),
+        track: expiration.track.to_string(),
+        rule: expiration.rule_id.clone(),
+        action: expiration.action.as_str().to_string(),
+    }
+}
+
 /// Build a CredentialSummary from a stored credential, omitting the secret
 /// HMAC
 fn summarize_credential(access_key_id: &str, credential: &Credential) -> CredentialSummary {
@@ -876,6 +1201,8 @@ fn summarize_credential(access_key_id: &str, credential: &Credential) -> Credent
             can_put: credential.caps.can_put,
             can_delete: credential.caps.can_delete,
             can_multipart: credential.caps.can_multipart,
+            can_get: credential.caps.can_get,
+            can_list: credential.caps.can_list,
         },
         scope,
         not_after: credential.not_after,
@@ -895,6 +1222,9 @@ fn view_policy_rule(key: PolicyRuleKey, rule: &PolicyRule) -> PolicyRuleView {
             PolicyAction::Put => "put",
             PolicyAction::Delete => "delete",
             PolicyAction::Multipart => "multipart",
+            PolicyAction::Configure => "configure",
+            PolicyAction::Get => "get",
+            PolicyAction::List => "list",
         }
         .to_string(),
         effect: match rule.effect {
@@ -903,6 +1233,7 @@ fn view_policy_rule(key: PolicyRuleKey, rule: &PolicyRule) -> PolicyRuleView {
         }
         .to_string(),
         reason: rule.reason.clone(),
+        conditions: ConditionsSpec::view(&rule.conditions),
     }
 }
 
@@ -943,6 +1274,49 @@ fn parse_optional_address(value: Option<&str>, field: &str) -> Result<Option<Add
     }
 }
 
+/// Parse `address/prefix`; a bare address is a single-host block
+fn parse_cidr(value: &str) -> Result<CidrBlock, AdminError> {
+    let invalid = || AdminError::bad_request(format!("source_cidrs: invalid block `{value}`"));
+    let (address, prefix_len) = match value.split_once('/') {
+        Some((address, prefix_len)) => (address, Some(prefix_len)),
+        None => (value, None),
+    };
+    let address: IpAddr = address.parse().map_err(|_| invalid())?;
+    let prefix_len = match prefix_len {
+        Some(prefix_len) => prefix_len.parse::<u8>().map_err(|_| invalid())?,
+        None if address.is_ipv4() => 32,
+        None => 128,
+    };
+    CidrBlock::new(address, prefix_len).ok_or_else(invalid)
+}
+
+fn format_cidr(block: &CidrBlock) -> String {
+    match *block {
+        CidrBlock::V4 { network, prefix_len } => {
+            format!("{}/{prefix_len}", IpAddr::from(network))
+        }
+        CidrBlock::V6 { network, prefix_len } => {
+            format!("{}/{prefix_len}", IpAddr::from(network))
+        }
+    }
+}
+
+/// Parse an `HH:MM` time of day into minutes after midnight
+fn parse_minute(value: &str) -> Result<u16, AdminError> {
+    let invalid = || AdminError::bad_request(format!("time_window: invalid time `{value}`"));
+    let (hours, minutes) = value.split_once(':').ok_or_else(invalid)?;
+    let hours: u16 = hours.parse().map_err(|_| invalid())?;
+    let minutes: u16 = minutes.parse().map_err(|_| invalid())?;
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+fn format_minute(minute: u16) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
 
 /// A JSON-rendered admin error: `{ "error": "<message>" }` with an HTTP status
 #[derive(Debug)]
@@ -1010,4 +1384,54 @@ mod tests {
         };
         assert!(bad.try_into_scope().is_err());
     }
+
+    // condition specs parse into stored conditions and read back the same
+    #[test]
+    fn conditions_spec() {
+        let spec: ConditionsSpec = serde_json::from_value(json!({
+            "key_prefix": "artifacts/ci/",
+            "source_cidrs": ["10.0.0.0/8", "2001:db8::1"],
+            "time_window": {"start": "09:00", "end": "17:30", "weekdays": ["mon", "fri"]},
+            "max_object_size": 1048576,
+        }))
+        .expect("test setup");
+        let conditions = spec.try_into_conditions().expect("valid conditions");
+        assert_eq!(
+            conditions.key,
+            Some(KeyPattern::Prefix("artifacts/ci/".to_string()))
+        );
+        assert!(conditions.source_cidrs[0].contains("10.2.3.4".parse().unwrap()));
+        assert!(!conditions.source_cidrs[1].contains("2001:db8::2".parse().unwrap()));
+        let window = conditions.time_window.expect("window");
+        assert_eq!((window.start_minute, window.end_minute), (540, 1050));
+        assert_eq!(window.weekdays, 0b1_0001);
+
+        let view = ConditionsSpec::view(&conditions);
+        assert_eq!(view.source_cidrs, vec!["10.0.0.0/8", "2001:db8::1/128"]);
+        assert_eq!(view.time_window.expect("window").weekdays, vec!["mon", "fri"]);
+
+        for bad in [
+            json!({"key_prefix": "a/", "key_glob": "*.tar"}),
+            json!({"source_cidrs": ["10.0.0.0/33"]}),
+            json!({"time_window": {"start": "24:00", "end": "01:00"}}),
+            json!({"time_window": {"start": "09:00", "end": "17:00", "weekdays": ["mo"]}}),
+        ] {
+            let spec: ConditionsSpec = serde_json::from_value(bad).expect("test setup");
+            assert!(spec.try_into_conditions().is_err());
+        }
+    }
+
+    // bucket access bodies use lowercase names and map onto the stored enum
+    #[test]
+    fn bucket_access_spec() {
+        let view: BucketAccessView =
+            serde_json::from_str(r#"{"access":"private"}"#).expect("parse");
+        assert_eq!(BucketAccess::from(view.access), BucketAccess::Private);
+        assert!(serde_json::from_str::<BucketAccessView>(r#"{"access":"secret"}"#).is_err());
+        let rendered = serde_json::to_string(&BucketAccessView {
+            access: BucketAccess::Public.into(),
+        })
+        .expect("render");
+        assert_eq!(rendered, r#"{"access":"public"}"#);
+    }
 }
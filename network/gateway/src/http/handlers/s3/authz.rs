@@ -1,16 +1,23 @@
-//! S3 write authorization.
+//! S3 write and private-read authorization.
 
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
 use rpc::Rpc;
 use store::Store;
 use tape_crypto::address::Address;
 use tape_node::config::gateway::WriteDefault;
 use tape_protocol::Api;
 use tape_store::ops::{
-    AuditOps, AuthStateOps, CredentialOps, PolicyDecision, PolicyOps, ReserveOutcome, ReserveRequest,
+    AuditOps, AuthStateOps, BucketAccessOps, CredentialOps, PolicyDecision, PolicyOps,
+    ReserveOutcome, ReserveRequest, TapeOps,
 };
 use tape_store::types::{
-    AuditDecision, AuditEntry, AuditOp, Credential, CredentialCaps, LedgerReservationKey,
-    PolicyAction,
+    AuditDecision, AuditEntry, AuditOp, BucketAccess, Credential, CredentialCaps,
+    CredentialScope, LedgerReservationKey, PolicyAction, PolicyRequest, PolicyRuleKey,
 };
 use tape_store::TapeStore;
 
@@ -20,6 +27,7 @@ use super::error::S3Error;
 use super::sigv4;
 use crate::admission::{AdmissionDeny, AdmissionRequest};
 use crate::http::state::AppState;
+use crate::meter::caller_ip;
 
 /// Flat per-op SOL fee estimate (lamports) reserved before a cost-bearing write.
 const ESTIMATED_LAMPORTS_PER_OP: u64 = 5_000;
@@ -31,8 +39,8 @@ pub fn peppered_secret_hmac(pepper: &str, secret: &str) -> Result<[u8; 32], S3Er
 /// The authenticated principal a request carries into the write path.
 #[derive(Clone, Debug)]
 pub enum Auth {
-    /// No SigV4 credentials were presented (anonymous request). Reads are public;
-    /// writes are denied.
+    /// No SigV4 credentials were presented (anonymous request). Reads of public
+    /// buckets are allowed; private reads and writes are denied.
     Anonymous,
     /// Credentials were presented and verified against an active credential
     Verified(Principal),
@@ -41,7 +49,10 @@ pub enum Auth {
 impl Auth {
     /// Build a verified Auth for a successfully-authenticated access key
     pub fn verified(access_key_id: String) -> Self {
-        Self::Verified(Principal { access_key_id })
+        Self::Verified(Principal {
+            access_key_id,
+            source_ip: None,
+        })
     }
 
     /// The verified access key id, when the request was signed
@@ -58,6 +69,23 @@ impl Auth {
 pub struct Principal {
     /// The access key id whose secret signed the request
     pub access_key_id: String,
+    /// The caller IP, resolved through the metering trusted proxies, that
+    /// source-scoped policy rules match against
+    pub source_ip: Option<IpAddr>,
+}
+
+/// Middleware, layered inside `sigv4_auth`, that stamps the resolved caller
+/// IP on a verified principal
+pub async fn principal_source_ip(
+    State(trusted): State<Arc<[IpAddr]>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let source_ip = caller_ip(&request, &trusted);
+    if let Some(Auth::Verified(principal)) = request.extensions_mut().get_mut::<Auth>() {
+        principal.source_ip = Some(source_ip);
+    }
+    next.run(request).await
 }
 
 /// The kind of write being authorized.
@@ -69,12 +97,17 @@ pub enum WriteOp {
     Delete,
     /// `CreateMultipartUpload` — mint an upload id (no on-chain cost yet)
     CreateMultipart,
-    /// `UploadPart` — buffer one part (no on-chain cost yet)
+    /// `UploadPart` — write one part onto chunk tracks
     UploadPart,
-    /// `CompleteMultipartUpload` — assemble buffered parts and write the object
+    /// `CompleteMultipartUpload` — link the written parts under the object's
+    /// manifest
     CompleteMultipart,
-    /// `AbortMultipartUpload` — discard a buffered upload (no on-chain cost)
+    /// `AbortMultipartUpload` — discard an upload and delete its part tracks
+    /// (no billed cost)
     Abort,
+    /// `PutBucketVersioning` — change the bucket's configuration (no on-chain
+    /// cost)
+    Configure,
 }
 
 impl WriteOp {
@@ -88,6 +121,7 @@ impl WriteOp {
             WriteOp::UploadPart => AuditOp::UploadPart,
             WriteOp::CompleteMultipart => AuditOp::CompleteMultipart,
             WriteOp::Abort => AuditOp::Abort,
+            WriteOp::Configure => AuditOp::Configure,
         }
     }
 
@@ -101,6 +135,7 @@ impl WriteOp {
             | WriteOp::UploadPart
             | WriteOp::CompleteMultipart
             | WriteOp::Abort => PolicyAction::Multipart,
+            WriteOp::Configure => PolicyAction::Configure,
         }
     }
 
@@ -113,6 +148,20 @@ impl WriteOp {
             | WriteOp::UploadPart
             | WriteOp::CompleteMultipart
             | WriteOp::Abort => caps.can_multipart,
+            // Configuration decides whether later deletes destroy data, so it
+            // takes both object-write caps.
+            WriteOp::Configure => caps.can_put && caps.can_delete,
+        }
+    }
+
+    /// The object size size-capped policy rules see; only ops that carry a
+    /// body have one.
+    fn policy_size(self, size: u64) -> Option<u64> {
+        match self {
+            WriteOp::Put | WriteOp::UploadPart | WriteOp::CompleteMultipart => Some(size),
+            WriteOp::Delete | WriteOp::CreateMultipart | WriteOp::Abort | WriteOp::Configure => {
+                None
+            }
         }
     }
 
@@ -120,15 +169,37 @@ impl WriteOp {
     fn is_cost_bearing(self) -> bool {
         matches!(
             self,
-            WriteOp::Put | WriteOp::Delete | WriteOp::CompleteMultipart
+            WriteOp::Put | WriteOp::Delete | WriteOp::UploadPart | WriteOp::CompleteMultipart
         )
     }
 
+    /// The bytes this op newly writes to the tape. A completion only writes
+    /// the manifest; its parts' bytes landed, and were billed, at UploadPart.
+    pub fn stored_bytes(self, size: u64) -> u64 {
+        match self {
+            WriteOp::Put | WriteOp::UploadPart => size,
+            WriteOp::Delete
+            | WriteOp::CreateMultipart
+            | WriteOp::CompleteMultipart
+            | WriteOp::Abort
+            | WriteOp::Configure => 0,
+        }
+    }
+
     /// The budget estimate this op reserves up front.
     fn reserve_request(self, size: u64) -> ReserveRequest {
         match self {
             WriteOp::Put | WriteOp::CompleteMultipart => ReserveRequest {
                 writes: 1,
+                bytes: self.stored_bytes(size),
+                sol: ESTIMATED_LAMPORTS_PER_OP,
+                is_onchain: true,
+                meters_capacity: true,
+            },
+            // A part is not an object, so it takes no write from the hourly
+            // count; the completion that names it does.
+            WriteOp::UploadPart => ReserveRequest {
+                writes: 0,
                 bytes: size,
                 sol: ESTIMATED_LAMPORTS_PER_OP,
                 is_onchain: true,
@@ -141,7 +212,7 @@ impl WriteOp {
                 is_onchain: true,
                 meters_capacity: false,
             },
-            WriteOp::CreateMultipart | WriteOp::UploadPart | WriteOp::Abort => ReserveRequest {
+            WriteOp::CreateMultipart | WriteOp::Abort | WriteOp::Configure => ReserveRequest {
                 writes: 0,
                 bytes: 0,
                 sol: 0,
@@ -152,6 +223,39 @@ impl WriteOp {
     }
 }
 
+/// The kind of read being authorized.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReadOp {
+    /// `GetObject` / `HeadObject`, and the source side of a copy
+    Get,
+    /// Object listings and bucket configuration reads
+    List,
+}
+
+impl ReadOp {
+    fn audit_op(self) -> AuditOp {
+        match self {
+            ReadOp::Get => AuditOp::Get,
+            ReadOp::List => AuditOp::List,
+        }
+    }
+
+    fn policy_action(self) -> PolicyAction {
+        match self {
+            ReadOp::Get => PolicyAction::Get,
+            ReadOp::List => PolicyAction::List,
+        }
+    }
+
+    /// Whether `caps` permit this op on a private bucket
+    fn permitted_by(self, caps: &CredentialCaps) -> bool {
+        match self {
+            ReadOp::Get => caps.can_get,
+            ReadOp::List => caps.can_list,
+        }
+    }
+}
+
 /// A budget reservation granted by authorize_write.
 #[must_use = "a WritePermit must be committed on success or refunded on failure"]
 #[derive(Debug)]
@@ -241,6 +345,8 @@ struct Decision {
     /// The reason code recorded in the audit log and (on deny) surfaced to the
     /// client.
     reason: String,
+    /// The policy rule that decided, when one did
+    rule: Option<PolicyRuleKey>,
 }
 
 impl Decision {
@@ -249,26 +355,48 @@ impl Decision {
             allowed: false,
             owner,
             reason: reason.into(),
+            rule: None,
         }
     }
-}
 
+    fn from_policy(owner: Address, decision: PolicyDecision) -> Self {
+        Self {
+            allowed: decision.is_allowed,
+            owner,
+            reason: decision.reason,
+            rule: decision.rule,
+        }
+    }
 
-/// The three durable reads the synchronous decision core (decide) makes,
+    /// The reason as audited, naming the deciding rule so the log shows
+    /// which rule in evaluation order fired.
+    fn audit_reason(&self) -> String {
+        match self.rule {
+            Some(rule) => format!("{} (rule {}/{})", self.reason, rule.priority, rule.id),
+            None => self.reason.clone(),
+        }
+    }
+}
+
+/// The durable reads the synchronous decision cores (decide, decide_read) make
 trait AuthzReads {
     /// Whether the global write kill switch is engaged
     fn is_write_killed(&self) -> Result<bool, String>;
 
+    /// The read access stored for `bucket`, if any
+    fn bucket_access(&self, bucket: &Address) -> Result<Option<BucketAccess>, String>;
+
     /// The credential record for `access_key_id`, if any
     fn get_credential(&self, access_key_id: &str) -> Result<Option<Credential>, String>;
 
-    /// Evaluate the policy ruleset for `(owner, bucket, action)` with the
-    /// configured default applied when no rule matches.
+    /// The current owner of `bucket` in the tape owner index, if any
+    fn tape_owner(&self, bucket: &Address) -> Result<Option<Address>, String>;
+
+    /// Evaluate the policy ruleset for `request` with the configured default
+    /// applied when no rule matches.
     fn evaluate_policy(
         &self,
-        owner: &Address,
-        bucket: &Address,
-        action: PolicyAction,
+        request: &PolicyRequest<'_>,
         default_allow: bool,
     ) -> Result<PolicyDecision, String>;
 }
@@ -278,29 +406,37 @@ impl<S: Store> AuthzReads for TapeStore<S> {
         AuthStateOps::is_write_killed(self).map_err(|error| error.to_string())
     }
 
+    fn bucket_access(&self, bucket: &Address) -> Result<Option<BucketAccess>, String> {
+        BucketAccessOps::get_bucket_access(self, *bucket).map_err(|error| error.to_string())
+    }
+
     fn get_credential(&self, access_key_id: &str) -> Result<Option<Credential>, String> {
         CredentialOps::get_credential(self, access_key_id).map_err(|error| error.to_string())
     }
 
+    fn tape_owner(&self, bucket: &Address) -> Result<Option<Address>, String> {
+        TapeOps::get_tape_owner(self, *bucket).map_err(|error| error.to_string())
+    }
+
     fn evaluate_policy(
         &self,
-        owner: &Address,
-        bucket: &Address,
-        action: PolicyAction,
+        request: &PolicyRequest<'_>,
         default_allow: bool,
     ) -> Result<PolicyDecision, String> {
-        PolicyOps::evaluate_policy(self, owner, bucket, action, default_allow)
-            .map_err(|error| error.to_string())
+        PolicyOps::evaluate_policy(self, request, default_allow).map_err(|error| error.to_string())
     }
 }
 
 /// Run the ordered, fail-closed decision flow.
+#[allow(clippy::too_many_arguments)]
 fn decide<R: AuthzReads>(
     reads: &R,
     bootstrap_id: Option<&str>,
     default_allow: bool,
     auth: &Auth,
     bucket: Address,
+    key: Option<&str>,
+    size: Option<u64>,
     op: WriteOp,
     now: i64,
 ) -> Decision {
@@ -319,8 +455,8 @@ fn decide<R: AuthzReads>(
     }
 
     // 2. The request must carry a SigV4-verified principal.
-    let access_key_id = match auth {
-        Auth::Verified(principal) => principal.access_key_id.as_str(),
+    let principal = match auth {
+        Auth::Verified(principal) => principal,
         Auth::Anonymous => {
             return Decision::deny(
                 Address::default(),
@@ -330,58 +466,195 @@ fn decide<R: AuthzReads>(
     };
 
     // 3. Resolve the credential record.
-    let owner = match reads.get_credential(access_key_id) {
+    let resolved = resolve_credential(
+        reads,
+        bootstrap_id,
+        &principal.access_key_id,
+        &bucket,
+        now,
+        op,
+        |caps| op.permitted_by(caps),
+    );
+    let owner = match resolved {
+        Ok(owner) => owner,
+        Err(denied) => return denied,
+    };
+
+    // 4. Policy engine: (principal, bucket, op) plus the request's key, source,
+    //    time and size → Allow | Deny. Default-deny with deny-precedence; the
+    //    `gateway.s3.write.default` config is the fallback when no rule matches.
+    let request = PolicyRequest {
+        principal: owner,
+        bucket,
+        action: op.policy_action(),
+        key,
+        source_ip: principal.source_ip,
+        size,
+        now,
+    };
+    match reads.evaluate_policy(&request, default_allow) {
+        Ok(decision) => Decision::from_policy(owner, decision),
+        Err(error) => {
+            tracing::warn!(%error, "s3 write authz: policy engine unavailable");
+            Decision::deny(owner, "policy engine is unavailable".to_string())
+        }
+    }
+}
+
+/// Resolve the credential behind `access_key_id` to its owner, checking it is
+/// usable, holds the cap for `op`, and is scoped to `bucket`. The bootstrap
+/// key has no record and resolves to the default owner.
+fn resolve_credential<R: AuthzReads, Op: std::fmt::Debug>(
+    reads: &R,
+    bootstrap_id: Option<&str>,
+    access_key_id: &str,
+    bucket: &Address,
+    now: i64,
+    op: Op,
+    permitted_by: impl FnOnce(&CredentialCaps) -> bool,
+) -> Result<Address, Decision> {
+    match reads.get_credential(access_key_id) {
         Ok(Some(credential)) => {
             if !credential.is_usable(now) {
-                return Decision::deny(
+                return Err(Decision::deny(
                     credential.principal,
                     "credential is revoked or expired",
-                );
+                ));
             }
-            if !op.permitted_by(&credential.caps) {
-                return Decision::deny(
+            if !permitted_by(&credential.caps) {
+                return Err(Decision::deny(
                     credential.principal,
                     format!("credential is not permitted to perform {op:?}"),
-                );
+                ));
             }
-            if !credential.allows_bucket(&bucket) {
-                return Decision::deny(
-                    credential.principal,
-                    "credential scope does not include this bucket",
-                );
+            match credential_admits(reads, &credential, bucket) {
+                Ok(true) => {}
+                Ok(false) => {
+                    return Err(Decision::deny(
+                        credential.principal,
+                        "credential scope does not include this bucket",
+                    ));
+                }
+                Err(error) => {
+                    tracing::warn!(%error, "s3 authz: tape owner index unavailable");
+                    return Err(Decision::deny(
+                        credential.principal,
+                        "bucket owner is unavailable",
+                    ));
+                }
             }
-            credential.principal
+            Ok(credential.principal)
         }
         Ok(None) => match bootstrap_id {
-            Some(bootstrap) if bootstrap == access_key_id => Address::default(),
-            Some(_) | None => {
-                return Decision::deny(
-                    Address::default(),
-                    "no active credential for this access key id",
-                );
-            }
+            Some(bootstrap) if bootstrap == access_key_id => Ok(Address::default()),
+            Some(_) | None => Err(Decision::deny(
+                Address::default(),
+                "no active credential for this access key id",
+            )),
         },
         Err(error) => {
-            tracing::warn!(%error, "s3 write authz: credential store unavailable");
-            return Decision::deny(
+            tracing::warn!(%error, "s3 authz: credential store unavailable");
+            Err(Decision::deny(
                 Address::default(),
                 "credential store is unavailable".to_string(),
+            ))
+        }
+    }
+}
+
+/// Whether `credential` is scoped to `bucket`. Only an `AnyOwned` key needs
+/// the bucket's owner, so only it reads the owner index.
+fn credential_admits<R: AuthzReads>(
+    reads: &R,
+    credential: &Credential,
+    bucket: &Address,
+) -> Result<bool, String> {
+    let owner = match credential.scope {
+        CredentialScope::AnyOwned => reads.tape_owner(bucket)?,
+        CredentialScope::Buckets(_) => None,
+    };
+    Ok(credential.allows_bucket(bucket, owner))
+}
+
+/// Run the fail-closed read decision flow.
+///
+/// A bucket with no stored access is public: any reader passes, and policy
+/// decides with the reader's principal when the request names a known key.
+/// A private bucket takes a usable credential with the read cap, scoped to
+/// the bucket. Reads skip the write kill switch, and when no read rule
+/// matches they are allowed.
+fn decide_read<R: AuthzReads>(
+    reads: &R,
+    bootstrap_id: Option<&str>,
+    auth: &Auth,
+    bucket: Address,
+    key: Option<&str>,
+    op: ReadOp,
+    now: i64,
+) -> Decision {
+    let access = match reads.bucket_access(&bucket) {
+        Ok(access) => access.unwrap_or(BucketAccess::Public),
+        Err(error) => {
+            tracing::warn!(%error, "s3 read authz: bucket access unavailable");
+            return Decision::deny(Address::default(), "bucket access setting is unavailable");
+        }
+    };
+
+    let owner = match (access, auth) {
+        (BucketAccess::Public, Auth::Anonymous) => Address::default(),
+        (BucketAccess::Public, Auth::Verified(principal)) => {
+            match reads.get_credential(&principal.access_key_id) {
+                Ok(credential) => credential.map_or(Address::default(), |found| found.principal),
+                Err(error) => {
+                    tracing::warn!(%error, "s3 read authz: credential store unavailable");
+                    return Decision::deny(
+                        Address::default(),
+                        "credential store is unavailable",
+                    );
+                }
+            }
+        }
+        (BucketAccess::Private, Auth::Anonymous) => {
+            return Decision::deny(
+                Address::default(),
+                "anonymous access to this private bucket is not allowed",
             );
         }
+        (BucketAccess::Private, Auth::Verified(principal)) => {
+            let resolved = resolve_credential(
+                reads,
+                bootstrap_id,
+                &principal.access_key_id,
+                &bucket,
+                now,
+                op,
+                |caps| op.permitted_by(caps),
+            );
+            match resolved {
+                Ok(owner) => owner,
+                Err(denied) => return denied,
+            }
+        }
     };
 
-    // 4. Policy engine: (principal, bucket, op) → Allow | Deny. Default-deny with
-    //    deny-precedence; the `gateway.s3.write.default` config is the fallback
-    //    when no rule matches.
-    match reads.evaluate_policy(&owner, &bucket, op.policy_action(), default_allow) {
-        Ok(decision) => Decision {
-            allowed: decision.is_allowed,
-            owner,
-            reason: decision.reason,
-        },
+    let source_ip = match auth {
+        Auth::Verified(principal) => principal.source_ip,
+        Auth::Anonymous => None,
+    };
+    let request = PolicyRequest {
+        principal: owner,
+        bucket,
+        action: op.policy_action(),
+        key,
+        source_ip,
+        size: None,
+        now,
+    };
+    match reads.evaluate_policy(&request, true) {
+        Ok(decision) => Decision::from_policy(owner, decision),
         Err(error) => {
-            tracing::warn!(%error, "s3 write authz: policy engine unavailable");
-            Decision::deny(owner, "policy engine is unavailable".to_string())
+            tracing::warn!(%error, "s3 read authz: policy engine unavailable");
+            Decision::deny(owner, "policy engine is unavailable")
         }
     }
 }
@@ -472,12 +745,15 @@ where
         default_allow,
         auth,
         bucket,
+        Some(key).filter(|key| !key.is_empty()),
+        op.policy_size(size),
         op,
         now,
     );
 
     if decision.allowed && op.is_cost_bearing() {
-        if let Err(reason) = accounting::check_onchain_precondition(state, bucket, size).await {
+        let stored = op.stored_bytes(size);
+        if let Err(reason) = accounting::check_onchain_precondition(state, bucket, stored).await {
             decision = Decision::deny(decision.owner, reason);
         }
     }
@@ -537,7 +813,7 @@ where
         } else {
             AuditDecision::Deny
         },
-        reason: decision.reason.clone(),
+        reason: decision.audit_reason(),
     };
     let audit_result = state
         .context
@@ -574,11 +850,49 @@ where
     })
 }
 
+/// The read-authorization chokepoint for objects, listings and bucket
+/// configuration reads
+///
+/// Only denials are audited; allowed reads are far too frequent to log.
+pub fn authorize_read<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket: Address,
+    key: Option<&str>,
+    op: ReadOp,
+) -> Result<(), S3Error>
+where
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+{
+    let now = now_unix();
+    let store = state.context.store.as_ref();
+    let bootstrap_id = bootstrap_access_key_id(state);
+    let decision = decide_read(store, bootstrap_id, auth, bucket, key, op, now);
+    if decision.allowed {
+        return Ok(());
+    }
+
+    let entry = AuditEntry {
+        timestamp: now,
+        principal: decision.owner,
+        bucket,
+        op: op.audit_op(),
+        decision: AuditDecision::Deny,
+        reason: decision.audit_reason(),
+    };
+    if let Err(error) = store.append_audit(&entry, state.accounting.next_audit_sequence()) {
+        tracing::error!(%error, "s3 read-authz: failed to record deny decision");
+    }
+    Err(S3Error::AccessDenied(decision.reason))
+}
+
 /// Authorize an authenticated principal to inspect a bucket's in-flight
 /// multipart state (ListParts / ListMultipartUploads)
 ///
-/// In-flight upload ids and part listings are not public the way object reads
-/// are: leaking them lets a stranger enumerate or interfere with another
+/// In-flight upload ids and part listings are never public the way reads of a
+/// public bucket are: leaking them lets a stranger enumerate or interfere with another
 /// tenant's uploads. They therefore require a usable, multipart-capable
 /// credential scoped to the bucket; the configured bootstrap credential is
 /// always allowed. This is a read check, so it neither reserves budget nor
@@ -615,12 +929,16 @@ where
                     "credential is not permitted to perform multipart operations".to_string(),
                 ));
             }
-            if !credential.allows_bucket(&bucket) {
-                return Err(S3Error::AccessDenied(
+            match credential_admits(store, &credential, &bucket) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(S3Error::AccessDenied(
                     "credential scope does not include this bucket".to_string(),
-                ));
+                )),
+                Err(error) => {
+                    tracing::warn!(%error, "s3 multipart read authz: tape owner index unavailable");
+                    Err(S3Error::Internal("bucket owner is unavailable".to_string()))
+                }
             }
-            Ok(())
         }
         Ok(None) => match bootstrap_access_key_id(state) {
             Some(bootstrap) if bootstrap == access_key_id => Ok(()),
@@ -640,7 +958,7 @@ mod tests {
     use super::*;
     use store_memory::MemoryStore;
     use tape_store::types::{
-        CredentialScope, CredentialStatus, PolicyEffect, PolicyRule, PolicyRuleKey,
+        CidrBlock, CredentialStatus, KeyPattern, PolicyConditions, PolicyEffect, PolicyRule,
     };
 
     /// A test double for the decision core's durable reads. Each field is the
@@ -648,7 +966,9 @@ mod tests {
     /// store error to prove fail-closed) while leaving the rest permissive.
     struct FakeReads {
         killed: Result<bool, String>,
+        access: Result<Option<BucketAccess>, String>,
         credential: Result<Option<Credential>, String>,
+        owner: Result<Option<Address>, String>,
         policy: Result<PolicyDecision, String>,
     }
 
@@ -658,10 +978,13 @@ mod tests {
             // path), policy allows. Tests override exactly one field.
             Self {
                 killed: Ok(false),
+                access: Ok(None),
                 credential: Ok(None),
+                owner: Ok(None),
                 policy: Ok(PolicyDecision {
                     is_allowed: true,
                     reason: "default-allow".to_string(),
+                    rule: None,
                 }),
             }
         }
@@ -672,15 +995,21 @@ mod tests {
             self.killed.clone()
         }
 
+        fn bucket_access(&self, _bucket: &Address) -> Result<Option<BucketAccess>, String> {
+            self.access.clone()
+        }
+
         fn get_credential(&self, _access_key_id: &str) -> Result<Option<Credential>, String> {
             self.credential.clone()
         }
 
+        fn tape_owner(&self, _bucket: &Address) -> Result<Option<Address>, String> {
+            self.owner.clone()
+        }
+
         fn evaluate_policy(
             &self,
-            _owner: &Address,
-            _bucket: &Address,
-            _action: PolicyAction,
+            _request: &PolicyRequest<'_>,
             _default_allow: bool,
         ) -> Result<PolicyDecision, String> {
             self.policy.clone()
@@ -719,6 +1048,7 @@ mod tests {
             WriteOp::CompleteMultipart.audit_op(),
             AuditOp::CompleteMultipart
         );
+        assert_eq!(WriteOp::Configure.audit_op(), AuditOp::Configure);
 
         assert_eq!(WriteOp::Put.policy_action(), PolicyAction::Put);
         assert_eq!(WriteOp::Delete.policy_action(), PolicyAction::Delete);
@@ -729,6 +1059,7 @@ mod tests {
         ] {
             assert_eq!(op.policy_action(), PolicyAction::Multipart);
         }
+        assert_eq!(WriteOp::Configure.policy_action(), PolicyAction::Configure);
     }
 
     // caps permit exactly their corresponding ops
@@ -738,10 +1069,13 @@ mod tests {
             can_put: true,
             can_delete: false,
             can_multipart: false,
+            can_get: false,
+            can_list: false,
         };
         assert!(WriteOp::Put.permitted_by(&only_put));
         assert!(!WriteOp::Delete.permitted_by(&only_put));
         assert!(!WriteOp::UploadPart.permitted_by(&only_put));
+        assert!(!WriteOp::Configure.permitted_by(&only_put));
 
         let all = CredentialCaps::all();
         for op in [
@@ -750,6 +1084,7 @@ mod tests {
             WriteOp::CreateMultipart,
             WriteOp::UploadPart,
             WriteOp::CompleteMultipart,
+            WriteOp::Configure,
         ] {
             assert!(op.permitted_by(&all));
         }
@@ -757,6 +1092,17 @@ mod tests {
         for op in [WriteOp::Put, WriteOp::Delete, WriteOp::CompleteMultipart] {
             assert!(!op.permitted_by(&none));
         }
+
+        // Read caps are independent of the write caps.
+        assert!(!ReadOp::Get.permitted_by(&only_put));
+        let only_get = CredentialCaps {
+            can_get: true,
+            ..CredentialCaps::none()
+        };
+        assert!(ReadOp::Get.permitted_by(&only_get));
+        assert!(!ReadOp::List.permitted_by(&only_get));
+        assert_eq!(ReadOp::Get.policy_action(), PolicyAction::Get);
+        assert_eq!(ReadOp::List.audit_op(), AuditOp::List);
     }
 
     // the peppered secret HMAC is deterministic and keyed by both inputs
@@ -791,15 +1137,24 @@ mod tests {
     fn reserve_estimates() {
         // Object writes reserve one write, their byte size, a per-op SOL estimate,
         // and meter capacity.
-        for op in [WriteOp::Put, WriteOp::CompleteMultipart] {
+        for op in [WriteOp::Put, WriteOp::UploadPart] {
             assert!(op.is_cost_bearing());
             let request = op.reserve_request(4096);
-            assert_eq!(request.writes, 1);
             assert_eq!(request.bytes, 4096);
             assert_eq!(request.sol, ESTIMATED_LAMPORTS_PER_OP);
             assert!(request.is_onchain);
             assert!(request.meters_capacity);
         }
+        assert_eq!(WriteOp::Put.reserve_request(4096).writes, 1);
+
+        // A part takes no write of its own, and the completion that names the
+        // parts takes the write but none of the bytes they already billed.
+        assert_eq!(WriteOp::UploadPart.reserve_request(4096).writes, 0);
+        assert!(WriteOp::CompleteMultipart.is_cost_bearing());
+        let complete = WriteOp::CompleteMultipart.reserve_request(4096);
+        assert_eq!(complete.writes, 1);
+        assert_eq!(complete.bytes, 0);
+        assert_eq!(complete.sol, ESTIMATED_LAMPORTS_PER_OP);
 
         // Delete reserves only the SOL fee (it frees space; not a "put").
         assert!(WriteOp::Delete.is_cost_bearing());
@@ -810,9 +1165,8 @@ mod tests {
         assert!(delete.is_onchain);
         assert!(!delete.meters_capacity);
 
-        // Minting an upload id / buffering a part is not cost-bearing and reserves
-        // nothing.
-        for op in [WriteOp::CreateMultipart, WriteOp::UploadPart] {
+        // Minting an upload id is not cost-bearing and reserves nothing.
+        for op in [WriteOp::CreateMultipart, WriteOp::Configure] {
             assert!(!op.is_cost_bearing());
             let request = op.reserve_request(8192);
             assert_eq!(request.writes, 0);
@@ -853,6 +1207,8 @@ mod tests {
             true,
             &Auth::Anonymous,
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -873,6 +1229,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -890,6 +1248,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -908,6 +1268,8 @@ mod tests {
             true,
             &verified("BOOTSTRAP"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -931,6 +1293,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -952,6 +1316,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -974,6 +1340,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1001,6 +1369,8 @@ mod tests {
             true,
             &verified("AKID"),
             other_bucket,
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1018,9 +1388,11 @@ mod tests {
         let decision = decide(
             &FakeReads {
                 credential: Ok(Some(credential)),
+                owner: Ok(Some(principal)),
                 policy: Ok(PolicyDecision {
                     is_allowed: false,
                     reason: "explicit deny rule".to_string(),
+                    rule: None,
                 }),
                 ..FakeReads::default()
             },
@@ -1028,6 +1400,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1044,9 +1418,11 @@ mod tests {
         let decision = decide(
             &FakeReads {
                 credential: Ok(Some(credential)),
+                owner: Ok(Some(principal)),
                 policy: Ok(PolicyDecision {
                     is_allowed: true,
                     reason: "allow rule".to_string(),
+                    rule: None,
                 }),
                 ..FakeReads::default()
             },
@@ -1054,6 +1430,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1077,6 +1455,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1097,6 +1477,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1111,6 +1493,7 @@ mod tests {
         let credential = active_credential(principal, CredentialCaps::all(), CredentialScope::AnyOwned);
         let reads = FakeReads {
             credential: Ok(Some(credential)),
+            owner: Ok(Some(principal)),
             policy: Err("rocksdb unavailable".to_string()),
             ..FakeReads::default()
         };
@@ -1120,6 +1503,8 @@ mod tests {
             true,
             &verified("AKID"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1128,6 +1513,259 @@ mod tests {
         assert_eq!(decision.owner, principal);
     }
 
+    // --- read decisions ---------------------------------------------------------
+
+    fn private() -> FakeReads {
+        FakeReads {
+            access: Ok(Some(BucketAccess::Private)),
+            ..FakeReads::default()
+        }
+    }
+
+    // anyone reads a public bucket, even with the write kill switch engaged
+    #[test]
+    fn public_read() {
+        let reads = FakeReads {
+            killed: Ok(true),
+            ..FakeReads::default()
+        };
+        let decision = decide_read(
+            &reads,
+            None,
+            &Auth::Anonymous,
+            Address::new_unique(),
+            None,
+            ReadOp::Get,
+            1_000,
+        );
+        assert!(decision.allowed);
+        assert_eq!(decision.owner, Address::default());
+    }
+
+    // a private bucket denies anonymous and unknown readers
+    #[test]
+    fn private_unauthenticated() {
+        let bucket = Address::new_unique();
+        let decision = decide_read(
+            &private(),
+            None,
+            &Auth::Anonymous,
+            bucket,
+            None,
+            ReadOp::List,
+            1_000,
+        );
+        assert!(!decision.allowed);
+        assert!(decision.reason.contains("anonymous"));
+
+        let decision = decide_read(
+            &private(),
+            None,
+            &verified("AKID"),
+            bucket,
+            None,
+            ReadOp::Get,
+            1_000,
+        );
+        assert!(!decision.allowed);
+        assert!(decision.reason.contains("no active credential"));
+
+        let decision = decide_read(
+            &private(),
+            Some("BOOT"),
+            &verified("BOOT"),
+            bucket,
+            None,
+            ReadOp::Get,
+            1_000,
+        );
+        assert!(decision.allowed);
+    }
+
+    // a private read needs the matching cap and a scope covering the bucket
+    #[test]
+    fn private_scope_and_caps() {
+        let principal = Address::new_unique();
+        let bucket = Address::new_unique();
+        let write_only = CredentialCaps {
+            can_get: false,
+            can_list: false,
+            ..CredentialCaps::all()
+        };
+        let reads = FakeReads {
+            credential: Ok(Some(active_credential(
+                principal,
+                write_only,
+                CredentialScope::AnyOwned,
+            ))),
+            ..private()
+        };
+        let decision = decide_read(
+            &reads,
+            None,
+            &verified("AKID"),
+            bucket,
+            None,
+            ReadOp::Get,
+            1_000,
+        );
+        assert!(!decision.allowed);
+        assert!(decision.reason.contains("not permitted"));
+        assert_eq!(decision.owner, principal);
+
+        let scoped = active_credential(
+            principal,
+            CredentialCaps::all(),
+            CredentialScope::Buckets(vec![bucket]),
+        );
+        let reads = FakeReads {
+            credential: Ok(Some(scoped)),
+            ..private()
+        };
+        let decision = decide_read(
+            &reads,
+            None,
+            &verified("AKID"),
+            bucket,
+            None,
+            ReadOp::List,
+            1_000,
+        );
+        assert!(decision.allowed);
+        let other = Address::new_unique();
+        let decision = decide_read(
+            &reads,
+            None,
+            &verified("AKID"),
+            other,
+            None,
+            ReadOp::List,
+            1_000,
+        );
+        assert!(!decision.allowed);
+        assert!(decision.reason.contains("scope does not include"));
+    }
+
+    // an AnyOwned key reaches only buckets its principal owns, so one
+    // tenant's key neither reads nor writes another tenant's private bucket
+    #[test]
+    fn cross_tenant() {
+        let tenant = Address::new_unique();
+        let other_tenant = Address::new_unique();
+        let bucket = Address::new_unique();
+        let reads = FakeReads {
+            credential: Ok(Some(active_credential(
+                tenant,
+                CredentialCaps::all(),
+                CredentialScope::AnyOwned,
+            ))),
+            owner: Ok(Some(other_tenant)),
+            ..private()
+        };
+        for op in [ReadOp::Get, ReadOp::List] {
+            let decision =
+                decide_read(&reads, None, &verified("AKID"), bucket, None, op, 1_000);
+            assert!(!decision.allowed);
+            assert!(decision.reason.contains("scope does not include"));
+        }
+        let decision = decide(
+            &reads,
+            None,
+            true,
+            &verified("AKID"),
+            bucket,
+            None,
+            None,
+            WriteOp::Put,
+            1_000,
+        );
+        assert!(!decision.allowed);
+
+        // A bucket missing from the owner index is owned by nobody.
+        let unowned = FakeReads { owner: Ok(None), ..reads };
+        let decision =
+            decide_read(&unowned, None, &verified("AKID"), bucket, None, ReadOp::Get, 1_000);
+        assert!(!decision.allowed);
+
+        let owned = FakeReads { owner: Ok(Some(tenant)), ..unowned };
+        let decision =
+            decide_read(&owned, None, &verified("AKID"), bucket, None, ReadOp::Get, 1_000);
+        assert!(decision.allowed);
+
+        let broken = FakeReads { owner: Err("rocksdb unavailable".to_string()), ..owned };
+        let decision =
+            decide_read(&broken, None, &verified("AKID"), bucket, None, ReadOp::Get, 1_000);
+        assert!(!decision.allowed);
+        assert!(decision.reason.contains("bucket owner is unavailable"));
+    }
+
+    // against the real store, ownership follows the tape owner index
+    #[test]
+    fn real_cross_tenant() {
+        let store = memory_store();
+        let tenant = Address::new_unique();
+        let other_tenant = Address::new_unique();
+        let bucket = Address::new_unique();
+        store
+            .put_credential(
+                "AKID",
+                &active_credential(tenant, CredentialCaps::all(), CredentialScope::AnyOwned),
+            )
+            .expect("test setup");
+        store.put_bucket_access(bucket, BucketAccess::Private).expect("test setup");
+        store.set_tape_owner(bucket, other_tenant).expect("test setup");
+
+        let decision =
+            decide_read(&store, None, &verified("AKID"), bucket, None, ReadOp::List, 1_000);
+        assert!(!decision.allowed);
+
+        store.set_tape_owner(bucket, tenant).expect("test setup");
+        let decision =
+            decide_read(&store, None, &verified("AKID"), bucket, None, ReadOp::List, 1_000);
+        assert!(decision.allowed);
+    }
+
+    // a read rule can deny even on a public bucket; store errors deny
+    #[test]
+    fn read_policy_and_errors() {
+        let reads = FakeReads {
+            policy: Ok(PolicyDecision {
+                is_allowed: false,
+                reason: "no reads".to_string(),
+                rule: None,
+            }),
+            ..FakeReads::default()
+        };
+        let bucket = Address::new_unique();
+        let decision = decide_read(
+            &reads,
+            None,
+            &Auth::Anonymous,
+            bucket,
+            None,
+            ReadOp::Get,
+            1_000,
+        );
+        assert!(!decision.allowed);
+        assert_eq!(decision.reason, "no reads");
+
+        let reads = FakeReads {
+            access: Err("rocksdb unavailable".to_string()),
+            ..FakeReads::default()
+        };
+        let decision = decide_read(
+            &reads,
+            None,
+            &Auth::Anonymous,
+            bucket,
+            None,
+            ReadOp::Get,
+            1_000,
+        );
+        assert!(!decision.allowed);
+        assert!(decision.reason.contains("bucket access setting is unavailable"));
+    }
+
     // --- decision core against the real TapeStore (the production AuthzReads) ---
 
     fn memory_store() -> TapeStore<MemoryStore> {
@@ -1146,6 +1784,8 @@ mod tests {
             false,
             &verified("BOOT"),
             bucket,
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1158,6 +1798,8 @@ mod tests {
             true,
             &verified("BOOT"),
             bucket,
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1176,6 +1818,8 @@ mod tests {
             true,
             &verified("BOOT"),
             Address::new_unique(),
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1195,6 +1839,7 @@ mod tests {
                 &active_credential(principal, CredentialCaps::all(), CredentialScope::AnyOwned),
             )
             .expect("test setup");
+        store.set_tape_owner(bucket, principal).expect("test setup");
         // Deny-precedence: a deny rule on this subject wins even with default-allow.
         store
             .put_policy_rule(
@@ -1205,6 +1850,7 @@ mod tests {
                     action: PolicyAction::Put,
                     effect: PolicyEffect::Deny,
                     reason: "blocked by rule".to_string(),
+                    conditions: PolicyConditions::default(),
                 },
             )
             .expect("test setup");
@@ -1215,6 +1861,8 @@ mod tests {
             true,
             &verified("AKID"),
             bucket,
+            None,
+            None,
             WriteOp::Put,
             1_000,
         );
@@ -1223,6 +1871,131 @@ mod tests {
         assert_eq!(decision.owner, principal);
     }
 
+    // against the real store, a conditioned allow admits only writes under its
+    // prefix from its network, and the audited reason names the rule
+    #[test]
+    fn real_conditions() {
+        let store = memory_store();
+        let principal = Address::new_unique();
+        let bucket = Address::new_unique();
+        store
+            .put_credential(
+                "AKID",
+                &active_credential(principal, CredentialCaps::all(), CredentialScope::AnyOwned),
+            )
+            .expect("test setup");
+        store.set_tape_owner(bucket, principal).expect("test setup");
+        let network = CidrBlock::new("10.0.0.0".parse().unwrap(), 8).unwrap();
+        store
+            .put_policy_rule(
+                PolicyRuleKey::new(1, 7),
+                &PolicyRule {
+                    principal: Some(principal),
+                    bucket: None,
+                    action: PolicyAction::Put,
+                    effect: PolicyEffect::Allow,
+                    reason: "ci artifacts".to_string(),
+                    conditions: PolicyConditions {
+                        key: Some(KeyPattern::Prefix("artifacts/ci/".to_string())),
+                        source_cidrs: vec![network],
+                        ..PolicyConditions::default()
+                    },
+                },
+            )
+            .expect("test setup");
+
+        let inside = Auth::Verified(Principal {
+            access_key_id: "AKID".to_string(),
+            source_ip: Some("10.1.2.3".parse().unwrap()),
+        });
+        let decision = decide(
+            &store,
+            None,
+            false,
+            &inside,
+            bucket,
+            Some("artifacts/ci/build.tar"),
+            Some(1_024),
+            WriteOp::Put,
+            1_000,
+        );
+        assert!(decision.allowed);
+        assert_eq!(decision.audit_reason(), "ci artifacts (rule 1/7)");
+
+        let decision = decide(
+            &store,
+            None,
+            false,
+            &inside,
+            bucket,
+            Some("releases/build.tar"),
+            Some(1_024),
+            WriteOp::Put,
+            1_000,
+        );
+        assert!(!decision.allowed);
+        assert_eq!(decision.audit_reason(), "default-deny");
+
+        let outside = Auth::Verified(Principal {
+            access_key_id: "AKID".to_string(),
+            source_ip: Some("192.0.2.1".parse().unwrap()),
+        });
+        let decision = decide(
+            &store,
+            None,
+            false,
+            &outside,
+            bucket,
+            Some("artifacts/ci/build.tar"),
+            Some(1_024),
+            WriteOp::Put,
+            1_000,
+        );
+        assert!(!decision.allowed);
+    }
+
+    // against the real store, a catch-all write deny leaves reads alone
+    #[test]
+    fn real_read_default() {
+        let store = memory_store();
+        let bucket = Address::new_unique();
+        store
+            .put_policy_rule(
+                PolicyRuleKey::new(1, 1),
+                &PolicyRule {
+                    principal: None,
+                    bucket: None,
+                    action: PolicyAction::Any,
+                    effect: PolicyEffect::Deny,
+                    reason: "writes frozen".to_string(),
+                    conditions: PolicyConditions::default(),
+                },
+            )
+            .expect("test setup");
+        store.put_bucket_access(bucket, BucketAccess::Private).expect("test setup");
+
+        let decision = decide_read(
+            &store,
+            Some("BOOT"),
+            &verified("BOOT"),
+            bucket,
+            None,
+            ReadOp::Get,
+            1_000,
+        );
+        assert!(decision.allowed);
+        let decision = decide_read(
+            &store,
+            None,
+            &Auth::Anonymous,
+            bucket,
+            None,
+            ReadOp::Get,
+            1_000,
+        );
+        assert!(!decision.allowed);
+    }
+
     // --- ledger reserve-outcome mapping (step 5) and the deny→S3Error mapping ---
 
     // a granted reserve carries the reservation forward
@@ -0,0 +1,191 @@
+//! S3 server-side copy request parsing (CopyObject, UploadPartCopy)
+
+use axum::http::HeaderMap;
+
+use super::error::S3Error;
+use super::resolve::{VersionSelector, parse_version_selector};
+use super::sigv4::{percent_decode, query_param};
+use crate::http::handlers::object::ByteRange;
+
+/// Header naming the copy source as `[/]{bucket}/{key}`, URL-encoded
+pub const AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+/// Header selecting the source bytes an UploadPartCopy takes
+pub const AMZ_COPY_SOURCE_RANGE: &str = "x-amz-copy-source-range";
+/// Header choosing whether a copy keeps or replaces the source's headers
+const AMZ_METADATA_DIRECTIVE: &str = "x-amz-metadata-directive";
+
+/// The `(bucket, key)` a copy reads from, and the version it selects
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CopySource {
+    /// Source bucket label (a base58 tape address)
+    pub bucket: String,
+    /// Source object key
+    pub key: String,
+    /// Source version from `?versionId=`; the current version when absent
+    pub version: Option<VersionSelector>,
+}
+
+/// Where a CopyObject takes the destination's headers and user metadata from
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MetadataDirective {
+    /// Keep the source object's headers (the S3 default)
+    Copy,
+    /// Use the headers sent with the copy request
+    Replace,
+}
+
+/// Whether a PUT is a server-side copy rather than an upload.
+pub fn is_copy(headers: &HeaderMap) -> bool {
+    headers.contains_key(AMZ_COPY_SOURCE)
+}
+
+/// Parse the request's `x-amz-copy-source` header.
+pub fn copy_source(headers: &HeaderMap) -> Result<CopySource, S3Error> {
+    let value = headers
+        .get(AMZ_COPY_SOURCE)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| S3Error::InvalidRequest("missing or unreadable x-amz-copy-source".into()))?;
+    parse_copy_source(value)
+}
+
+/// Parse a copy source value. The query (`?versionId=`) is split off before
+/// decoding so an encoded `?` in the key stays part of the key.
+pub fn parse_copy_source(value: &str) -> Result<CopySource, S3Error> {
+    let (path, query) = match value.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (value, None),
+    };
+    let version = match query.filter(|query| !query.is_empty()) {
+        Some(query) => {
+            let value = query_param(query, "versionId").ok_or_else(|| {
+                S3Error::InvalidRequest("x-amz-copy-source only accepts ?versionId=".into())
+            })?;
+            Some(parse_version_selector(&value)?)
+        }
+        None => None,
+    };
+
+    let decoded = percent_decode(path);
+    let (bucket, key) = decoded
+        .trim_start_matches('/')
+        .split_once('/')
+        .ok_or_else(|| S3Error::InvalidRequest("x-amz-copy-source must be bucket/key".into()))?;
+    if bucket.is_empty() || key.is_empty() {
+        return Err(S3Error::InvalidRequest("x-amz-copy-source must be bucket/key".into()));
+    }
+
+    Ok(CopySource {
+        bucket: bucket.to_string(),
+        key: key.to_string(),
+        version,
+    })
+}
+
+/// Read `x-amz-metadata-directive`, defaulting to `COPY`.
+pub fn metadata_directive(headers: &HeaderMap) -> Result<MetadataDirective, S3Error> {
+    let Some(value) = headers.get(AMZ_METADATA_DIRECTIVE) else {
+        return Ok(MetadataDirective::Copy);
+    };
+    match value.to_str() {
+        Ok("COPY") => Ok(MetadataDirective::Copy),
+        Ok("REPLACE") => Ok(MetadataDirective::Replace),
+        _ => Err(S3Error::InvalidRequest(
+            "x-amz-metadata-directive must be COPY or REPLACE".into(),
+        )),
+    }
+}
+
+/// Resolve an UploadPartCopy `x-amz-copy-source-range` against a source of
+/// `total` bytes. Unlike a GET `Range`, both ends are required and a range
+/// past the source is an error rather than being clamped.
+pub fn parse_copy_range(value: &str, total: u64) -> Result<ByteRange, S3Error> {
+    let malformed =
+        || S3Error::InvalidRequest("x-amz-copy-source-range must be bytes=first-last".into());
+
+    let (first, last) = value
+        .trim()
+        .strip_prefix("bytes=")
+        .and_then(|spec| spec.split_once('-'))
+        .ok_or_else(malformed)?;
+    let first = first.trim().parse::<u64>().map_err(|_| malformed())?;
+    let last = last.trim().parse::<u64>().map_err(|_| malformed())?;
+    if first > last {
+        return Err(malformed());
+    }
+    if last >= total {
+        return Err(S3Error::InvalidRange(total));
+    }
+
+    Ok(ByteRange {
+        start: first,
+        end: last + 1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the source splits into bucket and decoded key, with or without a leading slash
+    #[test]
+    fn source_parse() {
+        let expected = CopySource {
+            bucket: "bucket".to_string(),
+            key: "photos/a b?.jpg".to_string(),
+            version: None,
+        };
+        assert_eq!(parse_copy_source("/bucket/photos/a%20b%3F.jpg").unwrap(), expected);
+        assert_eq!(parse_copy_source("bucket/photos/a%20b%3F.jpg").unwrap(), expected);
+        assert_eq!(parse_copy_source("bucket/photos/a%20b%3F.jpg?").unwrap(), expected);
+    }
+
+    // a versionId selects the source version, including the null version
+    #[test]
+    fn source_version() {
+        let source = parse_copy_source("bucket/key?versionId=42").unwrap();
+        assert_eq!(source.key, "key");
+        assert_eq!(source.version, Some(parse_version_selector("42").unwrap()));
+
+        let source = parse_copy_source("/bucket/key?versionId=null").unwrap();
+        assert_eq!(source.version, Some(VersionSelector::Null));
+    }
+
+    // a missing key, an unparsable version, or another query is rejected
+    #[test]
+    fn source_invalid() {
+        assert!(parse_copy_source("bucket").is_err());
+        assert!(parse_copy_source("/bucket/").is_err());
+        assert!(parse_copy_source("bucket/key?versionId=abc").is_err());
+        assert!(parse_copy_source("bucket/key?partNumber=1").is_err());
+    }
+
+    // the directive defaults to COPY and only accepts the two S3 values
+    #[test]
+    fn directive() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(metadata_directive(&headers).unwrap(), MetadataDirective::Copy);
+
+        headers.insert(AMZ_METADATA_DIRECTIVE, "REPLACE".parse().unwrap());
+        assert_eq!(metadata_directive(&headers).unwrap(), MetadataDirective::Replace);
+
+        headers.insert(AMZ_METADATA_DIRECTIVE, "MERGE".parse().unwrap());
+        assert!(metadata_directive(&headers).is_err());
+    }
+
+    // copy ranges are inclusive, complete, and must fit the source
+    #[test]
+    fn copy_range() {
+        assert_eq!(
+            parse_copy_range("bytes=0-99", 1000).unwrap(),
+            ByteRange { start: 0, end: 100 }
+        );
+        assert_eq!(
+            parse_copy_range("bytes=999-999", 1000).unwrap(),
+            ByteRange { start: 999, end: 1000 }
+        );
+        assert!(matches!(parse_copy_range("bytes=0-1000", 1000), Err(S3Error::InvalidRange(1000))));
+        assert!(matches!(parse_copy_range("bytes=10-5", 1000), Err(S3Error::InvalidRequest(_))));
+        assert!(matches!(parse_copy_range("bytes=-5", 1000), Err(S3Error::InvalidRequest(_))));
+        assert!(matches!(parse_copy_range("bytes=5-", 1000), Err(S3Error::InvalidRequest(_))));
+    }
+}
@@ -27,6 +27,10 @@ pub enum S3Error {
     /// The specified multipart upload id does not exist (unknown, already
     /// completed, or aborted). HTTP 404
     NoSuchUpload,
+    /// The specified object version does not exist. HTTP 404
+    NoSuchVersion,
+    /// The bucket has no lifecycle configuration. HTTP 404
+    NoSuchLifecycleConfiguration,
     /// Anonymous or under-privileged access is denied. HTTP 403
     AccessDenied(String),
     /// A signed request's signature did not verify. HTTP 403
@@ -37,10 +41,15 @@ pub enum S3Error {
     EntityTooLarge(String),
     /// A multipart part below the 5 MiB minimum (last part exempt). HTTP 400
     EntityTooSmall(String),
+    /// The encoded object headers and user metadata exceed the 2 KiB limit. HTTP 400
+    MetadataTooLarge(String),
     /// The request was malformed. HTTP 400
     InvalidRequest(String),
     /// A Range request that cannot be satisfied; carries the object size. HTTP 416
     InvalidRange(u64),
+    /// The method is not allowed against this resource, e.g. reading a delete
+    /// marker. HTTP 405
+    MethodNotAllowed(String),
     /// The caller is being rate limited; carries Retry-After seconds. HTTP 503
     SlowDown { retry_after_seconds: u64 },
     /// The operation is recognized but not implemented yet. HTTP 501
@@ -63,13 +72,17 @@ impl S3Error {
             Self::NoSuchBucket => "NoSuchBucket",
             Self::NoSuchKey => "NoSuchKey",
             Self::NoSuchUpload => "NoSuchUpload",
+            Self::NoSuchVersion => "NoSuchVersion",
+            Self::NoSuchLifecycleConfiguration => "NoSuchLifecycleConfiguration",
             Self::AccessDenied(_) => "AccessDenied",
             Self::SignatureDoesNotMatch => "SignatureDoesNotMatch",
             Self::ContentSha256Mismatch => "XAmzContentSHA256Mismatch",
             Self::EntityTooLarge(_) => "EntityTooLarge",
             Self::EntityTooSmall(_) => "EntityTooSmall",
+            Self::MetadataTooLarge(_) => "MetadataTooLarge",
             Self::InvalidRequest(_) => "InvalidRequest",
             Self::InvalidRange(_) => "InvalidRange",
+            Self::MethodNotAllowed(_) => "MethodNotAllowed",
             Self::SlowDown { .. } => "SlowDown",
             Self::NotImplemented(_) => "NotImplemented",
             Self::Internal(_) => "InternalError",
@@ -79,13 +92,19 @@ impl S3Error {
     /// The HTTP status code for this error
     pub fn status(&self) -> StatusCode {
         match self {
-            Self::NoSuchBucket | Self::NoSuchKey | Self::NoSuchUpload => StatusCode::NOT_FOUND,
+            Self::NoSuchBucket
+            | Self::NoSuchKey
+            | Self::NoSuchUpload
+            | Self::NoSuchVersion
+            | Self::NoSuchLifecycleConfiguration => StatusCode::NOT_FOUND,
             Self::AccessDenied(_) | Self::SignatureDoesNotMatch => StatusCode::FORBIDDEN,
             Self::ContentSha256Mismatch
             | Self::EntityTooLarge(_)
             | Self::EntityTooSmall(_)
+            | Self::MetadataTooLarge(_)
             | Self::InvalidRequest(_) => StatusCode::BAD_REQUEST,
             Self::InvalidRange(_) => StatusCode::RANGE_NOT_SATISFIABLE,
+            Self::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
             Self::SlowDown { .. } => StatusCode::SERVICE_UNAVAILABLE,
             Self::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
             Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -93,7 +112,7 @@ impl S3Error {
     }
 
     /// A human-readable `<Message>` for this error
-    fn message(&self) -> String {
+    pub fn message(&self) -> String {
         match self {
             Self::NoSuchBucket => "The specified bucket does not exist.".to_string(),
             Self::NoSuchKey => "The specified key does not exist.".to_string(),
@@ -102,6 +121,12 @@ impl S3Error {
                  or the upload may have been aborted or completed."
                     .to_string()
             }
+            Self::NoSuchVersion => {
+                "The specified version does not exist.".to_string()
+            }
+            Self::NoSuchLifecycleConfiguration => {
+                "The lifecycle configuration does not exist.".to_string()
+            }
             Self::SignatureDoesNotMatch => {
                 "The request signature we calculated does not match the signature you provided."
                     .to_string()
@@ -120,7 +145,9 @@ impl S3Error {
             Self::AccessDenied(detail)
             | Self::EntityTooLarge(detail)
             | Self::EntityTooSmall(detail)
+            | Self::MetadataTooLarge(detail)
             | Self::InvalidRequest(detail)
+            | Self::MethodNotAllowed(detail)
             | Self::NotImplemented(detail) => detail.clone(),
         }
     }
@@ -133,14 +160,18 @@ impl S3Error {
             Self::NoSuchBucket
             | Self::NoSuchKey
             | Self::NoSuchUpload
+            | Self::NoSuchVersion
+            | Self::NoSuchLifecycleConfiguration
             | Self::AccessDenied(_)
             | Self::SignatureDoesNotMatch
             | Self::ContentSha256Mismatch
             | Self::EntityTooLarge(_)
             | Self::EntityTooSmall(_)
+            | Self::MetadataTooLarge(_)
             | Self::InvalidRequest(_)
             | Self::SlowDown { .. }
             | Self::InvalidRange(_)
+            | Self::MethodNotAllowed(_)
             | Self::NotImplemented(_) => None,
         }
     }
@@ -153,6 +184,7 @@ impl From<RouteError> for S3Error {
             RouteError::NotFound => Self::NoSuchKey,
             RouteError::RangeNotSatisfiable(total) => Self::InvalidRange(total),
             RouteError::BadRequest(message) => Self::InvalidRequest(message),
+            RouteError::Forbidden(message) => Self::AccessDenied(message),
             RouteError::BadGateway(message) | RouteError::Internal(message) => {
                 Self::Internal(message)
             }
@@ -182,13 +214,17 @@ impl IntoResponse for S3Error {
             Self::NoSuchBucket
             | Self::NoSuchKey
             | Self::NoSuchUpload
+            | Self::NoSuchVersion
+            | Self::NoSuchLifecycleConfiguration
             | Self::AccessDenied(_)
             | Self::SignatureDoesNotMatch
             | Self::ContentSha256Mismatch
             | Self::EntityTooLarge(_)
             | Self::EntityTooSmall(_)
+            | Self::MetadataTooLarge(_)
             | Self::InvalidRequest(_)
             | Self::InvalidRange(_)
+            | Self::MethodNotAllowed(_)
             | Self::NotImplemented(_)
             | Self::Internal(_) => None,
         };
@@ -240,6 +276,10 @@ mod tests {
             S3Error::from(RouteError::BadRequest("x".into())),
             S3Error::InvalidRequest(_)
         ));
+        assert!(matches!(
+            S3Error::from(RouteError::Forbidden("x".into())),
+            S3Error::AccessDenied(_)
+        ));
         assert!(matches!(
             S3Error::from(RouteError::BadGateway("x".into())),
             S3Error::Internal(_)
@@ -280,6 +320,12 @@ mod tests {
             StatusCode::RANGE_NOT_SATISFIABLE
         );
         assert_eq!(S3Error::InvalidRange(1024).code(), "InvalidRange");
+        assert_eq!(S3Error::NoSuchVersion.status(), StatusCode::NOT_FOUND);
+        assert_eq!(S3Error::NoSuchLifecycleConfiguration.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            S3Error::MethodNotAllowed("x".into()).status(),
+            StatusCode::METHOD_NOT_ALLOWED
+        );
         assert_eq!(
             S3Error::NotImplemented("x".into()).status(),
             StatusCode::NOT_IMPLEMENTED
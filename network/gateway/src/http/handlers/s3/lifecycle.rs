@@ -0,0 +1,498 @@
+//! S3 bucket lifecycle: rule evaluation and the background executor
+//!
+//! Rules are stored per bucket (`LifecycleOps`). Each pass plans the
+//! expirations every configured bucket is due and carries them out through the
+//! delegate write path: an expired current object has its track deleted, or,
+//! in a versioned bucket, is hidden behind a delete marker; an expired
+//! noncurrent version has its track deleted. The admin control plane reports
+//! the same plan without acting on it.
+//!
+//! A failed expiration is held back for `failure_backoff_secs`, so a key that
+//! keeps failing cannot use up every pass's budget ahead of the rest.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rpc::Rpc;
+use store::Store;
+use tape_api::program::tapedrive::track_pda;
+use tape_crypto::address::Address;
+use tape_node::config::gateway::S3LifecycleConfig;
+use tape_node::context::NodeContext;
+use tape_protocol::Api;
+use tape_sdk::error::TapedriveError;
+use tape_store::error::TapeStoreError;
+use tape_store::ops::{LifecycleOps, ObjectListOps, ObjectVersionOps};
+use tape_store::types::{
+    BucketLifecycle, LifecycleRule, ObjectListEntry, ObjectVersion, ObjectVersionId,
+};
+use tape_store::TapeStore;
+use tokio_util::sync::CancellationToken;
+
+use super::clock::{SECONDS_PER_DAY, now_unix};
+use super::write::S3WriteContext;
+
+/// Names or versions scanned per store page while planning
+const PLAN_PAGE_SIZE: usize = 1000;
+
+/// What a due expiration does
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExpirationAction {
+    /// Delete the current object's track (unversioned bucket)
+    DeleteObject,
+    /// Hide the current object behind a delete marker (versioned bucket)
+    PlaceDeleteMarker,
+    /// Delete a noncurrent version's track
+    DeleteVersion,
+}
+
+impl ExpirationAction {
+    /// Operator-facing name used in dry-run reports and logs
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::DeleteObject => "delete-object",
+            Self::PlaceDeleteMarker => "place-delete-marker",
+            Self::DeleteVersion => "delete-version",
+        }
+    }
+}
+
+/// One expiration a lifecycle rule has made due
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Expiration {
+    /// Object key
+    pub key: Vec<u8>,
+    /// The version expiring (the current track for a current object)
+    pub version: ObjectVersionId,
+    /// Track a delete removes on chain
+    pub track: Address,
+    /// Id of the rule that made it due
+    pub rule_id: String,
+    /// What carrying it out does
+    pub action: ExpirationAction,
+}
+
+/// Outcome counts of one lifecycle pass
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LifecycleReport {
+    /// Expirations found due
+    pub planned: usize,
+    /// Expirations carried out
+    pub applied: usize,
+    /// Expirations that failed and are retried on the next pass
+    pub failed: usize,
+}
+
+/// Expirations that failed, keyed by track, with the time each may be
+/// retried. Lives for the executor loop; a restart retries everything.
+#[derive(Debug, Default)]
+pub struct HeldExpirations {
+    retry_at: HashMap<Address, i64>,
+}
+
+impl HeldExpirations {
+    /// Skip `track` until `now + backoff_secs`.
+    pub fn hold(&mut self, track: Address, now: i64, backoff_secs: u64) {
+        let backoff = i64::try_from(backoff_secs).unwrap_or(i64::MAX);
+        self.retry_at.insert(track, now.saturating_add(backoff));
+    }
+
+    /// Whether `track` is still waiting out its backoff at `now`.
+    pub fn is_held(&self, track: &Address, now: i64) -> bool {
+        self.retry_at.get(track).is_some_and(|retry_at| *retry_at > now)
+    }
+
+    /// Number of expirations held back.
+    pub fn len(&self) -> usize {
+        self.retry_at.len()
+    }
+
+    /// Whether nothing is held back.
+    pub fn is_empty(&self) -> bool {
+        self.retry_at.is_empty()
+    }
+
+    /// Drop holds whose backoff has run out.
+    pub fn release_expired(&mut self, now: i64) {
+        self.retry_at.retain(|_, retry_at| *retry_at > now);
+    }
+}
+
+/// Plan up to `limit` expirations `lifecycle` makes due in `bucket` at `now`.
+pub fn plan_bucket<Db: Store>(
+    store: &TapeStore<Db>,
+    bucket: Address,
+    lifecycle: &BucketLifecycle,
+    now: i64,
+    limit: usize,
+) -> Result<Vec<Expiration>, TapeStoreError> {
+    plan_bucket_except(store, bucket, lifecycle, now, limit, &HeldExpirations::default())
+}
+
+/// Like [`plan_bucket`], passing over expirations `held` still holds back so
+/// they do not count against `limit`.
+pub fn plan_bucket_except<Db: Store>(
+    store: &TapeStore<Db>,
+    bucket: Address,
+    lifecycle: &BucketLifecycle,
+    now: i64,
+    limit: usize,
+    held: &HeldExpirations,
+) -> Result<Vec<Expiration>, TapeStoreError> {
+    let mut planned = Vec::new();
+    let rules: Vec<&LifecycleRule> = lifecycle.rules.iter().filter(|rule| rule.enabled).collect();
+    if rules.is_empty() || limit == 0 {
+        return Ok(planned);
+    }
+    let versioned = store.get_bucket_versioning(bucket)?.is_some();
+
+    if rules.iter().any(|rule| rule.expiration_days.is_some()) {
+        let action = if versioned {
+            ExpirationAction::PlaceDeleteMarker
+        } else {
+            ExpirationAction::DeleteObject
+        };
+        let mut start: Option<Vec<u8>> = None;
+        loop {
+            let page = store.list_objects(bucket, b"", None, start.as_deref(), PLAN_PAGE_SIZE)?;
+            for (name, entry) in page.objects {
+                let rule = rules.iter().find(|rule| {
+                    rule.matches(&name, &entry.headers)
+                        && rule.expiration_days.is_some_and(|days| {
+                            entry.block_time.is_some_and(|written| is_due(written, days, now))
+                        })
+                });
+                if let Some(rule) = rule {
+                    let expiration = current_expiration(name, &entry, &rule.id, action);
+                    if held.is_held(&expiration.track, now) {
+                        continue;
+                    }
+                    planned.push(expiration);
+                    if planned.len() >= limit {
+                        return Ok(planned);
+                    }
+                }
+            }
+            match page.next {
+                Some(next) if page.is_truncated => start = Some(next),
+                _ => break,
+            }
+        }
+    }
+
+    if versioned && rules.iter().any(|rule| rule.noncurrent_days.is_some()) {
+        // A version stops being current when the next newer one is written, so
+        // each version's age is read off the row listed just before it.
+        let mut newer: Option<(Vec<u8>, Option<i64>)> = None;
+        let mut after: Option<(Vec<u8>, ObjectVersionId)> = None;
+        loop {
+            let page = store.list_object_versions(
+                bucket,
+                b"",
+                after.as_ref().map(|(name, version)| (name.as_slice(), Some(*version))),
+                PLAN_PAGE_SIZE,
+            )?;
+            for row in page.versions {
+                let created = match &row.value {
+                    ObjectVersion::Object(entry) => entry.block_time,
+                    ObjectVersion::DeleteMarker { time } => Some(*time),
+                };
+                let superseded = match &newer {
+                    Some((name, time)) if *name == row.name => *time,
+                    _ => None,
+                };
+                if let (false, Some(superseded), ObjectVersion::Object(entry)) =
+                    (row.is_latest, superseded, &row.value)
+                {
+                    let rule = rules.iter().find(|rule| {
+                        rule.matches(&row.name, &entry.headers)
+                            && rule
+                                .noncurrent_days
+                                .is_some_and(|days| is_due(superseded, days, now))
+                    });
+                    if let Some(rule) = rule {
+                        let track = track_pda(entry.data_tape, entry.track_number).0;
+                        if !held.is_held(&track, now) {
+                            planned.push(Expiration {
+                                key: row.name.clone(),
+                                version: row.version,
+                                track,
+                                rule_id: rule.id.clone(),
+                                action: ExpirationAction::DeleteVersion,
+                            });
+                            if planned.len() >= limit {
+                                return Ok(planned);
+                            }
+                        }
+                    }
+                }
+                newer = Some((row.name, created));
+            }
+            match page.next {
+                Some(next) if page.is_truncated => after = Some(next),
+                _ => break,
+            }
+        }
+    }
+
+    Ok(planned)
+}
+
+fn current_expiration(
+    key: Vec<u8>,
+    entry: &ObjectListEntry,
+    rule_id: &str,
+    action: ExpirationAction,
+) -> Expiration {
+    Expiration {
+        key,
+        version: ObjectVersionId::track(entry.track_number),
+        track: track_pda(entry.data_tape, entry.track_number).0,
+        rule_id: rule_id.to_string(),
+        action,
+    }
+}
+
+/// Whether something dated `since` is at least `days` old at `now`.
+fn is_due(since: i64, days: u32, now: i64) -> bool {
+    now.saturating_sub(since) >= i64::from(days) * SECONDS_PER_DAY
+}
+
+/// Run one lifecycle pass over every configured bucket, carrying out at most
+/// `config.max_actions_per_pass` expirations. Failures are added to `held`.
+pub async fn run_pass<Db, Cluster, Blockchain>(
+    context: &NodeContext<Db, Cluster, Blockchain>,
+    write_ctx: &S3WriteContext,
+    config: &S3LifecycleConfig,
+    held: &mut HeldExpirations,
+) -> Result<LifecycleReport, TapeStoreError>
+where
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+{
+    let store = context.store.as_ref();
+    let now = now_unix();
+    let backoff = config.failure_backoff_secs;
+    let mut report = LifecycleReport::default();
+    held.release_expired(now);
+
+    for (bucket, lifecycle) in store.list_bucket_lifecycles()? {
+        let remaining = config.max_actions_per_pass.saturating_sub(report.planned);
+        if remaining == 0 {
+            break;
+        }
+        let planned = plan_bucket_except(store, bucket, &lifecycle, now, remaining, held)?;
+        report.planned += planned.len();
+
+        let mut tracks: Vec<Address> = Vec::new();
+        for expiration in &planned {
+            match expiration.action {
+                ExpirationAction::PlaceDeleteMarker => {
+                    match write_ctx
+                        .place_delete_marker(context, bucket, &expiration.key)
+                        .await
+                    {
+                        Ok(()) => report.applied += 1,
+                        Err(error) => {
+                            report.failed += 1;
+                            held.hold(expiration.track, now, backoff);
+                            tracing::warn!(%bucket, %error, "s3 lifecycle: delete marker failed");
+                        }
+                    }
+                }
+                ExpirationAction::DeleteObject | ExpirationAction::DeleteVersion => {
+                    tracks.push(expiration.track);
+                }
+            }
+        }
+        if tracks.is_empty() {
+            continue;
+        }
+
+        match write_ctx.delete_objects(context, bucket, &tracks).await {
+            Ok(results) => {
+                for (track, result) in tracks.iter().zip(results) {
+                    match result {
+                        // Already deleted by someone else: nothing left to expire.
+                        Ok(()) | Err(TapedriveError::NotFound) => report.applied += 1,
+                        Err(error) => {
+                            report.failed += 1;
+                            held.hold(*track, now, backoff);
+                            tracing::warn!(%bucket, %error, "s3 lifecycle: track delete failed");
+                        }
+                    }
+                }
+            }
+            Err(error) => {
+                report.failed += tracks.len();
+                for track in &tracks {
+                    held.hold(*track, now, backoff);
+                }
+                tracing::warn!(%bucket, %error, "s3 lifecycle: track deletes failed");
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Background loop that runs a lifecycle pass every `config.interval_secs`.
+pub async fn lifecycle_loop<Db, Cluster, Blockchain>(
+    context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    write_ctx: Arc<S3WriteContext>,
+    config: S3LifecycleConfig,
+    cancel: CancellationToken,
+) where
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+{
+    let mut held = HeldExpirations::default();
+    let mut ticker = tokio::time::interval(Duration::from_secs(config.interval_secs.max(1)));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            // Safe: `cancelled` is cancellation-safe and holds no state; losing this
+            // branch just means another branch ran first and the loop re-selects.
+            _ = cancel.cancelled() => break,
+            // Safe: `Interval::tick` is cancellation-safe, and the pass runs in the
+            // branch body after the tick resolves, so cancellation is only observed
+            // between passes and never interrupts one.
+            _ = ticker.tick() => {
+                match run_pass(&context, &write_ctx, &config, &mut held).await {
+                    Ok(report) if report.planned > 0 => tracing::info!(
+                        planned = report.planned,
+                        applied = report.applied,
+                        failed = report.failed,
+                        held = held.len(),
+                        "s3 lifecycle pass"
+                    ),
+                    Ok(_) => {}
+                    Err(error) => tracing::error!(%error, "s3 lifecycle: pass failed"),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store_memory::MemoryStore;
+    use tape_core::types::{ContentType, ObjectHeaders, SlotNumber, StorageUnits, TrackNumber};
+    use tape_crypto::Hash;
+    use tape_store::types::VersioningStatus;
+
+    const DAY: i64 = SECONDS_PER_DAY;
+
+    fn entry(bucket: Address, n: u64, written: i64) -> ObjectListEntry {
+        ObjectListEntry {
+            size: StorageUnits(n),
+            etag: Hash::new_unique(),
+            block_time: Some(written),
+            slot: SlotNumber(n),
+            data_tape: bucket,
+            track_number: TrackNumber(n),
+            kind: 1,
+            content_type: ContentType::Unknown,
+            headers: ObjectHeaders::default(),
+        }
+    }
+
+    fn rule(prefix: &str, expiration: Option<u32>, noncurrent: Option<u32>) -> LifecycleRule {
+        LifecycleRule {
+            id: format!("rule-{prefix}"),
+            enabled: true,
+            prefix: prefix.to_string(),
+            tags: Vec::new(),
+            expiration_days: expiration,
+            noncurrent_days: noncurrent,
+        }
+    }
+
+    // only current objects under the rule's prefix and past its age expire
+    #[test]
+    fn plans_current_expirations() {
+        let store = TapeStore::new(MemoryStore::new());
+        let bucket = Address::new_unique();
+        store.put_object_entry(bucket, b"logs/old", entry(bucket, 1, 0)).unwrap();
+        store.put_object_entry(bucket, b"logs/new", entry(bucket, 2, 9 * DAY)).unwrap();
+        store.put_object_entry(bucket, b"data/old", entry(bucket, 3, 0)).unwrap();
+        let lifecycle = BucketLifecycle {
+            rules: vec![rule("logs/", Some(7), None)],
+        };
+
+        let planned = plan_bucket(&store, bucket, &lifecycle, 10 * DAY, 100).unwrap();
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].key, b"logs/old".to_vec());
+        assert_eq!(planned[0].action, ExpirationAction::DeleteObject);
+        assert_eq!(planned[0].track, track_pda(bucket, TrackNumber(1)).0);
+
+        let mut disabled = lifecycle.clone();
+        disabled.rules[0].enabled = false;
+        assert!(plan_bucket(&store, bucket, &disabled, 10 * DAY, 100).unwrap().is_empty());
+    }
+
+    // a versioned bucket hides expired objects and deletes old noncurrent versions
+    #[test]
+    fn plans_versioned_expirations() {
+        let store = TapeStore::new(MemoryStore::new());
+        let bucket = Address::new_unique();
+        store.put_bucket_versioning(bucket, VersioningStatus::Enabled).unwrap();
+        for (n, written) in [(1, 0), (2, 2 * DAY), (3, 9 * DAY)] {
+            store
+                .put_object_version(
+                    bucket,
+                    b"k",
+                    ObjectVersionId::track(TrackNumber(n)),
+                    &ObjectVersion::Object(entry(bucket, n, written)),
+                )
+                .unwrap();
+        }
+        store.restore_latest_version(bucket, b"k").unwrap();
+        let lifecycle = BucketLifecycle {
+            rules: vec![rule("", Some(30), Some(5))],
+        };
+
+        // Track 1 was superseded on day 2 and track 2 on day 9; only the first
+        // has been noncurrent for five days by day 10.
+        let planned = plan_bucket(&store, bucket, &lifecycle, 10 * DAY, 100).unwrap();
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].version, ObjectVersionId::track(TrackNumber(1)));
+        assert_eq!(planned[0].action, ExpirationAction::DeleteVersion);
+
+        let planned = plan_bucket(&store, bucket, &lifecycle, 40 * DAY, 100).unwrap();
+        assert_eq!(planned.len(), 3);
+        assert_eq!(planned[0].action, ExpirationAction::PlaceDeleteMarker);
+        assert_eq!(planned[0].version, ObjectVersionId::track(TrackNumber(3)));
+
+        let capped = plan_bucket(&store, bucket, &lifecycle, 40 * DAY, 2).unwrap();
+        assert_eq!(capped.len(), 2);
+    }
+
+    // a held expiration is passed over until its backoff runs out
+    #[test]
+    fn plans_past_held_expirations() {
+        let store = TapeStore::new(MemoryStore::new());
+        let bucket = Address::new_unique();
+        store.put_object_entry(bucket, b"a", entry(bucket, 1, 0)).unwrap();
+        store.put_object_entry(bucket, b"b", entry(bucket, 2, 0)).unwrap();
+        let lifecycle = BucketLifecycle {
+            rules: vec![rule("", Some(7), None)],
+        };
+
+        let mut held = HeldExpirations::default();
+        held.hold(track_pda(bucket, TrackNumber(1)).0, 10 * DAY, DAY as u64);
+
+        let planned = plan_bucket_except(&store, bucket, &lifecycle, 10 * DAY, 1, &held).unwrap();
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].key, b"b".to_vec());
+
+        held.release_expired(11 * DAY);
+        assert!(held.is_empty());
+        let planned = plan_bucket_except(&store, bucket, &lifecycle, 11 * DAY, 1, &held).unwrap();
+        assert_eq!(planned[0].key, b"a".to_vec());
+    }
+}
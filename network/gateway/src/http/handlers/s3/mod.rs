@@ -5,11 +5,14 @@ pub mod admin;
 pub mod authz;
 pub mod chunked;
 pub mod clock;
+pub mod copy;
 pub mod error;
+pub mod lifecycle;
 pub mod multipart;
 pub mod resolve;
 pub mod response;
 pub mod routes;
 pub mod sigv4;
+pub mod sse;
 pub mod write;
 pub mod xml;
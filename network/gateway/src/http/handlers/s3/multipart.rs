@@ -1,17 +1,21 @@
 //! Durable S3 multipart upload state machine
+//!
+//! Each part is written to its own chunk tracks when it is uploaded, and only
+//! the tracks are recorded here. Completion links the listed parts' tracks
+//! under one manifest, so no object is ever held in memory whole.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use hex::encode;
 
-use tape_core::types::ContentType;
+use tape_core::types::{ObjectHeaders, TrackNumber};
 use tape_crypto::address::Address;
-use tape_crypto::hash::{hash, hashv};
+use tape_crypto::hash::hashv;
 use tape_crypto::Hash;
 use tape_store::ops::MultipartOps;
-use tape_store::types::{MultipartPart, MultipartUpload};
+use tape_store::types::{MultipartPart, MultipartPartChunks, MultipartUpload, PartChunk};
 
 use super::clock::now_unix;
 use super::error::S3Error;
@@ -34,14 +38,30 @@ pub struct CompletedPartRef {
     pub etag: String,
 }
 
-/// A multipart upload assembled and ready to write
+/// Where one listed part's bytes are, as completion links them
+pub enum PartSource {
+    /// The chunk tracks the part was written to at UploadPart, in order
+    Chunks(Vec<PartChunk>),
+    /// The bytes of a part buffered in the store before parts were written to
+    /// chunk tracks; completion writes them out first
+    Buffered(Vec<u8>),
+}
+
+/// A multipart upload validated and ready to link
 pub struct AssembledUpload {
     /// Object key (the on-chain track name)
     pub key: String,
-    /// Content type to apply to the written object
-    pub content_type: ContentType,
-    /// Concatenated part bytes in part-number order
-    pub data: Vec<u8>,
+    /// Headers and user metadata to apply to the written object
+    pub headers: ObjectHeaders,
+    /// The listed parts in part-number order
+    pub parts: Vec<PartSource>,
+    /// Object size in bytes
+    pub size: u64,
+    /// S3 multipart ETag over the listed parts' ETags
+    pub etag: String,
+    /// Chunk tracks of uploaded parts the completion left out, which nothing
+    /// references once the object is linked
+    pub unlisted: Vec<TrackNumber>,
 }
 
 /// A snapshot of one persisted part, for ListParts rendering
@@ -70,14 +90,14 @@ pub fn create_upload(
     store: &impl MultipartOps,
     bucket: Address,
     key: String,
-    content_type: ContentType,
+    headers: ObjectHeaders,
     principal: Address,
 ) -> Result<String, S3Error> {
     let upload_id = mint_upload_id(&bucket, &key);
     let upload = MultipartUpload {
         bucket,
         key,
-        content_type,
+        headers,
         initiated: now_unix(),
         principal,
     };
@@ -102,20 +122,21 @@ pub fn count_open_uploads(
     Ok(count)
 }
 
-/// Persist one part's bytes under `upload_id` (UploadPart) and return its ETag.
+/// Check that a `size`-byte part may be uploaded under `upload_id`, before any
+/// of its bytes are written to chunk tracks (UploadPart).
 ///
-/// The upload's total staged bytes are capped at `max_object_bytes`: buffered
-/// parts never exceed the largest object that could be assembled from them, so a
+/// The upload's total staged bytes are capped at `max_object_bytes`: written
+/// parts never exceed the largest object that could be linked from them, so a
 /// caller cannot stage unbounded data ahead of completion.
-pub fn put_part(
+pub fn check_part(
     store: &impl MultipartOps,
     upload_id: &str,
     bucket: Address,
     key: &str,
     part_number: u32,
-    data: Vec<u8>,
+    size: u64,
     max_object_bytes: usize,
-) -> Result<Hash, S3Error> {
+) -> Result<(), S3Error> {
     if !(MIN_PART_NUMBER..=MAX_PART_NUMBER).contains(&part_number) {
         return Err(S3Error::InvalidRequest(format!(
             "partNumber must be between {MIN_PART_NUMBER} and {MAX_PART_NUMBER}"
@@ -132,22 +153,33 @@ pub fn put_part(
             staged = staged.saturating_add(part.size);
         }
     }
-    let projected = staged.saturating_add(data.len() as u64);
+    let projected = staged.saturating_add(size);
     if projected > max_object_bytes as u64 {
         return Err(S3Error::EntityTooLarge(format!(
             "this upload's staged bytes ({projected}) would exceed the maximum object size of {max_object_bytes} bytes"
         )));
     }
+    Ok(())
+}
 
-    let etag = hash(&data);
-    let part = MultipartPart {
-        part_number,
-        etag,
-        last_modified: now_unix(),
-        size: data.len() as u64,
-    };
-    store.put_multipart_part(upload_id, &part, data).map_err(store_error)?;
-    Ok(etag)
+/// Record a part whose bytes were written to `chunks` under `upload_id`
+/// (UploadPart), returning the chunk tracks of the part it replaced.
+///
+/// The upload is looked up again, since it may have been completed or aborted
+/// while the part was being written; the caller then owns the new tracks.
+pub fn record_part(
+    store: &impl MultipartOps,
+    upload_id: &str,
+    bucket: Address,
+    key: &str,
+    part: &MultipartPart,
+    chunks: &MultipartPartChunks,
+) -> Result<Vec<TrackNumber>, S3Error> {
+    load_upload(store, upload_id, bucket, key)?;
+    let replaced = store
+        .put_multipart_part(upload_id, part, chunks)
+        .map_err(store_error)?;
+    Ok(replaced.map(|replaced| track_numbers(&replaced)).unwrap_or_default())
 }
 
 /// Snapshot the persisted parts of `upload_id` for ListParts
@@ -175,7 +207,9 @@ pub fn list_parts(
     })
 }
 
-/// Discard `upload_id` and its persisted parts (AbortMultipartUpload)
+/// Discard `upload_id` and its persisted parts (AbortMultipartUpload),
+/// returning the chunk tracks its parts were written to for the caller to
+/// delete.
 ///
 /// Only the principal that opened the upload may abort it, so a stranger holding
 /// the upload id cannot destroy another tenant's in-flight upload.
@@ -185,18 +219,29 @@ pub fn abort(
     bucket: Address,
     key: &str,
     principal: Address,
-) -> Result<(), S3Error> {
+) -> Result<Vec<TrackNumber>, S3Error> {
     let upload = load_upload(store, upload_id, bucket, key)?;
     if upload.principal != principal {
         return Err(S3Error::AccessDenied(
             "this multipart upload belongs to another principal".to_string(),
         ));
     }
-    store.delete_multipart_upload(upload_id).map_err(store_error)
+    let mut tracks = Vec::new();
+    for part in store.list_multipart_parts(upload_id).map_err(store_error)? {
+        if let Some(chunks) = store
+            .get_multipart_part_chunks(upload_id, part.part_number)
+            .map_err(store_error)?
+        {
+            tracks.extend(track_numbers(&chunks));
+        }
+    }
+    store.delete_multipart_upload(upload_id).map_err(store_error)?;
+    Ok(tracks)
 }
 
-/// Validate the client's part list against the persisted parts and assemble the
-/// object bytes (the read side of CompleteMultipartUpload).
+/// Validate the client's part list against the persisted parts and gather
+/// where the listed parts' bytes are (the read side of
+/// CompleteMultipartUpload).
 pub fn assemble(
     store: &impl MultipartOps,
     upload_id: &str,
@@ -246,8 +291,8 @@ pub fn assemble(
     }
 
     // Every part except the last must meet S3's minimum part size. Validate from
-    // metadata sizes (no payloads read yet) so the object ceiling is enforced
-    // before any part bytes are loaded into memory.
+    // metadata sizes (no chunk records read yet) so the object ceiling is
+    // enforced before a buffered part is loaded into memory.
     let last_index = ordered.len() - 1;
     let mut total: usize = 0;
     for (index, stored_part) in ordered.iter().enumerate() {
@@ -266,29 +311,70 @@ pub fn assemble(
         )));
     }
 
-    let mut data = Vec::with_capacity(total);
+    let mut parts = Vec::with_capacity(ordered.len());
     for stored_part in &ordered {
-        let payload = store
-            .get_multipart_part_data(upload_id, stored_part.part_number)
+        parts.push(part_source(store, upload_id, stored_part.part_number)?);
+    }
+
+    let listed: HashSet<u32> = requested.iter().map(|part| part.part_number).collect();
+    let mut unlisted = Vec::new();
+    for stored_part in &stored {
+        if listed.contains(&stored_part.part_number) {
+            continue;
+        }
+        if let Some(chunks) = store
+            .get_multipart_part_chunks(upload_id, stored_part.part_number)
             .map_err(store_error)?
-            .ok_or_else(|| {
-                S3Error::Internal(format!(
-                    "multipart part {} payload missing",
-                    stored_part.part_number
-                ))
-            })?;
-        data.extend_from_slice(&payload);
+        {
+            unlisted.extend(track_numbers(&chunks));
+        }
     }
 
+    let etags: Vec<Hash> = ordered.iter().map(|part| part.etag).collect();
     Ok(AssembledUpload {
         key: upload.key,
-        content_type: upload.content_type,
-        data,
+        headers: upload.headers,
+        parts,
+        size: total as u64,
+        etag: multipart_etag(&etags),
+        unlisted,
     })
 }
 
-/// Drop a completed upload and its parts (called after the assembled object is
-/// written)
+/// The S3 ETag of an object linked from parts with `etags`, in order: the
+/// digest of the concatenated part digests, suffixed with the part count.
+pub fn multipart_etag(etags: &[Hash]) -> String {
+    let digests: Vec<&[u8]> = etags.iter().map(|etag| etag.as_ref()).collect();
+    format!("{}-{}", encode(hashv(&digests)), etags.len())
+}
+
+/// Where one stored part's bytes are: its chunk tracks, or for a part from
+/// before parts were written to tracks, its buffered payload
+fn part_source(
+    store: &impl MultipartOps,
+    upload_id: &str,
+    part_number: u32,
+) -> Result<PartSource, S3Error> {
+    if let Some(chunks) = store
+        .get_multipart_part_chunks(upload_id, part_number)
+        .map_err(store_error)?
+    {
+        return Ok(PartSource::Chunks(chunks.chunks));
+    }
+    let payload = store
+        .get_multipart_part_data(upload_id, part_number)
+        .map_err(store_error)?
+        .ok_or_else(|| {
+            S3Error::Internal(format!("multipart part {part_number} has no chunk tracks"))
+        })?;
+    Ok(PartSource::Buffered(payload))
+}
+
+fn track_numbers(chunks: &MultipartPartChunks) -> Vec<TrackNumber> {
+    chunks.chunks.iter().map(|chunk| chunk.track_number).collect()
+}
+
+/// Drop a completed upload and its parts (called after the object is linked)
 pub fn remove(store: &impl MultipartOps, upload_id: &str) -> Result<(), S3Error> {
     store.delete_multipart_upload(upload_id).map_err(store_error)
 }
@@ -370,10 +456,16 @@ fn store_error(error: impl std::fmt::Display) -> S3Error {
 #[cfg(test)]
 mod tests {
     use store_memory::MemoryStore;
+    use tape_crypto::hash::hash;
+    use tape_store::columns::{S3MultipartPartCol, S3MultipartPartDataCol};
+    use tape_store::types::{MultipartPartData, MultipartPartKey};
     use tape_store::TapeStore;
 
     use super::*;
 
+    /// Track numbers handed to test parts, as if each was written to one track
+    static NEXT_TRACK: AtomicU64 = AtomicU64::new(1);
+
     fn store() -> TapeStore<MemoryStore> {
         TapeStore::new(MemoryStore::new())
     }
@@ -402,6 +494,7 @@ mod tests {
     // any test stages.
     const TEST_MAX_OBJECT: usize = 64 * 1024 * 1024;
 
+    // Upload a part as UploadPart does, writing it to one fresh chunk track.
     fn put_part(
         store: &TapeStore<MemoryStore>,
         upload_id: &str,
@@ -410,7 +503,34 @@ mod tests {
         part_number: u32,
         data: Vec<u8>,
     ) -> Result<Hash, S3Error> {
-        super::put_part(store, upload_id, bucket, key, part_number, data, TEST_MAX_OBJECT)
+        let size = data.len() as u64;
+        check_part(store, upload_id, bucket, key, part_number, size, TEST_MAX_OBJECT)?;
+        let part = MultipartPart {
+            part_number,
+            etag: hash(&data),
+            last_modified: now_unix(),
+            size,
+        };
+        let chunks = MultipartPartChunks {
+            chunks: vec![PartChunk {
+                track_number: TrackNumber(NEXT_TRACK.fetch_add(1, Ordering::Relaxed)),
+                size,
+            }],
+        };
+        record_part(store, upload_id, bucket, key, &part, &chunks)?;
+        Ok(part.etag)
+    }
+
+    fn part_tracks(
+        store: &TapeStore<MemoryStore>,
+        upload_id: &str,
+        part_number: u32,
+    ) -> Vec<TrackNumber> {
+        let chunks = store
+            .get_multipart_part_chunks(upload_id, part_number)
+            .expect("chunks")
+            .expect("part has chunks");
+        track_numbers(&chunks)
     }
 
     fn assemble(
@@ -428,16 +548,18 @@ mod tests {
         upload_id: &str,
         bucket: Address,
         key: &str,
-    ) -> Result<(), S3Error> {
+    ) -> Result<Vec<TrackNumber>, S3Error> {
         super::abort(store, upload_id, bucket, key, Address::new_unique())
     }
 
-    // create, upload parts, then assemble round-trips the bytes
+    // create, upload parts, then assemble gathers their chunk tracks in order
     #[test]
     fn round_trip() {
         let store = store();
         let bucket = bucket();
-        let upload_id = create_upload(&store, bucket, "obj".into(), ContentType::TextPlain, principal())
+        let mut headers = ObjectHeaders::with_content_type("application/x-parquet");
+        headers.insert_metadata("team", "lake");
+        let upload_id = create_upload(&store, bucket, "obj".into(), headers.clone(), principal())
             .expect("create upload");
 
         let head = big_part();
@@ -453,10 +575,26 @@ mod tests {
             &[completed(1, etag1), completed(2, etag2)],
         )
         .expect("assemble");
-        let mut expected = head;
-        expected.extend_from_slice(b"world");
-        assert_eq!(assembled.data, expected);
+        let tracks: Vec<Vec<TrackNumber>> = assembled
+            .parts
+            .iter()
+            .map(|part| match part {
+                PartSource::Chunks(chunks) => {
+                    chunks.iter().map(|chunk| chunk.track_number).collect()
+                }
+                PartSource::Buffered(_) => panic!("parts are written to chunk tracks"),
+            })
+            .collect();
+        assert_eq!(
+            tracks,
+            vec![part_tracks(&store, &upload_id, 1), part_tracks(&store, &upload_id, 2)]
+        );
+        assert_eq!(assembled.size, head.len() as u64 + 5);
+        assert_eq!(assembled.etag, multipart_etag(&[etag1, etag2]));
+        assert!(assembled.etag.ends_with("-2"));
+        assert!(assembled.unlisted.is_empty());
         assert_eq!(assembled.key, "obj");
+        assert_eq!(assembled.headers, headers);
 
         // Assemble retains the upload until the caller removes it.
         remove(&store, &upload_id).expect("remove");
@@ -472,7 +610,7 @@ mod tests {
         let store = store();
         let bucket = bucket();
         let upload_id =
-            create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         let etag1 = put_part(&store, &upload_id, bucket, "obj", 1, b"small".to_vec()).expect("p1");
         let etag2 = put_part(&store, &upload_id, bucket, "obj", 2, b"tail".to_vec()).expect("p2");
         assert!(matches!(
@@ -493,12 +631,77 @@ mod tests {
         let store = store();
         let bucket = bucket();
         let upload_id =
-            create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         let etag = put_part(&store, &upload_id, bucket, "obj", 1, b"tiny".to_vec()).expect("p1");
 
         let assembled = assemble(&store, &upload_id, bucket, "obj", &[completed(1, etag)])
             .expect("single small part is allowed");
-        assert_eq!(assembled.data, b"tiny");
+        assert_eq!(assembled.size, 4);
+    }
+
+    // the tracks of replaced, unlisted and aborted parts are handed back for deletion
+    #[test]
+    fn orphaned_tracks() {
+        let store = store();
+        let bucket = bucket();
+        let owner = principal();
+        let upload_id =
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), owner).expect("create");
+        put_part(&store, &upload_id, bucket, "obj", 1, b"first".to_vec()).expect("p1");
+        let replaced = part_tracks(&store, &upload_id, 1);
+        let part = MultipartPart {
+            part_number: 1,
+            etag: hash(b"again"),
+            last_modified: now_unix(),
+            size: 5,
+        };
+        let chunks = MultipartPartChunks {
+            chunks: vec![PartChunk {
+                track_number: TrackNumber(NEXT_TRACK.fetch_add(1, Ordering::Relaxed)),
+                size: 5,
+            }],
+        };
+        let orphaned = record_part(&store, &upload_id, bucket, "obj", &part, &chunks).expect("p1");
+        assert_eq!(orphaned, replaced);
+
+        let etag2 = put_part(&store, &upload_id, bucket, "obj", 2, b"second".to_vec()).expect("p2");
+        let assembled =
+            assemble(&store, &upload_id, bucket, "obj", &[completed(2, etag2)]).expect("assemble");
+        assert_eq!(assembled.unlisted, part_tracks(&store, &upload_id, 1));
+
+        let mut written = part_tracks(&store, &upload_id, 1);
+        written.extend(part_tracks(&store, &upload_id, 2));
+        let aborted = super::abort(&store, &upload_id, bucket, "obj", owner).expect("abort");
+        assert_eq!(aborted, written);
+        assert!(store.get_multipart_part_chunks(&upload_id, 1).expect("chunks").is_none());
+    }
+
+    // a part buffered before parts went to chunk tracks is linked from its payload
+    #[test]
+    fn buffered_part() {
+        let store = store();
+        let bucket = bucket();
+        let upload_id =
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
+        let data = b"buffered".to_vec();
+        let part = MultipartPart {
+            part_number: 1,
+            etag: hash(&data),
+            last_modified: now_unix(),
+            size: data.len() as u64,
+        };
+        let part_key = MultipartPartKey::new(hash(upload_id.as_bytes()), 1);
+        store.put::<S3MultipartPartCol>(&part_key, &part).expect("put part");
+        store
+            .put::<S3MultipartPartDataCol>(&part_key, &MultipartPartData { data: data.clone() })
+            .expect("put payload");
+
+        let assembled = assemble(&store, &upload_id, bucket, "obj", &[completed(1, part.etag)])
+            .expect("assemble");
+        assert!(matches!(
+            &assembled.parts[..],
+            [PartSource::Buffered(payload)] if *payload == data
+        ));
     }
 
     // an unknown upload id is NoSuchUpload
@@ -522,7 +725,7 @@ mod tests {
         let store = store();
         let bucket = bucket();
         let upload_id =
-            create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         assert!(matches!(
             put_part(&store, &upload_id, bucket, "other", 1, b"x".to_vec()),
             Err(S3Error::NoSuchUpload)
@@ -535,7 +738,7 @@ mod tests {
         let store = store();
         let bucket = bucket();
         let upload_id =
-            create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         assert!(matches!(
             put_part(&store, &upload_id, bucket, "obj", 0, b"x".to_vec()),
             Err(S3Error::InvalidRequest(_))
@@ -552,7 +755,7 @@ mod tests {
         let store = store();
         let bucket = bucket();
         let upload_id =
-            create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         let etag1 = put_part(&store, &upload_id, bucket, "obj", 1, b"a".to_vec()).expect("p1");
         let etag2 = put_part(&store, &upload_id, bucket, "obj", 2, b"b".to_vec()).expect("p2");
         assert!(matches!(
@@ -573,7 +776,7 @@ mod tests {
         let store = store();
         let bucket = bucket();
         let upload_id =
-            create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         put_part(&store, &upload_id, bucket, "obj", 1, b"a".to_vec()).expect("p1");
         assert!(matches!(
             assemble(
@@ -593,7 +796,7 @@ mod tests {
         let store = store();
         let bucket = bucket();
         let upload_id =
-            create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         let etag1 = put_part(&store, &upload_id, bucket, "obj", 1, big_part()).expect("p1");
         assert!(matches!(
             assemble(
@@ -613,7 +816,7 @@ mod tests {
         let store = store();
         let bucket = bucket();
         let upload_id =
-            create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+            create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         put_part(&store, &upload_id, bucket, "obj", 2, b"bb".to_vec()).expect("p2");
         put_part(&store, &upload_id, bucket, "obj", 1, b"a".to_vec()).expect("p1");
         let listing = list_parts(&store, &upload_id, bucket, "obj").expect("list");
@@ -628,8 +831,8 @@ mod tests {
     fn unique_ids() {
         let store = store();
         let bucket = bucket();
-        let a = create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
-        let b = create_upload(&store, bucket, "obj".into(), ContentType::Unknown, principal()).expect("create");
+        let a = create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
+        let b = create_upload(&store, bucket, "obj".into(), ObjectHeaders::default(), principal()).expect("create");
         assert_ne!(a, b);
     }
 
@@ -640,9 +843,9 @@ mod tests {
         let alice = principal();
         let bob = principal();
         let alice_first =
-            create_upload(&store, bucket(), "a1".into(), ContentType::Unknown, alice).expect("create");
-        create_upload(&store, bucket(), "a2".into(), ContentType::Unknown, alice).expect("create");
-        create_upload(&store, bucket(), "b1".into(), ContentType::Unknown, bob).expect("create");
+            create_upload(&store, bucket(), "a1".into(), ObjectHeaders::default(), alice).expect("create");
+        create_upload(&store, bucket(), "a2".into(), ObjectHeaders::default(), alice).expect("create");
+        create_upload(&store, bucket(), "b1".into(), ObjectHeaders::default(), bob).expect("create");
 
         assert_eq!(count_open_uploads(&store, alice).expect("count"), 2);
         assert_eq!(count_open_uploads(&store, bob).expect("count"), 1);
@@ -3,20 +3,25 @@
 //! Maps an S3 bucket (a base58 tape address) and object key (a name in the
 //! store's per-bucket, name-ordered object index) to the backing data tape and
 //! track number. The existing decode/read path under `handlers/object/` then
-//! turns that into bytes.
+//! turns that into bytes. A `versionId` selects a prior version from the
+//! bucket's version history instead of the current entry; `null` selects the
+//! key's null version.
 
 // `resolve_object` and most `ResolvedObject` fields are consumed by the
 // GET/HEAD read pass; `parse_bucket` is already used by the listing handler.
 #![allow(dead_code)]
 
+use std::fmt;
+
 use rpc::Rpc;
 use store::Store;
 use tape_api::program::tapedrive::track_pda;
-use tape_core::types::{ContentType, TrackNumber};
+use tape_core::types::{ContentType, ObjectHeaders, TrackNumber};
 use tape_crypto::Hash;
 use tape_crypto::address::Address;
 use tape_protocol::Api;
-use tape_store::ops::ObjectListOps;
+use tape_store::ops::{ObjectListOps, ObjectVersionOps};
+use tape_store::types::{ObjectListEntry, ObjectVersion, ObjectVersionId};
 
 use super::error::S3Error;
 use crate::http::state::AppState;
@@ -39,6 +44,39 @@ pub struct ResolvedObject {
     pub block_time: Option<i64>,
     /// Object content type recorded in the listing index
     pub content_type: ContentType,
+    /// Exact content type, representation headers and user metadata
+    pub headers: ObjectHeaders,
+}
+
+/// The version id S3 gives an object written while versioning was off or
+/// suspended
+pub const NULL_VERSION_ID: &str = "null";
+
+/// A client-selected version: the key's null version, or a version by id
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VersionSelector {
+    Null,
+    Id(ObjectVersionId),
+}
+
+impl VersionSelector {
+    /// How a listed version is shown: its key's null version is `null`.
+    pub fn listed(version: ObjectVersionId, is_null: bool) -> Self {
+        if is_null {
+            Self::Null
+        } else {
+            Self::Id(version)
+        }
+    }
+}
+
+impl fmt::Display for VersionSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str(NULL_VERSION_ID),
+            Self::Id(version) => f.write_str(&format_version_id(*version)),
+        }
+    }
 }
 
 /// Parse an S3 bucket label as a base58 tape Address.
@@ -61,9 +99,55 @@ pub fn resolve_object<Db: Store, Cluster: Api, Blockchain: Rpc>(
         return Ok(None);
     };
 
+    Ok(Some(resolved_from_entry(entry)))
+}
+
+/// Resolve one version of an S3 `(bucket, key)` pair to its backing track.
+///
+/// An unknown version is `NoSuchVersion`; a delete marker has no body, so
+/// reading it is `MethodNotAllowed`, as in S3.
+pub fn resolve_object_version<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    bucket: Address,
+    key: &str,
+    version: ObjectVersionId,
+) -> Result<ResolvedObject, S3Error> {
+    let value = state
+        .context
+        .store
+        .get_object_version(bucket, key.as_bytes(), version)
+        .map_err(|error| S3Error::Internal(format!("object version lookup: {error}")))?;
+    match value {
+        Some(ObjectVersion::Object(entry)) => Ok(resolved_from_entry(entry)),
+        Some(ObjectVersion::DeleteMarker { .. }) => Err(S3Error::MethodNotAllowed(
+            "The specified version is a delete marker.".into(),
+        )),
+        None => Err(S3Error::NoSuchVersion),
+    }
+}
+
+/// The version `selector` names for `(bucket, key)`, or `None` when it asks
+/// for a null version the key does not have.
+pub fn select_version<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    bucket: Address,
+    key: &str,
+    selector: VersionSelector,
+) -> Result<Option<ObjectVersionId>, S3Error> {
+    match selector {
+        VersionSelector::Id(version) => Ok(Some(version)),
+        VersionSelector::Null => state
+            .context
+            .store
+            .get_null_version(bucket, key.as_bytes())
+            .map_err(|error| S3Error::Internal(format!("null version lookup: {error}"))),
+    }
+}
+
+fn resolved_from_entry(entry: ObjectListEntry) -> ResolvedObject {
     let track_address = track_pda(entry.data_tape, entry.track_number).0;
 
-    Ok(Some(ResolvedObject {
+    ResolvedObject {
         data_tape: entry.data_tape,
         track_number: entry.track_number,
         track_address,
@@ -71,7 +155,46 @@ pub fn resolve_object<Db: Store, Cluster: Api, Blockchain: Rpc>(
         etag: entry.etag,
         block_time: entry.block_time,
         content_type: entry.content_type,
-    }))
+        headers: entry.headers,
+    }
+}
+
+/// Render a version id as clients see it: the track number for an object
+/// version, `{track}.{marker}` for a delete marker.
+pub fn format_version_id(version: ObjectVersionId) -> String {
+    if version.is_delete_marker() {
+        format!("{}.{}", version.track_number.0, version.marker)
+    } else {
+        version.track_number.0.to_string()
+    }
+}
+
+/// Parse a client-supplied `versionId`, the inverse of `format_version_id`.
+pub fn parse_version_id(value: &str) -> Result<ObjectVersionId, S3Error> {
+    let invalid = || S3Error::InvalidRequest("Invalid version id specified".into());
+    let (track, marker) = match value.split_once('.') {
+        Some((track, marker)) => {
+            let marker = marker.parse::<u32>().map_err(|_| invalid())?;
+            if marker == 0 {
+                return Err(invalid());
+            }
+            (track, marker)
+        }
+        None => (value, 0),
+    };
+    let track_number = track.parse::<u64>().map_err(|_| invalid())?;
+    Ok(ObjectVersionId {
+        track_number: TrackNumber(track_number),
+        marker,
+    })
+}
+
+/// Parse a client-supplied `versionId` that may name the null version.
+pub fn parse_version_selector(value: &str) -> Result<VersionSelector, S3Error> {
+    if value == NULL_VERSION_ID {
+        return Ok(VersionSelector::Null);
+    }
+    parse_version_id(value).map(VersionSelector::Id)
 }
 
 #[cfg(test)]
@@ -93,4 +216,37 @@ mod tests {
             Err(S3Error::NoSuchBucket)
         ));
     }
+
+    // version ids round-trip for both object versions and delete markers
+    #[test]
+    fn version_id_roundtrip() {
+        let object = ObjectVersionId::track(TrackNumber(42));
+        let marker = ObjectVersionId {
+            track_number: TrackNumber(42),
+            marker: 3,
+        };
+        assert_eq!(format_version_id(object), "42");
+        assert_eq!(format_version_id(marker), "42.3");
+        assert_eq!(parse_version_id("42").unwrap(), object);
+        assert_eq!(parse_version_id("42.3").unwrap(), marker);
+    }
+
+    // `null` selects the null version and prints back unchanged
+    #[test]
+    fn version_selector_null() {
+        let object = ObjectVersionId::track(TrackNumber(42));
+        assert_eq!(parse_version_selector("null").unwrap(), VersionSelector::Null);
+        assert_eq!(parse_version_selector("42").unwrap(), VersionSelector::Id(object));
+        assert_eq!(VersionSelector::listed(object, true).to_string(), "null");
+        assert_eq!(VersionSelector::listed(object, false).to_string(), "42");
+        assert!(parse_version_selector("Null").is_err());
+    }
+
+    // malformed version ids are rejected rather than misread
+    #[test]
+    fn version_id_invalid() {
+        for value in ["", "null", "42.", "42.0", "-1", "4.2.1"] {
+            assert!(parse_version_id(value).is_err(), "{value}");
+        }
+    }
 }
@@ -6,7 +6,7 @@ use axum::response::{IntoResponse, Response};
 use tape_crypto::Hash;
 
 use super::error::S3Error;
-use super::resolve::ResolvedObject;
+use super::resolve::{ResolvedObject, VersionSelector};
 use super::xml::civil_from_unix;
 use crate::http::handlers::object::{
     ObjectResponseMetadata, ranged_object_headers, resolve_range,
@@ -14,6 +14,11 @@ use crate::http::handlers::object::{
 
 use super::clock::SECONDS_PER_DAY;
 
+/// Header naming the object version a response refers to
+const AMZ_VERSION_ID: &str = "x-amz-version-id";
+/// Header flagging that a DeleteObject placed (or removed) a delete marker
+const AMZ_DELETE_MARKER: &str = "x-amz-delete-marker";
+
 /// Weekday abbreviations, indexed `0 = Sunday`
 const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
 /// Month abbreviations, indexed `0 = January`
@@ -44,16 +49,26 @@ pub fn set_last_modified(headers: &mut HeaderMap, block_time: Option<i64>) {
     }
 }
 
+/// Insert `x-amz-version-id` for a response about a specific version.
+pub fn set_version_id(headers: &mut HeaderMap, version: Option<VersionSelector>) {
+    if let Some(version) = version {
+        if let Ok(value) = HeaderValue::from_str(&version.to_string()) {
+            headers.insert(AMZ_VERSION_ID, value);
+        }
+    }
+}
+
 /// Build the `HEAD /{bucket}/{key}` response: object headers (Content-Type,
 /// Content-Length, quoted ETag, Cache-Control) from the listing index entry plus
 /// `Last-Modified`, with an empty body. A `Range` request answers with the
 /// ranged Content-Length and `Content-Range`, exactly as the GET would.
 pub fn head_response(resolved: &ResolvedObject, range: Option<&str>) -> Result<Response, S3Error> {
-    // S3 content type comes from the listing index; no filename (no
-    // Content-Disposition) is set for S3 objects.
+    // S3 headers come from the listing index; no filename is derived for S3
+    // objects, so Content-Disposition is only sent when the writer stored one.
     let metadata = ObjectResponseMetadata {
         content_type: resolved.content_type,
         filename: None,
+        headers: resolved.headers.clone(),
     };
 
     let range = resolve_range(range, resolved.size).map_err(S3Error::from)?;
@@ -89,6 +104,17 @@ pub fn delete_response() -> Response {
     StatusCode::NO_CONTENT.into_response()
 }
 
+/// DeleteObject success in a versioned bucket: the version that was placed or
+/// removed, flagged when it is a delete marker.
+pub fn versioned_delete_response(version: VersionSelector, delete_marker: bool) -> Response {
+    let mut headers = HeaderMap::new();
+    set_version_id(&mut headers, Some(version));
+    if delete_marker {
+        headers.insert(AMZ_DELETE_MARKER, HeaderValue::from_static("true"));
+    }
+    (StatusCode::NO_CONTENT, headers).into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
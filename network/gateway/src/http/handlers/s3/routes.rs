@@ -1,10 +1,13 @@
 //! S3 request handlers and the Axum router for the S3 listener
 //!
 //! Hosts the per-route handlers (ListBuckets, ListObjectsV2, GetObject,
-//! HeadObject, PutObject, multipart upload, DeleteObject).
+//! HeadObject, PutObject, CopyObject, multipart upload including
+//! UploadPartCopy, DeleteObject, DeleteObjects, bucket versioning,
+//! ListObjectVersions and bucket lifecycle configuration). PutObject and
+//! GetObject honor SSE-C customer keys.
 
 use std::io;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
 use axum::Router;
@@ -15,42 +18,67 @@ use axum::middleware::from_fn_with_state;
 use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use base64::{decode, encode};
+use futures::TryStreamExt;
 use tokio::join;
 use tokio::task::JoinError;
+use tokio_util::io::StreamReader;
 
 use rpc::{Rpc, RpcError};
 use store::Store;
 use tape_api::instruction::MAX_NAME_LEN;
-use tape_api::program::tapedrive::tape_pda;
+use tape_api::program::tapedrive::track_pda;
+use tape_core::tape::TapeVersioning;
 use tape_core::track::types::CompressedTrack;
-use tape_core::types::{ContentType, StorageUnits};
-use tape_crypto::Hash;
+use tape_core::types::{
+    ContentType, MAX_OBJECT_HEADERS_LEN, ObjectHeaders, StorageUnits, TrackNumber,
+};
+use tape_crypto::hash::hash;
+use tape_crypto::{Address, Hash};
 use tape_protocol::Api;
 use tape_sdk::error::TapedriveError;
-use tape_store::ops::{CredentialOps, ObjectListOps, TapeOps};
-use tape_store::types::CredentialScope;
+use tape_store::error::TapeStoreError;
+use tape_store::ops::{CredentialOps, LifecycleOps, ObjectListOps, ObjectVersionOps, TapeOps};
+use tape_store::types::{
+    CredentialScope, MultipartPart, MultipartPartChunks, ObjectVersion, ObjectVersionId,
+    VersioningStatus,
+};
 
-use crate::http::handlers::object::{ObjectResponseMetadata, range_header, read_object_response};
+use crate::http::handlers::object::{
+    ByteRange, ObjectResponseMetadata, OpenedObject, USER_METADATA_PREFIX, check_object_key,
+    open_object, range_header, read_object_response, stream_range_bytes,
+};
 use crate::http::handlers::track::track_with_pending;
 use crate::http::state::AppState;
 use crate::meter::{GatewayMeterDecision, MeterCaller};
 use super::accounting;
-use super::authz::{Auth, WriteOp, WritePermit, authorize_multipart_read, authorize_write};
+use super::authz::{
+    Auth, ReadOp, WriteOp, WritePermit, authorize_multipart_read, authorize_read, authorize_write,
+    principal_source_ip,
+};
 use super::chunked::object_reader;
 use super::clock::now_unix;
+use super::copy::{self, MetadataDirective};
 use super::error::S3Error;
-use super::multipart::{self, CompletedPartRef};
-use super::resolve::{ResolvedObject, parse_bucket, resolve_object};
+use super::multipart::{self, AssembledUpload, CompletedPartRef, PartSource};
+use super::resolve::{
+    ResolvedObject, VersionSelector, parse_bucket, parse_version_selector, resolve_object,
+    resolve_object_version, select_version,
+};
 use super::response::{
-    delete_response, head_response, put_response, set_last_modified, upload_part_response,
+    delete_response, head_response, put_response, set_last_modified, set_version_id,
+    upload_part_response, versioned_delete_response,
 };
 use super::sigv4::{query_param, sigv4_auth, verify_signed_body, SigV4Verifier, SignedPayloadHash};
+use super::sse::{self, CustomerKey};
 use super::write::S3WriteContext;
 use super::xml::{
-    BucketEntry, ListObjectsV1, ListObjectsV2, ObjectEntry, Owner, PartEntry,
-    STORAGE_CLASS_STANDARD, UploadEntry, complete_multipart_upload_body,
-    initiate_multipart_upload_body, list_all_my_buckets_body, list_multipart_uploads_body,
-    list_objects_v1_body, list_objects_v2_body, list_parts_body, parse_complete_multipart_upload,
+    BucketEntry, DeleteErrorEntry, ListObjectsV1, ListObjectsV2, ObjectEntry, Owner, PartEntry,
+    ListVersions, STORAGE_CLASS_STANDARD, UploadEntry, VersionEntry,
+    complete_multipart_upload_body, copy_result_body, delete_result_body,
+    initiate_multipart_upload_body, lifecycle_configuration_body, list_all_my_buckets_body,
+    list_multipart_uploads_body, list_objects_v1_body, list_objects_v2_body, list_parts_body,
+    list_versions_body, parse_complete_multipart_upload, parse_delete_objects,
+    parse_lifecycle_configuration, parse_versioning_configuration, versioning_configuration_body,
 };
 
 /// Build the S3-compatible Axum router over the shared AppState
@@ -59,15 +87,25 @@ use super::xml::{
 /// - `GET /` -> ListBuckets
 /// - `GET /{bucket}` -> ListObjectsV2 (`?list-type=2`) or an S3 error
 /// - `HEAD /{bucket}` -> HeadBucket
-/// - `GET|HEAD /{bucket}/{key}` -> GetObject / HeadObject
-/// - `PUT /{bucket}/{key}` -> PutObject (or UploadPart with `?uploadId=`)
+/// - `GET /{bucket}?versioning` -> GetBucketVersioning; `?versions` ->
+///   ListObjectVersions
+/// - `PUT /{bucket}?versioning` -> PutBucketVersioning
+/// - `GET|PUT|DELETE /{bucket}?lifecycle` -> Get/Put/DeleteBucketLifecycle
+/// - `POST /{bucket}` -> DeleteObjects (`?delete`)
+/// - `GET|HEAD /{bucket}/{key}` -> GetObject / HeadObject (`?versionId=` reads
+///   a prior version)
+/// - `PUT /{bucket}/{key}` -> PutObject (or UploadPart with `?uploadId=`);
+///   CopyObject / UploadPartCopy when `x-amz-copy-source` is set
 /// - `POST /{bucket}/{key}` -> CreateMultipartUpload (`?uploads`) /
 ///   CompleteMultipartUpload (`?uploadId=`)
 /// - `DELETE /{bucket}/{key}` -> DeleteObject (or AbortMultipartUpload with
-///   `?uploadId=`)
+///   `?uploadId=`); `?versionId=` deletes one version
 ///
-/// The `verifier` SigV4 layer gates every route (anonymous GET/HEAD/LIST are
-/// allowed; signed requests are verified; unsigned writes are rejected).
+/// The `verifier` SigV4 layer gates every route (anonymous GET/HEAD/LIST pass
+/// through to the handlers, which refuse them on private buckets; signed
+/// requests are verified; unsigned writes are rejected). Inside it, verified
+/// principals are stamped with their resolved caller IP for source-scoped
+/// policy rules.
 pub fn router<Db, Cluster, Blockchain>(
     state: AppState<Db, Cluster, Blockchain>,
     verifier: Arc<SigV4Verifier>,
@@ -77,12 +115,17 @@ where
     Cluster: Api + 'static,
     Blockchain: Rpc + 'static,
 {
+    let trusted: Arc<[IpAddr]> =
+        state.context.config.gateway.metering.trusted_proxies.clone().into();
     Router::new()
         .route("/", get(list_buckets::<Db, Cluster, Blockchain>))
         .route(
             "/{bucket}",
             get(bucket_get::<Db, Cluster, Blockchain>)
-                .head(head_bucket::<Db, Cluster, Blockchain>),
+                .head(head_bucket::<Db, Cluster, Blockchain>)
+                .put(bucket_put::<Db, Cluster, Blockchain>)
+                .post(bucket_post::<Db, Cluster, Blockchain>)
+                .delete(bucket_delete::<Db, Cluster, Blockchain>),
         )
         .route(
             "/{bucket}/{*key}",
@@ -93,6 +136,7 @@ where
                 .delete(delete_object::<Db, Cluster, Blockchain>),
         )
         .with_state(state)
+        .layer(from_fn_with_state(trusted, principal_source_ip))
         .layer(from_fn_with_state(verifier, sigv4_auth))
 }
 
@@ -182,26 +226,20 @@ where
                 creation_date: 0,
             })
             .collect(),
-        Some((principal, CredentialScope::AnyOwned)) => {
-            let (tape, _) = tape_pda(principal);
-            let reserved = state
-                .context
-                .store
-                .get_tape(tape)
-                .map_err(|error| {
-                    tracing::warn!(%error, "s3 ListBuckets: tape store unavailable");
-                    S3Error::Internal("tape store unavailable".to_string())
-                })?
-                .is_some();
-            if reserved {
-                vec![BucketEntry {
-                    name: tape.to_string(),
-                    creation_date: 0,
-                }]
-            } else {
-                Vec::new()
-            }
-        }
+        Some((principal, CredentialScope::AnyOwned)) => state
+            .context
+            .store
+            .list_owned_tapes(principal)
+            .map_err(|error| {
+                tracing::warn!(%error, "s3 ListBuckets: tape store unavailable");
+                S3Error::Internal("tape store unavailable".to_string())
+            })?
+            .into_iter()
+            .map(|tape| BucketEntry {
+                name: tape.to_string(),
+                creation_date: 0,
+            })
+            .collect(),
         None => Vec::new(),
     };
 
@@ -209,7 +247,9 @@ where
 }
 
 /// `GET /{bucket}` -> ListObjectsV2 (`?list-type=2`), ListMultipartUploads
-/// (`?uploads`), a recognized subresource (`NotImplemented`), or ListObjects V1
+/// (`?uploads`), GetBucketVersioning (`?versioning`), ListObjectVersions
+/// (`?versions`), GetBucketLifecycle (`?lifecycle`), a recognized subresource
+/// (`NotImplemented`), or ListObjects V1
 async fn bucket_get<Db, Cluster, Blockchain>(
     State(state): State<AppState<Db, Cluster, Blockchain>>,
     Extension(auth): Extension<Auth>,
@@ -223,18 +263,24 @@ where
 {
     let query = query.as_deref();
     if has_query_param(query, "list-type", Some("2")) {
-        list_objects_v2(&state, bucket, query)
+        list_objects_v2(&state, &auth, bucket, query)
     } else if has_query_param(query, "uploads", None) {
         list_multipart_uploads(&state, &auth, bucket)
+    } else if has_query_param(query, "versioning", None) {
+        get_bucket_versioning(&state, &auth, bucket)
+    } else if has_query_param(query, "versions", None) {
+        list_object_versions(&state, &auth, bucket, query)
+    } else if has_query_param(query, "lifecycle", None) {
+        get_bucket_lifecycle(&state, &auth, bucket)
     } else if BUCKET_SUBRESOURCES
         .iter()
         .any(|subresource| has_query_param(query, subresource, None))
     {
-        // ?acl, ?versioning, ?tagging, … are real subresources, not listings.
+        // ?acl, ?tagging, ?cors, … are real subresources, not listings.
         Err(not_implemented("bucket subresource"))
     } else {
         // Plain `GET /{bucket}` (prefix/marker/delimiter/max-keys) -> ListObjects V1.
-        list_objects_v1(&state, bucket, query)
+        list_objects_v1(&state, &auth, bucket, query)
     }
 }
 
@@ -244,12 +290,9 @@ where
 const BUCKET_SUBRESOURCES: &[&str] = &[
     "acl",
     "location",
-    "versioning",
-    "versions",
     "tagging",
     "cors",
     "policy",
-    "lifecycle",
     "logging",
     "notification",
     "replication",
@@ -297,6 +340,143 @@ where
     Ok(xml_ok_response(list_multipart_uploads_body(&bucket_label, &uploads)))
 }
 
+/// `GET /{bucket}?versioning` -> GetBucketVersioning
+///
+/// A bucket whose versioning was never configured reports no status.
+fn get_bucket_versioning<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket_label: String,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let bucket = parse_bucket(&bucket_label)?;
+    authorize_read(state, auth, bucket, None, ReadOp::List)?;
+    let status = state
+        .context
+        .store
+        .get_bucket_versioning(bucket)
+        .map_err(|error| S3Error::Internal(error.to_string()))?;
+    let status = status.map(|status| match status {
+        VersioningStatus::Enabled => "Enabled",
+        VersioningStatus::Suspended => "Suspended",
+    });
+    Ok(xml_ok_response(versioning_configuration_body(status)))
+}
+
+/// `GET /{bucket}?lifecycle` -> GetBucketLifecycle
+fn get_bucket_lifecycle<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket_label: String,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let bucket = parse_bucket(&bucket_label)?;
+    authorize_read(state, auth, bucket, None, ReadOp::List)?;
+    let lifecycle = state
+        .context
+        .store
+        .get_bucket_lifecycle(bucket)
+        .map_err(|error| S3Error::Internal(error.to_string()))?
+        .ok_or(S3Error::NoSuchLifecycleConfiguration)?;
+    Ok(xml_ok_response(lifecycle_configuration_body(&lifecycle)))
+}
+
+/// `GET /{bucket}?versions` -> ListObjectVersions
+///
+/// Walks the bucket's version history in key order, each key's versions
+/// newest first, paginated by `key-marker` / `version-id-marker`.
+/// `delimiter` is not supported; every version is listed flat.
+fn list_object_versions<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket_label: String,
+    query: Option<&str>,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let bucket = parse_bucket(&bucket_label)?;
+    authorize_read(state, auth, bucket, None, ReadOp::List)?;
+
+    let prefix = query_value(query, "prefix").unwrap_or_default();
+    let key_marker = query_value(query, "key-marker").unwrap_or_default();
+    let version_id_marker = query_value(query, "version-id-marker").unwrap_or_default();
+    let max_keys = query_value(query, "max-keys")
+        .and_then(|value| value.parse::<u32>().ok())
+        .unwrap_or(MAX_KEYS_LIMIT)
+        .clamp(1, MAX_KEYS_LIMIT);
+
+    // A version-id marker only means something alongside a key marker.
+    let after_version = match (key_marker.is_empty(), version_id_marker.is_empty()) {
+        (false, false) => {
+            let selector = parse_version_selector(&version_id_marker)?;
+            let version = select_version(state, bucket, &key_marker, selector)?;
+            Some(version.ok_or_else(|| {
+                S3Error::InvalidRequest("Invalid version id marker specified".into())
+            })?)
+        }
+        _ => None,
+    };
+    let after = (!key_marker.is_empty()).then(|| (key_marker.as_bytes(), after_version));
+
+    let page = state
+        .context
+        .store
+        .list_object_versions(bucket, prefix.as_bytes(), after, max_keys as usize)
+        .map_err(|error| S3Error::Internal(error.to_string()))?;
+
+    let last_is_null = page.versions.last().is_some_and(|row| row.is_null);
+    let mut versions: Vec<VersionEntry> = Vec::new();
+    for row in page.versions {
+        let (last_modified, object) = match row.value {
+            ObjectVersion::Object(entry) => (
+                entry.block_time,
+                Some((entry.etag.to_string(), entry.size.to_bytes())),
+            ),
+            ObjectVersion::DeleteMarker { time } => (Some(time), None),
+        };
+        versions.push(VersionEntry {
+            key: String::from_utf8_lossy(&row.name).into_owned(),
+            version_id: VersionSelector::listed(row.version, row.is_null).to_string(),
+            is_latest: row.is_latest,
+            last_modified,
+            object,
+        });
+    }
+
+    let (next_key_marker, next_version_id_marker) = match page.next {
+        Some((name, version)) if page.is_truncated => (
+            Some(String::from_utf8_lossy(&name).into_owned()),
+            Some(VersionSelector::listed(version, last_is_null).to_string()),
+        ),
+        _ => (None, None),
+    };
+
+    let result = ListVersions {
+        name: bucket_label,
+        prefix,
+        key_marker,
+        version_id_marker,
+        next_key_marker,
+        next_version_id_marker,
+        max_keys,
+        is_truncated: page.is_truncated,
+        versions,
+    };
+
+    Ok(xml_ok_response(list_versions_body(&result)))
+}
+
 /// S3 caps `max-keys` at 1000; requests above this are clamped
 const MAX_KEYS_LIMIT: u32 = 1000;
 
@@ -306,9 +486,10 @@ const MAX_KEYS_LIMIT: u32 = 1000;
 /// opaque base64 `continuation-token`) onto the store's `list_objects` op and
 /// renders the `ListBucketResult` XML, including `IsTruncated` and a
 /// `NextContinuationToken` (base64 of the store's raw-name cursor) when the
-/// listing is truncated.
+/// listing is truncated. `metadata=true` adds each object's stored headers.
 fn list_objects_v2<Db, Cluster, Blockchain>(
     state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
     bucket_label: String,
     query: Option<&str>,
 ) -> Result<Response, S3Error>
@@ -318,6 +499,7 @@ where
     Blockchain: Rpc + 'static,
 {
     let bucket = parse_bucket(&bucket_label)?;
+    authorize_read(state, auth, bucket, None, ReadOp::List)?;
 
     let prefix = query_value(query, "prefix").unwrap_or_default();
     let delimiter = query_value(query, "delimiter").filter(|delimiter| !delimiter.is_empty());
@@ -328,6 +510,9 @@ where
         .and_then(|value| value.parse::<u32>().ok())
         .unwrap_or(MAX_KEYS_LIMIT)
         .clamp(1, MAX_KEYS_LIMIT);
+    // `metadata=true` is the MinIO extension for listing stored headers; they
+    // live in the listing entry, so it costs no extra lookups.
+    let with_metadata = has_query_param(query, "metadata", Some("true"));
 
     // A continuation token (the opaque base64 of a prior page's raw-name cursor)
     // takes precedence over `start-after`, matching S3.
@@ -356,6 +541,8 @@ where
             etag: entry.etag.to_string(),
             size: entry.size.to_bytes(),
             storage_class: STORAGE_CLASS_STANDARD,
+            user_metadata: with_metadata
+                .then(|| listing_metadata(&entry.headers, entry.content_type)),
         });
     }
     let mut common_prefixes: Vec<String> = Vec::new();
@@ -393,6 +580,7 @@ where
 /// key to resume after) and rendered in the V1 `<Marker>`/`<NextMarker>` shape.
 fn list_objects_v1<Db, Cluster, Blockchain>(
     state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
     bucket_label: String,
     query: Option<&str>,
 ) -> Result<Response, S3Error>
@@ -402,6 +590,7 @@ where
     Blockchain: Rpc + 'static,
 {
     let bucket = parse_bucket(&bucket_label)?;
+    authorize_read(state, auth, bucket, None, ReadOp::List)?;
 
     let prefix = query_value(query, "prefix").unwrap_or_default();
     let delimiter = query_value(query, "delimiter").filter(|delimiter| !delimiter.is_empty());
@@ -434,6 +623,7 @@ where
             etag: entry.etag.to_string(),
             size: entry.size.to_bytes(),
             storage_class: STORAGE_CLASS_STANDARD,
+            user_metadata: None,
         });
     }
     let mut common_prefixes: Vec<String> = Vec::new();
@@ -491,9 +681,191 @@ fn xml_ok_response(body: String) -> Response {
         .into_response()
 }
 
+/// `POST /{bucket}` -> DeleteObjects (`?delete`)
+async fn bucket_post<Db, Cluster, Blockchain>(
+    State(state): State<AppState<Db, Cluster, Blockchain>>,
+    Extension(auth): Extension<Auth>,
+    Extension(signed_payload): Extension<SignedPayloadHash>,
+    Path(bucket): Path<String>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    // The key list is itself signed; verify it before parsing.
+    verify_signed_body(&signed_payload, &body)?;
+
+    if has_query_param(query.as_deref(), "delete", None) {
+        return delete_objects(&state, &auth, bucket, body).await;
+    }
+    Err(not_implemented("bucket POST"))
+}
+
+/// `PUT /{bucket}` -> PutBucketVersioning (`?versioning`) or
+/// PutBucketLifecycle (`?lifecycle`)
+async fn bucket_put<Db, Cluster, Blockchain>(
+    State(state): State<AppState<Db, Cluster, Blockchain>>,
+    Extension(auth): Extension<Auth>,
+    Extension(signed_payload): Extension<SignedPayloadHash>,
+    Path(bucket): Path<String>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    verify_signed_body(&signed_payload, &body)?;
+
+    if has_query_param(query.as_deref(), "versioning", None) {
+        return put_bucket_versioning(&state, &auth, bucket, body).await;
+    }
+    if has_query_param(query.as_deref(), "lifecycle", None) {
+        return put_bucket_lifecycle(&state, &auth, bucket, body).await;
+    }
+    Err(not_implemented("bucket PUT"))
+}
+
+/// `DELETE /{bucket}` -> DeleteBucketLifecycle (`?lifecycle`)
+async fn bucket_delete<Db, Cluster, Blockchain>(
+    State(state): State<AppState<Db, Cluster, Blockchain>>,
+    Extension(auth): Extension<Auth>,
+    Path(bucket): Path<String>,
+    RawQuery(query): RawQuery,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    if has_query_param(query.as_deref(), "lifecycle", None) {
+        return delete_bucket_lifecycle(&state, &auth, bucket).await;
+    }
+    Err(not_implemented("DeleteBucket"))
+}
+
+/// Handle `PUT /{bucket}?lifecycle` (PutBucketLifecycleConfiguration)
+///
+/// Replaces the bucket's whole rule set; the background executor picks the new
+/// rules up on its next pass.
+async fn put_bucket_lifecycle<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket_label: String,
+    body: Bytes,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let bucket = parse_bucket(&bucket_label)?;
+    let body_text = std::str::from_utf8(&body).map_err(|_| {
+        S3Error::InvalidRequest("LifecycleConfiguration body is not valid UTF-8".into())
+    })?;
+    let lifecycle = parse_lifecycle_configuration(body_text).map_err(S3Error::InvalidRequest)?;
+
+    let permit = authorize_write(state, auth, bucket, "", WriteOp::Configure, 0).await?;
+    match state.context.store.put_bucket_lifecycle(bucket, &lifecycle) {
+        Ok(()) => {
+            permit.commit(state, 0);
+            Ok(StatusCode::OK.into_response())
+        }
+        Err(error) => {
+            permit.refund(state);
+            Err(S3Error::Internal(error.to_string()))
+        }
+    }
+}
+
+/// Handle `DELETE /{bucket}?lifecycle` (DeleteBucketLifecycle)
+///
+/// Deleting a configuration that does not exist succeeds, as in S3.
+async fn delete_bucket_lifecycle<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket_label: String,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let bucket = parse_bucket(&bucket_label)?;
+    let permit = authorize_write(state, auth, bucket, "", WriteOp::Configure, 0).await?;
+    match state.context.store.delete_bucket_lifecycle(bucket) {
+        Ok(_) => {
+            permit.commit(state, 0);
+            Ok(StatusCode::NO_CONTENT.into_response())
+        }
+        Err(error) => {
+            permit.refund(state);
+            Err(S3Error::Internal(error.to_string()))
+        }
+    }
+}
+
+/// Handle `PUT /{bucket}?versioning` (PutBucketVersioning)
+///
+/// The status is set on chain, so every node and gateway applies it when the
+/// transaction replays. The first time versioning is configured, every object
+/// already in the bucket becomes its key's null version. While suspended, a
+/// write or delete replaces the key's null version and other versions stay.
+async fn put_bucket_versioning<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket_label: String,
+    body: Bytes,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let Some(write_ctx) = state.write_ctx.as_ref() else {
+        return Err(write_not_implemented(false, "PutBucketVersioning"));
+    };
+    let bucket = parse_bucket(&bucket_label)?;
+    let body_text = std::str::from_utf8(&body).map_err(|_| {
+        S3Error::InvalidRequest("VersioningConfiguration body is not valid UTF-8".into())
+    })?;
+    let status = match parse_versioning_configuration(body_text)
+        .map_err(S3Error::InvalidRequest)?
+        .as_str()
+    {
+        "Enabled" => TapeVersioning::Enabled,
+        "Suspended" => TapeVersioning::Suspended,
+        other => {
+            return Err(S3Error::InvalidRequest(format!(
+                "unknown versioning status {other:?}"
+            )));
+        }
+    };
+
+    let permit = authorize_write(state, auth, bucket, "", WriteOp::Configure, 0).await?;
+    match write_ctx
+        .set_versioning(state.context.as_ref(), bucket, status)
+        .await
+    {
+        Ok(()) => {
+            permit.commit(state, 0);
+            Ok(StatusCode::OK.into_response())
+        }
+        Err(error) => {
+            permit.refund(state);
+            Err(s3_write_error(error))
+        }
+    }
+}
+
 /// `HEAD /{bucket}` -> HeadBucket
 async fn head_bucket<Db, Cluster, Blockchain>(
     State(state): State<AppState<Db, Cluster, Blockchain>>,
+    Extension(auth): Extension<Auth>,
     Path(bucket): Path<String>,
 ) -> Result<Response, S3Error>
 where
@@ -503,7 +875,9 @@ where
 {
     // A bucket is a tape; it exists iff its tape account resolves on-chain.
     // HeadBucket has no body, so any resolution failure is reported as 404.
+    // A private bucket is only confirmed to readers allowed to list it.
     let tape = parse_bucket(&bucket)?;
+    authorize_read(&state, &auth, tape, None, ReadOp::List)?;
     match state.context.rpc.get_tape_by_address(&tape).await {
         Ok(_) => Ok(StatusCode::OK.into_response()),
         Err(_) => Err(S3Error::NoSuchBucket),
@@ -518,14 +892,27 @@ where
 /// listed key whose track is missing or not yet certified (so it cannot be
 /// served) to S3Error::NoSuchKey as well — the object simply is not
 /// retrievable. Shared by GET (which decodes the track) and HEAD (which only
-/// reports the entry metadata) so both agree on what is readable.
+/// reports the entry metadata) so both agree on what is readable. A `version`
+/// reads that entry of the version history instead of the current one; a key
+/// without the null version it asks for is S3Error::NoSuchVersion. The read is
+/// authorized against the bucket's access before anything resolves.
 fn resolve_readable<Db: Store, Cluster: Api, Blockchain: Rpc>(
     state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
     bucket_label: &str,
     key: &str,
+    version: Option<VersionSelector>,
 ) -> Result<(ResolvedObject, CompressedTrack), S3Error> {
     let bucket = parse_bucket(bucket_label)?;
-    let resolved = resolve_object(state, bucket, key)?.ok_or(S3Error::NoSuchKey)?;
+    authorize_read(state, auth, bucket, Some(key), ReadOp::Get)?;
+    let resolved = match version {
+        Some(selector) => {
+            let version =
+                select_version(state, bucket, key, selector)?.ok_or(S3Error::NoSuchVersion)?;
+            resolve_object_version(state, bucket, key, version)?
+        }
+        None => resolve_object(state, bucket, key)?.ok_or(S3Error::NoSuchKey)?,
+    };
     let track = track_with_pending(state, resolved.track_address)
         .map_err(S3Error::from)?
         .ok_or(S3Error::NoSuchKey)?;
@@ -560,17 +947,23 @@ where
     if has_query_param(query.as_deref(), "uploadId", None) {
         return list_parts(&state, &auth, bucket, key, query.as_deref());
     }
+    let version = requested_version(query.as_deref())?;
     let range = range_header(&headers).map(str::to_string);
+    let customer_key = sse::customer_key(&headers)?;
     let caller = meter_caller(&state, &headers, remote, &auth);
-    get_object_impl(state, caller, bucket, key, range).await
+    get_object_impl(state, &auth, caller, bucket, key, version, range, customer_key).await
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn get_object_impl<Db, Cluster, Blockchain>(
     state: AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
     caller: MeterCaller,
     bucket: String,
     key: String,
+    version: Option<VersionSelector>,
     range: Option<String>,
+    customer_key: Option<CustomerKey>,
 ) -> Result<Response, S3Error>
 where
     Db: Store + 'static,
@@ -579,18 +972,20 @@ where
 {
     check_request_rate(&state, &caller)?;
 
-    let (resolved, track) = resolve_readable(&state, &bucket, &key)?;
-    // The S3 content type comes from the object-list index; objects carry no
-    // separate filename, so no Content-Disposition is set.
+    let (resolved, track) = resolve_readable(&state, auth, &bucket, &key, version)?;
+    // S3 headers come from the object-list index; objects carry no separate
+    // filename, so Content-Disposition is only what the writer stored.
     let metadata = ObjectResponseMetadata {
         content_type: resolved.content_type,
         filename: None,
+        headers: resolved.headers,
     };
     let block_time = resolved.block_time;
 
     // `Range` is honored for every object: single-track objects slice the
     // decoded bytes, multi-track streams decode only the chunks the range
-    // touches; see docs/s3-gateway-status.md (Range).
+    // touches; see docs/s3-gateway-status.md (Range). An SSE-C object opens
+    // with the request's key, chunk by chunk, ranged or not.
     let mut response = read_object_response(
         state,
         resolved.track_address,
@@ -598,12 +993,17 @@ where
         metadata,
         &caller,
         range,
+        customer_key.as_ref().map(CustomerKey::as_provider),
         |retry_after| S3Error::slow_down(retry_after).into_response(),
     )
     .await
     .map_err(S3Error::from)?;
 
     set_last_modified(response.headers_mut(), block_time);
+    set_version_id(response.headers_mut(), version);
+    if let Some(customer_key) = &customer_key {
+        customer_key.set_response_headers(response.headers_mut());
+    }
     Ok(response)
 }
 
@@ -612,12 +1012,14 @@ where
 /// Returns the same headers as GetObject (Content-Type, Content-Length, quoted
 /// ETag, Cache-Control, Last-Modified) with no body, including the ranged
 /// headers for a `Range` request. Metadata comes straight from the object-list
-/// index entry, so HEAD never decodes the object body.
+/// index entry; the top track is only opened (through the decode cache) to hold
+/// HEAD to GetObject's SSE-C rules, so no chunk is read.
 async fn head_object<Db, Cluster, Blockchain>(
     State(state): State<AppState<Db, Cluster, Blockchain>>,
     Extension(auth): Extension<Auth>,
     ConnectInfo(remote): ConnectInfo<SocketAddr>,
     Path((bucket, key)): Path<(String, String)>,
+    RawQuery(query): RawQuery,
     headers: HeaderMap,
 ) -> Result<Response, S3Error>
 where
@@ -625,20 +1027,41 @@ where
     Cluster: Api + 'static,
     Blockchain: Rpc + 'static,
 {
+    let version = requested_version(query.as_deref())?;
+    let customer_key = sse::customer_key(&headers)?;
     let caller = meter_caller(&state, &headers, remote, &auth);
-    head_object_impl(&state, &caller, &bucket, &key, range_header(&headers))
+    let range = range_header(&headers);
+    head_object_impl(&state, &auth, &caller, &bucket, &key, version, range, customer_key).await
 }
 
-fn head_object_impl<Db: Store, Cluster: Api, Blockchain: Rpc>(
+#[allow(clippy::too_many_arguments)]
+async fn head_object_impl<Db: Store, Cluster: Api, Blockchain: Rpc>(
     state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
     caller: &MeterCaller,
     bucket: &str,
     key: &str,
+    version: Option<VersionSelector>,
     range: Option<&str>,
+    customer_key: Option<CustomerKey>,
 ) -> Result<Response, S3Error> {
     check_request_rate(state, caller)?;
-    let (resolved, _track) = resolve_readable(state, bucket, key)?;
-    head_response(&resolved, range)
+    let (resolved, track) = resolve_readable(state, auth, bucket, key, version)?;
+    let opened = open_object(state, resolved.track_address, track).await?;
+    check_object_key(&opened, customer_key.as_ref().map(CustomerKey::as_provider))?;
+    let mut response = head_response(&resolved, range)?;
+    set_version_id(response.headers_mut(), version);
+    if let Some(customer_key) = &customer_key {
+        customer_key.set_response_headers(response.headers_mut());
+    }
+    Ok(response)
+}
+
+/// The `versionId` a read or delete targets, if any.
+fn requested_version(query: Option<&str>) -> Result<Option<VersionSelector>, S3Error> {
+    query_value(query, "versionId")
+        .map(|value| parse_version_selector(&value))
+        .transpose()
 }
 
 /// The metering identity for an S3 read: the resolved caller IP, plus the
@@ -679,13 +1102,16 @@ fn check_request_rate<Db: Store, Cluster: Api, Blockchain: Rpc>(
     }
 }
 
-/// `PUT /{bucket}/{key}` -> PutObject, or UploadPart when `?uploadId=` is set
+/// `PUT /{bucket}/{key}` -> PutObject, or UploadPart when `?uploadId=` is set;
+/// with `x-amz-copy-source`, CopyObject or UploadPartCopy
 ///
 /// PutObject is signed by the configured delegate keypair. A signed-hash request is
 /// buffered and integrity-checked, then written as one track or a multi-track
 /// stream depending on size; an `UNSIGNED-PAYLOAD` / `aws-chunked` request is
-/// streamed straight onto chunk tracks with bounded memory. UploadPart buffers
-/// the part bytes under the upload id.
+/// streamed straight onto chunk tracks with bounded memory. With SSE-C headers
+/// the object is sealed under the customer key as a stream of any size.
+/// UploadPart writes each part to its own chunk tracks; copies and parts do
+/// not take SSE-C yet.
 async fn put_object<Db, Cluster, Blockchain>(
     State(state): State<AppState<Db, Cluster, Blockchain>>,
     Extension(auth): Extension<Auth>,
@@ -700,9 +1126,22 @@ where
     Cluster: Api + 'static,
     Blockchain: Rpc + 'static,
 {
+    if copy::is_copy(&headers) {
+        // Server-side copies read the source from the store; the (empty) body is
+        // ignored.
+        return if has_query_param(query.as_deref(), "uploadId", None) {
+            sse::reject_sse_c(&headers, "UploadPartCopy")?;
+            upload_part_copy(&state, &auth, bucket, key, query.as_deref(), &headers).await
+        } else {
+            sse::reject_sse_c(&headers, "CopyObject")?;
+            copy_object(state, &auth, bucket, key, &headers).await
+        };
+    }
+
     if has_query_param(query.as_deref(), "uploadId", None) {
-        // UploadPart buffers the part bytes under the upload id; the assembled
-        // object is written at CompleteMultipartUpload.
+        // UploadPart writes the part to its own chunk tracks; the object's
+        // manifest is written over them at CompleteMultipartUpload.
+        sse::reject_sse_c(&headers, "UploadPart")?;
         let max_buffered_bytes = state.context.config.gateway.s3.max_buffered_bytes;
         let part = buffer_object_body(body, max_buffered_bytes).await?;
         verify_signed_body(&signed_payload, &part)?;
@@ -738,7 +1177,9 @@ where
     // (1..=MAX_NAME_LEN bytes) up front for a precise client error.
     validate_object_key(&key)?;
 
-    let content_type = content_type_from_headers(headers);
+    let object_headers = object_headers_from_request(headers)?;
+    let customer_key = sse::customer_key(headers)?;
+    let key_provider = customer_key.as_ref().map(CustomerKey::provider);
     let max_object_bytes = state.context.config.gateway.s3.max_object_bytes;
     let max_buffered_bytes = state.context.config.gateway.s3.max_buffered_bytes;
 
@@ -759,9 +1200,10 @@ where
                     state.context.as_ref(),
                     tape,
                     key.as_bytes(),
-                    content_type,
+                    &object_headers,
                     StorageUnits::from_bytes(size),
                     reader,
+                    key_provider,
                 ),
                 producer,
             );
@@ -773,7 +1215,14 @@ where
             let size = data.len() as u64;
             let permit = authorize_write(&state, auth, tape, &key, WriteOp::Put, size).await?;
             let result = write_ctx
-                .write_object(state.context.as_ref(), tape, key.as_bytes(), content_type, &data)
+                .write_object(
+                    state.context.as_ref(),
+                    tape,
+                    key.as_bytes(),
+                    &object_headers,
+                    &data,
+                    key_provider,
+                )
                 .await;
             settle_write(permit, &state, size, result)?
         }
@@ -787,36 +1236,166 @@ where
         .map(|resolved| resolved.etag)
         .unwrap_or(written_etag);
 
-    put_response(etag)
+    let mut response = put_response(etag)?;
+    if let Some(customer_key) = &customer_key {
+        customer_key.set_response_headers(response.headers_mut());
+    }
+    Ok(response)
 }
 
-/// Header carrying the decoded object size for an `aws-chunked` streaming upload.
-const AMZ_DECODED_CONTENT_LENGTH: &str = "x-amz-decoded-content-length";
-
-/// The declared object size for the streamed (bounded-memory) write path, or
-/// `None` to buffer and hash-verify
+/// `PUT /{bucket}/{key}` with `x-amz-copy-source` -> CopyObject
 ///
-/// `aws-chunked` uploads must stream (the framing has to be stripped) and carry
-/// the size in `x-amz-decoded-content-length`; plain `UNSIGNED-PAYLOAD` uploads
-/// stream when `Content-Length` is present and non-empty. A signed-hash request
-/// always buffers so the body can be verified, so it returns `None`.
-fn streamed_object_size(
-    signed_payload: &SignedPayloadHash,
+/// A stream copied within its own bucket is re-indexed: a new manifest names
+/// the source's chunk tracks, and no slice is uploaded again. A single-track
+/// source, or any copy across buckets, is decoded and written through the
+/// delegate write engine, streaming chunk by chunk when the source is a stream.
+/// The reservation covers the full object size and is reconciled to the bytes
+/// actually written.
+async fn copy_object<Db, Cluster, Blockchain>(
+    state: AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket: String,
+    key: String,
     headers: &HeaderMap,
-) -> Result<Option<u64>, S3Error> {
-    if signed_payload.is_aws_chunked() {
-        let size = parse_byte_count(headers, AMZ_DECODED_CONTENT_LENGTH).ok_or_else(|| {
-            S3Error::InvalidRequest(
-                "aws-chunked upload is missing x-amz-decoded-content-length".into(),
-            )
-        })?;
-        return Ok(Some(size));
-    }
-    if signed_payload.is_verifiable() {
-        return Ok(None);
-    }
-    Ok(parse_byte_count(headers, header::CONTENT_LENGTH.as_str()).filter(|&size| size > 0))
-}
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let Some(write_ctx) = state.write_ctx.clone() else {
+        return Err(write_not_implemented(false, "CopyObject"));
+    };
+
+    let tape = parse_bucket(&bucket)?;
+    validate_object_key(&key)?;
+
+    let source = copy::copy_source(headers)?;
+    let directive = copy::metadata_directive(headers)?;
+    // Copying a selected version back onto its own key restores it, which S3
+    // allows without a new directive.
+    let onto_itself =
+        source.version.is_none() && parse_bucket(&source.bucket)? == tape && source.key == key;
+    if directive == MetadataDirective::Copy && onto_itself {
+        return Err(S3Error::InvalidRequest(
+            "copying an object onto itself requires x-amz-metadata-directive: REPLACE".into(),
+        ));
+    }
+    let (resolved, track) =
+        resolve_readable(&state, auth, &source.bucket, &source.key, source.version)?;
+    let object_headers = match directive {
+        MetadataDirective::Copy => resolved.headers.clone(),
+        MetadataDirective::Replace => object_headers_from_request(headers)?,
+    };
+
+    let size = resolved.size;
+    let max_object_bytes = state.context.config.gateway.s3.max_object_bytes;
+    if size > max_object_bytes as u64 {
+        return Err(S3Error::EntityTooLarge(format!(
+            "object size {size} exceeds the maximum of {max_object_bytes} bytes"
+        )));
+    }
+
+    // Authorization chokepoint runs before the source is decoded, so a denied
+    // copy costs no reads.
+    let permit = authorize_write(&state, auth, tape, &key, WriteOp::Put, size).await?;
+    let opened = match open_object(&state, resolved.track_address, track).await {
+        Ok(opened) => opened,
+        Err(error) => {
+            permit.refund(&state);
+            return Err(error.into());
+        }
+    };
+
+    let context = state.context.as_ref();
+    let written_etag = match opened {
+        OpenedObject::Stream { manifest, .. } if resolved.data_tape == tape => {
+            let written = manifest
+                .to_bytes()
+                .map(|bytes| bytes.len() as u64)
+                .unwrap_or_default();
+            let result = write_ctx
+                .relink_object(context, tape, key.as_bytes(), &object_headers, &manifest)
+                .await;
+            settle_write(permit, &state, written, result)?
+        }
+        OpenedObject::Stream { manifest, .. } => {
+            let window = ByteRange { start: 0, end: size };
+            let chunks = match stream_range_bytes(
+                state.clone(),
+                resolved.data_tape,
+                resolved.track_address,
+                Arc::new(manifest),
+                window,
+                None,
+            ) {
+                Ok(chunks) => chunks,
+                Err(error) => {
+                    permit.refund(&state);
+                    return Err(error.into());
+                }
+            };
+            let reader = StreamReader::new(Box::pin(chunks.map_err(io::Error::other)));
+            let result = write_ctx
+                .write_object_stream(
+                    context,
+                    tape,
+                    key.as_bytes(),
+                    &object_headers,
+                    StorageUnits::from_bytes(size),
+                    reader,
+                    None,
+                )
+                .await;
+            settle_write(permit, &state, size, result)?
+        }
+        OpenedObject::Single(decoded) => {
+            let result = write_ctx
+                .write_object(context, tape, key.as_bytes(), &object_headers, &decoded.bytes, None)
+                .await;
+            settle_write(permit, &state, size, result)?
+        }
+    };
+
+    // Same canonical-ETag preference as PutObject.
+    let etag = resolve_object(&state, tape, &key)?
+        .map(|resolved| resolved.etag)
+        .unwrap_or(written_etag);
+
+    Ok(xml_ok_response(copy_result_body(
+        "CopyObjectResult",
+        &etag.to_string(),
+        now_unix(),
+    )))
+}
+
+/// Header carrying the decoded object size for an `aws-chunked` streaming upload.
+const AMZ_DECODED_CONTENT_LENGTH: &str = "x-amz-decoded-content-length";
+
+/// The declared object size for the streamed (bounded-memory) write path, or
+/// `None` to buffer and hash-verify
+///
+/// `aws-chunked` uploads must stream (the framing has to be stripped) and carry
+/// the size in `x-amz-decoded-content-length`; plain `UNSIGNED-PAYLOAD` uploads
+/// stream when `Content-Length` is present and non-empty. A signed-hash request
+/// always buffers so the body can be verified, so it returns `None`.
+fn streamed_object_size(
+    signed_payload: &SignedPayloadHash,
+    headers: &HeaderMap,
+) -> Result<Option<u64>, S3Error> {
+    if signed_payload.is_aws_chunked() {
+        let size = parse_byte_count(headers, AMZ_DECODED_CONTENT_LENGTH).ok_or_else(|| {
+            S3Error::InvalidRequest(
+                "aws-chunked upload is missing x-amz-decoded-content-length".into(),
+            )
+        })?;
+        return Ok(Some(size));
+    }
+    if signed_payload.is_verifiable() {
+        return Ok(None);
+    }
+    Ok(parse_byte_count(headers, header::CONTENT_LENGTH.as_str()).filter(|&size| size > 0))
+}
 
 /// Parse a `u64` byte count from a request header.
 fn parse_byte_count(headers: &HeaderMap, name: &str) -> Option<u64> {
@@ -996,8 +1575,8 @@ where
         return create_multipart_upload(&state, &auth, bucket, key, &headers).await;
     }
     if has_query_param(query.as_deref(), "uploadId", None) {
-        // CompleteMultipartUpload assembles the buffered parts (XML body lists
-        // them) and drives the write pipeline.
+        // CompleteMultipartUpload links the uploaded parts (XML body lists
+        // them) under the object's manifest.
         return complete_multipart_upload(&state, &auth, bucket, key, query.as_deref(), body).await;
     }
     Err(not_implemented("object POST"))
@@ -1018,17 +1597,22 @@ where
 {
     if has_query_param(query.as_deref(), "uploadId", None) {
         // `DELETE /{bucket}/{key}?uploadId=..` is AbortMultipartUpload: discard
-        // the buffered parts for the upload id.
+        // the upload id's parts and their chunk tracks.
         return abort_multipart_upload(&state, &auth, bucket, key, query.as_deref()).await;
     }
-    delete_object_impl(&state, &auth, bucket, key).await
+    let version = requested_version(query.as_deref())?;
+    delete_object_impl(&state, &auth, bucket, key, version).await
 }
 
+/// DeleteObject proper. In a versioned bucket a plain delete hides the key
+/// behind a delete marker placed on chain and keeps its tracks; a `versionId`
+/// delete removes that one version for good, deleting its track on chain.
 async fn delete_object_impl<Db, Cluster, Blockchain>(
     state: &AppState<Db, Cluster, Blockchain>,
     auth: &Auth,
     bucket: String,
     key: String,
+    version: Option<VersionSelector>,
 ) -> Result<Response, S3Error>
 where
     Db: Store + 'static,
@@ -1042,11 +1626,46 @@ where
     };
 
     let tape = parse_bucket(&bucket)?;
+    let versioned = is_versioned(state, tape)?;
 
     // Authorization chokepoint runs before the existence check so an
     // unauthorized caller cannot probe which keys exist via the response code.
     let permit = authorize_write(state, auth, tape, &key, WriteOp::Delete, 0).await?;
 
+    if let Some(selector) = version {
+        let version = match select_version(state, tape, &key, selector) {
+            Ok(Some(version)) => version,
+            // No null version to delete: already deleted.
+            Ok(None) => {
+                permit.refund(state);
+                return Ok(versioned_delete_response(selector, false));
+            }
+            Err(error) => {
+                permit.refund(state);
+                return Err(error);
+            }
+        };
+        return delete_object_version(state, write_ctx, permit, tape, &key, selector, version)
+            .await;
+    }
+
+    if versioned {
+        return match place_delete_marker(state, write_ctx, tape, &key).await {
+            Ok(Some(marker)) => {
+                permit.commit(state, 0);
+                Ok(versioned_delete_response(marker, true))
+            }
+            Ok(None) => {
+                permit.refund(state);
+                Ok(delete_response())
+            }
+            Err(error) => {
+                permit.refund(state);
+                Err(error)
+            }
+        };
+    }
+
     // S3 DeleteObject is idempotent: a key absent from the object-list index is
     // already "deleted", so report success without touching the chain. Nothing
     // was spent, so release the reservation.
@@ -1055,20 +1674,114 @@ where
         return Ok(delete_response());
     };
 
-    match write_ctx
-        .delete_object(state.context.as_ref(), tape, resolved.track_address)
-        .await
-    {
+    delete_track(state, write_ctx, permit, tape, resolved.track_address, delete_response()).await
+}
+
+/// `DELETE /{bucket}/{key}?versionId=..`: remove one version, `selector` as
+/// the client named it. A delete marker is removed on chain; an object version
+/// is its track, so the track is deleted on chain. Either way replay drops the
+/// version (and promotes the one before it) once the transaction lands. A
+/// version that does not exist is already deleted.
+async fn delete_object_version<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    write_ctx: &S3WriteContext,
+    permit: WritePermit,
+    tape: Address,
+    key: &str,
+    selector: VersionSelector,
+    version: ObjectVersionId,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    if version.is_delete_marker() {
+        let response = versioned_delete_response(selector, true);
+        let known = state
+            .context
+            .store
+            .get_object_version(tape, key.as_bytes(), version)
+            .map_err(|error| S3Error::Internal(error.to_string()));
+        match known {
+            Ok(Some(ObjectVersion::DeleteMarker { .. })) => {}
+            Ok(_) => {
+                permit.refund(state);
+                return Ok(response);
+            }
+            Err(error) => {
+                permit.refund(state);
+                return Err(error);
+            }
+        }
+        let removed = write_ctx
+            .remove_delete_marker(state.context.as_ref(), tape, key.as_bytes(), version)
+            .await;
+        return match removed {
+            Ok(()) => {
+                permit.commit(state, 0);
+                Ok(response)
+            }
+            Err(error) => {
+                permit.refund(state);
+                Err(s3_write_error(error))
+            }
+        };
+    }
+
+    // Writes made before versioning was enabled have no recorded version yet,
+    // but the current entry is still addressable by its track number.
+    let track_address = match resolve_object_version(state, tape, key, version) {
+        Ok(resolved) => resolved.track_address,
+        Err(S3Error::NoSuchVersion) => match resolve_object(state, tape, key) {
+            Ok(Some(current)) if current.track_number == version.track_number => {
+                current.track_address
+            }
+            Ok(_) => {
+                permit.refund(state);
+                return Ok(versioned_delete_response(selector, false));
+            }
+            Err(error) => {
+                permit.refund(state);
+                return Err(error);
+            }
+        },
+        Err(error) => {
+            permit.refund(state);
+            return Err(error);
+        }
+    };
+
+    let response = versioned_delete_response(selector, false);
+    delete_track(state, write_ctx, permit, tape, track_address, response).await
+}
+
+/// Delete one object track on chain under `permit`, answering `response` on
+/// success.
+async fn delete_track<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    write_ctx: &S3WriteContext,
+    permit: WritePermit,
+    tape: Address,
+    track: Address,
+    response: Response,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    match write_ctx.delete_object(state.context.as_ref(), tape, track).await {
         Ok(()) => {
             permit.commit(state, 0);
-            Ok(delete_response())
+            Ok(response)
         }
         // A track that raced to deletion (no longer resolvable on-chain) is
         // treated as an idempotent success, matching S3; nothing was spent, so
         // refund the reservation.
         Err(TapedriveError::NotFound) => {
             permit.refund(state);
-            Ok(delete_response())
+            Ok(response)
         }
         Err(error) => {
             permit.refund(state);
@@ -1077,6 +1790,69 @@ where
     }
 }
 
+/// Whether `bucket` keeps a version history (versioning enabled now or at any
+/// point before).
+fn is_versioned<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    bucket: Address,
+) -> Result<bool, S3Error> {
+    state
+        .context
+        .store
+        .get_bucket_versioning(bucket)
+        .map(|status| status.is_some())
+        .map_err(|error| S3Error::Internal(error.to_string()))
+}
+
+/// Hide `key` behind a new delete marker placed on chain. Returns the id
+/// replay gives the marker, or `None` without a transaction when the key has
+/// no version to hide.
+///
+/// The marker stacks on the key's newest version, so its id is known before
+/// the transaction lands; while versioning is suspended it is the key's null
+/// version.
+async fn place_delete_marker<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    write_ctx: &S3WriteContext,
+    bucket: Address,
+    key: &str,
+) -> Result<Option<VersionSelector>, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let store = state.context.store.as_ref();
+    let internal = |error: TapeStoreError| S3Error::Internal(error.to_string());
+    let newest = match store.object_versions(bucket, key.as_bytes()).map_err(internal)?.first() {
+        Some((version, _)) => Some(*version),
+        None => store
+            .get_object_entry(bucket, key.as_bytes())
+            .map_err(internal)?
+            .map(|entry| ObjectVersionId::track(entry.track_number)),
+    };
+    let Some(newest) = newest else {
+        return Ok(None);
+    };
+    let suspended =
+        store.get_bucket_versioning(bucket).map_err(internal)? == Some(VersioningStatus::Suspended);
+
+    write_ctx
+        .place_delete_marker(state.context.as_ref(), bucket, key.as_bytes())
+        .await
+        .map_err(s3_write_error)?;
+
+    let marker = ObjectVersionId {
+        track_number: newest.track_number,
+        marker: newest.marker + 1,
+    };
+    Ok(Some(if suspended {
+        VersionSelector::Null
+    } else {
+        VersionSelector::Id(marker)
+    }))
+}
+
 /// S3 caps `max-parts` (and a single ListParts page) at 1000
 const MAX_PARTS_LIMIT: u32 = 1000;
 
@@ -1104,12 +1880,92 @@ fn validate_object_key(key: &str) -> Result<(), S3Error> {
     Ok(())
 }
 
-fn content_type_from_headers(headers: &HeaderMap) -> ContentType {
-    headers
-        .get(header::CONTENT_TYPE)
-        .and_then(|value| value.to_str().ok())
-        .map(ContentType::from_str)
-        .unwrap_or(ContentType::Unknown)
+/// Collect the representation headers and `x-amz-meta-*` user metadata a
+/// write stores with the object.
+///
+/// Repeated metadata headers are joined with `,` as S3 does. The encoded
+/// record must fit `MAX_OBJECT_HEADERS_LEN`, and every value must be one the
+/// gateway can serve back unchanged.
+fn object_headers_from_request(headers: &HeaderMap) -> Result<ObjectHeaders, S3Error> {
+    let text = |name: header::HeaderName| -> Result<Option<String>, S3Error> {
+        headers
+            .get(&name)
+            .map(|value| {
+                value
+                    .to_str()
+                    .map(str::to_string)
+                    .map_err(|_| S3Error::InvalidRequest(format!("{name} must be ASCII")))
+            })
+            .transpose()
+    };
+
+    let mut object_headers = ObjectHeaders {
+        content_type: text(header::CONTENT_TYPE)?,
+        content_encoding: text(header::CONTENT_ENCODING)?.and_then(strip_aws_chunked),
+        content_disposition: text(header::CONTENT_DISPOSITION)?,
+        cache_control: text(header::CACHE_CONTROL)?,
+        user_metadata: Vec::new(),
+    };
+
+    for name in headers.keys() {
+        let Some(key) = name.as_str().strip_prefix(USER_METADATA_PREFIX) else {
+            continue;
+        };
+        if key.is_empty() {
+            return Err(S3Error::InvalidRequest("empty user metadata key".into()));
+        }
+        let values = headers
+            .get_all(name)
+            .iter()
+            .map(|value| value.to_str())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| S3Error::InvalidRequest(format!("{name} must be ASCII")))?;
+        object_headers.insert_metadata(key, &values.join(","));
+    }
+
+    let Some(encoded) = object_headers.to_bytes() else {
+        return Err(S3Error::MetadataTooLarge(format!(
+            "object headers and user metadata take {} bytes, above the {MAX_OBJECT_HEADERS_LEN} byte limit",
+            object_headers.encoded_len()
+        )));
+    };
+    if ObjectHeaders::from_bytes(&encoded).is_none() {
+        return Err(S3Error::InvalidRequest(
+            "object headers contain unsupported characters".into(),
+        ));
+    }
+
+    Ok(object_headers)
+}
+
+/// Flatten an object's stored headers into the `(header name, value)` pairs a
+/// metadata listing reports, in the order a GET would send them.
+fn listing_metadata(headers: &ObjectHeaders, hot: ContentType) -> Vec<(String, String)> {
+    let mut pairs = vec![(header::CONTENT_TYPE.to_string(), headers.mime(hot).to_string())];
+    for (name, value) in [
+        (header::CONTENT_ENCODING, &headers.content_encoding),
+        (header::CONTENT_DISPOSITION, &headers.content_disposition),
+        (header::CACHE_CONTROL, &headers.cache_control),
+    ] {
+        if let Some(value) = value {
+            pairs.push((name.to_string(), value.clone()));
+        }
+    }
+    for entry in &headers.user_metadata {
+        pairs.push((format!("{USER_METADATA_PREFIX}{}", entry.key), entry.value.clone()));
+    }
+    pairs
+}
+
+/// Drop the `aws-chunked` transfer coding from a stored Content-Encoding; it
+/// describes the upload framing, not the object.
+fn strip_aws_chunked(encoding: String) -> Option<String> {
+    let kept = encoding
+        .split(',')
+        .map(str::trim)
+        .filter(|coding| !coding.is_empty() && !coding.eq_ignore_ascii_case("aws-chunked"))
+        .collect::<Vec<_>>();
+    (!kept.is_empty()).then(|| kept.join(","))
 }
 
 /// Borrow the delegate write context.
@@ -1138,7 +1994,7 @@ fn require_upload_id(query: Option<&str>) -> Result<String, S3Error> {
 /// `POST /{bucket}/{key}?uploads` -> CreateMultipartUpload
 ///
 /// Mints an opaque upload id bound to `(bucket, key)` and records the object's
-/// content type. No tracks are written until CompleteMultipartUpload.
+/// headers and user metadata. No tracks are written until CompleteMultipartUpload.
 async fn create_multipart_upload<Db, Cluster, Blockchain>(
     state: &AppState<Db, Cluster, Blockchain>,
     auth: &Auth,
@@ -1152,10 +2008,11 @@ where
     Blockchain: Rpc + 'static,
 {
     require_write_ctx(state, "CreateMultipartUpload")?;
+    sse::reject_sse_c(headers, "CreateMultipartUpload")?;
     let bucket = parse_bucket(&bucket_label)?;
     validate_object_key(&key)?;
 
-    let content_type = content_type_from_headers(headers);
+    let object_headers = object_headers_from_request(headers)?;
     let store = state.context.store.as_ref();
 
     // Authorization chokepoint.
@@ -1174,7 +2031,8 @@ where
         if multipart::count_open_uploads(store, principal)? as u64 >= limit as u64 {
             return Ok(None);
         }
-        multipart::create_upload(store, bucket, key.clone(), content_type, principal).map(Some)
+        multipart::create_upload(store, bucket, key.clone(), object_headers.clone(), principal)
+            .map(Some)
     });
     let upload_id = match admitted {
         Ok(Some(upload_id)) => {
@@ -1202,9 +2060,10 @@ where
 
 /// `PUT /{bucket}/{key}?uploadId=..&partNumber=..` -> UploadPart
 ///
-/// Buffers the part bytes under the upload id keyed by part number and returns
-/// the part's ETag (hex of a placeholder content hash) for the client to echo
-/// back at CompleteMultipartUpload.
+/// Writes the part bytes to their own chunk tracks on the bucket, records the
+/// tracks under the upload id keyed by part number, and returns the part's
+/// ETag (hex of its content hash) for the client to echo back at
+/// CompleteMultipartUpload.
 async fn upload_part<Db, Cluster, Blockchain>(
     state: &AppState<Db, Cluster, Blockchain>,
     auth: &Auth,
@@ -1226,22 +2085,336 @@ where
         .ok_or_else(|| S3Error::InvalidRequest("missing or invalid partNumber".into()))?;
     let store = state.context.store.as_ref();
 
-    // Authorization chokepoint: gate persisting a part. The part is held in the
-    // store until CompleteMultipartUpload (no on-chain cost here), so nothing is
-    // reserved — the object cost is reserved at CompleteMultipartUpload. The
-    // object ceiling bounds the bytes a single upload may stage.
+    // The object ceiling bounds the bytes a single upload may stage; check it
+    // before any track is written.
     let size = body.len() as u64;
     let max_object_bytes = state.context.config.gateway.s3.max_object_bytes;
+    multipart::check_part(store, &upload_id, bucket, &key, part_number, size, max_object_bytes)?;
+
+    // Authorization chokepoint: the part's bytes go on chain here, so they are
+    // reserved and billed here; CompleteMultipartUpload only adds the manifest.
     let permit = authorize_write(state, auth, bucket, &key, WriteOp::UploadPart, size).await?;
-    let etag = settle(
-        permit,
-        state,
-        size,
-        multipart::put_part(store, &upload_id, bucket, &key, part_number, body.to_vec(), max_object_bytes),
-    )?;
+    let result = write_part(state, &upload_id, bucket, &key, part_number, &body).await;
+    let etag = settle(permit, state, size, result)?;
     upload_part_response(etag)
 }
 
+/// Write one part's bytes to chunk tracks on `bucket` and record them as part
+/// `part_number` of `upload_id`, returning the part's ETag. Tracks nothing
+/// references afterwards, the replaced part's or these when the upload is
+/// gone, are deleted.
+async fn write_part<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    upload_id: &str,
+    bucket: Address,
+    key: &str,
+    part_number: u32,
+    data: &[u8],
+) -> Result<Hash, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let write_ctx = require_write_ctx(state, "UploadPart")?;
+    let chunks = write_ctx
+        .write_part(state.context.as_ref(), bucket, data)
+        .await
+        .map_err(s3_write_error)?;
+    let part = MultipartPart {
+        part_number,
+        etag: hash(data),
+        last_modified: now_unix(),
+        size: data.len() as u64,
+    };
+    let chunks = MultipartPartChunks { chunks };
+    let store = state.context.store.as_ref();
+    match multipart::record_part(store, upload_id, bucket, key, &part, &chunks) {
+        Ok(replaced) => {
+            delete_part_tracks(state, bucket, &replaced).await;
+            Ok(part.etag)
+        }
+        Err(error) => {
+            let written: Vec<TrackNumber> =
+                chunks.chunks.iter().map(|chunk| chunk.track_number).collect();
+            delete_part_tracks(state, bucket, &written).await;
+            Err(error)
+        }
+    }
+}
+
+/// Delete multipart part tracks on `bucket` that no object or upload
+/// references anymore. A failed delete only leaves reclaimable bytes on the
+/// tape, so it is logged rather than failing the request.
+async fn delete_part_tracks<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    bucket: Address,
+    tracks: &[TrackNumber],
+) where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let Some(write_ctx) = state.write_ctx.as_ref() else {
+        return;
+    };
+    if tracks.is_empty() {
+        return;
+    }
+    let addresses: Vec<Address> = tracks
+        .iter()
+        .map(|track_number| track_pda(bucket, *track_number).0)
+        .collect();
+    match write_ctx
+        .delete_objects(state.context.as_ref(), bucket, &addresses)
+        .await
+    {
+        Ok(results) => {
+            for (address, result) in addresses.iter().zip(results) {
+                if let Err(error) = result {
+                    tracing::warn!(%address, %error, "s3 multipart: failed to delete part track");
+                }
+            }
+        }
+        Err(error) => tracing::warn!(%error, "s3 multipart: failed to delete part tracks"),
+    }
+}
+
+/// `PUT /{bucket}/{key}?uploadId=..&partNumber=..` with `x-amz-copy-source` ->
+/// UploadPartCopy
+///
+/// Reads `x-amz-copy-source-range` (or the whole source) from the stored object
+/// and writes it as the part, exactly as if the bytes had been uploaded. The
+/// range is bounded by the same buffering limit as an uploaded part.
+async fn upload_part_copy<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket_label: String,
+    key: String,
+    query: Option<&str>,
+    headers: &HeaderMap,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    require_write_ctx(state, "UploadPartCopy")?;
+    let bucket = parse_bucket(&bucket_label)?;
+    let upload_id = require_upload_id(query)?;
+    let part_number = query_value(query, "partNumber")
+        .and_then(|value| value.parse::<u32>().ok())
+        .ok_or_else(|| S3Error::InvalidRequest("missing or invalid partNumber".into()))?;
+
+    let source = copy::copy_source(headers)?;
+    let (resolved, track) =
+        resolve_readable(state, auth, &source.bucket, &source.key, source.version)?;
+    let range = match headers
+        .get(copy::AMZ_COPY_SOURCE_RANGE)
+        .map(|value| value.to_str())
+    {
+        Some(Ok(value)) => copy::parse_copy_range(value, resolved.size)?,
+        Some(Err(_)) => {
+            return Err(S3Error::InvalidRequest("unreadable x-amz-copy-source-range".into()));
+        }
+        None => ByteRange {
+            start: 0,
+            end: resolved.size,
+        },
+    };
+
+    let size = range.end - range.start;
+    let max_buffered_bytes = state.context.config.gateway.s3.max_buffered_bytes;
+    if size > max_buffered_bytes as u64 {
+        return Err(S3Error::EntityTooLarge(format!(
+            "copy range of {size} bytes exceeds the maximum of {max_buffered_bytes} bytes"
+        )));
+    }
+
+    // Same checks and chokepoint as UploadPart, before the source is read.
+    let store = state.context.store.as_ref();
+    let max_object_bytes = state.context.config.gateway.s3.max_object_bytes;
+    multipart::check_part(store, &upload_id, bucket, &key, part_number, size, max_object_bytes)?;
+    let permit = authorize_write(state, auth, bucket, &key, WriteOp::UploadPart, size).await?;
+    let part = match read_copy_range(state, &resolved, track, range).await {
+        Ok(part) => part,
+        Err(error) => {
+            permit.refund(state);
+            return Err(error);
+        }
+    };
+
+    let result = write_part(state, &upload_id, bucket, &key, part_number, &part).await;
+    let etag = settle(permit, state, size, result)?;
+    Ok(xml_ok_response(copy_result_body(
+        "CopyPartResult",
+        &hex::encode(etag),
+        now_unix(),
+    )))
+}
+
+/// Read `range` of a resolved object into memory, decoding only the chunk
+/// tracks the range touches when the object is a stream.
+async fn read_copy_range<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    resolved: &ResolvedObject,
+    track: CompressedTrack,
+    range: ByteRange,
+) -> Result<Vec<u8>, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    match open_object(state, resolved.track_address, track).await? {
+        OpenedObject::Single(decoded) => decoded
+            .bytes
+            .get(range.start as usize..range.end as usize)
+            .map(<[u8]>::to_vec)
+            .ok_or(S3Error::InvalidRange(resolved.size)),
+        OpenedObject::Stream { manifest, .. } => {
+            let manifest = Arc::new(manifest);
+            let chunks = stream_range_bytes(
+                state.clone(),
+                resolved.data_tape,
+                resolved.track_address,
+                manifest,
+                range,
+                None,
+            )?;
+            let chunks: Vec<Bytes> = chunks.try_collect().await?;
+            Ok(chunks.concat())
+        }
+    }
+}
+
+/// `POST /{bucket}?delete` -> DeleteObjects
+///
+/// Every key passes the same authorization chokepoint (and leaves the same
+/// audit entry) as DeleteObject. Keys that resolve to a track are deleted
+/// together, packed into as few transactions as fit; keys absent from the
+/// index count as deleted. In a versioned bucket each key gets a delete
+/// marker instead. Failures are reported per key in the result body, which
+/// quiet mode trims to the failures alone.
+async fn delete_objects<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    auth: &Auth,
+    bucket_label: String,
+    body: Bytes,
+) -> Result<Response, S3Error>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let Some(write_ctx) = state.write_ctx.as_ref() else {
+        return Err(write_not_implemented(false, "DeleteObjects"));
+    };
+
+    let tape = parse_bucket(&bucket_label)?;
+    let body_text = std::str::from_utf8(&body)
+        .map_err(|_| S3Error::InvalidRequest("DeleteObjects body is not valid UTF-8".into()))?;
+    let request = parse_delete_objects(body_text).map_err(S3Error::InvalidRequest)?;
+    let versioned = is_versioned(state, tape)?;
+
+    let mut deleted = Vec::new();
+    let mut errors = Vec::new();
+    // Keys backed by a live track, with their permits, in request order.
+    let mut targets: Vec<(String, Address, WritePermit)> = Vec::new();
+    for key in request.keys {
+        let permit = match authorize_write(state, auth, tape, &key, WriteOp::Delete, 0).await {
+            Ok(permit) => permit,
+            Err(error) => {
+                errors.push(delete_error_entry(key, &error));
+                continue;
+            }
+        };
+        // A versioned bucket hides each key behind a delete marker instead of
+        // deleting its track.
+        if versioned {
+            match place_delete_marker(state, write_ctx, tape, &key).await {
+                Ok(Some(_)) => {
+                    permit.commit(state, 0);
+                    deleted.push(key);
+                }
+                Ok(None) => {
+                    permit.refund(state);
+                    deleted.push(key);
+                }
+                Err(error) => {
+                    permit.refund(state);
+                    errors.push(delete_error_entry(key, &error));
+                }
+            }
+            continue;
+        }
+        match resolve_object(state, tape, &key) {
+            Ok(Some(resolved))
+                if !targets.iter().any(|(_, track, _)| *track == resolved.track_address) =>
+            {
+                targets.push((key, resolved.track_address, permit));
+            }
+            // Absent keys (and repeats of a queued key) are already deleted as
+            // far as S3 is concerned; nothing is spent.
+            Ok(_) => {
+                permit.refund(state);
+                deleted.push(key);
+            }
+            Err(error) => {
+                permit.refund(state);
+                errors.push(delete_error_entry(key, &error));
+            }
+        }
+    }
+
+    if !targets.is_empty() {
+        let tracks: Vec<Address> = targets.iter().map(|(_, track, _)| *track).collect();
+        match write_ctx.delete_objects(state.context.as_ref(), tape, &tracks).await {
+            Ok(results) => {
+                for ((key, _, permit), result) in targets.into_iter().zip(results) {
+                    match result {
+                        Ok(()) => {
+                            permit.commit(state, 0);
+                            deleted.push(key);
+                        }
+                        // Raced to deletion: idempotent success, as in DeleteObject.
+                        Err(TapedriveError::NotFound) => {
+                            permit.refund(state);
+                            deleted.push(key);
+                        }
+                        Err(error) => {
+                            permit.refund(state);
+                            errors.push(delete_error_entry(key, &s3_write_error(error)));
+                        }
+                    }
+                }
+            }
+            Err(error) => {
+                let error = s3_write_error(error);
+                for (key, _, permit) in targets {
+                    permit.refund(state);
+                    errors.push(delete_error_entry(key, &error));
+                }
+            }
+        }
+    }
+
+    Ok(xml_ok_response(delete_result_body(request.quiet, &deleted, &errors)))
+}
+
+/// Render a per-key DeleteObjects failure, logging what an internal error
+/// keeps out of the response.
+fn delete_error_entry(key: String, error: &S3Error) -> DeleteErrorEntry {
+    if let S3Error::Internal(detail) = error {
+        tracing::error!(%key, "s3 DeleteObjects: {detail}");
+    }
+    DeleteErrorEntry {
+        key,
+        code: error.code(),
+        message: error.message(),
+    }
+}
+
 /// `GET /{bucket}/{key}?uploadId=..` -> ListParts
 ///
 /// Reports the upload's parts (number, size, ETag, last-modified) in ascending
@@ -1313,7 +2486,8 @@ where
 
 /// `DELETE /{bucket}/{key}?uploadId=..` -> AbortMultipartUpload
 ///
-/// Discards the buffered parts for the upload id and returns `204 No Content`.
+/// Discards the upload id's parts, deletes the chunk tracks they were written
+/// to, and returns `204 No Content`.
 /// Aborting another tenant's in-flight upload deletes durable state, so it
 /// passes the write chokepoint and `multipart::abort` additionally requires the
 /// caller to own the upload.
@@ -1336,8 +2510,9 @@ where
 
     let permit = authorize_write(state, auth, bucket, &key, WriteOp::Abort, 0).await?;
     match multipart::abort(store, &upload_id, bucket, &key, permit.owner()) {
-        Ok(()) => {
+        Ok(tracks) => {
             permit.commit(state, 0);
+            delete_part_tracks(state, bucket, &tracks).await;
             Ok(delete_response())
         }
         Err(error) => {
@@ -1350,9 +2525,9 @@ where
 /// `POST /{bucket}/{key}?uploadId=..` -> CompleteMultipartUpload
 ///
 /// Parses the part list from the request body, validates it against the
-/// buffered parts, concatenates them in part-number order, and writes the
-/// assembled object. An object that fits in a single 64 MiB chunk track is
-/// materialized as one delegate-signed named track.
+/// uploaded parts, and writes a manifest naming the parts' chunk tracks in
+/// part-number order; no part bytes are read or rewritten. The response
+/// carries the S3 multipart ETag, `<digest of part ETags>-<part count>`.
 async fn complete_multipart_upload<Db, Cluster, Blockchain>(
     state: &AppState<Db, Cluster, Blockchain>,
     auth: &Auth,
@@ -1380,38 +2555,29 @@ where
         requested.push(CompletedPartRef { part_number, etag });
     }
 
-    // Validate the client's part list and concatenate the persisted parts. The
-    // upload is retained until the write below lands, so a failed write leaves
-    // it intact for the client to retry or abort.
+    // Validate the client's part list and gather the parts' chunk tracks. The
+    // upload is retained until the manifest below lands, so a failed write
+    // leaves it intact for the client to retry or abort.
     let max_object_bytes = state.context.config.gateway.s3.max_object_bytes;
     let assembled = multipart::assemble(store, &upload_id, bucket, &key, &requested, max_object_bytes)?;
 
-    // Authorization chokepoint.
-    let size = assembled.data.len() as u64;
-    let permit = authorize_write(state, auth, bucket, &key, WriteOp::CompleteMultipart, size).await?;
-    let result = write_ctx
-        .write_object(
-            state.context.as_ref(),
-            bucket,
-            assembled.key.as_bytes(),
-            assembled.content_type,
-            &assembled.data,
-        )
-        .await;
+    // Authorization chokepoint. Size-capped rules see the whole object, but
+    // only the manifest is billed; the parts were billed at UploadPart.
+    let size = assembled.size;
+    let op = WriteOp::CompleteMultipart;
+    let permit = authorize_write(state, auth, bucket, &key, op, size).await?;
+    let result = link_parts(state, write_ctx, bucket, &assembled).await;
     // On failure `?` returns before the upload is dropped, so it stays intact for
     // the client to retry or abort.
-    let written_etag = settle_write(permit, state, size, result)?;
+    settle_write(permit, state, op.stored_bytes(size), result)?;
 
-    // The object is durable; drop the persisted upload state. A delete failure
-    // only leaks reclaimable upload state, so log it rather than fail the write.
+    // The object is durable; drop the persisted upload state and the tracks of
+    // parts the client left out. A delete failure only leaks reclaimable state,
+    // so log it rather than fail the write.
     if let Err(error) = multipart::remove(store, &upload_id) {
         tracing::warn!(?error, "s3 CompleteMultipartUpload: failed to drop upload state");
     }
-
-    // Mirror PutObject's ETag resolution.
-    let etag = resolve_object(state, bucket, &assembled.key)?
-        .map(|resolved| resolved.etag)
-        .unwrap_or(written_etag);
+    delete_part_tracks(state, bucket, &assembled.unlisted).await;
 
     // Location is the configured public endpoint URL, else a path-style resource.
     let location = match &state.context.config.gateway.s3.public_endpoint {
@@ -1422,10 +2588,47 @@ where
         &location,
         &bucket_label,
         &assembled.key,
-        &etag.to_string(),
+        &assembled.etag,
     )))
 }
 
+/// Write the manifest of a completed multipart upload over its parts' chunk
+/// tracks. Parts buffered before parts were written to tracks are written out
+/// first; an empty object is a single empty named track, as PutObject writes
+/// it.
+async fn link_parts<Db, Cluster, Blockchain>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    write_ctx: &S3WriteContext,
+    bucket: Address,
+    assembled: &AssembledUpload,
+) -> Result<Hash, TapedriveError>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let context = state.context.as_ref();
+    let name = assembled.key.as_bytes();
+    if assembled.size == 0 {
+        return write_ctx
+            .write_object(context, bucket, name, &assembled.headers, &[], None)
+            .await;
+    }
+
+    let mut parts = Vec::with_capacity(assembled.parts.len());
+    for part in &assembled.parts {
+        match part {
+            PartSource::Chunks(chunks) => parts.push(chunks.clone()),
+            PartSource::Buffered(data) => {
+                parts.push(write_ctx.write_part(context, bucket, data).await?)
+            }
+        }
+    }
+    write_ctx
+        .link_object(context, bucket, name, &assembled.headers, &parts)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1473,6 +2676,39 @@ mod tests {
         assert!(!has_query_param(None, "uploads", None));
     }
 
+    // request headers become the object's stored headers
+    #[test]
+    fn request_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, "application/x-parquet".parse().unwrap());
+        headers.insert(header::CONTENT_ENCODING, "gzip, aws-chunked".parse().unwrap());
+        headers.insert(header::CACHE_CONTROL, "no-cache".parse().unwrap());
+        headers.append("x-amz-meta-team", "data".parse().unwrap());
+        headers.append("x-amz-meta-team", "lake".parse().unwrap());
+
+        let object_headers = object_headers_from_request(&headers).expect("headers");
+
+        assert_eq!(object_headers.content_type.as_deref(), Some("application/x-parquet"));
+        assert_eq!(object_headers.content_encoding.as_deref(), Some("gzip"));
+        assert_eq!(object_headers.cache_control.as_deref(), Some("no-cache"));
+        assert_eq!(object_headers.content_disposition, None);
+        assert_eq!(object_headers.user_metadata.len(), 1);
+        assert_eq!(object_headers.user_metadata[0].key, "team");
+        assert_eq!(object_headers.user_metadata[0].value, "data,lake");
+    }
+
+    // oversized user metadata is rejected before any write
+    #[test]
+    fn request_metadata_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-amz-meta-blob", "a".repeat(MAX_OBJECT_HEADERS_LEN).parse().unwrap());
+
+        assert!(matches!(
+            object_headers_from_request(&headers),
+            Err(S3Error::MetadataTooLarge(_))
+        ));
+    }
+
     // write-pipeline errors map onto the S3 surface
     #[test]
     fn error_mapping() {
@@ -0,0 +1,154 @@
+//! S3 server-side encryption with customer-provided keys (SSE-C)
+//!
+//! The request's 256-bit key wraps the object's data key in the same chunk
+//! envelope SDK clients write, so an object put through the gateway with SSE-C
+//! reads back through the SDK with the same key, and vice versa. The key is
+//! held only for the request and never stored.
+
+use std::sync::Arc;
+
+use axum::http::{HeaderMap, HeaderValue};
+use base64::{decode, encode};
+use md5::{Digest, Md5};
+use tape_sdk::stream::envelope::{KeyProvider, StaticKeyProvider};
+
+use super::error::S3Error;
+
+/// Header naming the SSE-C algorithm; only `AES256` is accepted
+pub const AMZ_SSE_CUSTOMER_ALGORITHM: &str = "x-amz-server-side-encryption-customer-algorithm";
+/// Header carrying the base64 customer key
+pub const AMZ_SSE_CUSTOMER_KEY: &str = "x-amz-server-side-encryption-customer-key";
+/// Header carrying the base64 MD5 of the customer key
+pub const AMZ_SSE_CUSTOMER_KEY_MD5: &str = "x-amz-server-side-encryption-customer-key-md5";
+
+/// The one SSE-C algorithm S3 defines
+const SSE_ALGORITHM: &str = "AES256";
+
+/// A request's customer key, ready to seal or open an object
+pub struct CustomerKey {
+    provider: Arc<StaticKeyProvider>,
+    /// The client's key digest, echoed back on success
+    key_md5: String,
+}
+
+impl CustomerKey {
+    /// The key as a provider for the write client.
+    pub fn provider(&self) -> Arc<dyn KeyProvider> {
+        self.provider.clone()
+    }
+
+    /// The key as a provider for the read path.
+    pub fn as_provider(&self) -> &dyn KeyProvider {
+        self.provider.as_ref()
+    }
+
+    /// Echo the SSE-C algorithm and key digest, as S3 does on every SSE-C
+    /// response.
+    pub fn set_response_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(AMZ_SSE_CUSTOMER_ALGORITHM, HeaderValue::from_static(SSE_ALGORITHM));
+        if let Ok(value) = HeaderValue::from_str(&self.key_md5) {
+            headers.insert(AMZ_SSE_CUSTOMER_KEY_MD5, value);
+        }
+    }
+}
+
+/// Whether the request carries any SSE-C header.
+pub fn is_sse_c(headers: &HeaderMap) -> bool {
+    headers.contains_key(AMZ_SSE_CUSTOMER_ALGORITHM)
+        || headers.contains_key(AMZ_SSE_CUSTOMER_KEY)
+        || headers.contains_key(AMZ_SSE_CUSTOMER_KEY_MD5)
+}
+
+/// Parse the request's SSE-C headers: `None` without any, an error when they
+/// are incomplete or malformed.
+///
+/// The key must decode to exactly 256 bits, and the MD5 header must be the
+/// base64 MD5 of the decoded key, so a key mangled in transit is refused
+/// before it seals or opens anything.
+pub fn customer_key(headers: &HeaderMap) -> Result<Option<CustomerKey>, S3Error> {
+    if !is_sse_c(headers) {
+        return Ok(None);
+    }
+
+    let algorithm = header(headers, AMZ_SSE_CUSTOMER_ALGORITHM)?;
+    if algorithm != SSE_ALGORITHM {
+        return Err(S3Error::InvalidRequest(format!(
+            "unsupported {AMZ_SSE_CUSTOMER_ALGORITHM} {algorithm:?}; only AES256 is supported"
+        )));
+    }
+    let key = decode(header(headers, AMZ_SSE_CUSTOMER_KEY)?)
+        .map_err(|_| S3Error::InvalidRequest(format!("{AMZ_SSE_CUSTOMER_KEY} is not base64")))?;
+    let provider = StaticKeyProvider::from_slice(&key)
+        .map_err(|error| S3Error::InvalidRequest(format!("{AMZ_SSE_CUSTOMER_KEY}: {error}")))?;
+    let key_md5 = header(headers, AMZ_SSE_CUSTOMER_KEY_MD5)?.to_string();
+    if encode(Md5::digest(&key)) != key_md5 {
+        return Err(S3Error::InvalidRequest(format!(
+            "{AMZ_SSE_CUSTOMER_KEY_MD5} does not match the supplied key"
+        )));
+    }
+
+    Ok(Some(CustomerKey {
+        provider: Arc::new(provider),
+        key_md5,
+    }))
+}
+
+/// Refuse SSE-C on an operation that cannot honor it yet.
+pub fn reject_sse_c(headers: &HeaderMap, operation: &str) -> Result<(), S3Error> {
+    if is_sse_c(headers) {
+        return Err(S3Error::NotImplemented(format!(
+            "{operation} with customer-provided encryption keys is not supported"
+        )));
+    }
+    Ok(())
+}
+
+fn header<'headers>(headers: &'headers HeaderMap, name: &str) -> Result<&'headers str, S3Error> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| S3Error::InvalidRequest(format!("missing or unreadable {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use tape_sdk::stream::envelope::KEY_LEN;
+
+    use super::*;
+
+    fn sse_headers(algorithm: &str, key: &[u8]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AMZ_SSE_CUSTOMER_ALGORITHM, algorithm.parse().unwrap());
+        headers.insert(AMZ_SSE_CUSTOMER_KEY, encode(key).parse().unwrap());
+        headers.insert(AMZ_SSE_CUSTOMER_KEY_MD5, encode(Md5::digest(key)).parse().unwrap());
+        headers
+    }
+
+    // no headers is a plaintext request; a full set yields a key
+    #[test]
+    fn key_parse() {
+        assert!(customer_key(&HeaderMap::new()).unwrap().is_none());
+
+        let key = customer_key(&sse_headers("AES256", &[7; KEY_LEN])).unwrap().unwrap();
+        let mut headers = HeaderMap::new();
+        key.set_response_headers(&mut headers);
+        assert_eq!(headers[AMZ_SSE_CUSTOMER_ALGORITHM], "AES256");
+        assert_eq!(headers[AMZ_SSE_CUSTOMER_KEY_MD5], encode(Md5::digest([7; KEY_LEN])));
+    }
+
+    // a partial set, another algorithm, a short key, or a wrong digest is rejected
+    #[test]
+    fn key_invalid() {
+        let mut mismatched = sse_headers("AES256", &[7; KEY_LEN]);
+        let wrong_md5 = encode(Md5::digest([8; KEY_LEN]));
+        mismatched.insert(AMZ_SSE_CUSTOMER_KEY_MD5, wrong_md5.parse().unwrap());
+        assert!(customer_key(&mismatched).is_err());
+
+        let mut partial = sse_headers("AES256", &[7; KEY_LEN]);
+        partial.remove(AMZ_SSE_CUSTOMER_KEY_MD5);
+        assert!(customer_key(&partial).is_err());
+        assert!(customer_key(&sse_headers("aws:kms", &[7; KEY_LEN])).is_err());
+        assert!(customer_key(&sse_headers("AES256", &[7; 16])).is_err());
+        assert!(reject_sse_c(&partial, "CopyObject").is_err());
+    }
+}
@@ -5,11 +5,13 @@
 //! tape's own authority key, which the gateway never holds.
 
 use std::path::Path;
+use std::sync::Arc;
 
 use arc_swap::ArcSwap;
 use rpc::Rpc;
 use store::Store;
-use tape_core::types::{ContentType, StorageUnits};
+use tape_core::tape::TapeVersioning;
+use tape_core::types::{ObjectHeaders, StorageUnits};
 use tape_crypto::address::Address;
 use tape_crypto::ed25519::{Keypair, Pubkey};
 use tape_crypto::Hash;
@@ -19,8 +21,10 @@ use tape_protocol::Api;
 use tape_sdk::error::TapedriveError;
 use tape_sdk::keys::helpers::load_ed25519_keypair;
 use tape_sdk::keys::operator::TapeDelegate;
-use tape_sdk::stream::manifest::MAX_TRACK_SIZE;
+use tape_sdk::stream::envelope::KeyProvider;
+use tape_sdk::stream::manifest::{ChunkEntry, ChunkManifest, MAX_TRACK_SIZE};
 use tape_sdk::Tapedrive;
+use tape_store::types::{ObjectVersionId, PartChunk};
 use tokio::io::AsyncRead;
 use zeroize::Zeroizing;
 
@@ -59,23 +63,30 @@ impl S3WriteContext {
     }
 
     /// Build an SDK `Tapedrive`` write client over the gateway's shared node
-    /// resources, with the delegate as fee payer.
+    /// resources, with the delegate as fee payer and, for SSE-C writes, the
+    /// request's key provider.
     fn client<Db, Cluster, Blockchain>(
         &self,
         context: &NodeContext<Db, Cluster, Blockchain>,
+        key_provider: Option<Arc<dyn KeyProvider>>,
     ) -> Result<Tapedrive<Blockchain, Cluster>, TapedriveError>
     where
         Db: Store,
         Cluster: Api,
         Blockchain: Rpc,
     {
-        Ok(Tapedrive::from_parts(
+        let client = Tapedrive::from_parts(
             ArcSwap::new(context.state()),
             context.peer_manager.clone(),
             context.api.clone(),
             context.rpc.clone(),
             Some(self.delegate_keypair()?),
-        ))
+        )
+        .with_slice_batcher(context.slices.clone());
+        Ok(match key_provider {
+            Some(key_provider) => client.with_key_provider(key_provider),
+            None => client,
+        })
     }
 
     /// Build the delegate operator bound to a specific target `tape`.
@@ -84,30 +95,35 @@ impl S3WriteContext {
     }
 
     /// Write an in-memory object to `tape` as the delegate, returning its ETag.
+    ///
+    /// With a `key_provider` a non-empty object is always written as a sealed
+    /// stream, since only stream manifests carry an envelope.
     pub async fn write_object<Db, Cluster, Blockchain>(
         &self,
         context: &NodeContext<Db, Cluster, Blockchain>,
         tape: Address,
         name: &[u8],
-        content_type: ContentType,
+        headers: &ObjectHeaders,
         data: &[u8],
+        key_provider: Option<Arc<dyn KeyProvider>>,
     ) -> Result<Hash, TapedriveError>
     where
         Db: Store,
         Cluster: Api,
         Blockchain: Rpc,
     {
-        let client = self.client(context)?;
+        let sealed = key_provider.is_some() && !data.is_empty();
+        let client = self.client(context, key_provider)?;
         let operator = self.operator(tape)?;
 
-        if data.len() <= MAX_TRACK_SIZE {
+        if data.len() <= MAX_TRACK_SIZE && !sealed {
             let track = client
-                .write_named_track_as(&operator, name, content_type, data)
+                .write_named_track_as(&operator, name, headers.clone(), data)
                 .await?;
             Ok(track.value_hash)
         } else {
             let receipt = client
-                .write_named_bytes_as(&operator, name, content_type, data)
+                .write_named_bytes_as(&operator, name, headers.clone(), data)
                 .await?;
             Ok(receipt.manifest_value_hash)
         }
@@ -119,9 +135,10 @@ impl S3WriteContext {
         context: &NodeContext<Db, Cluster, Blockchain>,
         tape: Address,
         name: &[u8],
-        content_type: ContentType,
+        headers: &ObjectHeaders,
         size: StorageUnits,
         reader: Reader,
+        key_provider: Option<Arc<dyn KeyProvider>>,
     ) -> Result<Hash, TapedriveError>
     where
         Db: Store,
@@ -129,10 +146,87 @@ impl S3WriteContext {
         Blockchain: Rpc,
         Reader: AsyncRead + Unpin,
     {
-        let client = self.client(context)?;
+        let client = self.client(context, key_provider)?;
         let operator = self.operator(tape)?;
         let receipt = client
-            .write_named_stream_as(&operator, name, content_type, size, reader)
+            .write_named_stream_as(&operator, name, headers.clone(), size, reader)
+            .await?;
+        Ok(receipt.manifest_value_hash)
+    }
+
+    /// Name a new object over the chunk tracks of an existing stream on `tape`,
+    /// writing only its manifest.
+    pub async fn relink_object<Db, Cluster, Blockchain>(
+        &self,
+        context: &NodeContext<Db, Cluster, Blockchain>,
+        tape: Address,
+        name: &[u8],
+        headers: &ObjectHeaders,
+        source: &ChunkManifest,
+    ) -> Result<Hash, TapedriveError>
+    where
+        Db: Store,
+        Cluster: Api,
+        Blockchain: Rpc,
+    {
+        let client = self.client(context, None)?;
+        let operator = self.operator(tape)?;
+        let receipt = client
+            .relink_stream_as(&operator, name, headers.clone(), source)
+            .await?;
+        Ok(receipt.manifest_value_hash)
+    }
+
+    /// Write one multipart part onto unnamed chunk tracks of `tape` as the
+    /// delegate, returning the tracks in order. An empty part takes no track.
+    pub async fn write_part<Db, Cluster, Blockchain>(
+        &self,
+        context: &NodeContext<Db, Cluster, Blockchain>,
+        tape: Address,
+        data: &[u8],
+    ) -> Result<Vec<PartChunk>, TapedriveError>
+    where
+        Db: Store,
+        Cluster: Api,
+        Blockchain: Rpc,
+    {
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+        let client = self.client(context, None)?;
+        let operator = self.operator(tape)?;
+        let size = StorageUnits::from_bytes(data.len() as u64);
+        let entries = client.write_chunks_as(&operator, size, data).await?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| PartChunk {
+                track_number: entry.track_number,
+                size: entry.size.to_bytes(),
+            })
+            .collect())
+    }
+
+    /// Name a new object over the chunk tracks of its multipart parts on
+    /// `tape`, in order, writing only its manifest.
+    pub async fn link_object<Db, Cluster, Blockchain>(
+        &self,
+        context: &NodeContext<Db, Cluster, Blockchain>,
+        tape: Address,
+        name: &[u8],
+        headers: &ObjectHeaders,
+        parts: &[Vec<PartChunk>],
+    ) -> Result<Hash, TapedriveError>
+    where
+        Db: Store,
+        Cluster: Api,
+        Blockchain: Rpc,
+    {
+        let runs: Vec<Vec<ChunkEntry>> =
+            parts.iter().map(Vec::as_slice).map(part_entries).collect();
+        let client = self.client(context, None)?;
+        let operator = self.operator(tape)?;
+        let receipt = client
+            .link_stream_as(&operator, name, headers.clone(), &runs)
             .await?;
         Ok(receipt.manifest_value_hash)
     }
@@ -149,8 +243,99 @@ impl S3WriteContext {
         Cluster: Api,
         Blockchain: Rpc,
     {
-        let client = self.client(context)?;
+        let client = self.client(context, None)?;
         let operator = self.operator(tape)?;
         client.delete_as(&operator, track).await
     }
+
+    /// Delete the `tracks` backing several objects on `tape` as the delegate,
+    /// batching the deletes into as few transactions as fit. Results line up
+    /// with `tracks`.
+    pub async fn delete_objects<Db, Cluster, Blockchain>(
+        &self,
+        context: &NodeContext<Db, Cluster, Blockchain>,
+        tape: Address,
+        tracks: &[Address],
+    ) -> Result<Vec<Result<(), TapedriveError>>, TapedriveError>
+    where
+        Db: Store,
+        Cluster: Api,
+        Blockchain: Rpc,
+    {
+        let client = self.client(context, None)?;
+        let operator = self.operator(tape)?;
+        client.delete_batch_as(&operator, tracks).await
+    }
+
+    /// Enable or suspend versioning on `tape` as the delegate. Every node
+    /// applies the status when it replays the transaction.
+    pub async fn set_versioning<Db, Cluster, Blockchain>(
+        &self,
+        context: &NodeContext<Db, Cluster, Blockchain>,
+        tape: Address,
+        status: TapeVersioning,
+    ) -> Result<(), TapedriveError>
+    where
+        Db: Store,
+        Cluster: Api,
+        Blockchain: Rpc,
+    {
+        let client = self.client(context, None)?;
+        let operator = self.operator(tape)?;
+        client.set_tape_versioning_as(&operator, status).await
+    }
+
+    /// Hide `name` on versioned `tape` behind a new delete marker as the
+    /// delegate.
+    pub async fn place_delete_marker<Db, Cluster, Blockchain>(
+        &self,
+        context: &NodeContext<Db, Cluster, Blockchain>,
+        tape: Address,
+        name: &[u8],
+    ) -> Result<(), TapedriveError>
+    where
+        Db: Store,
+        Cluster: Api,
+        Blockchain: Rpc,
+    {
+        let client = self.client(context, None)?;
+        let operator = self.operator(tape)?;
+        client.place_delete_marker_as(&operator, name).await
+    }
+
+    /// Remove the delete marker `version` of `name` on `tape` as the delegate.
+    pub async fn remove_delete_marker<Db, Cluster, Blockchain>(
+        &self,
+        context: &NodeContext<Db, Cluster, Blockchain>,
+        tape: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+    ) -> Result<(), TapedriveError>
+    where
+        Db: Store,
+        Cluster: Api,
+        Blockchain: Rpc,
+    {
+        let client = self.client(context, None)?;
+        let operator = self.operator(tape)?;
+        client
+            .remove_delete_marker_as(&operator, name, version.track_number, version.marker)
+            .await
+    }
+}
+
+/// A part's chunk tracks as manifest entries, offset from the part's start.
+fn part_entries(chunks: &[PartChunk]) -> Vec<ChunkEntry> {
+    let mut offset = StorageUnits::zero();
+    let mut entries = Vec::with_capacity(chunks.len());
+    for chunk in chunks {
+        let size = StorageUnits::from_bytes(chunk.size);
+        entries.push(ChunkEntry {
+            track_number: chunk.track_number,
+            offset,
+            size,
+        });
+        offset = offset.saturating_add(size);
+    }
+    entries
 }
@@ -10,6 +10,9 @@ pub const S3_XMLNS: &str = "http://s3.amazonaws.com/doc/2006-03-01/";
 #[allow(dead_code)]
 pub const STORAGE_CLASS_STANDARD: &str = "STANDARD";
 
+use tape_core::types::MetadataEntry;
+use tape_store::types::{BucketLifecycle, LifecycleRule};
+
 use super::clock::{SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE};
 
 /// Escape the five predefined XML entities in `value` into `out`
@@ -152,6 +155,9 @@ pub struct ObjectEntry {
     pub size: u64,
     /// S3 storage class (typically STORAGE_CLASS_STANDARD)
     pub storage_class: &'static str,
+    /// Stored headers as `(header name, value)` pairs, rendered as
+    /// `<UserMetadata>` when the listing asked for `metadata=true`
+    pub user_metadata: Option<Vec<(String, String)>>,
 }
 
 /// A `ListBucketResult` (ListObjectsV2) response.
@@ -222,6 +228,18 @@ pub fn list_objects_v2_body(result: &ListObjectsV2) -> String {
         out.push_str("\"</ETag>");
         push_element(&mut out, "Size", &entry.size.to_string());
         push_element(&mut out, "StorageClass", entry.storage_class);
+        if let Some(metadata) = &entry.user_metadata {
+            // Header names are not always valid XML element names, so each pair
+            // is its own <Items> element.
+            out.push_str("<UserMetadata>");
+            for (name, value) in metadata {
+                out.push_str("<Items>");
+                push_element(&mut out, "Key", name);
+                push_element(&mut out, "Value", value);
+                out.push_str("</Items>");
+            }
+            out.push_str("</UserMetadata>");
+        }
         out.push_str("</Contents>");
     }
 
@@ -337,6 +355,205 @@ pub fn complete_multipart_upload_body(
     out
 }
 
+/// Build a `CopyObjectResult` (CopyObject) or `CopyPartResult` (UploadPartCopy)
+/// body; `root` picks which.
+pub fn copy_result_body(root: &str, etag: &str, last_modified: i64) -> String {
+    let mut out = String::with_capacity(256);
+    out.push_str(XML_DECL);
+    out.push('<');
+    out.push_str(root);
+    out.push_str(" xmlns=\"");
+    out.push_str(S3_XMLNS);
+    out.push_str("\">");
+    push_element(&mut out, "LastModified", &iso8601(last_modified));
+    out.push_str("<ETag>\"");
+    escape_into(&mut out, etag);
+    out.push_str("\"</ETag>");
+    out.push_str("</");
+    out.push_str(root);
+    out.push('>');
+    out
+}
+
+/// One `<Error>` entry in a `DeleteResult` body
+pub struct DeleteErrorEntry {
+    /// Key that could not be deleted
+    pub key: String,
+    /// S3 error code
+    pub code: &'static str,
+    /// Human-readable reason
+    pub message: String,
+}
+
+/// Build a `DeleteResult` (DeleteObjects) body. Quiet mode leaves out the
+/// keys that were deleted.
+pub fn delete_result_body(quiet: bool, deleted: &[String], errors: &[DeleteErrorEntry]) -> String {
+    let mut out = String::with_capacity(128 + deleted.len() * 64 + errors.len() * 160);
+    out.push_str(XML_DECL);
+    out.push_str("<DeleteResult xmlns=\"");
+    out.push_str(S3_XMLNS);
+    out.push_str("\">");
+    if !quiet {
+        for key in deleted {
+            out.push_str("<Deleted>");
+            push_element(&mut out, "Key", key);
+            out.push_str("</Deleted>");
+        }
+    }
+    for error in errors {
+        out.push_str("<Error>");
+        push_element(&mut out, "Key", &error.key);
+        push_element(&mut out, "Code", error.code);
+        push_element(&mut out, "Message", &error.message);
+        out.push_str("</Error>");
+    }
+    out.push_str("</DeleteResult>");
+    out
+}
+
+/// Build a `VersioningConfiguration` (GetBucketVersioning) body. A bucket that
+/// was never configured reports no `<Status>`.
+pub fn versioning_configuration_body(status: Option<&str>) -> String {
+    let mut out = String::with_capacity(160);
+    out.push_str(XML_DECL);
+    out.push_str("<VersioningConfiguration xmlns=\"");
+    out.push_str(S3_XMLNS);
+    out.push_str("\">");
+    push_optional(&mut out, "Status", status);
+    out.push_str("</VersioningConfiguration>");
+    out
+}
+
+/// One `<Version>` or `<DeleteMarker>` entry in a `ListVersionsResult` body
+pub struct VersionEntry {
+    /// Object key
+    pub key: String,
+    /// Version id as clients echo it back in `versionId`
+    pub version_id: String,
+    /// Whether this is the key's newest version
+    pub is_latest: bool,
+    /// Last-modified time in unix seconds, when known
+    pub last_modified: Option<i64>,
+    /// ETag and size of an object version; `None` renders a `<DeleteMarker>`
+    pub object: Option<(String, u64)>,
+}
+
+/// A `ListVersionsResult` (ListObjectVersions) response
+pub struct ListVersions {
+    /// Bucket name (the base58 tape address as requested)
+    pub name: String,
+    /// Echoed request prefix (empty string when none was supplied)
+    pub prefix: String,
+    /// Echoed `key-marker` (empty string when none was supplied)
+    pub key_marker: String,
+    /// Echoed `version-id-marker` (empty string when none was supplied)
+    pub version_id_marker: String,
+    /// Key to resume from, when `is_truncated`
+    pub next_key_marker: Option<String>,
+    /// Version to resume after within `next_key_marker`, when `is_truncated`
+    pub next_version_id_marker: Option<String>,
+    /// Echoed, clamped `max-keys`
+    pub max_keys: u32,
+    /// Whether more versions remain beyond this page
+    pub is_truncated: bool,
+    /// Versions in key order, each key's versions newest first
+    pub versions: Vec<VersionEntry>,
+}
+
+/// Build a `ListVersionsResult` (ListObjectVersions) body
+pub fn list_versions_body(result: &ListVersions) -> String {
+    let mut out = String::with_capacity(512 + result.versions.len() * 256);
+    out.push_str(XML_DECL);
+    out.push_str("<ListVersionsResult xmlns=\"");
+    out.push_str(S3_XMLNS);
+    out.push_str("\">");
+
+    push_element(&mut out, "Name", &result.name);
+    push_element(&mut out, "Prefix", &result.prefix);
+    push_element(&mut out, "KeyMarker", &result.key_marker);
+    push_element(&mut out, "VersionIdMarker", &result.version_id_marker);
+    push_optional(&mut out, "NextKeyMarker", result.next_key_marker.as_deref());
+    push_optional(
+        &mut out,
+        "NextVersionIdMarker",
+        result.next_version_id_marker.as_deref(),
+    );
+    push_element(&mut out, "MaxKeys", &result.max_keys.to_string());
+    push_element(&mut out, "IsTruncated", if result.is_truncated { "true" } else { "false" });
+
+    for entry in &result.versions {
+        let tag = if entry.object.is_some() { "Version" } else { "DeleteMarker" };
+        out.push('<');
+        out.push_str(tag);
+        out.push('>');
+        push_element(&mut out, "Key", &entry.key);
+        push_element(&mut out, "VersionId", &entry.version_id);
+        push_element(&mut out, "IsLatest", if entry.is_latest { "true" } else { "false" });
+        push_element(
+            &mut out,
+            "LastModified",
+            &iso8601(entry.last_modified.unwrap_or(0)),
+        );
+        if let Some((etag, size)) = &entry.object {
+            out.push_str("<ETag>\"");
+            escape_into(&mut out, etag);
+            out.push_str("\"</ETag>");
+            push_element(&mut out, "Size", &size.to_string());
+            push_element(&mut out, "StorageClass", STORAGE_CLASS_STANDARD);
+        }
+        out.push_str("</");
+        out.push_str(tag);
+        out.push('>');
+    }
+
+    out.push_str("</ListVersionsResult>");
+    out
+}
+
+/// Build a `LifecycleConfiguration` (GetBucketLifecycleConfiguration) body
+pub fn lifecycle_configuration_body(lifecycle: &BucketLifecycle) -> String {
+    let mut out = String::with_capacity(256 + lifecycle.rules.len() * 256);
+    out.push_str(XML_DECL);
+    out.push_str("<LifecycleConfiguration xmlns=\"");
+    out.push_str(S3_XMLNS);
+    out.push_str("\">");
+
+    for rule in &lifecycle.rules {
+        out.push_str("<Rule>");
+        push_element(&mut out, "ID", &rule.id);
+        out.push_str("<Filter>");
+        if rule.tags.is_empty() {
+            push_element(&mut out, "Prefix", &rule.prefix);
+        } else {
+            out.push_str("<And>");
+            push_element(&mut out, "Prefix", &rule.prefix);
+            for tag in &rule.tags {
+                out.push_str("<Tag>");
+                push_element(&mut out, "Key", &tag.key);
+                push_element(&mut out, "Value", &tag.value);
+                out.push_str("</Tag>");
+            }
+            out.push_str("</And>");
+        }
+        out.push_str("</Filter>");
+        push_element(&mut out, "Status", if rule.enabled { "Enabled" } else { "Disabled" });
+        if let Some(days) = rule.expiration_days {
+            out.push_str("<Expiration>");
+            push_element(&mut out, "Days", &days.to_string());
+            out.push_str("</Expiration>");
+        }
+        if let Some(days) = rule.noncurrent_days {
+            out.push_str("<NoncurrentVersionExpiration>");
+            push_element(&mut out, "NoncurrentDays", &days.to_string());
+            out.push_str("</NoncurrentVersionExpiration>");
+        }
+        out.push_str("</Rule>");
+    }
+
+    out.push_str("</LifecycleConfiguration>");
+    out
+}
+
 /// One `<Part>` entry in a `ListPartsResult` body
 pub struct PartEntry {
     /// Part number (1..=10000)
@@ -458,6 +675,177 @@ pub fn parse_complete_multipart_upload(body: &str) -> Result<Vec<(u32, String)>,
     Ok(parts)
 }
 
+/// Most keys one `DeleteObjects` request may name
+pub const MAX_DELETE_KEYS: usize = 1000;
+
+/// A parsed `DeleteObjects` request body
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeleteRequest {
+    /// Report only the keys that failed
+    pub quiet: bool,
+    /// Keys to delete, in request order
+    pub keys: Vec<String>,
+}
+
+/// Parse a `DeleteObjects` request body. Version selectors are not supported.
+pub fn parse_delete_objects(body: &str) -> Result<DeleteRequest, String> {
+    let quiet = extract_element(body, "Quiet").is_some_and(|value| value.trim() == "true");
+
+    let mut keys = Vec::new();
+    let mut rest = body;
+    while let Some(open) = rest.find("<Object>") {
+        let after = &rest[open + "<Object>".len()..];
+        let close = after
+            .find("</Object>")
+            .ok_or_else(|| "unterminated <Object> element".to_string())?;
+        let block = &after[..close];
+
+        if block.contains("<VersionId>") {
+            return Err("DeleteObjects version selectors are not supported".to_string());
+        }
+        let key = extract_element(block, "Key")
+            .ok_or_else(|| "missing <Key> in <Object>".to_string())?;
+        keys.push(key);
+        rest = &after[close + "</Object>".len()..];
+    }
+
+    if keys.is_empty() {
+        return Err("DeleteObjects listed no <Object> elements".to_string());
+    }
+    if keys.len() > MAX_DELETE_KEYS {
+        return Err(format!("DeleteObjects lists more than {MAX_DELETE_KEYS} keys"));
+    }
+    Ok(DeleteRequest { quiet, keys })
+}
+
+/// Parse a `PutBucketVersioning` body into its `<Status>` value.
+pub fn parse_versioning_configuration(body: &str) -> Result<String, String> {
+    if extract_element(body, "MfaDelete").is_some_and(|value| value.trim() == "Enabled") {
+        return Err("MFA delete is not supported".to_string());
+    }
+    extract_element(body, "Status")
+        .map(|status| status.trim().to_string())
+        .ok_or_else(|| "missing <Status> in VersioningConfiguration".to_string())
+}
+
+/// Most rules one `LifecycleConfiguration` may carry, as in S3
+pub const MAX_LIFECYCLE_RULES: usize = 1000;
+
+/// Parse a `PutBucketLifecycleConfiguration` body.
+///
+/// Supports prefix and tag filters (legacy `<Prefix>`, `<Filter>` and
+/// `<And>`), `<Expiration><Days>` and `<NoncurrentVersionExpiration>`. Tags
+/// are matched against user metadata, whose keys are case-insensitive, so tag
+/// keys are lowercased. Date-based expiration and transitions are rejected
+/// rather than ignored.
+pub fn parse_lifecycle_configuration(body: &str) -> Result<BucketLifecycle, String> {
+    let mut rules = Vec::new();
+    let mut rest = body;
+    while let Some((block, after)) = next_block(rest, "Rule")? {
+        rest = after;
+        rules.push(parse_lifecycle_rule(block, rules.len())?);
+    }
+
+    if rules.is_empty() {
+        return Err("LifecycleConfiguration listed no <Rule> elements".to_string());
+    }
+    if rules.len() > MAX_LIFECYCLE_RULES {
+        return Err(format!(
+            "LifecycleConfiguration lists more than {MAX_LIFECYCLE_RULES} rules"
+        ));
+    }
+    Ok(BucketLifecycle { rules })
+}
+
+fn parse_lifecycle_rule(block: &str, index: usize) -> Result<LifecycleRule, String> {
+    for unsupported in [
+        "Transition",
+        "NoncurrentVersionTransition",
+        "AbortIncompleteMultipartUpload",
+    ] {
+        if block.contains(&format!("<{unsupported}>")) {
+            return Err(format!("lifecycle <{unsupported}> is not supported"));
+        }
+    }
+
+    let id = extract_element(block, "ID")
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| format!("rule-{}", index + 1));
+    let enabled = match extract_element(block, "Status").as_deref().map(str::trim) {
+        Some("Enabled") => true,
+        Some("Disabled") => false,
+        _ => return Err(format!("rule {id}: <Status> must be Enabled or Disabled")),
+    };
+    let prefix = extract_element(block, "Prefix").unwrap_or_default();
+
+    let mut tags = Vec::new();
+    let mut rest = block;
+    while let Some((tag, after)) = next_block(rest, "Tag")? {
+        rest = after;
+        let key = extract_element(tag, "Key")
+            .ok_or_else(|| format!("rule {id}: missing <Key> in <Tag>"))?;
+        tags.push(MetadataEntry {
+            key: key.to_ascii_lowercase(),
+            value: extract_element(tag, "Value").unwrap_or_default(),
+        });
+    }
+
+    let expiration_days = match next_block(block, "Expiration")? {
+        Some((expiration, _)) => {
+            if expiration.contains("<Date>")
+                || expiration.contains("<ExpiredObjectDeleteMarker>")
+            {
+                return Err(format!("rule {id}: only <Days> expiration is supported"));
+            }
+            Some(parse_days(expiration, "Days", &id)?)
+        }
+        None => None,
+    };
+    let noncurrent_days = match next_block(block, "NoncurrentVersionExpiration")? {
+        Some((expiration, _)) => Some(parse_days(expiration, "NoncurrentDays", &id)?),
+        None => None,
+    };
+    if expiration_days.is_none() && noncurrent_days.is_none() {
+        return Err(format!("rule {id}: no supported expiration action"));
+    }
+
+    Ok(LifecycleRule {
+        id,
+        enabled,
+        prefix,
+        tags,
+        expiration_days,
+        noncurrent_days,
+    })
+}
+
+/// Read a positive day count from `<tag>` in `block`.
+fn parse_days(block: &str, tag: &str, id: &str) -> Result<u32, String> {
+    extract_element(block, tag)
+        .and_then(|days| days.trim().parse::<u32>().ok())
+        .filter(|days| *days > 0)
+        .ok_or_else(|| format!("rule {id}: <{tag}> must be a positive whole number of days"))
+}
+
+/// Split the raw content of the first `<tag>...</tag>` in `body` from the text
+/// after it.
+fn next_block<'body>(
+    body: &'body str,
+    tag: &str,
+) -> Result<Option<(&'body str, &'body str)>, String> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let Some(start) = body.find(&open) else {
+        return Ok(None);
+    };
+    let after = &body[start + open.len()..];
+    let end = after
+        .find(&close)
+        .ok_or_else(|| format!("unterminated <{tag}> element"))?;
+    Ok(Some((&after[..end], &after[end + close.len()..])))
+}
+
 /// Read the text content of the first `<tag>...</tag>` in `block`, unescaping the
 /// predefined XML entities.
 fn extract_element(block: &str, tag: &str) -> Option<String> {
@@ -570,6 +958,7 @@ mod tests {
                 etag: "abc123".to_string(),
                 size: 42,
                 storage_class: STORAGE_CLASS_STANDARD,
+                user_metadata: None,
             }],
             common_prefixes: vec!["photos/sub/".to_string()],
         };
@@ -612,6 +1001,7 @@ mod tests {
                 etag: "d41d8cd98f00b204e9800998ecf8427e".to_string(),
                 size: 11,
                 storage_class: STORAGE_CLASS_STANDARD,
+                user_metadata: None,
             }],
             common_prefixes: Vec::new(),
         };
@@ -635,6 +1025,57 @@ mod tests {
         assert_eq!(list_objects_v2_body(&result), expected);
     }
 
+    // a copy result carries the quoted ETag and LastModified
+    #[test]
+    fn copy_result() {
+        let body = copy_result_body("CopyPartResult", "abc", 1_255_369_830);
+        assert_eq!(
+            body,
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+                "<CopyPartResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">",
+                "<LastModified>2009-10-12T17:50:30.000Z</LastModified>",
+                "<ETag>\"abc\"</ETag>",
+                "</CopyPartResult>",
+            )
+        );
+    }
+
+    // metadata listings render stored headers per object
+    #[test]
+    fn list_user_metadata() {
+        let result = ListObjectsV2 {
+            name: "bucket".to_string(),
+            prefix: String::new(),
+            delimiter: None,
+            max_keys: 1000,
+            key_count: 1,
+            is_truncated: false,
+            continuation_token: None,
+            next_continuation_token: None,
+            start_after: None,
+            contents: vec![ObjectEntry {
+                key: "part-0.parquet".to_string(),
+                last_modified: None,
+                etag: "abc".to_string(),
+                size: 1,
+                storage_class: STORAGE_CLASS_STANDARD,
+                user_metadata: Some(vec![
+                    ("content-type".to_string(), "application/x-parquet".to_string()),
+                    ("x-amz-meta-team".to_string(), "a&b".to_string()),
+                ]),
+            }],
+            common_prefixes: Vec::new(),
+        };
+        let body = list_objects_v2_body(&result);
+        assert!(body.contains(concat!(
+            "<StorageClass>STANDARD</StorageClass><UserMetadata>",
+            "<Items><Key>content-type</Key><Value>application/x-parquet</Value></Items>",
+            "<Items><Key>x-amz-meta-team</Key><Value>a&amp;b</Value></Items>",
+            "</UserMetadata></Contents>",
+        )));
+    }
+
     // a V1 listing uses Marker/NextMarker and omits the V2-only fields
     #[test]
     fn list_v1_render() {
@@ -652,6 +1093,7 @@ mod tests {
                 etag: "abc123".to_string(),
                 size: 42,
                 storage_class: STORAGE_CLASS_STANDARD,
+                user_metadata: None,
             }],
             common_prefixes: vec!["photos/sub/".to_string()],
         };
@@ -764,6 +1206,159 @@ mod tests {
         assert!(parse_complete_multipart_upload(body).is_err());
     }
 
+    // the delete body parses its keys and quiet flag
+    #[test]
+    fn parse_delete() {
+        let body = "<Delete><Quiet>true</Quiet>\
+            <Object><Key>a &amp; b</Key></Object>\
+            <Object><Key>photos/c.jpg</Key></Object>\
+            </Delete>";
+        let request = parse_delete_objects(body).expect("test setup");
+        assert!(request.quiet);
+        assert_eq!(request.keys, vec!["a & b".to_string(), "photos/c.jpg".to_string()]);
+
+        assert!(parse_delete_objects("<Delete/>").is_err());
+        let versioned = "<Delete><Object><Key>a</Key><VersionId>v</VersionId></Object></Delete>";
+        assert!(parse_delete_objects(versioned).is_err());
+    }
+
+    // a delete result lists deletions only outside quiet mode, and errors always
+    #[test]
+    fn delete_result() {
+        let deleted = vec!["gone".to_string()];
+        let errors = vec![DeleteErrorEntry {
+            key: "kept".to_string(),
+            code: "AccessDenied",
+            message: "denied".to_string(),
+        }];
+
+        let body = delete_result_body(false, &deleted, &errors);
+        assert!(body.contains("<Deleted><Key>gone</Key></Deleted>"));
+        assert!(body.contains(
+            "<Error><Key>kept</Key><Code>AccessDenied</Code><Message>denied</Message></Error>"
+        ));
+
+        let quiet = delete_result_body(true, &deleted, &errors);
+        assert!(!quiet.contains("<Deleted>"));
+        assert!(quiet.contains("<Error>"));
+    }
+
+    // the versioning status parses out of the request and renders back
+    #[test]
+    fn versioning_configuration() {
+        let body = "<VersioningConfiguration><Status>Enabled</Status></VersioningConfiguration>";
+        assert_eq!(parse_versioning_configuration(body).unwrap(), "Enabled");
+        assert!(parse_versioning_configuration("<VersioningConfiguration/>").is_err());
+
+        assert!(versioning_configuration_body(Some("Suspended"))
+            .contains("<Status>Suspended</Status>"));
+        assert!(!versioning_configuration_body(None).contains("<Status>"));
+    }
+
+    // prefix, tag and both expiration kinds parse out of a rule
+    #[test]
+    fn lifecycle_parse() {
+        let body = "<LifecycleConfiguration>\
+            <Rule><ID>logs</ID><Filter><Prefix>logs/</Prefix></Filter><Status>Enabled</Status>\
+            <Expiration><Days>30</Days></Expiration></Rule>\
+            <Rule><Filter><And><Prefix>tmp/</Prefix><Tag><Key>Tier</Key><Value>scratch</Value></Tag>\
+            </And></Filter><Status>Disabled</Status>\
+            <NoncurrentVersionExpiration><NoncurrentDays>7</NoncurrentDays></NoncurrentVersionExpiration>\
+            </Rule></LifecycleConfiguration>";
+        let lifecycle = parse_lifecycle_configuration(body).unwrap();
+        assert_eq!(lifecycle.rules.len(), 2);
+
+        let logs = &lifecycle.rules[0];
+        assert_eq!(logs.id, "logs");
+        assert!(logs.enabled);
+        assert_eq!(logs.prefix, "logs/");
+        assert_eq!(logs.expiration_days, Some(30));
+        assert_eq!(logs.noncurrent_days, None);
+
+        let tmp = &lifecycle.rules[1];
+        assert_eq!(tmp.id, "rule-2");
+        assert!(!tmp.enabled);
+        assert_eq!(tmp.prefix, "tmp/");
+        assert_eq!(
+            tmp.tags,
+            vec![MetadataEntry {
+                key: "tier".to_string(),
+                value: "scratch".to_string(),
+            }]
+        );
+        assert_eq!(tmp.noncurrent_days, Some(7));
+
+        let rendered = lifecycle_configuration_body(&lifecycle);
+        assert_eq!(parse_lifecycle_configuration(&rendered).unwrap(), lifecycle);
+    }
+
+    // unsupported actions and malformed rules are rejected
+    #[test]
+    fn lifecycle_invalid() {
+        let rule = |inner: &str| {
+            format!(
+                "<LifecycleConfiguration><Rule><Status>Enabled</Status>{inner}</Rule>\
+                 </LifecycleConfiguration>"
+            )
+        };
+        assert!(parse_lifecycle_configuration("<LifecycleConfiguration/>").is_err());
+        assert!(parse_lifecycle_configuration(&rule("")).is_err());
+        assert!(
+            parse_lifecycle_configuration(&rule("<Expiration><Days>0</Days></Expiration>")).is_err()
+        );
+        assert!(parse_lifecycle_configuration(&rule(
+            "<Expiration><Date>2030-01-01T00:00:00Z</Date></Expiration>"
+        ))
+        .is_err());
+        assert!(parse_lifecycle_configuration(&rule(
+            "<Expiration><Days>1</Days></Expiration><Transition><Days>1</Days></Transition>"
+        ))
+        .is_err());
+        assert!(parse_lifecycle_configuration(
+            "<LifecycleConfiguration><Rule><Expiration><Days>1</Days></Expiration></Rule>\
+             </LifecycleConfiguration>"
+        )
+        .is_err());
+    }
+
+    // object versions and delete markers render as their own elements
+    #[test]
+    fn versions_render() {
+        let result = ListVersions {
+            name: "bucket".to_string(),
+            prefix: String::new(),
+            key_marker: String::new(),
+            version_id_marker: String::new(),
+            next_key_marker: Some("a".to_string()),
+            next_version_id_marker: Some("3".to_string()),
+            max_keys: 2,
+            is_truncated: true,
+            versions: vec![
+                VersionEntry {
+                    key: "a".to_string(),
+                    version_id: "7.1".to_string(),
+                    is_latest: true,
+                    last_modified: Some(0),
+                    object: None,
+                },
+                VersionEntry {
+                    key: "a".to_string(),
+                    version_id: "3".to_string(),
+                    is_latest: false,
+                    last_modified: None,
+                    object: Some(("abc".to_string(), 42)),
+                },
+            ],
+        };
+        let body = list_versions_body(&result);
+        assert!(body.contains(
+            "<DeleteMarker><Key>a</Key><VersionId>7.1</VersionId><IsLatest>true</IsLatest>"
+        ));
+        assert!(body.contains("<Version><Key>a</Key><VersionId>3</VersionId><IsLatest>false</IsLatest>"));
+        assert!(body.contains("<ETag>\"abc\"</ETag><Size>42</Size>"));
+        assert!(body.contains("<NextKeyMarker>a</NextKeyMarker><NextVersionIdMarker>3</NextVersionIdMarker>"));
+    }
+
     // unescape inverts escape
     #[test]
     fn round_trip() {
@@ -20,8 +20,9 @@ use tape_protocol::api::{
 };
 use tape_store::ops::{ObjectListOps, TapeOps, TrackOps};
 
-use super::{parse_address, track_data_with_pending, track_with_pending};
+use super::{authorize_native_read, parse_address, track_data_with_pending, track_with_pending};
 use crate::http::error::RouteError;
+use crate::http::handlers::s3::authz::ReadOp;
 use crate::http::handlers::{binary_response, store_error};
 use crate::http::state::AppState;
 
@@ -46,6 +47,7 @@ pub async fn get_track_data<Db: Store, Cluster: Api, Blockchain: Rpc>(
 ) -> Result<impl IntoResponse, RouteError> {
     let track_addr = parse_address(&track_id, "track id")?;
     let track = track_with_pending(&state, track_addr)?.ok_or(RouteError::NotFound)?;
+    authorize_native_read(&state, track.tape, ReadOp::Get)?;
     let data_addr = track_pda(track.tape, track.track_number).0.into();
     let data = track_data_with_pending(&state, data_addr)?.ok_or(RouteError::NotFound)?;
 
@@ -205,6 +207,7 @@ pub async fn list_objects<Db: Store, Cluster: Api, Blockchain: Rpc>(
     body: Bytes,
 ) -> Result<impl IntoResponse, RouteError> {
     let bucket = parse_address(&tape_id, "tape id")?;
+    authorize_native_read(&state, bucket, ReadOp::List)?;
     let request: ListObjectsRequest = wincode::deserialize(&body)
         .map_err(|error| RouteError::BadRequest(format!("list objects request: {error}")))?;
     let limit = (request.limit as usize).clamp(1, MAX_OBJECT_LIST_LIMIT);
@@ -238,6 +241,7 @@ pub async fn list_objects<Db: Store, Cluster: Api, Blockchain: Rpc>(
             track_number: entry.track_number,
             kind: entry.kind,
             content_type: entry.content_type,
+            headers: entry.headers,
         })
         .collect();
 
@@ -7,6 +7,8 @@ use tape_protocol::Api;
 use tape_store::ops::{TrackDataOps, TrackOps};
 
 use crate::http::error::RouteError;
+use crate::http::handlers::s3::authz::{Auth, ReadOp, authorize_read};
+use crate::http::handlers::s3::error::S3Error;
 use crate::http::handlers::store_error;
 use crate::http::state::AppState;
 
@@ -39,6 +41,20 @@ pub fn track_data_with_pending<Db: Store, Cluster: Api, Blockchain: Rpc>(
     }
 }
 
+/// Gate a native read of `bucket`. Native routes carry no credentials, so
+/// they read as an anonymous S3 caller would: a private bucket is refused and
+/// read policy rules still apply.
+pub fn authorize_native_read<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    bucket: Address,
+    op: ReadOp,
+) -> Result<(), RouteError> {
+    authorize_read(state, &Auth::Anonymous, bucket, None, op).map_err(|error| match error {
+        S3Error::AccessDenied(reason) => RouteError::Forbidden(reason),
+        other => RouteError::Internal(other.message()),
+    })
+}
+
 pub fn parse_address(value: &str, label: &str) -> Result<Address, RouteError> {
     value
         .parse()
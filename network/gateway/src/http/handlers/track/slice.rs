@@ -1,28 +1,35 @@
 use axum::extract::{Path, State};
-use axum::http::{StatusCode, header};
+use axum::http::{HeaderMap, StatusCode, header};
 use axum::response::IntoResponse;
 use rpc::Rpc;
 use store::Store;
-use tape_core::erasure::GROUP_SIZE;
+use tape_core::track::blob::BlobEncoding;
 use tape_core::track::data::BlobData;
-use tape_core::types::SpoolIndex;
+use tape_core::types::{GroupIndex, SpoolIndex};
 use tape_crypto::address::Address;
-use tape_crypto::merkle::hash_leaf;
 use tape_protocol::Api;
 use tape_protocol::api::{BINARY_CONTENT, GetSliceReq};
 use tracing::debug;
 
 use super::{parse_address, track_data_with_pending, track_with_pending};
-use crate::cache::{CacheRead, CacheSource};
+use crate::cache::{CacheRead, CacheSource, SliceCacheKey};
+use crate::fleet::{FLEET_PEER_HEADER, FleetPeer};
 use crate::http::error::RouteError;
 use crate::http::state::AppState;
 
 pub async fn get_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
     State(state): State<AppState<Db, Cluster, Blockchain>>,
     Path((track_id, spool_id)): Path<(String, SpoolIndex)>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, RouteError> {
     let track_addr = parse_address(&track_id, "track id")?;
-    let read = read_cached_slice(&state, track_addr, spool_id).await?;
+    // A fleet peer only forwards slices it thinks this gateway owns; serving
+    // them locally keeps a disagreement over owners from bouncing a request.
+    let read = if headers.contains_key(FLEET_PEER_HEADER) {
+        read_owned_slice(&state, track_addr, spool_id).await?
+    } else {
+        read_cached_slice(&state, track_addr, spool_id).await?
+    };
     let data = read.data;
     state.context.metrics.add_downloaded(data.len() as u64);
     if read.source == CacheSource::Hit {
@@ -32,10 +39,52 @@ pub async fn get_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
     Ok((StatusCode::OK, [(header::CONTENT_TYPE, BINARY_CONTENT)], data))
 }
 
+/// Read a slice through the cache, asking the fleet peer that owns it before
+/// the storage nodes.
 pub async fn read_cached_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
     state: &AppState<Db, Cluster, Blockchain>,
     track_addr: Address,
     spool_id: SpoolIndex,
+) -> Result<CacheRead, RouteError> {
+    if let Some(peer) = state.fleet.owner_of(SliceCacheKey::new(spool_id, track_addr)) {
+        // A copy from an earlier peer failure still serves.
+        if let Some(data) = state.slice_cache.get(spool_id, track_addr)? {
+            return Ok(CacheRead {
+                data,
+                source: CacheSource::Hit,
+            });
+        }
+
+        match fetch_slice_from_peer(state, peer, track_addr, spool_id).await {
+            Ok(data) => {
+                crate::metrics::inc_cache("peer");
+                return Ok(CacheRead {
+                    data,
+                    source: CacheSource::Peer,
+                });
+            }
+            Err(error) => {
+                crate::metrics::inc_cache("peer_failed");
+                debug!(
+                    track = %track_addr,
+                    spool = spool_id.0,
+                    peer = peer.url(),
+                    %error,
+                    "fleet peer slice fetch failed, reading from storage nodes"
+                );
+            }
+        }
+    }
+
+    read_owned_slice(state, track_addr, spool_id).await
+}
+
+/// Read a slice this gateway owns: from its cache, or once from the
+/// storage node holding the spool.
+async fn read_owned_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_addr: Address,
+    spool_id: SpoolIndex,
 ) -> Result<CacheRead, RouteError> {
     state
         .slice_cache
@@ -45,11 +94,43 @@ pub async fn read_cached_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
         .await
 }
 
-async fn fetch_slice_from_owner<Db: Store, Cluster: Api, Blockchain: Rpc>(
+/// Peers are not trusted: their slice is checked against the blob's leaf
+/// like any storage node reply.
+async fn fetch_slice_from_peer<Db: Store, Cluster: Api, Blockchain: Rpc>(
     state: &AppState<Db, Cluster, Blockchain>,
+    peer: &FleetPeer,
     track_addr: Address,
     spool_id: SpoolIndex,
 ) -> Result<Vec<u8>, RouteError> {
+    let (_, blob, position) = expected_slice(state, track_addr, spool_id)?;
+    let data = state
+        .fleet
+        .get_slice(peer, track_addr, spool_id)
+        .await
+        .map_err(|error| RouteError::BadGateway(format!("fleet get_slice: {error}")))?;
+
+    if !blob.verify_slice(position, &data) {
+        return Err(RouteError::BadGateway("fleet slice leaf hash mismatch".into()));
+    }
+
+    debug!(
+        track = %track_addr,
+        spool = spool_id.0,
+        peer = peer.url(),
+        bytes = data.len(),
+        "gateway fetched slice from fleet peer"
+    );
+
+    Ok(data)
+}
+
+/// The group of a coded track, its blob, and the position whose leaf the
+/// slice on `spool_id` must match.
+fn expected_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_addr: Address,
+    spool_id: SpoolIndex,
+) -> Result<(GroupIndex, BlobEncoding, SpoolIndex), RouteError> {
     let track = track_with_pending(state, track_addr)?.ok_or(RouteError::NotFound)?;
     if !track.is_coded() {
         return Err(RouteError::BadRequest("track is not coded".into()));
@@ -67,17 +148,27 @@ async fn fetch_slice_from_owner<Db: Store, Cluster: Api, Blockchain: Rpc>(
         ));
     };
 
+    Ok((track.group, blob, SpoolIndex(position as u64)))
+}
+
+async fn fetch_slice_from_owner<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_addr: Address,
+    spool_id: SpoolIndex,
+) -> Result<Vec<u8>, RouteError> {
+    let (group, blob, position) = expected_slice(state, track_addr, spool_id)?;
+
     let owner = state
         .context
         .state()
-        .group_peers(track.group)
+        .group_peers(group)
         .into_iter()
         .find_map(|(spool, node)| (spool == spool_id).then_some(node))
         .ok_or_else(|| RouteError::BadGateway("spool owner not found".into()))?;
 
     let response = state
         .context
-        .api
+        .slices
         .get_slice(
             owner,
             &GetSliceReq {
@@ -88,7 +179,7 @@ async fn fetch_slice_from_owner<Db: Store, Cluster: Api, Blockchain: Rpc>(
         .await
         .map_err(|error| RouteError::BadGateway(format!("get_slice: {error}")))?;
 
-    if position >= GROUP_SIZE || hash_leaf(&response.data) != blob.leaves[position] {
+    if !blob.verify_slice(position, &response.data) {
         return Err(RouteError::BadGateway("slice leaf hash mismatch".into()));
     }
 
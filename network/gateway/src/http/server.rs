@@ -25,11 +25,13 @@ use tower_http::trace::TraceLayer;
 use tracing::info;
 
 use crate::admission::{AdmitAll, Admission};
-use crate::cache::GatewaySliceCache;
+use crate::cache::{DecodedChunkCache, GatewaySliceCache};
+use crate::fleet::GatewayFleet;
 use crate::http::AppState;
 use crate::http::handlers::s3::{
     accounting::{Accounting, reservation_sweep_loop},
     admin::{AdminState, admin_router},
+    lifecycle::lifecycle_loop,
     routes::router,
     sigv4::verifier_from_config,
     write::S3WriteContext,
@@ -40,6 +42,8 @@ use crate::meter::GatewayMeter;
 pub struct GatewayHttpServer<Db: Store, Cluster: Api, Blockchain: Rpc> {
     context: Arc<NodeContext<Db, Cluster, Blockchain>>,
     slice_cache: Arc<GatewaySliceCache<Db>>,
+    decoded_cache: Arc<DecodedChunkCache>,
+    fleet: Arc<GatewayFleet>,
     meter: Arc<GatewayMeter>,
     http_config: HttpConfig,
     cancel: CancellationToken,
@@ -51,12 +55,14 @@ where
     Cluster: Api + 'static,
     Blockchain: Rpc + 'static,
 {
-    /// Build the native read listener over a slice cache and meter shared with
-    /// the S3 listener, so the disk-cache budget and read rate limits are each
-    /// enforced once across both listeners rather than twice.
+    /// Build the native read listener over slice and decoded caches and a meter
+    /// shared with the S3 listener, so the cache budgets and read rate limits
+    /// are each enforced once across both listeners rather than twice.
     pub fn new(
         context: Arc<NodeContext<Db, Cluster, Blockchain>>,
         slice_cache: Arc<GatewaySliceCache<Db>>,
+        decoded_cache: Arc<DecodedChunkCache>,
+        fleet: Arc<GatewayFleet>,
         meter: Arc<GatewayMeter>,
         http_config: HttpConfig,
         cancel: CancellationToken,
@@ -64,6 +70,8 @@ where
         Self {
             context,
             slice_cache,
+            decoded_cache,
+            fleet,
             meter,
             http_config,
             cancel,
@@ -74,6 +82,8 @@ where
         let state = AppState {
             context: self.context.clone(),
             slice_cache: self.slice_cache.clone(),
+            decoded_cache: self.decoded_cache.clone(),
+            fleet: self.fleet.clone(),
             meter: self.meter.clone(),
             // The native read listener never writes, so it carries no delegate
             // signing context and its accounting and admission state are never
@@ -209,6 +219,8 @@ where
 pub struct GatewayS3Server<Db: Store, Cluster: Api, Blockchain: Rpc> {
     context: Arc<NodeContext<Db, Cluster, Blockchain>>,
     slice_cache: Arc<GatewaySliceCache<Db>>,
+    decoded_cache: Arc<DecodedChunkCache>,
+    fleet: Arc<GatewayFleet>,
     meter: Arc<GatewayMeter>,
     write_ctx: Option<Arc<S3WriteContext>>,
     accounting: Arc<Accounting>,
@@ -223,12 +235,14 @@ where
     Cluster: Api + 'static,
     Blockchain: Rpc + 'static,
 {
-    /// Build the S3 listener over a slice cache and meter shared with the native
+    /// Build the S3 listener over caches and a meter shared with the native
     /// read listener and an `Accounting` shared with the admin control plane (so
     /// their ledger mutations serialize against one lock).
     pub fn new(
         context: Arc<NodeContext<Db, Cluster, Blockchain>>,
         slice_cache: Arc<GatewaySliceCache<Db>>,
+        decoded_cache: Arc<DecodedChunkCache>,
+        fleet: Arc<GatewayFleet>,
         meter: Arc<GatewayMeter>,
         accounting: Arc<Accounting>,
         admission: Arc<dyn Admission>,
@@ -258,6 +272,8 @@ where
         Self {
             context,
             slice_cache,
+            decoded_cache,
+            fleet,
             meter,
             write_ctx,
             accounting,
@@ -271,6 +287,8 @@ where
         let state = AppState {
             context: self.context.clone(),
             slice_cache: self.slice_cache.clone(),
+            decoded_cache: self.decoded_cache.clone(),
+            fleet: self.fleet.clone(),
             meter: self.meter.clone(),
             write_ctx: self.write_ctx.clone(),
             accounting: self.accounting.clone(),
@@ -305,6 +323,20 @@ where
                 self.cancel.clone(),
             ))
         });
+        // Lifecycle expirations are carried out as delegate deletes, so the
+        // executor only runs where writes are possible.
+        let lifecycle_handle = self
+            .write_ctx
+            .clone()
+            .filter(|_| self.s3_config.lifecycle.enabled)
+            .map(|write_ctx| {
+                tokio::spawn(lifecycle_loop(
+                    self.context.clone(),
+                    write_ctx,
+                    self.s3_config.lifecycle.clone(),
+                    self.cancel.clone(),
+                ))
+            });
 
         let cancel = self.cancel.clone();
         let serve_result =
@@ -320,6 +352,11 @@ where
                 tracing::warn!(%error, "s3 reservation sweep task did not exit cleanly");
             }
         }
+        if let Some(handle) = lifecycle_handle {
+            if let Err(error) = handle.await {
+                tracing::warn!(%error, "s3 lifecycle task did not exit cleanly");
+            }
+        }
 
         serve_result
     }
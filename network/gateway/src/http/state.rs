@@ -6,7 +6,8 @@ use tape_node::context::NodeContext;
 use tape_protocol::Api;
 
 use crate::admission::Admission;
-use crate::cache::GatewaySliceCache;
+use crate::cache::{DecodedChunkCache, GatewaySliceCache};
+use crate::fleet::GatewayFleet;
 use crate::http::handlers::s3::accounting::Accounting;
 use crate::http::handlers::s3::write::S3WriteContext;
 use crate::meter::GatewayMeter;
@@ -14,6 +15,10 @@ use crate::meter::GatewayMeter;
 pub struct AppState<Db: Store, Cluster: Api, Blockchain: Rpc> {
     pub context: Arc<NodeContext<Db, Cluster, Blockchain>>,
     pub slice_cache: Arc<GatewaySliceCache<Db>>,
+    /// Decoded tracks kept in memory, shared by both read listeners.
+    pub decoded_cache: Arc<DecodedChunkCache>,
+    /// Peer gateways that own a share of slice cache misses.
+    pub fleet: Arc<GatewayFleet>,
     pub meter: Arc<GatewayMeter>,
     /// Delegate signing context for the S3 write path. `None` on the native read
     /// listener and whenever `gateway.s3.delegate_key` is unset (writes
@@ -32,6 +37,8 @@ impl<Db: Store, Cluster: Api, Blockchain: Rpc> Clone for AppState<Db, Cluster, B
         Self {
             context: self.context.clone(),
             slice_cache: self.slice_cache.clone(),
+            decoded_cache: self.decoded_cache.clone(),
+            fleet: self.fleet.clone(),
             meter: self.meter.clone(),
             write_ctx: self.write_ctx.clone(),
             accounting: self.accounting.clone(),
@@ -1,5 +1,6 @@
 pub mod admission;
 pub mod cache;
+pub mod fleet;
 pub mod http;
 mod meter;
 pub(crate) mod metrics;
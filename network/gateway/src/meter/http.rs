@@ -58,6 +58,11 @@ where
     }
 }
 
+/// The trusted-proxy-resolved caller IP of `req`
+pub fn caller_ip(req: &Request, trusted: &[IpAddr]) -> IpAddr {
+    resolve_caller_ip(peer_ip(req), req.headers(), trusted)
+}
+
 fn peer_ip(req: &Request) -> IpAddr {
     req.extensions()
         .get::<ConnectInfo<SocketAddr>>()
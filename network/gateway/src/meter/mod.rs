@@ -2,4 +2,4 @@ mod bucket;
 mod http;
 
 pub use bucket::{GatewayMeter, GatewayMeterDecision, MeterCaller};
-pub use http::{object_read_metering, rate_limited_response};
+pub use http::{caller_ip, object_read_metering, rate_limited_response};
@@ -41,3 +41,9 @@ pub fn inc_cache_evicted(count: u64) {
     #[cfg(feature = "metrics")]
     tape_metrics::metrics().cache_evicted_total.inc_by(count);
 }
+
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub fn inc_decoded_cache(result: &str) {
+    #[cfg(feature = "metrics")]
+    tape_metrics::metrics().decoded_cache_total.with_label_values(&[result]).inc();
+}
@@ -24,7 +24,8 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, Instrument};
 
 use crate::admission::{AdmitAll, Admission};
-use crate::cache::GatewaySliceCache;
+use crate::cache::{DecodedChunkCache, GatewaySliceCache};
+use crate::fleet::GatewayFleet;
 use crate::http::handlers::s3::accounting::Accounting;
 use crate::http::server::{GatewayHttpServer, GatewayS3AdminServer, GatewayS3Server};
 use crate::meter::GatewayMeter;
@@ -62,6 +63,8 @@ async fn supervise_with_context<Db, Cluster, Blockchain>(
     config: NodeConfig,
     admission: Arc<dyn Admission>,
     slice_cache: Arc<GatewaySliceCache<Db>>,
+    decoded_cache: Arc<DecodedChunkCache>,
+    fleet: Arc<GatewayFleet>,
     meter: Arc<GatewayMeter>,
     start_slot: SlotNumber,
     cancel: CancellationToken,
@@ -102,6 +105,8 @@ where
         let s3_server = GatewayS3Server::new(
             context.clone(),
             slice_cache.clone(),
+            decoded_cache.clone(),
+            fleet.clone(),
             meter.clone(),
             accounting.clone(),
             admission,
@@ -184,18 +189,26 @@ where
 {
     let cancel = CancellationToken::new();
 
-    // The slice cache and per-IP meter are built once and shared by the native
-    // read listener and the S3 listener, so the disk-cache budget and the rate
-    // limit are each enforced once across both rather than twice.
+    // The caches and per-IP meter are built once and shared by the native read
+    // listener and the S3 listener, so the cache budgets and the rate limit are
+    // each enforced once across both rather than twice.
     let slice_cache = Arc::new(
         GatewaySliceCache::new(context.store.clone(), context.config.gateway.cache.clone())
             .map_err(|error| NodeError::Store(error.to_string()))?,
     );
+    let decoded_cache =
+        Arc::new(DecodedChunkCache::new(context.config.gateway.cache.decoded_max_bytes));
+    let fleet = Arc::new(
+        GatewayFleet::new(&context.config.gateway.fleet)
+            .map_err(|error| NodeError::Config(error.to_string()))?,
+    );
     let meter = Arc::new(GatewayMeter::new(context.config.gateway.metering.clone()));
 
     let http_server = GatewayHttpServer::new(
         context.clone(),
         slice_cache.clone(),
+        decoded_cache.clone(),
+        fleet.clone(),
         meter.clone(),
         config.http.clone(),
         cancel.clone(),
@@ -212,6 +225,8 @@ where
         config,
         admission,
         slice_cache,
+        decoded_cache,
+        fleet,
         meter,
         start_slot,
         cancel,
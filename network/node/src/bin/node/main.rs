@@ -1,4 +1,5 @@
 mod keygen;
+mod restore;
 
 use std::process::ExitCode;
 
@@ -27,6 +28,9 @@ enum Command {
     /// Generate a fresh per-node key bundle (identity, BLS, TLS) and a
     /// starter node.yaml. Used by operators and by tape-network.
     Keygen(keygen::KeygenArgs),
+    /// Replace the stopped node's store with a checkpoint written on SIGUSR1,
+    /// after checking it belongs to this node.
+    Restore(restore::RestoreArgs),
     /// Print the boot marker (Cargo version + git sha).
     Version,
 }
@@ -42,6 +46,13 @@ fn main() -> ExitCode {
                 ExitCode::FAILURE
             }
         },
+        Some(Command::Restore(args)) => match restore::run(&cli.config, args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("restore failed: {error}");
+                ExitCode::FAILURE
+            }
+        },
         Some(Command::Version) => {
             println!("{VERSION}");
             ExitCode::SUCCESS
@@ -0,0 +1,53 @@
+use clap::Args;
+use tape_api::program::tapedrive::node_pda;
+use tape_node::config::node::{ConfigError, NodeConfig};
+use tape_node::core::error::NodeError;
+use tape_store::error::TapeStoreError;
+use tape_store::restore::restore_checkpoint;
+
+#[derive(Debug, Args)]
+pub struct RestoreArgs {
+    /// Checkpoint name under the store's checkpoint directory, as logged when
+    /// it was written (e.g. checkpoint-1760000000).
+    pub name: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RestoreError {
+    #[error("configuration failed: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error(transparent)]
+    Node(#[from] NodeError),
+
+    #[error("checkpoint {0} not found")]
+    NotFound(String),
+
+    #[error(transparent)]
+    Store(#[from] TapeStoreError),
+}
+
+/// Replace the configured store with a named checkpoint. The node must be
+/// stopped; the replaced store is kept beside it with a `.pre-restore` suffix.
+pub fn run(config_path: &str, args: RestoreArgs) -> Result<(), RestoreError> {
+    let config = NodeConfig::from_yaml_file(config_path)?;
+    let keypair = config.load_node_keypair()?;
+    let (node_address, _) = node_pda(keypair.address());
+
+    let checkpoint = config.store.checkpoint_dir(&args.name);
+    if !checkpoint.exists() {
+        return Err(RestoreError::NotFound(checkpoint.display().to_string()));
+    }
+
+    let identity = restore_checkpoint(
+        &checkpoint,
+        &config.store.meta_dir(),
+        &config.store.bulk_dir(),
+        node_address,
+    )?;
+    println!("restored {} for node {node_address}", args.name);
+    if let Some(cluster_hash) = identity.cluster_hash {
+        println!("cluster {cluster_hash}");
+    }
+    Ok(())
+}
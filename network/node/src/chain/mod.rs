@@ -11,10 +11,13 @@ pub mod propose_assignment;
 pub mod propose_eviction;
 pub mod propose_snapshot;
 pub mod register_node;
+pub mod renew_tape;
 pub mod resize_committee;
 pub mod resize_peer_set;
 pub mod set_network_tls;
+pub mod slash_pool;
 pub mod sync_spool;
+pub mod upgrade_node;
 pub mod vote_assignment;
 pub mod vote_eviction;
 pub mod vote_snapshot;
@@ -31,10 +34,13 @@ pub use join_committee::submit_join_committee;
 pub use propose_assignment::submit_propose_assignment;
 pub use propose_eviction::submit_propose_eviction;
 pub use propose_snapshot::submit_propose_snapshot;
+pub use renew_tape::submit_renew_tape;
 pub use resize_committee::submit_resize_committee;
 pub use resize_peer_set::submit_resize_peer_set;
 pub use set_network_tls::submit_set_network_tls;
+pub use slash_pool::submit_slash_pool;
 pub use sync_spool::submit_sync_spool;
+pub use upgrade_node::submit_upgrade_node;
 pub use vote_assignment::submit_vote_assignment;
 pub use vote_eviction::submit_vote_eviction;
 pub use vote_snapshot::submit_vote_snapshot;
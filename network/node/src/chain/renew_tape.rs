@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use rpc::{Rpc, RpcError};
+use store::Store;
+use tape_api::compute::RENEW_TAPE_CU;
+use tape_api::instruction::build_renew_tape_ix;
+use tape_crypto::{Address, tx::Txid};
+use tape_protocol::Api;
+
+use crate::context::NodeContext;
+
+pub async fn submit_renew_tape<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    ctx: &Arc<NodeContext<Db, Cluster, Blockchain>>,
+    tape: Address,
+) -> Result<Txid, RpcError> {
+    let fee_payer = ctx.pubkey().into();
+    let ix = build_renew_tape_ix(fee_payer, tape);
+
+    ctx.rpc
+        .send_instructions_with_compute_unit_limit(ctx.signer(), RENEW_TAPE_CU, vec![ix])
+        .await
+}
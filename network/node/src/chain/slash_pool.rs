@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use rpc::{Rpc, RpcError};
+use store::Store;
+use tape_api::compute::SLASH_POOL_CU;
+use tape_api::instruction::build_slash_pool_ix;
+use tape_core::bls::BlsSignature;
+use tape_core::spooler::GroupIndex;
+use tape_core::types::{EpochNumber, SpoolBitmap};
+use tape_crypto::{Address, tx::Txid};
+use tape_protocol::Api;
+
+use crate::context::NodeContext;
+
+pub async fn submit_slash_pool<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    ctx: &Arc<NodeContext<Db, Cluster, Blockchain>>,
+    epoch: EpochNumber,
+    node: Address,
+    group: GroupIndex,
+    bitmap: SpoolBitmap,
+    signature: BlsSignature,
+) -> Result<Txid, RpcError> {
+    let fee_payer = ctx.pubkey().into();
+    let ix = build_slash_pool_ix(fee_payer, epoch, node, group, bitmap, signature);
+
+    ctx.rpc
+        .send_instructions_with_compute_unit_limit(ctx.signer(), SLASH_POOL_CU, vec![ix])
+        .await
+}
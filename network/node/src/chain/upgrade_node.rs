@@ -0,0 +1,56 @@
+use rpc::{Rpc, RpcError};
+use rpc_client::RpcClient;
+use tape_api::instruction::build_upgrade_node_ix;
+use tape_crypto::address::Address;
+use tape_crypto::ed25519::Keypair;
+use tape_crypto::tx::Txid;
+
+/// Submit `UpgradeNode` to grow a Node account registered before slashing
+/// to the current layout. The authority pays the extra rent.
+pub async fn submit_upgrade_node<Blockchain: Rpc>(
+    rpc: &RpcClient<Blockchain>,
+    authority: &Keypair,
+    node_address: Address,
+) -> Result<Txid, RpcError> {
+    let ix = build_upgrade_node_ix(authority.address(), node_address);
+    rpc.send_instructions(authority, vec![ix]).await
+}
+
+#[cfg(test)]
+mod tests {
+    use rpc::Rpc;
+    use tape_api::program::tapedrive;
+    use tape_api::state::Node;
+    use tape_core::types::EpochNumber;
+
+    use super::submit_upgrade_node;
+    use crate::harness::NodeHarness;
+
+    #[tokio::test]
+    async fn success() {
+        let harness = NodeHarness::builder()
+            .nodes(20)
+            .epoch(EpochNumber(3))
+            .build()
+            .await
+            .expect("build harness");
+        let ctx = harness.ctx_for(7);
+        let node_address = ctx.node_address();
+
+        let before = ctx.rpc.get_node_by_address(&node_address).await.expect("fetch node");
+        let account = ctx.rpc.rpc().get_account(&node_address).await.expect("fetch node");
+        harness
+            .rpc()
+            .set_account_data(node_address, tapedrive::ID, &account.data[..Node::PRE_SLASH_SIZE])
+            .expect("truncate node");
+
+        submit_upgrade_node(ctx.rpc.as_ref(), ctx.signer(), node_address)
+            .await
+            .expect("submit upgrade node");
+
+        let account = ctx.rpc.rpc().get_account(&node_address).await.expect("fetch node");
+        assert_eq!(account.data.len(), Node::get_size());
+        let after = ctx.rpc.get_node_by_address(&node_address).await.expect("fetch node");
+        assert_eq!(after, before);
+    }
+}
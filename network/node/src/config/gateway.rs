@@ -20,6 +20,10 @@ pub struct GatewayConfig {
     /// S3-compatible gateway listener. Disabled by default.
     #[serde(default)]
     pub s3: S3Config,
+
+    /// Peer gateways sharing slice cache misses. Empty by default.
+    #[serde(default)]
+    pub fleet: GatewayFleetConfig,
 }
 
 impl Default for GatewayConfig {
@@ -28,10 +32,53 @@ impl Default for GatewayConfig {
             cache: GatewayCacheConfig::default(),
             metering: GatewayMeteringConfig::default(),
             s3: S3Config::default(),
+            fleet: GatewayFleetConfig::default(),
+        }
+    }
+}
+
+/// Cooperative slice caching across a fleet of gateways. Each slice has one
+/// owning gateway, picked by rendezvous hashing over `peers`; a miss for a
+/// slice owned elsewhere asks that gateway before the storage nodes.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct GatewayFleetConfig {
+    /// Base URL peers reach this gateway at. Must appear in `peers`.
+    #[serde(default)]
+    pub self_url: Option<String>,
+
+    /// Base URLs of every gateway in the fleet, this one included. Every
+    /// member must list the same set, or they disagree on slice owners.
+    #[serde(default)]
+    pub peers: Vec<String>,
+
+    /// Give up on a peer's reply after this many milliseconds.
+    #[serde(default = "default_fleet_peer_timeout_ms")]
+    pub peer_timeout_ms: u64,
+
+    /// Seconds a peer is skipped after a failed request.
+    #[serde(default = "default_fleet_peer_down_secs")]
+    pub peer_down_secs: u64,
+}
+
+impl Default for GatewayFleetConfig {
+    fn default() -> Self {
+        Self {
+            self_url: None,
+            peers: Vec::new(),
+            peer_timeout_ms: default_fleet_peer_timeout_ms(),
+            peer_down_secs: default_fleet_peer_down_secs(),
         }
     }
 }
 
+fn default_fleet_peer_timeout_ms() -> u64 {
+    2_000
+}
+
+fn default_fleet_peer_down_secs() -> u64 {
+    10
+}
+
 /// S3-compatible gateway listener controls.
 #[derive(Clone, Deserialize, Eq, PartialEq)]
 pub struct S3Config {
@@ -77,6 +124,10 @@ pub struct S3Config {
     /// path-style resource (`/{bucket}/{key}`) is returned.
     #[serde(default)]
     pub public_endpoint: Option<String>,
+
+    /// Background executor for bucket lifecycle rules.
+    #[serde(default)]
+    pub lifecycle: S3LifecycleConfig,
 }
 
 impl Default for S3Config {
@@ -91,6 +142,7 @@ impl Default for S3Config {
             max_object_bytes: default_s3_max_object_bytes(),
             max_buffered_bytes: default_s3_max_buffered_bytes(),
             public_endpoint: None,
+            lifecycle: S3LifecycleConfig::default(),
         }
     }
 }
@@ -109,6 +161,7 @@ impl std::fmt::Debug for S3Config {
             .field("max_object_bytes", &self.max_object_bytes)
             .field("max_buffered_bytes", &self.max_buffered_bytes)
             .field("public_endpoint", &self.public_endpoint)
+            .field("lifecycle", &self.lifecycle)
             .finish()
     }
 }
@@ -127,6 +180,56 @@ fn default_s3_max_buffered_bytes() -> usize {
     256 * 1024 * 1024
 }
 
+/// Bucket lifecycle executor controls.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct S3LifecycleConfig {
+    /// Run the lifecycle executor. It also needs a delegate key, since
+    /// expirations are ordinary track deletes. Off by default: enable it on
+    /// one gateway per fleet, or each instance places its own delete markers.
+    #[serde(default = "default_lifecycle_enabled")]
+    pub enabled: bool,
+
+    /// Seconds between lifecycle passes.
+    #[serde(default = "default_lifecycle_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Most expirations one pass carries out; the rest wait for the next pass.
+    #[serde(default = "default_lifecycle_max_actions")]
+    pub max_actions_per_pass: usize,
+
+    /// Seconds a failed expiration is skipped before it is retried, so it
+    /// does not take the pass budget from the expirations behind it.
+    #[serde(default = "default_lifecycle_failure_backoff_secs")]
+    pub failure_backoff_secs: u64,
+}
+
+impl Default for S3LifecycleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_lifecycle_enabled(),
+            interval_secs: default_lifecycle_interval_secs(),
+            max_actions_per_pass: default_lifecycle_max_actions(),
+            failure_backoff_secs: default_lifecycle_failure_backoff_secs(),
+        }
+    }
+}
+
+fn default_lifecycle_enabled() -> bool {
+    false
+}
+
+fn default_lifecycle_interval_secs() -> u64 {
+    60 * 60
+}
+
+fn default_lifecycle_max_actions() -> usize {
+    1_000
+}
+
+fn default_lifecycle_failure_backoff_secs() -> u64 {
+    6 * 60 * 60
+}
+
 /// S3 write-authorization defaults and control-plane wiring.
 #[derive(Clone, Deserialize, Eq, PartialEq)]
 pub struct S3WriteConfig {
@@ -286,6 +389,19 @@ pub struct GatewayCacheConfig {
     /// A value of 0 disables explicit reclaim triggers.
     #[serde(default = "default_reclaim_after_deleted_slices")]
     pub reclaim_after_deleted_slices: usize,
+
+    /// Maximum decoded chunk bytes the gateway keeps in memory, so popular
+    /// objects skip erasure decoding on repeat reads.
+    ///
+    /// A value of 0 disables the decoded cache.
+    #[serde(default = "default_decoded_max_bytes")]
+    pub decoded_max_bytes: u64,
+
+    /// Manifest chunks decoded ahead of a sequential stream reader.
+    ///
+    /// A value of 0 disables read-ahead.
+    #[serde(default = "default_prefetch_chunks")]
+    pub prefetch_chunks: usize,
 }
 
 impl Default for GatewayCacheConfig {
@@ -294,6 +410,8 @@ impl Default for GatewayCacheConfig {
             max_bytes: default_max_bytes(),
             eviction_batch: default_eviction_batch(),
             reclaim_after_deleted_slices: default_reclaim_after_deleted_slices(),
+            decoded_max_bytes: default_decoded_max_bytes(),
+            prefetch_chunks: default_prefetch_chunks(),
         }
     }
 }
@@ -310,6 +428,14 @@ fn default_reclaim_after_deleted_slices() -> usize {
     1024
 }
 
+fn default_decoded_max_bytes() -> u64 {
+    512 * 1024 * 1024
+}
+
+fn default_prefetch_chunks() -> usize {
+    2
+}
+
 /// One named bundle of read-metering rates.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
 pub struct MeteringGrade {
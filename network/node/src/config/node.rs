@@ -173,6 +173,21 @@ impl NodeConfig {
             ));
         }
 
+        let fleet = &self.gateway.fleet;
+        if !fleet.peers.is_empty() {
+            let listed = fleet.self_url.as_deref().is_some_and(|url| {
+                fleet
+                    .peers
+                    .iter()
+                    .any(|peer| peer.trim_end_matches('/') == url.trim_end_matches('/'))
+            });
+            if !listed {
+                return Err(ConfigError::Invalid(
+                    "gateway.fleet.self_url must be set and listed in gateway.fleet.peers".into(),
+                ));
+            }
+        }
+
         for (name, grade) in &self.gateway.metering.grades {
             if grade.read_per_sec == 0 {
                 return Err(ConfigError::Invalid(format!(
@@ -444,6 +459,8 @@ gateway:
     max_bytes: 1073741824
     eviction_batch: 16
     reclaim_after_deleted_slices: 64
+    decoded_max_bytes: 8388608
+    prefetch_chunks: 4
   metering:
     grades:
       anonymous:
@@ -467,6 +484,10 @@ recovery:
   scan_batch: 77
   repair_batch: 8
   recover_batch: 6
+  scrub_batch: 12
+  scrub_interval_secs: 300
+  scrub_bytes_per_sec: 1048576
+  scrub_quarantine_limit: 5
 logging:
   filter: "debug"
   format: "json"
@@ -514,6 +535,8 @@ metrics:
         assert_eq!(config.gateway.cache.max_bytes, 1024 * 1024 * 1024);
         assert_eq!(config.gateway.cache.eviction_batch, 16);
         assert_eq!(config.gateway.cache.reclaim_after_deleted_slices, 64);
+        assert_eq!(config.gateway.cache.decoded_max_bytes, 8 * 1024 * 1024);
+        assert_eq!(config.gateway.cache.prefetch_chunks, 4);
         let anonymous = &config.gateway.metering.grades["anonymous"];
         assert_eq!(anonymous.read_per_sec, 12);
         assert_eq!(anonymous.read_burst, 24);
@@ -537,6 +560,10 @@ metrics:
         assert_eq!(config.recovery.scan_batch, 77);
         assert_eq!(config.recovery.repair_batch, 8);
         assert_eq!(config.recovery.recover_batch, 6);
+        assert_eq!(config.recovery.scrub_batch, 12);
+        assert_eq!(config.recovery.scrub_interval_secs, 300);
+        assert_eq!(config.recovery.scrub_bytes_per_sec, 1048576);
+        assert_eq!(config.recovery.scrub_quarantine_limit, 5);
         assert_eq!(config.logging.filter, "debug");
         assert_eq!(config.logging.format, LoggingFormat::Json);
         assert!(!config.metrics.enabled);
@@ -619,6 +646,21 @@ node:
         assert!(config.metrics.enabled);
     }
 
+    // a member missing from its own peer list would route its slices elsewhere
+    #[test]
+    fn rejects_fleet_without_self_in_peers() {
+        let result = NodeConfig::from_yaml_str(
+            r#"
+gateway:
+  fleet:
+    self_url: "http://10.0.0.3:3430"
+    peers: ["http://10.0.0.1:3430", "http://10.0.0.2:3430"]
+"#,
+        );
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn rejects_invalid_commission() {
         let result = NodeConfig::from_yaml_str(
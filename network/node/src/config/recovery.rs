@@ -22,6 +22,22 @@ pub struct RecoveryConfig {
     /// Batch size for pending recovery work.
     #[serde(default = "default_recover_batch")]
     pub recover_batch: usize,
+
+    /// Slices re-verified per scrub run; zero disables scrubbing.
+    #[serde(default = "default_scrub_batch")]
+    pub scrub_batch: usize,
+
+    /// Minimum seconds between scrub runs on the same spool.
+    #[serde(default = "default_scrub_interval_secs")]
+    pub scrub_interval_secs: u64,
+
+    /// Read budget for a single scrub worker, in bytes per second.
+    #[serde(default = "default_scrub_bytes_per_sec")]
+    pub scrub_bytes_per_sec: u64,
+
+    /// Most slices a spool may hold in quarantine; scrub pauses at the cap.
+    #[serde(default = "default_scrub_quarantine_limit")]
+    pub scrub_quarantine_limit: usize,
 }
 
 impl Default for RecoveryConfig {
@@ -32,6 +48,10 @@ impl Default for RecoveryConfig {
             scan_batch: default_scan_batch(),
             repair_batch: default_repair_batch(),
             recover_batch: default_recover_batch(),
+            scrub_batch: default_scrub_batch(),
+            scrub_interval_secs: default_scrub_interval_secs(),
+            scrub_bytes_per_sec: default_scrub_bytes_per_sec(),
+            scrub_quarantine_limit: default_scrub_quarantine_limit(),
         }
     }
 }
@@ -55,3 +75,19 @@ fn default_repair_batch() -> usize {
 fn default_recover_batch() -> usize {
     10
 }
+
+fn default_scrub_batch() -> usize {
+    64
+}
+
+fn default_scrub_interval_secs() -> u64 {
+    60
+}
+
+fn default_scrub_bytes_per_sec() -> u64 {
+    32 * 1024 * 1024
+}
+
+fn default_scrub_quarantine_limit() -> usize {
+    32
+}
@@ -20,6 +20,12 @@ pub struct SolanaConfig {
     #[serde(default)]
     pub rpc_strategy: EndpointStrategy,
 
+    /// Optional websocket endpoint pushing confirmed blocks via
+    /// `blockSubscribe`. Near the tip the ingestor reads from it instead
+    /// of polling `getBlock`, and polls again whenever it drops or skips.
+    #[serde(default)]
+    pub block_feed: Option<String>,
+
     /// Optional override for the first slot the ingestor should process.
     /// When absent the bootstrap phase derives the start slot from
     /// on-chain state (replay tail → local sync cursor → current
@@ -46,6 +52,7 @@ impl Default for SolanaConfig {
         Self {
             rpc: default_rpc(),
             rpc_strategy: EndpointStrategy::default(),
+            block_feed: None,
             start_slot: None,
         }
     }
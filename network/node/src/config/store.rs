@@ -4,6 +4,9 @@ use serde::Deserialize;
 
 use super::helpers::{deserialize_option_pathbuf, deserialize_pathbuf};
 
+/// Subdirectory of the bulk root that holds named store checkpoints
+pub const CHECKPOINTS_SUBDIR: &str = "checkpoints";
+
 /// Local RocksDB store settings.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 pub struct StoreConfig {
@@ -49,6 +52,16 @@ impl StoreConfig {
             .unwrap_or(&self.path)
             .join(tape_store::config::BULK_SUBDIR)
     }
+
+    /// Directory of the named checkpoint, beside the bulk store so its bulk
+    /// files hard-link instead of copying
+    pub fn checkpoint_dir(&self, name: &str) -> PathBuf {
+        self.bulk_path
+            .as_ref()
+            .unwrap_or(&self.path)
+            .join(CHECKPOINTS_SUBDIR)
+            .join(name)
+    }
 }
 
 impl Default for StoreConfig {
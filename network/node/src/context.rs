@@ -17,6 +17,7 @@ use tape_core::prelude::{EpochPhase, NodeId, NodeStatus, SpoolIndex};
 use tape_core::types::tls::NetworkTlsPubkey;
 use tape_crypto::prelude::{Address, BLSError, Keypair, Signature};
 use tape_crypto::ed25519::Pubkey;
+use tape_protocol::api::batch::SliceBatcher;
 use tape_protocol::{Api, ProtocolState};
 use tape_store::{TapeStore, ops::MetaOps};
 
@@ -27,6 +28,7 @@ use crate::core::ingest::{IngestBus, IngestState};
 use crate::core::metrics::NodeMetrics;
 use crate::core::state::StateBus;
 use crate::features::block::pending_tracks::PendingTracks;
+use crate::features::challenge::ChallengeLedger;
 use crate::features::eviction::EvictionQueue;
 use crate::features::http::admission::AdmissionLimiter;
 
@@ -42,8 +44,12 @@ pub struct NodeContext<Db: Store, Cluster: Api, Blockchain: Rpc> {
     pub pending: Arc<PendingTracks>,
     pub peer_manager: Arc<PeerManager>,
     pub api: Arc<Cluster>,
+    /// Coalesces slice calls to peers into batch requests, for clients that
+    /// move many small slices over this context.
+    pub slices: Arc<SliceBatcher<Cluster>>,
     pub admission: Arc<AdmissionLimiter>,
     pub eviction_queue: Arc<EvictionQueue>,
+    pub challenge_ledger: Arc<ChallengeLedger>,
     pub metrics: NodeMetrics,
 
     node_id: NodeId,
@@ -246,6 +252,20 @@ impl<Db: Store, Cluster: Api, Blockchain: Rpc> NodeContextBuilder<Db, Cluster, B
             .set_node_id(node_id)
             .map_err(|error| NodeError::Store(format!("set_node_id: {error}")))?;
 
+        // A store restored from another node's checkpoint must not start
+        // serving under this node's identity.
+        let stored_address = self
+            .store
+            .get_node_address()
+            .map_err(|error| NodeError::Store(format!("get_node_address: {error}")))?;
+        if let Some(stored_address) = stored_address {
+            if stored_address != node_address {
+                return Err(NodeError::Store(format!(
+                    "store belongs to node {stored_address}, not {node_address}"
+                )));
+            }
+        }
+
         self.store
             .set_node_address(node_address.into())
             .map_err(|error| NodeError::Store(format!("set_node_address: {error}")))?;
@@ -264,9 +284,11 @@ impl<Db: Store, Cluster: Api, Blockchain: Rpc> NodeContextBuilder<Db, Cluster, B
             bootstrap: BootstrapBus::default(),
             pending: Arc::new(PendingTracks::new()),
             peer_manager: self.peer_manager,
+            slices: Arc::new(SliceBatcher::new(self.api.clone())),
             api: self.api,
             admission,
             eviction_queue: Arc::new(EvictionQueue::default()),
+            challenge_ledger: Arc::new(ChallengeLedger::default()),
             metrics: NodeMetrics::default(),
             reclaim_pending: AtomicBool::new(false),
         }))
@@ -15,6 +15,10 @@ pub struct NodeMetricsSnapshot {
     pub repair_escalations: u64,
     pub recover_bytes_fetched: u64,
     pub recover_bytes_persisted: u64,
+    pub scrub_slices_verified: u64,
+    pub scrub_slices_corrupt: u64,
+    pub scrub_bytes: u64,
+    pub scrub_passes: u64,
 }
 
 #[derive(Debug, Default)]
@@ -47,6 +51,10 @@ impl NodeMetrics {
                 repair_escalations: m.repair_escalations_total.get(),
                 recover_bytes_fetched: spool("recover", "fetched"),
                 recover_bytes_persisted: spool("recover", "persisted"),
+                scrub_slices_verified: m.scrub_slices_total.with_label_values(&["verified"]).get(),
+                scrub_slices_corrupt: m.scrub_slices_total.with_label_values(&["corrupt"]).get(),
+                scrub_bytes: m.scrub_bytes_total.get(),
+                scrub_passes: m.scrub_passes_total.get(),
             }
         }
         #[cfg(not(feature = "metrics"))]
@@ -115,6 +123,31 @@ impl NodeMetrics {
         tape_metrics::metrics().repair_escalations_total.inc();
     }
 
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub fn add_scrub_verified(&self, bytes: u64) {
+        #[cfg(feature = "metrics")]
+        {
+            let m = tape_metrics::metrics();
+            m.scrub_slices_total.with_label_values(&["verified"]).inc();
+            m.scrub_bytes_total.inc_by(bytes);
+        }
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub fn add_scrub_corrupt(&self, bytes: u64) {
+        #[cfg(feature = "metrics")]
+        {
+            let m = tape_metrics::metrics();
+            m.scrub_slices_total.with_label_values(&["corrupt"]).inc();
+            m.scrub_bytes_total.inc_by(bytes);
+        }
+    }
+
+    pub fn inc_scrub_passes(&self) {
+        #[cfg(feature = "metrics")]
+        tape_metrics::metrics().scrub_passes_total.inc();
+    }
+
     #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
     fn add_spool(&self, op: &str, stage: &str, n: u64) {
         #[cfg(feature = "metrics")]
@@ -150,6 +183,9 @@ mod tests {
         m.inc_repair_escalations();
         m.add_recover_fetched(23);
         m.add_recover_persisted(29);
+        m.add_scrub_verified(31);
+        m.add_scrub_corrupt(37);
+        m.inc_scrub_passes();
 
         let after = m.snapshot();
         assert_eq!(after.requests_total - before.requests_total, 1);
@@ -165,5 +201,9 @@ mod tests {
         assert_eq!(after.repair_escalations - before.repair_escalations, 1);
         assert_eq!(after.recover_bytes_fetched - before.recover_bytes_fetched, 23);
         assert_eq!(after.recover_bytes_persisted - before.recover_bytes_persisted, 29);
+        assert_eq!(after.scrub_slices_verified - before.scrub_slices_verified, 1);
+        assert_eq!(after.scrub_slices_corrupt - before.scrub_slices_corrupt, 1);
+        assert_eq!(after.scrub_bytes - before.scrub_bytes, 68);
+        assert_eq!(after.scrub_passes - before.scrub_passes, 1);
     }
 }
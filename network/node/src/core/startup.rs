@@ -22,6 +22,7 @@ use tracing::{info, warn};
 
 use crate::chain::register_node::submit_register_node;
 use crate::chain::set_network_tls::submit_set_network_tls;
+use crate::chain::upgrade_node::submit_upgrade_node;
 use crate::config::node::NodeConfig;
 use crate::context::{AppContext, NodeContextBuilder};
 use crate::core::error::NodeError;
@@ -30,15 +31,25 @@ pub fn open_primary_store(config: &NodeConfig) -> Result<TapeStore<SplitStore>,
     let meta_dir = config.store.meta_dir();
     let bulk_dir = config.store.bulk_dir();
 
-    // The first open after an upgrade rebuilds the slice size index, scanning
-    // every stored slice, so the gap between these two lines can run minutes.
+    // The first open after an upgrade runs any pending schema migrations, which
+    // can scan every stored slice, so the gap between these two lines can run
+    // minutes; each migration step logs its progress.
     info!(meta = %meta_dir.display(), bulk = %bulk_dir.display(), "opening store");
     let opened_at = Instant::now();
 
-    let store = TapeStore::open_primary_split(
+    let store = TapeStore::open_primary_split_with_progress(
         &meta_dir,
         &bulk_dir,
         config.store.compaction_mb_per_sec,
+        &mut |progress| {
+            info!(
+                version = progress.version,
+                migration = progress.name,
+                processed = progress.processed,
+                done = progress.done,
+                "store migration progress"
+            )
+        },
     )
     .map_err(|error| {
         NodeError::Store(format!(
@@ -84,6 +95,7 @@ fn build_rpc_client(config: &NodeConfig) -> Result<RpcClient<SolanaRpc>, NodeErr
     let rpc_config = RpcConfig {
         endpoints: config.solana.rpc.clone(),
         strategy: config.solana.rpc_strategy,
+        block_feed: config.solana.block_feed.clone(),
         ..RpcConfig::default()
     };
 
@@ -225,6 +237,31 @@ async fn reconcile_network_tls<Blockchain: Rpc>(
     Ok(())
 }
 
+/// Grow the node account to the current layout if it was registered before
+/// slashing. Instructions that load the node need the full account.
+async fn upgrade_legacy_node<Blockchain: Rpc>(
+    rpc: &RpcClient<Blockchain>,
+    authority: &Keypair,
+) -> Result<(), NodeError> {
+    let (node_address, _) = node_pda(authority.address());
+    let account = rpc
+        .rpc()
+        .get_account(&node_address)
+        .await
+        .map_err(NodeError::Rpc)?;
+    if account.data.len() != Node::PRE_SLASH_SIZE {
+        return Ok(());
+    }
+
+    info!(node = %node_address, "upgrading node account to the current layout");
+
+    submit_upgrade_node(rpc, authority, node_address)
+        .await
+        .map_err(NodeError::Rpc)?;
+
+    Ok(())
+}
+
 fn validate_node_metadata(
     node: &Node,
     config: &NodeConfig,
@@ -274,6 +311,7 @@ pub async fn ensure_registered<Blockchain: Rpc>(
                 node.metadata.network_tls,
             )
             .await?;
+            upgrade_legacy_node(rpc, keypair).await?;
             return Ok(());
         }
         Err(RpcError::AccountNotFound(_)) => {}
@@ -351,8 +389,11 @@ mod tests {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
     use std::path::PathBuf;
 
+    use rpc::Rpc;
     use rpc_client::RpcClient;
     use tape_api::genesis::GenesisConfig;
+    use tape_api::program::tapedrive::{self, node_pda};
+    use tape_api::state::Node;
     use tape_api::utils::to_name;
     use tape_core::bls::BlsPrivateKey;
     use tape_core::system::NodePreferences;
@@ -542,6 +583,43 @@ mod tests {
             .expect("ensure_registered idempotent");
     }
 
+    // a node registered before slashing is grown on startup
+    #[tokio::test]
+    async fn upgrades_legacy_node() {
+        let harness = NodeHarness::builder()
+            .nodes(20)
+            .epoch(EpochNumber(3))
+            .build()
+            .await
+            .expect("build harness");
+
+        let mut rng = rand::thread_rng();
+        let keypair = Keypair::new(&mut rng);
+        let bls = BlsPrivateKey::from_random();
+        let tls = Keypair::new(&mut rng);
+        let address = NetworkAddress::from_socket_addr(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 443),
+        );
+
+        register_fresh_node(&harness, &keypair, &bls, address, tls_pubkey(&tls)).await;
+
+        let (node_address, _) = node_pda(keypair.address());
+        let account = harness.rpc().get_account(&node_address).await.expect("fetch node");
+        harness
+            .rpc()
+            .set_account_data(node_address, tapedrive::ID, &account.data[..Node::PRE_SLASH_SIZE])
+            .expect("truncate node");
+
+        let config = test_config_with_address([10, 0, 0, 1], 443);
+        let rpc = RpcClient::from_rpc(harness.rpc().clone());
+        ensure_registered(&config, &rpc, &keypair, &bls, &tls)
+            .await
+            .expect("ensure_registered");
+
+        let account = harness.rpc().get_account(&node_address).await.expect("fetch node");
+        assert_eq!(account.data.len(), Node::get_size());
+    }
+
     #[tokio::test]
     async fn auto_updates_network_tls_on_mismatch() {
         let harness = NodeHarness::builder()
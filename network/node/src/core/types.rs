@@ -8,6 +8,8 @@ pub enum ServiceName {
     IngestMonitor,
     AssignmentManager,
     EvictionManager,
+    ChallengeManager,
+    RenewalManager,
     LifecycleManager,
     SpoolManager,
     SnapshotManager,
@@ -15,6 +17,7 @@ pub enum ServiceName {
     StoreManager,
     StateManager,
     GcManager,
+    CheckpointManager,
     PeerAggregator,
 }
 
@@ -29,6 +32,8 @@ impl ServiceName {
             Self::IngestMonitor => "IngestMonitor",
             Self::AssignmentManager => "AssignmentManager",
             Self::EvictionManager => "EvictionManager",
+            Self::ChallengeManager => "ChallengeManager",
+            Self::RenewalManager => "RenewalManager",
             Self::LifecycleManager => "LifecycleManager",
             Self::SpoolManager => "SpoolManager",
             Self::SnapshotManager => "SnapshotManager",
@@ -36,6 +41,7 @@ impl ServiceName {
             Self::StoreManager => "StoreManager",
             Self::StateManager => "StateManager",
             Self::GcManager => "GcManager",
+            Self::CheckpointManager => "CheckpointManager",
             Self::PeerAggregator => "PeerAggregator",
         }
     }
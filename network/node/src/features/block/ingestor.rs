@@ -1,7 +1,6 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use futures::StreamExt;
-use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
@@ -17,12 +16,9 @@ use crate::core::channels::{DownstreamSenders, send_block};
 use crate::context::NodeContext;
 use crate::core::error::NodeError;
 use crate::core::types::ChannelName;
-use crate::features::block::fetch::{
-    FETCH_PIPELINE_DEPTH, fetch_and_parse_block, fetch_blocks_ordered,
-};
+use crate::features::block::fetch::{FETCH_PIPELINE_DEPTH, fetch_blocks_ordered};
 use crate::features::block::pending_blocks::{AppendOutcome, PendingBlocks};
-
-const TIP_POLL_MS: u64 = 400;
+use crate::features::block::source::{BlockSource, FeedSource};
 
 /// Minimum interval between INFO-level dispatch summaries. Per-block
 /// dispatch logging is debug-level; at catch-up rates it would flood.
@@ -39,8 +35,15 @@ pub struct ParsedBlock {
     pub instruction_tx_ids: Vec<Txid>,
 }
 
-pub struct BlockIngestor<Db: Store, Cluster: Api, Blockchain: Rpc> {
+pub struct BlockIngestor<
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+    Source = FeedSource<Db, Cluster, Blockchain>,
+> {
     context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    /// Supplies blocks once the ingestor is near the confirmed tip.
+    source: Source,
     start_slot: SlotNumber,
     senders: DownstreamSenders,
     cancel: CancellationToken,
@@ -55,14 +58,32 @@ pub struct BlockIngestor<Db: Store, Cluster: Api, Blockchain: Rpc> {
 impl<Db: Store, Cluster: Api, Blockchain: Rpc>
     BlockIngestor<Db, Cluster, Blockchain> {
 
+    /// Ingest near-tip blocks from the backend's block feed, polling when
+    /// it has none.
     pub fn new(
         context: Arc<NodeContext<Db, Cluster, Blockchain>>,
         start_slot: SlotNumber,
         senders: DownstreamSenders,
         cancel: CancellationToken,
+    ) -> Self {
+        let source = FeedSource::new(context.clone(), cancel.clone());
+        Self::with_source(context, start_slot, senders, cancel, source)
+    }
+}
+
+impl<Db: Store, Cluster: Api, Blockchain: Rpc, Source: BlockSource>
+    BlockIngestor<Db, Cluster, Blockchain, Source> {
+
+    pub fn with_source(
+        context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+        start_slot: SlotNumber,
+        senders: DownstreamSenders,
+        cancel: CancellationToken,
+        source: Source,
     ) -> Self {
         Self {
             context,
+            source,
             start_slot,
             senders,
             cancel,
@@ -143,8 +164,8 @@ impl<Db: Store, Cluster: Api, Blockchain: Rpc>
         Ok(end_slot.next())
     }
 
-    /// Serial near-tip path: fetch one block at or below the confirmed tip,
-    /// promote, and return the next slot to fetch.
+    /// Near-tip path: take whatever blocks the source has ready from `slot`
+    /// on, promote, and return the next slot to fetch.
     async fn fetch_parse_and_dispatch(
         &mut self,
         slot: SlotNumber,
@@ -154,21 +175,14 @@ impl<Db: Store, Cluster: Api, Blockchain: Rpc>
 
         // Ingest readiness is measured against finalized_tip, because
         // promoted/durable consumers intentionally lag confirmed.
-        let next = if slot.0 > tip {
-            sleep(Duration::from_millis(TIP_POLL_MS)).await;
-            slot
-        } else {
-            let fetched =
-                fetch_and_parse_block(self.context.clone(), self.cancel.clone(), slot).await?;
-            if let Some(block) = fetched {
-                self.enqueue(block);
-            }
-            slot.next()
-        };
+        let sourced = self.source.next_blocks(slot, tip).await?;
+        for block in sourced.blocks {
+            self.enqueue(block);
+        }
 
         self.promote().await?;
 
-        Ok(next)
+        Ok(sourced.next)
     }
 
     async fn refresh_finalized_tip(&mut self) -> Result<(), NodeError> {
@@ -372,6 +386,7 @@ mod tests {
     use super::BlockIngestor;
     use crate::chain::{submit_join_committee, submit_set_network_tls};
     use crate::core::channels::{downstream_channels, store_channel};
+    use crate::features::block::source::PollSource;
     use crate::features::replay::manager::ReplayManager;
     use crate::harness::NodeHarness;
 
@@ -437,11 +452,12 @@ mod tests {
         );
         let replay_task = tokio::spawn(replay.run());
 
-        let mut ingestor = BlockIngestor::new(
+        let mut ingestor = BlockIngestor::with_source(
             ctx.clone(),
             join_slot,
             senders,
             CancellationToken::new(),
+            PollSource::new(ctx.clone(), CancellationToken::new()),
         );
 
         // First fetch: queues the join block but cannot promote yet because
@@ -532,11 +548,12 @@ mod tests {
         );
         let replay_task = tokio::spawn(replay.run());
 
-        let mut ingestor = BlockIngestor::new(
+        let mut ingestor = BlockIngestor::with_source(
             ctx.clone(),
             join_slot,
             senders,
             CancellationToken::new(),
+            PollSource::new(ctx.clone(), CancellationToken::new()),
         );
 
         let tip = ctx.rpc.get_slot().await.expect("get tip");
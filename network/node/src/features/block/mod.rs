@@ -3,3 +3,4 @@ pub mod ingest_monitor;
 pub mod ingestor;
 pub mod pending_blocks;
 pub mod pending_tracks;
+pub mod source;
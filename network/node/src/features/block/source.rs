@@ -0,0 +1,266 @@
+//! Near-tip block sources for the ingestor.
+//!
+//! Catch-up always goes through the fetch pipeline; once the ingestor is
+//! within a pipeline's depth of the confirmed tip it asks a [`BlockSource`]
+//! for the next blocks instead. [`PollSource`] fetches slot by slot, and
+//! [`FeedSource`] waits on the backend's block subscription, polling only
+//! to fill gaps or while the feed is down.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use rpc::{BlockSubscription, Rpc};
+use store::Store;
+use tape_core::types::SlotNumber;
+use tape_protocol::Api;
+use tokio::time::{sleep, timeout};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::context::NodeContext;
+use crate::core::error::NodeError;
+use crate::features::block::fetch::{fetch_and_parse_block, fetch_blocks_ordered, parse_block};
+use crate::features::block::ingestor::ParsedBlock;
+
+/// Wait between tip checks when polling finds nothing new.
+const TIP_POLL_MS: u64 = 400;
+
+/// Silence on the feed longer than this is treated as a stall, and the
+/// ingestor polls once before waiting on the feed again.
+const FEED_STALL: Duration = Duration::from_secs(2);
+
+/// Wait before resubscribing after the feed ends or fails.
+const RESUBSCRIBE_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Blocks handed to the ingestor by one [`BlockSource::next_blocks`] call.
+#[derive(Debug, Default)]
+pub struct SourcedBlocks {
+    /// Confirmed blocks in slot order. Skipped slots have no entry.
+    pub blocks: Vec<Arc<ParsedBlock>>,
+
+    /// First slot not covered by `blocks`.
+    pub next: SlotNumber,
+}
+
+/// Supplies confirmed blocks near the tip.
+pub trait BlockSource: Send {
+    /// Return the blocks from `from` onward that are ready, given the
+    /// confirmed `tip` the ingestor last saw. An empty batch with `next ==
+    /// from` means nothing new has been confirmed yet.
+    fn next_blocks(
+        &mut self,
+        from: SlotNumber,
+        tip: u64,
+    ) -> impl Future<Output = Result<SourcedBlocks, NodeError>> + Send;
+}
+
+/// Fetches one slot per call through `get_block`.
+pub struct PollSource<Db: Store, Cluster: Api, Blockchain: Rpc> {
+    context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    cancel: CancellationToken,
+}
+
+impl<Db: Store, Cluster: Api, Blockchain: Rpc> PollSource<Db, Cluster, Blockchain> {
+    pub fn new(
+        context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self { context, cancel }
+    }
+}
+
+impl<Db: Store, Cluster: Api, Blockchain: Rpc> BlockSource for PollSource<Db, Cluster, Blockchain> {
+    async fn next_blocks(
+        &mut self,
+        from: SlotNumber,
+        tip: u64,
+    ) -> Result<SourcedBlocks, NodeError> {
+        if from.0 > tip {
+            sleep(Duration::from_millis(TIP_POLL_MS)).await;
+            return Ok(SourcedBlocks {
+                blocks: Vec::new(),
+                next: from,
+            });
+        }
+
+        let fetched = fetch_and_parse_block(self.context.clone(), self.cancel.clone(), from).await?;
+        Ok(SourcedBlocks {
+            blocks: fetched.into_iter().collect(),
+            next: from.next(),
+        })
+    }
+}
+
+/// Reads blocks from the backend's push feed, falling back to
+/// [`PollSource`] when the backend has none, the feed drops, or it skips
+/// over a block.
+pub struct FeedSource<Db: Store, Cluster: Api, Blockchain: Rpc> {
+    context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    cancel: CancellationToken,
+    poll: PollSource<Db, Cluster, Blockchain>,
+    feed: Option<BlockSubscription>,
+    /// Cleared once the backend reports it has no feed.
+    supported: bool,
+    resubscribe_at: Instant,
+}
+
+impl<Db: Store, Cluster: Api, Blockchain: Rpc> FeedSource<Db, Cluster, Blockchain> {
+    pub fn new(
+        context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            poll: PollSource::new(context.clone(), cancel.clone()),
+            context,
+            cancel,
+            feed: None,
+            supported: true,
+            resubscribe_at: Instant::now(),
+        }
+    }
+
+    async fn ensure_subscribed(&mut self) {
+        if self.feed.is_some() || !self.supported || Instant::now() < self.resubscribe_at {
+            return;
+        }
+
+        match self.context.rpc.subscribe_blocks().await {
+            Ok(Some(feed)) => {
+                info!("block_source: subscribed to block feed");
+                self.feed = Some(feed);
+            }
+            Ok(None) => {
+                debug!("block_source: backend has no block feed, polling");
+                self.supported = false;
+            }
+            Err(error) => {
+                warn!(error = %error, "block_source: block feed subscribe failed");
+                self.resubscribe_at = Instant::now() + RESUBSCRIBE_BACKOFF;
+            }
+        }
+    }
+
+    fn drop_feed(&mut self) {
+        self.feed = None;
+        self.resubscribe_at = Instant::now() + RESUBSCRIBE_BACKOFF;
+    }
+}
+
+impl<Db: Store, Cluster: Api, Blockchain: Rpc> BlockSource for FeedSource<Db, Cluster, Blockchain> {
+    async fn next_blocks(
+        &mut self,
+        from: SlotNumber,
+        tip: u64,
+    ) -> Result<SourcedBlocks, NodeError> {
+        self.ensure_subscribed().await;
+        let Some(feed) = self.feed.as_mut() else {
+            return self.poll.next_blocks(from, tip).await;
+        };
+
+        let pushed = match timeout(FEED_STALL, feed.next()).await {
+            Ok(Some(Ok(pushed))) => pushed,
+            Ok(Some(Err(error))) => {
+                warn!(error = %error, "block_source: block feed failed, polling");
+                self.drop_feed();
+                return self.poll.next_blocks(from, tip).await;
+            }
+            Ok(None) => {
+                warn!("block_source: block feed ended, polling");
+                self.drop_feed();
+                return self.poll.next_blocks(from, tip).await;
+            }
+            Err(_) => return self.poll.next_blocks(from, tip).await,
+        };
+
+        let slot = SlotNumber(pushed.slot);
+        if slot < from {
+            return Ok(SourcedBlocks {
+                blocks: Vec::new(),
+                next: from,
+            });
+        }
+        let block = Arc::new(parse_block(slot, &pushed.block)?);
+
+        // A parent at or past `from` is a block the feed never delivered;
+        // a parent below it means everything in between was skipped.
+        let mut blocks = Vec::new();
+        if block.parent_slot >= from {
+            debug!(
+                from = from.0,
+                slot = slot.0,
+                "block_source: feed skipped blocks, filling by polling"
+            );
+            blocks = fill_gap(self.context.clone(), self.cancel.clone(), from, slot).await?;
+        }
+        blocks.push(block);
+
+        Ok(SourcedBlocks {
+            blocks,
+            next: slot.next(),
+        })
+    }
+}
+
+/// Fetch `from..until` through the pipeline, for blocks the feed never
+/// delivered.
+async fn fill_gap<Db, Cluster, Blockchain>(
+    context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    cancel: CancellationToken,
+    from: SlotNumber,
+    until: SlotNumber,
+) -> Result<Vec<Arc<ParsedBlock>>, NodeError>
+where
+    Db: Store,
+    Cluster: Api,
+    Blockchain: Rpc,
+{
+    let mut blocks = Vec::new();
+    let mut fetched = fetch_blocks_ordered(context, cancel, from.0..=until.0.saturating_sub(1));
+    while let Some((_, block)) = fetched.next().await {
+        blocks.extend(block?);
+    }
+    Ok(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use tape_core::system::EpochPhase;
+    use tape_core::types::EpochNumber;
+
+    use super::*;
+    use crate::harness::NodeHarness;
+
+    const EPOCH: EpochNumber = EpochNumber(3);
+    const NODE: usize = 24;
+
+    // blocks confirmed before the subscription are fetched ahead of the
+    // first pushed block
+    #[tokio::test]
+    async fn feed_fills_gap_before_first_pushed_block() {
+        let harness = NodeHarness::builder()
+            .nodes(25)
+            .epoch(EPOCH)
+            .phase(EpochPhase::Active)
+            .build()
+            .await
+            .expect("build harness");
+        let ctx = harness.ctx_for(NODE);
+        let tip = ctx.rpc.get_slot().await.expect("get tip");
+
+        harness.rpc().warp_to_slot(tip + 1).expect("confirm unpushed slot");
+
+        let mut source = FeedSource::new(ctx.clone(), CancellationToken::new());
+        let from = SlotNumber(tip + 1);
+        let (sourced, _) = tokio::join!(source.next_blocks(from, tip + 1), async {
+            sleep(Duration::from_millis(50)).await;
+            harness.rpc().warp_to_slot(tip + 2).expect("confirm pushed slot");
+        });
+        let sourced = sourced.expect("next blocks");
+
+        let slots: Vec<u64> = sourced.blocks.iter().map(|block| block.slot.0).collect();
+        assert_eq!(slots, vec![tip + 1, tip + 2]);
+        assert_eq!(sourced.next, SlotNumber(tip + 3));
+    }
+}
@@ -19,7 +19,7 @@ use tape_store::ops::MetaOps;
 use crate::config::node::NodeConfig;
 use crate::context::NodeContext;
 use crate::core::error::NodeError;
-use crate::features::bootstrap::{block, discovery, fetch, validate};
+use crate::features::bootstrap::{block, discovery, fetch, owners, validate};
 use crate::features::replay::engine::{ReplayEngine, ReplayPersistFn};
 use crate::features::store::manager::persist_batch;
 
@@ -82,6 +82,7 @@ where
 
     let start_slot = run_replay_phases(context, config, &checkpoint, cancel, persist).await?;
     validate::validate_bootstrap_store(context.store.as_ref())?;
+    owners::backfill_tape_owners(context, cancel).await?;
     context.bootstrap.mark_ready();
 
     info!(
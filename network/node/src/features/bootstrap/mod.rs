@@ -2,6 +2,7 @@ pub mod block;
 pub mod discovery;
 pub mod fetch;
 pub mod manager;
+pub mod owners;
 pub mod replay;
 mod validate;
 
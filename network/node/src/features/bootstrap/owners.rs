@@ -0,0 +1,83 @@
+//! Tape owner index backfill.
+//!
+//! The owner index is written when a `ReserveTape` or `TransferTape` event is
+//! applied. Tapes a store replayed before the index existed have no owner row,
+//! and their reserve events are long pruned, so the owner is read from the
+//! tape account on chain instead. Later transfer events keep the row current.
+
+use std::sync::Arc;
+
+use rpc::Rpc;
+use store::Store;
+use tape_core::tape::TapeFlags;
+use tape_protocol::Api;
+use tape_store::ops::TapeOps;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::context::NodeContext;
+use crate::core::error::NodeError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OwnerBackfillStats {
+    pub backfilled: usize,
+    /// Tapes whose account could not be read; they are retried next boot
+    pub unresolved: usize,
+}
+
+/// Record the on-chain authority of every user tape missing from the owner
+/// index. A no-op once every tape has an owner row.
+pub async fn backfill_tape_owners<Db, Cluster, Blockchain>(
+    context: &Arc<NodeContext<Db, Cluster, Blockchain>>,
+    cancel: &CancellationToken,
+) -> Result<OwnerBackfillStats, NodeError>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let store = context.store.as_ref();
+    let mut stats = OwnerBackfillStats::default();
+    let tapes = store.iter_all_tapes().map_err(store_error)?;
+
+    for (tape, info) in tapes {
+        if cancel.is_cancelled() {
+            return Err(NodeError::Store("bootstrap owner backfill: cancelled".into()));
+        }
+        // System tapes belong to the program and never enter the index.
+        if TapeFlags::is_system(info.flags) {
+            continue;
+        }
+        if store.get_tape_owner(tape).map_err(store_error)?.is_some() {
+            continue;
+        }
+
+        match context.rpc.get_tape_by_address(&tape).await {
+            Ok(account) => {
+                store
+                    .set_tape_owner(tape, account.authority)
+                    .map_err(store_error)?;
+                stats.backfilled += 1;
+            }
+            Err(error) => {
+                warn!(tape = %tape, %error, "owner backfill: tape account unavailable");
+                stats.unresolved += 1;
+            }
+        }
+    }
+
+    if stats.backfilled > 0 || stats.unresolved > 0 {
+        info!(
+            backfilled = stats.backfilled,
+            unresolved = stats.unresolved,
+            "owner backfill: complete"
+        );
+    } else {
+        debug!("owner backfill: index already complete");
+    }
+    Ok(stats)
+}
+
+fn store_error(error: impl std::fmt::Display) -> NodeError {
+    NodeError::Store(error.to_string())
+}
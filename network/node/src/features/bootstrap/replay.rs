@@ -39,8 +39,8 @@ mod tests {
     use tape_core::track::types::{CompressedTrack, TrackKind, TrackState};
     use tape_core::types::coin::TAPE;
     use tape_core::types::{
-        ChunkNumber, ContentType, EpochNumber, SlotNumber, StorageUnits, StripeCount,
-        TapeNumber, TrackNumber,
+        ChunkNumber, EpochNumber, ObjectHeaders, SlotNumber, StorageUnits, StripeCount, TapeNumber,
+        TrackNumber,
     };
     use tape_crypto::address::Address;
     use tape_crypto::tx::Txid;
@@ -106,7 +106,7 @@ mod tests {
         epoch: EpochNumber,
         blob: BlobEncoding,
         name: Vec<u8>,
-        content_type: ContentType,
+        headers: ObjectHeaders,
     ) -> ReplayableEvent {
         ReplayableEvent::Track(ReplayTrack {
             state: CompressedTrack {
@@ -123,8 +123,9 @@ mod tests {
             blob: Some(blob),
             object: Some(ReplayTrackObject {
                 name,
-                content_type,
+                content_type: headers.hot_content_type(),
                 logical_size: blob.size,
+                headers,
             }),
         })
     }
@@ -346,7 +347,10 @@ mod tests {
         let track = track_pda(tape, track_number).0;
         let blob = blob();
         let name = b"photos/cat.jpg".to_vec();
-        let content_type = ContentType::ImageJpeg;
+        let mut headers = ObjectHeaders::with_content_type("image/jpeg; profile=srgb");
+        headers.content_disposition = Some("inline".into());
+        headers.insert_metadata("album", "cats");
+        let content_type = headers.hot_content_type();
 
         let log = log_with_entries(
             epoch,
@@ -374,7 +378,7 @@ mod tests {
                         epoch,
                         blob,
                         name.clone(),
-                        content_type,
+                        headers.clone(),
                     )),
                 ],
             }],
@@ -397,6 +401,7 @@ mod tests {
         assert_eq!(snapshot_entry, live_entry);
         assert_eq!(snapshot_entry.etag, blob.commitment);
         assert_eq!(snapshot_entry.content_type, content_type);
+        assert_eq!(snapshot_entry.headers, headers);
         assert_eq!(snapshot_entry.block_time, block_time);
         assert_eq!(snapshot_entry.slot, slot);
 
@@ -0,0 +1,377 @@
+//! Draw and check the storage challenges this node issues to a group peer.
+
+use std::sync::Arc;
+
+use rpc::Rpc;
+use store::Store;
+use tape_core::spooler::GroupIndex;
+use tape_core::system::{
+    CHALLENGES_PER_EPOCH, ChallengeTarget, challenge_seed, select_challenge_stripe,
+    select_challenge_track, verify_challenge,
+};
+use tape_core::track::data::BlobData;
+use tape_core::types::{BitmapRead, EpochNumber, SpoolIndex};
+use tape_crypto::Address;
+use tape_protocol::api::{ApiError, ChallengeReq};
+use tape_protocol::{Api, ProtocolState};
+use tape_store::ops::{ObjectInfoOps, SliceOps, TrackDataOps};
+use tape_store::types::{ObjectInfo, SystemObjectKind};
+use tracing::{debug, trace};
+
+use crate::context::NodeContext;
+use crate::core::error::NodeError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeOutcome {
+    /// Every drawn challenge was answered with a slice matching its commitment.
+    Passed,
+    /// At least one answer was missing or did not verify.
+    Failed,
+    /// Nothing could be judged: either side is still syncing the group, no
+    /// tracks to draw from, or the peer was unreachable. Liveness is left to
+    /// the eviction health probe.
+    Skipped,
+}
+
+/// Challenge `peer` on the slices it holds for `group` this epoch.
+///
+/// Tracks are drawn from this node's own slice set for the group, since every
+/// spool in a group stores a slice of the same tracks. Only tracks certified
+/// before this epoch are drawn, so the peer has had a full epoch to store them.
+pub async fn challenge_peer<Db, Cluster, Blockchain>(
+    ctx: &Arc<NodeContext<Db, Cluster, Blockchain>>,
+    state: &ProtocolState,
+    group: GroupIndex,
+    peer: Address,
+) -> Result<ChallengeOutcome, NodeError>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let me = ctx.node_address();
+    let Some((my_spool, _)) = state.spool_for_node_in_group(group, me) else {
+        return Ok(ChallengeOutcome::Skipped);
+    };
+    let Some((peer_spool, _)) = state.spool_for_node_in_group(group, peer) else {
+        return Ok(ChallengeOutcome::Skipped);
+    };
+
+    // Either side may still be pulling the group's slices this epoch.
+    if !spool_synced(state, group, my_spool) || !spool_synced(state, group, peer_spool) {
+        trace!(%peer, group = group.0, "challenge: group sync incomplete");
+        return Ok(ChallengeOutcome::Skipped);
+    }
+
+    let epoch = state.epoch();
+    let mut tracks = Vec::new();
+    for track in ctx
+        .store
+        .iter_slice_keys_by_spool(my_spool)
+        .map_err(|e| NodeError::Store(format!("iter_slice_keys_by_spool: {e}")))?
+    {
+        let info = ctx
+            .store
+            .get_object_info(track)
+            .map_err(|e| NodeError::Store(format!("get_object_info: {e}")))?;
+        if info.is_some_and(|info| certified_before(&info, epoch)) {
+            tracks.push(track);
+        }
+    }
+    tracks.sort();
+
+    let seed = challenge_seed(epoch, state.nonce(), me, peer);
+    let mut outcome = ChallengeOutcome::Skipped;
+
+    for round in 0..CHALLENGES_PER_EPOCH {
+        let Some(track) = select_challenge_track(seed, round, &tracks) else {
+            break;
+        };
+
+        let blob = match ctx
+            .store
+            .get_track_data(track)
+            .map_err(|e| NodeError::Store(format!("get_track_data: {e}")))?
+        {
+            Some(BlobData::Coded(blob)) => blob,
+            _ => continue,
+        };
+        // Leaves that do not rebuild the commitment cannot judge the peer,
+        // and whole-slice leaves have no segment to answer with.
+        if !blob.is_segmented() || blob.commitment_root() != blob.commitment {
+            continue;
+        }
+
+        let Some(stripe) = select_challenge_stripe(seed, round, &blob) else {
+            continue;
+        };
+
+        let target = ChallengeTarget {
+            track,
+            spool: peer_spool,
+            stripe,
+        };
+        let request = ChallengeReq {
+            track,
+            spool: peer_spool,
+            stripe,
+            epoch,
+        };
+
+        match ctx.api.challenge(peer, &request).await {
+            Ok(answer) if verify_challenge(&blob, &target, &answer.data, &answer.segment_proof) => {
+                outcome = ChallengeOutcome::Passed;
+            }
+            Ok(_) | Err(ApiError::NotFound | ApiError::NotResponsible) => {
+                debug!(%peer, %track, stripe, group = group.0, "challenge: answer failed");
+                return Ok(ChallengeOutcome::Failed);
+            }
+            // A slice the peer already has queued for repair proves nothing
+            // either way; the repair is what puts it back.
+            Err(ApiError::BlacklistedObject | ApiError::PendingRepair) => continue,
+            Err(error) => {
+                trace!(?error, %peer, %track, "challenge: peer unreachable");
+                return Ok(ChallengeOutcome::Skipped);
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Whether `spool` has reported its sync for `group` this epoch.
+fn spool_synced(state: &ProtocolState, group: GroupIndex, spool: SpoolIndex) -> bool {
+    let Some(position) = group.position_of(spool) else {
+        return false;
+    };
+    state
+        .group(group)
+        .is_some_and(|group| group.synced.is_set(position))
+}
+
+/// Whether the object was certified in an epoch before `epoch`.
+fn certified_before(info: &ObjectInfo, epoch: EpochNumber) -> bool {
+    match info {
+        ObjectInfo::Valid {
+            certified_epoch: Some(certified),
+            ..
+        }
+        | ObjectInfo::System {
+            certified_epoch: Some(certified),
+            ..
+        } => *certified < epoch,
+        ObjectInfo::System {
+            kind: SystemObjectKind::Snapshot { epoch: snapshot },
+            ..
+        } => *snapshot < epoch,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use peer_memory::MemoryApi;
+    use tape_core::encoding::{EncodingProfile, LeafScheme};
+    use tape_core::erasure::{GROUP_SIZE, SLICE_TREE_HEIGHT};
+    use tape_core::track::blob::BlobEncoding;
+    use tape_core::track::stripe::{
+        segment_hashes, segment_proof, segment_range, slice_leaf, stripe_segment,
+    };
+    use tape_core::types::{SlotNumber, SpoolBitmap, StorageUnits, StripeCount};
+    use tape_crypto::Hash;
+    use tape_crypto::merkle::root_from_leaf_hashes;
+    use tape_protocol::api::{ChallengeRes, PeerReq, PeerRes};
+
+    use super::*;
+    use crate::features::vote::{group_peers_without, member_groups};
+    use crate::harness::{NodeHarness, TestContext};
+
+    const STRIPES: StripeCount = StripeCount(4);
+
+    async fn test_context(api: MemoryApi) -> TestContext {
+        NodeHarness::builder()
+            .nodes(25)
+            .no_prev_snapshot_tape()
+            .api(api)
+            .build()
+            .await
+            .expect("build harness")
+            .ctx_for(0)
+    }
+
+    fn slices() -> Vec<Vec<u8>> {
+        (0..GROUP_SIZE)
+            .map(|position| (0..96).map(|byte| (byte + position * 7) as u8).collect())
+            .collect()
+    }
+
+    fn blob() -> BlobEncoding {
+        let slices = slices();
+        let leaves: [Hash; GROUP_SIZE] =
+            core::array::from_fn(|position| slice_leaf(&slices[position], STRIPES));
+        BlobEncoding {
+            size: StorageUnits::from_bytes(1_537),
+            commitment: root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&leaves),
+            profile: EncodingProfile::basic_default().with_leaf_scheme(LeafScheme::Segmented),
+            stripe_size: StorageUnits::from_bytes(512),
+            stripe_count: STRIPES,
+            leaves,
+        }
+    }
+
+    // the answer an honest peer gives
+    fn honest(req: &ChallengeReq) -> ChallengeRes {
+        let slice = &slices()[req.spool.as_usize() % GROUP_SIZE];
+        let segment = stripe_segment(u64::from(req.stripe), STRIPES);
+        let hashes = segment_hashes(slice, STRIPES);
+        ChallengeRes {
+            data: slice[segment_range(slice.len(), STRIPES, segment)].to_vec(),
+            segment_proof: segment_proof(&hashes, segment).unwrap(),
+        }
+    }
+
+    fn peer_api(
+        respond: impl Fn(ChallengeRes) -> Result<ChallengeRes, ApiError> + Send + Sync + 'static,
+    ) -> MemoryApi {
+        MemoryApi::new(move |_, req| match req {
+            PeerReq::Challenge(req) => PeerRes::Challenge(respond(honest(&req))),
+            _ => panic!("unexpected request"),
+        })
+    }
+
+    // a synced group peer, with this node's slice of one track in the group
+    // certified the epoch before
+    fn seed(ctx: &TestContext, blob: BlobEncoding) -> (GroupIndex, Address) {
+        let state = ctx.state();
+        let me = ctx.node_address();
+        let group = member_groups(&state.member_spools(me))[0];
+        let peer = group_peers_without(&state, group, me)[0];
+        let (spool, _) = state.spool_for_node_in_group(group, me).unwrap();
+
+        let track = Address::new_unique();
+        ctx.store.put_track_data(track, BlobData::Coded(blob)).unwrap();
+        ctx.store
+            .put_slice(spool, track, slices()[spool.as_usize() % GROUP_SIZE].clone())
+            .unwrap();
+        certify(ctx, track, EpochNumber(state.epoch().0 - 1));
+        mark_synced(ctx, group, GROUP_SIZE);
+        (group, peer)
+    }
+
+    fn certify(ctx: &TestContext, track: Address, epoch: EpochNumber) {
+        ctx.store
+            .put_object_info(
+                track,
+                ObjectInfo::Valid {
+                    track_address: track,
+                    registered_epoch: epoch,
+                    certified_epoch: Some(epoch),
+                    slot: SlotNumber(10),
+                },
+            )
+            .unwrap();
+    }
+
+    // mark the first `count` spools of the group as synced this epoch
+    fn mark_synced(ctx: &TestContext, group: GroupIndex, count: usize) {
+        let mut state = (*ctx.state()).clone();
+        let positions: Vec<usize> = (0..count).collect();
+        state
+            .current
+            .groups
+            .iter_mut()
+            .find(|candidate| candidate.id == group)
+            .unwrap()
+            .synced = SpoolBitmap::from_indices(&positions);
+        ctx.set_state(state).unwrap();
+    }
+
+    async fn outcome(api: MemoryApi) -> ChallengeOutcome {
+        let ctx = test_context(api).await;
+        let (group, peer) = seed(&ctx, blob());
+        challenge_peer(&ctx, &ctx.state(), group, peer).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn passes_honest_peer() {
+        assert_eq!(outcome(peer_api(Ok)).await, ChallengeOutcome::Passed);
+    }
+
+    #[tokio::test]
+    async fn fails_corrupted_answer() {
+        let api = peer_api(|mut res| {
+            res.data[0] ^= 0xFF;
+            Ok(res)
+        });
+        assert_eq!(outcome(api).await, ChallengeOutcome::Failed);
+    }
+
+    #[tokio::test]
+    async fn fails_missing_slice() {
+        let api = peer_api(|_| Err(ApiError::NotFound));
+        assert_eq!(outcome(api).await, ChallengeOutcome::Failed);
+    }
+
+    // a peer with the slice queued for repair is not failed for it
+    #[tokio::test]
+    async fn skips_pending_repair() {
+        let api = peer_api(|_| Err(ApiError::PendingRepair));
+        assert_eq!(outcome(api).await, ChallengeOutcome::Skipped);
+    }
+
+    #[tokio::test]
+    async fn skips_unreachable_peer() {
+        let api = peer_api(|_| Err(ApiError::ConnectionFailed("down".into())));
+        assert_eq!(outcome(api).await, ChallengeOutcome::Skipped);
+    }
+
+    // local leaves that miss the commitment never judge the peer
+    #[tokio::test]
+    async fn skips_unverifiable_blob() {
+        let ctx = test_context(peer_api(|_| Err(ApiError::NotFound))).await;
+        let mut forged = blob();
+        forged.commitment = Hash::default();
+        let (group, peer) = seed(&ctx, forged);
+
+        let outcome = challenge_peer(&ctx, &ctx.state(), group, peer).await.unwrap();
+        assert_eq!(outcome, ChallengeOutcome::Skipped);
+    }
+
+    // nobody is judged while either side is still syncing the group
+    #[tokio::test]
+    async fn skips_until_group_synced() {
+        let ctx = test_context(peer_api(|_| Err(ApiError::NotFound))).await;
+        let (group, peer) = seed(&ctx, blob());
+        mark_synced(&ctx, group, 0);
+
+        let outcome = challenge_peer(&ctx, &ctx.state(), group, peer).await.unwrap();
+        assert_eq!(outcome, ChallengeOutcome::Skipped);
+    }
+
+    // a track certified this epoch may still be landing on the peer
+    #[tokio::test]
+    async fn skips_freshly_certified_track() {
+        let ctx = test_context(peer_api(|_| Err(ApiError::NotFound))).await;
+        let (group, peer) = seed(&ctx, blob());
+        let state = ctx.state();
+        let (spool, _) = state.spool_for_node_in_group(group, ctx.node_address()).unwrap();
+        for track in ctx.store.iter_slice_keys_by_spool(spool).unwrap() {
+            certify(&ctx, track, state.epoch());
+        }
+
+        let outcome = challenge_peer(&ctx, &state, group, peer).await.unwrap();
+        assert_eq!(outcome, ChallengeOutcome::Skipped);
+    }
+
+    // tracks certified under whole-slice leaves are never challenged
+    #[tokio::test]
+    async fn skips_whole_leaf_blob() {
+        let ctx = test_context(peer_api(|_| Err(ApiError::NotFound))).await;
+        let mut whole = blob();
+        whole.profile = whole.profile.with_leaf_scheme(LeafScheme::Whole);
+        let (group, peer) = seed(&ctx, whole);
+
+        let outcome = challenge_peer(&ctx, &ctx.state(), group, peer).await.unwrap();
+        assert_eq!(outcome, ChallengeOutcome::Skipped);
+    }
+}
@@ -0,0 +1,49 @@
+//! Certified challenge failures this node has aggregated.
+//!
+//! An entry is recorded once a supermajority of a group has signed that the
+//! target failed its storage challenges. The eviction manager treats a
+//! certified target as failing even while it still answers health probes.
+//! Entries are dropped when the target passes a later round or is evicted.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tape_core::bls::BlsSignature;
+use tape_core::spooler::GroupIndex;
+use tape_core::types::{EpochNumber, SpoolBitmap};
+use tape_crypto::Address;
+
+#[derive(Clone, Copy)]
+pub struct ChallengeCertificate {
+    pub epoch: EpochNumber,
+    pub group: GroupIndex,
+    pub bitmap: SpoolBitmap,
+    pub signature: BlsSignature,
+}
+
+#[derive(Default)]
+pub struct ChallengeLedger {
+    certified: Mutex<HashMap<Address, ChallengeCertificate>>,
+}
+
+impl ChallengeLedger {
+    pub fn record(&self, node: Address, certificate: ChallengeCertificate) {
+        self.lock().insert(node, certificate);
+    }
+
+    pub fn remove(&self, node: &Address) {
+        self.lock().remove(node);
+    }
+
+    pub fn get(&self, node: &Address) -> Option<ChallengeCertificate> {
+        self.lock().get(node).copied()
+    }
+
+    pub fn contains(&self, node: &Address) -> bool {
+        self.lock().contains_key(node)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Address, ChallengeCertificate>> {
+        self.certified.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rpc::Rpc;
+use store::Store;
+use tape_core::spooler::GroupIndex;
+use tape_core::system::EpochPhase;
+use tape_core::types::EpochNumber;
+use tape_crypto::Address;
+use tape_protocol::Api;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::context::NodeContext;
+use crate::core::error::NodeError;
+use crate::features::challenge::issue::{ChallengeOutcome, challenge_peer};
+use crate::features::challenge::vote::{
+    certify_challenge_failure, create_challenge_votes, fanout_challenge_votes,
+    submit_challenge_slash,
+};
+use crate::features::vote::{group_peers_without, member_groups};
+
+const CHALLENGE_HEARTBEAT: Duration = Duration::from_secs(60);
+
+pub struct ChallengeManager<Db: Store, Cluster: Api, Blockchain: Rpc> {
+    context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    cancel: CancellationToken,
+    // Epoch the bookkeeping below belongs to; both sets reset when it moves.
+    epoch: EpochNumber,
+    // Peers already judged this epoch, per shared group.
+    judged: HashSet<(Address, GroupIndex)>,
+    // Peers that failed a challenge this epoch and whose vote is still open.
+    failed: HashMap<Address, bool>,
+}
+
+impl<Db, Cluster, Blockchain> ChallengeManager<Db, Cluster, Blockchain>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    pub fn new(
+        context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            context,
+            cancel,
+            epoch: EpochNumber::zero(),
+            judged: HashSet::new(),
+            failed: HashMap::new(),
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), NodeError> {
+        let mut heartbeat = tokio::time::interval(CHALLENGE_HEARTBEAT);
+
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => return Ok(()),
+                _ = heartbeat.tick() => {
+                    let cancel = self.cancel.clone();
+                    cancel.run_until_cancelled(self.try_progress()).await.unwrap_or(Ok(()))?;
+                }
+            }
+        }
+    }
+
+    /// Challenge every group peer once per epoch, then drive the failure
+    /// votes for any peer that could not prove it still holds its slices.
+    ///
+    /// Challenges are only issued in the active phase, and only once both
+    /// this node and the peer have reported their spool sync for the group,
+    /// so a peer is never judged on data it is still receiving.
+    async fn try_progress(&mut self) -> Result<(), NodeError> {
+        // Readiness gate: never sign off stale local state.
+        if !self.context.is_at_tip() {
+            return Ok(());
+        }
+
+        let state = self.context.state();
+        let me = self.context.node_address();
+        if state.find_member(me).is_none() || state.phase() != EpochPhase::Active {
+            return Ok(());
+        }
+
+        if self.epoch != state.epoch() {
+            self.epoch = state.epoch();
+            self.judged.clear();
+            self.failed.clear();
+        }
+
+        for group in member_groups(&state.member_spools(me)) {
+            for peer in group_peers_without(&state, group, me) {
+                if self.judged.contains(&(peer, group)) {
+                    continue;
+                }
+
+                match challenge_peer(&self.context, &state, group, peer).await? {
+                    ChallengeOutcome::Passed => {
+                        self.judged.insert((peer, group));
+                        if !self.failed.contains_key(&peer) {
+                            self.context.challenge_ledger.remove(&peer);
+                        }
+                    }
+                    ChallengeOutcome::Failed => {
+                        self.judged.insert((peer, group));
+                        if self.failed.insert(peer, false).is_none() {
+                            info!(node = %peer, epoch = self.epoch.0, "challenge: peer failed, voting");
+                            create_challenge_votes(&self.context, &state, peer).await?;
+                        }
+                    }
+                    // Retried on the next heartbeat.
+                    ChallengeOutcome::Skipped => {}
+                }
+            }
+        }
+
+        for (peer, certified) in self.failed.iter_mut() {
+            if *certified {
+                continue;
+            }
+
+            fanout_challenge_votes(&self.context, &state, *peer).await?;
+            if certify_challenge_failure(&self.context, &state, *peer)? {
+                // Hand the target to the eviction manager, which votes
+                // against it on the strength of the certificate.
+                self.context.eviction_queue.insert(*peer);
+                submit_challenge_slash(&self.context, *peer).await;
+                *certified = true;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use peer_memory::MemoryApi;
+    use tape_core::encoding::{EncodingProfile, LeafScheme};
+    use tape_core::erasure::{GROUP_SIZE, SLICE_TREE_HEIGHT};
+    use tape_core::track::blob::BlobEncoding;
+    use tape_core::track::data::BlobData;
+    use tape_core::track::stripe::slice_leaf;
+    use tape_core::types::{SlotNumber, SpoolBitmap, StorageUnits, StripeCount};
+    use tape_crypto::Hash;
+    use tape_crypto::merkle::root_from_leaf_hashes;
+    use tape_protocol::api::{ApiError, PeerReq, PeerRes, VoteRes};
+    use tape_store::ops::{ObjectInfoOps, SliceOps, TrackDataOps, VoteOps};
+    use tape_store::types::ObjectInfo;
+
+    use super::*;
+    use crate::core::ingest::IngestState;
+    use crate::features::challenge::vote::challenge_vote;
+    use crate::harness::NodeHarness;
+
+    // a track certified last epoch whose slice this node holds in its first
+    // group, with the whole group synced
+    fn seed(ctx: &crate::harness::TestContext) -> GroupIndex {
+        let state = ctx.state();
+        let me = ctx.node_address();
+        let group = member_groups(&state.member_spools(me))[0];
+        let (spool, _) = state.spool_for_node_in_group(group, me).unwrap();
+
+        let slice = vec![0xAB; 96];
+        let leaves = [slice_leaf(&slice, StripeCount(4)); GROUP_SIZE];
+        let blob = BlobEncoding {
+            size: StorageUnits::from_bytes(1_537),
+            commitment: root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&leaves),
+            profile: EncodingProfile::basic_default().with_leaf_scheme(LeafScheme::Segmented),
+            stripe_size: StorageUnits::from_bytes(512),
+            stripe_count: StripeCount(4),
+            leaves,
+        };
+
+        let track = Address::new_unique();
+        ctx.store.put_track_data(track, BlobData::Coded(blob)).unwrap();
+        ctx.store.put_slice(spool, track, slice).unwrap();
+
+        let certified = EpochNumber(state.epoch().0 - 1);
+        ctx.store
+            .put_object_info(
+                track,
+                ObjectInfo::Valid {
+                    track_address: track,
+                    registered_epoch: certified,
+                    certified_epoch: Some(certified),
+                    slot: SlotNumber(10),
+                },
+            )
+            .unwrap();
+
+        let mut synced = (*state).clone();
+        let positions: Vec<usize> = (0..GROUP_SIZE).collect();
+        synced
+            .current
+            .groups
+            .iter_mut()
+            .find(|candidate| candidate.id == group)
+            .unwrap()
+            .synced = SpoolBitmap::from_indices(&positions);
+        ctx.set_state(synced).unwrap();
+        group
+    }
+
+    // peers that lost their slices fail, get voted on and see the votes
+    #[tokio::test]
+    async fn votes_against_failing_peers() {
+        let pushed = Arc::new(Mutex::new(Vec::new()));
+        let sink = pushed.clone();
+        let api = MemoryApi::new(move |node, req| match req {
+            PeerReq::Challenge(_) => PeerRes::Challenge(Err(ApiError::NotFound)),
+            PeerReq::Vote(req) => {
+                sink.lock().unwrap().push((node, req.candidate));
+                PeerRes::Vote(Ok(VoteRes))
+            }
+            _ => panic!("unexpected request"),
+        });
+        let ctx = NodeHarness::builder()
+            .nodes(25)
+            .no_prev_snapshot_tape()
+            .phase(EpochPhase::Active)
+            .api(api)
+            .build()
+            .await
+            .expect("build harness")
+            .ctx_for(0);
+        let group = seed(&ctx);
+        let mut manager = ChallengeManager::new(ctx.clone(), CancellationToken::new());
+
+        // Nothing is judged off stale local state.
+        manager.try_progress().await.unwrap();
+        assert!(manager.failed.is_empty());
+
+        ctx.ingest.publish(IngestState::AtTip);
+        manager.try_progress().await.unwrap();
+
+        let state = ctx.state();
+        let me = ctx.node_address();
+        let peers = group_peers_without(&state, group, me);
+        assert!(!peers.is_empty());
+        for peer in &peers {
+            assert_eq!(manager.failed.get(peer), Some(&false));
+            assert!(manager.judged.contains(&(*peer, group)));
+
+            let vote = challenge_vote(&state, *peer);
+            let sigs = ctx.store.iter_vote_sigs(vote, group).unwrap();
+            assert!(sigs.iter().any(|(signer, _)| *signer == me));
+            assert!(!ctx.challenge_ledger.contains(peer));
+        }
+
+        // Votes reach the rest of the group, never the target itself.
+        let pushed = pushed.lock().unwrap();
+        assert!(!pushed.is_empty());
+        for (node, candidate) in pushed.iter() {
+            let target = peers
+                .iter()
+                .find(|peer| challenge_vote(&state, **peer) == *candidate)
+                .expect("vote for a failed peer");
+            assert_ne!(node, target);
+        }
+    }
+}
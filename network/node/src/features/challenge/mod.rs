@@ -0,0 +1,6 @@
+pub mod issue;
+pub mod ledger;
+pub mod manager;
+pub mod vote;
+
+pub use ledger::ChallengeLedger;
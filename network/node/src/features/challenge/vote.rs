@@ -0,0 +1,298 @@
+//! Sign, push and certify challenge failure votes for a target node.
+
+use std::sync::Arc;
+
+use rpc::Rpc;
+use store::Store;
+use tape_core::bft::is_supermajority;
+use tape_core::bls::BlsSignature;
+use tape_core::cert::{ChallengeFailMessage, challenge_vote_hash};
+use tape_core::erasure::GROUP_SIZE;
+use tape_core::spooler::GroupIndex;
+use tape_core::system::{VoteCandidate, VoteKind};
+use tape_core::types::SpoolBitmap;
+use tape_crypto::Address;
+use tape_protocol::api::VoteReq;
+use tape_protocol::{Api, ProtocolState};
+use tape_store::ops::VoteOps;
+use tracing::{debug, info, trace};
+
+use crate::chain::submit_slash_pool;
+use crate::context::NodeContext;
+use crate::core::chain_tx::{TxOutcome, submit_if_at_tip};
+use crate::core::error::NodeError;
+use crate::features::challenge::ledger::ChallengeCertificate;
+use crate::features::vote::{bitmap_index_in_group, group_peers_without, member_groups};
+
+/// Vote identity for a challenge failure of `node` in the current epoch.
+pub fn challenge_vote(state: &ProtocolState, node: Address) -> VoteCandidate {
+    VoteCandidate {
+        kind: VoteKind::Challenge,
+        voting_epoch: state.epoch(),
+        target_epoch: state.epoch(),
+        hash: challenge_vote_hash(node),
+    }
+}
+
+/// Groups this node shares with the target, i.e. where both hold a spool.
+pub fn shared_groups(state: &ProtocolState, me: Address, node: Address) -> Vec<GroupIndex> {
+    let theirs = member_groups(&state.member_spools(node));
+    member_groups(&state.member_spools(me))
+        .into_iter()
+        .filter(|group| theirs.contains(group))
+        .collect()
+}
+
+pub async fn create_challenge_votes<Db, Cluster, Blockchain>(
+    ctx: &Arc<NodeContext<Db, Cluster, Blockchain>>,
+    state: &ProtocolState,
+    node: Address,
+) -> Result<(), NodeError>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let me = ctx.node_address();
+    let message = ChallengeFailMessage::new(state.epoch(), state.nonce(), node).to_bytes();
+    let signature = ctx
+        .bls_sign(&message)
+        .map_err(|e| NodeError::Store(format!("challenge bls_sign: {e:?}")))?;
+
+    let vote = challenge_vote(state, node);
+    for group in shared_groups(state, me, node) {
+        ctx.store
+            .put_vote_sig(vote, group, me, &signature)
+            .map_err(|e| NodeError::Store(format!("put_vote_sig: {e}")))?;
+    }
+
+    Ok(())
+}
+
+pub async fn fanout_challenge_votes<Db, Cluster, Blockchain>(
+    ctx: &Arc<NodeContext<Db, Cluster, Blockchain>>,
+    state: &ProtocolState,
+    node: Address,
+) -> Result<(), NodeError>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let me = ctx.node_address();
+    let vote = challenge_vote(state, node);
+
+    for group in shared_groups(state, me, node) {
+        let sigs = ctx
+            .store
+            .iter_vote_sigs(vote, group)
+            .map_err(|e| NodeError::Store(format!("iter_vote_sigs: {e}")))?;
+
+        if is_supermajority(sigs.len() as u64, GROUP_SIZE as u64) {
+            continue;
+        }
+
+        let Some((_, signature)) = sigs.into_iter().find(|(signer, _)| *signer == me) else {
+            continue;
+        };
+
+        let request = VoteReq {
+            signer: me,
+            candidate: vote,
+            group,
+            signature,
+        };
+
+        // The target is left out: it never signs against itself.
+        for peer in group_peers_without(state, group, me) {
+            if peer == node {
+                continue;
+            }
+            if let Err(error) = ctx.api.vote(peer, &request).await {
+                trace!(
+                    ?error,
+                    %peer,
+                    epoch = vote.voting_epoch.0,
+                    group = group.0,
+                    %node,
+                    "challenge: vote push failed"
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Aggregate the first shared group to reach a supermajority into a
+/// certificate. Returns whether the target is certified.
+pub fn certify_challenge_failure<Db, Cluster, Blockchain>(
+    ctx: &Arc<NodeContext<Db, Cluster, Blockchain>>,
+    state: &ProtocolState,
+    node: Address,
+) -> Result<bool, NodeError>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let me = ctx.node_address();
+    let vote = challenge_vote(state, node);
+
+    for group in shared_groups(state, me, node) {
+        let sigs = ctx
+            .store
+            .iter_vote_sigs(vote, group)
+            .map_err(|e| NodeError::Store(format!("iter_vote_sigs: {e}")))?;
+
+        let mut indices = Vec::with_capacity(sigs.len());
+        let mut partials = Vec::with_capacity(sigs.len());
+
+        for (signer, signature) in sigs {
+            let Some(index) = bitmap_index_in_group(state, group, signer) else {
+                continue;
+            };
+            indices.push(index as usize);
+            partials.push(signature);
+        }
+
+        if !is_supermajority(partials.len() as u64, GROUP_SIZE as u64) {
+            continue;
+        }
+
+        let signature = BlsSignature::aggregate(&partials)
+            .map_err(|e| NodeError::Store(format!("aggregate challenge sigs: {e:?}")))?;
+
+        ctx.challenge_ledger.record(
+            node,
+            ChallengeCertificate {
+                epoch: state.epoch(),
+                group,
+                bitmap: SpoolBitmap::from_indices(&indices),
+                signature,
+            },
+        );
+
+        info!(
+            %node,
+            epoch = state.epoch().0,
+            group = group.0,
+            signers = partials.len(),
+            "challenge: failure certified"
+        );
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+/// Slash the target's pool on the strength of its failure certificate. Every
+/// signer that certifies submits; the first to land wins and the rest are
+/// refused on chain, so failures are only logged.
+pub async fn submit_challenge_slash<Db, Cluster, Blockchain>(
+    ctx: &Arc<NodeContext<Db, Cluster, Blockchain>>,
+    node: Address,
+) where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    let Some(certificate) = ctx.challenge_ledger.get(&node) else {
+        return;
+    };
+
+    let outcome = submit_if_at_tip(
+        &ctx.ingest,
+        "slash_pool",
+        submit_slash_pool(
+            ctx,
+            certificate.epoch,
+            node,
+            certificate.group,
+            certificate.bitmap,
+            certificate.signature,
+        ),
+    )
+    .await;
+
+    match outcome {
+        TxOutcome::Confirmed(txid) => {
+            info!(%node, epoch = certificate.epoch.0, %txid, "challenge: pool slashed");
+        }
+        TxOutcome::Rejected { kind, err } => {
+            debug!(%node, epoch = certificate.epoch.0, ?kind, %err, "challenge: slash rejected");
+        }
+        TxOutcome::SkippedStale => {
+            debug!(%node, epoch = certificate.epoch.0, "challenge: slash deferred, ingest stale");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::NodeHarness;
+
+    async fn harness() -> NodeHarness {
+        NodeHarness::builder()
+            .nodes(25)
+            .no_prev_snapshot_tape()
+            .build()
+            .await
+            .expect("build harness")
+    }
+
+    // a node sharing at least one group with the first node
+    fn target(state: &ProtocolState, me: Address) -> (GroupIndex, Address) {
+        let group = member_groups(&state.member_spools(me))[0];
+        (group, group_peers_without(state, group, me)[0])
+    }
+
+    #[tokio::test]
+    async fn signs_shared_groups() {
+        let harness = harness().await;
+        let ctx = harness.ctx_for(0);
+        let state = ctx.state();
+        let me = ctx.node_address();
+        let (group, node) = target(&state, me);
+
+        create_challenge_votes(&ctx, &state, node).await.unwrap();
+
+        let vote = challenge_vote(&state, node);
+        let sigs = ctx.store.iter_vote_sigs(vote, group).unwrap();
+        assert_eq!(sigs.len(), 1);
+        assert_eq!(sigs[0].0, me);
+        assert!(shared_groups(&state, me, node).contains(&group));
+    }
+
+    #[tokio::test]
+    async fn certifies_on_supermajority() {
+        let harness = harness().await;
+        let ctx = harness.ctx_for(0);
+        let state = ctx.state();
+        let me = ctx.node_address();
+        let (group, node) = target(&state, me);
+
+        create_challenge_votes(&ctx, &state, node).await.unwrap();
+        assert!(!certify_challenge_failure(&ctx, &state, node).unwrap());
+        assert!(!ctx.challenge_ledger.contains(&node));
+
+        // Every other group peer but the target signs the same failure.
+        let vote = challenge_vote(&state, node);
+        let message = ChallengeFailMessage::new(state.epoch(), state.nonce(), node).to_bytes();
+        for index in 1..25 {
+            let peer = harness.ctx_for(index);
+            let signer = peer.node_address();
+            if signer == node || state.spool_for_node_in_group(group, signer).is_none() {
+                continue;
+            }
+            let signature = peer.bls_sign(&message).unwrap();
+            ctx.store.put_vote_sig(vote, group, signer, &signature).unwrap();
+        }
+
+        assert!(certify_challenge_failure(&ctx, &state, node).unwrap());
+        let certificate = ctx.challenge_ledger.get(&node).expect("certificate");
+        assert_eq!(certificate.epoch, state.epoch());
+        assert_eq!(certificate.group, group);
+    }
+}
@@ -97,6 +97,7 @@ where
                 ParsedInstruction::NodeEvicted { event } => {
                     self.context.eviction_queue.remove(&event.node);
                     self.probe_failed.remove(&event.node);
+                    self.context.challenge_ledger.remove(&event.node);
                     info!(node = %event.node, "eviction: landed, cleared target");
                 }
                 _ => {}
@@ -142,12 +143,21 @@ where
 
     /// Judge the target with this node's own probe, at most once per voting
     /// epoch. A proposal alone never recruits a signature: only a target this
-    /// node observes failing stays queued, and a recovered target is dropped.
+    /// node observes failing, by probe or by certified storage challenge,
+    /// stays queued, and a recovered target is dropped.
     async fn judge_target(&mut self, node: Address, epoch: EpochNumber) -> bool {
         if self.probe_failed.get(&node) == Some(&epoch) {
             return true;
         }
 
+        // A certified challenge failure outweighs a healthy probe: the target
+        // is up but could not prove it still holds its slices.
+        if self.context.challenge_ledger.contains(&node) {
+            info!(node = %node, epoch = epoch.0, "eviction: target failed storage challenges, voting to evict");
+            self.probe_failed.insert(node, epoch);
+            return true;
+        }
+
         let healthy = matches!(
             self.context.api.get_health(node, &GetHealthReq).await,
             Ok(GetHealthRes { ok: true })
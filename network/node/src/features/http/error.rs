@@ -1,9 +1,11 @@
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use tape_protocol::api::SliceBatchStatus;
 
 #[derive(Debug)]
 pub enum RouteError {
     NotFound,
+    PendingRepair,
     BadRequest(String),
     Forbidden(String),
     NotResponsible,
@@ -17,6 +19,7 @@ impl IntoResponse for RouteError {
     fn into_response(self) -> Response {
         match self {
             Self::NotFound => (StatusCode::NOT_FOUND, "not found").into_response(),
+            Self::PendingRepair => (StatusCode::NOT_FOUND, "pending repair").into_response(),
             Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
             Self::Forbidden(message) => (StatusCode::FORBIDDEN, message).into_response(),
             Self::NotResponsible => (StatusCode::FORBIDDEN, "not responsible").into_response(),
@@ -34,3 +37,23 @@ impl IntoResponse for RouteError {
         }
     }
 }
+
+impl RouteError {
+    /// The per-item status a batch route answers with in place of this error.
+    pub fn batch_status(self) -> SliceBatchStatus {
+        match self {
+            Self::NotFound | Self::PendingRepair => SliceBatchStatus::NotFound,
+            Self::NotResponsible => SliceBatchStatus::NotResponsible,
+            Self::BlacklistedObject => SliceBatchStatus::BlacklistedObject,
+            Self::BadRequest(message) | Self::Forbidden(message) => {
+                SliceBatchStatus::Rejected(message)
+            }
+            Self::NotInCommittee => SliceBatchStatus::Rejected("not in committee".into()),
+            Self::InvalidSignature => SliceBatchStatus::Rejected("invalid signature".into()),
+            Self::Internal(message) => {
+                tracing::error!("http internal error: {message}");
+                SliceBatchStatus::Failed("internal error".into())
+            }
+        }
+    }
+}
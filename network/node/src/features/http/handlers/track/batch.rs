@@ -0,0 +1,102 @@
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+
+use rpc::Rpc;
+use store::Store;
+use tape_protocol::Api;
+use tape_protocol::api::{
+    BINARY_CONTENT, SLICE_BATCH_BYTES_LIMIT, SLICE_BATCH_ITEMS_LIMIT, SliceBatchGetRequest,
+    SliceBatchGetResponse, SliceBatchGetResult, SliceBatchPutRequest, SliceBatchPutResponse,
+    SliceBatchStatus,
+};
+use tracing::debug;
+
+use crate::features::http::auth::{MaybeStakedPeer, local_access_threshold};
+use crate::features::http::error::RouteError;
+use crate::features::http::handlers::track::slice::{put_slice_payload, read_stored_slice};
+use crate::features::http::state::AppState;
+
+/// Store slices for many tracks from one request. Each item gets the checks a
+/// single upload would, and a refused item does not stop the rest.
+pub async fn put_slices<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    State(state): State<AppState<Db, Cluster, Blockchain>>,
+    body: Bytes,
+) -> Result<impl IntoResponse, RouteError> {
+    let request: SliceBatchPutRequest = wincode::deserialize(&body)
+        .map_err(|error| RouteError::BadRequest(format!("slice batch: {error}")))?;
+    check_item_count(request.items.len())?;
+
+    let mut stored = 0u64;
+    let results: Vec<SliceBatchStatus> = request
+        .items
+        .into_iter()
+        .map(|item| match put_slice_payload(&state, item.track_address, item.spool, item.payload) {
+            Ok(bytes) => {
+                stored += bytes;
+                SliceBatchStatus::Ok
+            }
+            Err(error) => error.batch_status(),
+        })
+        .collect();
+
+    debug!(items = results.len(), payload_bytes = stored, "http put_slices done");
+    let body = wincode::serialize(&SliceBatchPutResponse { results })
+        .map_err(|error| RouteError::Internal(format!("serialize put slices response: {error}")))?;
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, BINARY_CONTENT)], body))
+}
+
+/// Serve slices for many tracks from one request. Items that would push the
+/// response past [`SLICE_BATCH_BYTES_LIMIT`] come back deferred, for the
+/// caller to fetch on their own.
+pub async fn get_slices<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    State(state): State<AppState<Db, Cluster, Blockchain>>,
+    MaybeStakedPeer(staked_peer): MaybeStakedPeer,
+    body: Bytes,
+) -> Result<impl IntoResponse, RouteError> {
+    if local_access_threshold(&state).0 > 0 && staked_peer.is_none() {
+        return Err(RouteError::Forbidden("staked peer required".into()));
+    }
+
+    let request: SliceBatchGetRequest = wincode::deserialize(&body)
+        .map_err(|error| RouteError::BadRequest(format!("slice batch: {error}")))?;
+    check_item_count(request.items.len())?;
+
+    let mut served = 0usize;
+    let mut results = Vec::with_capacity(request.items.len());
+    for item in request.items {
+        let result = match read_stored_slice(&state, item.track_address, item.spool) {
+            Ok(data) if served + data.len() > SLICE_BATCH_BYTES_LIMIT => SliceBatchGetResult {
+                status: SliceBatchStatus::Deferred,
+                data: Vec::new(),
+            },
+            Ok(data) => {
+                served += data.len();
+                SliceBatchGetResult {
+                    status: SliceBatchStatus::Ok,
+                    data,
+                }
+            }
+            Err(error) => SliceBatchGetResult {
+                status: error.batch_status(),
+                data: Vec::new(),
+            },
+        };
+        results.push(result);
+    }
+    state.context.metrics.add_downloaded(served as u64);
+
+    let body = wincode::serialize(&SliceBatchGetResponse { results })
+        .map_err(|error| RouteError::Internal(format!("serialize get slices response: {error}")))?;
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, BINARY_CONTENT)], body))
+}
+
+fn check_item_count(count: usize) -> Result<(), RouteError> {
+    if count > SLICE_BATCH_ITEMS_LIMIT {
+        return Err(RouteError::BadRequest(format!(
+            "slice batch carries {count} items, limit is {SLICE_BATCH_ITEMS_LIMIT}"
+        )));
+    }
+    Ok(())
+}
@@ -420,6 +420,7 @@ pub async fn list_objects<Db: Store, Cluster: Api, Blockchain: Rpc>(
             track_number: entry.track_number,
             kind: entry.kind,
             content_type: entry.content_type,
+            headers: entry.headers,
         });
     }
 
@@ -0,0 +1,306 @@
+use std::fmt::Display;
+
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+
+use rpc::Rpc;
+use store::Store;
+use tape_core::track::data::BlobData;
+use tape_core::track::stripe::{segment_hashes, segment_proof, segment_range, stripe_segment};
+use tape_core::types::SpoolIndex;
+use tape_crypto::address::Address;
+use tape_protocol::Api;
+use tape_protocol::api::{BINARY_CONTENT, ChallengeRequest, ChallengeResponse};
+use tape_store::ops::{SliceOps, SpoolOps, TrackDataOps, TrackOps};
+use tracing::trace;
+
+use crate::features::blacklist::refuses_object;
+use crate::features::http::auth::ActivePeer;
+use crate::features::http::error::RouteError;
+use crate::features::http::state::AppState;
+
+/// Answer a committee peer's proof-of-storage challenge for one stripe.
+///
+/// Only the slice segment holding the stripe goes back, with its path to the
+/// slice leaf the challenger already has from the blob encoding.
+pub async fn challenge<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    State(state): State<AppState<Db, Cluster, Blockchain>>,
+    active_peer: ActivePeer,
+    Path((track_id, spool_id)): Path<(String, SpoolIndex)>,
+    body: Bytes,
+) -> Result<impl IntoResponse, RouteError> {
+    let request: ChallengeRequest = wincode::deserialize(&body)
+        .map_err(|error| RouteError::BadRequest(format!("challenge request: {error}")))?;
+
+    trace!(
+        track_id = %track_id,
+        spool_id = %spool_id,
+        stripe = request.stripe,
+        challenger = %active_peer.node,
+        "http challenge start"
+    );
+
+    let epoch = state.context.state().epoch();
+    if request.epoch != epoch {
+        return Err(RouteError::BadRequest(format!(
+            "challenge epoch {} does not match local epoch {}",
+            request.epoch.0, epoch.0
+        )));
+    }
+
+    let track: Address = track_id
+        .parse()
+        .map_err(|error| RouteError::BadRequest(format!("invalid track id: {error}")))?;
+
+    state
+        .context
+        .store
+        .get_spool_state(spool_id)
+        .map_err(store_error)?
+        .ok_or(RouteError::NotResponsible)?;
+
+    let track_info = state
+        .context
+        .store
+        .get_track(track)
+        .map_err(store_error)?
+        .ok_or(RouteError::NotFound)?;
+    if !track_info.is_coded() {
+        return Err(RouteError::BadRequest("raw tracks cannot be challenged".into()));
+    }
+
+    if refuses_object(
+        state.context.store.as_ref(),
+        state.context.node_address(),
+        epoch,
+        track,
+        track_info.tape,
+    )
+    .map_err(store_error)?
+    {
+        return Err(RouteError::BlacklistedObject);
+    }
+
+    let track_data = state
+        .context
+        .store
+        .get_track_data(track)
+        .map_err(store_error)?
+        .ok_or(RouteError::NotFound)?;
+    let BlobData::Coded(blob) = track_data else {
+        return Err(RouteError::BadRequest("track data is not blob metadata".into()));
+    };
+
+    if !blob.is_segmented() {
+        return Err(RouteError::BadRequest("track has no segment leaves to challenge".into()));
+    }
+    if u64::from(request.stripe) >= blob.stripe_count.0 {
+        return Err(RouteError::BadRequest("challenged stripe out of range".into()));
+    }
+
+    let quarantined = state
+        .context
+        .store
+        .is_slice_quarantined(spool_id, track)
+        .map_err(store_error)?;
+    let stored = if quarantined {
+        None
+    } else {
+        state.context.store.get_slice(spool_id, track).map_err(store_error)?
+    };
+    let data = stored.ok_or_else(|| missing_slice(&state, spool_id, track))?;
+
+    let segment = stripe_segment(u64::from(request.stripe), blob.stripe_count);
+    let hashes = segment_hashes(&data, blob.stripe_count);
+    let segment_proof = segment_proof(&hashes, segment)
+        .ok_or_else(|| RouteError::Internal("challenge segment proof".into()))?;
+    let data = data[segment_range(data.len(), blob.stripe_count, segment)].to_vec();
+
+    let body = wincode::serialize(&ChallengeResponse { data, segment_proof })
+        .map_err(|error| RouteError::Internal(format!("challenge response: {error}")))?;
+
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, BINARY_CONTENT)],
+        body,
+    ))
+}
+
+/// The answer for a slice this node cannot serve: `PendingRepair` when it is
+/// already queued for repair or recovery, `NotFound` otherwise.
+fn missing_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    spool_id: SpoolIndex,
+    track: Address,
+) -> RouteError {
+    let store = &state.context.store;
+    match (
+        store.has_pending_repair(spool_id, track),
+        store.has_pending_recovery(spool_id, track),
+    ) {
+        (Ok(true), _) | (_, Ok(true)) => RouteError::PendingRepair,
+        (Err(error), _) | (_, Err(error)) => store_error(error),
+        (Ok(false), Ok(false)) => RouteError::NotFound,
+    }
+}
+
+fn store_error(error: impl Display) -> RouteError {
+    RouteError::Internal(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::{to_bytes, Bytes};
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    use tape_api::program::tapedrive::{snapshot_tape_pda, track_pda};
+    use tape_core::encoding::{EncodingProfile, LeafScheme};
+    use tape_core::erasure::{GROUP_SIZE, SLICE_TREE_HEIGHT};
+    use tape_core::prelude::{SpoolState, SpoolStatus};
+    use tape_core::spooler::GroupIndex;
+    use tape_core::system::{verify_challenge, ChallengeTarget};
+    use tape_core::track::blob::BlobEncoding;
+    use tape_core::track::stripe::slice_leaf;
+    use tape_core::track::types::{CompressedTrack, TrackKind, TrackState};
+    use tape_core::types::{ChunkNumber, EpochNumber, StorageUnits, StripeCount, TrackNumber};
+    use tape_crypto::merkle::root_from_leaf_hashes;
+    use tape_crypto::Hash;
+    use tape_protocol::api::{ChallengeRequest, ChallengeResponse};
+    use tape_snapshot::snapshot_chunk_key;
+    use tape_store::ops::{SliceOps, SpoolOps, TrackDataOps, TrackOps};
+
+    use super::*;
+    use crate::features::http::state::AppState;
+    use crate::harness::{NodeHarness, TestContext};
+
+    async fn test_context() -> TestContext {
+        NodeHarness::builder()
+            .nodes(25)
+            .no_prev_snapshot_tape()
+            .build()
+            .await
+            .expect("build harness")
+            .ctx_for(0)
+    }
+
+    // a coded track with this node's slice for one spool of group 2
+    fn seed_track(ctx: &TestContext) -> (Address, SpoolIndex, BlobEncoding, Vec<u8>) {
+        let group = GroupIndex(2);
+        let spool = group.spool_at(5);
+        let slice: Vec<u8> = (0..96u8).collect();
+
+        let mut leaves = [Hash::from([0x44; 32]); GROUP_SIZE];
+        leaves[spool.as_usize() % GROUP_SIZE] = slice_leaf(&slice, StripeCount(4));
+        let blob = BlobEncoding {
+            size: StorageUnits::from_bytes(1_537),
+            commitment: root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&leaves),
+            profile: EncodingProfile::basic_default().with_leaf_scheme(LeafScheme::Segmented),
+            stripe_size: StorageUnits::from_bytes(512),
+            stripe_count: StripeCount(4),
+            leaves,
+        };
+
+        let epoch = EpochNumber(5);
+        let (tape, _) = snapshot_tape_pda(epoch);
+        let track = track_pda(tape, TrackNumber(9)).0;
+        let info = CompressedTrack {
+            tape,
+            key: snapshot_chunk_key(epoch, group, ChunkNumber(0)),
+            track_number: TrackNumber(9),
+            kind: TrackKind::Coded as u64,
+            state: TrackState::Certified as u64,
+            size: blob.size,
+            group,
+            value_hash: blob.get_hash(),
+        };
+
+        ctx.store.put_track(track, info).expect("seed track");
+        ctx.store
+            .put_track_data(track, BlobData::Coded(blob.clone()))
+            .expect("seed track data");
+        ctx.store.put_slice(spool, track, slice.clone()).expect("seed slice");
+        ctx.store
+            .set_spool_state(spool, SpoolState::new(SpoolStatus::Active, EpochNumber(0)))
+            .expect("set spool state");
+
+        (track, spool, blob, slice)
+    }
+
+    async fn answer(
+        ctx: &TestContext,
+        track: Address,
+        spool: SpoolIndex,
+        stripe: u32,
+    ) -> Result<ChallengeResponse, RouteError> {
+        let request = ChallengeRequest { epoch: ctx.state().epoch(), stripe };
+        let body = wincode::serialize(&request).expect("serialize challenge request");
+
+        let response = challenge(
+            State(AppState {
+                context: ctx.clone(),
+            }),
+            ActivePeer {
+                node: ctx.node_address(),
+                tls_pubkey: ctx.tls_pubkey(),
+            },
+            Path((track.to_string(), spool)),
+            Bytes::from(body),
+        )
+        .await?
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read response body");
+        Ok(wincode::deserialize(&bytes).expect("decode challenge response"))
+    }
+
+    #[tokio::test]
+    async fn answers_with_stripe_segment() {
+        let ctx = test_context().await;
+        let (track, spool, blob, slice) = seed_track(&ctx);
+
+        for stripe in 0..4 {
+            let Ok(response) = answer(&ctx, track, spool, stripe).await else {
+                panic!("challenge failed for stripe {stripe}");
+            };
+            assert_eq!(response.data, slice[stripe as usize * 24..][..24]);
+
+            let target = ChallengeTarget { track, spool, stripe };
+            assert!(verify_challenge(&blob, &target, &response.data, &response.segment_proof));
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_stripe_out_of_range() {
+        let ctx = test_context().await;
+        let (track, spool, _, _) = seed_track(&ctx);
+
+        let result = answer(&ctx, track, spool, 4).await;
+        assert!(matches!(result, Err(RouteError::BadRequest(_))));
+
+        ctx.store.delete_slice(spool, track).unwrap();
+        let result = answer(&ctx, track, spool, 0).await;
+        assert!(matches!(result, Err(RouteError::NotFound)));
+    }
+
+    // a quarantined slice is not served, and its queued repair is reported
+    #[tokio::test]
+    async fn reports_pending_repair() {
+        let ctx = test_context().await;
+        let (track, spool, _, _) = seed_track(&ctx);
+
+        ctx.store.quarantine_slice(spool, track, EpochNumber(1)).unwrap();
+        let result = answer(&ctx, track, spool, 0).await;
+        assert!(matches!(result, Err(RouteError::NotFound)));
+
+        ctx.store.add_pending_repair(spool, track).unwrap();
+        let result = answer(&ctx, track, spool, 0).await;
+        assert!(matches!(result, Err(RouteError::PendingRepair)));
+    }
+}
@@ -1,4 +1,6 @@
+pub mod batch;
 pub mod catalog;
+pub mod challenge;
 pub mod inconsistency;
 pub mod repair;
 pub mod sign;
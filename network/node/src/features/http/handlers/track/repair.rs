@@ -74,6 +74,15 @@ pub async fn repair<Db: Store, Cluster: Api, Blockchain: Rpc>(
         return Err(RouteError::BadRequest("track data is not blob metadata".into()));
     };
 
+    if state
+        .context
+        .store
+        .is_slice_quarantined(request.helper_spool, track_key)
+        .map_err(store_error)?
+    {
+        return Err(RouteError::NotFound);
+    }
+
     let helper_slice = state
         .context
         .store
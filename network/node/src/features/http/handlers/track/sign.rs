@@ -10,7 +10,7 @@ use tape_core::cert::track::TrackWriteMessage;
 use tape_crypto::address::Address;
 use tape_protocol::Api;
 use tape_protocol::api::{BINARY_CONTENT, BlsSignResponse};
-use tape_store::ops::{SliceOps, TrackOps};
+use tape_store::ops::{SpoolOps, TrackOps};
 
 use crate::features::blacklist::refuses_object;
 use crate::features::http::error::RouteError;
@@ -66,7 +66,7 @@ pub async fn certify<Db: Store, Cluster: Api, Blockchain: Rpc>(
             state
                 .context
                 .store
-                .has_slice(spool_id, track_key)
+                .has_usable_slice(spool_id, track_key)
                 .unwrap_or(false)
         });
 
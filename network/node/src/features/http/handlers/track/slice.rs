@@ -1,19 +1,23 @@
 use std::fmt::Display;
 
-use axum::body::Bytes;
-use axum::extract::{Path, State};
+use axum::body::BodyDataStream;
+use axum::extract::{Path, Request, State};
 use axum::http::{header, StatusCode};
 use axum::response::IntoResponse;
+use axum::RequestExt;
 
 use rpc::Rpc;
 use store::Store;
 use tape_core::erasure::{GROUP_SIZE, SLICE_TREE_HEIGHT};
+use tape_core::track::blob::BlobEncoding;
 use tape_core::track::data::BlobData;
-use tape_core::types::SpoolIndex;
+use tape_core::types::{SpoolIndex, StorageUnits};
+use tape_crypto::Hash;
 use tape_crypto::address::Address;
-use tape_crypto::merkle::{hash_leaf, verify_proof};
+use tape_crypto::merkle::{hash_leaf, verify_leaf_proof};
 use tape_protocol::Api;
-use tape_protocol::api::{BINARY_CONTENT, SlicePayload};
+use tape_protocol::api::stream::{BodyReader, StreamError};
+use tape_protocol::api::{BINARY_CONTENT, SLICE_BYTES_LIMIT, SlicePayload};
 use tape_store::ops::{SliceOps, SpoolOps, TrackDataOps, TrackOps};
 use tracing::{debug, trace};
 
@@ -36,8 +40,25 @@ pub async fn get_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
     let track: Address = track_id
         .parse()
         .map_err(|error| RouteError::BadRequest(format!("invalid track id: {error}")))?;
-    let track_key = track;
+    let data = read_stored_slice(&state, track, spool_id)?;
+
+    state.context.metrics.add_downloaded(data.len() as u64);
 
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, BINARY_CONTENT)],
+        data,
+    ))
+}
+
+/// Read a stored slice for a spool this node keeps, refusing blacklisted
+/// objects and slices quarantined by scrub. Callers check the reader's
+/// access first.
+pub(crate) fn read_stored_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_key: Address,
+    spool_id: SpoolIndex,
+) -> Result<Vec<u8>, RouteError> {
     state
         .context
         .store
@@ -64,42 +85,144 @@ pub async fn get_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
         return Err(RouteError::BlacklistedObject);
     }
 
-    let data = state
+    if state
         .context
         .store
-        .get_slice(spool_id, track_key)
+        .is_slice_quarantined(spool_id, track_key)
         .map_err(store_error)?
-        .ok_or(RouteError::NotFound)?;
-
-    state.context.metrics.add_downloaded(data.len() as u64);
+    {
+        return Err(RouteError::NotFound);
+    }
 
-    Ok((
-        StatusCode::OK,
-        [(header::CONTENT_TYPE, BINARY_CONTENT)],
-        data,
-    ))
+    state
+        .context
+        .store
+        .get_slice(spool_id, track_key)
+        .map_err(store_error)?
+        .ok_or(RouteError::NotFound)
 }
 
+/// Accept a slice upload as it streams in. Checks that need no slice bytes run
+/// before the body is read, and the leaf, its proof and the segment hashes
+/// are checked against the commitment before any slice bytes. Each
+/// segment is then checked as it lands and staged in the store, so no more
+/// than one segment is held in memory.
 pub async fn put_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
     State(state): State<AppState<Db, Cluster, Blockchain>>,
     Path((track_id, spool_id)): Path<(String, SpoolIndex)>,
-    body: Bytes,
+    request: Request,
 ) -> Result<StatusCode, RouteError> {
-    trace!(
-        track_id = %track_id,
-        spool_id = %spool_id,
-        payload_bytes = body.len(),
-        "http put_slice start"
-    );
+    trace!(track_id = %track_id, spool_id = %spool_id, "http put_slice start");
 
     let track: Address = track_id
         .parse()
         .map_err(|error| RouteError::BadRequest(format!("invalid track id: {error}")))?;
 
     let track_key = track;
-    let payload: SlicePayload = wincode::deserialize(&body)
-        .map_err(|error| RouteError::BadRequest(format!("slice payload: {error}")))?;
 
+    let blob = admit_slice(&state, track_key, spool_id)?;
+
+    let mut body = BodyReader::new(request.into_limited_body().into_data_stream());
+    let leaf = body.read_hash().await.map_err(payload_error)?;
+    let merkle_proof = body.read_proof().await.map_err(payload_error)?;
+    let segments = body.read_segments().await.map_err(payload_error)?;
+    check_leaf(&blob, spool_id, leaf, &merkle_proof, &segments)?;
+
+    let len = body
+        .read_slice_len(slice_len_limit(&blob))
+        .await
+        .map_err(payload_error)?;
+
+    let store = &state.context.store;
+    let staged = stage_segments(&state, track_key, spool_id, &blob, &segments, len, body)
+        .await
+        .and_then(|()| {
+            store
+                .commit_slice_segments(spool_id, track_key, len)
+                .map_err(store_error)
+        });
+    if let Err(error) = staged {
+        if let Err(discard) = store.discard_slice_segments(spool_id, track_key) {
+            debug!(track_id = %track_id, spool_id = %spool_id, %discard, "discard staged segments");
+        }
+        return Err(error);
+    }
+    state.context.metrics.add_uploaded(len as u64);
+
+    debug!(
+        track_id = %track_id,
+        spool_id = %spool_id,
+        payload_bytes = len,
+        "http put_slice success"
+    );
+
+    Ok(StatusCode::OK)
+}
+
+/// Read the segments of a slice off an upload body, checking each against
+/// its hash and staging it before the next one is read.
+async fn stage_segments<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_key: Address,
+    spool_id: SpoolIndex,
+    blob: &BlobEncoding,
+    segments: &[Hash],
+    len: usize,
+    mut body: BodyReader<BodyDataStream>,
+) -> Result<(), RouteError> {
+    for (segment, expected) in segments.iter().enumerate() {
+        let range = blob.segment_range(len, segment as u64);
+        let data = body.read_bytes(range.len()).await.map_err(payload_error)?;
+        if hash_leaf(&data) != *expected {
+            return Err(RouteError::BadRequest(format!(
+                "slice segment {segment} does not match its leaf"
+            )));
+        }
+        state
+            .context
+            .store
+            .put_slice_segment(spool_id, track_key, segment as u16, data)
+            .map_err(store_error)?;
+    }
+    body.finish().await.map_err(payload_error)
+}
+
+/// Check a slice from a batch upload, then store it. Runs the same checks as
+/// a streamed upload, on a payload that is already in memory.
+pub(crate) fn put_slice_payload<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_key: Address,
+    spool_id: SpoolIndex,
+    payload: SlicePayload,
+) -> Result<u64, RouteError> {
+    let blob = admit_slice(state, track_key, spool_id)?;
+    let limit = slice_len_limit(&blob);
+    if payload.data.len() > limit {
+        return Err(payload_error(StreamError::TooLarge { limit: limit as u64 }));
+    }
+
+    check_leaf(&blob, spool_id, payload.leaf_hash, &payload.merkle_proof, &payload.segments)?;
+    if blob.segment_hashes(&payload.data) != payload.segments {
+        return Err(RouteError::BadRequest("slice does not match its segment leaves".into()));
+    }
+
+    let data_len = payload.data.len() as u64;
+    state
+        .context
+        .store
+        .put_slice(spool_id, track_key, payload.data)
+        .map_err(store_error)?;
+    state.context.metrics.add_uploaded(data_len);
+    Ok(data_len)
+}
+
+/// Checks an upload passes before any slice bytes are read: the track takes
+/// slices, is not blacklisted, and lands on an unlocked spool of this node.
+fn admit_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    state: &AppState<Db, Cluster, Blockchain>,
+    track_key: Address,
+    spool_id: SpoolIndex,
+) -> Result<BlobEncoding, RouteError> {
     let in_store = state
         .context
         .store
@@ -141,22 +264,6 @@ pub async fn put_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
         return Err(RouteError::BadRequest("track data is not blob metadata".into()));
     };
 
-    if hash_leaf(&payload.data) != payload.leaf_hash {
-        return Err(RouteError::BadRequest("leaf hash mismatch".into()));
-    }
-
-    let leaf_pos = spool_id.as_usize() % GROUP_SIZE;
-
-    if !verify_proof(
-        &payload.data,
-        &blob.commitment,
-        &payload.merkle_proof,
-        leaf_pos as u64,
-        SLICE_TREE_HEIGHT,
-    ) {
-        return Err(RouteError::BadRequest("invalid merkle proof".into()));
-    }
-
     let spool_state = state
         .context
         .store
@@ -168,17 +275,59 @@ pub async fn put_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
         return Err(RouteError::NotResponsible);
     }
 
-    let data_len = payload.data.len() as u64;
-    state
-        .context
-        .store
-        .put_slice(spool_id, track_key, payload.data)
-        .map_err(store_error)?;
-    state.context.metrics.add_uploaded(data_len);
+    Ok(blob)
+}
 
-    debug!(track_id = %track_id, spool_id = %spool_id, "http put_slice success");
+/// The leaf a spool's slice must hash to. Stored leaves only stand in for the
+/// commitment when they rebuild it.
+fn expected_leaf(blob: &BlobEncoding, spool_id: SpoolIndex) -> Option<Hash> {
+    let leaf_pos = spool_id.as_usize() % GROUP_SIZE;
+    (blob.commitment_root() == blob.commitment).then(|| blob.leaves[leaf_pos])
+}
 
-    Ok(StatusCode::OK)
+/// Check an upload's leaf against the blob commitment, and that its segment
+/// hashes rebuild that leaf under the blob's leaf scheme.
+fn check_leaf(
+    blob: &BlobEncoding,
+    spool_id: SpoolIndex,
+    leaf: Hash,
+    merkle_proof: &[Hash],
+    segments: &[Hash],
+) -> Result<(), RouteError> {
+    if expected_leaf(blob, spool_id).is_some_and(|expected| expected != leaf) {
+        return Err(RouteError::BadRequest("slice does not match its commitment leaf".into()));
+    }
+    if blob.leaf_from_segments(segments) != Some(leaf) {
+        return Err(RouteError::BadRequest("leaf hash mismatch".into()));
+    }
+
+    let leaf_pos = spool_id.as_usize() % GROUP_SIZE;
+    if merkle_proof.len() > SLICE_TREE_HEIGHT
+        || !verify_leaf_proof(
+            leaf,
+            &blob.commitment,
+            merkle_proof,
+            leaf_pos as u64,
+            SLICE_TREE_HEIGHT,
+        )
+    {
+        return Err(RouteError::BadRequest("invalid merkle proof".into()));
+    }
+    Ok(())
+}
+
+/// Longest slice a blob can have: one full stripe per stripe, as sync checks.
+fn slice_len_limit(blob: &BlobEncoding) -> usize {
+    blob.stripe_size
+        .checked_mul(StorageUnits::from_bytes(blob.stripe_count.as_u64()))
+        .filter(|max_len| *max_len > StorageUnits::zero())
+        .map_or(SLICE_BYTES_LIMIT, |max_len| {
+            usize::try_from(max_len.as_u64()).unwrap_or(SLICE_BYTES_LIMIT)
+        })
+}
+
+fn payload_error(error: StreamError) -> RouteError {
+    RouteError::BadRequest(format!("slice payload: {error}"))
 }
 
 fn store_error(error: impl Display) -> RouteError {
@@ -187,13 +336,16 @@ fn store_error(error: impl Display) -> RouteError {
 
 #[cfg(test)]
 mod tests {
-    use axum::body::to_bytes;
+    use std::convert::Infallible;
+    use std::sync::Arc;
+
+    use axum::body::{to_bytes, Body, Bytes};
     use axum::extract::{Path, State};
     use axum::http::StatusCode;
     use axum::response::IntoResponse;
 
     use tape_api::program::tapedrive::{snapshot_tape_pda, track_pda};
-    use tape_core::encoding::EncodingProfile;
+    use tape_core::encoding::{EncodingProfile, LeafScheme};
     use tape_core::erasure::{GROUP_SIZE, SLICE_TREE_HEIGHT};
     use tape_core::prelude::{SpoolState, SpoolStatus};
     use tape_snapshot::snapshot_chunk_key;
@@ -207,11 +359,18 @@ mod tests {
         ChunkNumber, EpochNumber, SlotNumber, StorageUnits, StripeCount, TrackNumber,
     };
     use tape_crypto::Hash;
-    use tape_crypto::merkle::root_from_leaf_hashes;
-    use tape_store::ops::{ObjectInfoOps, SpoolOps, TapeOps, TrackDataOps};
+    use tape_core::track::stripe::scheme_leaf;
+    use tape_crypto::merkle::{create_proof_from_leaf_hashes, root_from_leaf_hashes};
+    use tape_protocol::api::stream::slice_payload_frames;
+    use tape_protocol::api::{
+        SliceBatchGetItem, SliceBatchGetRequest, SliceBatchGetResponse, SliceBatchPutItem,
+        SliceBatchPutRequest, SliceBatchPutResponse, SliceBatchStatus, SlicePayload,
+    };
+    use tape_store::ops::{ObjectInfoOps, SliceOps, SpoolOps, TapeOps, TrackDataOps};
     use tape_store::types::{ObjectInfo, SystemObjectKind, TapeInfo};
 
     use super::*;
+    use crate::features::http::handlers::track::batch::{get_slices, put_slices};
     use crate::features::http::state::AppState;
     use crate::harness::{NodeHarness, TestContext};
 
@@ -226,6 +385,13 @@ mod tests {
     }
 
     fn seed_projected_snapshot_track(ctx: &TestContext) -> (Address, SpoolIndex, Vec<u8>) {
+        seed_snapshot_track_with(ctx, LeafScheme::Segmented)
+    }
+
+    fn seed_snapshot_track_with(
+        ctx: &TestContext,
+        scheme: LeafScheme,
+    ) -> (Address, SpoolIndex, Vec<u8>) {
         let epoch = EpochNumber(5);
 
         let group = GroupIndex(2);
@@ -233,12 +399,14 @@ mod tests {
         let owned_spool = group.spool_at(5);
         let slice_bytes = vec![0xAB; 96];
 
-        let leaves = [Hash::from([0x44; 32]); GROUP_SIZE];
+        let mut leaves = [Hash::from([0x44; 32]); GROUP_SIZE];
+        leaves[owned_spool.as_usize() % GROUP_SIZE] =
+            scheme_leaf(&slice_bytes, scheme, StripeCount(4));
         let commitment = root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&leaves);
         let blob = BlobEncoding {
             size: StorageUnits::from_bytes(1_537),
             commitment,
-            profile: EncodingProfile::basic_default(),
+            profile: EncodingProfile::basic_default().with_leaf_scheme(scheme),
             stripe_size: StorageUnits::from_bytes(512),
             stripe_count: StripeCount(4),
             leaves,
@@ -335,4 +503,201 @@ mod tests {
 
         assert_eq!(body.as_ref(), slice_bytes.as_slice());
     }
+
+    #[tokio::test]
+    async fn hides_quarantined_slice() {
+        let ctx = test_context().await;
+        let (track_address, owned_spool, _) = seed_projected_snapshot_track(&ctx);
+        ctx.store
+            .quarantine_slice(owned_spool, track_address, EpochNumber(1))
+            .expect("quarantine slice");
+
+        let state = AppState {
+            context: ctx.clone(),
+        };
+        let result = read_stored_slice(&state, track_address, owned_spool);
+
+        assert!(matches!(result, Err(RouteError::NotFound)));
+        assert!(ctx.store.has_slice(owned_spool, track_address).unwrap());
+    }
+
+    // the payload for the spool's leaf of a projected snapshot track
+    fn payload(
+        ctx: &TestContext,
+        track: Address,
+        spool: SpoolIndex,
+        data: Vec<u8>,
+    ) -> SlicePayload {
+        let Some(BlobData::Coded(blob)) = ctx.store.get_track_data(track).unwrap() else {
+            panic!("seeded track has blob metadata");
+        };
+        let leaf_pos = spool.as_usize() % GROUP_SIZE;
+        let proof =
+            create_proof_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&blob.leaves, leaf_pos).unwrap();
+        let segments = blob.segment_hashes(&data);
+        SlicePayload::new(data, blob.leaves[leaf_pos], proof, segments)
+    }
+
+    fn upload(payload: SlicePayload) -> Request {
+        // Split the slice the way a transport would hand it over.
+        let frames: Vec<Bytes> = slice_payload_frames(Arc::new(payload))
+            .concat()
+            .chunks(17)
+            .map(Bytes::copy_from_slice)
+            .collect();
+        let frames = frames.into_iter().map(Ok::<_, Infallible>);
+        let body = Body::from_stream(futures::stream::iter(frames));
+        axum::http::Request::builder().body(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn accepts_streamed_slice() {
+        let ctx = test_context().await;
+        let (track_address, owned_spool, slice_bytes) = seed_projected_snapshot_track(&ctx);
+        ctx.store.delete_slice(owned_spool, track_address).unwrap();
+
+        let request = upload(payload(&ctx, track_address, owned_spool, slice_bytes.clone()));
+        let status = put_slice(
+            State(AppState {
+                context: ctx.clone(),
+            }),
+            Path((track_address.to_string(), owned_spool)),
+            request,
+        )
+        .await
+        .expect("streamed upload");
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ctx.store.get_slice(owned_spool, track_address).unwrap(), Some(slice_bytes));
+    }
+
+    // slices of blobs certified under whole-slice leaves upload as one segment
+    #[tokio::test]
+    async fn accepts_whole_leaf_slice() {
+        let ctx = test_context().await;
+        let (track_address, owned_spool, slice_bytes) =
+            seed_snapshot_track_with(&ctx, LeafScheme::Whole);
+        ctx.store.delete_slice(owned_spool, track_address).unwrap();
+
+        let request = upload(payload(&ctx, track_address, owned_spool, slice_bytes.clone()));
+        let status = put_slice(
+            State(AppState {
+                context: ctx.clone(),
+            }),
+            Path((track_address.to_string(), owned_spool)),
+            request,
+        )
+        .await
+        .expect("whole-leaf upload");
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ctx.store.get_slice(owned_spool, track_address).unwrap(), Some(slice_bytes));
+    }
+
+    #[tokio::test]
+    async fn refuses_slice_off_its_leaf() {
+        let ctx = test_context().await;
+        let (track_address, owned_spool, slice_bytes) = seed_projected_snapshot_track(&ctx);
+        ctx.store.delete_slice(owned_spool, track_address).unwrap();
+
+        // The leaf and segment hashes are honest; the second segment is not.
+        let mut tampered = payload(&ctx, track_address, owned_spool, slice_bytes.clone());
+        tampered.data[40] ^= 0xFF;
+
+        let request = upload(tampered);
+        let result = put_slice(
+            State(AppState {
+                context: ctx.clone(),
+            }),
+            Path((track_address.to_string(), owned_spool)),
+            request,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(RouteError::BadRequest(message)) if message.contains("segment 1")
+        ));
+        assert_eq!(ctx.store.get_slice(owned_spool, track_address).unwrap(), None);
+
+        // A staged first segment is dropped, so a later upload starts clean.
+        let request = upload(payload(&ctx, track_address, owned_spool, slice_bytes.clone()));
+        put_slice(
+            State(AppState {
+                context: ctx.clone(),
+            }),
+            Path((track_address.to_string(), owned_spool)),
+            request,
+        )
+        .await
+        .expect("clean upload");
+        assert_eq!(ctx.store.get_slice(owned_spool, track_address).unwrap(), Some(slice_bytes));
+    }
+
+    #[tokio::test]
+    async fn batch_routes_answer_per_item() {
+        let ctx = test_context().await;
+        let (track_address, owned_spool, slice_bytes) = seed_projected_snapshot_track(&ctx);
+        ctx.store.delete_slice(owned_spool, track_address).unwrap();
+        let unknown = Address::new([0x5A; 32]);
+
+        let request = SliceBatchPutRequest {
+            items: vec![
+                SliceBatchPutItem {
+                    track_address,
+                    spool: owned_spool,
+                    payload: payload(&ctx, track_address, owned_spool, slice_bytes.clone()),
+                },
+                SliceBatchPutItem {
+                    track_address: unknown,
+                    spool: owned_spool,
+                    payload: payload(&ctx, track_address, owned_spool, slice_bytes.clone()),
+                },
+            ],
+        };
+        let response = put_slices(
+            State(AppState {
+                context: ctx.clone(),
+            }),
+            Bytes::from(wincode::serialize(&request).unwrap()),
+        )
+        .await
+        .expect("batch upload")
+        .into_response();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response: SliceBatchPutResponse = wincode::deserialize(&body).unwrap();
+        assert_eq!(response.results, vec![SliceBatchStatus::Ok, SliceBatchStatus::NotFound]);
+
+        let request = SliceBatchGetRequest {
+            items: vec![
+                SliceBatchGetItem {
+                    track_address: unknown,
+                    spool: owned_spool,
+                },
+                SliceBatchGetItem {
+                    track_address,
+                    spool: owned_spool,
+                },
+            ],
+        };
+        let response = get_slices(
+            State(AppState {
+                context: ctx.clone(),
+            }),
+            MaybeStakedPeer(Some(crate::features::http::auth::StakedPeer {
+                node: ctx.node_address(),
+                tls_pubkey: ctx.tls_pubkey(),
+                stake: TAPE(1),
+            })),
+            Bytes::from(wincode::serialize(&request).unwrap()),
+        )
+        .await
+        .expect("batch read")
+        .into_response();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response: SliceBatchGetResponse = wincode::deserialize(&body).unwrap();
+        assert_eq!(response.results[0].status, SliceBatchStatus::NotFound);
+        assert_eq!(response.results[1].status, SliceBatchStatus::Ok);
+        assert_eq!(response.results[1].data, slice_bytes);
+    }
 }
@@ -1,6 +1,7 @@
+use std::convert::Infallible;
 use std::fmt::Display;
 
-use axum::body::Bytes;
+use axum::body::{Body, Bytes};
 use axum::extract::State;
 use axum::http::{header, StatusCode};
 use axum::response::IntoResponse;
@@ -9,6 +10,7 @@ use rpc::Rpc;
 use store::Store;
 use tape_crypto::address::Address;
 use tape_protocol::Api;
+use tape_protocol::api::stream::sync_slices_frames;
 use tape_protocol::api::{
     BINARY_CONTENT, SyncSliceEntry, SyncSlicesRequest, SyncSlicesResponse, SyncTrackEntry,
     SyncTracksRequest, SyncTracksResponse,
@@ -54,6 +56,15 @@ pub async fn sync_slices<Db: Store, Cluster: Api, Blockchain: Rpc>(
     let current_epoch = state.context.state().epoch();
     let mut entries = Vec::with_capacity(slices.len());
     for (track_address, slice_data) in slices {
+        if state
+            .context
+            .store
+            .is_slice_quarantined(request.spool_index, track_address)
+            .map_err(store_error)?
+        {
+            continue;
+        }
+
         let Some(track) = state
             .context
             .store
@@ -86,13 +97,14 @@ pub async fn sync_slices<Db: Store, Cluster: Api, Blockchain: Rpc>(
         next_cursor,
     };
 
-    let bytes = wincode::serialize(&response)
-        .map_err(|error| RouteError::Internal(format!("serialize sync response: {error}")))?;
+    // Each slice goes out as its own frame, so the page is never copied into
+    // one serialized buffer next to the slices it holds.
+    let frames = sync_slices_frames(response).into_iter().map(Ok::<_, Infallible>);
 
     Ok((
         StatusCode::OK,
         [(header::CONTENT_TYPE, BINARY_CONTENT)],
-        bytes,
+        Body::from_stream(futures::stream::iter(frames)),
     ))
 }
 
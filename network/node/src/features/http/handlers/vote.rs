@@ -8,7 +8,10 @@ use axum::response::IntoResponse;
 use rpc::Rpc;
 use store::Store;
 use tape_core::bls::BlsPubkey;
-use tape_core::cert::{AssignmentVoteMessage, NodeEvictMessage, SnapshotSignMessage, eviction_vote_node};
+use tape_core::cert::{
+    AssignmentVoteMessage, ChallengeFailMessage, NodeEvictMessage, SnapshotSignMessage,
+    challenge_vote_node, eviction_vote_node,
+};
 use tape_core::system::{VoteCandidate, VoteKind};
 use tape_core::types::EpochNumber;
 use tape_crypto::Hash;
@@ -118,6 +121,18 @@ fn validate_candidate(
                 .to_bytes()
                 .to_vec())
         }
+        VoteKind::Challenge => {
+            if candidate.target_epoch != current_epoch {
+                return Err(RouteError::BadRequest(format!(
+                    "challenge vote target epoch {} does not match local epoch {}",
+                    candidate.target_epoch.0, current_epoch.0
+                )));
+            }
+            let node = challenge_vote_node(candidate.hash);
+            Ok(ChallengeFailMessage::new(current_epoch, protocol.nonce(), node)
+                .to_bytes()
+                .to_vec())
+        }
     }
 }
 
@@ -145,3 +160,93 @@ fn group_signer_pubkey(
         .spool_for_node_in_group(group, signer)
         .map(|(_, spool)| spool.bls_pubkey)
 }
+
+#[cfg(test)]
+mod tests {
+    use peer_memory::MemoryApi;
+    use rpc_litesvm::LiteSvmRpc;
+    use store_memory::MemoryStore;
+    use tape_core::cert::challenge_vote_hash;
+
+    use super::*;
+    use crate::features::vote::{group_peers_without, member_groups};
+    use crate::harness::NodeHarness;
+
+    async fn push(
+        state: &AppState<MemoryStore, MemoryApi, LiteSvmRpc>,
+        peer: ActivePeer,
+        request: &VoteRequest,
+    ) -> Result<StatusCode, RouteError> {
+        let body = Bytes::from(wincode::serialize(request).unwrap());
+        vote(State(state.clone()), peer, body)
+            .await
+            .map(|response| response.into_response().status())
+    }
+
+    // a group peer's signed challenge failure vote is stored once it checks out
+    #[tokio::test]
+    async fn accepts_challenge_vote() {
+        let harness = NodeHarness::builder()
+            .nodes(25)
+            .no_prev_snapshot_tape()
+            .build()
+            .await
+            .expect("build harness");
+        let ctx = harness.ctx_for(0);
+        let protocol = ctx.state();
+        let me = ctx.node_address();
+        let group = member_groups(&protocol.member_spools(me))[0];
+        let peers = group_peers_without(&protocol, group, me);
+        let (signer, target) = (peers[0], peers[1]);
+        let signer_ctx = (1..25)
+            .map(|index| harness.ctx_for(index))
+            .find(|peer| peer.node_address() == signer)
+            .expect("signer context");
+
+        let candidate = VoteCandidate {
+            kind: VoteKind::Challenge,
+            voting_epoch: protocol.epoch(),
+            target_epoch: protocol.epoch(),
+            hash: challenge_vote_hash(target),
+        };
+        let message = ChallengeFailMessage::new(protocol.epoch(), protocol.nonce(), target);
+        let request = VoteRequest {
+            signer,
+            candidate,
+            group,
+            signature: signer_ctx.bls_sign(&message.to_bytes()).unwrap(),
+        };
+        let state = AppState {
+            context: ctx.clone(),
+        };
+        let peer = ActivePeer {
+            node: signer,
+            tls_pubkey: signer_ctx.tls_pubkey(),
+        };
+
+        // A vote for another epoch is refused before its signature is read.
+        let stale = VoteRequest {
+            candidate: VoteCandidate {
+                target_epoch: EpochNumber(protocol.epoch().0 + 1),
+                ..candidate
+            },
+            ..request.clone()
+        };
+        assert!(matches!(push(&state, peer, &stale).await, Err(RouteError::BadRequest(_))));
+
+        // A signature over another target does not pass for this one.
+        let forged = VoteRequest {
+            candidate: VoteCandidate {
+                hash: challenge_vote_hash(me),
+                ..candidate
+            },
+            ..request.clone()
+        };
+        assert!(matches!(push(&state, peer, &forged).await, Err(RouteError::InvalidSignature)));
+
+        assert_eq!(push(&state, peer, &request).await.ok(), Some(StatusCode::OK));
+        let sigs = ctx.store.iter_vote_sigs(candidate, group).unwrap();
+        assert_eq!(sigs.len(), 1);
+        assert!(sigs[0].0 == signer && sigs[0].1 == request.signature);
+    }
+}
@@ -170,12 +170,31 @@ impl<Db: Store + 'static, Cluster: Api + 'static, Blockchain: Rpc + 'static>
                 api_routes::TRACK_SLICE_PATH,
                 get(handlers::track::slice::get_slice::<Db, Cluster, Blockchain>)
                     .put(handlers::track::slice::put_slice::<Db, Cluster, Blockchain>)
-                    .layer(slice_body_limit)
+                    .layer(slice_body_limit.clone())
                     .layer(from_fn_with_state(
                         state.clone(),
                         admission::slice_admission::<Db, Cluster, Blockchain>,
                     )),
             )
+            // Batched slice reads and writes, gated like the single-slice route.
+            .route(
+                api_routes::SLICE_BATCH_GET_PATH,
+                post(handlers::track::batch::get_slices::<Db, Cluster, Blockchain>)
+                    .layer(peer_body_limit.clone())
+                    .layer(from_fn_with_state(
+                        state.clone(),
+                        admission::metered_route_admission::<Db, Cluster, Blockchain>,
+                    )),
+            )
+            .route(
+                api_routes::SLICE_BATCH_PUT_PATH,
+                post(handlers::track::batch::put_slices::<Db, Cluster, Blockchain>)
+                    .layer(slice_body_limit.clone())
+                    .layer(from_fn_with_state(
+                        state.clone(),
+                        admission::direct_write_admission::<Db, Cluster, Blockchain>,
+                    )),
+            )
             // Staked-peer gated POSTs. Snapshot/system tape catalogs are also
             // listable for bootstrap catch-up.
             .route(
@@ -235,6 +254,15 @@ impl<Db: Store + 'static, Cluster: Api + 'static, Blockchain: Rpc + 'static>
                         admission::metered_route_admission::<Db, Cluster, Blockchain>,
                     )),
             )
+            .route(
+                api_routes::TRACK_SLICE_CHALLENGE_PATH,
+                post(handlers::track::challenge::challenge::<Db, Cluster, Blockchain>)
+                    .layer(peer_body_limit.clone())
+                    .layer(from_fn_with_state(
+                        state.clone(),
+                        admission::metered_route_admission::<Db, Cluster, Blockchain>,
+                    )),
+            )
             .route(
                 api_routes::VOTE_PATH,
                 post(handlers::vote::vote::<Db, Cluster, Blockchain>)
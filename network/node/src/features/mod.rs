@@ -2,10 +2,12 @@ pub mod assignment;
 pub mod blacklist;
 pub mod block;
 pub mod bootstrap;
+pub mod challenge;
 pub mod eviction;
 pub mod gc;
 pub mod http;
 pub mod lifecycle;
+pub mod renewal;
 pub mod replay;
 pub mod snapshot;
 pub mod spool;
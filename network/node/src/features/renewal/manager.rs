@@ -0,0 +1,232 @@
+//! Crank escrow-funded tape renewals.
+//!
+//! A tape with a renewal escrow is only renewed when someone submits
+//! `RenewTape` inside its renewal window. Committee nodes take that on, one
+//! designated node per tape and epoch, so a funded tape never runs out just
+//! because nobody called the crank.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rpc::Rpc;
+use store::Store;
+use tape_core::tape::{TapeFlags, in_renewal_window};
+use tape_core::types::EpochNumber;
+use tape_crypto::Address;
+use tape_crypto::hash::hashv;
+use tape_protocol::{Api, ProtocolState};
+use tape_store::TapeStore;
+use tape_store::ops::TapeOps;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, trace};
+
+use crate::chain::submit_renew_tape;
+use crate::context::NodeContext;
+use crate::core::chain_tx::{TxOutcome, submit_if_at_tip};
+use crate::core::error::NodeError;
+
+const RENEWAL_HEARTBEAT: Duration = Duration::from_secs(60);
+
+pub struct RenewalManager<Db: Store, Cluster: Api, Blockchain: Rpc> {
+    context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    cancel: CancellationToken,
+    // Epoch the set below belongs to; it resets when the epoch moves.
+    epoch: EpochNumber,
+    // Tapes already cranked, or found without an escrow, this epoch.
+    cranked: HashSet<Address>,
+}
+
+impl<Db, Cluster, Blockchain> RenewalManager<Db, Cluster, Blockchain>
+where
+    Db: Store + 'static,
+    Cluster: Api + 'static,
+    Blockchain: Rpc + 'static,
+{
+    pub fn new(
+        context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            context,
+            cancel,
+            epoch: EpochNumber::zero(),
+            cranked: HashSet::new(),
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), NodeError> {
+        let mut heartbeat = tokio::time::interval(RENEWAL_HEARTBEAT);
+
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => return Ok(()),
+                _ = heartbeat.tick() => {
+                    let cancel = self.cancel.clone();
+                    cancel.run_until_cancelled(self.try_progress()).await.unwrap_or(Ok(()))?;
+                }
+            }
+        }
+    }
+
+    /// Renew every escrowed tape this node is the cranker for this epoch.
+    ///
+    /// A short escrow is not an error on chain: the crank still lands and
+    /// logs `TapeEscrowLow`, so owners hear about it while the tape has
+    /// epochs left.
+    async fn try_progress(&mut self) -> Result<(), NodeError> {
+        // Local expiry is only trustworthy when our catalog is current.
+        if !self.context.is_at_tip() {
+            return Ok(());
+        }
+
+        let state = self.context.state();
+        let me = self.context.node_address();
+        if state.find_member(me).is_none() {
+            return Ok(());
+        }
+
+        if self.epoch != state.epoch() {
+            self.epoch = state.epoch();
+            self.cranked.clear();
+        }
+
+        for tape in due_tapes(self.context.store.as_ref(), &state, me)? {
+            if self.cranked.contains(&tape) {
+                continue;
+            }
+
+            // Tapes without an escrow have nothing to renew from.
+            if let Err(error) = self.context.rpc.get_escrow(&tape).await {
+                trace!(%tape, %error, "renewal: no escrow");
+                self.cranked.insert(tape);
+                continue;
+            }
+
+            let submit = submit_renew_tape(&self.context, tape);
+            let outcome = submit_if_at_tip(&self.context.ingest, "renew_tape", submit).await;
+            match outcome {
+                TxOutcome::Confirmed(txid) => {
+                    info!(%tape, epoch = self.epoch.0, %txid, "renewal: tape cranked");
+                }
+                // Left to next epoch's cranker.
+                TxOutcome::Rejected { kind, err } => {
+                    debug!(%tape, epoch = self.epoch.0, ?kind, %err, "renewal: crank rejected");
+                }
+                TxOutcome::SkippedStale => {
+                    debug!(epoch = self.epoch.0, "renewal: ingest stale, deferring");
+                    return Ok(());
+                }
+            }
+            self.cranked.insert(tape);
+        }
+
+        Ok(())
+    }
+}
+
+/// User tapes inside their renewal window that `me` cranks this epoch.
+pub fn due_tapes<Db: Store>(
+    store: &TapeStore<Db>,
+    state: &ProtocolState,
+    me: Address,
+) -> Result<Vec<Address>, NodeError> {
+    let tapes = store
+        .iter_all_tapes()
+        .map_err(|e| NodeError::Store(format!("iter_all_tapes: {e}")))?;
+
+    Ok(tapes
+        .into_iter()
+        .filter(|(_, info)| !TapeFlags::is_system(info.flags))
+        .filter(|(_, info)| in_renewal_window(state.epoch(), info.end_epoch))
+        .map(|(tape, _)| tape)
+        .filter(|tape| renewal_cranker(state, *tape) == Some(me))
+        .collect())
+}
+
+/// The committee member that cranks `tape` this epoch. The pick moves with
+/// the epoch, so a tape whose cranker misses its turn goes to another node
+/// while the window is still open.
+pub fn renewal_cranker(state: &ProtocolState, tape: Address) -> Option<Address> {
+    let committee = &state.current.committee;
+    if committee.is_empty() {
+        return None;
+    }
+
+    let digest = hashv(&[tape.as_ref(), &state.epoch().0.to_le_bytes()]);
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&digest.as_ref()[..8]);
+    let index = u64::from_le_bytes(word) % committee.len() as u64;
+
+    Some(committee[index as usize].node)
+}
+
+#[cfg(test)]
+mod tests {
+    use tape_core::tape::RENEWAL_WINDOW_EPOCHS;
+    use tape_core::types::{TapeNumber, TrackNumber};
+    use tape_store::types::TapeInfo;
+
+    use super::*;
+    use crate::harness::{NodeHarness, TestContext};
+
+    async fn test_context() -> TestContext {
+        NodeHarness::builder()
+            .nodes(25)
+            .no_prev_snapshot_tape()
+            .build()
+            .await
+            .expect("build harness")
+            .ctx_for(0)
+    }
+
+    fn tape_info(flags: u64, end_epoch: EpochNumber) -> TapeInfo {
+        TapeInfo {
+            id: TapeNumber(1),
+            flags,
+            end_epoch,
+            next_track_number: TrackNumber(0),
+        }
+    }
+
+    #[tokio::test]
+    async fn cranker_is_a_member() {
+        let ctx = test_context().await;
+        let state = ctx.state();
+        let tape = Address::new_unique();
+
+        let cranker = renewal_cranker(&state, tape).expect("cranker");
+        assert!(state.find_member(cranker).is_some());
+        assert_eq!(renewal_cranker(&state, tape), Some(cranker));
+        assert_eq!(renewal_cranker(&ProtocolState::default(), tape), None);
+    }
+
+    // only user tapes inside the window, and only those this node cranks
+    #[tokio::test]
+    async fn picks_due_tapes() {
+        let ctx = test_context().await;
+        let state = ctx.state();
+        let epoch = state.epoch();
+        let due = EpochNumber(epoch.0 + RENEWAL_WINDOW_EPOCHS);
+        let early = EpochNumber(epoch.0 + RENEWAL_WINDOW_EPOCHS + 1);
+
+        let mut expected = Vec::new();
+        for _ in 0..64 {
+            let tape = Address::new_unique();
+            ctx.store.put_tape(tape, tape_info(0, due)).unwrap();
+            if renewal_cranker(&state, tape) == Some(ctx.node_address()) {
+                expected.push(tape);
+            }
+            ctx.store.put_tape(Address::new_unique(), tape_info(0, early)).unwrap();
+            ctx.store.put_tape(Address::new_unique(), tape_info(0, epoch)).unwrap();
+            ctx.store
+                .put_tape(Address::new_unique(), tape_info(TapeFlags::SYSTEM, due))
+                .unwrap();
+        }
+
+        let mut found = due_tapes(ctx.store.as_ref(), &state, ctx.node_address()).unwrap();
+        found.sort();
+        expected.sort();
+        assert_eq!(found, expected);
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use rpc::Rpc;
 use store::Store;
@@ -16,7 +16,7 @@ use crate::config::recovery::RecoveryConfig;
 use crate::context::NodeContext;
 use crate::core::error::NodeError;
 use crate::features::spool::types::{Action, ScanResult, TaskDone, TaskResult};
-use crate::features::spool::{recover, repair, scan, sync};
+use crate::features::spool::{recover, repair, scan, scrub, sync};
 
 const SPOOL_MANAGER_HEARTBEAT: Duration = Duration::from_secs(1);
 const LOCKED_SPOOL_RETENTION_EPOCHS: u64 = 4;
@@ -27,6 +27,8 @@ pub struct SpoolManager<Db: Store, Cluster: Api, Blockchain: Rpc> {
     cancel: CancellationToken,
     workers: HashMap<SpoolIndex, CancellationToken>,
     join_set: JoinSet<TaskDone>,
+    // When each spool last started a scrub run, for spacing runs out.
+    last_scrub: HashMap<SpoolIndex, Instant>,
 }
 
 impl<Db: Store + 'static, Cluster: Api + 'static, Blockchain: Rpc + 'static>
@@ -46,6 +48,7 @@ impl<Db: Store + 'static, Cluster: Api + 'static, Blockchain: Rpc + 'static>
             cancel,
             workers, 
             join_set,
+            last_scrub: HashMap::new(),
         }
     }
 
@@ -164,6 +167,8 @@ impl<Db: Store + 'static, Cluster: Api + 'static, Blockchain: Rpc + 'static>
                     return Ok(Some(Action::Repair { spool, epoch }));
                 } else if has_recovery {
                     return Ok(Some(Action::Recover { spool, epoch }));
+                } else if self.scrub_due(spool) {
+                    return Ok(Some(Action::Scrub { spool, epoch }));
                 }
             }
         }
@@ -215,6 +220,9 @@ impl<Db: Store + 'static, Cluster: Api + 'static, Blockchain: Rpc + 'static>
             let token = self.cancel.child_token();
 
             self.workers.insert(spool, token.clone());
+            if matches!(action, Action::Scrub { .. }) {
+                self.last_scrub.insert(spool, Instant::now());
+            }
 
             info!(?action, epoch = epoch.0, "spool: spawning task");
 
@@ -233,6 +241,9 @@ impl<Db: Store + 'static, Cluster: Api + 'static, Blockchain: Rpc + 'static>
                         Action::Recover { spool, .. } => {
                             TaskResult::Recover(recover::run(ctx, &config, spool, &token).await)
                         }
+                        Action::Scrub { spool, .. } => {
+                            TaskResult::Scrub(scrub::run(ctx, &config, spool, &token).await)
+                        }
                     };
 
                     if token.is_cancelled() {
@@ -406,6 +417,18 @@ impl<Db: Store + 'static, Cluster: Api + 'static, Blockchain: Rpc + 'static>
         Ok(())
     }
 
+    /// Whether an Active spool is due for its next scrub run.
+    fn scrub_due(&self, spool: SpoolIndex) -> bool {
+        if self.config.scrub_batch == 0 {
+            return false;
+        }
+
+        let interval = Duration::from_secs(self.config.scrub_interval_secs);
+        self.last_scrub
+            .get(&spool)
+            .is_none_or(|started| started.elapsed() >= interval)
+    }
+
     /// Check if the spool manager already has a running task for the given spool index.
     fn is_running(&self, spool: SpoolIndex) -> bool {
         self.workers.contains_key(&spool)
@@ -489,6 +512,10 @@ fn transition_status<Db: Store>(
             reconcile(store, spool).map(Some)
         }
 
+        (Action::Scrub { .. }, TaskResult::Scrub(_)) => {
+            reconcile(store, spool).map(Some)
+        }
+
         _ => Ok(None),
     }
 }
@@ -541,6 +568,10 @@ fn reset_spool_state<Db: Store, Cluster: Api, Blockchain: Rpc>(
         .remove_spool_sync_cursor(spool)
         .map_err(|e| NodeError::Store(format!("remove_spool_sync_cursor({spool}): {e}")))?;
 
+    ctx.store
+        .remove_spool_scrub_cursor(spool)
+        .map_err(|e| NodeError::Store(format!("remove_spool_scrub_cursor({spool}): {e}")))?;
+
     Ok(())
 }
 
@@ -555,6 +586,12 @@ fn purge_locked_spool<Db: Store, Cluster: Api, Blockchain: Rpc>(
 
     reset_spool_state(ctx, spool)?;
 
+    // Quarantine markers outlive the lock so a locked spool never hands out
+    // slices that failed scrub; they go only once the bytes are gone.
+    ctx.store
+        .clear_all_quarantined_slices(spool)
+        .map_err(|e| NodeError::Store(format!("clear_all_quarantined_slices({spool}): {e}")))?;
+
     ctx.store
         .remove_spool_state(spool)
         .map_err(|e| NodeError::Store(format!("remove_spool_state({spool}): {e}")))?;
@@ -583,7 +620,7 @@ mod tests {
     use super::SpoolManager;
     use crate::config::recovery::RecoveryConfig;
     use crate::features::spool::types::{
-        Action, RepairResult, ScanResult, SyncResult, TaskDone, TaskResult,
+        Action, RepairResult, ScanResult, ScrubResult, SyncResult, TaskDone, TaskResult,
     };
     use crate::harness::{NodeHarness, TestContext};
 
@@ -851,4 +888,87 @@ mod tests {
         assert_eq!(state.status, SpoolStatus::Repair);
         assert!(manager.is_running(SPOOL));
     }
+
+    #[tokio::test]
+    async fn active_idle_scrubs_once_per_interval() {
+        let ctx = test_context().await;
+        ctx.store
+            .set_spool_state(SPOOL, SpoolState::new(SpoolStatus::Active, EPOCH))
+            .unwrap();
+
+        let mut manager = SpoolManager::new(
+            ctx.clone(),
+            RecoveryConfig::default(),
+            CancellationToken::new(),
+        );
+        assert_eq!(
+            manager.next_action(EPOCH).unwrap(),
+            Some(Action::Scrub { spool: SPOOL, epoch: EPOCH })
+        );
+
+        manager.try_spawn(EPOCH).unwrap();
+
+        // Scrubbing leaves the spool serving.
+        let state = ctx.store.get_spool_state(SPOOL).unwrap().unwrap();
+        assert_eq!(state.status, SpoolStatus::Active);
+        assert!(manager.is_running(SPOOL));
+
+        manager
+            .handle_done(
+                TaskDone::Done(
+                    Action::Scrub { spool: SPOOL, epoch: EPOCH },
+                    TaskResult::Scrub(ScrubResult::Done { scrubbed: 0, corrupt: 0 }),
+                ),
+                EPOCH,
+            )
+            .unwrap();
+
+        // The next pass waits out the interval.
+        assert_eq!(manager.next_action(EPOCH).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn scrub_disabled_with_zero_batch() {
+        let ctx = test_context().await;
+        ctx.store
+            .set_spool_state(SPOOL, SpoolState::new(SpoolStatus::Active, EPOCH))
+            .unwrap();
+
+        let config = RecoveryConfig {
+            scrub_batch: 0,
+            ..RecoveryConfig::default()
+        };
+        let manager = SpoolManager::new(ctx, config, CancellationToken::new());
+        assert_eq!(manager.next_action(EPOCH).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn scrub_corruption_moves_to_repair() {
+        let ctx = test_context().await;
+        ctx.store
+            .set_spool_state(SPOOL, SpoolState::new(SpoolStatus::Active, EPOCH))
+            .unwrap();
+        ctx.store
+            .add_pending_repair(SPOOL, Address::from([1; 32]))
+            .unwrap();
+
+        let mut manager = SpoolManager::new(
+            ctx.clone(),
+            RecoveryConfig::default(),
+            CancellationToken::new(),
+        );
+
+        manager
+            .handle_done(
+                TaskDone::Done(
+                    Action::Scrub { spool: SPOOL, epoch: EPOCH },
+                    TaskResult::Scrub(ScrubResult::Done { scrubbed: 1, corrupt: 1 }),
+                ),
+                EPOCH,
+            )
+            .unwrap();
+
+        let state = ctx.store.get_spool_state(SPOOL).unwrap().unwrap();
+        assert_eq!(state.status, SpoolStatus::Repair);
+    }
 }
@@ -2,5 +2,6 @@ pub mod manager;
 pub mod recover;
 pub mod repair;
 pub mod scan;
+pub mod scrub;
 pub mod sync;
 pub mod types;
@@ -37,7 +37,8 @@ const RECOVER_FETCH_CONCURRENCY: usize = 4;
 //
 //    For each track_address:
 //      a. Check cancellation.
-//      b. Skip if slice already present (has_slice). Remove from queue.
+//      b. Skip if a usable slice is already present (has_usable_slice).
+//         Remove from queue.
 //      c. Load track_info. If missing, remove from queue, continue.
 //
 //      d. Fetch k full slices (per-track: per-helper fallback across both peer maps):
@@ -121,10 +122,10 @@ pub async fn run<Db: Store, Cluster: Api + 'static, Blockchain: Rpc>(
                 break;
             }
 
-            let has_slice = match ctx.store.has_slice(spool, track_addr) {
+            let has_slice = match ctx.store.has_usable_slice(spool, track_addr) {
                 Ok(has_slice) => has_slice,
                 Err(error) => {
-                    warn!(spool = %spool, track = %track_addr, %error, "has_slice failed");
+                    warn!(spool = %spool, track = %track_addr, %error, "has_usable_slice failed");
                     continue;
                 }
             };
@@ -225,6 +226,7 @@ pub async fn run<Db: Store, Cluster: Api + 'static, Blockchain: Rpc>(
 
             ctx.metrics.add_recover_persisted(recovered_len);
             let _ = ctx.store.remove_pending_recovery(spool, track_addr);
+            let _ = ctx.store.release_quarantined_slice(spool, track_addr);
 
             made_progress = true;
         }
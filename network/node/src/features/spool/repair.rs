@@ -12,6 +12,7 @@ use tape_core::track::data::BlobData;
 use tape_core::types::{StorageUnits, StripeCount};
 use tape_crypto::address::Address;
 use tape_protocol::Api;
+use tape_protocol::api::ApiError;
 use tape_protocol::api::ops::RepairReq;
 use tape_protocol::api::types::StripeSubChunkRequest;
 use tape_retry::RetryConfig;
@@ -46,7 +47,8 @@ const REPAIR_FETCH_CONCURRENCY: usize = 4;
 //
 //    For each track_address:
 //      a. Check cancellation.
-//      b. Skip if slice already present (has_slice). Remove from pending_repairs.
+//      b. Skip if a usable slice is already present (has_usable_slice), i.e.
+//         stored and not quarantined. Remove from pending_repairs.
 //      c. Load track_info. If missing, remove from pending_repairs, continue.
 //      d. Validate encoding is Clay and stripe params are non-zero.
 //         If not → escalate, continue.
@@ -132,15 +134,16 @@ pub async fn run<Db: Store, Cluster: Api + 'static, Blockchain: Rpc>(
                 break;
             }
 
-            let has_slice = match ctx.store.has_slice(spool, track) {
+            let has_slice = match ctx.store.has_usable_slice(spool, track) {
                 Ok(has_slice) => has_slice,
                 Err(error) => {
-                    warn!(spool = %spool, track = %track, %error, "has_slice failed");
+                    warn!(spool = %spool, track = %track, %error, "has_usable_slice failed");
                     continue;
                 }
             };
 
-            // If slice already exists, just remove from pending_repairs and skip.
+            // If a usable slice already exists, just remove from pending_repairs
+            // and skip. Quarantined bytes stay queued until a repair replaces them.
             if has_slice {
                 let _ = ctx.store.remove_pending_repair(spool, track);
                 info!(spool = %spool, track = %track, "slice already present, skipping");
@@ -204,6 +207,7 @@ pub async fn run<Db: Store, Cluster: Api + 'static, Blockchain: Rpc>(
                     }
                     ctx.metrics.add_repair_persisted(repaired_len);
                     let _ = ctx.store.remove_pending_repair(spool, track);
+                    let _ = ctx.store.release_quarantined_slice(spool, track);
                 }
                 Err(()) => {
                     info!(spool = %spool, track = %track, "repair failed, escalating to recovery");
@@ -354,22 +358,40 @@ async fn fetch_one_helper<Cluster: Api + 'static>(
     token: CancellationToken,
     candidates: [Option<Address>; 2],
     req: RepairReq,
+    sub_chunk_size: u64,
     slice_idx: SliceIndex,
 ) -> Result<(SliceIndex, Vec<u8>), SliceIndex> {
+    let sub_chunks: usize = req.stripes.iter().map(|stripe| stripe.sub_chunks.len()).sum();
+    let answer_len = sub_chunks.saturating_mul(sub_chunk_size as usize);
     for node_id in candidates.into_iter().flatten() {
-        if let Ok(res) = call_peer(
+        if let Ok(data) = call_peer(
             &peer_manager,
             RetryConfig::three(),
             node_id,
             Some(&token),
-            || api.repair(node_id, &req),
+            || read_helper_answer(api.as_ref(), node_id, &req, answer_len),
         ).await {
-            return Ok((slice_idx, res.data));
+            return Ok((slice_idx, data));
         }
     }
     Err(slice_idx)
 }
 
+/// Read a helper's answer as it streams in. The plan fixes its length, so an
+/// answer is read straight into a buffer of that size and anything longer is
+/// refused.
+async fn read_helper_answer<Cluster: Api>(
+    api: &Cluster,
+    node: Address,
+    req: &RepairReq,
+    len: usize,
+) -> Result<Vec<u8>, ApiError> {
+    let mut body = api.repair_body(node, req).await?;
+    let data = body.read_bytes(len).await?;
+    body.finish().await?;
+    Ok(data)
+}
+
 /// Fetch sub-chunk data from all helpers in the plan using bounded concurrency.
 ///
 /// For each helper, tries the previous peer map first, then the current.
@@ -410,6 +432,7 @@ async fn fetch_helpers<Db: Store, Cluster: Api + 'static, Blockchain: Rpc>(
                 token.clone(),
                 candidates,
                 req,
+                plan.sub_chunk_size,
                 slice_idx,
             )
             .in_current_span(),
@@ -442,6 +465,7 @@ async fn fetch_helpers<Db: Store, Cluster: Api + 'static, Blockchain: Rpc>(
                             token.clone(),
                             candidates,
                             next_req,
+                            plan.sub_chunk_size,
                             next_idx,
                         )
                         .in_current_span(),
@@ -611,6 +635,7 @@ mod tests {
     fn clay_blob(size: u64, slices: &[Vec<u8>]) -> BlobEncoding {
         let metadata = SliceMetadata::from_slice(&slices[0]).unwrap();
         let stripe_size = metadata.stripe_size() as u64;
+        let stripe_count = StripeCount(size.div_ceil(stripe_size));
         let leaves = core::array::from_fn(|index| hash_leaf(&slices[index]));
         let commitment = root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&leaves);
         BlobEncoding {
@@ -618,7 +643,7 @@ mod tests {
             commitment,
             profile: EncodingProfile::clay_default(),
             stripe_size: StorageUnits::from_bytes(stripe_size),
-            stripe_count: StripeCount(size.div_ceil(stripe_size)),
+            stripe_count,
             leaves,
         }
     }
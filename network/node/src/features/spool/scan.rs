@@ -25,7 +25,8 @@ use crate::features::spool::types::ScanResult;
 //    a. Check cancellation.
 //    b. For each (track_address, track_info) in the batch:
 //       - Skip if track's spool group doesn't include this spool.
-//       - Check if we have the slice locally via has_slice.
+//       - Check if we have a usable slice locally via has_usable_slice, so
+//         a quarantined slice whose repair was dropped is queued again.
 //       - If missing → add_pending_repair(spool, track_address).
 //         Increment gap counter.
 //    c. Advance cursor to last track in the batch.
@@ -97,8 +98,8 @@ pub async fn run<Db: Store, Cluster: Api, Blockchain: Rpc>(
                 }
             }
 
-            // Check if slice exists locally.
-            let has_slice = match ctx.store.has_slice(spool, *track_addr) {
+            // Check if a usable slice exists locally.
+            let has_slice = match ctx.store.has_usable_slice(spool, *track_addr) {
                 Ok(has_slice) => has_slice,
                 Err(error) => {
                     warn!(spool = %spool, track = %track_addr, %error, "scan has_slice failed");
@@ -0,0 +1,491 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rpc::Rpc;
+use store::Store;
+use tape_core::spooler::GroupIndex;
+use tape_core::track::data::BlobData;
+use tape_core::types::{EpochNumber, SpoolIndex};
+use tape_crypto::address::Address;
+use tape_protocol::Api;
+use tape_store::ops::{ObjectInfoOps, SliceOps, SpoolOps, TrackDataOps, TrackOps};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::config::recovery::RecoveryConfig;
+use crate::context::NodeContext;
+use crate::features::spool::sync::verify_slice;
+use crate::features::spool::types::ScrubResult;
+
+// Purpose: Catch bit-rot in stored slices before a reader trips over it.
+//          Re-hashes slice bytes against the track's blob leaves and hands
+//          mismatches to the Repair task. Scrub never deletes bytes: a
+//          failed slice is quarantined in place and stops being served until
+//          a repair or recovery overwrites it with verified bytes.
+//
+// Scrub runs on Active spools only, one bounded batch per run, and the
+// spool manager spaces runs out by `scrub_interval_secs`. Progress is kept
+// in a persisted cursor, so a pass spans many runs and survives restarts.
+//
+// Algorithm:
+// 1. Load the scrub cursor (last track scrubbed in the current pass).
+// 2. Up to scrub_batch times:
+//    a. Check cancellation.
+//    b. Read the next slice after the cursor. If none, the pass is complete.
+//    c. If scrub_quarantine_limit slices are already quarantined, stop
+//       without advancing the cursor, so a verifier bug cannot condemn a
+//       whole spool. The pass resumes once repairs drain the backlog.
+//    d. Skip slices that are already quarantined, slices for tracks that
+//       are missing, raw, outside this spool's group, or not yet certified
+//       (repair only rebuilds certified tracks), and slices whose stored
+//       blob is not the one the track commits to.
+//    e. Verify the bytes against the blob leaf for our position.
+//    f. On mismatch → quarantine: record it and add_pending_repair. The
+//       bytes stay on disk. Increment corrupt.
+//    g. Advance the cursor and throttle to scrub_bytes_per_sec.
+// 3. On a complete pass, clear the cursor so the next run starts over.
+// 4. Return Done { scrubbed, corrupt }.
+//
+// The manager reconciles the spool afterwards, so any quarantined slice
+// moves the spool into Repair.
+
+pub async fn run<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    ctx: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    config: &RecoveryConfig,
+    spool: SpoolIndex,
+    cancel: &CancellationToken,
+) -> ScrubResult {
+    let mut cursor = match ctx.store.get_spool_scrub_cursor(spool) {
+        Ok(cursor) => cursor,
+        Err(error) => {
+            warn!(spool = %spool, %error, "scrub get_spool_scrub_cursor failed");
+            return ScrubResult::Done { scrubbed: 0, corrupt: 0 };
+        }
+    };
+
+    let group = GroupIndex::containing(spool);
+    let epoch = ctx.state().epoch();
+    let started = Instant::now();
+
+    let mut scrubbed = 0usize;
+    let mut corrupt = 0usize;
+    let mut read_bytes = 0u64;
+    let mut pass_complete = false;
+
+    for _ in 0..config.scrub_batch {
+        if cancel.is_cancelled() {
+            break;
+        }
+
+        let next = match ctx.store.iter_slices_by_spool_from(spool, cursor, 1) {
+            Ok(mut page) => page.pop(),
+            Err(error) => {
+                warn!(spool = %spool, %error, "scrub iter_slices_by_spool_from failed");
+                break;
+            }
+        };
+
+        let Some((track, data)) = next else {
+            pass_complete = true;
+            break;
+        };
+
+        if quarantine_full(ctx.as_ref(), config, spool) {
+            break;
+        }
+
+        cursor = Some(track);
+        read_bytes += data.len() as u64;
+
+        match check_slice(ctx.as_ref(), spool, group, track, &data) {
+            Some(true) => {
+                ctx.metrics.add_scrub_verified(data.len() as u64);
+                scrubbed += 1;
+            }
+            Some(false) => {
+                ctx.metrics.add_scrub_corrupt(data.len() as u64);
+                if quarantine(ctx.as_ref(), spool, track, epoch) {
+                    corrupt += 1;
+                }
+                scrubbed += 1;
+            }
+            None => {}
+        }
+
+        throttle(config.scrub_bytes_per_sec, started, read_bytes, cancel).await;
+    }
+
+    let persisted = match (pass_complete, cursor) {
+        (true, _) => ctx.store.remove_spool_scrub_cursor(spool),
+        (false, Some(track)) => ctx.store.set_spool_scrub_cursor(spool, track),
+        (false, None) => Ok(()),
+    };
+    if let Err(error) = persisted {
+        warn!(spool = %spool, %error, "scrub cursor update failed");
+    }
+
+    if pass_complete {
+        ctx.metrics.inc_scrub_passes();
+    }
+
+    ScrubResult::Done { scrubbed, corrupt }
+}
+
+/// Whether the spool's quarantine backlog has reached the configured cap.
+/// An unreadable count is treated as full, so scrub errs on keeping bytes.
+fn quarantine_full<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    ctx: &NodeContext<Db, Cluster, Blockchain>,
+    config: &RecoveryConfig,
+    spool: SpoolIndex,
+) -> bool {
+    match ctx.store.count_quarantined_slices(spool) {
+        Ok(count) if count < config.scrub_quarantine_limit => false,
+        Ok(count) => {
+            warn!(spool = %spool, count, "scrub: quarantine limit reached, pausing pass");
+            true
+        }
+        Err(error) => {
+            warn!(spool = %spool, %error, "scrub count_quarantined_slices failed");
+            true
+        }
+    }
+}
+
+/// Verify one stored slice. `None` means the slice is out of scope for
+/// scrubbing; `Some(valid)` carries the verdict.
+fn check_slice<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    ctx: &NodeContext<Db, Cluster, Blockchain>,
+    spool: SpoolIndex,
+    group: GroupIndex,
+    track: Address,
+    data: &[u8],
+) -> Option<bool> {
+    match ctx.store.is_slice_quarantined(spool, track) {
+        Ok(false) => {}
+        Ok(true) => return None,
+        Err(error) => {
+            warn!(spool = %spool, track = %track, %error, "scrub is_slice_quarantined failed");
+            return None;
+        }
+    }
+
+    let track_info = match ctx.store.get_track(track) {
+        Ok(Some(info)) => info,
+        Ok(None) => return None,
+        Err(error) => {
+            warn!(spool = %spool, track = %track, %error, "scrub get_track failed");
+            return None;
+        }
+    };
+
+    if track_info.group != group || !track_info.is_coded() {
+        return None;
+    }
+
+    match ctx.store.get_object_info(track) {
+        Ok(Some(info)) if info.is_certified() => {}
+        Ok(_) => return None,
+        Err(error) => {
+            warn!(spool = %spool, track = %track, %error, "scrub get_object_info failed");
+            return None;
+        }
+    }
+
+    let track_data = match ctx.store.get_track_data(track) {
+        Ok(Some(BlobData::Coded(blob))) => blob,
+        Ok(_) => return None,
+        Err(error) => {
+            warn!(spool = %spool, track = %track, %error, "scrub get_track_data failed");
+            return None;
+        }
+    };
+
+    // Leaves from a blob the track does not commit to prove nothing about the
+    // slice, and quarantining on them would drop bytes that may be intact.
+    if track_info.value_hash != track_data.get_hash() {
+        warn!(spool = %spool, track = %track, "scrub: stored blob does not match the track");
+        return None;
+    }
+
+    Some(verify_slice(spool, &track_info, &track_data, data))
+}
+
+/// Mark the slice quarantined and queue it for repair. The bytes stay in
+/// place: a repair overwrites them once it has verified replacements, so a
+/// false verdict costs a repair instead of data.
+fn quarantine<Db: Store, Cluster: Api, Blockchain: Rpc>(
+    ctx: &NodeContext<Db, Cluster, Blockchain>,
+    spool: SpoolIndex,
+    track: Address,
+    epoch: EpochNumber,
+) -> bool {
+    info!(spool = %spool, track = %track, "scrub: slice failed verification, quarantining");
+
+    if let Err(error) = ctx.store.quarantine_slice(spool, track, epoch) {
+        warn!(spool = %spool, track = %track, %error, "scrub quarantine_slice failed");
+        return false;
+    }
+
+    if let Err(error) = ctx.store.add_pending_repair(spool, track) {
+        warn!(spool = %spool, track = %track, %error, "scrub add_pending_repair failed");
+        return false;
+    }
+
+    true
+}
+
+/// Sleep until the bytes read so far fit inside the configured read rate.
+async fn throttle(
+    bytes_per_sec: u64,
+    started: Instant,
+    read_bytes: u64,
+    cancel: &CancellationToken,
+) {
+    if bytes_per_sec == 0 {
+        return;
+    }
+
+    let due = Duration::from_secs_f64(read_bytes as f64 / bytes_per_sec as f64);
+    let Some(wait) = due.checked_sub(started.elapsed()) else {
+        return;
+    };
+
+    tokio::select! {
+        _ = tokio::time::sleep(wait) => {}
+        _ = cancel.cancelled() => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tape_core::encoding::EncodingProfile;
+    use tape_core::erasure::{GROUP_SIZE, SLICE_TREE_HEIGHT};
+    use tape_core::track::blob::BlobEncoding;
+    use tape_core::track::types::{CompressedTrack, TrackKind, TrackState};
+    use tape_core::types::{EpochNumber, SlotNumber, StorageUnits, StripeCount, TrackNumber};
+    use tape_crypto::Hash;
+    use tape_crypto::merkle::{hash_leaf, root_from_leaf_hashes};
+    use tape_store::types::ObjectInfo;
+
+    use crate::harness::{NodeHarness, TestContext};
+
+    const SPOOL: SpoolIndex = SpoolIndex(5);
+
+    async fn test_context() -> TestContext {
+        NodeHarness::builder()
+            .nodes(25)
+            .no_prev_snapshot_tape()
+            .build()
+            .await
+            .expect("build harness")
+            .ctx_for(SPOOL.as_usize())
+    }
+
+    fn addr(n: u8) -> Address {
+        Address::from([n; 32])
+    }
+
+    fn slice_for(n: u8) -> Vec<u8> {
+        vec![n; 64]
+    }
+
+    // Seed a certified coded track whose leaf for SPOOL commits to `slice`.
+    fn seed_track(ctx: &TestContext, track: Address, slice: &[u8]) {
+        let group = GroupIndex::containing(SPOOL);
+        let position = group.position_of(SPOOL).unwrap();
+
+        let mut leaves = [hash_leaf(&[]); GROUP_SIZE];
+        leaves[position] = hash_leaf(slice);
+
+        let blob = BlobEncoding {
+            size: StorageUnits::from_bytes(1024),
+            commitment: root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&leaves),
+            profile: EncodingProfile::clay_default(),
+            stripe_size: StorageUnits::from_bytes(64),
+            stripe_count: StripeCount(1),
+            leaves,
+        };
+
+        ctx.store
+            .put_track(
+                track,
+                CompressedTrack {
+                    tape: Address::from([0; 32]),
+                    key: Hash::new_unique(),
+                    track_number: TrackNumber(0),
+                    kind: TrackKind::Coded as u64,
+                    state: TrackState::Certified as u64,
+                    size: StorageUnits::from_bytes(1024),
+                    group,
+                    value_hash: blob.get_hash(),
+                },
+            )
+            .unwrap();
+        ctx.store.put_track_data(track, BlobData::Coded(blob)).unwrap();
+        ctx.store
+            .put_object_info(
+                track,
+                ObjectInfo::Valid {
+                    track_address: track,
+                    registered_epoch: EpochNumber(1),
+                    certified_epoch: Some(EpochNumber(2)),
+                    slot: SlotNumber(10),
+                },
+            )
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn verifies_intact_slices() {
+        let ctx = test_context().await;
+        let a = addr(1);
+
+        seed_track(&ctx, a, &slice_for(1));
+        ctx.store.put_slice(SPOOL, a, slice_for(1)).unwrap();
+
+        let result = run(ctx.clone(), &RecoveryConfig::default(), SPOOL, &CancellationToken::new()).await;
+        assert_eq!(result, ScrubResult::Done { scrubbed: 1, corrupt: 0 });
+
+        assert!(ctx.store.has_slice(SPOOL, a).unwrap());
+        assert!(!ctx.store.has_pending_repair(SPOOL, a).unwrap());
+        // Whole spool covered: the cursor resets for the next pass.
+        assert!(ctx.store.get_spool_scrub_cursor(SPOOL).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn quarantines_corrupt_slice() {
+        let ctx = test_context().await;
+        let a = addr(1);
+
+        seed_track(&ctx, a, &slice_for(1));
+        let mut rotten = slice_for(1);
+        rotten[7] ^= 0x40;
+        ctx.store.put_slice(SPOOL, a, rotten).unwrap();
+
+        let result = run(ctx.clone(), &RecoveryConfig::default(), SPOOL, &CancellationToken::new()).await;
+        assert_eq!(result, ScrubResult::Done { scrubbed: 1, corrupt: 1 });
+
+        // Kept in place for the repair to overwrite, but no longer usable.
+        assert!(ctx.store.has_slice(SPOOL, a).unwrap());
+        assert!(!ctx.store.has_usable_slice(SPOOL, a).unwrap());
+        assert!(ctx.store.has_pending_repair(SPOOL, a).unwrap());
+        assert!(ctx.store.is_slice_quarantined(SPOOL, a).unwrap());
+    }
+
+    #[tokio::test]
+    async fn skips_quarantined_slice() {
+        let ctx = test_context().await;
+        let a = addr(1);
+
+        seed_track(&ctx, a, &slice_for(1));
+        ctx.store.put_slice(SPOOL, a, vec![0xFF; 64]).unwrap();
+        ctx.store.quarantine_slice(SPOOL, a, EpochNumber(1)).unwrap();
+
+        let result = run(ctx.clone(), &RecoveryConfig::default(), SPOOL, &CancellationToken::new()).await;
+        assert_eq!(result, ScrubResult::Done { scrubbed: 0, corrupt: 0 });
+        assert_eq!(ctx.store.count_quarantined_slices(SPOOL).unwrap(), 1);
+    }
+
+    // a verifier that rejects everything stops at the cap instead of
+    // condemning the whole spool
+    #[tokio::test]
+    async fn pauses_at_quarantine_limit() {
+        let ctx = test_context().await;
+        let a = addr(1);
+        let b = addr(2);
+
+        for track in [a, b] {
+            seed_track(&ctx, track, &slice_for(1));
+            ctx.store.put_slice(SPOOL, track, vec![0xFF; 64]).unwrap();
+        }
+
+        let config = RecoveryConfig {
+            scrub_quarantine_limit: 1,
+            ..RecoveryConfig::default()
+        };
+
+        let result = run(ctx.clone(), &config, SPOOL, &CancellationToken::new()).await;
+        assert_eq!(result, ScrubResult::Done { scrubbed: 1, corrupt: 1 });
+        assert!(ctx.store.is_slice_quarantined(SPOOL, a).unwrap());
+        assert!(!ctx.store.is_slice_quarantined(SPOOL, b).unwrap());
+        assert!(!ctx.store.has_pending_repair(SPOOL, b).unwrap());
+        // The pass is left open at the last slice checked.
+        assert_eq!(ctx.store.get_spool_scrub_cursor(SPOOL).unwrap(), Some(a));
+    }
+
+    #[tokio::test]
+    async fn resumes_from_cursor() {
+        let ctx = test_context().await;
+        let a = addr(1);
+        let b = addr(2);
+
+        for (track, n) in [(a, 1), (b, 2)] {
+            seed_track(&ctx, track, &slice_for(n));
+            ctx.store.put_slice(SPOOL, track, slice_for(n)).unwrap();
+        }
+
+        let config = RecoveryConfig {
+            scrub_batch: 1,
+            ..RecoveryConfig::default()
+        };
+
+        let first = run(ctx.clone(), &config, SPOOL, &CancellationToken::new()).await;
+        assert_eq!(first, ScrubResult::Done { scrubbed: 1, corrupt: 0 });
+        assert_eq!(ctx.store.get_spool_scrub_cursor(SPOOL).unwrap(), Some(a));
+
+        let second = run(ctx.clone(), &config, SPOOL, &CancellationToken::new()).await;
+        assert_eq!(second, ScrubResult::Done { scrubbed: 1, corrupt: 0 });
+        assert_eq!(ctx.store.get_spool_scrub_cursor(SPOOL).unwrap(), Some(b));
+
+        // Nothing after the cursor: the pass completes and the cursor resets.
+        let third = run(ctx.clone(), &config, SPOOL, &CancellationToken::new()).await;
+        assert_eq!(third, ScrubResult::Done { scrubbed: 0, corrupt: 0 });
+        assert!(ctx.store.get_spool_scrub_cursor(SPOOL).unwrap().is_none());
+    }
+
+    // leaves from a blob the track does not commit to never condemn a slice
+    #[tokio::test]
+    async fn skips_mismatched_blob() {
+        let ctx = test_context().await;
+        let a = addr(1);
+
+        seed_track(&ctx, a, &slice_for(1));
+        let Some(BlobData::Coded(mut blob)) = ctx.store.get_track_data(a).unwrap() else {
+            panic!("seeded coded blob");
+        };
+        blob.leaves[0] = Hash::new_unique();
+        ctx.store.put_track_data(a, BlobData::Coded(blob)).unwrap();
+        ctx.store.put_slice(SPOOL, a, vec![0xFF; 64]).unwrap();
+
+        let config = RecoveryConfig::default();
+        let result = run(ctx.clone(), &config, SPOOL, &CancellationToken::new()).await;
+        assert_eq!(result, ScrubResult::Done { scrubbed: 0, corrupt: 0 });
+        assert!(ctx.store.has_slice(SPOOL, a).unwrap());
+        assert!(!ctx.store.has_pending_repair(SPOOL, a).unwrap());
+    }
+
+    #[tokio::test]
+    async fn skips_uncertified() {
+        let ctx = test_context().await;
+        let a = addr(1);
+
+        seed_track(&ctx, a, &slice_for(1));
+        ctx.store
+            .put_object_info(
+                a,
+                ObjectInfo::Valid {
+                    track_address: a,
+                    registered_epoch: EpochNumber(1),
+                    certified_epoch: None,
+                    slot: SlotNumber(10),
+                },
+            )
+            .unwrap();
+        ctx.store.put_slice(SPOOL, a, vec![0xFF; 64]).unwrap();
+
+        let result = run(ctx.clone(), &RecoveryConfig::default(), SPOOL, &CancellationToken::new()).await;
+        assert_eq!(result, ScrubResult::Done { scrubbed: 0, corrupt: 0 });
+        assert!(ctx.store.has_slice(SPOOL, a).unwrap());
+    }
+}
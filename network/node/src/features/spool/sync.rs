@@ -263,7 +263,8 @@ async fn pull_batch<Db: Store, Cluster: Api, Blockchain: Rpc>(
     })
 }
 
-fn verify_slice(
+/// Check slice bytes held for `spool` against the track's blob leaves.
+pub fn verify_slice(
     spool: SpoolIndex,
     track_info: &CompressedTrack,
     track_data: &BlobEncoding,
@@ -7,6 +7,7 @@ pub enum Action {
     Scan { spool: SpoolIndex, epoch: EpochNumber },
     Repair { spool: SpoolIndex, epoch: EpochNumber },
     Recover { spool: SpoolIndex, epoch: EpochNumber },
+    Scrub { spool: SpoolIndex, epoch: EpochNumber },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,12 +41,18 @@ pub enum RecoverResult {
     Done { remaining: usize },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrubResult {
+    Done { scrubbed: usize, corrupt: usize },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskResult {
     Sync(SyncResult),
     Scan(ScanResult),
     Repair(RepairResult),
     Recover(RecoverResult),
+    Scrub(ScrubResult),
 }
 
 impl Action {
@@ -54,7 +61,8 @@ impl Action {
             Action::Sync { spool, .. }
             | Action::Scan { spool, .. }
             | Action::Repair { spool, .. }
-            | Action::Recover { spool, .. } => spool,
+            | Action::Recover { spool, .. }
+            | Action::Scrub { spool, .. } => spool,
         }
     }
 
@@ -63,7 +71,8 @@ impl Action {
             Action::Sync { epoch, .. }
             | Action::Scan { epoch, .. }
             | Action::Repair { epoch, .. }
-            | Action::Recover { epoch, .. } => epoch,
+            | Action::Recover { epoch, .. }
+            | Action::Scrub { epoch, .. } => epoch,
         }
     }
 }
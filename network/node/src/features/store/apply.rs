@@ -6,17 +6,20 @@ use tape_core::snapshot::replay::{ReplayTrack, ReplayableEvent};
 use tape_core::system::SpoolStatus;
 use tape_core::tape::{
     blacklist_tape_number, history_tape_number, snapshot_tape_number, tape_index, tape_namespace,
-    TapeFlags, TapeNamespace,
+    TapeFlags, TapeNamespace, TapeVersioning,
 };
 use tape_core::track::data::BlobData;
 use tape_core::track::types::TrackState;
 use tape_core::types::{EpochNumber, SlotNumber, TapeNumber, TrackNumber};
 use tape_crypto::address::Address;
 use tape_store::ops::{
-    ObjectInfoOps, ObjectListOps, ObjectMetadataOps, SliceOps, SpoolOps, TapeOps, TrackDataOps,
-    TrackOps,
+    ObjectInfoOps, ObjectListOps, ObjectMetadataOps, ObjectVersionOps, SliceOps, SpoolOps, TapeOps,
+    TrackDataOps, TrackOps,
+};
+use tape_store::types::{
+    ObjectInfo, ObjectListEntry, ObjectMetadata, ObjectVersion, ObjectVersionId, SystemObjectKind,
+    TapeInfo, VersioningStatus,
 };
-use tape_store::types::{ObjectInfo, ObjectListEntry, ObjectMetadata, SystemObjectKind, TapeInfo};
 use tape_store::TapeStore;
 use tracing::warn;
 
@@ -63,6 +66,7 @@ pub fn apply_event<Db: Store>(
             tape,
             id,
             flags,
+            authority,
             expiry_epoch,
             ..
         } => {
@@ -77,6 +81,7 @@ pub fn apply_event<Db: Store>(
                     },
                 )
                 .map_err(store_error)?;
+            store.set_tape_owner(*tape, *authority).map_err(store_error)?;
         }
         ReplayableEvent::DestroyTape { tape, .. } => {
             delete_tape_local(store, *tape, DELETE_TAPE_BATCH_SIZE)?;
@@ -102,6 +107,56 @@ pub fn apply_event<Db: Store>(
                 }
             }
         }
+        ReplayableEvent::TransferTape { tape, authority, .. } => {
+            // Same as extend: a tape swept before the transfer landed has no
+            // owner left to move.
+            if store.get_tape(*tape).map_err(store_error)?.is_some() {
+                store.set_tape_owner(*tape, *authority).map_err(store_error)?;
+            } else {
+                warn!(tape = %tape, "transfer for unknown tape, skipping");
+            }
+        }
+        ReplayableEvent::SetTapeVersioning { tape, status } => {
+            set_bucket_versioning(store, *tape, *status)?;
+        }
+        ReplayableEvent::PlaceDeleteMarker { tape, name } => {
+            // A key with no version to hide gets no marker, on every node alike.
+            match store.get_bucket_versioning(*tape).map_err(store_error)? {
+                Some(status) => {
+                    store
+                        .place_delete_marker(
+                            *tape,
+                            name,
+                            block_time.unwrap_or_default(),
+                            status == VersioningStatus::Suspended,
+                        )
+                        .map_err(store_error)?;
+                }
+                None => {
+                    warn!(tape = %tape, "delete marker for unversioned bucket, skipping");
+                }
+            }
+        }
+        ReplayableEvent::RemoveDeleteMarker {
+            tape,
+            name,
+            track_number,
+            marker,
+        } => {
+            let version = ObjectVersionId {
+                track_number: *track_number,
+                marker: *marker,
+            };
+            if version.is_delete_marker()
+                && store
+                    .delete_object_version(*tape, name, version)
+                    .map_err(store_error)?
+            {
+                store
+                    .restore_latest_version(*tape, name)
+                    .map_err(store_error)?;
+            }
+        }
         ReplayableEvent::RegisterNode { node, id, .. } => {
             let (history, _) = history_pda(*node);
             store
@@ -163,6 +218,7 @@ pub fn apply_event<Db: Store>(
         | ReplayableEvent::StakeDeposited { .. }
         | ReplayableEvent::StakeUnlockRequested { .. }
         | ReplayableEvent::StakeWithdrawn { .. }
+        | ReplayableEvent::StakeSlashed { .. }
         | ReplayableEvent::VoteProposed { .. }
         | ReplayableEvent::VoteRecorded { .. } => {}
     }
@@ -267,6 +323,7 @@ fn put_named_object_entry<Db: Store>(
             ObjectMetadata {
                 name: object.name.clone(),
                 content_type: object.content_type,
+                headers: object.headers.clone(),
             },
         )
         .map_err(store_error)?;
@@ -280,10 +337,56 @@ fn put_named_object_entry<Db: Store>(
         track_number: replay.state.track_number,
         kind: replay.state.kind,
         content_type: object.content_type,
+        headers: object.headers.clone(),
+    };
+
+    let bucket = replay.state.tape;
+    let Some(status) = store.get_bucket_versioning(bucket).map_err(store_error)? else {
+        return store
+            .put_object_entry(bucket, &object.name, entry)
+            .map_err(store_error);
+    };
+
+    // A versioned bucket keeps the object it replaces as a prior version.
+    // While suspended the write becomes the key's null version instead,
+    // replacing the previous null version as S3 does.
+    store
+        .preserve_current_version(bucket, &object.name)
+        .map_err(store_error)?;
+    let version = ObjectVersionId::track(entry.track_number);
+    let value = ObjectVersion::Object(entry);
+    let name = object.name.as_slice();
+    let written = match status {
+        VersioningStatus::Enabled => store.put_object_version(bucket, name, version, &value),
+        VersioningStatus::Suspended => store.put_null_version(bucket, name, version, &value),
     };
+    written.map_err(store_error)?;
+    store
+        .restore_latest_version(bucket, &object.name)
+        .map_err(store_error)
+}
 
+/// Apply a bucket's versioning status. The program already checked it, so
+/// an unknown value means this node is behind the program and is skipped.
+fn set_bucket_versioning<Db: Store>(
+    store: &TapeStore<Db>,
+    tape: Address,
+    status: u64,
+) -> Result<(), NodeError> {
+    let status = match TapeVersioning::try_from(status) {
+        Ok(TapeVersioning::Enabled) => VersioningStatus::Enabled,
+        Ok(TapeVersioning::Suspended) => VersioningStatus::Suspended,
+        Err(_) => {
+            warn!(tape = %tape, status, "unknown versioning status, skipping");
+            return Ok(());
+        }
+    };
+    if store.get_tape(tape).map_err(store_error)?.is_none() {
+        warn!(tape = %tape, "versioning for unknown tape, skipping");
+        return Ok(());
+    }
     store
-        .put_object_entry(replay.state.tape, &object.name, entry)
+        .configure_bucket_versioning(tape, status)
         .map_err(store_error)
 }
 
@@ -472,21 +575,23 @@ mod tests {
     use tape_core::snapshot::replay::{ReplayTrack, ReplayTrackObject, ReplayableEvent};
     use tape_core::spooler::GroupIndex;
     use tape_core::system::{SpoolState, SpoolStatus};
+    use tape_core::tape::TapeVersioning;
     use tape_core::track::blob::BlobEncoding;
     use tape_core::track::data::BlobData;
     use tape_core::track::types::{CompressedTrack, TrackKind, TrackState};
     use tape_core::types::coin::TAPE;
     use tape_core::types::{
-        ContentType, EpochNumber, SlotNumber, StorageUnits, StripeCount, TapeNumber, TrackNumber,
+        ContentType, EpochNumber, ObjectHeaders, SlotNumber, StorageUnits, StripeCount, TapeNumber,
+        TrackNumber,
     };
     use tape_crypto::address::Address;
     use tape_crypto::hash::hash;
     use tape_crypto::Hash;
     use tape_store::ops::{
-        ObjectInfoOps, ObjectListOps, ObjectMetadataOps, SliceOps, SpoolOps, TapeOps,
-        TrackDataOps, TrackOps,
+        ObjectInfoOps, ObjectListOps, ObjectMetadataOps, ObjectVersionOps, SliceOps, SpoolOps,
+        TapeOps, TrackDataOps, TrackOps,
     };
-    use tape_store::types::{ObjectInfo, TapeInfo};
+    use tape_store::types::{ObjectInfo, ObjectVersionId, TapeInfo, VersioningStatus};
     use tape_store::TapeStore;
 
     use super::apply_slot;
@@ -631,6 +736,8 @@ mod tests {
         let track_number = TrackNumber(11);
         let (track, _) = track_pda(tape, track_number);
         let name = b"photos/cat.jpg".to_vec();
+        let mut headers = ObjectHeaders::with_content_type("image/jpeg");
+        headers.insert_metadata("album", "cats");
 
         let mut track_event = make_blob_track(tape, track_number, EpochNumber(7));
         let blob = match &mut track_event {
@@ -640,6 +747,7 @@ mod tests {
                     name: name.clone(),
                     content_type: ContentType::ImageJpeg,
                     logical_size: StorageUnits::mb(2),
+                    headers: headers.clone(),
                 });
                 replay.blob.expect("blob metadata")
             }
@@ -677,10 +785,12 @@ mod tests {
         assert_eq!(page.objects[0].1.track_number, track_number);
         assert_eq!(page.objects[0].1.kind, TrackKind::Coded as u64);
         assert_eq!(page.objects[0].1.content_type, ContentType::ImageJpeg);
+        assert_eq!(page.objects[0].1.headers, headers);
 
         let metadata = store.get_object_metadata(track).unwrap().unwrap();
         assert_eq!(metadata.name, b"photos/cat.jpg".to_vec());
         assert_eq!(metadata.content_type, ContentType::ImageJpeg);
+        assert_eq!(metadata.headers, headers);
 
         apply_slot(
             &store,
@@ -697,6 +807,170 @@ mod tests {
         assert!(store.get_object_metadata(track).unwrap().is_none());
     }
 
+    // overwrites in a versioned bucket keep history; deleting the current
+    // version promotes the previous one
+    #[test]
+    fn versioned_overwrite() {
+        let store = test_store();
+        let tape = Address::new_unique();
+        let name = b"report.csv".to_vec();
+        store
+            .put_bucket_versioning(tape, VersioningStatus::Enabled)
+            .unwrap();
+
+        let named = |track_number: TrackNumber| {
+            let mut event = make_blob_track(tape, track_number, EpochNumber(7));
+            if let ReplayableEvent::Track(replay) = &mut event {
+                replay.state.key = hash(&name);
+                replay.object = Some(ReplayTrackObject {
+                    name: name.clone(),
+                    content_type: ContentType::Unknown,
+                    logical_size: StorageUnits::mb(2),
+                    headers: ObjectHeaders::default(),
+                });
+            }
+            event
+        };
+        apply_slot(&store, SlotNumber(10), None, &[named(TrackNumber(3))]).unwrap();
+        apply_slot(&store, SlotNumber(11), None, &[named(TrackNumber(4))]).unwrap();
+
+        let current = store.get_object_entry(tape, &name).unwrap().unwrap();
+        assert_eq!(current.track_number, TrackNumber(4));
+        let versions: Vec<ObjectVersionId> = store
+            .object_versions(tape, &name)
+            .unwrap()
+            .into_iter()
+            .map(|(version, _)| version)
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ObjectVersionId::track(TrackNumber(4)),
+                ObjectVersionId::track(TrackNumber(3)),
+            ]
+        );
+
+        let (track, _) = track_pda(tape, TrackNumber(4));
+        apply_slot(
+            &store,
+            SlotNumber(12),
+            None,
+            &[ReplayableEvent::DeleteTrack {
+                track,
+                epoch: EpochNumber(8),
+            }],
+        )
+        .unwrap();
+
+        let current = store.get_object_entry(tape, &name).unwrap().unwrap();
+        assert_eq!(current.track_number, TrackNumber(3));
+        assert_eq!(store.object_versions(tape, &name).unwrap().len(), 1);
+    }
+
+    // the chain drives versioning: suspended writes and deletes replace the
+    // null version, and removing the marker brings the key back
+    #[test]
+    fn versioning_events() {
+        let store = test_store();
+        let tape = Address::new_unique();
+        let name = b"report.csv".to_vec();
+
+        let named = |track_number: TrackNumber| {
+            let mut event = make_blob_track(tape, track_number, EpochNumber(7));
+            if let ReplayableEvent::Track(replay) = &mut event {
+                replay.state.key = hash(&name);
+                replay.object = Some(ReplayTrackObject {
+                    name: name.clone(),
+                    content_type: ContentType::Unknown,
+                    logical_size: StorageUnits::mb(2),
+                    headers: ObjectHeaders::default(),
+                });
+            }
+            event
+        };
+        let versions = || -> Vec<ObjectVersionId> {
+            store
+                .object_versions(tape, &name)
+                .unwrap()
+                .into_iter()
+                .map(|(version, _)| version)
+                .collect()
+        };
+        let set_versioning = |status: TapeVersioning| ReplayableEvent::SetTapeVersioning {
+            tape,
+            status: status.into(),
+        };
+
+        apply_slot(
+            &store,
+            SlotNumber(10),
+            None,
+            &[
+                ReplayableEvent::ReserveTape {
+                    tape,
+                    id: TapeNumber(1),
+                    flags: 0,
+                    authority: Address::new_unique(),
+                    capacity: StorageUnits::mb(10),
+                    active_epoch: EpochNumber(6),
+                    expiry_epoch: EpochNumber(12),
+                    cost: TAPE(0),
+                    burned: TAPE(0),
+                    scheduled: TAPE(0),
+                },
+                named(TrackNumber(2)),
+                set_versioning(TapeVersioning::Enabled),
+                named(TrackNumber(3)),
+                set_versioning(TapeVersioning::Suspended),
+                named(TrackNumber(4)),
+            ],
+        )
+        .unwrap();
+
+        // The pre-versioning object was the null version until the
+        // suspended write replaced it.
+        let track = ObjectVersionId::track;
+        assert_eq!(
+            store.get_bucket_versioning(tape).unwrap(),
+            Some(VersioningStatus::Suspended)
+        );
+        assert_eq!(versions(), vec![track(TrackNumber(4)), track(TrackNumber(3))]);
+        assert_eq!(store.get_null_version(tape, &name).unwrap(), Some(track(TrackNumber(4))));
+
+        apply_slot(
+            &store,
+            SlotNumber(11),
+            Some(1_700_000_000),
+            &[ReplayableEvent::PlaceDeleteMarker {
+                tape,
+                name: name.clone(),
+            }],
+        )
+        .unwrap();
+        let marker = ObjectVersionId {
+            track_number: TrackNumber(4),
+            marker: 1,
+        };
+        assert_eq!(versions(), vec![marker, track(TrackNumber(3))]);
+        assert!(store.get_object_entry(tape, &name).unwrap().is_none());
+
+        apply_slot(
+            &store,
+            SlotNumber(12),
+            None,
+            &[ReplayableEvent::RemoveDeleteMarker {
+                tape,
+                name: name.clone(),
+                track_number: TrackNumber(4),
+                marker: 1,
+            }],
+        )
+        .unwrap();
+        let current = store.get_object_entry(tape, &name).unwrap().unwrap();
+        assert_eq!(current.track_number, TrackNumber(3));
+        assert!(store.get_null_version(tape, &name).unwrap().is_none());
+    }
+
     #[test]
     fn writes_raw_state() {
         let store = test_store();
@@ -1001,6 +1275,41 @@ mod tests {
         assert!(store.get_tape(tape).expect("get tape").is_none());
     }
 
+    // a transfer moves the tape between the owners' listings
+    #[test]
+    fn transfers_tape_owner() {
+        let store = test_store();
+        let tape = Address::new_unique();
+        let previous = Address::new_unique();
+        let authority = Address::new_unique();
+
+        let events = vec![
+            ReplayableEvent::ReserveTape {
+                tape,
+                id: TapeNumber(4),
+                flags: 0,
+                authority: previous,
+                capacity: StorageUnits::mb(10),
+                active_epoch: EpochNumber(6),
+                expiry_epoch: EpochNumber(12),
+                cost: TAPE(0),
+                burned: TAPE(0),
+                scheduled: TAPE(0),
+            },
+            ReplayableEvent::TransferTape {
+                tape,
+                previous_authority: previous,
+                authority,
+            },
+        ];
+
+        apply_slot(&store, SlotNumber(62), None, &events).expect("apply events");
+
+        assert_eq!(store.get_tape_owner(tape).expect("get owner"), Some(authority));
+        assert_eq!(store.list_owned_tapes(authority).expect("list owned"), vec![tape]);
+        assert!(store.list_owned_tapes(previous).expect("list owned").is_empty());
+    }
+
     #[test]
     fn certify_enqueues_repair() {
         let store = test_store();
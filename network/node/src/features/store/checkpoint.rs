@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::task::spawn_blocking;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use rpc::Rpc;
+use store::Store;
+use tape_protocol::Api;
+
+use crate::config::store::StoreConfig;
+use crate::context::NodeContext;
+use crate::core::error::NodeError;
+use crate::core::types::ServiceName;
+
+/// Writes a consistent checkpoint of the live store on SIGUSR1
+///
+/// Each checkpoint is named `checkpoint-<unix seconds>` under the store's
+/// checkpoint directory, where `tape-node restore` picks it up by name.
+pub struct CheckpointManager<Db: Store, Cluster: Api, Blockchain: Rpc> {
+    context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+    config: StoreConfig,
+    cancel: CancellationToken,
+}
+
+impl<Db: Store + 'static, Cluster: Api, Blockchain: Rpc>
+    CheckpointManager<Db, Cluster, Blockchain>
+{
+    pub fn new(
+        context: Arc<NodeContext<Db, Cluster, Blockchain>>,
+        config: StoreConfig,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            context,
+            config,
+            cancel,
+        }
+    }
+
+    #[cfg(unix)]
+    pub async fn run(self) -> Result<(), NodeError> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut requests =
+            signal(SignalKind::user_defined1()).map_err(NodeError::SignalRegistration)?;
+
+        loop {
+            tokio::select! {
+                _ = self.cancel.cancelled() => return Ok(()),
+                received = requests.recv() => {
+                    if received.is_none() {
+                        return Ok(());
+                    }
+                    self.checkpoint().await?;
+                }
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub async fn run(self) -> Result<(), NodeError> {
+        self.cancel.cancelled().await;
+        Ok(())
+    }
+
+    /// Write one checkpoint; a failed checkpoint is logged, not fatal.
+    async fn checkpoint(&self) -> Result<(), NodeError> {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let name = format!("checkpoint-{secs}");
+        let path = self.config.checkpoint_dir(&name);
+
+        let store = self.context.store.clone();
+        let target = path.clone();
+        let result = spawn_blocking(move || {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            store.inner().inner().checkpoint(&target)
+        })
+        .await
+        .map_err(|source| NodeError::ServiceJoin {
+            service: ServiceName::CheckpointManager,
+            source,
+        })?;
+
+        match result {
+            Ok(()) => info!(checkpoint = %name, path = %path.display(), "store checkpoint written"),
+            Err(error) => warn!(checkpoint = %name, error = %error, "store checkpoint failed"),
+        }
+        Ok(())
+    }
+}
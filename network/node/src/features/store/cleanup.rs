@@ -5,9 +5,10 @@ use tape_core::track::types::CompressedTrack;
 use tape_core::types::SpoolIndex;
 use tape_crypto::address::Address;
 use tape_store::ops::{
-    ObjectInfoOps, ObjectListOps, ObjectMetadataOps, SliceOps, SpoolOps, TapeOps, TrackDataOps,
-    TrackOps,
+    BucketAccessOps, LifecycleOps, ObjectInfoOps, ObjectListOps, ObjectMetadataOps,
+    ObjectVersionOps, SliceOps, SpoolOps, TapeOps, TrackDataOps, TrackOps,
 };
+use tape_store::types::ObjectVersionId;
 use tape_store::TapeStore;
 
 use crate::core::error::NodeError;
@@ -74,6 +75,12 @@ pub fn delete_tape_local<Db: Store>(
         cursor = tracks.last().map(|(track, _)| *track);
     }
 
+    // The tape address can be reserved again, so no version history, delete
+    // marker, lifecycle rule or access setting may outlive it.
+    store.clear_bucket_versions(tape).map_err(store_error)?;
+    store.delete_bucket_lifecycle(tape).map_err(store_error)?;
+    store.delete_bucket_access(tape).map_err(store_error)?;
+    store.delete_tape_owner(tape).map_err(store_error)?;
     store.delete_tape(tape).map_err(store_error)?;
     Ok(stats)
 }
@@ -140,6 +147,14 @@ pub fn remove_object_listing_for_track<Db: Store>(
         return Ok(());
     };
 
+    store
+        .delete_object_version(
+            info.tape,
+            &metadata.name,
+            ObjectVersionId::track(info.track_number),
+        )
+        .map_err(store_error)?;
+
     let entry = store
         .get_object_entry(info.tape, &metadata.name)
         .map_err(store_error)?;
@@ -148,9 +163,11 @@ pub fn remove_object_listing_for_track<Db: Store>(
         return Ok(());
     };
 
+    // Deleting the current object promotes the newest remaining version, if
+    // any; without one the name is simply unlisted.
     if entry.data_tape == info.tape && entry.track_number == info.track_number {
         store
-            .delete_object_entry(info.tape, &metadata.name)
+            .restore_latest_version(info.tape, &metadata.name)
             .map_err(store_error)?;
     }
 
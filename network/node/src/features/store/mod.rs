@@ -1,4 +1,5 @@
 pub mod apply;
+pub mod checkpoint;
 pub mod cleanup;
 pub mod manager;
 pub mod util;
@@ -1,20 +1,23 @@
 //! Builds a node's board from live context and the metric set.
 
-use std::sync::{Mutex, OnceLock};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock, PoisonError};
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use rpc::Rpc;
 use store::{Column, Store, StoreVolume};
 use tape_core::system::NodeStatus;
+use tape_core::types::SpoolIndex;
 use tape_metrics::prometheus::proto::{Histogram, MetricFamily};
 use tape_store::columns::{ObjectInfoCol, TapeCol, TrackCol};
 use tape_store::ops::{SliceOps, SpoolOps};
 use tape_observe_api::{
     phase_name, BootstrapInfo, Bucket, CacheStats, Board, ChainStats, DecodeStats, EpochInfo,
     HttpStats, IngestInfo, Labeled, LinkStatus, NetworkNode, Network, NetworkSpool, NodeInfo,
-    NodeStats, ResourceInfo, SpoolStat, StatsSource, StorageContents, StorageInfo, StorageVolume,
-    StoreIo, ThroughputTotals, CACHE_RESULTS, DECODE_RESULTS, DECODE_SLICE_OUTCOMES, SPOOL_OPS,
-    SPOOL_STAGES,
+    NodeStats, ResourceInfo, ScrubCursor, ScrubStats, SpoolStat, StatsSource, StorageContents,
+    StorageInfo, StorageVolume, StoreIo, ThroughputTotals, CACHE_RESULTS, DECODE_RESULTS,
+    DECODE_SLICE_OUTCOMES, DECODED_CACHE_RESULTS, SCRUB_RESULTS, SPOOL_OPS, SPOOL_STAGES,
 };
 use tape_protocol::Api;
 
@@ -292,6 +295,41 @@ fn store_io_stats(families: &[MetricFamily]) -> StoreIo {
     io
 }
 
+/// Scrub cursor and quarantine backlog for each of the node's spools. The
+/// cursors are point reads, but a backlog is a key scan and the board polls
+/// hot, so backlogs are cached and one spool is recounted per poll, round
+/// robin; a spool without a cached backlog is counted when first seen.
+fn scrub_cursors<Db: Store>(store: &tape_store::TapeStore<Db>) -> Vec<ScrubCursor> {
+    static QUARANTINED: Mutex<Option<HashMap<SpoolIndex, u64>>> = Mutex::new(None);
+    static RECOUNT: AtomicUsize = AtomicUsize::new(0);
+
+    let spools = store.iter_all_spools().unwrap_or_default();
+    let mut cache = QUARANTINED.lock().unwrap_or_else(PoisonError::into_inner);
+    let backlogs = cache.get_or_insert_with(HashMap::new);
+    backlogs.retain(|id, _| spools.iter().any(|(spool, _)| spool == id));
+    let recount = RECOUNT.fetch_add(1, Ordering::Relaxed) % spools.len().max(1);
+
+    spools
+        .iter()
+        .enumerate()
+        .map(|(index, (id, _))| {
+            let quarantined = match backlogs.get(id) {
+                Some(backlog) if index != recount => *backlog,
+                _ => {
+                    let backlog = store.count_quarantined_slices(*id).unwrap_or(0) as u64;
+                    backlogs.insert(*id, backlog);
+                    backlog
+                }
+            };
+            ScrubCursor {
+                spool: id.0,
+                track: store.get_spool_scrub_cursor(*id).ok().flatten().map(|t| t.to_string()),
+                quarantined,
+            }
+        })
+        .collect()
+}
+
 /// Real slice count across the node's spools. The RocksDB key estimate is
 /// unreliable for the blob-backed slice column, so count keys directly, but
 /// cache the result: the count is a full key scan and the board polls hot.
@@ -689,8 +727,17 @@ where
         cache: CacheStats {
             results: labeled(CACHE_RESULTS, &|r| m.cache_requests_total.with_label_values(&[r]).get()),
             evicted: m.cache_evicted_total.get(),
+            decoded: labeled(DECODED_CACHE_RESULTS, &|r| {
+                m.decoded_cache_total.with_label_values(&[r]).get()
+            }),
         },
         spool,
+        scrub: ScrubStats {
+            results: labeled(SCRUB_RESULTS, &|r| m.scrub_slices_total.with_label_values(&[r]).get()),
+            bytes: m.scrub_bytes_total.get(),
+            passes: m.scrub_passes_total.get(),
+            cursors: scrub_cursors(&context.store),
+        },
         last_epoch: super::last_epoch(),
         current_epoch: current_epoch.clone(),
         lifetime: super::epoch::lifetime_including(&current_epoch),
@@ -26,12 +26,15 @@ use crate::features::block::ingestor::BlockIngestor;
 use crate::features::bootstrap;
 use crate::features::assignment::manager::AssignmentManager;
 use crate::features::eviction::manager::EvictionManager;
+use crate::features::challenge::manager::ChallengeManager;
 use crate::features::gc::manager::GcManager;
 use crate::features::http::server::HttpServer;
 use crate::features::lifecycle::manager::LifecycleManager;
+use crate::features::renewal::manager::RenewalManager;
 use crate::features::replay::manager::ReplayManager;
 use crate::features::snapshot::manager::SnapshotManager;
 use crate::features::spool::manager::SpoolManager;
+use crate::features::store::checkpoint::CheckpointManager;
 use crate::features::store::manager::StoreManager;
 use crate::features::state::manager::StateManager;
 use crate::supervisor::Supervisor;
@@ -374,6 +377,24 @@ where
         .run(),
     );
 
+    supervisor.spawn(
+        ServiceName::ChallengeManager,
+        ChallengeManager::new(
+            context.clone(),
+            cancel.clone(),
+        )
+        .run(),
+    );
+
+    supervisor.spawn(
+        ServiceName::RenewalManager,
+        RenewalManager::new(
+            context.clone(),
+            cancel.clone(),
+        )
+        .run(),
+    );
+
     supervisor.spawn(
         ServiceName::SnapshotManager,
         SnapshotManager::new(
@@ -404,6 +425,15 @@ where
         ).run(),
     );
 
+    supervisor.spawn(
+        ServiceName::CheckpointManager,
+        CheckpointManager::new(
+            context.clone(),
+            config.store.clone(),
+            cancel.clone(),
+        ).run(),
+    );
+
     supervisor.spawn(
         ServiceName::GcManager,
         GcManager::new(
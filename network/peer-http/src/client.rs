@@ -1,13 +1,19 @@
+use std::convert::Infallible;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use dashmap::DashMap;
+use futures::StreamExt;
 use peer_manager::{PeerManager, PeerNode};
 use tape_core::track::types::{CompressedTrack, CompressedTrackProof};
 use tape_core::types::network::NetworkAddress;
 use tape_core::types::tls::NetworkTlsPubkey;
 use tape_protocol::api::*;
+use tape_protocol::api::stream::{
+    read_sync_slices, slice_batch_put_frames, slice_payload_frames, BodyReader, SliceBody,
+    StreamError,
+};
 use tape_crypto::Address;
 use tape_crypto::ed25519::Keypair;
 
@@ -132,17 +138,16 @@ impl Api for HttpApi {
         let (client, base) = self.resolve(node)?;
         let track_id = req.track.to_string();
         let url = format!("{base}{}", slice_url(&track_id, req.spool));
-        let body =
-            wincode::serialize(&req.payload)
-            .map_err(|e| ApiError::Serialization(e.to_string()))?;
+        let frames = slice_payload_frames(req.payload.clone());
 
-        let bytes_sent = body.len() as u64;
+        let bytes_sent = frames.iter().map(|frame| frame.len() as u64).sum();
+        let body = futures::stream::iter(frames.into_iter().map(Ok::<_, Infallible>));
         let start = Instant::now();
         let resp = client
             .put(&url)
             .timeout(self.put_slice_timeout)
             .header("content-type", BINARY_CONTENT)
-            .body(body)
+            .body(reqwest::Body::wrap_stream(body))
             .send()
             .await
             .map_err(map_reqwest)?;
@@ -155,24 +160,95 @@ impl Api for HttpApi {
     }
 
     async fn get_slice(&self, node: Address, req: &GetSliceReq) -> Result<GetSliceRes, ApiError> {
+        let body = self.get_slice_body(node, req).await?;
+        let data = read_slice_body(body).await?;
+        self.record_rx("get_slice", data.len() as u64);
+        Ok(GetSliceRes { data })
+    }
+
+    async fn put_slices(&self, node: Address, req: &PutSlicesReq) -> Result<PutSlicesRes, ApiError> {
         let (client, base) = self.resolve(node)?;
-        let track_id = req.track.to_string();
-        let url = format!("{base}{}", slice_url(&track_id, req.spool));
+        let url = format!("{base}{}", SLICE_BATCH_PUT_PATH);
+        let frames = slice_batch_put_frames(&req.items);
 
+        let bytes_sent = frames.iter().map(|frame| frame.len() as u64).sum();
+        let body = futures::stream::iter(frames.into_iter().map(Ok::<_, Infallible>));
         let start = Instant::now();
         let resp = client
-            .get(&url)
-            .timeout(self.get_slice_timeout)
+            .post(&url)
+            .timeout(self.put_slice_timeout)
+            .header("content-type", BINARY_CONTENT)
+            .body(reqwest::Body::wrap_stream(body))
             .send()
             .await
             .map_err(map_reqwest)?;
 
-        self.record("get_slice", &resp, start, 0);
+        self.record("put_slices", &resp, start, bytes_sent);
         let resp = check_status(resp).await?;
         let bytes = resp.bytes().await.map_err(map_reqwest)?;
-        self.record_rx("get_slice", bytes.len() as u64);
-        Ok(GetSliceRes {
-            data: bytes.to_vec(),
+        self.record_rx("put_slices", bytes.len() as u64);
+        let wire_res: SliceBatchPutResponse =
+            wincode::deserialize(&bytes)
+            .map_err(|e| ApiError::Serialization(e.to_string()))?;
+
+        Ok(PutSlicesRes {
+            results: wire_res
+                .results
+                .into_iter()
+                .map(|status| status.into_result().map(|()| PutSliceRes))
+                .collect(),
+        })
+    }
+
+    async fn get_slices(&self, node: Address, req: &GetSlicesReq) -> Result<GetSlicesRes, ApiError> {
+        let (client, base) = self.resolve(node)?;
+        let url = format!("{base}{}", SLICE_BATCH_GET_PATH);
+        let wire_req = SliceBatchGetRequest {
+            items: req
+                .items
+                .iter()
+                .map(|item| SliceBatchGetItem {
+                    track_address: item.track,
+                    spool: item.spool,
+                })
+                .collect(),
+        };
+        let body =
+            wincode::serialize(&wire_req)
+            .map_err(|e| ApiError::Serialization(e.to_string()))?;
+
+        let bytes_sent = body.len() as u64;
+        let start = Instant::now();
+        let resp = client
+            .post(&url)
+            .timeout(self.get_slice_timeout)
+            .header("content-type", BINARY_CONTENT)
+            .body(body)
+            .send()
+            .await
+            .map_err(map_reqwest)?;
+
+        self.record("get_slices", &resp, start, bytes_sent);
+        let resp = check_status(resp).await?;
+        let size_hint = resp.content_length();
+        let bytes = BodyReader::new(resp.bytes_stream())
+            .read_to_end(SLICE_BATCH_BODY_LIMIT, size_hint)
+            .await
+            .map_err(map_stream)?;
+        self.record_rx("get_slices", bytes.len() as u64);
+        let wire_res: SliceBatchGetResponse =
+            wincode::deserialize(&bytes)
+            .map_err(|e| ApiError::Serialization(e.to_string()))?;
+
+        Ok(GetSlicesRes {
+            results: wire_res
+                .results
+                .into_iter()
+                .map(|result| {
+                    let data = result.data;
+                    result.status.into_result().map(|()| GetSliceRes { data })
+                })
+                .collect(),
         })
     }
 
@@ -432,11 +508,14 @@ impl Api for HttpApi {
 
         self.record("sync_slices", &resp, start, bytes_sent);
         let resp = check_status(resp).await?;
-        let bytes = resp.bytes().await.map_err(map_reqwest)?;
-        self.record_rx("sync_slices", bytes.len() as u64);
-        let wire_res: SyncSlicesResponse =
-            wincode::deserialize(&bytes)
-            .map_err(|e| ApiError::Serialization(e.to_string()))?;
+        // The node clamps a page to at least one entry.
+        let max_entries = req.limit.max(1) as usize;
+        let mut reader = BodyReader::new(resp.bytes_stream());
+        let wire_res = read_sync_slices(&mut reader, max_entries)
+            .await
+            .map_err(map_stream)?;
+        self.record_rx("sync_slices", reader.received());
+        reader.finish().await.map_err(map_stream)?;
 
         Ok(SyncSlicesRes {
             entries: wire_res.entries,
@@ -485,6 +564,13 @@ impl Api for HttpApi {
     }
 
     async fn repair(&self, node: Address, req: &RepairReq) -> Result<RepairRes, ApiError> {
+        let body = self.repair_body(node, req).await?;
+        let data = read_slice_body(body).await?;
+        self.record_rx("repair", data.len() as u64);
+        Ok(RepairRes { data })
+    }
+
+    async fn repair_body(&self, node: Address, req: &RepairReq) -> Result<SliceBody, ApiError> {
         let (client, base) = self.resolve(node)?;
         let track_id = req.track.to_string();
         let url = format!("{base}{}", repair_url(&track_id));
@@ -509,10 +595,47 @@ impl Api for HttpApi {
 
         self.record("repair", &resp, start, bytes_sent);
         let resp = check_status(resp).await?;
+        Ok(response_body(resp))
+    }
+
+    async fn challenge(
+        &self,
+        node: Address,
+        req: &ChallengeReq,
+    ) -> Result<ChallengeRes, ApiError> {
+        let (client, base) = self.resolve(node)?;
+        let track_id = req.track.to_string();
+        let url = format!("{base}{}", challenge_url(&track_id, req.spool));
+        let wire_req = ChallengeRequest {
+            epoch: req.epoch,
+            stripe: req.stripe,
+        };
+        let body =
+            wincode::serialize(&wire_req)
+            .map_err(|e| ApiError::Serialization(e.to_string()))?;
+
+        let bytes_sent = body.len() as u64;
+        let start = Instant::now();
+        let resp = client
+            .post(&url)
+            .timeout(self.get_slice_timeout)
+            .header("content-type", BINARY_CONTENT)
+            .body(body)
+            .send()
+            .await
+            .map_err(map_reqwest)?;
+
+        self.record("challenge", &resp, start, bytes_sent);
+        let resp = check_status(resp).await?;
         let bytes = resp.bytes().await.map_err(map_reqwest)?;
-        self.record_rx("repair", bytes.len() as u64);
-        Ok(RepairRes {
-            data: bytes.to_vec(),
+        self.record_rx("challenge", bytes.len() as u64);
+        let wire: ChallengeResponse =
+            wincode::deserialize(&bytes)
+            .map_err(|e| ApiError::Serialization(e.to_string()))?;
+
+        Ok(ChallengeRes {
+            data: wire.data,
+            segment_proof: wire.segment_proof,
         })
     }
 
@@ -675,6 +798,28 @@ impl Api for HttpApi {
         self.record_rx("get_observe_board", bytes.len() as u64);
         Ok(bytes.to_vec())
     }
+
+    async fn get_slice_body(
+        &self,
+        node: Address,
+        req: &GetSliceReq,
+    ) -> Result<SliceBody, ApiError> {
+        let (client, base) = self.resolve(node)?;
+        let track_id = req.track.to_string();
+        let url = format!("{base}{}", slice_url(&track_id, req.spool));
+
+        let start = Instant::now();
+        let resp = client
+            .get(&url)
+            .timeout(self.get_slice_timeout)
+            .send()
+            .await
+            .map_err(map_reqwest)?;
+
+        self.record("get_slice", &resp, start, 0);
+        let resp = check_status(resp).await?;
+        Ok(response_body(resp))
+    }
 }
 
 fn map_reqwest(e: reqwest::Error) -> ApiError {
@@ -688,6 +833,30 @@ fn map_reqwest(e: reqwest::Error) -> ApiError {
     }
 }
 
+fn map_stream(error: StreamError) -> ApiError {
+    match error {
+        StreamError::Transport(source) => match source.downcast::<reqwest::Error>() {
+            Ok(error) => map_reqwest(*error),
+            Err(source) => ApiError::Other(error_chain(source.as_ref())),
+        },
+        error => ApiError::Serialization(error.to_string()),
+    }
+}
+
+/// Hand a raw slice-sized body to the caller as it arrives.
+fn response_body(resp: reqwest::Response) -> SliceBody {
+    let stream = resp.bytes_stream().map(|chunk| chunk.map_err(Into::into));
+    BodyReader::new(stream.boxed())
+}
+
+/// Read a raw slice-sized body into one buffer, for callers that need it
+/// whole.
+async fn read_slice_body(mut body: SliceBody) -> Result<Vec<u8>, ApiError> {
+    body.read_to_end(SLICE_BYTES_LIMIT, None)
+        .await
+        .map_err(map_stream)
+}
+
 fn error_chain(e: &dyn std::error::Error) -> String {
     let mut msg = e.to_string();
     let mut source = e.source();
@@ -705,7 +874,14 @@ async fn check_status(resp: reqwest::Response) -> Result<reqwest::Response, ApiE
         return Ok(resp);
     }
     match status.as_u16() {
-        404 => Err(ApiError::NotFound),
+        404 => {
+            let body = resp.text().await.unwrap_or_default();
+            if body.contains("pending repair") {
+                Err(ApiError::PendingRepair)
+            } else {
+                Err(ApiError::NotFound)
+            }
+        }
         403 => {
             let body = resp.text().await.unwrap_or_default();
             if body.contains("not responsible") {
@@ -739,7 +915,7 @@ mod tests {
 
     use axum::body::Bytes;
     use axum::http::StatusCode;
-    use axum::routing::post;
+    use axum::routing::{post, put};
     use axum::Router;
     use axum_server::tls_rustls::RustlsConfig;
     use peer_manager::PeerNode;
@@ -750,7 +926,7 @@ mod tests {
     use tape_core::system::VoteKind;
     use tape_core::system::NodePreferences;
     use tape_core::types::coin::TAPE;
-    use tape_core::types::{BasisPoints, EpochDuration, EpochNumber, StorageUnits};
+    use tape_core::types::{BasisPoints, EpochDuration, EpochNumber, SpoolIndex, StorageUnits};
     use tape_crypto::address::Address;
     use tape_crypto::ed25519::Keypair as EdKeypair;
     use tape_crypto::Hash;
@@ -960,6 +1136,90 @@ mod tests {
         api.vote(target, &api_request).await.unwrap();
     }
 
+    // streamed slice bodies are what a buffering peer reads and writes
+    #[tokio::test]
+    async fn streamed_slice_bodies_over_tls() {
+        install_default_provider();
+        let mut rng = thread_rng();
+        let tls = EdKeypair::new(&mut rng);
+        let tls_pubkey = pubkey_of(&tls);
+        let target = address(7);
+
+        let payload = Arc::new(SlicePayload::new(
+            vec![0x3C; 200_000],
+            Hash::from([0x11; 32]),
+            vec![Hash::from([0x22; 32]); 5],
+            vec![Hash::from([0x33; 32]); 4],
+        ));
+        let slice = Bytes::from(vec![0x6E; 180_000]);
+        let page = SyncSlicesResponse {
+            entries: vec![SyncSliceEntry {
+                track_address: [4; 32],
+                slice_data: vec![0x5D; 150_000],
+            }],
+            next_cursor: Some([4; 32]),
+        };
+
+        let expected_payload = Arc::clone(&payload);
+        let served_slice = slice.clone();
+        let page_bytes = Bytes::from(wincode::serialize(&page).unwrap());
+        let router = Router::new()
+            .route(
+                TRACK_SLICE_PATH,
+                put(move |body: Bytes| {
+                    let expected_payload = Arc::clone(&expected_payload);
+                    async move {
+                        let decoded: SlicePayload = wincode::deserialize(&body).unwrap();
+                        assert_eq!(decoded, *expected_payload);
+                        StatusCode::OK
+                    }
+                })
+                .get(move || {
+                    let served_slice = served_slice.clone();
+                    async move { served_slice }
+                }),
+            )
+            .route(
+                SYNC_SLICES_PATH,
+                post(move |_: Bytes| {
+                    let page_bytes = page_bytes.clone();
+                    async move { page_bytes }
+                }),
+            );
+
+        let (addr, _handle) = serve_tls(tls, router).await;
+
+        let peer_manager = Arc::new(PeerManager::new());
+        peer_manager.add_peer(make_peer(target, addr.port(), tls_pubkey));
+        let api = HttpApi::with_default_timeouts(peer_manager);
+
+        let put_req = PutSliceReq {
+            track: address(3),
+            spool: SpoolIndex(5),
+            payload,
+        };
+        api.put_slice(target, &put_req).await.unwrap();
+
+        let get_req = GetSliceReq {
+            track: address(3),
+            spool: SpoolIndex(5),
+        };
+        let mut body = api.get_slice_body(target, &get_req).await.unwrap();
+        let head = body.read_bytes(1_000).await.unwrap();
+        assert_eq!(head, slice[..1_000]);
+        let fetched = api.get_slice(target, &get_req).await.unwrap();
+        assert_eq!(fetched.data, slice);
+
+        let sync_req = SyncSlicesReq {
+            spool_index: SpoolIndex(5),
+            cursor: None,
+            limit: 1,
+        };
+        let synced = api.sync_slices(target, &sync_req).await.unwrap();
+        assert_eq!(synced.entries, page.entries);
+        assert_eq!(synced.next_cursor, page.next_cursor);
+    }
+
     #[tokio::test]
     async fn rebuilds_client_when_peer_rotates_tls_key() {
         install_default_provider();
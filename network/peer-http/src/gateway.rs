@@ -7,6 +7,7 @@
 use std::time::Duration;
 
 use async_trait::async_trait;
+use futures::future::join_all;
 use tape_core::track::types::{CompressedTrack, CompressedTrackProof};
 use tape_crypto::Address;
 use tape_protocol::Api;
@@ -136,6 +137,20 @@ impl Api for GatewayApi {
         Ok(GetSliceRes { data })
     }
 
+    async fn put_slices(
+        &self,
+        _node: Address,
+        _req: &PutSlicesReq,
+    ) -> Result<PutSlicesRes, ApiError> {
+        Err(unsupported("put_slices"))
+    }
+
+    /// The gateway serves slices one at a time, so a batch fans out.
+    async fn get_slices(&self, node: Address, req: &GetSlicesReq) -> Result<GetSlicesRes, ApiError> {
+        let results = join_all(req.items.iter().map(|item| self.get_slice(node, item))).await;
+        Ok(GetSlicesRes { results })
+    }
+
     async fn get_track(&self, _node: Address, req: &GetTrackReq) -> Result<GetTrackRes, ApiError> {
         let bytes = self.get_bytes(track_url(&req.track.to_string())).await?;
         let wire: TrackResponse =
@@ -275,6 +290,14 @@ impl Api for GatewayApi {
         Err(unsupported("repair"))
     }
 
+    async fn challenge(
+        &self,
+        _node: Address,
+        _req: &ChallengeReq,
+    ) -> Result<ChallengeRes, ApiError> {
+        Err(unsupported("challenge"))
+    }
+
     async fn certify(&self, _node: Address, _req: &CertifyReq) -> Result<CertifyRes, ApiError> {
         Err(unsupported("certify"))
     }
@@ -2,12 +2,12 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use tape_protocol::api::{
-    Api, ApiError, CertifyReq, CertifyRes, FindTrackReq, FindTrackRes, GetHealthReq,
+    Api, ApiError, CertifyReq, CertifyRes, ChallengeReq, ChallengeRes, FindTrackReq, FindTrackRes, GetHealthReq,
     GetHealthRes, GetSliceReq, GetSliceRes, GetStatsReq, GetStatsRes, GetTrackByNumberReq,
     GetTrackByNumberRes, GetTrackDataReq, GetTrackDataRes, GetTrackProofReq, GetTrackProofRes,
     GetTrackReq, GetTrackRes, InvalidateReq, InvalidateRes, ListTracksByTapeReq,
     ListTracksByTapeRes, ListObjectsReq, ListObjectsRes, PeerReq, PeerRes, PutSliceReq,
-    PutSliceRes, RepairReq, RepairRes, SyncSlicesReq, SyncSlicesRes, SyncTracksReq, SyncTracksRes,
+    PutSliceRes, PutSlicesReq, PutSlicesRes, GetSlicesReq, GetSlicesRes, RepairReq, RepairRes, SyncSlicesReq, SyncSlicesRes, SyncTracksReq, SyncTracksRes,
     VoteReq, VoteRes,
 };
 use tape_crypto::Address;
@@ -28,6 +28,8 @@ impl MemoryApi {
         Self::new(|_, req| match req {
             PeerReq::PutSlice(_) => PeerRes::PutSlice(Err(not_impl())),
             PeerReq::GetSlice(_) => PeerRes::GetSlice(Err(not_impl())),
+            PeerReq::PutSlices(_) => PeerRes::PutSlices(Err(not_impl())),
+            PeerReq::GetSlices(_) => PeerRes::GetSlices(Err(not_impl())),
             PeerReq::GetTrack(_) => PeerRes::GetTrack(Err(not_impl())),
             PeerReq::GetTrackByNumber(_) => PeerRes::GetTrackByNumber(Err(not_impl())),
             PeerReq::FindTrack(_) => PeerRes::FindTrack(Err(not_impl())),
@@ -38,6 +40,7 @@ impl MemoryApi {
             PeerReq::SyncSlices(_) => PeerRes::SyncSlices(Err(not_impl())),
             PeerReq::SyncTracks(_) => PeerRes::SyncTracks(Err(not_impl())),
             PeerReq::Repair(_) => PeerRes::Repair(Err(not_impl())),
+            PeerReq::Challenge(_) => PeerRes::Challenge(Err(not_impl())),
             PeerReq::Certify(_) => PeerRes::Certify(Err(not_impl())),
             PeerReq::Invalidate(_) => PeerRes::Invalidate(Err(not_impl())),
             PeerReq::Vote(_) => PeerRes::Vote(Err(not_impl())),
@@ -71,6 +74,14 @@ impl Api for MemoryApi {
         dispatch!(self, node, GetSliceReq { track: req.track, spool: req.spool }, GetSlice)
     }
 
+    async fn put_slices(&self, node: Address, req: &PutSlicesReq) -> Result<PutSlicesRes, ApiError> {
+        dispatch!(self, node, req.clone(), PutSlices)
+    }
+
+    async fn get_slices(&self, node: Address, req: &GetSlicesReq) -> Result<GetSlicesRes, ApiError> {
+        dispatch!(self, node, req.clone(), GetSlices)
+    }
+
     async fn get_track(&self, node: Address, req: &GetTrackReq) -> Result<GetTrackRes, ApiError> {
         dispatch!(self, node, GetTrackReq { track: req.track }, GetTrack)
     }
@@ -126,6 +137,10 @@ impl Api for MemoryApi {
         dispatch!(self, node, RepairReq { track: req.track, helper_spool: req.helper_spool, stripes: req.stripes.clone() }, Repair)
     }
 
+    async fn challenge(&self, node: Address, req: &ChallengeReq) -> Result<ChallengeRes, ApiError> {
+        dispatch!(self, node, ChallengeReq { track: req.track, spool: req.spool, stripe: req.stripe, epoch: req.epoch }, Challenge)
+    }
+
     async fn certify(&self, node: Address, req: &CertifyReq) -> Result<CertifyRes, ApiError> {
         dispatch!(self, node, CertifyReq { track: req.track }, Certify)
     }
@@ -170,7 +185,11 @@ mod tests {
     use tape_core::system::VoteKind;
     use tape_core::types::EpochNumber;
     use tape_crypto::Hash;
-    use tape_protocol::api::VoteCandidate;
+    use std::sync::Mutex;
+
+    use tape_core::types::SpoolIndex;
+    use tape_protocol::api::batch::SliceBatcher;
+    use tape_protocol::api::{SlicePayload, VoteCandidate};
 
     fn address(byte: u8) -> Address {
         let mut bytes = [0u8; 32];
@@ -277,4 +296,82 @@ mod tests {
             .await
             .unwrap();
     }
+
+    fn put_req(track: u8) -> PutSliceReq {
+        PutSliceReq {
+            track: address(track),
+            spool: SpoolIndex(3),
+            payload: Arc::new(SlicePayload::new(
+                vec![track; 32],
+                Hash::from([track; 32]),
+                Vec::new(),
+                Vec::new(),
+            )),
+        }
+    }
+
+    #[tokio::test]
+    async fn batcher_coalesces_concurrent_puts() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let seen = calls.clone();
+        let api = MemoryApi::new(move |_, req| match req {
+            PeerReq::PutSlices(req) => {
+                seen.lock().unwrap().push(req.items.len());
+                let results = req
+                    .items
+                    .iter()
+                    .map(|item| match item.track == address(2) {
+                        true => Err(ApiError::NotResponsible),
+                        false => Ok(PutSliceRes),
+                    })
+                    .collect();
+                PeerRes::PutSlices(Ok(PutSlicesRes { results }))
+            }
+            _ => PeerRes::PutSlice(Err(ApiError::Other("unexpected".into()))),
+        });
+        let batcher = SliceBatcher::new(Arc::new(api));
+
+        let (a, b, c) = (put_req(1), put_req(2), put_req(3));
+        let (first, second, third) = tokio::join!(
+            batcher.put_slice(address(7), &a),
+            batcher.put_slice(address(7), &b),
+            batcher.put_slice(address(7), &c),
+        );
+
+        assert!(first.is_ok());
+        assert!(matches!(second, Err(ApiError::NotResponsible)));
+        assert!(third.is_ok());
+        assert_eq!(*calls.lock().unwrap(), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn batcher_falls_back_without_batch_route() {
+        let singles = Arc::new(Mutex::new(0));
+        let seen = singles.clone();
+        let api = MemoryApi::new(move |_, req| match req {
+            PeerReq::GetSlices(_) => PeerRes::GetSlices(Err(ApiError::NotFound)),
+            PeerReq::GetSlice(req) => {
+                *seen.lock().unwrap() += 1;
+                PeerRes::GetSlice(Ok(GetSliceRes {
+                    data: req.track.to_bytes().to_vec(),
+                }))
+            }
+            _ => PeerRes::GetSlice(Err(ApiError::Other("unexpected".into()))),
+        });
+        let batcher = SliceBatcher::new(Arc::new(api));
+        let get = |track| GetSliceReq {
+            track: address(track),
+            spool: SpoolIndex(3),
+        };
+
+        let (a, b) = (get(1), get(2));
+        let (first, second) = tokio::join!(
+            batcher.get_slice(address(7), &a),
+            batcher.get_slice(address(7), &b),
+        );
+
+        assert_eq!(first.unwrap().data[0], 1);
+        assert_eq!(second.unwrap().data[0], 2);
+        assert_eq!(*singles.lock().unwrap(), 2);
+    }
 }
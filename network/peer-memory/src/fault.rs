@@ -0,0 +1,686 @@
+//! Fault injection for any `Api`.
+//!
+//! A [`FaultApi`] sits between one node and its transport and consults a
+//! shared [`FaultPlan`] on every call: the plan can cut links between node
+//! sets, lose requests, delay them, or make the far end lie by corrupting
+//! the slices it returns and the signatures it sends. The plan is mutable
+//! while the nodes run, and a [`FaultScript`] changes it on a timeline.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tape_core::bls::{BlsPrivateKey, BlsSignature};
+use tape_crypto::Address;
+use tape_protocol::api::{
+    Api, ApiError, CertifyReq, CertifyRes, ChallengeReq, ChallengeRes, FindTrackReq, FindTrackRes,
+    GetHealthReq, GetHealthRes, GetSliceReq, GetSliceRes, GetSlicesReq, GetSlicesRes,
+    GetStatsReq, GetStatsRes, GetTrackByNumberReq, GetTrackByNumberRes, GetTrackDataReq,
+    GetTrackDataRes, GetTrackProofReq, GetTrackProofRes, GetTrackReq, GetTrackRes, InvalidateReq,
+    InvalidateRes, ListObjectsReq, ListObjectsRes, ListTracksByTapeReq, ListTracksByTapeRes,
+    PutSliceReq, PutSliceRes, PutSlicesReq, PutSlicesRes, RepairReq, RepairRes, SyncSlicesReq,
+    SyncSlicesRes, SyncTracksReq, SyncTracksRes, VoteReq, VoteRes,
+};
+use tokio::task::JoinHandle;
+
+/// One-way delay added to a call before it is sent.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Latency {
+    #[default]
+    None,
+    Fixed(Duration),
+    /// Drawn uniformly from `min..=max`.
+    Uniform { min: Duration, max: Duration },
+    /// `base` on most calls, `base + spike` on a `rate` share of them.
+    Spike {
+        base: Duration,
+        spike: Duration,
+        rate: f64,
+    },
+}
+
+/// Faults applied to calls over a link.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkFaults {
+    pub latency: Latency,
+    /// Share of calls, in `[0, 1]`, lost before they reach the far end.
+    pub drop_rate: f64,
+    /// Flip a byte in every slice the far end returns.
+    pub corrupt_slices: bool,
+    /// Replace the BLS signatures the faulted node signs with signatures
+    /// from an unrelated key: those in its replies, and, under a node rule,
+    /// those on the votes it sends.
+    pub wrong_signatures: bool,
+}
+
+/// A change to a [`FaultPlan`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FaultChange {
+    /// Faults for every link without a more specific rule.
+    Default(LinkFaults),
+    /// Faults for every call to `node`, as if the node itself misbehaves.
+    Node { node: Address, faults: LinkFaults },
+    /// Faults for calls from `from` to `to` only.
+    Link {
+        from: Address,
+        to: Address,
+        faults: LinkFaults,
+    },
+    /// Cut every link between the two sets, both ways.
+    Partition(Vec<Address>, Vec<Address>),
+    /// Remove every partition; other faults stay.
+    Heal,
+    /// Remove every fault.
+    Clear,
+}
+
+struct PlanState {
+    default: LinkFaults,
+    nodes: HashMap<Address, LinkFaults>,
+    links: HashMap<(Address, Address), LinkFaults>,
+    partitions: Vec<(HashSet<Address>, HashSet<Address>)>,
+    rng: StdRng,
+}
+
+/// What happens to one call.
+enum Verdict {
+    Partitioned,
+    Dropped,
+    Deliver {
+        delay: Duration,
+        corrupt_slices: bool,
+        wrong_signatures: bool,
+    },
+}
+
+/// Faults shared by every [`FaultApi`] in a network. Random draws come from
+/// one seeded generator, so a run with the same calls in the same order
+/// sees the same faults.
+pub struct FaultPlan {
+    state: Mutex<PlanState>,
+}
+
+impl FaultPlan {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Mutex::new(PlanState {
+                default: LinkFaults::default(),
+                nodes: HashMap::new(),
+                links: HashMap::new(),
+                partitions: Vec::new(),
+                rng: StdRng::seed_from_u64(seed),
+            }),
+        }
+    }
+
+    pub fn apply(&self, change: FaultChange) {
+        let mut state = self.lock();
+        match change {
+            FaultChange::Default(faults) => state.default = faults,
+            FaultChange::Node { node, faults } => {
+                state.nodes.insert(node, faults);
+            }
+            FaultChange::Link { from, to, faults } => {
+                state.links.insert((from, to), faults);
+            }
+            FaultChange::Partition(left, right) => {
+                let sides = (left.into_iter().collect(), right.into_iter().collect());
+                state.partitions.push(sides);
+            }
+            FaultChange::Heal => state.partitions.clear(),
+            FaultChange::Clear => {
+                state.default = LinkFaults::default();
+                state.nodes.clear();
+                state.links.clear();
+                state.partitions.clear();
+            }
+        }
+    }
+
+    /// Decide the fate of a call from `from` to `to`. A link rule wins over
+    /// a node rule, which wins over the default.
+    fn verdict(&self, from: Address, to: Address) -> Verdict {
+        let mut state = self.lock();
+        let cut = state.partitions.iter().any(|(left, right)| {
+            (left.contains(&from) && right.contains(&to))
+                || (right.contains(&from) && left.contains(&to))
+        });
+        if cut {
+            return Verdict::Partitioned;
+        }
+
+        let faults = state
+            .links
+            .get(&(from, to))
+            .or_else(|| state.nodes.get(&to))
+            .unwrap_or(&state.default)
+            .clone();
+        if faults.drop_rate > 0.0 && state.rng.gen_bool(faults.drop_rate.clamp(0.0, 1.0)) {
+            return Verdict::Dropped;
+        }
+
+        Verdict::Deliver {
+            delay: sample(faults.latency, &mut state.rng),
+            corrupt_slices: faults.corrupt_slices,
+            wrong_signatures: faults.wrong_signatures,
+        }
+    }
+
+    /// Whether `node` signs its own requests with the wrong key. A request
+    /// carries its sender's signature, so only a rule for the sender counts.
+    fn forges_requests(&self, node: Address) -> bool {
+        let state = self.lock();
+        state.nodes.get(&node).unwrap_or(&state.default).wrong_signatures
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PlanState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn sample(latency: Latency, rng: &mut StdRng) -> Duration {
+    match latency {
+        Latency::None => Duration::ZERO,
+        Latency::Fixed(delay) => delay,
+        Latency::Uniform { min, max } if max > min => {
+            let nanos = rng.gen_range(min.as_nanos() as u64..=max.as_nanos() as u64);
+            Duration::from_nanos(nanos)
+        }
+        Latency::Uniform { min, .. } => min,
+        Latency::Spike { base, spike, rate } => {
+            if rate > 0.0 && rng.gen_bool(rate.clamp(0.0, 1.0)) {
+                base + spike
+            } else {
+                base
+            }
+        }
+    }
+}
+
+/// Plan changes applied at offsets from when the script is spawned.
+#[derive(Clone, Debug, Default)]
+pub struct FaultScript {
+    steps: Vec<(Duration, FaultChange)>,
+}
+
+impl FaultScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn at(mut self, offset: Duration, change: FaultChange) -> Self {
+        self.steps.push((offset, change));
+        self
+    }
+
+    /// Apply the steps to `plan` in offset order. Steps at the same offset
+    /// apply in the order they were added.
+    pub fn spawn(mut self, plan: Arc<FaultPlan>) -> JoinHandle<()> {
+        self.steps.sort_by_key(|(offset, _)| *offset);
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now();
+            for (offset, change) in self.steps {
+                tokio::time::sleep_until(start + offset).await;
+                plan.apply(change);
+            }
+        })
+    }
+}
+
+/// Wraps an `Api` so every call from `local` passes through a [`FaultPlan`].
+pub struct FaultApi<A: Api> {
+    inner: A,
+    local: Address,
+    plan: Arc<FaultPlan>,
+}
+
+/// Byzantine faults to apply to a delivered call.
+#[derive(Clone, Copy)]
+struct Delivery {
+    corrupt_slices: bool,
+    wrong_signatures: bool,
+}
+
+impl<A: Api> FaultApi<A> {
+    pub fn new(inner: A, local: Address, plan: Arc<FaultPlan>) -> Self {
+        Self { inner, local, plan }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn plan(&self) -> &Arc<FaultPlan> {
+        &self.plan
+    }
+
+    async fn admit(&self, node: Address) -> Result<Delivery, ApiError> {
+        match self.plan.verdict(self.local, node) {
+            Verdict::Partitioned => Err(ApiError::ConnectionFailed("link partitioned".into())),
+            Verdict::Dropped => Err(ApiError::Timeout),
+            Verdict::Deliver {
+                delay,
+                corrupt_slices,
+                wrong_signatures,
+            } => {
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                Ok(Delivery {
+                    corrupt_slices,
+                    wrong_signatures,
+                })
+            }
+        }
+    }
+}
+
+fn corrupt(data: &mut Vec<u8>) {
+    match data.first_mut() {
+        Some(byte) => *byte ^= 0xFF,
+        None => data.push(0xFF),
+    }
+}
+
+fn wrong_signature() -> Result<BlsSignature, ApiError> {
+    BlsPrivateKey::from_random()
+        .sign(b"fault-injected signature")
+        .map_err(|error| ApiError::Other(format!("fault signature: {error:?}")))
+}
+
+#[async_trait]
+impl<A: Api> Api for FaultApi<A> {
+    async fn put_slice(&self, node: Address, req: &PutSliceReq) -> Result<PutSliceRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.put_slice(node, req).await
+    }
+
+    async fn get_slice(&self, node: Address, req: &GetSliceReq) -> Result<GetSliceRes, ApiError> {
+        let delivery = self.admit(node).await?;
+        let mut res = self.inner.get_slice(node, req).await?;
+        if delivery.corrupt_slices {
+            corrupt(&mut res.data);
+        }
+        Ok(res)
+    }
+
+    async fn put_slices(&self, node: Address, req: &PutSlicesReq) -> Result<PutSlicesRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.put_slices(node, req).await
+    }
+
+    async fn get_slices(&self, node: Address, req: &GetSlicesReq) -> Result<GetSlicesRes, ApiError> {
+        let delivery = self.admit(node).await?;
+        let mut res = self.inner.get_slices(node, req).await?;
+        if delivery.corrupt_slices {
+            for slice in res.results.iter_mut().flatten() {
+                corrupt(&mut slice.data);
+            }
+        }
+        Ok(res)
+    }
+
+    async fn get_track(&self, node: Address, req: &GetTrackReq) -> Result<GetTrackRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.get_track(node, req).await
+    }
+
+    async fn get_track_by_number(
+        &self,
+        node: Address,
+        req: &GetTrackByNumberReq,
+    ) -> Result<GetTrackByNumberRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.get_track_by_number(node, req).await
+    }
+
+    async fn find_track(&self, node: Address, req: &FindTrackReq) -> Result<FindTrackRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.find_track(node, req).await
+    }
+
+    async fn list_tracks_by_tape(
+        &self,
+        node: Address,
+        req: &ListTracksByTapeReq,
+    ) -> Result<ListTracksByTapeRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.list_tracks_by_tape(node, req).await
+    }
+
+    async fn list_objects(
+        &self,
+        node: Address,
+        req: &ListObjectsReq,
+    ) -> Result<ListObjectsRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.list_objects(node, req).await
+    }
+
+    async fn get_track_data(
+        &self,
+        node: Address,
+        req: &GetTrackDataReq,
+    ) -> Result<GetTrackDataRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.get_track_data(node, req).await
+    }
+
+    async fn get_track_proof(
+        &self,
+        node: Address,
+        req: &GetTrackProofReq,
+    ) -> Result<GetTrackProofRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.get_track_proof(node, req).await
+    }
+
+    async fn sync_slices(
+        &self,
+        node: Address,
+        req: &SyncSlicesReq,
+    ) -> Result<SyncSlicesRes, ApiError> {
+        let delivery = self.admit(node).await?;
+        let mut res = self.inner.sync_slices(node, req).await?;
+        if delivery.corrupt_slices {
+            for entry in &mut res.entries {
+                corrupt(&mut entry.slice_data);
+            }
+        }
+        Ok(res)
+    }
+
+    async fn sync_tracks(
+        &self,
+        node: Address,
+        req: &SyncTracksReq,
+    ) -> Result<SyncTracksRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.sync_tracks(node, req).await
+    }
+
+    async fn repair(&self, node: Address, req: &RepairReq) -> Result<RepairRes, ApiError> {
+        let delivery = self.admit(node).await?;
+        let mut res = self.inner.repair(node, req).await?;
+        if delivery.corrupt_slices {
+            corrupt(&mut res.data);
+        }
+        Ok(res)
+    }
+
+    async fn challenge(&self, node: Address, req: &ChallengeReq) -> Result<ChallengeRes, ApiError> {
+        let delivery = self.admit(node).await?;
+        let mut res = self.inner.challenge(node, req).await?;
+        if delivery.corrupt_slices {
+            corrupt(&mut res.data);
+        }
+        Ok(res)
+    }
+
+    async fn certify(&self, node: Address, req: &CertifyReq) -> Result<CertifyRes, ApiError> {
+        let delivery = self.admit(node).await?;
+        let mut res = self.inner.certify(node, req).await?;
+        if delivery.wrong_signatures {
+            res.signature = wrong_signature()?;
+        }
+        Ok(res)
+    }
+
+    async fn invalidate(
+        &self,
+        node: Address,
+        req: &InvalidateReq,
+    ) -> Result<InvalidateRes, ApiError> {
+        let delivery = self.admit(node).await?;
+        let mut res = self.inner.invalidate(node, req).await?;
+        if delivery.wrong_signatures {
+            res.signature = wrong_signature()?;
+        }
+        Ok(res)
+    }
+
+    async fn vote(&self, node: Address, req: &VoteReq) -> Result<VoteRes, ApiError> {
+        self.admit(node).await?;
+        if self.plan.forges_requests(self.local) {
+            let mut forged = req.clone();
+            forged.signature = wrong_signature()?;
+            return self.inner.vote(node, &forged).await;
+        }
+        self.inner.vote(node, req).await
+    }
+
+    async fn get_health(&self, node: Address, req: &GetHealthReq) -> Result<GetHealthRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.get_health(node, req).await
+    }
+
+    async fn get_stats(&self, node: Address, req: &GetStatsReq) -> Result<GetStatsRes, ApiError> {
+        self.admit(node).await?;
+        self.inner.get_stats(node, req).await
+    }
+
+    async fn get_observe_board(&self, node: Address) -> Result<Vec<u8>, ApiError> {
+        self.admit(node).await?;
+        self.inner.get_observe_board(node).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use tape_core::system::{VoteCandidate, VoteKind};
+    use tape_core::types::{EpochNumber, GroupIndex, SpoolIndex};
+    use tape_crypto::Hash;
+    use tape_protocol::api::{PeerReq, PeerRes};
+
+    use super::*;
+    use crate::MemoryApi;
+
+    const SIGNATURE_MESSAGE: &[u8] = b"certify";
+
+    fn address(byte: u8) -> Address {
+        let mut bytes = [0u8; 32];
+        bytes[0] = byte;
+        Address::new(bytes)
+    }
+
+    fn echo(signer: BlsPrivateKey) -> MemoryApi {
+        MemoryApi::new(move |node, req| match req {
+            PeerReq::GetHealth(_) => PeerRes::GetHealth(Ok(GetHealthRes { ok: true })),
+            PeerReq::GetSlice(_) => PeerRes::GetSlice(Ok(GetSliceRes {
+                data: vec![1, 2, 3],
+            })),
+            PeerReq::Certify(_) => PeerRes::Certify(Ok(CertifyRes {
+                signature: signer.sign(SIGNATURE_MESSAGE).unwrap(),
+                node,
+                epoch: EpochNumber(1),
+            })),
+            _ => PeerRes::GetHealth(Err(ApiError::Other("unexpected".into()))),
+        })
+    }
+
+    fn api(local: u8, plan: &Arc<FaultPlan>) -> FaultApi<MemoryApi> {
+        FaultApi::new(echo(BlsPrivateKey::from_random()), address(local), plan.clone())
+    }
+
+    fn slice_req() -> GetSliceReq {
+        GetSliceReq {
+            track: address(9),
+            spool: SpoolIndex(3),
+        }
+    }
+
+    #[tokio::test]
+    async fn partition_cuts_both_ways_until_healed() {
+        let plan = Arc::new(FaultPlan::new(0));
+        plan.apply(FaultChange::Partition(vec![address(1)], vec![address(2), address(3)]));
+
+        let res = api(1, &plan).get_health(address(2), &GetHealthReq).await;
+        assert!(matches!(res, Err(ApiError::ConnectionFailed(_))));
+        let res = api(3, &plan).get_health(address(1), &GetHealthReq).await;
+        assert!(matches!(res, Err(ApiError::ConnectionFailed(_))));
+        // links inside one side are untouched
+        assert!(api(2, &plan).get_health(address(3), &GetHealthReq).await.is_ok());
+
+        plan.apply(FaultChange::Heal);
+        assert!(api(1, &plan).get_health(address(2), &GetHealthReq).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn link_rule_overrides_node_and_default() {
+        let plan = Arc::new(FaultPlan::new(0));
+        let lossy = LinkFaults {
+            drop_rate: 1.0,
+            ..LinkFaults::default()
+        };
+        plan.apply(FaultChange::Default(lossy.clone()));
+        plan.apply(FaultChange::Node {
+            node: address(2),
+            faults: LinkFaults::default(),
+        });
+        plan.apply(FaultChange::Link {
+            from: address(1),
+            to: address(2),
+            faults: lossy,
+        });
+
+        let res = api(1, &plan).get_health(address(2), &GetHealthReq).await;
+        assert!(matches!(res, Err(ApiError::Timeout)));
+        assert!(api(3, &plan).get_health(address(2), &GetHealthReq).await.is_ok());
+        let res = api(3, &plan).get_health(address(4), &GetHealthReq).await;
+        assert!(matches!(res, Err(ApiError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn byzantine_node_corrupts_slices_and_signatures() {
+        let plan = Arc::new(FaultPlan::new(0));
+        plan.apply(FaultChange::Node {
+            node: address(2),
+            faults: LinkFaults {
+                corrupt_slices: true,
+                wrong_signatures: true,
+                ..LinkFaults::default()
+            },
+        });
+        let signer = BlsPrivateKey::from_random();
+        let client = FaultApi::new(echo(signer), address(1), plan.clone());
+
+        let slice = client.get_slice(address(2), &slice_req()).await.unwrap();
+        assert_ne!(slice.data, vec![1, 2, 3]);
+        let slice = client.get_slice(address(3), &slice_req()).await.unwrap();
+        assert_eq!(slice.data, vec![1, 2, 3]);
+
+        let honest = signer.sign(SIGNATURE_MESSAGE).unwrap();
+        let certify = CertifyReq { track: address(9) };
+        let res = client.certify(address(2), &certify).await.unwrap();
+        assert_ne!(res.signature, honest);
+        let res = client.certify(address(3), &certify).await.unwrap();
+        assert_eq!(res.signature, honest);
+    }
+
+    // a byzantine voter's votes are forged wherever they go, and honest
+    // votes sent to it still verify
+    #[tokio::test]
+    async fn byzantine_voter_is_rejected() {
+        let plan = Arc::new(FaultPlan::new(0));
+        plan.apply(FaultChange::Node {
+            node: address(2),
+            faults: LinkFaults {
+                wrong_signatures: true,
+                ..LinkFaults::default()
+            },
+        });
+        let voter = BlsPrivateKey::from_random();
+        let pubkey = voter.public_key().unwrap();
+        let verifier = || {
+            MemoryApi::new(move |_, req| match req {
+                PeerReq::Vote(req) => {
+                    match req.signature.verify_aggregate(SIGNATURE_MESSAGE, &[pubkey]) {
+                        Ok(()) => PeerRes::Vote(Ok(VoteRes)),
+                        Err(_) => PeerRes::Vote(Err(ApiError::Other("forged vote".into()))),
+                    }
+                }
+                _ => PeerRes::Vote(Err(ApiError::Other("unexpected".into()))),
+            })
+        };
+        let vote = |signer| VoteReq {
+            signer: address(signer),
+            candidate: VoteCandidate {
+                kind: VoteKind::Snapshot,
+                voting_epoch: EpochNumber(2),
+                target_epoch: EpochNumber(1),
+                hash: Hash::from([7; 32]),
+            },
+            group: GroupIndex(0),
+            signature: voter.sign(SIGNATURE_MESSAGE).unwrap(),
+        };
+
+        let byzantine = FaultApi::new(verifier(), address(2), plan.clone());
+        let res = byzantine.vote(address(1), &vote(2)).await;
+        assert!(matches!(res, Err(ApiError::Other(_))));
+
+        let honest = FaultApi::new(verifier(), address(3), plan.clone());
+        assert!(honest.vote(address(2), &vote(3)).await.is_ok());
+        assert!(honest.vote(address(1), &vote(3)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn latency_delays_the_call() {
+        let plan = Arc::new(FaultPlan::new(0));
+        plan.apply(FaultChange::Default(LinkFaults {
+            latency: Latency::Fixed(Duration::from_millis(30)),
+            ..LinkFaults::default()
+        }));
+
+        let start = Instant::now();
+        api(1, &plan).get_health(address(2), &GetHealthReq).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn seeded_plans_draw_the_same_faults() {
+        let faults = LinkFaults {
+            latency: Latency::Uniform {
+                min: Duration::from_millis(1),
+                max: Duration::from_millis(50),
+            },
+            drop_rate: 0.3,
+            ..LinkFaults::default()
+        };
+        let draws = |seed| {
+            let plan = FaultPlan::new(seed);
+            plan.apply(FaultChange::Default(faults.clone()));
+            (0..32)
+                .map(|_| match plan.verdict(address(1), address(2)) {
+                    Verdict::Deliver { delay, .. } => Some(delay),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first = draws(42);
+        assert_eq!(first, draws(42));
+        assert!(first.iter().any(Option::is_none));
+        assert!(first.iter().any(Option::is_some));
+    }
+
+    #[tokio::test]
+    async fn script_applies_changes_in_offset_order() {
+        let plan = Arc::new(FaultPlan::new(0));
+        let cut = FaultChange::Partition(vec![address(1)], vec![address(2)]);
+        FaultScript::new()
+            .at(Duration::from_millis(60), FaultChange::Heal)
+            .at(Duration::ZERO, cut)
+            .spawn(plan.clone());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(api(1, &plan).get_health(address(2), &GetHealthReq).await.is_err());
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(api(1, &plan).get_health(address(2), &GetHealthReq).await.is_ok());
+    }
+}
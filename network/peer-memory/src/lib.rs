@@ -1,5 +1,8 @@
-//! In-memory mock implementation of the `Api` trait for testing.
+//! In-memory mock implementation of the `Api` trait for testing, and a fault
+//! layer that wraps any `Api`.
 
 mod client;
+mod fault;
 
 pub use client::MemoryApi;
+pub use fault::{FaultApi, FaultChange, FaultPlan, FaultScript, Latency, LinkFaults};
@@ -0,0 +1,370 @@
+//! Coalesces single-slice calls into batch requests.
+//!
+//! Uploading or reading many small objects costs one round trip per slice, per
+//! spool. [`SliceBatcher`] wraps an [`Api`] and holds each `put_slice` or
+//! `get_slice` for a short linger, so concurrent calls to the same node go out
+//! as one [`Api::put_slices`] or [`Api::get_slices`] request. A batch of one
+//! is sent as the plain single-slice call, and a peer without the batch route
+//! is served item by item.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use tape_crypto::Address;
+use tokio::sync::oneshot;
+
+use crate::api::ops::*;
+use crate::api::{Api, ApiError, SLICE_BATCH_ITEMS_LIMIT};
+
+/// Limits on how slice calls are held and grouped.
+#[derive(Clone, Copy, Debug)]
+pub struct SliceBatchConfig {
+    /// How long the first call to a node waits for others to join it.
+    pub linger: Duration,
+    /// Calls per batch; a full batch is sent without waiting out the linger.
+    pub max_items: usize,
+    /// Slice bytes per upload batch, kept under the nodes' body limit.
+    pub max_bytes: usize,
+    /// Uploads larger than this are sent on their own.
+    pub max_item_bytes: usize,
+}
+
+impl Default for SliceBatchConfig {
+    fn default() -> Self {
+        Self {
+            linger: Duration::from_millis(2),
+            max_items: 64,
+            max_bytes: 1024 * 1024,
+            max_item_bytes: 64 * 1024,
+        }
+    }
+}
+
+/// An [`Api`] whose single-slice calls are coalesced per node. Every other
+/// call passes straight through to the wrapped client.
+pub struct SliceBatcher<A: Api> {
+    inner: Arc<Inner<A>>,
+}
+
+struct Inner<A: Api> {
+    api: Arc<A>,
+    config: SliceBatchConfig,
+    puts: Mutex<HashMap<Address, Pending<PutSliceReq, PutSliceRes>>>,
+    gets: Mutex<HashMap<Address, Pending<GetSliceReq, GetSliceRes>>>,
+    next_id: AtomicU64,
+}
+
+type Waiter<Res> = oneshot::Sender<Result<Res, ApiError>>;
+
+/// Calls queued for one node, tagged so a lingering leader only sends the
+/// batch it opened.
+struct Pending<Req, Res> {
+    id: u64,
+    bytes: usize,
+    items: Vec<Req>,
+    waiters: Vec<Waiter<Res>>,
+}
+
+enum Joined<Req, Res> {
+    /// Opened a new batch and owes it a send after the linger.
+    Leader(u64),
+    /// Filled the batch, which goes out now.
+    Full(Pending<Req, Res>),
+    Follower,
+}
+
+impl<A: Api> SliceBatcher<A> {
+    pub fn new(api: Arc<A>) -> Self {
+        Self::with_config(api, SliceBatchConfig::default())
+    }
+
+    pub fn with_config(api: Arc<A>, mut config: SliceBatchConfig) -> Self {
+        config.max_items = config.max_items.clamp(1, SLICE_BATCH_ITEMS_LIMIT);
+        Self {
+            inner: Arc::new(Inner {
+                api,
+                config,
+                puts: Mutex::new(HashMap::new()),
+                gets: Mutex::new(HashMap::new()),
+                next_id: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// The client batches are sent through.
+    pub fn api(&self) -> &Arc<A> {
+        &self.inner.api
+    }
+
+    pub fn config(&self) -> SliceBatchConfig {
+        self.inner.config
+    }
+}
+
+impl<A: Api> Inner<A> {
+    fn join<Req, Res>(
+        &self,
+        queues: &Mutex<HashMap<Address, Pending<Req, Res>>>,
+        node: Address,
+        req: Req,
+        bytes: usize,
+        waiter: Waiter<Res>,
+    ) -> Joined<Req, Res> {
+        let mut queues = queues.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let pending = queues.entry(node).or_insert_with(|| Pending {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            bytes: 0,
+            items: Vec::new(),
+            waiters: Vec::new(),
+        });
+        let (id, opened) = (pending.id, pending.items.is_empty());
+        pending.bytes = pending.bytes.saturating_add(bytes);
+        pending.items.push(req);
+        pending.waiters.push(waiter);
+
+        if pending.items.len() >= self.config.max_items || pending.bytes >= self.config.max_bytes {
+            return queues.remove(&node).map_or(Joined::Follower, Joined::Full);
+        }
+        match opened {
+            true => Joined::Leader(id),
+            false => Joined::Follower,
+        }
+    }
+
+    fn take<Req, Res>(
+        queues: &Mutex<HashMap<Address, Pending<Req, Res>>>,
+        node: Address,
+        id: u64,
+    ) -> Option<Pending<Req, Res>> {
+        let mut queues = queues.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match queues.get(&node) {
+            Some(pending) if pending.id == id => queues.remove(&node),
+            _ => None,
+        }
+    }
+
+    async fn send_puts(&self, node: Address, batch: Pending<PutSliceReq, PutSliceRes>) {
+        let Pending { items, waiters, .. } = batch;
+        let results = match items.len() {
+            1 => vec![self.api.put_slice(node, &items[0]).await],
+            _ => {
+                let req = PutSlicesReq { items };
+                match self.api.put_slices(node, &req).await {
+                    Ok(res) => lined_up(res.results, req.items.len()),
+                    // The peer predates the batch route.
+                    Err(ApiError::NotFound) => {
+                        join_all(req.items.iter().map(|item| self.api.put_slice(node, item))).await
+                    }
+                    Err(error) => repeated(error, req.items.len()),
+                }
+            }
+        };
+        answer(waiters, results);
+    }
+
+    async fn send_gets(&self, node: Address, batch: Pending<GetSliceReq, GetSliceRes>) {
+        let Pending { items, waiters, .. } = batch;
+        let results = match items.len() {
+            1 => vec![self.api.get_slice(node, &items[0]).await],
+            _ => {
+                let req = GetSlicesReq { items };
+                match self.api.get_slices(node, &req).await {
+                    Ok(res) => {
+                        let mut results = lined_up(res.results, req.items.len());
+                        // Items the peer held back to bound its response go out singly.
+                        let deferred: Vec<usize> = results
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, result)| matches!(result, Err(ApiError::Deferred)))
+                            .map(|(index, _)| index)
+                            .collect();
+                        let refetched = join_all(
+                            deferred
+                                .iter()
+                                .map(|&index| self.api.get_slice(node, &req.items[index])),
+                        )
+                        .await;
+                        for (index, result) in deferred.into_iter().zip(refetched) {
+                            results[index] = result;
+                        }
+                        results
+                    }
+                    Err(ApiError::NotFound) => {
+                        join_all(req.items.iter().map(|item| self.api.get_slice(node, item))).await
+                    }
+                    Err(error) => repeated(error, req.items.len()),
+                }
+            }
+        };
+        answer(waiters, results);
+    }
+}
+
+/// Results from a peer that answered a different number of items than asked
+/// for cannot be matched to callers, so every caller sees the mismatch.
+fn lined_up<Res>(
+    results: Vec<Result<Res, ApiError>>,
+    expected: usize,
+) -> Vec<Result<Res, ApiError>> {
+    if results.len() == expected {
+        return results;
+    }
+    let error = ApiError::Serialization(format!(
+        "slice batch answered {} of {expected} items",
+        results.len()
+    ));
+    repeated(error, expected)
+}
+
+fn repeated<Res>(error: ApiError, count: usize) -> Vec<Result<Res, ApiError>> {
+    (0..count).map(|_| Err(error.clone())).collect()
+}
+
+fn answer<Res>(waiters: Vec<Waiter<Res>>, results: Vec<Result<Res, ApiError>>) {
+    for (waiter, result) in waiters.into_iter().zip(results) {
+        // A caller that gave up has dropped its receiver.
+        let _ = waiter.send(result);
+    }
+}
+
+fn batch_dropped() -> ApiError {
+    ApiError::Other("slice batch dropped".into())
+}
+
+#[async_trait]
+impl<A: Api> Api for SliceBatcher<A> {
+    async fn put_slice(&self, node: Address, req: &PutSliceReq) -> Result<PutSliceRes, ApiError> {
+        let inner = &self.inner;
+        let bytes = req.payload.data.len();
+        if inner.config.max_items == 1 || bytes > inner.config.max_item_bytes {
+            return inner.api.put_slice(node, req).await;
+        }
+
+        // Sends run on their own task so a caller dropping its future does not
+        // strand the rest of the batch.
+        let (waiter, answer) = oneshot::channel();
+        match inner.join(&inner.puts, node, req.clone(), bytes, waiter) {
+            Joined::Leader(id) => {
+                let inner = inner.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(inner.config.linger).await;
+                    if let Some(batch) = Inner::<A>::take(&inner.puts, node, id) {
+                        inner.send_puts(node, batch).await;
+                    }
+                });
+            }
+            Joined::Full(batch) => {
+                let inner = inner.clone();
+                tokio::spawn(async move { inner.send_puts(node, batch).await });
+            }
+            Joined::Follower => {}
+        }
+        answer.await.unwrap_or_else(|_| Err(batch_dropped()))
+    }
+
+    async fn get_slice(&self, node: Address, req: &GetSliceReq) -> Result<GetSliceRes, ApiError> {
+        let inner = &self.inner;
+        if inner.config.max_items == 1 {
+            return inner.api.get_slice(node, req).await;
+        }
+
+        let (waiter, answer) = oneshot::channel();
+        match inner.join(&inner.gets, node, req.clone(), 0, waiter) {
+            Joined::Leader(id) => {
+                let inner = inner.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(inner.config.linger).await;
+                    if let Some(batch) = Inner::<A>::take(&inner.gets, node, id) {
+                        inner.send_gets(node, batch).await;
+                    }
+                });
+            }
+            Joined::Full(batch) => {
+                let inner = inner.clone();
+                tokio::spawn(async move { inner.send_gets(node, batch).await });
+            }
+            Joined::Follower => {}
+        }
+        answer.await.unwrap_or_else(|_| Err(batch_dropped()))
+    }
+
+    async fn put_slices(&self, node: Address, req: &PutSlicesReq) -> Result<PutSlicesRes, ApiError> {
+        self.inner.api.put_slices(node, req).await
+    }
+
+    async fn get_slices(&self, node: Address, req: &GetSlicesReq) -> Result<GetSlicesRes, ApiError> {
+        self.inner.api.get_slices(node, req).await
+    }
+
+    async fn get_track(&self, node: Address, req: &GetTrackReq) -> Result<GetTrackRes, ApiError> {
+        self.inner.api.get_track(node, req).await
+    }
+
+    async fn get_track_by_number(&self, node: Address, req: &GetTrackByNumberReq) -> Result<GetTrackByNumberRes, ApiError> {
+        self.inner.api.get_track_by_number(node, req).await
+    }
+
+    async fn find_track(&self, node: Address, req: &FindTrackReq) -> Result<FindTrackRes, ApiError> {
+        self.inner.api.find_track(node, req).await
+    }
+
+    async fn list_tracks_by_tape(&self, node: Address, req: &ListTracksByTapeReq) -> Result<ListTracksByTapeRes, ApiError> {
+        self.inner.api.list_tracks_by_tape(node, req).await
+    }
+
+    async fn list_objects(&self, node: Address, req: &ListObjectsReq) -> Result<ListObjectsRes, ApiError> {
+        self.inner.api.list_objects(node, req).await
+    }
+
+    async fn get_track_data(&self, node: Address, req: &GetTrackDataReq) -> Result<GetTrackDataRes, ApiError> {
+        self.inner.api.get_track_data(node, req).await
+    }
+
+    async fn get_track_proof(&self, node: Address, req: &GetTrackProofReq) -> Result<GetTrackProofRes, ApiError> {
+        self.inner.api.get_track_proof(node, req).await
+    }
+
+    async fn sync_slices(&self, node: Address, req: &SyncSlicesReq) -> Result<SyncSlicesRes, ApiError> {
+        self.inner.api.sync_slices(node, req).await
+    }
+
+    async fn sync_tracks(&self, node: Address, req: &SyncTracksReq) -> Result<SyncTracksRes, ApiError> {
+        self.inner.api.sync_tracks(node, req).await
+    }
+
+    async fn repair(&self, node: Address, req: &RepairReq) -> Result<RepairRes, ApiError> {
+        self.inner.api.repair(node, req).await
+    }
+
+    async fn challenge(&self, node: Address, req: &ChallengeReq) -> Result<ChallengeRes, ApiError> {
+        self.inner.api.challenge(node, req).await
+    }
+
+    async fn certify(&self, node: Address, req: &CertifyReq) -> Result<CertifyRes, ApiError> {
+        self.inner.api.certify(node, req).await
+    }
+
+    async fn invalidate(&self, node: Address, req: &InvalidateReq) -> Result<InvalidateRes, ApiError> {
+        self.inner.api.invalidate(node, req).await
+    }
+
+    async fn vote(&self, node: Address, req: &VoteReq) -> Result<VoteRes, ApiError> {
+        self.inner.api.vote(node, req).await
+    }
+
+    async fn get_health(&self, node: Address, req: &GetHealthReq) -> Result<GetHealthRes, ApiError> {
+        self.inner.api.get_health(node, req).await
+    }
+
+    async fn get_stats(&self, node: Address, req: &GetStatsReq) -> Result<GetStatsRes, ApiError> {
+        self.inner.api.get_stats(node, req).await
+    }
+
+    async fn get_observe_board(&self, node: Address) -> Result<Vec<u8>, ApiError> {
+        self.inner.api.get_observe_board(node).await
+    }
+}
@@ -3,7 +3,7 @@
 use tape_crypto::Address;
 use tape_retry::Retryable;
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Clone, Debug, thiserror::Error)]
 pub enum ApiError {
     #[error("node not found in directory: {0:?}")]
     NodeUnresolved(Address),
@@ -11,6 +11,10 @@ pub enum ApiError {
     #[error("not found")]
     NotFound,
 
+    /// The peer lacks the slice but already has it queued for repair.
+    #[error("slice pending repair")]
+    PendingRepair,
+
     #[error("connection failed: {0}")]
     ConnectionFailed(String),
 
@@ -35,6 +39,10 @@ pub enum ApiError {
     #[error("stale track proof")]
     StaleTrackProof,
 
+    /// A batch item the peer left unanswered to keep its response bounded.
+    #[error("deferred by peer")]
+    Deferred,
+
     #[error("peer error: {0}")]
     Other(String),
 }
@@ -42,9 +50,13 @@ pub enum ApiError {
 impl Retryable for ApiError {
     fn is_retryable(&self) -> bool {
         match self {
-            Self::ConnectionFailed(_) | Self::Timeout | Self::StaleTrackProof => true,
+            Self::ConnectionFailed(_)
+            | Self::Timeout
+            | Self::StaleTrackProof
+            | Self::Deferred => true,
             Self::ServerError { status, .. } => matches!(status, 408 | 429 | 500 | 502 | 503 | 504),
             Self::NotFound
+            | Self::PendingRepair
             | Self::NotResponsible
             | Self::BlacklistedObject
             | Self::NotInCommittee
@@ -1,8 +1,10 @@
 //! Protocol API: routes, wire types, ops, and the `Api` trait.
 
+pub mod batch;
 pub mod error;
 pub mod ops;
 pub mod routes;
+pub mod stream;
 pub mod types;
 
 pub use error::ApiError;
@@ -13,6 +15,8 @@ pub use types::*;
 use async_trait::async_trait;
 use tape_crypto::Address;
 
+use crate::api::stream::SliceBody;
+
 /// Content type for binary request/response bodies.
 pub const BINARY_CONTENT: &str = "application/octet-stream";
 
@@ -24,6 +28,8 @@ pub const JSON_CONTENT: &str = "application/json";
 pub trait Api: Send + Sync + 'static {
     async fn put_slice(&self, node: Address, req: &PutSliceReq) -> Result<PutSliceRes, ApiError>;
     async fn get_slice(&self, node: Address, req: &GetSliceReq) -> Result<GetSliceRes, ApiError>;
+    async fn put_slices(&self, node: Address, req: &PutSlicesReq) -> Result<PutSlicesRes, ApiError>;
+    async fn get_slices(&self, node: Address, req: &GetSlicesReq) -> Result<GetSlicesRes, ApiError>;
     async fn get_track(&self, node: Address, req: &GetTrackReq) -> Result<GetTrackRes, ApiError>;
     async fn get_track_by_number(&self, node: Address, req: &GetTrackByNumberReq) -> Result<GetTrackByNumberRes, ApiError>;
     async fn find_track(&self, node: Address, req: &FindTrackReq) -> Result<FindTrackRes, ApiError>;
@@ -34,6 +40,7 @@ pub trait Api: Send + Sync + 'static {
     async fn sync_slices(&self, node: Address, req: &SyncSlicesReq) -> Result<SyncSlicesRes, ApiError>;
     async fn sync_tracks(&self, node: Address, req: &SyncTracksReq) -> Result<SyncTracksRes, ApiError>;
     async fn repair(&self, node: Address, req: &RepairReq) -> Result<RepairRes, ApiError>;
+    async fn challenge(&self, node: Address, req: &ChallengeReq) -> Result<ChallengeRes, ApiError>;
     async fn certify(&self, node: Address, req: &CertifyReq) -> Result<CertifyRes, ApiError>;
     async fn invalidate(&self, node: Address, req: &InvalidateReq) -> Result<InvalidateRes, ApiError>;
     async fn vote(&self, node: Address, req: &VoteReq) -> Result<VoteRes, ApiError>;
@@ -43,4 +50,21 @@ pub trait Api: Send + Sync + 'static {
     /// Fetch a peer's board as raw JSON bytes, returned verbatim so a proxy can
     /// re-serve them without re-encoding.
     async fn get_observe_board(&self, node: Address) -> Result<Vec<u8>, ApiError>;
+
+    /// Read a slice as it arrives. Transports that do not stream hand back
+    /// the buffered `get_slice` answer.
+    async fn get_slice_body(
+        &self,
+        node: Address,
+        req: &GetSliceReq,
+    ) -> Result<SliceBody, ApiError> {
+        let res = self.get_slice(node, req).await?;
+        Ok(SliceBody::buffered(res.data))
+    }
+
+    /// Read a repair answer as it arrives, like [`Api::get_slice_body`].
+    async fn repair_body(&self, node: Address, req: &RepairReq) -> Result<SliceBody, ApiError> {
+        let res = self.repair(node, req).await?;
+        Ok(SliceBody::buffered(res.data))
+    }
 }
@@ -1,5 +1,7 @@
 //! Request/response types for peer operations.
 
+use std::sync::Arc;
+
 use tape_core::bls::BlsSignature;
 use tape_core::prelude::{BlobData, CompressedTrack, EpochNumber, SpoolIndex, TrackNumber};
 use tape_core::spooler::GroupIndex;
@@ -14,11 +16,13 @@ use wincode_derive::{SchemaRead, SchemaWrite};
 
 use crate::api::ApiError;
 
+/// A slice upload. The payload is shared, so retries and batching never
+/// copy the slice bytes.
 #[derive(Clone, Debug)]
 pub struct PutSliceReq {
     pub track: Address,
     pub spool: SpoolIndex,
-    pub payload: SlicePayload,
+    pub payload: Arc<SlicePayload>,
 }
 
 #[derive(Clone, Debug)]
@@ -35,6 +39,29 @@ pub struct GetSliceRes {
     pub data: Vec<u8>,
 }
 
+/// Many slice uploads to one node in a single round trip. Results line up
+/// with `items`, so one refused slice does not fail the rest.
+#[derive(Clone, Debug)]
+pub struct PutSlicesReq {
+    pub items: Vec<PutSliceReq>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PutSlicesRes {
+    pub results: Vec<Result<PutSliceRes, ApiError>>,
+}
+
+/// Many slice reads from one node in a single round trip, answered in order.
+#[derive(Clone, Debug)]
+pub struct GetSlicesReq {
+    pub items: Vec<GetSliceReq>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GetSlicesRes {
+    pub results: Vec<Result<GetSliceRes, ApiError>>,
+}
+
 #[derive(Clone, Debug)]
 pub struct GetTrackReq {
     pub track: Address,
@@ -162,6 +189,20 @@ pub struct RepairRes {
     pub data: Vec<u8>,
 }
 
+#[derive(Clone, Debug)]
+pub struct ChallengeReq {
+    pub track: Address,
+    pub spool: SpoolIndex,
+    pub stripe: u32,
+    pub epoch: EpochNumber,
+}
+
+#[derive(Clone, Debug)]
+pub struct ChallengeRes {
+    pub data: Vec<u8>,
+    pub segment_proof: Vec<Hash>,
+}
+
 #[derive(Clone, Debug)]
 pub struct CertifyReq {
     pub track: Address,
@@ -218,6 +259,8 @@ pub struct GetStatsRes {
 pub enum PeerReq {
     PutSlice(PutSliceReq),
     GetSlice(GetSliceReq),
+    PutSlices(PutSlicesReq),
+    GetSlices(GetSlicesReq),
     GetTrack(GetTrackReq),
     GetTrackByNumber(GetTrackByNumberReq),
     FindTrack(FindTrackReq),
@@ -228,6 +271,7 @@ pub enum PeerReq {
     SyncSlices(SyncSlicesReq),
     SyncTracks(SyncTracksReq),
     Repair(RepairReq),
+    Challenge(ChallengeReq),
     Certify(CertifyReq),
     Invalidate(InvalidateReq),
     Vote(VoteReq),
@@ -238,6 +282,8 @@ pub enum PeerReq {
 pub enum PeerRes {
     PutSlice(Result<PutSliceRes, ApiError>),
     GetSlice(Result<GetSliceRes, ApiError>),
+    PutSlices(Result<PutSlicesRes, ApiError>),
+    GetSlices(Result<GetSlicesRes, ApiError>),
     GetTrack(Result<GetTrackRes, ApiError>),
     GetTrackByNumber(Result<GetTrackByNumberRes, ApiError>),
     FindTrack(Result<FindTrackRes, ApiError>),
@@ -248,6 +294,7 @@ pub enum PeerRes {
     SyncSlices(Result<SyncSlicesRes, ApiError>),
     SyncTracks(Result<SyncTracksRes, ApiError>),
     Repair(Result<RepairRes, ApiError>),
+    Challenge(Result<ChallengeRes, ApiError>),
     Certify(Result<CertifyRes, ApiError>),
     Invalidate(Result<InvalidateRes, ApiError>),
     Vote(Result<VoteRes, ApiError>),
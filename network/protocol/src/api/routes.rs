@@ -16,6 +16,9 @@ pub const OBSERVE_PEER_PATH: &str = tape_observe_api::PEER_BOARD_PATH;
 
 pub const VOTE_PATH: &str = "/v1/votes";
 
+pub const SLICE_BATCH_GET_PATH: &str = "/v1/slices/batch/get";
+pub const SLICE_BATCH_PUT_PATH: &str = "/v1/slices/batch/put";
+
 pub const SYNC_SLICES_PATH: &str = "/v1/sync/slices";
 pub const SYNC_TRACKS_PATH: &str = "/v1/sync/tracks";
 
@@ -31,6 +34,7 @@ pub const TRACK_PROOF_PATH: &str = "/v1/tracks/{track_id}/proof";
 pub const TRACK_REPAIR_PATH: &str = "/v1/tracks/{track_id}/repair";
 pub const TRACK_SIGN_PATH: &str = "/v1/tracks/{track_id}/sign";
 pub const TRACK_SLICE_PATH: &str = "/v1/tracks/{track_id}/slices/{spool_id}";
+pub const TRACK_SLICE_CHALLENGE_PATH: &str = "/v1/tracks/{track_id}/slices/{spool_id}/challenge";
 pub const TRACK_SLICE_STATUS_PATH: &str = "/v1/tracks/{track_id}/slices/{spool_id}/status";
 pub const TRACK_STATUS_PATH: &str = "/v1/tracks/{track_id}/status";
 
@@ -40,6 +44,10 @@ pub fn slice_url(track_id: &str, spool_id: SpoolIndex) -> String {
     format!("/v1/tracks/{track_id}/slices/{}", spool_id.0)
 }
 
+pub fn challenge_url(track_id: &str, spool_id: SpoolIndex) -> String {
+    format!("/v1/tracks/{track_id}/slices/{}/challenge", spool_id.0)
+}
+
 pub fn status_url(track_id: &str) -> String {
     format!("/v1/tracks/{track_id}/status")
 }
@@ -91,6 +99,10 @@ mod tests {
     #[test]
     fn url_builders() {
         assert_eq!(slice_url("abc", SpoolIndex(5)), "/v1/tracks/abc/slices/5");
+        assert_eq!(
+            challenge_url("abc", SpoolIndex(5)),
+            "/v1/tracks/abc/slices/5/challenge"
+        );
         assert_eq!(track_url("abc"), "/v1/tracks/abc");
         assert_eq!(track_data_url("abc"), "/v1/tracks/abc/data");
         assert_eq!(track_proof_url("abc"), "/v1/tracks/abc/proof");
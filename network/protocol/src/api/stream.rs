@@ -0,0 +1,491 @@
+//! Streamed slice bodies.
+//!
+//! Slice uploads, slice reads, sync pages and repair answers each carry up to
+//! [`SLICE_BYTES_LIMIT`] bytes per slice. Senders hand these bodies to the
+//! transport as frames that concatenate to the same wincode encoding a
+//! buffered body holds, so streaming and buffering peers interoperate.
+//! Receivers pull bodies through a [`BodyReader`], which reads each slice
+//! straight into its own buffer as the frames arrive instead of holding the
+//! whole body next to the decoded value. An upload leads with its leaf, proof
+//! and stripe segment hashes, so the receiver checks every segment before the
+//! next one is read.
+
+use std::error::Error as StdError;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use tape_core::erasure::SLICE_TREE_HEIGHT;
+use tape_core::track::stripe::MAX_SLICE_SEGMENTS;
+use tape_crypto::prelude::Hash;
+
+use crate::api::error::ApiError;
+use crate::api::ops::PutSliceReq;
+use crate::api::types::{SlicePayload, SyncSliceEntry, SyncSlicesResponse, SLICE_BYTES_LIMIT};
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// A slice read or repair answer, handed over as it arrives.
+pub type SliceBody = BodyReader<BoxStream<'static, Result<Bytes, BoxError>>>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StreamError {
+    #[error("transport: {0}")]
+    Transport(BoxError),
+
+    #[error("body ended early")]
+    Truncated,
+
+    #[error("body exceeds {limit} bytes")]
+    TooLarge { limit: u64 },
+
+    #[error("malformed body: {0}")]
+    Malformed(String),
+}
+
+/// Frames of a slice upload body.
+///
+/// The slice bytes go out as one frame that borrows the shared payload, so a
+/// payload sent to several nodes, or sent again, is never copied.
+pub fn slice_payload_frames(payload: Arc<SlicePayload>) -> Vec<Bytes> {
+    let hashes = 1 + payload.merkle_proof.len() + payload.segments.len();
+    let mut head = Vec::with_capacity(hashes * Hash::LEN + 3 * 8);
+    head.extend_from_slice(payload.leaf_hash.as_ref());
+    push_hashes(&mut head, &payload.merkle_proof);
+    push_hashes(&mut head, &payload.segments);
+    head.extend_from_slice(&(payload.data.len() as u64).to_le_bytes());
+
+    vec![Bytes::from(head), Bytes::from_owner(SharedSlice(payload))]
+}
+
+/// Frames of a slice batch upload, each payload shared as in
+/// [`slice_payload_frames`].
+pub fn slice_batch_put_frames(items: &[PutSliceReq]) -> Vec<Bytes> {
+    let mut frames = Vec::with_capacity(1 + 3 * items.len());
+    frames.push(length_prefix(items.len()));
+
+    for item in items {
+        let mut header = Vec::with_capacity(32 + 8);
+        header.extend_from_slice(item.track.as_ref());
+        header.extend_from_slice(&item.spool.0.to_le_bytes());
+        frames.push(Bytes::from(header));
+        frames.extend(slice_payload_frames(item.payload.clone()));
+    }
+    frames
+}
+
+/// Slice bytes of a shared upload payload.
+struct SharedSlice(Arc<SlicePayload>);
+
+impl AsRef<[u8]> for SharedSlice {
+    fn as_ref(&self) -> &[u8] {
+        &self.0.data
+    }
+}
+
+fn push_hashes(out: &mut Vec<u8>, hashes: &[Hash]) {
+    out.extend_from_slice(&(hashes.len() as u64).to_le_bytes());
+    for hash in hashes {
+        out.extend_from_slice(hash.as_ref());
+    }
+}
+
+/// Frames of a sync page, one header and one data frame per entry.
+pub fn sync_slices_frames(response: SyncSlicesResponse) -> Vec<Bytes> {
+    let mut frames = Vec::with_capacity(2 * response.entries.len() + 2);
+    frames.push(length_prefix(response.entries.len()));
+
+    for entry in response.entries {
+        let mut header = Vec::with_capacity(32 + 8);
+        header.extend_from_slice(&entry.track_address);
+        header.extend_from_slice(&(entry.slice_data.len() as u64).to_le_bytes());
+        frames.push(Bytes::from(header));
+        frames.push(Bytes::from(entry.slice_data));
+    }
+
+    let cursor = match response.next_cursor {
+        Some(cursor) => [&[1u8][..], &cursor[..]].concat(),
+        None => vec![0u8],
+    };
+    frames.push(Bytes::from(cursor));
+    frames
+}
+
+fn length_prefix(len: usize) -> Bytes {
+    Bytes::copy_from_slice(&(len as u64).to_le_bytes())
+}
+
+/// A body cut short reads like a dropped connection; anything else the
+/// peer sent is malformed.
+impl From<StreamError> for ApiError {
+    fn from(error: StreamError) -> Self {
+        match error {
+            StreamError::Transport(_) | StreamError::Truncated => {
+                ApiError::ConnectionFailed(error.to_string())
+            }
+            error => ApiError::Serialization(error.to_string()),
+        }
+    }
+}
+
+/// Pulls a body apart as its frames arrive.
+pub struct BodyReader<S> {
+    stream: Pin<Box<S>>,
+    chunk: Bytes,
+    received: u64,
+}
+
+impl<S, E> BodyReader<S>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: Into<BoxError>,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            chunk: Bytes::new(),
+            received: 0,
+        }
+    }
+
+    /// Body bytes received so far.
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    pub async fn read_exact(&mut self, out: &mut [u8]) -> Result<(), StreamError> {
+        let mut filled = 0;
+        while filled < out.len() {
+            if !self.fill().await? {
+                return Err(StreamError::Truncated);
+            }
+            let take = (out.len() - filled).min(self.chunk.len());
+            out[filled..filled + take].copy_from_slice(&self.chunk.split_to(take));
+            filled += take;
+        }
+        Ok(())
+    }
+
+    pub async fn read_u64(&mut self) -> Result<u64, StreamError> {
+        let mut bytes = [0u8; 8];
+        self.read_exact(&mut bytes).await?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    pub async fn read_hash(&mut self) -> Result<Hash, StreamError> {
+        let mut bytes = [0u8; Hash::LEN];
+        self.read_exact(&mut bytes).await?;
+        Ok(Hash::from(bytes))
+    }
+
+    /// Read a slice length prefix, refusing lengths past `limit`.
+    pub async fn read_slice_len(&mut self, limit: usize) -> Result<usize, StreamError> {
+        let limit = limit.min(SLICE_BYTES_LIMIT);
+        let len = self.read_u64().await?;
+        if len > limit as u64 {
+            return Err(StreamError::TooLarge { limit: limit as u64 });
+        }
+        Ok(len as usize)
+    }
+
+    /// Read `len` bytes into one buffer as they land.
+    pub async fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, StreamError> {
+        let mut out = Vec::with_capacity(len);
+        while out.len() < len {
+            if !self.fill().await? {
+                return Err(StreamError::Truncated);
+            }
+            let take = (len - out.len()).min(self.chunk.len());
+            out.extend_from_slice(&self.chunk.split_to(take));
+        }
+        Ok(out)
+    }
+
+    /// Read the merkle path of a slice upload's leaf.
+    pub async fn read_proof(&mut self) -> Result<Vec<Hash>, StreamError> {
+        self.read_hashes(SLICE_TREE_HEIGHT as u64, "merkle proof").await
+    }
+
+    /// Read the stripe segment hashes of a slice upload.
+    pub async fn read_segments(&mut self) -> Result<Vec<Hash>, StreamError> {
+        self.read_hashes(MAX_SLICE_SEGMENTS, "segment list").await
+    }
+
+    async fn read_hashes(&mut self, max: u64, what: &str) -> Result<Vec<Hash>, StreamError> {
+        let count = self.read_u64().await?;
+        if count > max {
+            return Err(StreamError::Malformed(format!("{what} of {count} hashes")));
+        }
+        let mut hashes = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            hashes.push(self.read_hash().await?);
+        }
+        Ok(hashes)
+    }
+
+    /// Read the rest of the body, up to `limit` bytes.
+    pub async fn read_to_end(
+        &mut self,
+        limit: usize,
+        size_hint: Option<u64>,
+    ) -> Result<Vec<u8>, StreamError> {
+        let capacity = size_hint.unwrap_or(0).min(limit as u64);
+        let mut out = Vec::with_capacity(capacity as usize);
+        while self.fill().await? {
+            if out.len() + self.chunk.len() > limit {
+                return Err(StreamError::TooLarge { limit: limit as u64 });
+            }
+            out.extend_from_slice(&self.chunk);
+            self.chunk.clear();
+        }
+        Ok(out)
+    }
+
+    /// Check that nothing follows what was read.
+    pub async fn finish(mut self) -> Result<(), StreamError> {
+        if self.fill().await? {
+            return Err(StreamError::Malformed("trailing bytes".into()));
+        }
+        Ok(())
+    }
+
+    /// Make sure some unread bytes are buffered; false at the end of the body.
+    async fn fill(&mut self) -> Result<bool, StreamError> {
+        while self.chunk.is_empty() {
+            match self.stream.next().await {
+                Some(Ok(chunk)) => {
+                    self.received += chunk.len() as u64;
+                    self.chunk = chunk;
+                }
+                Some(Err(error)) => return Err(StreamError::Transport(error.into())),
+                None => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl SliceBody {
+    /// A body already in memory, for transports that do not stream.
+    pub fn buffered(data: Vec<u8>) -> Self {
+        let body = futures::stream::once(async move { Ok::<_, BoxError>(Bytes::from(data)) });
+        Self::new(body.boxed())
+    }
+}
+
+/// Read a sync page of at most `max_entries` entries.
+pub async fn read_sync_slices<S, E>(
+    reader: &mut BodyReader<S>,
+    max_entries: usize,
+) -> Result<SyncSlicesResponse, StreamError>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: Into<BoxError>,
+{
+    let count = reader.read_u64().await?;
+    if count > max_entries as u64 {
+        return Err(StreamError::Malformed(format!(
+            "sync page of {count} entries, asked for {max_entries}"
+        )));
+    }
+
+    let mut entries = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let mut track_address = [0u8; 32];
+        reader.read_exact(&mut track_address).await?;
+        let len = reader.read_slice_len(SLICE_BYTES_LIMIT).await?;
+        let slice_data = reader.read_bytes(len).await?;
+        entries.push(SyncSliceEntry {
+            track_address,
+            slice_data,
+        });
+    }
+
+    let mut tag = [0u8; 1];
+    reader.read_exact(&mut tag).await?;
+    let next_cursor = match tag[0] {
+        0 => None,
+        1 => {
+            let mut cursor = [0u8; 32];
+            reader.read_exact(&mut cursor).await?;
+            Some(cursor)
+        }
+        tag => return Err(StreamError::Malformed(format!("cursor tag {tag}"))),
+    };
+
+    Ok(SyncSlicesResponse {
+        entries,
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures::stream;
+    use tape_core::types::SpoolIndex;
+    use tape_crypto::prelude::Address;
+
+    use super::*;
+    use crate::api::types::{SliceBatchPutItem, SliceBatchPutRequest};
+
+    // re-split frames at odd boundaries, the way a transport may deliver them
+    fn delivered(frames: Vec<Bytes>, piece: usize) -> Vec<Result<Bytes, Infallible>> {
+        let body = frames.concat();
+        body.chunks(piece)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect()
+    }
+
+    fn payload() -> Arc<SlicePayload> {
+        let data = (0..5_000u32).map(|byte| byte as u8).collect();
+        let proof = (0..SLICE_TREE_HEIGHT as u8).map(|byte| Hash::from([byte; 32])).collect();
+        let segments = (0..4u8).map(|byte| Hash::from([0x40 + byte; 32])).collect();
+        Arc::new(SlicePayload::new(data, Hash::from([0x77; 32]), proof, segments))
+    }
+
+    // the frames of both bodies are their wincode encodings
+    #[test]
+    fn frames_match_wincode() {
+        let payload = payload();
+        let encoded = wincode::serialize(payload.as_ref()).unwrap();
+        assert_eq!(slice_payload_frames(payload.clone()).concat(), encoded);
+
+        let items: Vec<PutSliceReq> = (0..2u8)
+            .map(|index| PutSliceReq {
+                track: Address::new([index; 32]),
+                spool: SpoolIndex(40 + index as u64),
+                payload: payload.clone(),
+            })
+            .collect();
+        let batch = SliceBatchPutRequest {
+            items: items
+                .iter()
+                .map(|item| SliceBatchPutItem {
+                    track_address: item.track,
+                    spool: item.spool,
+                    payload: payload.as_ref().clone(),
+                })
+                .collect(),
+        };
+        let encoded = wincode::serialize(&batch).unwrap();
+        assert_eq!(slice_batch_put_frames(&items).concat(), encoded);
+
+        let response = SyncSlicesResponse {
+            entries: vec![
+                SyncSliceEntry {
+                    track_address: [1; 32],
+                    slice_data: vec![0xAA; 300],
+                },
+                SyncSliceEntry {
+                    track_address: [2; 32],
+                    slice_data: Vec::new(),
+                },
+            ],
+            next_cursor: Some([2; 32]),
+        };
+        let encoded = wincode::serialize(&response).unwrap();
+        assert_eq!(sync_slices_frames(response).concat(), encoded);
+
+        let last = SyncSlicesResponse {
+            entries: Vec::new(),
+            next_cursor: None,
+        };
+        let encoded = wincode::serialize(&last).unwrap();
+        assert_eq!(sync_slices_frames(last).concat(), encoded);
+    }
+
+    // an upload reads back piece by piece across frame boundaries
+    #[tokio::test]
+    async fn reads_payload() {
+        let payload = payload();
+        let chunks = delivered(slice_payload_frames(payload.clone()), 777);
+        let mut reader = BodyReader::new(stream::iter(chunks));
+
+        assert_eq!(reader.read_hash().await.unwrap(), payload.leaf_hash);
+        assert_eq!(reader.read_proof().await.unwrap(), payload.merkle_proof);
+        assert_eq!(reader.read_segments().await.unwrap(), payload.segments);
+        let len = reader.read_slice_len(SLICE_BYTES_LIMIT).await.unwrap();
+        let data = reader.read_bytes(len).await.unwrap();
+        assert_eq!(data, payload.data);
+        let encoded = wincode::serialize(payload.as_ref()).unwrap();
+        assert_eq!(reader.received(), encoded.len() as u64);
+        reader.finish().await.unwrap();
+    }
+
+    async fn read_head<S>(reader: &mut BodyReader<S>)
+    where
+        S: Stream<Item = Result<Bytes, Infallible>>,
+    {
+        reader.read_hash().await.unwrap();
+        reader.read_proof().await.unwrap();
+        reader.read_segments().await.unwrap();
+    }
+
+    // oversized, short, overlong and over-segmented bodies are refused
+    #[tokio::test]
+    async fn refuses_bad_bodies() {
+        let frames = slice_payload_frames(payload());
+
+        let mut reader = BodyReader::new(stream::iter(delivered(frames.clone(), 64)));
+        read_head(&mut reader).await;
+        let refused = reader.read_slice_len(1_000).await;
+        assert!(matches!(refused, Err(StreamError::TooLarge { limit: 1_000 })));
+
+        let mut short = frames.concat();
+        short.truncate(600);
+        let mut reader = BodyReader::new(stream::iter(delivered(vec![short.into()], 64)));
+        read_head(&mut reader).await;
+        let len = reader.read_slice_len(SLICE_BYTES_LIMIT).await.unwrap();
+        assert!(matches!(reader.read_bytes(len).await, Err(StreamError::Truncated)));
+
+        let mut overlong = frames.concat();
+        overlong.push(0);
+        let mut reader = BodyReader::new(stream::iter(delivered(vec![overlong.into()], 64)));
+        read_head(&mut reader).await;
+        let len = reader.read_slice_len(SLICE_BYTES_LIMIT).await.unwrap();
+        reader.read_bytes(len).await.unwrap();
+        assert!(matches!(reader.finish().await, Err(StreamError::Malformed(_))));
+
+        let mut segments = wincode::serialize(&vec![Hash::default(); 300]).unwrap();
+        segments.splice(0..0, [0u8; Hash::LEN + 8]);
+        let mut reader = BodyReader::new(stream::iter(delivered(vec![segments.into()], 64)));
+        reader.read_hash().await.unwrap();
+        reader.read_proof().await.unwrap();
+        assert!(matches!(reader.read_segments().await, Err(StreamError::Malformed(_))));
+
+        let body = vec![Ok::<_, Infallible>(Bytes::from(vec![0u8; 600]))];
+        let mut reader = BodyReader::new(stream::iter(body));
+        let refused = reader.read_to_end(512, Some(600)).await;
+        assert!(matches!(refused, Err(StreamError::TooLarge { limit: 512 })));
+    }
+
+    // a sync page decodes from its frames and respects the requested size
+    #[tokio::test]
+    async fn reads_sync_page() {
+        let response = SyncSlicesResponse {
+            entries: (0..3u8)
+                .map(|index| SyncSliceEntry {
+                    track_address: [index; 32],
+                    slice_data: vec![index; 1_000],
+                })
+                .collect(),
+            next_cursor: Some([2; 32]),
+        };
+
+        let chunks = delivered(sync_slices_frames(response.clone()), 301);
+        let mut reader = BodyReader::new(stream::iter(chunks));
+        assert_eq!(read_sync_slices(&mut reader, 3).await.unwrap(), response);
+        reader.finish().await.unwrap();
+
+        let chunks = delivered(sync_slices_frames(response), 301);
+        let mut reader = BodyReader::new(stream::iter(chunks));
+        assert!(matches!(
+            read_sync_slices(&mut reader, 2).await,
+            Err(StreamError::Malformed(_))
+        ));
+    }
+}
@@ -9,21 +9,38 @@ use tape_core::{
 };
 pub use tape_core::system::VoteCandidate;
 use tape_core::prelude::{BlobData, EpochNumber, SpoolIndex, TrackNumber};
+use tape_core::track::stripe::MAX_SLICE_SEGMENTS;
 use tape_core::track::types::{PackedTrack, PackedTrackProof};
-use tape_core::types::{ContentType, SlotNumber, SpoolBitmap, StorageUnits};
+use tape_core::types::{ContentType, ObjectHeaders, SlotNumber, SpoolBitmap, StorageUnits};
 use tape_crypto::prelude::{Address, Hash};
 use wincode::containers::{Pod, Vec as WincodeVec};
 use wincode::len::BincodeLen;
 use wincode_derive::{SchemaRead, SchemaWrite};
 
 use crate::api::ops::FindTrackVersion;
+use crate::api::ApiError;
 
 pub const SLICE_BYTES_LIMIT: usize = 10 * 1024 * 1024;
 pub const SLICE_BODY_LIMIT: usize = size_of::<u64>()
     + SLICE_BYTES_LIMIT
     + Hash::LEN
     + size_of::<u64>()
-    + (SLICE_TREE_HEIGHT * Hash::LEN);
+    + (SLICE_TREE_HEIGHT * Hash::LEN)
+    + size_of::<u64>()
+    + (MAX_SLICE_SEGMENTS as usize * Hash::LEN);
+
+/// Most (track, spool) items one slice batch request may carry.
+pub const SLICE_BATCH_ITEMS_LIMIT: usize = 256;
+
+/// Slice bytes one batch read answers with; items past it come back deferred.
+pub const SLICE_BATCH_BYTES_LIMIT: usize = SLICE_BYTES_LIMIT;
+
+/// Room per batch item for its status and length framing.
+const SLICE_BATCH_ITEM_OVERHEAD: usize = 256;
+
+pub const SLICE_BATCH_BODY_LIMIT: usize = size_of::<u64>()
+    + SLICE_BATCH_BYTES_LIMIT
+    + (SLICE_BATCH_ITEMS_LIMIT * SLICE_BATCH_ITEM_OVERHEAD);
 
 type SliceBytes = WincodeVec<Pod<u8>, BincodeLen<SLICE_BYTES_LIMIT>>;
 
@@ -173,24 +190,50 @@ impl From<&NodeStats> for tape_observe_api::NodeStats {
 }
 
 /// Payload for slice upload requests.
+///
+/// The leaf, its merkle path and the stripe segment hashes come ahead of the
+/// slice bytes, so a receiver checks each segment as it arrives.
 #[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
 pub struct SlicePayload {
-    #[wincode(with = "SliceBytes")]
-    pub data: Vec<u8>,
     pub leaf_hash: Hash,
     pub merkle_proof: Vec<Hash>,
+    pub segments: Vec<Hash>,
+    #[wincode(with = "SliceBytes")]
+    pub data: Vec<u8>,
 }
 
 impl SlicePayload {
-    pub fn new(data: Vec<u8>, leaf_hash: Hash, merkle_proof: Vec<Hash>) -> Self {
+    pub fn new(
+        data: Vec<u8>,
+        leaf_hash: Hash,
+        merkle_proof: Vec<Hash>,
+        segments: Vec<Hash>,
+    ) -> Self {
         Self {
-            data,
             leaf_hash,
             merkle_proof,
+            segments,
+            data,
         }
     }
 }
 
+/// Request for a proof-of-storage challenge answer.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct ChallengeRequest {
+    pub epoch: EpochNumber,
+    pub stripe: u32,
+}
+
+/// Answer to a proof-of-storage challenge: the slice segment holding the
+/// challenged stripe and its path to the slice leaf.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct ChallengeResponse {
+    #[wincode(with = "SliceBytes")]
+    pub data: Vec<u8>,
+    pub segment_proof: Vec<Hash>,
+}
+
 /// Request for slice synchronization.
 #[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
 pub struct SyncSlicesRequest {
@@ -216,6 +259,86 @@ pub struct SyncSliceEntry {
     pub slice_data: Vec<u8>,
 }
 
+/// Outcome of one item in a slice batch, standing in for the status a single
+/// slice request would have answered with.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub enum SliceBatchStatus {
+    Ok,
+    NotFound,
+    NotResponsible,
+    BlacklistedObject,
+    Rejected(String),
+    Failed(String),
+    Deferred,
+}
+
+impl SliceBatchStatus {
+    pub fn into_result(self) -> Result<(), ApiError> {
+        match self {
+            Self::Ok => Ok(()),
+            Self::NotFound => Err(ApiError::NotFound),
+            Self::NotResponsible => Err(ApiError::NotResponsible),
+            Self::BlacklistedObject => Err(ApiError::BlacklistedObject),
+            Self::Rejected(message) => Err(ApiError::ServerError {
+                status: 400,
+                message,
+            }),
+            Self::Failed(message) => Err(ApiError::ServerError {
+                status: 500,
+                message,
+            }),
+            Self::Deferred => Err(ApiError::Deferred),
+        }
+    }
+}
+
+/// One slice upload inside a batch.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct SliceBatchPutItem {
+    pub track_address: Address,
+    pub spool: SpoolIndex,
+    pub payload: SlicePayload,
+}
+
+/// Request carrying slice uploads for many tracks at once.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct SliceBatchPutRequest {
+    pub items: Vec<SliceBatchPutItem>,
+}
+
+/// Response to a slice batch upload, one status per request item.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct SliceBatchPutResponse {
+    pub results: Vec<SliceBatchStatus>,
+}
+
+/// One slice read inside a batch.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct SliceBatchGetItem {
+    pub track_address: Address,
+    pub spool: SpoolIndex,
+}
+
+/// Request reading slices for many tracks at once.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct SliceBatchGetRequest {
+    pub items: Vec<SliceBatchGetItem>,
+}
+
+/// Response to a slice batch read, one result per request item.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct SliceBatchGetResponse {
+    pub results: Vec<SliceBatchGetResult>,
+}
+
+/// A read item's status and, when it is `Ok`, the slice bytes.
+#[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
+pub struct SliceBatchGetResult {
+    pub status: SliceBatchStatus,
+    #[wincode(with = "SliceBytes")]
+    pub data: Vec<u8>,
+}
+
 /// Request for track-data synchronization.
 #[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
 pub struct SyncTracksRequest {
@@ -282,6 +405,7 @@ pub struct ObjectListItem {
     pub track_number: TrackNumber,
     pub kind: u64,
     pub content_type: ContentType,
+    pub headers: ObjectHeaders,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, SchemaRead, SchemaWrite)]
@@ -309,6 +433,7 @@ mod tests {
     use tape_core::erasure::GROUP_SIZE;
     use tape_core::system::VoteKind;
     use tape_core::track::blob::BlobEncoding;
+    use tape_core::track::stripe::STRIPE_TREE_HEIGHT;
     use tape_core::types::{StorageUnits, StripeCount};
     use tape_crypto::bls12254::min_sig::G1CompressedPoint;
 
@@ -324,13 +449,16 @@ mod tests {
         let leaf_hash = Hash::from([0x11; 32]);
         let proof = vec![Hash::from([0x22; 32]); SLICE_TREE_HEIGHT];
 
-        let payload = SlicePayload::new(data.clone(), leaf_hash, proof.clone());
+        let segments = vec![Hash::from([0x33; 32]); 4];
+
+        let payload = SlicePayload::new(data.clone(), leaf_hash, proof.clone(), segments.clone());
         let bytes = wincode::serialize(&payload).unwrap();
         let recovered: SlicePayload = wincode::deserialize(&bytes).unwrap();
 
         assert_eq!(recovered.data, data);
         assert_eq!(recovered.leaf_hash, leaf_hash);
         assert_eq!(recovered.merkle_proof, proof);
+        assert_eq!(recovered.segments, segments);
     }
 
     #[test]
@@ -346,6 +474,7 @@ mod tests {
             vec![0xAB; (4 * 1024 * 1024) + 1],
             Hash::from([0x11; 32]),
             vec![Hash::from([0x22; 32]); SLICE_TREE_HEIGHT],
+            vec![Hash::from([0x33; 32]); MAX_SLICE_SEGMENTS as usize],
         );
 
         let bytes = wincode::serialize(&payload).unwrap();
@@ -361,6 +490,7 @@ mod tests {
             vec![0xAB; SLICE_BYTES_LIMIT + 1],
             Hash::from([0x11; 32]),
             vec![Hash::from([0x22; 32]); SLICE_TREE_HEIGHT],
+            vec![Hash::from([0x33; 32]); MAX_SLICE_SEGMENTS as usize],
         );
 
         let bytes = wincode::serialize(&payload).unwrap();
@@ -376,6 +506,7 @@ mod tests {
             vec![0xAB; SLICE_BYTES_LIMIT],
             Hash::from([0x11; 32]),
             vec![Hash::from([0x22; 32]); SLICE_TREE_HEIGHT],
+            vec![Hash::from([0x33; 32]); MAX_SLICE_SEGMENTS as usize],
         );
 
         let bytes = wincode::serialize(&payload).unwrap();
@@ -383,6 +514,49 @@ mod tests {
         assert_eq!(bytes.len(), SLICE_BODY_LIMIT);
     }
 
+    #[test]
+    fn slice_batch_roundtrip() {
+        let put = SliceBatchPutRequest {
+            items: vec![SliceBatchPutItem {
+                track_address: address(3),
+                spool: SpoolIndex(41),
+                payload: SlicePayload::new(
+                    vec![0xAB; 64],
+                    Hash::from([0x11; 32]),
+                    vec![Hash::from([0x22; 32]); SLICE_TREE_HEIGHT],
+                    vec![Hash::from([0x33; 32]); 2],
+                ),
+            }],
+        };
+        let bytes = wincode::serialize(&put).unwrap();
+        let decoded: SliceBatchPutRequest = wincode::deserialize(&bytes).unwrap();
+        assert_eq!(put, decoded);
+
+        let got = SliceBatchGetResponse {
+            results: vec![
+                SliceBatchGetResult {
+                    status: SliceBatchStatus::Ok,
+                    data: vec![0xCD; 32],
+                },
+                SliceBatchGetResult {
+                    status: SliceBatchStatus::Rejected("bad spool".into()),
+                    data: Vec::new(),
+                },
+                SliceBatchGetResult {
+                    status: SliceBatchStatus::Deferred,
+                    data: Vec::new(),
+                },
+            ],
+        };
+        let bytes = wincode::serialize(&got).unwrap();
+        let decoded: SliceBatchGetResponse = wincode::deserialize(&bytes).unwrap();
+        assert_eq!(got, decoded);
+        assert!(matches!(
+            decoded.results[1].status.clone().into_result(),
+            Err(ApiError::ServerError { status: 400, .. })
+        ));
+    }
+
     #[test]
     fn sign_response() {
         let resp = BlsSignResponse {
@@ -456,6 +630,25 @@ mod tests {
         assert_eq!(req, decoded);
     }
 
+    #[test]
+    fn challenge() {
+        let req = ChallengeRequest {
+            epoch: EpochNumber(9),
+            stripe: 3,
+        };
+        let bytes = wincode::serialize(&req).unwrap();
+        let decoded: ChallengeRequest = wincode::deserialize(&bytes).unwrap();
+        assert_eq!(req, decoded);
+
+        let resp = ChallengeResponse {
+            data: vec![0xAB; 2048],
+            segment_proof: vec![Hash::from([0x22; 32]); STRIPE_TREE_HEIGHT],
+        };
+        let bytes = wincode::serialize(&resp).unwrap();
+        let decoded: ChallengeResponse = wincode::deserialize(&bytes).unwrap();
+        assert_eq!(resp, decoded);
+    }
+
     #[test]
     fn sync_slices_request() {
         let req = SyncSlicesRequest {
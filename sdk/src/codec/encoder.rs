@@ -3,14 +3,15 @@
 //! This module provides `BlobEncoder` which wraps slicers to encode
 //! raw blobs into network-ready slices with merkle commitments.
 
-use tape_core::encoding::{EncodingProfile, EncodingType};
+use tape_core::encoding::{EncodingProfile, EncodingType, LeafScheme};
 use tape_core::erasure::GROUP_SIZE;
-use tape_core::types::SpoolIndex;
+use tape_core::track::stripe::{segment_hashes, segments_root};
+use tape_core::types::{SpoolIndex, StripeCount};
 use tape_crypto::merkle::{create_proof_from_leaf_hashes, hash_leaf, root_from_leaf_hashes};
 use tape_crypto::Hash;
 use tape_slicer::{
     ClayCoder, ReedSolomonCoder, Slicer, ErasureCoder, SLICE_TREE_HEIGHT,
-    build_blob_merkle_tree, BlobMerkleRoot, DEFAULT_STRIPE_SIZE,
+    build_blob_merkle_tree, num_stripes, pick_stripe_size, BlobMerkleRoot, DEFAULT_STRIPE_SIZE,
 };
 
 use crate::error::UploadError;
@@ -101,6 +102,13 @@ impl BlobEncoder {
         self.profile
     }
 
+    /// Get the leaf scheme the profile commits slices under.
+    fn leaf_scheme(&self) -> Result<LeafScheme, UploadError> {
+        self.profile
+            .leaf_scheme()
+            .ok_or_else(|| UploadError::Encoding("unknown leaf scheme".into()))
+    }
+
     /// Internal encoding dispatch that returns the raw chunks.
     fn encode_internal(&mut self, data: &[u8]) -> Result<Vec<Vec<u8>>, UploadError> {
         match self.encoding_type() {
@@ -167,10 +175,11 @@ impl BlobEncoder {
         &mut self,
         data: Vec<u8>,
     ) -> Result<(Vec<(SpoolIndex, Vec<u8>)>, BlobMerkleRoot), UploadError> {
+        let scheme = self.leaf_scheme()?;
         let chunks = self.encode_internal(&data)?;
 
         // Build Merkle tree from slices to compute root
-        let tree = build_blob_merkle_tree(&chunks);
+        let tree = build_blob_merkle_tree(&chunks, scheme, blob_stripes(&data));
         let root = tree.root();
 
         let output: Vec<(SpoolIndex, Vec<u8>)> = chunks
@@ -195,10 +204,11 @@ impl BlobEncoder {
         &mut self,
         data: Vec<u8>,
     ) -> Result<(Vec<Vec<u8>>, BlobMerkleRoot), UploadError> {
+        let scheme = self.leaf_scheme()?;
         let chunks = self.encode_internal(&data)?;
 
         // Build Merkle tree from slices
-        let tree = build_blob_merkle_tree(&chunks);
+        let tree = build_blob_merkle_tree(&chunks, scheme, blob_stripes(&data));
         let root = tree.root();
 
         Ok((chunks, root))
@@ -221,11 +231,16 @@ impl BlobEncoder {
         &mut self,
         data: Vec<u8>,
     ) -> Result<(Vec<SliceWithProof>, BlobMerkleRoot), UploadError> {
+        let scheme = self.leaf_scheme()?;
         let chunks = self.encode_internal(&data)?;
-
-        // Hash each slice once, then reuse the hashes for the root, proofs,
-        // and per-slice leaf hash stored in the upload payload.
-        let leaf_hashes: Vec<Hash> = chunks.iter().map(|chunk| hash_leaf(chunk)).collect();
+        let stripes = blob_stripes(&data);
+
+        // Hash each slice's segments once, then reuse the hashes for the
+        // leaves, root, proofs and the segment list of the upload payload.
+        let (segments, leaf_hashes): (Vec<Vec<Hash>>, Vec<Hash>) = chunks
+            .iter()
+            .map(|chunk| slice_commitment(chunk, scheme, stripes))
+            .unzip();
         let root = root_from_leaf_hashes::<SLICE_TREE_HEIGHT>(&leaf_hashes);
 
         let proofs: Result<Vec<Vec<Hash>>, _> = (0..leaf_hashes.len())
@@ -235,10 +250,11 @@ impl BlobEncoder {
 
         // Generate proof for each slice
         let mut output = Vec::with_capacity(chunks.len());
-        for (idx, ((chunk, leaf_hash), proof_vec)) in chunks
+        for (idx, (((chunk, leaf_hash), proof_vec), segments)) in chunks
             .into_iter()
             .zip(leaf_hashes.into_iter())
             .zip(proofs)
+            .zip(segments)
             .enumerate()
         {
 
@@ -253,6 +269,7 @@ impl BlobEncoder {
                 chunk,
                 leaf_hash,
                 proof_arr,
+                segments,
             ));
         }
 
@@ -276,6 +293,28 @@ impl BlobEncoder {
     }
 }
 
+/// Segment hashes of a slice and the leaf they rebuild under `scheme`. A
+/// whole-slice leaf is its own single segment.
+fn slice_commitment(chunk: &[u8], scheme: LeafScheme, stripes: StripeCount) -> (Vec<Hash>, Hash) {
+    match scheme {
+        LeafScheme::Whole => {
+            let leaf = hash_leaf(chunk);
+            (vec![leaf], leaf)
+        }
+        LeafScheme::Segmented => {
+            let hashes = segment_hashes(chunk, stripes);
+            let leaf = segments_root(&hashes).expect("segment count");
+            (hashes, leaf)
+        }
+    }
+}
+
+/// Stripe count the slicer cuts a blob of this data into, as registered on
+/// chain.
+fn blob_stripes(data: &[u8]) -> StripeCount {
+    StripeCount(num_stripes(data.len(), pick_stripe_size(data.len())) as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -404,8 +443,6 @@ mod tests {
 
     #[test]
     fn test_encode_with_proofs_has_leaf_hash() {
-        use tape_crypto::merkle::hash_leaf;
-
         let mut encoder = test_encoder();
         let data = vec![0xEF; 10_000];
         let (slices_with_proofs, _) = encoder.encode_with_proofs(data).unwrap();
@@ -414,6 +451,26 @@ mod tests {
         for slice in &slices_with_proofs {
             let expected_leaf = hash_leaf(&slice.data);
             assert_eq!(slice.leaf_hash, expected_leaf);
+            assert_eq!(slice.segments, vec![expected_leaf]);
+        }
+    }
+
+    #[test]
+    fn test_encode_with_proofs_segmented_leaves() {
+        use tape_core::track::stripe::slice_leaf;
+
+        let profile = EncodingProfile::basic_default().with_leaf_scheme(LeafScheme::Segmented);
+        let mut encoder = BlobEncoder::with_profile(profile);
+        let data = vec![0xEF; 10_000];
+        let stripes = blob_stripes(&data);
+        let (_, root1) = encoder.encode_with_root(data.clone()).unwrap();
+        let (slices_with_proofs, root2) = encoder.encode_with_proofs(data).unwrap();
+        assert_eq!(root1, root2);
+
+        for slice in &slices_with_proofs {
+            let expected_leaf = slice_leaf(&slice.data, stripes);
+            assert_eq!(slice.leaf_hash, expected_leaf);
+            assert_eq!(segments_root(&slice.segments), Some(expected_leaf));
         }
     }
 
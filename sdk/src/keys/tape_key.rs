@@ -12,7 +12,9 @@ use crate::keys::helpers::{load_ed25519_keypair, HelperError};
 ///
 /// Each tape has exactly one key, and each key controls exactly one tape.
 /// The tape's on-chain address is derived from this key, you don't need
-/// to store the address separately.
+/// to store the address separately. The exception is a tape received through
+/// an ownership transfer: its address stays derived from the previous owner,
+/// so point the key at it with [`TapeKey::with_tape`].
 ///
 /// **Keep this key safe.** Anyone with it can write to, delete from, or
 /// destroy the tape. You can share the *address* freely for reads.
@@ -29,6 +31,8 @@ use crate::keys::helpers::{load_ed25519_keypair, HelperError};
 /// ```
 pub struct TapeKey {
     keypair: Keypair,
+    /// Tape operated instead of the derived one, set for a received tape
+    tape: Option<Address>,
 }
 
 impl TapeKey {
@@ -37,13 +41,17 @@ impl TapeKey {
         let mut rng = rand::thread_rng();
         Self {
             keypair: Keypair::new(&mut rng),
+            tape: None,
         }
     }
 
     /// Load from a Solana-compatible JSON keypair file.
     pub fn load(path: impl AsRef<Path>) -> Result<Self, HelperError> {
         let keypair = load_ed25519_keypair(path.as_ref())?;
-        Ok(Self { keypair })
+        Ok(Self {
+            keypair,
+            tape: None,
+        })
     }
 
     /// Save to a JSON keypair file. Creates parent directories if needed.
@@ -59,10 +67,19 @@ impl TapeKey {
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
     }
 
+    /// Operate `tape` with this key, e.g. a tape whose ownership this key
+    /// accepted. Only the key is saved; the tape must be set again on load.
+    pub fn with_tape(mut self, tape: Address) -> Self {
+        self.tape = Some(tape);
+        self
+    }
+
     /// The on-chain address of the tape this key controls.
-    /// This is a PDA derived from the key, safe to share publicly.
+    /// This is a PDA derived from the key unless [`TapeKey::with_tape`] set
+    /// one, safe to share publicly.
     pub fn address(&self) -> Address {
-        tape_pda(self.keypair.address()).0
+        self.tape
+            .unwrap_or_else(|| tape_pda(self.keypair.address()).0)
     }
 
     /// The underlying public key (the authority). Rarely needed directly.
@@ -87,6 +104,15 @@ mod tests {
         assert_eq!(key.address(), tape_pda(key.pubkey().into()).0);
     }
 
+    // a received tape keeps the previous owner's address
+    #[test]
+    fn with_tape() {
+        let previous = TapeKey::generate();
+        let key = TapeKey::generate().with_tape(previous.address());
+        assert_eq!(key.address(), previous.address());
+        assert_ne!(key.address(), tape_pda(key.pubkey().into()).0);
+    }
+
     #[test]
     fn save_and_load() {
         let dir = std::env::temp_dir().join("tape_key_test");
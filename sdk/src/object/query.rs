@@ -23,6 +23,7 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
             size: entry.size.to_bytes(),
             etag: entry.etag,
             content_type: entry.content_type,
+            headers: entry.headers,
             block_time: entry.block_time,
             slot: entry.slot,
         })
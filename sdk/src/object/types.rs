@@ -1,4 +1,4 @@
-use tape_core::types::{ContentType, SlotNumber, StorageUnits, TrackNumber};
+use tape_core::types::{ContentType, ObjectHeaders, SlotNumber, StorageUnits, TrackNumber};
 use tape_crypto::Hash;
 use tape_crypto::prelude::Address;
 
@@ -9,6 +9,7 @@ pub struct ObjectMeta {
     pub size: u64,
     pub etag: Hash,
     pub content_type: ContentType,
+    pub headers: ObjectHeaders,
     pub block_time: Option<i64>,
     pub slot: SlotNumber,
 }
@@ -24,6 +25,7 @@ pub struct ListedObject {
     pub track_number: TrackNumber,
     pub kind: u64,
     pub content_type: ContentType,
+    pub headers: ObjectHeaders,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -89,6 +91,7 @@ impl From<tape_protocol::api::ObjectListItem> for ListedObject {
             track_number: value.track_number,
             kind: value.kind,
             content_type: value.content_type,
+            headers: value.headers,
         }
     }
 }
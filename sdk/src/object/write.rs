@@ -1,6 +1,6 @@
 use rpc::Rpc;
 use tape_api::program::tapedrive::track_pda;
-use tape_core::types::ContentType;
+use tape_core::types::ObjectHeaders;
 use tape_crypto::prelude::Address;
 use tape_protocol::Api;
 
@@ -11,6 +11,9 @@ use crate::tapedrive::Tapedrive;
 
 impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
     /// Write a named object into a bucket.
+    ///
+    /// Objects over one track, and every non-empty object when a key provider
+    /// is attached, are written as streams.
     pub async fn put_object(
         &self,
         bucket: &TapeKey,
@@ -18,19 +21,20 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         data: &[u8],
         content_type: Option<&str>,
     ) -> Result<Address, TapedriveError> {
-        let content_type = content_type
-            .map(ContentType::from_str)
-            .unwrap_or(ContentType::Unknown);
+        let headers = content_type
+            .map(ObjectHeaders::with_content_type)
+            .unwrap_or_default();
 
-        if data.len() > MAX_TRACK_SIZE {
+        let sealed = self.key_provider.is_some() && !data.is_empty();
+        if data.len() > MAX_TRACK_SIZE || sealed {
             let receipt = self
-                .write_named_bytes(bucket, name, content_type, data)
+                .write_named_bytes(bucket, name, headers, data)
                 .await?;
 
             Ok(receipt.manifest)
         } else {
             let track = self
-                .write_named_track(bucket, name, content_type, data)
+                .write_named_track(bucket, name, headers, data)
                 .await?;
 
             Ok(track_pda(track.tape, track.track_number).0)
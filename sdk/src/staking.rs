@@ -9,7 +9,7 @@ use tape_api::errors::TapeError;
 use tape_api::helpers::build_authority_with_tokens_ix;
 use tape_api::instruction::{
     build_advance_pool_ix, build_request_stake_unlock_ix, build_stake_with_pool_ix,
-    build_unstake_from_pool_ix,
+    build_unstake_from_pool_ix, build_upgrade_archive_ix, build_upgrade_node_ix,
 };
 use tape_api::program::tapedrive::{history_pda, track_pda};
 use tape_core::staking::{PoolRate, RateSpan};
@@ -84,6 +84,29 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         Ok(())
     }
 
+    /// Grow a node registered before slashing to the current account layout.
+    /// The payer covers the extra rent; anyone may upgrade any node.
+    pub async fn upgrade_node(&self, node: Address) -> Result<(), TapedriveError> {
+        let payer = self.payer()?;
+        let ix = build_upgrade_node_ix(payer.pubkey().into(), node);
+
+        self.rpc().send_instructions(payer, vec![ix]).await?;
+
+        Ok(())
+    }
+
+    /// Grow an archive created before slashing to the current account layout,
+    /// turning slashing on at the default rate. `AdvanceEpoch` does this on
+    /// its own; call this to upgrade before the next epoch.
+    pub async fn upgrade_archive(&self) -> Result<(), TapedriveError> {
+        let payer = self.payer()?;
+        let ix = build_upgrade_archive_ix(payer.pubkey().into());
+
+        self.rpc().send_instructions(payer, vec![ix]).await?;
+
+        Ok(())
+    }
+
     /// Request unlock of a delegated stake from a pool.
     pub async fn request_stake_unlock(
         &self,
@@ -0,0 +1,103 @@
+//! Opt-in compression for stream chunks.
+//!
+//! Each chunk is compressed into its own frame before it is sealed and erasure
+//! coded, so a ranged read only decompresses the chunks it touches. A chunk
+//! whose frame would not be smaller is stored as is; readers tell the two apart
+//! because a frame is always strictly shorter than the chunk it holds.
+
+use serde::{Deserialize, Serialize};
+use wincode_derive::{SchemaRead, SchemaWrite};
+
+use super::error::StreamError;
+
+/// zstd level used by [`Compression::default`].
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Codec a compressed stream's chunks were written with, recorded in its
+/// manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+pub enum ChunkCodec {
+    /// One zstd frame per chunk.
+    Zstd,
+}
+
+impl ChunkCodec {
+    /// Decompress a chunk frame that must expand to exactly `size` bytes.
+    pub fn decompress(&self, frame: &[u8], size: usize) -> Result<Vec<u8>, StreamError> {
+        let chunk = match self {
+            Self::Zstd => zstd::bulk::decompress(frame, size)
+                .map_err(|error| StreamError::Chunk(format!("zstd decompress failed: {error}")))?,
+        };
+        if chunk.len() != size {
+            return Err(StreamError::Integrity(format!(
+                "decompressed chunk size mismatch: expected {size}, got {}",
+                chunk.len()
+            )));
+        }
+        Ok(chunk)
+    }
+}
+
+/// Compression a client applies to the streams it writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Compression {
+    /// Codec recorded in the manifest.
+    pub codec: ChunkCodec,
+    /// Codec-specific compression level.
+    pub level: i32,
+}
+
+impl Compression {
+    /// zstd at `level`.
+    pub fn zstd(level: i32) -> Self {
+        Self {
+            codec: ChunkCodec::Zstd,
+            level,
+        }
+    }
+
+    /// Compress one chunk, returning `None` when the frame would not be
+    /// smaller than the chunk itself.
+    pub fn compress(&self, chunk: &[u8]) -> Result<Option<Vec<u8>>, StreamError> {
+        let frame = match self.codec {
+            ChunkCodec::Zstd => zstd::bulk::compress(chunk, self.level)
+                .map_err(|error| StreamError::Chunk(format!("zstd compress failed: {error}")))?,
+        };
+        Ok((frame.len() < chunk.len()).then_some(frame))
+    }
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::zstd(DEFAULT_ZSTD_LEVEL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // repetitive chunks shrink and expand back to the same bytes.
+    #[test]
+    fn roundtrip() {
+        let chunk = br#"{"level":"info","msg":"request served"}"#.repeat(1000);
+        let frame = Compression::default()
+            .compress(&chunk)
+            .expect("compress")
+            .expect("repetitive chunk should shrink");
+        assert!(frame.len() < chunk.len());
+
+        let restored = ChunkCodec::Zstd.decompress(&frame, chunk.len()).expect("decompress");
+        assert_eq!(restored, chunk);
+    }
+
+    // incompressible chunks are left as is, and a wrong size is rejected.
+    #[test]
+    fn incompressible() {
+        let chunk: Vec<u8> = (0..64u32).map(|index| (index * 37 % 251) as u8).collect();
+        assert!(Compression::default().compress(&chunk).expect("compress").is_none());
+
+        let frame = zstd::bulk::compress(&[0u8; 100], DEFAULT_ZSTD_LEVEL).expect("compress");
+        assert!(ChunkCodec::Zstd.decompress(&frame, 99).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Client-side envelope encryption for stream chunks.
+//!
+//! A sealed stream gets a fresh random data key. Every chunk is sealed with
+//! AES-256-GCM-SIV under that key before it is erasure coded, so the slices a
+//! spool group holds only ever reconstruct ciphertext. The data key is wrapped
+//! by a caller-supplied [`KeyProvider`] and recorded, together with the nonce
+//! prefix, in the stream's [`Envelope`]; chunk `i` is sealed under the nonce
+//! `prefix || i`.
+
+use aes_gcm_siv::aead::{Aead, KeyInit, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Key, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use wincode_derive::{SchemaRead, SchemaWrite};
+use zeroize::Zeroizing;
+
+use super::error::StreamError;
+
+/// Bytes the AEAD tag adds to every sealed chunk.
+pub const SEAL_OVERHEAD: usize = 16;
+
+/// Length of data keys and of the keys that wrap them (AES-256).
+pub const KEY_LEN: usize = 32;
+
+/// AES-GCM-SIV nonce length.
+const NONCE_LEN: usize = 12;
+
+/// Nonce bytes fixed per stream; the chunk index fills the other eight.
+const NONCE_PREFIX_LEN: usize = 4;
+
+/// Associated data binding a wrapped data key to this envelope format.
+const WRAP_AAD: &[u8] = b"tape-sdk/envelope/v1";
+
+/// A stream's symmetric data key, wiped on drop.
+pub struct DataKey(Zeroizing<[u8; KEY_LEN]>);
+
+impl DataKey {
+    /// Generate a random data key.
+    pub fn generate() -> Self {
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        rand::thread_rng().fill_bytes(key.as_mut());
+        Self(key)
+    }
+
+    /// Wrap raw key bytes, e.g. after a provider unwrapped them.
+    pub fn from_bytes(bytes: [u8; KEY_LEN]) -> Self {
+        Self(Zeroizing::new(bytes))
+    }
+
+    /// The raw key bytes.
+    pub fn as_bytes(&self) -> &[u8; KEY_LEN] {
+        &self.0
+    }
+}
+
+/// Wraps and unwraps stream data keys, e.g. with a KMS or a local master key.
+///
+/// The wrapped form is opaque to the SDK and stored in the stream manifest.
+/// Unwrapping with the wrong key must fail rather than return a different key.
+pub trait KeyProvider: Send + Sync {
+    /// Wrap a freshly generated data key for storage.
+    fn wrap_key(&self, key: &DataKey) -> Result<Vec<u8>, StreamError>;
+
+    /// Recover a data key from its wrapped form.
+    fn unwrap_key(&self, wrapped: &[u8]) -> Result<DataKey, StreamError>;
+}
+
+/// Key provider holding one 256-bit key-encryption key in memory.
+///
+/// Data keys are wrapped with AES-256-GCM-SIV under a random nonce stored in
+/// front of the wrapped key. This is also the provider behind S3 SSE-C, where
+/// the request supplies the key-encryption key.
+pub struct StaticKeyProvider {
+    key: Zeroizing<[u8; KEY_LEN]>,
+}
+
+impl StaticKeyProvider {
+    /// Build a provider over a 256-bit key.
+    pub fn new(key: [u8; KEY_LEN]) -> Self {
+        Self {
+            key: Zeroizing::new(key),
+        }
+    }
+
+    /// Build a provider from key bytes that must be exactly 256 bits long.
+    pub fn from_slice(key: &[u8]) -> Result<Self, StreamError> {
+        let key: [u8; KEY_LEN] = key.try_into().map_err(|_| {
+            StreamError::Encryption(format!("key must be {KEY_LEN} bytes, got {}", key.len()))
+        })?;
+        Ok(Self::new(key))
+    }
+}
+
+impl KeyProvider for StaticKeyProvider {
+    fn wrap_key(&self, key: &DataKey) -> Result<Vec<u8>, StreamError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let payload = Payload {
+            msg: key.as_bytes(),
+            aad: WRAP_AAD,
+        };
+        let sealed = aead(&self.key)
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|_| StreamError::Encryption("data key wrap failed".into()))?;
+
+        let mut wrapped = Vec::with_capacity(NONCE_LEN + sealed.len());
+        wrapped.extend_from_slice(&nonce);
+        wrapped.extend_from_slice(&sealed);
+        Ok(wrapped)
+    }
+
+    fn unwrap_key(&self, wrapped: &[u8]) -> Result<DataKey, StreamError> {
+        if wrapped.len() != NONCE_LEN + KEY_LEN + SEAL_OVERHEAD {
+            return Err(StreamError::Encryption("wrapped data key has the wrong length".into()));
+        }
+        let (nonce, sealed) = wrapped.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: sealed,
+            aad: WRAP_AAD,
+        };
+        let opened = Zeroizing::new(
+            aead(&self.key)
+                .decrypt(Nonce::from_slice(nonce), payload)
+                .map_err(|_| StreamError::Encryption("key does not unwrap the data key".into()))?,
+        );
+        let mut key = [0u8; KEY_LEN];
+        key.copy_from_slice(&opened);
+        Ok(DataKey::from_bytes(key))
+    }
+}
+
+/// How a sealed stream's chunks were encrypted, recorded in its manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+pub struct Envelope {
+    /// The data key as wrapped by the writer's key provider.
+    pub wrapped_key: Vec<u8>,
+    /// Leading nonce bytes shared by every chunk of the stream.
+    pub nonce_prefix: [u8; NONCE_PREFIX_LEN],
+}
+
+impl Envelope {
+    /// Start a sealed stream: generate its data key, wrap it with `provider`,
+    /// and return the envelope to record alongside the cipher for its chunks.
+    pub fn seal(provider: &dyn KeyProvider) -> Result<(Self, ChunkCipher), StreamError> {
+        let key = DataKey::generate();
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce_prefix);
+        let envelope = Self {
+            wrapped_key: provider.wrap_key(&key)?,
+            nonce_prefix,
+        };
+        Ok((envelope, ChunkCipher::new(&key, nonce_prefix)))
+    }
+
+    /// Unwrap the data key with `provider` and return the cipher for the
+    /// stream's chunks.
+    pub fn open(&self, provider: &dyn KeyProvider) -> Result<ChunkCipher, StreamError> {
+        let key = provider.unwrap_key(&self.wrapped_key)?;
+        Ok(ChunkCipher::new(&key, self.nonce_prefix))
+    }
+}
+
+/// Seals and opens the chunks of one stream.
+#[derive(Clone)]
+pub struct ChunkCipher {
+    aead: Aes256GcmSiv,
+    nonce_prefix: [u8; NONCE_PREFIX_LEN],
+}
+
+impl ChunkCipher {
+    fn new(key: &DataKey, nonce_prefix: [u8; NONCE_PREFIX_LEN]) -> Self {
+        Self {
+            aead: aead(key.as_bytes()),
+            nonce_prefix,
+        }
+    }
+
+    /// Chunk `index`'s nonce: the stream prefix followed by the index.
+    fn nonce(&self, index: u64) -> Nonce {
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[..NONCE_PREFIX_LEN].copy_from_slice(&self.nonce_prefix);
+        nonce[NONCE_PREFIX_LEN..].copy_from_slice(&index.to_be_bytes());
+        Nonce::from(nonce)
+    }
+
+    /// Seal chunk `index`; the result is [`SEAL_OVERHEAD`] bytes longer.
+    pub fn seal(&self, index: u64, plaintext: &[u8]) -> Result<Vec<u8>, StreamError> {
+        self.aead
+            .encrypt(&self.nonce(index), plaintext)
+            .map_err(|_| StreamError::Encryption(format!("chunk {index} seal failed")))
+    }
+
+    /// Open chunk `index`, failing if it was altered or sealed at another index.
+    pub fn open(&self, index: u64, sealed: &[u8]) -> Result<Vec<u8>, StreamError> {
+        self.aead
+            .decrypt(&self.nonce(index), sealed)
+            .map_err(|_| StreamError::Encryption(format!("chunk {index} failed authentication")))
+    }
+}
+
+fn aead(key: &[u8; KEY_LEN]) -> Aes256GcmSiv {
+    Aes256GcmSiv::new(Key::<Aes256GcmSiv>::from_slice(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a sealed chunk opens under the same envelope and provider.
+    #[test]
+    fn roundtrip() {
+        let provider = StaticKeyProvider::new([7; KEY_LEN]);
+        let (envelope, cipher) = Envelope::seal(&provider).expect("seal");
+        let sealed = cipher.seal(3, b"chunk bytes").expect("seal chunk");
+        assert_eq!(sealed.len(), b"chunk bytes".len() + SEAL_OVERHEAD);
+
+        let reopened = envelope.open(&provider).expect("open");
+        assert_eq!(reopened.open(3, &sealed).expect("open chunk"), b"chunk bytes");
+    }
+
+    // a different key cannot unwrap the data key.
+    #[test]
+    fn wrong_key() {
+        let (envelope, _) = Envelope::seal(&StaticKeyProvider::new([7; KEY_LEN])).expect("seal");
+        assert!(envelope.open(&StaticKeyProvider::new([8; KEY_LEN])).is_err());
+        assert!(StaticKeyProvider::from_slice(&[7; 16]).is_err());
+    }
+
+    // chunks are bound to their index and to their bytes.
+    #[test]
+    fn tamper() {
+        let (_, cipher) = Envelope::seal(&StaticKeyProvider::new([7; KEY_LEN])).expect("seal");
+        let mut sealed = cipher.seal(0, b"chunk bytes").expect("seal chunk");
+        assert!(cipher.open(1, &sealed).is_err());
+
+        sealed[0] ^= 1;
+        assert!(cipher.open(0, &sealed).is_err());
+    }
+}
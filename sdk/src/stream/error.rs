@@ -24,4 +24,10 @@ pub enum StreamError {
 
     #[error("stream integrity error: {0}")]
     Integrity(String),
+
+    #[error("encryption error: {0}")]
+    Encryption(String),
+
+    #[error("upload journal error: {0}")]
+    Journal(String),
 }
@@ -0,0 +1,420 @@
+//! Upload journal for resumable stream writes.
+//!
+//! A client with a [`JournalStore`] records every stream write as it runs:
+//! the tape, the format the chunks are written in, each chunk track once it is
+//! registered and again once it is certified, and the mirrored track tree
+//! frontier after every certify. If the writer dies partway, `resume_stream`
+//! loads the journal, checks the certified chunks against the chain, stores
+//! and certifies the chunks it registered but never certified, and writes
+//! only the chunks after them. Certifies run in stream order, so the
+//! certified chunks always form a prefix.
+
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tape_core::types::{StorageUnits, TrackNumber};
+use tape_crypto::address::Address;
+use tape_crypto::Hash;
+use tokio::sync::Mutex;
+use wincode_derive::{SchemaRead, SchemaWrite};
+
+use super::compression::ChunkCodec;
+use super::envelope::Envelope;
+use super::error::StreamError;
+
+/// Journal format version.
+pub const JOURNAL_VERSION: u8 = 1;
+
+/// Progress of one stream write.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+pub struct UploadJournal {
+    /// Format version for future evolution.
+    pub version: u8,
+    /// The tape PDA address the stream is written to.
+    pub tape: Address,
+    /// Stream-level content key, the hash of the stream's name.
+    pub key: Hash,
+    /// Stream size in bytes.
+    pub total_size: StorageUnits,
+    /// Number of chunk tracks the stream needs.
+    pub chunk_count: TrackNumber,
+    /// Envelope the chunks are sealed under; a resumed write must reuse it.
+    pub encryption: Option<Envelope>,
+    /// Codec the chunks are compressed with.
+    pub compression: Option<ChunkCodec>,
+    /// Chunks registered so far, in stream order.
+    pub chunks: Vec<JournalChunk>,
+    /// Mirrored track tree after the latest certify.
+    pub frontier: Option<JournalFrontier>,
+}
+
+/// One registered chunk track.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+pub struct JournalChunk {
+    /// Track number on the tape that holds this chunk.
+    pub track_number: TrackNumber,
+    /// Value hash the chunk track was registered with.
+    pub value_hash: Hash,
+    /// Bytes the chunk takes on its track, after compression and sealing.
+    pub stored_size: StorageUnits,
+    /// Whether the track's certify has landed.
+    pub certified: bool,
+}
+
+/// The stream's mirror of the tape's track tree, as left by a certify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SchemaRead, SchemaWrite)]
+pub struct JournalFrontier {
+    /// Track tree root.
+    pub root: Hash,
+    /// Track number the tape's next register gets.
+    pub next_number: TrackNumber,
+}
+
+impl UploadJournal {
+    /// Start the journal of a write that has not registered any chunk yet.
+    pub fn new(
+        tape: Address,
+        key: Hash,
+        total_size: StorageUnits,
+        chunk_count: TrackNumber,
+        encryption: Option<Envelope>,
+        compression: Option<ChunkCodec>,
+    ) -> Self {
+        Self {
+            version: JOURNAL_VERSION,
+            tape,
+            key,
+            total_size,
+            chunk_count,
+            encryption,
+            compression,
+            chunks: Vec::new(),
+            frontier: None,
+        }
+    }
+
+    /// The leading chunks whose certifies have landed.
+    pub fn certified_chunks(&self) -> &[JournalChunk] {
+        let certified = self.chunks.iter().take_while(|chunk| chunk.certified).count();
+        &self.chunks[..certified]
+    }
+
+    /// Serialize to bytes using wincode.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, StreamError> {
+        wincode::serialize(self).map_err(|error| StreamError::Journal(error.to_string()))
+    }
+
+    /// Deserialize and validate from bytes using wincode.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, StreamError> {
+        let journal: Self =
+            wincode::deserialize(bytes).map_err(|error| StreamError::Journal(error.to_string()))?;
+        if journal.version != JOURNAL_VERSION {
+            return Err(StreamError::Journal(format!(
+                "unsupported journal version {}",
+                journal.version
+            )));
+        }
+        if journal.chunks.len() as u64 > journal.chunk_count.as_u64() {
+            return Err(StreamError::Journal(format!(
+                "journal records {} chunks for a {}-chunk stream",
+                journal.chunks.len(),
+                journal.chunk_count
+            )));
+        }
+        Ok(journal)
+    }
+}
+
+/// Persists upload journals, one per stream name on a tape.
+///
+/// Journals are saved after every register and certify. The write pipeline
+/// calls the store on a blocking thread, so implementations may block on
+/// file or network I/O.
+pub trait JournalStore: Send + Sync {
+    /// Load the journal of an unfinished write, if there is one.
+    fn load(&self, tape: &Address, key: &Hash) -> Result<Option<UploadJournal>, StreamError>;
+
+    /// Replace the stored journal with `journal`.
+    fn save(&self, journal: &UploadJournal) -> Result<(), StreamError>;
+
+    /// Drop the journal of a finished write.
+    fn remove(&self, tape: &Address, key: &Hash) -> Result<(), StreamError>;
+}
+
+/// Journal store keeping one file per unfinished write in a local directory.
+///
+/// Saves write and fsync a temporary file, then rename it over the journal, so
+/// a crash mid-save leaves the previous journal in place and a finished save
+/// survives a power loss.
+pub struct FileJournalStore {
+    dir: PathBuf,
+}
+
+impl FileJournalStore {
+    /// Store journals under `dir`, creating it on the first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, tape: &Address, key: &Hash) -> PathBuf {
+        self.dir.join(format!("{tape}-{key}.journal"))
+    }
+}
+
+impl JournalStore for FileJournalStore {
+    fn load(&self, tape: &Address, key: &Hash) -> Result<Option<UploadJournal>, StreamError> {
+        match fs::read(self.path(tape, key)) {
+            Ok(bytes) => UploadJournal::from_bytes(&bytes).map(Some),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(journal_io_error(error)),
+        }
+    }
+
+    fn save(&self, journal: &UploadJournal) -> Result<(), StreamError> {
+        let bytes = journal.to_bytes()?;
+        let path = self.path(&journal.tape, &journal.key);
+        let staged = path.with_extension("journal.tmp");
+        fs::create_dir_all(&self.dir).map_err(journal_io_error)?;
+        let mut file = File::create(&staged).map_err(journal_io_error)?;
+        file.write_all(&bytes).map_err(journal_io_error)?;
+        file.sync_all().map_err(journal_io_error)?;
+        fs::rename(&staged, &path).map_err(journal_io_error)
+    }
+
+    fn remove(&self, tape: &Address, key: &Hash) -> Result<(), StreamError> {
+        match fs::remove_file(self.path(tape, key)) {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(journal_io_error(error)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn journal_io_error(error: std::io::Error) -> StreamError {
+    StreamError::Journal(error.to_string())
+}
+
+/// Load a journal through `store` on a blocking thread.
+pub(crate) async fn load_journal(
+    store: Arc<dyn JournalStore>,
+    tape: Address,
+    key: Hash,
+) -> Result<Option<UploadJournal>, StreamError> {
+    blocking(move || store.load(&tape, &key)).await
+}
+
+/// Run a store call on a blocking thread, off the write pipeline's runtime.
+async fn blocking<T: Send + 'static>(
+    call: impl FnOnce() -> Result<T, StreamError> + Send + 'static,
+) -> Result<T, StreamError> {
+    tokio::task::spawn_blocking(call)
+        .await
+        .map_err(|error| StreamError::Journal(format!("journal task failed: {error}")))?
+}
+
+/// A running write's journal, saved through its store on every update.
+/// Updates hold the lock across their save, so saves land in update order.
+pub(crate) struct JournalWriter {
+    store: Arc<dyn JournalStore>,
+    journal: Mutex<UploadJournal>,
+}
+
+impl JournalWriter {
+    /// Save `journal` as the write's starting point.
+    pub(crate) async fn start(
+        store: Arc<dyn JournalStore>,
+        journal: UploadJournal,
+    ) -> Result<Self, StreamError> {
+        save(&store, journal.clone()).await?;
+        Ok(Self {
+            store,
+            journal: Mutex::new(journal),
+        })
+    }
+
+    /// Record chunk `index` as registered on a track. A resumed write records
+    /// over a journaled chunk it could not reuse.
+    pub(crate) async fn record_written(
+        &self,
+        index: usize,
+        track_number: TrackNumber,
+        value_hash: Hash,
+        stored_size: StorageUnits,
+    ) -> Result<(), StreamError> {
+        self.update(|journal| {
+            let chunk = JournalChunk {
+                track_number,
+                value_hash,
+                stored_size,
+                certified: false,
+            };
+            match journal.chunks.get_mut(index) {
+                Some(journaled) if !journaled.certified => *journaled = chunk,
+                None if index == journal.chunks.len() => journal.chunks.push(chunk),
+                _ => {
+                    return Err(StreamError::Journal(format!(
+                        "chunk {index} registered out of order; journal holds {}",
+                        journal.chunks.len()
+                    )));
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Record the certify of the chunk on `track_number`, and the mirrored
+    /// tree it left.
+    pub(crate) async fn record_certified(
+        &self,
+        track_number: TrackNumber,
+        frontier: JournalFrontier,
+    ) -> Result<(), StreamError> {
+        self.update(|journal| {
+            let chunk = journal
+                .chunks
+                .iter_mut()
+                .find(|chunk| chunk.track_number == track_number)
+                .ok_or_else(|| {
+                    StreamError::Journal(format!(
+                        "certified track {track_number} was never registered"
+                    ))
+                })?;
+            chunk.certified = true;
+            journal.frontier = Some(frontier);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Drop the journal once the manifest is written.
+    pub(crate) async fn finish(&self) -> Result<(), StreamError> {
+        let journal = self.journal.lock().await;
+        let store = self.store.clone();
+        let (tape, key) = (journal.tape, journal.key);
+        blocking(move || store.remove(&tape, &key)).await
+    }
+
+    async fn update(
+        &self,
+        apply: impl FnOnce(&mut UploadJournal) -> Result<(), StreamError>,
+    ) -> Result<(), StreamError> {
+        let mut journal = self.journal.lock().await;
+        apply(&mut journal)?;
+        save(&self.store, journal.clone()).await
+    }
+}
+
+async fn save(store: &Arc<dyn JournalStore>, journal: UploadJournal) -> Result<(), StreamError> {
+    let store = store.clone();
+    blocking(move || store.save(&journal)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_journal() -> UploadJournal {
+        UploadJournal::new(
+            Address::new_unique(),
+            Hash::from([0x11; 32]),
+            StorageUnits::from_bytes(300),
+            TrackNumber(3),
+            None,
+            Some(ChunkCodec::Zstd),
+        )
+    }
+
+    // only the leading certified chunks count as done.
+    #[tokio::test]
+    async fn certified_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileJournalStore::new(dir.path()));
+        let writer = JournalWriter::start(store, sample_journal()).await.expect("start");
+        for index in 0..3 {
+            let track_number = TrackNumber(10 + index as u64);
+            let stored = StorageUnits::from_bytes(100);
+            writer
+                .record_written(index, track_number, Hash::from([index as u8; 32]), stored)
+                .await
+                .expect("written");
+        }
+        let skipped = StorageUnits::zero();
+        let out_of_order = writer.record_written(5, TrackNumber(20), Hash::default(), skipped);
+        assert!(out_of_order.await.is_err());
+
+        let frontier = JournalFrontier {
+            root: Hash::from([0x22; 32]),
+            next_number: TrackNumber(13),
+        };
+        writer.record_certified(TrackNumber(10), frontier).await.expect("certified");
+        writer.record_certified(TrackNumber(12), frontier).await.expect("certified");
+
+        let journal = writer.journal.lock().await;
+        assert_eq!(journal.certified_chunks().len(), 1);
+        assert_eq!(journal.certified_chunks()[0].track_number, TrackNumber(10));
+        assert_eq!(journal.frontier, Some(frontier));
+    }
+
+    // a resumed write records over an uncertified journaled chunk, never a
+    // certified one, and every update reaches the store.
+    #[tokio::test]
+    async fn rerecords_uncertified_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileJournalStore::new(dir.path()));
+        let mut journal = sample_journal();
+        let (tape, key) = (journal.tape, journal.key);
+        for (track, certified) in [(4, true), (5, false)] {
+            journal.chunks.push(JournalChunk {
+                track_number: TrackNumber(track),
+                value_hash: Hash::from([track as u8; 32]),
+                stored_size: StorageUnits::from_bytes(100),
+                certified,
+            });
+        }
+        let writer = JournalWriter::start(store.clone(), journal).await.expect("start");
+
+        let stored = StorageUnits::from_bytes(90);
+        let rewritten = writer.record_written(1, TrackNumber(9), Hash::default(), stored);
+        rewritten.await.expect("rerecorded");
+        let certified = writer.record_written(0, TrackNumber(9), Hash::default(), stored);
+        assert!(certified.await.is_err());
+
+        let saved = store.load(&tape, &key).expect("load").expect("journal");
+        assert_eq!(saved.chunks[1].track_number, TrackNumber(9));
+        assert_eq!(saved.chunks[0].track_number, TrackNumber(4));
+    }
+
+    // the file store round-trips a journal and forgets it once removed.
+    #[test]
+    fn file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileJournalStore::new(dir.path().join("journals"));
+        let mut journal = sample_journal();
+        assert_eq!(store.load(&journal.tape, &journal.key).expect("load"), None);
+
+        journal.chunks.push(JournalChunk {
+            track_number: TrackNumber(4),
+            value_hash: Hash::from([0x33; 32]),
+            stored_size: StorageUnits::from_bytes(90),
+            certified: true,
+        });
+        store.save(&journal).expect("save");
+        assert_eq!(store.load(&journal.tape, &journal.key).expect("load"), Some(journal.clone()));
+
+        store.remove(&journal.tape, &journal.key).expect("remove");
+        store.remove(&journal.tape, &journal.key).expect("remove is idempotent");
+        assert_eq!(store.load(&journal.tape, &journal.key).expect("load"), None);
+    }
+
+    // journals from another format version are rejected.
+    #[test]
+    fn version() {
+        let mut journal = sample_journal();
+        journal.version = JOURNAL_VERSION + 1;
+        let bytes = journal.to_bytes().expect("serialize");
+        assert!(UploadJournal::from_bytes(&bytes).is_err());
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! When a byte stream exceeds the single-track size limit, the SDK splits it
 //! into chunks stored across multiple tracks. A manifest track is written last
-//! so the original stream can be reconstructed during reads.
+//! so the original stream can be reconstructed during reads. A sealed stream's
+//! manifest also carries the envelope its chunks were encrypted under, and a
+//! compressed stream's the codec its chunk frames were written with.
 
 use serde::{Deserialize, Serialize};
 use tape_core::track::TRACK_TREE_HEIGHT;
@@ -10,10 +12,18 @@ use tape_core::types::{StorageUnits, TrackNumber};
 use tape_crypto::Hash;
 use wincode_derive::{SchemaRead, SchemaWrite};
 
+use super::compression::ChunkCodec;
+use super::envelope::{ChunkCipher, Envelope, SEAL_OVERHEAD};
 use super::error::StreamError;
 
 /// Manifest format version.
-pub const MANIFEST_VERSION: u8 = 1;
+pub const MANIFEST_VERSION: u8 = 3;
+
+/// Version of manifests written before streams could be sealed.
+const UNSEALED_MANIFEST_VERSION: u8 = 1;
+
+/// Version of manifests written before chunks could be compressed.
+const UNCOMPRESSED_MANIFEST_VERSION: u8 = 2;
 
 /// Maximum bytes per direct coded track / stream chunk track.
 ///
@@ -47,6 +57,10 @@ pub struct ChunkManifest {
     pub key: Hash,
     /// Ordered chunk entries.
     pub chunks: Vec<ChunkEntry>,
+    /// Envelope the chunks were sealed under; `None` for plaintext streams.
+    pub encryption: Option<Envelope>,
+    /// Codec the chunk frames were compressed with; `None` when no chunk is.
+    pub compression: Option<ChunkCodec>,
 }
 
 /// One chunk within a manifest.
@@ -56,24 +70,156 @@ pub struct ChunkEntry {
     pub track_number: TrackNumber,
     /// Byte offset of this chunk in the original stream.
     pub offset: StorageUnits,
-    /// Actual byte count stored in this chunk.
+    /// Byte count of the stream this chunk holds (before any compression or
+    /// sealing).
     pub size: StorageUnits,
 }
 
+/// Layout of a version 1 manifest.
+#[derive(SchemaRead)]
+struct UnsealedManifest {
+    version: u8,
+    total_size: StorageUnits,
+    chunk_count: TrackNumber,
+    chunk_size: StorageUnits,
+    key: Hash,
+    chunks: Vec<ChunkEntry>,
+}
+
+/// Layout of a version 2 manifest.
+#[derive(SchemaRead)]
+struct UncompressedManifest {
+    version: u8,
+    total_size: StorageUnits,
+    chunk_count: TrackNumber,
+    chunk_size: StorageUnits,
+    key: Hash,
+    chunks: Vec<ChunkEntry>,
+    encryption: Option<Envelope>,
+}
+
+impl From<UnsealedManifest> for UncompressedManifest {
+    fn from(legacy: UnsealedManifest) -> Self {
+        Self {
+            version: legacy.version,
+            total_size: legacy.total_size,
+            chunk_count: legacy.chunk_count,
+            chunk_size: legacy.chunk_size,
+            key: legacy.key,
+            chunks: legacy.chunks,
+            encryption: None,
+        }
+    }
+}
+
+impl From<UncompressedManifest> for ChunkManifest {
+    fn from(legacy: UncompressedManifest) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            total_size: legacy.total_size,
+            chunk_count: legacy.chunk_count,
+            chunk_size: legacy.chunk_size,
+            key: legacy.key,
+            chunks: legacy.chunks,
+            encryption: legacy.encryption,
+            compression: None,
+        }
+    }
+}
+
 impl ChunkManifest {
     /// Serialize to bytes using wincode.
     pub fn to_bytes(&self) -> Result<Vec<u8>, StreamError> {
         wincode::serialize(self).map_err(|error| StreamError::Manifest(error.to_string()))
     }
 
-    /// Deserialize and validate from bytes using wincode.
+    /// Deserialize and validate from bytes using wincode. Manifests written
+    /// in an earlier format are upgraded to the current one.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, StreamError> {
-        let manifest: Self = wincode::deserialize(bytes)
-            .map_err(|error| StreamError::Manifest(error.to_string()))?;
+        let decode_error = |error: wincode::ReadError| StreamError::Manifest(error.to_string());
+        let manifest: Self = match bytes.first() {
+            Some(&UNSEALED_MANIFEST_VERSION) => {
+                let legacy: UnsealedManifest = wincode::deserialize(bytes).map_err(decode_error)?;
+                UncompressedManifest::from(legacy).into()
+            }
+            Some(&UNCOMPRESSED_MANIFEST_VERSION) => {
+                let legacy: UncompressedManifest =
+                    wincode::deserialize(bytes).map_err(decode_error)?;
+                legacy.into()
+            }
+            _ => wincode::deserialize(bytes).map_err(decode_error)?,
+        };
         manifest.validate()?;
         Ok(manifest)
     }
 
+    /// Whether the stream's chunks are sealed.
+    pub fn is_sealed(&self) -> bool {
+        self.encryption.is_some()
+    }
+
+    /// Whether the stream's chunks may be compressed frames.
+    pub fn is_compressed(&self) -> bool {
+        self.compression.is_some()
+    }
+
+    /// Upper bound on the bytes chunk `entry` occupies on its track: its size,
+    /// plus the AEAD tag when the stream is sealed. Only a chunk of an
+    /// uncompressed stream is always exactly this size.
+    pub fn max_stored_chunk_size(&self, entry: &ChunkEntry) -> StorageUnits {
+        if self.is_sealed() {
+            entry.size.saturating_add(StorageUnits::from_bytes(SEAL_OVERHEAD as u64))
+        } else {
+            entry.size
+        }
+    }
+
+    /// Recover chunk `chunk_index` from the bytes read off its track: open it
+    /// with `cipher` when the stream is sealed, then expand a compressed frame
+    /// back to the chunk's size. A frame is always shorter than its chunk, so
+    /// a payload of the full size was stored as is.
+    pub fn open_chunk(
+        &self,
+        chunk_index: usize,
+        stored: Vec<u8>,
+        cipher: Option<&ChunkCipher>,
+    ) -> Result<Vec<u8>, StreamError> {
+        let entry = self.chunks.get(chunk_index).ok_or_else(|| {
+            StreamError::Chunk(format!("chunk {chunk_index} is not in the manifest"))
+        })?;
+        let max_stored_size = self.max_stored_chunk_size(entry).to_bytes();
+        let stored_size = stored.len() as u64;
+        let exact = !self.is_compressed();
+        if stored_size > max_stored_size || (exact && stored_size != max_stored_size) {
+            return Err(StreamError::Chunk(format!(
+                "chunk {chunk_index} size mismatch: expected {max_stored_size}, got {stored_size}"
+            )));
+        }
+
+        let payload = match (cipher, self.is_sealed()) {
+            (Some(cipher), true) => cipher.open(chunk_index as u64, &stored)?,
+            (None, false) => stored,
+            (None, true) => {
+                return Err(StreamError::Encryption(
+                    "stream is encrypted; a key is required to read it".into(),
+                ));
+            }
+            (Some(_), false) => {
+                return Err(StreamError::Encryption("stream is not encrypted".into()));
+            }
+        };
+
+        let size = entry.size.as_usize();
+        match self.compression {
+            Some(codec) if payload.len() < size => codec.decompress(&payload, size),
+            _ if payload.len() == size => Ok(payload),
+            _ => Err(StreamError::Integrity(format!(
+                "chunk {chunk_index} size mismatch: expected {size}, got {}",
+                payload.len()
+            ))),
+        }
+    }
+
     /// Check manifest invariants.
     fn validate(&self) -> Result<(), StreamError> {
         if self.version != MANIFEST_VERSION {
@@ -128,6 +274,8 @@ impl ChunkManifest {
 
 #[cfg(test)]
 mod tests {
+    use crate::stream::compression::Compression;
+
     use super::*;
 
     fn sample_manifest(chunk_count: usize) -> ChunkManifest {
@@ -146,6 +294,8 @@ mod tests {
             chunk_size: StorageUnits::from_bytes(MAX_TRACK_SIZE as u64),
             key: Hash::from([0xAB; 32]),
             chunks,
+            encryption: None,
+            compression: None,
         }
     }
 
@@ -158,6 +308,72 @@ mod tests {
         assert_eq!(manifest, recovered);
     }
 
+    // a sealed manifest keeps its envelope and reports tagged chunk sizes.
+    #[test]
+    fn sealed() {
+        let mut manifest = sample_manifest(2);
+        manifest.encryption = Some(Envelope {
+            wrapped_key: vec![0x5A; 60],
+            nonce_prefix: [1, 2, 3, 4],
+        });
+        let bytes = manifest.to_bytes().expect("serialize manifest");
+        let recovered = ChunkManifest::from_bytes(&bytes).expect("deserialize manifest");
+        assert_eq!(manifest, recovered);
+        assert_eq!(
+            recovered.max_stored_chunk_size(&recovered.chunks[0]).to_bytes(),
+            MAX_TRACK_SIZE as u64 + SEAL_OVERHEAD as u64
+        );
+    }
+
+    // compressed chunks expand back; raw chunks pass through at full size.
+    #[test]
+    fn compressed() {
+        let mut manifest = sample_manifest(1);
+        manifest.total_size = StorageUnits::from_bytes(4096);
+        manifest.chunks[0].size = StorageUnits::from_bytes(4096);
+        manifest.compression = Some(ChunkCodec::Zstd);
+        let bytes = manifest.to_bytes().expect("serialize manifest");
+        let manifest = ChunkManifest::from_bytes(&bytes).expect("deserialize manifest");
+
+        let chunk = vec![b'a'; 4096];
+        let frame = Compression::default()
+            .compress(&chunk)
+            .expect("compress")
+            .expect("frame");
+        assert_eq!(manifest.open_chunk(0, frame, None).expect("open frame"), chunk);
+        assert_eq!(manifest.open_chunk(0, chunk.clone(), None).expect("open raw"), chunk);
+        assert!(manifest.open_chunk(0, vec![b'a'; 4097], None).is_err());
+    }
+
+    // version 1 manifests still decode, as unsealed streams.
+    #[test]
+    fn legacy() {
+        #[derive(SchemaWrite)]
+        struct V1 {
+            version: u8,
+            total_size: StorageUnits,
+            chunk_count: TrackNumber,
+            chunk_size: StorageUnits,
+            key: Hash,
+            chunks: Vec<ChunkEntry>,
+        }
+
+        let manifest = sample_manifest(3);
+        let bytes = wincode::serialize(&V1 {
+            version: UNSEALED_MANIFEST_VERSION,
+            total_size: manifest.total_size,
+            chunk_count: manifest.chunk_count,
+            chunk_size: manifest.chunk_size,
+            key: manifest.key,
+            chunks: manifest.chunks.clone(),
+        })
+        .expect("serialize legacy manifest");
+        let recovered = ChunkManifest::from_bytes(&bytes).expect("deserialize legacy manifest");
+        assert_eq!(manifest, recovered);
+        assert!(!recovered.is_sealed());
+        assert!(!recovered.is_compressed());
+    }
+
     // single-chunk manifests preserve their single entry.
     #[test]
     fn single() {
@@ -210,6 +426,8 @@ mod tests {
                     size: StorageUnits::from_bytes(1000),
                 },
             ],
+            encryption: None,
+            compression: None,
         };
 
         let bytes = manifest.to_bytes().expect("serialize manifest");
@@ -2,9 +2,15 @@
 //!
 //! Large byte streams are split into chunk tracks, each stored separately on a
 //! single tape. A manifest track is written last to record chunk layout for
-//! reassembly during reads.
+//! reassembly during reads. Streams written by a client with a key provider
+//! are sealed chunk by chunk; see [`envelope`]. Chunks can also be compressed
+//! before sealing; see [`compression`]. A client with a journal store records
+//! each write's progress so an interrupted write can resume; see [`journal`].
 
+pub mod compression;
+pub mod envelope;
 pub mod error;
+pub mod journal;
 pub mod manifest;
 pub mod read;
 pub mod receipt;
@@ -1,7 +1,8 @@
 //! Stream read implementation.
 //!
 //! Reads a manifest track, fetches chunk tracks sequentially, and reassembles
-//! the original byte stream.
+//! the original byte stream. Sealed chunks are opened with the client's key
+//! provider, and compressed chunks expanded, as they arrive.
 
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -19,6 +20,7 @@ use crate::error::TapedriveError;
 use crate::metrics::{Operation, Phase};
 use crate::tapedrive::Tapedrive;
 
+use super::envelope::ChunkCipher;
 use super::error::StreamError;
 use super::manifest::ChunkManifest;
 
@@ -68,20 +70,17 @@ async fn read_manifest_into<Blockchain: Rpc, Cluster: Api, Writer: AsyncWrite +
     writer: &mut Writer,
 ) -> Result<(), TapedriveError> {
     let tape_address = manifest_track.tape;
+    let cipher = stream_cipher(client, manifest)?;
     let mut total_written = StorageUnits::zero();
 
     for (chunk_index, entry) in manifest.chunks.iter().enumerate() {
         let track_address = track_pda(tape_address, entry.track_number).0;
         let chunk_data = client.read_as(&track_address, Operation::ReadStream).await?;
 
+        let chunk_data = manifest
+            .open_chunk(chunk_index, chunk_data, cipher.as_ref())
+            .map_err(stream_error)?;
         let data_size = StorageUnits::from_bytes(chunk_data.len() as u64);
-        if data_size != entry.size {
-            return Err(stream_error(StreamError::Chunk(format!(
-                "chunk {chunk_index} size mismatch: expected {}, got {}",
-                entry.size,
-                chunk_data.len()
-            ))));
-        }
 
         let write_sink = client
             .timer(Operation::ReadStream, Phase::WriteSink)
@@ -110,6 +109,22 @@ async fn read_manifest_into<Blockchain: Rpc, Cluster: Api, Writer: AsyncWrite +
     Ok(())
 }
 
+/// Open a sealed stream's envelope with the client's key provider.
+fn stream_cipher<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    manifest: &ChunkManifest,
+) -> Result<Option<ChunkCipher>, TapedriveError> {
+    let Some(envelope) = &manifest.encryption else {
+        return Ok(None);
+    };
+    let provider = client.key_provider.as_deref().ok_or_else(|| {
+        stream_error(StreamError::Encryption(
+            "stream is encrypted; attach a key provider".into(),
+        ))
+    })?;
+    envelope.open(provider).map(Some).map_err(stream_error)
+}
+
 fn stream_error(error: StreamError) -> TapedriveError {
     TapedriveError::Stream(error.to_string())
 }
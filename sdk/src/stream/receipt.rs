@@ -3,7 +3,7 @@
 use tape_crypto::address::Address;
 use tape_crypto::Hash;
 
-use tape_core::types::TrackNumber;
+use tape_core::types::{StorageUnits, TrackNumber};
 
 /// Returned by `write_bytes` and `write_stream`.
 #[derive(Debug, Clone)]
@@ -16,4 +16,9 @@ pub struct StreamReceipt {
     pub manifest_track_number: TrackNumber,
     /// Value hash of the manifest track, used as the stream's content ETag.
     pub manifest_value_hash: Hash,
+    /// Bytes in the stream as written by the caller.
+    pub logical_size: StorageUnits,
+    /// Bytes this write put on tracks: its chunks after compression and
+    /// sealing, plus the manifest.
+    pub stored_size: StorageUnits,
 }
@@ -1,8 +1,9 @@
 //! Stream write implementation.
 
+use std::sync::Arc;
 use std::time::Duration;
 
-use futures::stream::{self, FuturesOrdered, Stream, StreamExt};
+use futures::stream::{self, FuturesOrdered, Stream, StreamExt, TryStreamExt};
 use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::sync::{mpsc, oneshot, Mutex};
 use tokio::time::sleep;
@@ -13,8 +14,9 @@ use tape_api::state::Tape;
 use tape_core::prelude::CompressedTrack;
 use tape_core::track::mirror::ArchiveMirror;
 use tape_core::track::types::CompressedTrackProof;
-use tape_core::types::ContentType;
+use tape_core::types::ObjectHeaders;
 use tape_core::types::{StorageUnits, TrackNumber};
+use tape_crypto::address::Address;
 use tape_crypto::hash::hash;
 use tape_crypto::Hash;
 use tape_protocol::Api;
@@ -26,14 +28,17 @@ use crate::tapedrive::Tapedrive;
 use crate::metrics::{Operation, Phase};
 use crate::track::write::{
     certified_track, certify_submit_with_retry, certify_with_retry, collect_certification,
-    encode_blob, inline_write_fits, register_blob_processed, resolve_sent_blob,
-    should_retry_certification, submit_blob_with_logical_size, submit_certification_with_proof,
-    submit_raw_with_logical_size, upload_with_retry, wait_for_certified_track,
-    UploadPlan, WrittenTrack, UNNAMED_TRACK, UNTYPED_TRACK,
+    encode_stream_chunk, inline_write_fits, plan_value_hash, register_blob_processed,
+    resolve_sent_blob, should_retry_certification, submit_blob_with_logical_size,
+    submit_certification_with_proof, submit_raw_with_logical_size, upload_with_retry,
+    wait_for_certified_track, SentBlob, UploadPlan, WrittenTrack, UNNAMED_TRACK,
 };
 use crate::transfer::certify::CollectedSignatures;
 
+use super::compression::{ChunkCodec, Compression, DEFAULT_ZSTD_LEVEL};
+use super::envelope::{ChunkCipher, Envelope, SEAL_OVERHEAD};
 use super::error::StreamError;
+use super::journal::{load_journal, JournalChunk, JournalFrontier, JournalWriter, UploadJournal};
 use super::manifest::{
     ChunkEntry, ChunkManifest, MAX_TRACK_SIZE, MAX_TRACKS_PER_TAPE, MANIFEST_VERSION,
 };
@@ -73,12 +78,23 @@ const CERTIFY_CONFLICT_ATTEMPTS: usize = 3;
 /// processed but not yet confirmed, so an immediate refetch can miss it.
 const CERTIFY_CONFLICT_DELAY: Duration = Duration::from_millis(400);
 
+/// Journaled chunk tracks checked against peers at once when resuming.
+const RESUME_VERIFY_CONCURRENCY: usize = 8;
+
 // Chunks are internal fragments addressed by track number, never by name.
 struct PendingChunk {
     pub entry: ChunkEntry,
     pub written: WrittenTrack,
 }
 
+// How a chunk's track came to be registered.
+enum Registration {
+    // Registered by this run; its track-written event is still to resolve.
+    Sent(SentBlob),
+    // Registered by an interrupted run and picked back up from its journal.
+    Journaled(WrittenTrack, UploadPlan),
+}
+
 // A registered chunk whose encoded slices still need to be stored.
 struct RegisteredChunk {
     entry: ChunkEntry,
@@ -86,6 +102,55 @@ struct RegisteredChunk {
     plan: UploadPlan,
 }
 
+// A certified chunk as the manifest records it, with the bytes its track
+// holds.
+struct StoredChunk {
+    entry: ChunkEntry,
+    stored_size: StorageUnits,
+}
+
+impl From<PendingChunk> for StoredChunk {
+    fn from(pending: PendingChunk) -> Self {
+        Self {
+            entry: pending.entry,
+            stored_size: pending.written.track.size,
+        }
+    }
+}
+
+// One pipeline run over chunks `first_chunk..chunk_count` of a stream.
+struct ChunkRun {
+    size: StorageUnits,
+    chunk_count: TrackNumber,
+    first_chunk: usize,
+    transform: ChunkTransform,
+    journal: Option<Arc<JournalWriter>>,
+    // Chunks `first_chunk..` an interrupted run registered but never
+    // certified, in stream order.
+    journaled: Vec<JournalChunk>,
+    // Bytes the run's chunk tracks may take; each chunk's stored size is
+    // checked against what is left before it registers.
+    capacity: StorageUnits,
+}
+
+// A validated write: its tape, chunk count and format, and the bytes its
+// chunk tracks may take once the manifest is held back.
+struct PreparedWrite {
+    tape: Tape,
+    chunk_count: TrackNumber,
+    format: StreamFormat,
+    chunk_capacity: StorageUnits,
+}
+
+// What a write needs from its tape.
+struct RequiredCapacity {
+    // Free bytes the preflight asks for.
+    upfront: StorageUnits,
+    // Bytes held back for the manifest while chunks are written.
+    manifest: StorageUnits,
+    tracks: TrackNumber,
+}
+
 /// Validate stream-level input before any track writes begin.
 fn validate_stream_size(size: StorageUnits) -> Result<(), StreamError> {
     if size.is_zero() {
@@ -157,6 +222,8 @@ fn build_manifest(
     key: Hash,
     total_size: StorageUnits,
     entries: Vec<ChunkEntry>,
+    encryption: Option<Envelope>,
+    compression: Option<ChunkCodec>,
 ) -> Result<ChunkManifest, StreamError> {
     let chunk_count = TrackNumber(
         u64::try_from(entries.len())
@@ -170,9 +237,86 @@ fn build_manifest(
         chunk_size: StorageUnits::from_bytes(MAX_TRACK_SIZE as u64),
         key,
         chunks: entries,
+        encryption,
+        compression,
     })
 }
 
+/// How each chunk is transformed before it is encoded: compressed, then
+/// sealed under its index.
+#[derive(Clone, Default)]
+pub(crate) struct ChunkTransform {
+    compression: Option<Compression>,
+    cipher: Option<Arc<ChunkCipher>>,
+}
+
+impl ChunkTransform {
+    /// The bytes chunk `index` is stored as.
+    pub(crate) fn apply(&self, index: u64, chunk: Vec<u8>) -> Result<Vec<u8>, StreamError> {
+        let chunk = match &self.compression {
+            Some(compression) => compression.compress(&chunk)?.unwrap_or(chunk),
+            None => chunk,
+        };
+        match &self.cipher {
+            Some(cipher) => cipher.seal(index, &chunk),
+            None => Ok(chunk),
+        }
+    }
+}
+
+/// The format one stream is written in, fixed before its first chunk.
+#[derive(Default)]
+struct StreamFormat {
+    transform: ChunkTransform,
+    envelope: Option<Envelope>,
+}
+
+impl StreamFormat {
+    /// Pick up the client's compression, and seal under a fresh data key when
+    /// it has a key provider.
+    fn for_client<Blockchain: Rpc, Cluster: Api>(
+        client: &Tapedrive<Blockchain, Cluster>,
+    ) -> Result<Self, StreamError> {
+        let mut format = Self::default();
+        format.transform.compression = client.compression;
+        if let Some(provider) = client.key_provider.as_deref() {
+            let (envelope, cipher) = Envelope::seal(provider)?;
+            format.envelope = Some(envelope);
+            format.transform.cipher = Some(Arc::new(cipher));
+        }
+        Ok(format)
+    }
+
+    /// Pick the journaled write's format back up: its codec, at the client's
+    /// level when the client compresses with the same codec, and its envelope,
+    /// reopened with the client's key provider.
+    fn resume<Blockchain: Rpc, Cluster: Api>(
+        client: &Tapedrive<Blockchain, Cluster>,
+        journal: &UploadJournal,
+    ) -> Result<Self, StreamError> {
+        let mut format = Self::default();
+        format.transform.compression = journal.compression.map(|codec| {
+            let level = client
+                .compression
+                .filter(|compression| compression.codec == codec)
+                .map_or(DEFAULT_ZSTD_LEVEL, |compression| compression.level);
+            Compression { codec, level }
+        });
+        if let Some(envelope) = &journal.encryption {
+            let provider = client.key_provider.as_deref().ok_or_else(|| {
+                StreamError::Encryption("resuming a sealed stream needs a key provider".into())
+            })?;
+            format.transform.cipher = Some(Arc::new(envelope.open(provider)?));
+            format.envelope = Some(envelope.clone());
+        }
+        Ok(format)
+    }
+
+    fn codec(&self) -> Option<ChunkCodec> {
+        self.transform.compression.map(|compression| compression.codec)
+    }
+}
+
 /// Verify the tape has enough capacity and track slots for the stream.
 fn preflight(
     tape: &Tape,
@@ -210,19 +354,36 @@ pub async fn write_bytes<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     data: &[u8],
 ) -> Result<StreamReceipt, TapedriveError> {
     let size = StorageUnits::from_bytes(data.len() as u64);
-    let (tape, chunk_count) = prepare_write(client, tape_key, name, size).await?;
+    let PreparedWrite {
+        tape,
+        chunk_count,
+        format,
+        chunk_capacity,
+    } = prepare_write(client, tape_key, name, size).await?;
+    let journal = start_journal(client, tape_key, name, size, chunk_count, &format).await?;
     let chunk_sources = stream::iter(
         data.chunks(MAX_TRACK_SIZE)
             .map(|chunk| Ok::<_, TapedriveError>(chunk.to_vec())),
     );
-    let pending_chunks =
-        pipeline_chunks(client, tape_key, &tape, size, chunk_count, chunk_sources).await?;
+    let run = ChunkRun {
+        size,
+        chunk_count,
+        first_chunk: 0,
+        transform: format.transform.clone(),
+        journal: journal.clone(),
+        journaled: Vec::new(),
+        capacity: chunk_capacity,
+    };
+    let pending_chunks = pipeline_chunks(client, tape_key, &tape, run, chunk_sources).await?;
 
-    finalize_write(client, tape_key, name, content_type, size, pending_chunks).await
+    let chunks = pending_chunks.into_iter().map(StoredChunk::from).collect();
+    let receipt = finalize_write(client, tape_key, name, headers, size, format, chunks).await?;
+    finish_journal(journal.as_deref()).await?;
+    Ok(receipt)
 }
 
 /// Write bytes from an async reader as a multi-track stream.
@@ -230,37 +391,154 @@ pub async fn write_stream<Blockchain: Rpc, Cluster: Api, Reader: AsyncRead + Unp
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     size: StorageUnits,
     mut reader: Reader,
 ) -> Result<StreamReceipt, TapedriveError> {
-    let (tape, chunk_count) = prepare_write(client, tape_key, name, size).await?;
-    let chunk_sources = stream::unfold(
-        (&mut reader, 0usize),
-        move |(reader, chunk_index)| async move {
-            if chunk_index >= chunk_count.as_usize() {
-                return None;
-            }
-            let result = read_chunk(reader, chunk_index, chunk_count, size).await;
-            Some((result, (reader, chunk_index + 1)))
-        },
-    );
-    let pending_chunks =
-        pipeline_chunks(client, tape_key, &tape, size, chunk_count, chunk_sources).await?;
+    let PreparedWrite {
+        tape,
+        chunk_count,
+        format,
+        chunk_capacity,
+    } = prepare_write(client, tape_key, name, size).await?;
+    let journal = start_journal(client, tape_key, name, size, chunk_count, &format).await?;
+    let chunk_sources = reader_chunks(&mut reader, 0, chunk_count, size);
+    let run = ChunkRun {
+        size,
+        chunk_count,
+        first_chunk: 0,
+        transform: format.transform.clone(),
+        journal: journal.clone(),
+        journaled: Vec::new(),
+        capacity: chunk_capacity,
+    };
+    let pending_chunks = pipeline_chunks(client, tape_key, &tape, run, chunk_sources).await?;
 
     verify_stream_drained(&mut reader).await?;
-    finalize_write(client, tape_key, name, content_type, size, pending_chunks).await
+    let chunks = pending_chunks.into_iter().map(StoredChunk::from).collect();
+    let receipt = finalize_write(client, tape_key, name, headers, size, format, chunks).await?;
+    finish_journal(journal.as_deref()).await?;
+    Ok(receipt)
 }
 
-/// Validate the write upfront, returning the fetched tape and the chunk
-/// count. The tape carries the pre-stream track tree the pipeline seeds its
-/// mirror from, so the pipeline does not refetch it.
+/// Finish a stream write that an earlier, interrupted run journaled.
+///
+/// `reader` must yield the same `size` bytes the interrupted write was given,
+/// from the start. Chunks the journal records as certified are checked on
+/// chain and read past; the rest are written as `write_stream` would, followed
+/// by the manifest. A chunk that registered but never certified is stored and
+/// certified on its journaled track when it still encodes to the registered
+/// blob, and written to a new track otherwise. Without a journal for `name`
+/// this is a fresh `write_stream`.
+pub async fn resume_stream<Blockchain: Rpc, Cluster: Api, Reader: AsyncRead + Unpin>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_key: &impl TapeOperator,
+    name: &[u8],
+    headers: &ObjectHeaders,
+    size: StorageUnits,
+    mut reader: Reader,
+) -> Result<StreamReceipt, TapedriveError> {
+    let store = client.journal.clone().ok_or_else(|| {
+        TapedriveError::InvalidArgument("resuming a stream needs a journal store".into())
+    })?;
+    let journal = load_journal(store.clone(), tape_key.address(), hash(name))
+        .await
+        .map_err(stream_error)?;
+    let Some(mut journal) = journal else {
+        return write_stream(client, tape_key, name, headers, size, reader).await;
+    };
+
+    let PreparedWrite {
+        tape,
+        chunk_count,
+        format,
+        chunk_capacity,
+    } = prepare_resume(client, tape_key, name, size, &journal).await?;
+    let certified = verify_certified_chunks(client, &tape_key.address(), &tape, &journal).await?;
+    let first_chunk = certified.len();
+
+    let skipped = chunk_offset(first_chunk).map_err(stream_error)?.min(size);
+    skip_stream_bytes(&mut reader, skipped).await?;
+
+    for chunk in &mut journal.chunks[..first_chunk] {
+        chunk.certified = true;
+    }
+    let journaled = journal.chunks[first_chunk..].to_vec();
+    let journal = JournalWriter::start(store, journal).await.map_err(stream_error)?;
+    let journal = Arc::new(journal);
+    let mut chunks = certified;
+    if first_chunk < chunk_count.as_usize() {
+        let chunk_sources = reader_chunks(&mut reader, first_chunk, chunk_count, size);
+        let run = ChunkRun {
+            size,
+            chunk_count,
+            first_chunk,
+            transform: format.transform.clone(),
+            journal: Some(journal.clone()),
+            journaled,
+            capacity: chunk_capacity,
+        };
+        let pending_chunks = pipeline_chunks(client, tape_key, &tape, run, chunk_sources).await?;
+        chunks.extend(pending_chunks.into_iter().map(StoredChunk::from));
+    }
+
+    verify_stream_drained(&mut reader).await?;
+    let receipt = finalize_write(client, tape_key, name, headers, size, format, chunks).await?;
+    finish_journal(Some(&journal)).await?;
+    Ok(receipt)
+}
+
+/// Write `size` bytes from an async reader as unnamed chunk tracks, without a
+/// manifest.
+///
+/// The chunks are stored and certified as a stream's would be, but nothing
+/// names them until [`link_stream`] writes a manifest over them. The returned
+/// entries hold their track numbers, with offsets counted from the start of
+/// `reader`. Chunks written apart from their manifest cannot share its
+/// envelope, so a client with a key provider is refused.
+pub async fn write_chunks<Blockchain: Rpc, Cluster: Api, Reader: AsyncRead + Unpin>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_key: &impl TapeOperator,
+    size: StorageUnits,
+    mut reader: Reader,
+) -> Result<Vec<ChunkEntry>, TapedriveError> {
+    if client.key_provider.is_some() {
+        return Err(TapedriveError::InvalidArgument(
+            "unlinked chunk tracks cannot be sealed".into(),
+        ));
+    }
+    let PreparedWrite {
+        tape,
+        chunk_count,
+        format,
+        chunk_capacity,
+    } = prepare_write(client, tape_key, &[], size).await?;
+    let chunk_sources = reader_chunks(&mut reader, 0, chunk_count, size);
+    let run = ChunkRun {
+        size,
+        chunk_count,
+        first_chunk: 0,
+        transform: format.transform,
+        journal: None,
+        journaled: Vec::new(),
+        capacity: chunk_capacity,
+    };
+    let pending_chunks = pipeline_chunks(client, tape_key, &tape, run, chunk_sources).await?;
+
+    verify_stream_drained(&mut reader).await?;
+    Ok(pending_chunks.into_iter().map(|pending| pending.entry).collect())
+}
+
+/// Validate the write upfront, returning the fetched tape, the chunk count,
+/// and the stream's format. The tape carries the pre-stream track tree the
+/// pipeline seeds its mirror from, so the pipeline does not refetch it.
+/// See [`required_capacity`] for what the tape must hold.
 async fn prepare_write<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
     size: StorageUnits,
-) -> Result<(Tape, TrackNumber), TapedriveError> {
+) -> Result<PreparedWrite, TapedriveError> {
     let timer = client
         .timer(Operation::WriteStream, Phase::Preflight)
         .bytes(size.to_bytes());
@@ -268,39 +546,260 @@ async fn prepare_write<Blockchain: Rpc, Cluster: Api>(
     let result = async {
         validate_stream_size(size).map_err(stream_error)?;
         let chunk_count = chunk_count_for_size(size).map_err(stream_error)?;
-        let tracks_needed = chunk_count.checked_next().ok_or_else(|| {
-            stream_error(StreamError::InvalidInput("stream has too many chunks".into()))
-        })?;
+        let format = StreamFormat::for_client(client).map_err(stream_error)?;
+        let required =
+            required_capacity(name, size, chunk_count, 0, &format).map_err(stream_error)?;
 
-        let entries = build_entries(TrackNumber(0), chunk_count, size).map_err(stream_error)?;
-        let manifest = build_manifest(hash(name), size, entries).map_err(stream_error)?;
-        let manifest_bytes = manifest.to_bytes().map_err(stream_error)?;
-        let total_size = size
-            .checked_add(StorageUnits::from_bytes(manifest_bytes.len() as u64))
-            .ok_or_else(|| stream_error(StreamError::InvalidInput("stream size overflow".into())))?;
+        let tape = client.get_tape(&tape_key.address()).await?;
+        preflight(&tape, required.upfront, required.tracks)?;
+        Ok(PreparedWrite {
+            chunk_capacity: chunk_capacity(&tape, required.manifest),
+            tape,
+            chunk_count,
+            format,
+        })
+    }
+    .await;
+    timer.finish_result(&result);
+    result
+}
+
+/// Validate a journaled write against the resumed call, returning the fetched
+/// tape and the journaled format. Capacity is checked for the chunks the
+/// journal does not record as registered, plus the manifest; a journaled chunk
+/// that has to be written again is checked as it registers.
+async fn prepare_resume<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_key: &impl TapeOperator,
+    name: &[u8],
+    size: StorageUnits,
+    journal: &UploadJournal,
+) -> Result<PreparedWrite, TapedriveError> {
+    let timer = client
+        .timer(Operation::WriteStream, Phase::Preflight)
+        .bytes(size.to_bytes());
+
+    let result = async {
+        validate_stream_size(size).map_err(stream_error)?;
+        let chunk_count = chunk_count_for_size(size).map_err(stream_error)?;
+        if journal.total_size != size || journal.chunk_count != chunk_count {
+            return Err(stream_error(StreamError::Journal(format!(
+                "journal is for a {} byte stream, not {size}",
+                journal.total_size
+            ))));
+        }
+        let format = StreamFormat::resume(client, journal).map_err(stream_error)?;
+        let registered = journal.chunks.len();
+        let required = required_capacity(name, size, chunk_count, registered, &format)
+            .map_err(stream_error)?;
 
         let tape = client.get_tape(&tape_key.address()).await?;
-        preflight(&tape, total_size, tracks_needed)?;
-        Ok((tape, chunk_count))
+        preflight(&tape, required.upfront, required.tracks)?;
+        Ok(PreparedWrite {
+            chunk_capacity: chunk_capacity(&tape, required.manifest),
+            tape,
+            chunk_count,
+            format,
+        })
     }
     .await;
     timer.finish_result(&result);
     result
 }
 
+/// Capacity and track slots needed to write chunks `first_chunk..` of a
+/// stream and its manifest.
+///
+/// An uncompressed stream's chunks are stored at their full size, so all of
+/// it is asked for upfront. A compressed stream's stored size is only known
+/// chunk by chunk, so upfront it needs the manifest plus one worst-case
+/// chunk; the pipeline then checks every chunk's compressed size against the
+/// tape as it registers, and a tape reserved for the compressed size is
+/// enough.
+fn required_capacity(
+    name: &[u8],
+    size: StorageUnits,
+    chunk_count: TrackNumber,
+    first_chunk: usize,
+    format: &StreamFormat,
+) -> Result<RequiredCapacity, StreamError> {
+    let remaining_chunks = chunk_count.saturating_sub(TrackNumber(first_chunk as u64));
+    let tracks_needed = remaining_chunks
+        .checked_next()
+        .ok_or_else(|| StreamError::InvalidInput("stream has too many chunks".into()))?;
+
+    let entries = build_entries(TrackNumber(0), chunk_count, size)?;
+    let manifest =
+        build_manifest(hash(name), size, entries, format.envelope.clone(), format.codec())?;
+    let manifest_size = StorageUnits::from_bytes(manifest.to_bytes()?.len() as u64);
+    let remaining_size = size.saturating_sub(chunk_offset(first_chunk)?);
+    // A chunk whose frame would not shrink is stored as is, so no chunk
+    // takes more than its uncompressed size.
+    let (chunk_bytes, sealed_chunks) = if format.transform.compression.is_some() {
+        let worst_chunk = remaining_size.min(StorageUnits::from_bytes(MAX_TRACK_SIZE as u64));
+        (worst_chunk, remaining_chunks.0.min(1))
+    } else {
+        (remaining_size, remaining_chunks.0)
+    };
+    let seal_overhead = if format.envelope.is_some() {
+        sealed_chunks.saturating_mul(SEAL_OVERHEAD as u64)
+    } else {
+        0
+    };
+    let upfront = chunk_bytes
+        .checked_add(manifest_size)
+        .and_then(|total| total.checked_add(StorageUnits::from_bytes(seal_overhead)))
+        .ok_or_else(|| StreamError::InvalidInput("stream size overflow".into()))?;
+
+    Ok(RequiredCapacity {
+        upfront,
+        manifest: manifest_size,
+        tracks: tracks_needed,
+    })
+}
+
+/// Bytes left on `tape` for chunk tracks once the manifest is held back.
+fn chunk_capacity(tape: &Tape, manifest: StorageUnits) -> StorageUnits {
+    tape.capacity.saturating_sub(tape.used).saturating_sub(manifest)
+}
+
+/// Start journaling a write, when the client has a journal store. Any journal
+/// left by an earlier write of the same name is replaced.
+async fn start_journal<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_key: &impl TapeOperator,
+    name: &[u8],
+    size: StorageUnits,
+    chunk_count: TrackNumber,
+    format: &StreamFormat,
+) -> Result<Option<Arc<JournalWriter>>, TapedriveError> {
+    let Some(store) = client.journal.clone() else {
+        return Ok(None);
+    };
+    let journal = UploadJournal::new(
+        tape_key.address(),
+        hash(name),
+        size,
+        chunk_count,
+        format.envelope.clone(),
+        format.codec(),
+    );
+    let writer = JournalWriter::start(store, journal).await.map_err(stream_error)?;
+    Ok(Some(Arc::new(writer)))
+}
+
+/// Drop a finished write's journal.
+async fn finish_journal(journal: Option<&JournalWriter>) -> Result<(), TapedriveError> {
+    match journal {
+        Some(journal) => journal.finish().await.map_err(stream_error),
+        None => Ok(()),
+    }
+}
+
+/// Check the chunks a journal records as certified against the chain and
+/// return them as the resumed stream's leading chunks. While the tape's
+/// track tree still matches the frontier journaled by the last certify, that
+/// root already commits to every journaled chunk; otherwise each chunk track
+/// is fetched and compared, and the next journaled chunk counts as certified
+/// too when its certify landed after the journal was last saved.
+async fn verify_certified_chunks<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_address: &Address,
+    tape: &Tape,
+    journal: &UploadJournal,
+) -> Result<Vec<StoredChunk>, TapedriveError> {
+    let mut certified = journal.certified_chunks().to_vec();
+    let unchanged = journal.frontier == Some(JournalFrontier {
+        root: tape.tracks.tree.root(),
+        next_number: tape.tracks.next_number(),
+    });
+
+    if !unchanged {
+        stream::iter(certified.iter().map(|chunk| async move {
+            let track = client.get_track_by_number(tape_address, chunk.track_number).await?;
+            if !track.is_certified()
+                || track.value_hash != chunk.value_hash
+                || track.size != chunk.stored_size
+            {
+                return Err(stream_error(StreamError::Journal(format!(
+                    "journaled chunk track {} is not certified as recorded",
+                    chunk.track_number
+                ))));
+            }
+            Ok(())
+        }))
+        .buffered(RESUME_VERIFY_CONCURRENCY)
+        .try_collect::<Vec<()>>()
+        .await?;
+
+        if let Some(next) = journal.chunks.get(certified.len()) {
+            let track = client.get_track_by_number(tape_address, next.track_number).await;
+            let landed = track.is_ok_and(|track| {
+                track.is_certified()
+                    && track.value_hash == next.value_hash
+                    && track.size == next.stored_size
+            });
+            if landed {
+                certified.push(next.clone());
+            }
+        }
+    }
+
+    certified
+        .iter()
+        .enumerate()
+        .map(|(chunk_index, chunk)| {
+            Ok(StoredChunk {
+                entry: ChunkEntry {
+                    track_number: chunk.track_number,
+                    offset: chunk_offset(chunk_index)?,
+                    size: chunk_size(chunk_index, journal.chunk_count, journal.total_size)?,
+                },
+                stored_size: chunk.stored_size,
+            })
+        })
+        .collect::<Result<_, StreamError>>()
+        .map_err(stream_error)
+}
+
+/// The track an interrupted run registered for a chunk, when it is still
+/// registered on chain for the blob `plan` encodes. Chunk encoding is
+/// deterministic, so a matching value hash means the journaled track can be
+/// stored and certified instead of registering a new one.
+async fn journaled_track<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_address: &Address,
+    chunk: &JournalChunk,
+    plan: &UploadPlan,
+) -> Result<Option<WrittenTrack>, TapedriveError> {
+    if plan_value_hash(plan)? != chunk.value_hash || plan.storage_units != chunk.stored_size {
+        return Ok(None);
+    }
+    let Ok(track) = client.get_track_by_number(tape_address, chunk.track_number).await else {
+        return Ok(None);
+    };
+    if !track.is_registered() || track.value_hash != chunk.value_hash {
+        return Ok(None);
+    }
+    Ok(Some(WrittenTrack {
+        address: track_pda(track.tape, track.track_number).0,
+        track,
+    }))
+}
+
 /// Run chunk writes as a pipeline: register chunks one at a time at processed
 /// level, resolve their track numbers concurrently in track order, keep up to
 /// STORE_CONCURRENCY slice uploads in flight, and certify stored chunks
 /// strictly in track order behind the uploads. A local mirror of the tape's
 /// track tree supplies certify proofs without per-chunk refetches. Stage
 /// errors cancel the whole pipeline; incomplete tracks are left for the
-/// recovery worker.
+/// recovery worker. Each chunk passes through the run's transform before it
+/// is encoded, and is journaled once registered and again once certified.
 async fn pipeline_chunks<Blockchain, Cluster, Chunks>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     tape: &Tape,
-    size: StorageUnits,
-    chunk_count: TrackNumber,
+    run: ChunkRun,
     chunk_sources: Chunks,
 ) -> Result<Vec<PendingChunk>, TapedriveError>
 where
@@ -308,6 +807,17 @@ where
     Cluster: Api,
     Chunks: Stream<Item = Result<Vec<u8>, TapedriveError>>,
 {
+    let ChunkRun {
+        size,
+        chunk_count,
+        first_chunk,
+        transform,
+        journal,
+        journaled,
+        capacity,
+    } = run;
+    let journal = journal.as_deref();
+
     // The mirror seeds from the pre-stream track tree; the resolve stage
     // appends every registered track and the certify stage proves against
     // and updates the same tree, so both share it behind a mutex.
@@ -336,7 +846,7 @@ where
             .min(MAX_ENCODE_WORKERS);
         let mut in_flight = FuturesOrdered::new();
         let mut chunk_sources = std::pin::pin!(chunk_sources);
-        let mut chunk_index = 0usize;
+        let mut chunk_index = first_chunk;
         while let Some(chunk_data) = chunk_sources.next().await {
             if in_flight.len() >= workers {
                 let Some(encoded) = in_flight.next().await else { break };
@@ -346,8 +856,9 @@ where
             }
             let data = chunk_data?;
             let index = chunk_index;
+            let transform = transform.clone();
             in_flight.push_back(async move {
-                encode_blob(client, data, Operation::WriteStream)
+                encode_stream_chunk(client, data, transform, index as u64, Operation::WriteStream)
                     .await
                     .map(|plan| (index, plan))
             });
@@ -365,19 +876,45 @@ where
     // keep track numbers assigned in stream order, and the confirmed-level
     // event wait moves into the resolve stage.
     let register_stage = async move {
+        let tape_address = tape_key.address();
+        let mut capacity = capacity;
+        // Journaled tracks are only reused as an unbroken run, so chunk
+        // tracks stay in stream order once one has to be registered again.
+        let mut journaled = journaled.into_iter();
         while let Some((chunk_index, plan)) = encoded_receiver.recv().await {
-            let logical_size = plan.storage_units;
-            let sent = register_blob_processed(
-                client,
-                tape_key,
-                UNNAMED_TRACK,
-                UNTYPED_TRACK,
-                logical_size,
-                plan,
-                Operation::WriteStream,
-            )
-            .await?;
-            if sent_sender.send((chunk_index, sent)).await.is_err() {
+            // A chunk an interrupted run registered keeps its track and the
+            // capacity it already took.
+            let registered = match journaled.next() {
+                Some(chunk) => journaled_track(client, &tape_address, &chunk, &plan).await?,
+                None => None,
+            };
+            let registration = match registered {
+                Some(written) => Registration::Journaled(written, plan),
+                None => {
+                    journaled = Vec::new().into_iter();
+                    let logical_size = plan.storage_units;
+                    // Checked here rather than in preflight: a compressed
+                    // chunk's stored size is only known once it is encoded.
+                    capacity = capacity.checked_sub(logical_size).ok_or(
+                        TapedriveError::InsufficientCapacity {
+                            need: logical_size,
+                            available: capacity,
+                        },
+                    )?;
+                    let sent = register_blob_processed(
+                        client,
+                        tape_key,
+                        UNNAMED_TRACK,
+                        &ObjectHeaders::default(),
+                        logical_size,
+                        plan,
+                        Operation::WriteStream,
+                    )
+                    .await?;
+                    Registration::Sent(sent)
+                }
+            };
+            if sent_sender.send((chunk_index, registration)).await.is_err() {
                 break;
             }
         }
@@ -398,10 +935,14 @@ where
                     if is_registering && in_flight.len() < RESOLVE_CONCURRENCY =>
                 {
                     match sent {
-                        Some((chunk_index, sent)) => in_flight.push_back(async move {
-                            resolve_sent_blob(client, sent)
-                                .await
-                                .map(|resolved| (chunk_index, resolved))
+                        Some((chunk_index, registration)) => in_flight.push_back(async move {
+                            let (resolved, fresh) = match registration {
+                                Registration::Sent(sent) => {
+                                    (resolve_sent_blob(client, sent).await?, true)
+                                }
+                                Registration::Journaled(written, plan) => ((written, plan), false),
+                            };
+                            Ok::<_, TapedriveError>((chunk_index, resolved, fresh))
                         }),
                         None => is_registering = false,
                     }
@@ -410,8 +951,20 @@ where
                 // completes; a cancelled poll leaves every resolve in place.
                 resolved = in_flight.next(), if !in_flight.is_empty() => {
                     let Some(resolved) = resolved else { continue };
-                    let (chunk_index, (written, plan)) = resolved?;
+                    let (chunk_index, (written, plan), fresh) = resolved?;
                     append_to_mirror(mirror, &written).await?;
+                    if let Some(journal) = journal.filter(|_| fresh) {
+                        let track = &written.track;
+                        journal
+                            .record_written(
+                                chunk_index,
+                                track.track_number,
+                                track.value_hash,
+                                track.size,
+                            )
+                            .await
+                            .map_err(stream_error)?;
+                    }
                     let registered = RegisteredChunk {
                         entry: ChunkEntry {
                             track_number: written.track.track_number,
@@ -494,6 +1047,19 @@ where
         let mut pending_chunks = Vec::with_capacity(chunk_count.as_usize());
         while let Some((pending, collected)) = collected_receiver.recv().await {
             certify_chunk(client, tape_key, mirror, &pending.written, &collected).await?;
+            if let Some(journal) = journal {
+                let frontier = {
+                    let mirror = mirror.lock().await;
+                    JournalFrontier {
+                        root: mirror.root(),
+                        next_number: mirror.next_number(),
+                    }
+                };
+                journal
+                    .record_certified(pending.written.track.track_number, frontier)
+                    .await
+                    .map_err(stream_error)?;
+            }
             pending_chunks.push(pending);
         }
 
@@ -741,6 +1307,42 @@ async fn verify_mirror_root<Blockchain: Rpc, Cluster: Api>(
     .await
 }
 
+/// Chunks `first_chunk..chunk_count` of a stream, read in order from `reader`.
+fn reader_chunks<Reader: AsyncRead + Unpin>(
+    reader: &mut Reader,
+    first_chunk: usize,
+    chunk_count: TrackNumber,
+    size: StorageUnits,
+) -> impl Stream<Item = Result<Vec<u8>, TapedriveError>> + '_ {
+    stream::unfold(
+        (reader, first_chunk),
+        move |(reader, chunk_index)| async move {
+            if chunk_index >= chunk_count.as_usize() {
+                return None;
+            }
+            let result = read_chunk(reader, chunk_index, chunk_count, size).await;
+            Some((result, (reader, chunk_index + 1)))
+        },
+    )
+}
+
+/// Read past the leading bytes of the source reader that a resumed write
+/// already stored.
+async fn skip_stream_bytes<Reader: AsyncRead + Unpin>(
+    reader: &mut Reader,
+    bytes: StorageUnits,
+) -> Result<(), TapedriveError> {
+    let mut skipped = reader.take(bytes.to_bytes());
+    let read = tokio::io::copy(&mut skipped, &mut tokio::io::sink()).await?;
+    if read != bytes.to_bytes() {
+        return Err(stream_error(StreamError::InvalidInput(
+            "stream ended before declared size".into(),
+        )));
+    }
+
+    Ok(())
+}
+
 /// Read one chunk's bytes from the source reader.
 async fn read_chunk<Reader: AsyncRead + Unpin>(
     reader: &mut Reader,
@@ -808,8 +1410,12 @@ enum ManifestWriteMode {
     Coded,
 }
 
-fn manifest_write_mode(name: &[u8], manifest_bytes: &[u8]) -> ManifestWriteMode {
-    if inline_write_fits(name, manifest_bytes.len()) {
+fn manifest_write_mode(
+    name: &[u8],
+    headers: &ObjectHeaders,
+    manifest_bytes: &[u8],
+) -> ManifestWriteMode {
+    if inline_write_fits(name, headers, manifest_bytes.len()) {
         ManifestWriteMode::Inline
     } else {
         ManifestWriteMode::Coded
@@ -821,16 +1427,16 @@ async fn write_manifest<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
     manifest_bytes: &[u8],
 ) -> Result<WrittenTrack, TapedriveError> {
-    if manifest_write_mode(name, manifest_bytes) == ManifestWriteMode::Inline {
+    if manifest_write_mode(name, headers, manifest_bytes) == ManifestWriteMode::Inline {
         return submit_raw_with_logical_size(
             client,
             tape_key,
             name,
-            content_type,
+            headers,
             logical_size,
             manifest_bytes,
             Operation::WriteStream,
@@ -842,7 +1448,7 @@ async fn write_manifest<Blockchain: Rpc, Cluster: Api>(
         client,
         tape_key,
         name,
-        content_type,
+        headers,
         logical_size,
         manifest_bytes,
         Operation::WriteStream,
@@ -861,18 +1467,99 @@ async fn finalize_write<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     size: StorageUnits,
-    pending_chunks: Vec<PendingChunk>,
+    format: StreamFormat,
+    chunks: Vec<StoredChunk>,
+) -> Result<StreamReceipt, TapedriveError> {
+    let chunks_stored = chunks
+        .iter()
+        .fold(StorageUnits::zero(), |total, chunk| total.saturating_add(chunk.stored_size));
+    let entries = chunks.into_iter().map(|chunk| chunk.entry).collect();
+    let manifest = build_manifest(hash(name), size, entries, format.envelope, format.codec())
+        .map_err(stream_error)?;
+    write_stream_manifest(client, tape_key, name, headers, &manifest, chunks_stored).await
+}
+
+/// Write a named manifest over chunk tracks already stored on the tape.
+///
+/// The chunks stay shared with the stream `source` describes; only the new
+/// manifest track is written, so no slice is uploaded. Deleting either
+/// manifest leaves the chunks in place for the other. A sealed or compressed
+/// source keeps its envelope and codec.
+pub async fn relink_stream<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_key: &impl TapeOperator,
+    name: &[u8],
+    headers: &ObjectHeaders,
+    source: &ChunkManifest,
+) -> Result<StreamReceipt, TapedriveError> {
+    let manifest = ChunkManifest {
+        key: hash(name),
+        ..source.clone()
+    };
+    write_stream_manifest(client, tape_key, name, headers, &manifest, StorageUnits::zero()).await
+}
+
+/// Write a named manifest over runs of chunk tracks from [`write_chunks`],
+/// joined in the order given.
+///
+/// Each run's offsets start at zero; they are shifted to follow the runs
+/// before it. The manifest records the client's codec, so the runs must come
+/// from a client with the same compression.
+pub async fn link_stream<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_key: &impl TapeOperator,
+    name: &[u8],
+    headers: &ObjectHeaders,
+    runs: &[Vec<ChunkEntry>],
+) -> Result<StreamReceipt, TapedriveError> {
+    let (size, entries) = join_chunk_runs(runs).map_err(stream_error)?;
+    validate_stream_size(size).map_err(stream_error)?;
+    let codec = client.compression.map(|compression| compression.codec);
+    let manifest = build_manifest(hash(name), size, entries, None, codec).map_err(stream_error)?;
+    write_stream_manifest(client, tape_key, name, headers, &manifest, StorageUnits::zero()).await
+}
+
+/// Concatenate chunk runs into one stream's entries, returning its size.
+fn join_chunk_runs(
+    runs: &[Vec<ChunkEntry>],
+) -> Result<(StorageUnits, Vec<ChunkEntry>), StreamError> {
+    let mut total_size = StorageUnits::zero();
+    let mut entries = Vec::with_capacity(runs.iter().map(Vec::len).sum());
+    for run in runs {
+        let run_start = total_size;
+        for entry in run {
+            let offset = run_start
+                .checked_add(entry.offset)
+                .ok_or_else(|| StreamError::InvalidInput("stream size overflow".into()))?;
+            total_size = offset
+                .checked_add(entry.size)
+                .ok_or_else(|| StreamError::InvalidInput("stream size overflow".into()))?;
+            entries.push(ChunkEntry {
+                track_number: entry.track_number,
+                offset,
+                size: entry.size,
+            });
+        }
+    }
+    Ok((total_size, entries))
+}
+
+async fn write_stream_manifest<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    tape_key: &impl TapeOperator,
+    name: &[u8],
+    headers: &ObjectHeaders,
+    manifest: &ChunkManifest,
+    chunks_stored: StorageUnits,
 ) -> Result<StreamReceipt, TapedriveError> {
-    let entries = pending_chunks
-        .into_iter()
-        .map(|pending_chunk| pending_chunk.entry)
-        .collect();
-    let manifest = build_manifest(hash(name), size, entries).map_err(stream_error)?;
     let manifest_bytes = manifest.to_bytes().map_err(stream_error)?;
+    let size = manifest.total_size;
+    let stored_size =
+        chunks_stored.saturating_add(StorageUnits::from_bytes(manifest_bytes.len() as u64));
 
-    let manifest_track = write_manifest(client, tape_key, name, content_type, size, &manifest_bytes).await?;
+    let manifest_track = write_manifest(client, tape_key, name, headers, size, &manifest_bytes).await?;
     let manifest_address = track_pda(manifest_track.track.tape, manifest_track.track.track_number).0;
 
     Ok(StreamReceipt {
@@ -880,6 +1567,8 @@ async fn finalize_write<Blockchain: Rpc, Cluster: Api>(
         manifest: manifest_address,
         manifest_track_number: manifest_track.track.track_number,
         manifest_value_hash: manifest_track.track.value_hash,
+        logical_size: size,
+        stored_size,
     })
 }
 
@@ -913,7 +1602,7 @@ mod tests {
         let total_size = StorageUnits::from_bytes(MAX_TRACK_SIZE as u64 * chunk_count);
         let entries = build_entries(TrackNumber(0), TrackNumber(chunk_count), total_size)
             .expect("build entries");
-        build_manifest(key, total_size, entries)
+        build_manifest(key, total_size, entries, None, None)
             .expect("build manifest")
             .to_bytes()
             .expect("serialize manifest")
@@ -924,7 +1613,7 @@ mod tests {
         let manifest_bytes = sample_manifest_bytes(1);
 
         assert_eq!(
-            manifest_write_mode(b"roms/small.bin", &manifest_bytes),
+            manifest_write_mode(b"roms/small.bin", &ObjectHeaders::default(), &manifest_bytes),
             ManifestWriteMode::Inline
         );
     }
@@ -934,7 +1623,7 @@ mod tests {
         let manifest_bytes = sample_manifest_bytes(64);
 
         assert_eq!(
-            manifest_write_mode(b"roms/large.bin", &manifest_bytes),
+            manifest_write_mode(b"roms/large.bin", &ObjectHeaders::default(), &manifest_bytes),
             ManifestWriteMode::Coded
         );
     }
@@ -945,7 +1634,7 @@ mod tests {
         let key = Hash::from([0x11; 32]);
         let total_size = StorageUnits::from_bytes(MAX_TRACK_SIZE as u64);
         let entries = build_entries(TrackNumber(0), TrackNumber(1), total_size).expect("build entries");
-        let manifest = build_manifest(key, total_size, entries).expect("build manifest");
+        let manifest = build_manifest(key, total_size, entries, None, None).expect("build manifest");
         let manifest_bytes = manifest.to_bytes().expect("serialize manifest");
         let total_required = total_size + StorageUnits::from_bytes(manifest_bytes.len() as u64);
         let tape = make_tape(total_required.to_bytes() - 1, 0, 0);
@@ -961,6 +1650,34 @@ mod tests {
         }
     }
 
+    // compressed streams ask upfront for one chunk and the manifest, and
+    // leave the rest to the per-chunk check against the tape.
+    #[test]
+    fn compressed_capacity() {
+        let size = StorageUnits::from_bytes(MAX_TRACK_SIZE as u64 * 3);
+        let chunk_count = chunk_count_for_size(size).expect("chunk count");
+        let plain = StreamFormat::default();
+        let compressed = StreamFormat {
+            transform: ChunkTransform {
+                compression: Some(Compression::default()),
+                cipher: None,
+            },
+            envelope: None,
+        };
+
+        let plain = required_capacity(b"logs", size, chunk_count, 0, &plain).expect("plain");
+        assert_eq!(plain.upfront, size + plain.manifest);
+        let compressed =
+            required_capacity(b"logs", size, chunk_count, 0, &compressed).expect("compressed");
+        let one_chunk = StorageUnits::from_bytes(MAX_TRACK_SIZE as u64);
+        assert_eq!(compressed.upfront, one_chunk + compressed.manifest);
+        assert_eq!(compressed.tracks, TrackNumber(4));
+
+        let tape = make_tape(1000, 300, 0);
+        let capacity = chunk_capacity(&tape, StorageUnits::from_bytes(200));
+        assert_eq!(capacity, StorageUnits::from_bytes(500));
+    }
+
     // empty streams are rejected before chunk planning.
     #[test]
     fn empty_stream() {
@@ -974,6 +1691,26 @@ mod tests {
         }
     }
 
+    // joined runs follow one another, and each run keeps its own chunk sizes.
+    #[test]
+    fn chunk_runs() {
+        let first_size = StorageUnits::from_bytes(MAX_TRACK_SIZE as u64 + 10);
+        let first = build_entries(TrackNumber(4), TrackNumber(2), first_size).expect("entries");
+        let second_size = StorageUnits::from_bytes(7);
+        let second = build_entries(TrackNumber(9), TrackNumber(1), second_size).expect("entries");
+
+        let (size, entries) = join_chunk_runs(&[first, second]).expect("join");
+        assert_eq!(size, first_size + second_size);
+        let offsets: Vec<_> = entries.iter().map(|entry| entry.offset.to_bytes()).collect();
+        assert_eq!(offsets, [0, MAX_TRACK_SIZE as u64, MAX_TRACK_SIZE as u64 + 10]);
+        assert_eq!(entries[2].track_number, TrackNumber(9));
+        assert_eq!(entries[2].size, second_size);
+
+        let manifest = build_manifest(Hash::default(), size, entries, None, None).expect("build");
+        let bytes = manifest.to_bytes().expect("serialize");
+        assert_eq!(ChunkManifest::from_bytes(&bytes).expect("valid"), manifest);
+    }
+
     // manifest entries reject track number overflow.
     #[test]
     fn overflow() {
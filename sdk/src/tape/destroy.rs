@@ -11,7 +11,11 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
     pub async fn destroy(&self, tape_key: &TapeKey) -> Result<(), TapedriveError> {
         let payer = self.payer()?;
         let tape_signer = tape_key.keypair();
-        let ix = build_destroy_tape_ix(payer.pubkey().into(), tape_key.pubkey().into());
+        let ix = build_destroy_tape_ix(
+            payer.pubkey().into(),
+            tape_key.pubkey().into(),
+            tape_key.address(),
+        );
 
         self.rpc()
             .send_instructions_with_signers(payer, vec![ix], &[tape_signer])
@@ -0,0 +1,127 @@
+use rpc::Rpc;
+use tape_api::helpers::build_authority_with_tokens_ix;
+use tape_api::instruction::{
+    build_fund_tape_escrow_ix, build_renew_tape_ix, build_withdraw_tape_escrow_ix,
+};
+use tape_api::state::{Escrow, Tape};
+use tape_core::types::coin::{Coin, TAPE};
+use tape_core::types::EpochNumber;
+use tape_crypto::address::Address;
+use tape_protocol::Api;
+
+use crate::error::TapedriveError;
+use crate::keys::tape_key::TapeKey;
+use crate::tape::price::reservation_cost;
+use crate::tapedrive::Tapedrive;
+
+/// Where a tape's renewal escrow stands at the current storage price.
+#[derive(Clone, Copy, Debug)]
+pub struct EscrowStatus {
+    pub escrow: Escrow,
+    /// Cost of the next renewal.
+    pub renewal_cost: Coin<TAPE>,
+    /// Renewals the balance covers if the price holds.
+    pub renewals_left: u64,
+    /// Expiry the tape reaches once those renewals have run.
+    pub funded_until: EpochNumber,
+}
+
+impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
+    /// Put `amount` into the tape's renewal escrow, opening it on first use.
+    /// Each renewal pushes expiry out by `renewal_epochs`; the value is only
+    /// read when the escrow is opened.
+    pub async fn fund_tape_escrow(
+        &self,
+        tape_key: &TapeKey,
+        tape: Address,
+        amount: Coin<TAPE>,
+        renewal_epochs: u64,
+    ) -> Result<Escrow, TapedriveError> {
+        let payer = self.payer()?;
+        let tape_signer = tape_key.keypair();
+
+        let mut ixs = build_authority_with_tokens_ix(
+            payer.pubkey().into(),
+            tape_key.pubkey().into(),
+            amount,
+        )
+        .map_err(|error| TapedriveError::InvalidArgument(error.to_string()))?;
+
+        ixs.push(build_fund_tape_escrow_ix(
+            payer.pubkey().into(),
+            tape_key.pubkey().into(),
+            tape,
+            amount,
+            renewal_epochs,
+        ));
+
+        self.rpc()
+            .send_instructions_with_signers(payer, ixs, &[tape_signer])
+            .await?;
+
+        Ok(self.rpc().get_escrow(&tape).await?)
+    }
+
+    /// Take `amount` back out of the escrow into the tape key's token account.
+    /// Emptying the escrow closes it.
+    pub async fn withdraw_tape_escrow(
+        &self,
+        tape_key: &TapeKey,
+        tape: Address,
+        amount: Coin<TAPE>,
+    ) -> Result<(), TapedriveError> {
+        let payer = self.payer()?;
+        let tape_signer = tape_key.keypair();
+        let ix = build_withdraw_tape_escrow_ix(
+            payer.pubkey().into(),
+            tape_key.pubkey().into(),
+            tape,
+            amount,
+        );
+
+        self.rpc()
+            .send_instructions_with_signers(payer, vec![ix], &[tape_signer])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Report the escrow balance and how far it carries the tape.
+    pub async fn tape_escrow_status(&self, tape: &Address) -> Result<EscrowStatus, TapedriveError> {
+        let escrow = self.rpc().get_escrow(tape).await?;
+        let state = self.get_tape(tape).await?;
+        let archive = self.rpc().get_archive().await?;
+
+        let renewal_cost =
+            reservation_cost(archive.storage_price, state.capacity, escrow.renewal_epochs)?;
+        let renewals_left = match renewal_cost.flux() {
+            0 => 0,
+            cost => escrow.balance.flux() / cost,
+        };
+        let funded_until = EpochNumber(
+            state
+                .expiry_epoch
+                .as_u64()
+                .saturating_add(renewals_left.saturating_mul(escrow.renewal_epochs)),
+        );
+
+        Ok(EscrowStatus {
+            escrow,
+            renewal_cost,
+            renewals_left,
+            funded_until,
+        })
+    }
+
+    /// Renew a tape from its escrow. Committee nodes crank this on their own;
+    /// anyone may call it within `RENEWAL_WINDOW_EPOCHS` of expiry. A short
+    /// balance leaves the tape untouched.
+    pub async fn renew_tape(&self, tape: &Address) -> Result<Tape, TapedriveError> {
+        let payer = self.payer()?;
+        let ix = build_renew_tape_ix(payer.pubkey().into(), *tape);
+
+        self.rpc().send_instructions(payer, vec![ix]).await?;
+
+        self.get_tape(tape).await
+    }
+}
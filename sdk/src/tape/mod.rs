@@ -1,6 +1,9 @@
 mod price;
 mod delegate;
 mod destroy;
+mod escrow;
 mod extend;
 mod query;
 mod reserve;
+mod transfer;
+mod versioning;
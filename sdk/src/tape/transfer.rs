@@ -0,0 +1,60 @@
+use rpc::Rpc;
+use tape_api::instruction::{build_accept_tape_transfer_ix, build_propose_tape_transfer_ix};
+use tape_crypto::Address;
+use tape_protocol::Api;
+
+use crate::error::TapedriveError;
+use crate::keys::tape_key::TapeKey;
+use crate::tapedrive::Tapedrive;
+
+impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
+    /// Offer a tape to `new_authority`. Ownership only moves once the new
+    /// authority accepts; proposing `Address::default()` withdraws the offer.
+    ///
+    /// The tape address is passed explicitly because it stays fixed across
+    /// transfers, so a key that received the tape does not derive it.
+    pub async fn propose_tape_transfer(
+        &self,
+        tape_key: &TapeKey,
+        tape: Address,
+        new_authority: Address,
+    ) -> Result<(), TapedriveError> {
+        let payer = self.payer()?;
+        let tape_signer = tape_key.keypair();
+        let ix = build_propose_tape_transfer_ix(
+            payer.pubkey().into(),
+            tape_key.pubkey().into(),
+            tape,
+            new_authority,
+        );
+
+        self.rpc()
+            .send_instructions_with_signers(payer, vec![ix], &[tape_signer])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Accept a pending transfer, making `tape_key` the tape's authority.
+    /// Any delegate set by the previous owner is cleared. Operate the tape
+    /// afterwards with `tape_key.with_tape(tape)`.
+    pub async fn accept_tape_transfer(
+        &self,
+        tape_key: &TapeKey,
+        tape: Address,
+    ) -> Result<(), TapedriveError> {
+        let payer = self.payer()?;
+        let tape_signer = tape_key.keypair();
+        let ix = build_accept_tape_transfer_ix(
+            payer.pubkey().into(),
+            tape_key.pubkey().into(),
+            tape,
+        );
+
+        self.rpc()
+            .send_instructions_with_signers(payer, vec![ix], &[tape_signer])
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use rpc::Rpc;
+use tape_api::instruction::{
+    build_place_delete_marker_ix, build_remove_delete_marker_ix, build_set_tape_versioning_ix,
+};
+use tape_core::tape::TapeVersioning;
+use tape_core::types::TrackNumber;
+use tape_protocol::Api;
+
+use crate::error::TapedriveError;
+use crate::keys::operator::TapeOperator;
+use crate::tapedrive::Tapedrive;
+
+impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
+    /// Enable or suspend object versioning on the operator's tape. Nodes
+    /// apply the change when they replay the transaction.
+    pub async fn set_tape_versioning_as(
+        &self,
+        operator: &impl TapeOperator,
+        status: TapeVersioning,
+    ) -> Result<(), TapedriveError> {
+        let payer = self.payer()?;
+        let ix = build_set_tape_versioning_ix(
+            payer.pubkey().into(),
+            operator.pubkey().into(),
+            operator.address(),
+            status,
+        );
+
+        self.rpc()
+            .send_instructions_with_signers(payer, vec![ix], &[operator.keypair()])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Hide `name` behind a new delete marker on the operator's versioned
+    /// tape. Its versions and their tracks are kept.
+    pub async fn place_delete_marker_as(
+        &self,
+        operator: &impl TapeOperator,
+        name: &[u8],
+    ) -> Result<(), TapedriveError> {
+        let payer = self.payer()?;
+        let ix = build_place_delete_marker_ix(
+            payer.pubkey().into(),
+            operator.pubkey().into(),
+            operator.address(),
+            name,
+        )
+        .map_err(|error| TapedriveError::InvalidArgument(error.to_string()))?;
+
+        self.rpc()
+            .send_instructions_with_signers(payer, vec![ix], &[operator.keypair()])
+            .await?;
+
+        Ok(())
+    }
+
+    /// Remove the delete marker `marker` stacked on `track_number` from the
+    /// history of `name`, bringing back the version it hid.
+    pub async fn remove_delete_marker_as(
+        &self,
+        operator: &impl TapeOperator,
+        name: &[u8],
+        track_number: TrackNumber,
+        marker: u32,
+    ) -> Result<(), TapedriveError> {
+        let payer = self.payer()?;
+        let ix = build_remove_delete_marker_ix(
+            payer.pubkey().into(),
+            operator.pubkey().into(),
+            operator.address(),
+            name,
+            track_number,
+            marker,
+        )
+        .map_err(|error| TapedriveError::InvalidArgument(error.to_string()))?;
+
+        self.rpc()
+            .send_instructions_with_signers(payer, vec![ix], &[operator.keypair()])
+            .await?;
+
+        Ok(())
+    }
+}
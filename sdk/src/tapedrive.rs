@@ -8,8 +8,9 @@ use peer_manager::PeerManager;
 use rpc::Rpc;
 use rpc_client::RpcClient;
 use tape_core::prelude::{CompressedTrack, StorageUnits};
-use tape_core::types::ContentType;
+use tape_core::types::ObjectHeaders;
 use tape_crypto::prelude::{Address, Keypair};
+use tape_protocol::api::batch::SliceBatcher;
 use tape_protocol::{Api, ProtocolState};
 use tokio::io::{AsyncRead, AsyncWrite};
 
@@ -18,9 +19,16 @@ use crate::keys::operator::TapeOperator;
 use crate::keys::tape_key::TapeKey;
 use crate::metrics::{Metrics, Noop, Operation, Outcome, Phase, Timer};
 use crate::stream::{
+    compression::Compression,
+    envelope::KeyProvider,
+    journal::JournalStore,
+    manifest::{ChunkEntry, ChunkManifest},
     read::{read_bytes, read_into},
     receipt::StreamReceipt,
-    write::{write_bytes as write_stream_bytes, write_stream as write_reader_stream},
+    write::{
+        link_stream, relink_stream, resume_stream, write_bytes as write_stream_bytes,
+        write_chunks, write_stream as write_reader_stream,
+    },
 };
 use crate::track::write::{UNNAMED_TRACK, UNTYPED_TRACK};
 
@@ -31,9 +39,18 @@ pub struct Tapedrive<Blockchain: Rpc, Cluster: Api> {
     pub state: ArcSwap<ProtocolState>,
     pub peer_manager: Arc<PeerManager>,
     pub api: Arc<Cluster>,
+    /// Coalesces slice uploads and reads into batch requests per node.
+    pub slices: Arc<SliceBatcher<Cluster>>,
     pub rpc: Arc<RpcClient<Blockchain>>,
     pub payer: Option<Keypair>,
     pub metrics: Arc<dyn Metrics>,
+    /// Seals stream writes and opens sealed streams on read, when attached.
+    pub key_provider: Option<Arc<dyn KeyProvider>>,
+    /// Compresses the chunks of stream writes, when set.
+    pub compression: Option<Compression>,
+    /// Records stream write progress so interrupted writes can resume, when
+    /// attached.
+    pub journal: Option<Arc<dyn JournalStore>>,
 }
 
 /// Default constructor using `HttpApi`.
@@ -54,10 +71,14 @@ impl<Blockchain: Rpc> Tapedrive<Blockchain, HttpApi> {
         Self {
             state: ArcSwap::from_pointee(ProtocolState::default()),
             peer_manager,
+            slices: Arc::new(SliceBatcher::new(api.clone())),
             api,
             rpc: rpc_client,
             payer: None,
             metrics: Arc::new(Noop),
+            key_provider: None,
+            compression: None,
+            journal: None,
         }
     }
 }
@@ -74,10 +95,14 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         Self {
             state,
             peer_manager,
+            slices: Arc::new(SliceBatcher::new(api.clone())),
             api,
             rpc,
             payer,
             metrics: Arc::new(Noop),
+            key_provider: None,
+            compression: None,
+            journal: None,
         }
     }
 
@@ -93,6 +118,44 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         self
     }
 
+    /// Attach or replace the key provider for client-side encryption.
+    ///
+    /// Stream writes (and `put_object`) are then sealed per chunk under a
+    /// fresh data key the provider wraps; single-track writes stay plaintext.
+    pub fn with_key_provider(mut self, key_provider: Arc<dyn KeyProvider>) -> Self {
+        self.key_provider = Some(key_provider);
+        self
+    }
+
+    /// Compress the chunks of stream writes before they are encoded.
+    ///
+    /// Each chunk becomes its own frame, so reads (ranged ones included)
+    /// decompress only the chunks they touch. Reads of compressed streams need
+    /// no setting.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Attach or replace the store that journals stream writes.
+    ///
+    /// Each stream write then records its chunk tracks as they register and
+    /// certify; an interrupted write can be finished with
+    /// [`resume_named_stream_as`](Self::resume_named_stream_as).
+    pub fn with_journal(mut self, journal: Arc<dyn JournalStore>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Share a slice batcher with other clients on the same cluster.
+    ///
+    /// Clients built per request (as a gateway does) only coalesce each
+    /// other's slices when they batch through one batcher.
+    pub fn with_slice_batcher(mut self, slices: Arc<SliceBatcher<Cluster>>) -> Self {
+        self.slices = slices;
+        self
+    }
+
     /// Access the underlying RPC client.
     pub fn rpc(&self) -> &RpcClient<Blockchain> {
         &self.rpc
@@ -140,7 +203,7 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
     pub async fn write_named(
         &self,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
         data: &[u8],
         epochs: u64,
     ) -> Result<(TapeKey, CompressedTrack), TapedriveError> {
@@ -161,7 +224,7 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         }
 
         let result = self
-            .write_named_track(&tape_key, name, content_type, data)
+            .write_named_track(&tape_key, name, headers, data)
             .await;
         total.finish_result(&result);
         let track = result?;
@@ -190,16 +253,16 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
 
     /// Write named in-memory bytes to an existing tape as a logical stream.
     ///
-    /// The manifest track carries the object's name and content type; internal
+    /// The manifest track carries the object's name and headers; internal
     /// chunk tracks remain unnamed.
     pub async fn write_named_bytes(
         &self,
         tape_key: &TapeKey,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
         data: &[u8],
     ) -> Result<StreamReceipt, TapedriveError> {
-        self.write_named_bytes_as(tape_key, name, content_type, data)
+        self.write_named_bytes_as(tape_key, name, headers, data)
             .await
     }
 
@@ -208,13 +271,13 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         &self,
         operator: &impl TapeOperator,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
         data: &[u8],
     ) -> Result<StreamReceipt, TapedriveError> {
         let timer = self
             .timer(Operation::WriteStream, Phase::Total)
             .bytes(data.len() as u64);
-        let result = write_stream_bytes(self, operator, name.as_ref(), content_type, data).await;
+        let result = write_stream_bytes(self, operator, name.as_ref(), &headers.into(), data).await;
         timer.finish_result(&result);
         result
     }
@@ -241,17 +304,17 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
 
     /// Write a named byte stream from an async reader into an existing tape.
     ///
-    /// The manifest track carries the object's name and content type; internal
+    /// The manifest track carries the object's name and headers; internal
     /// chunk tracks remain unnamed.
     pub async fn write_named_stream<Reader: AsyncRead + Unpin>(
         &self,
         tape_key: &TapeKey,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
         size: StorageUnits,
         reader: Reader,
     ) -> Result<StreamReceipt, TapedriveError> {
-        self.write_named_stream_as(tape_key, name, content_type, size, reader)
+        self.write_named_stream_as(tape_key, name, headers, size, reader)
             .await
     }
 
@@ -260,7 +323,30 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         &self,
         operator: &impl TapeOperator,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
+        size: StorageUnits,
+        reader: Reader,
+    ) -> Result<StreamReceipt, TapedriveError> {
+        let timer = self
+            .timer(Operation::WriteStream, Phase::Total)
+            .bytes(size.to_bytes());
+        let result =
+            write_reader_stream(self, operator, name.as_ref(), &headers.into(), size, reader).await;
+        timer.finish_result(&result);
+        result
+    }
+
+    /// Finish a named stream write that an interrupted run journaled.
+    ///
+    /// Needs a journal store (see [`with_journal`](Self::with_journal)). The
+    /// reader must yield the same `size` bytes as the interrupted write, from
+    /// the start; chunks already certified are skipped over rather than
+    /// written again.
+    pub async fn resume_named_stream_as<Reader: AsyncRead + Unpin>(
+        &self,
+        operator: &impl TapeOperator,
+        name: impl AsRef<[u8]>,
+        headers: impl Into<ObjectHeaders>,
         size: StorageUnits,
         reader: Reader,
     ) -> Result<StreamReceipt, TapedriveError> {
@@ -268,7 +354,53 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
             .timer(Operation::WriteStream, Phase::Total)
             .bytes(size.to_bytes());
         let result =
-            write_reader_stream(self, operator, name.as_ref(), content_type, size, reader).await;
+            resume_stream(self, operator, name.as_ref(), &headers.into(), size, reader).await;
+        timer.finish_result(&result);
+        result
+    }
+
+    /// Write a named manifest over the chunks of an existing stream on the
+    /// same tape, e.g. to copy or rename an object without re-uploading it.
+    pub async fn relink_stream_as(
+        &self,
+        operator: &impl TapeOperator,
+        name: impl AsRef<[u8]>,
+        headers: impl Into<ObjectHeaders>,
+        source: &ChunkManifest,
+    ) -> Result<StreamReceipt, TapedriveError> {
+        let timer = self.timer(Operation::WriteStream, Phase::Total);
+        let result = relink_stream(self, operator, name.as_ref(), &headers.into(), source).await;
+        timer.finish_result(&result);
+        result
+    }
+
+    /// Write bytes from an async reader as chunk tracks that no manifest
+    /// names yet, e.g. one part of a multipart upload.
+    pub async fn write_chunks_as<Reader: AsyncRead + Unpin>(
+        &self,
+        operator: &impl TapeOperator,
+        size: StorageUnits,
+        reader: Reader,
+    ) -> Result<Vec<ChunkEntry>, TapedriveError> {
+        let timer = self
+            .timer(Operation::WriteStream, Phase::Total)
+            .bytes(size.to_bytes());
+        let result = write_chunks(self, operator, size, reader).await;
+        timer.finish_result(&result);
+        result
+    }
+
+    /// Write a named manifest joining runs of chunk tracks written by
+    /// [`write_chunks_as`](Self::write_chunks_as), in order.
+    pub async fn link_stream_as(
+        &self,
+        operator: &impl TapeOperator,
+        name: impl AsRef<[u8]>,
+        headers: impl Into<ObjectHeaders>,
+        runs: &[Vec<ChunkEntry>],
+    ) -> Result<StreamReceipt, TapedriveError> {
+        let timer = self.timer(Operation::WriteStream, Phase::Total);
+        let result = link_stream(self, operator, name.as_ref(), &headers.into(), runs).await;
         timer.finish_result(&result);
         result
     }
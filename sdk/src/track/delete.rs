@@ -1,7 +1,13 @@
+use futures::stream::{self, StreamExt};
 use rpc::Rpc;
+use tape_api::compute::{MAX_COMPUTE_UNIT_LIMIT, TRACK_WRITE_CU};
 use tape_api::instruction::build_delete_track_ix;
+use tape_core::track::TRACK_TREE_HEIGHT;
+use tape_core::track::types::CompressedTrackProof;
 use tape_crypto::address::Address;
+use tape_crypto::merkle::{advance_proof, compute_path, hash_leaf};
 use tape_protocol::Api;
+use tracing::debug;
 
 use crate::error::TapedriveError;
 use crate::keys::operator::TapeOperator;
@@ -9,6 +15,9 @@ use crate::keys::tape_key::TapeKey;
 use crate::tapedrive::Tapedrive;
 use crate::track::query;
 
+/// Track proofs fetched from peers at once.
+const PROOF_FETCH_CONCURRENCY: usize = 16;
+
 impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
     /// Delete a concrete track version and free its capacity on the tape.
     pub async fn delete(&self, tape_key: &TapeKey, track: Address) -> Result<(), TapedriveError> {
@@ -32,4 +41,180 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
 
         Ok(())
     }
+
+    /// Delete many track versions as an arbitrary TapeOperator, packing as
+    /// many deletes into each transaction as the serialized transaction size
+    /// and the compute budget allow.
+    ///
+    /// Proofs are fetched once and advanced locally past every committed
+    /// delete, since peers only serve proofs against the new root once they
+    /// have ingested the block. A send that reports an error may still have
+    /// landed, so the chain decides whether it did, and the proofs still
+    /// pending are fetched again before the next send. A transaction that did
+    /// not land is retried one delete at a time so a single bad track does not
+    /// fail its neighbours. The returned results line up with `tracks`.
+    pub async fn delete_batch_as(
+        &self,
+        operator: &impl TapeOperator,
+        tracks: &[Address],
+    ) -> Result<Vec<Result<(), TapedriveError>>, TapedriveError> {
+        let payer = self.payer()?;
+        let tape_signer = operator.keypair();
+        let fee_payer: Address = payer.pubkey().into();
+        let authority: Address = operator.pubkey().into();
+
+        let mut results: Vec<Option<Result<(), TapedriveError>>> =
+            tracks.iter().map(|_| None).collect();
+        for (slot, track) in tracks.iter().enumerate() {
+            if tracks[..slot].contains(track) {
+                results[slot] = Some(Err(TapedriveError::InvalidArgument(format!(
+                    "track {track} is listed more than once"
+                ))));
+            }
+        }
+
+        // Pending deletes in request order, each proof current against the
+        // committed root.
+        let unique: Vec<usize> =
+            (0..tracks.len()).filter(|slot| results[*slot].is_none()).collect();
+        let mut pending = self.fetch_proofs(tracks, unique, &mut results).await;
+
+        // Deletes left to send alone after a batch they were part of failed.
+        let mut solo = 0;
+        while !pending.is_empty() {
+            let limit = if solo > 0 { 1 } else { pending.len() };
+
+            // A delete carries its whole track proof, so the transaction size
+            // usually caps a batch well before the compute budget does.
+            let mut batch = vec![pending[0].1];
+            let mut instructions = vec![build_delete_track_ix(fee_payer, authority, pending[0].1)];
+            while batch.len() < limit
+                && TRACK_WRITE_CU * (batch.len() as u32 + 1) <= MAX_COMPUTE_UNIT_LIMIT
+            {
+                let mut next = pending[batch.len()].1;
+                advance_past(&mut next, &batch)?;
+                instructions.push(build_delete_track_ix(fee_payer, authority, next));
+                if !self.rpc().fits_capped_transaction(payer, &instructions) {
+                    instructions.pop();
+                    break;
+                }
+                batch.push(next);
+            }
+
+            let count = batch.len();
+            let result = self
+                .rpc()
+                .send_instructions_with_signers_and_compute_unit_limit(
+                    payer,
+                    TRACK_WRITE_CU * count as u32,
+                    instructions,
+                    &[tape_signer],
+                    self.rpc().rpc().commitment(),
+                    false,
+                )
+                .await;
+
+            let error = match result {
+                Ok(_) => None,
+                Err(error) if self.batch_landed(&batch).await => {
+                    debug!("delete batch of {count} landed despite {error}");
+                    None
+                }
+                Err(error) => Some(error),
+            };
+
+            match error {
+                None => {
+                    for (slot, _) in pending.drain(..count) {
+                        results[slot] = Some(Ok(()));
+                    }
+                    for (_, proof) in pending.iter_mut() {
+                        advance_past(proof, &batch)?;
+                    }
+                    solo = solo.saturating_sub(count);
+                    continue;
+                }
+                Some(error) if count == 1 => {
+                    let (slot, _) = pending.remove(0);
+                    results[slot] = Some(Err(error.into()));
+                    solo = solo.saturating_sub(1);
+                }
+                Some(_) => solo = count,
+            }
+
+            // The tape root may have moved under the failed send, so the
+            // local proofs are no longer trusted.
+            let slots = pending.iter().map(|(slot, _)| *slot).collect();
+            pending = self.fetch_proofs(tracks, slots, &mut results).await;
+            solo = solo.min(pending.len());
+        }
+
+        Ok(results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|| Err(TapedriveError::NotFound)))
+            .collect())
+    }
+
+    /// Fetch current proofs for the tracks at `slots`, a bounded number at a
+    /// time. A track whose proof cannot be fetched gets its error in
+    /// `results` and drops out.
+    async fn fetch_proofs(
+        &self,
+        tracks: &[Address],
+        slots: Vec<usize>,
+        results: &mut [Option<Result<(), TapedriveError>>],
+    ) -> Vec<(usize, CompressedTrackProof)> {
+        let proofs: Vec<_> = stream::iter(slots)
+            .map(|slot| async move { (slot, query::query_track_proof(self, &tracks[slot]).await) })
+            .buffered(PROOF_FETCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut pending = Vec::with_capacity(proofs.len());
+        for (slot, proof) in proofs {
+            match proof {
+                Ok(proof) => pending.push((slot, proof)),
+                Err(error) => results[slot] = Some(Err(error)),
+            }
+        }
+        pending
+    }
+
+    /// Whether a batch whose send reported an error landed anyway. The batch
+    /// is one transaction, so its last delete being on chain settles it; a
+    /// tape that cannot be read counts as not landed.
+    async fn batch_landed(&self, batch: &[CompressedTrackProof]) -> bool {
+        let Some(last) = batch.last() else {
+            return false;
+        };
+        let tape_address: Address = last.state.tape.into();
+        let Ok(tape) = self.rpc().get_tape_by_address(&tape_address).await else {
+            return false;
+        };
+        let mut tree = tape.tracks.tree;
+        tree.ensure_initialized();
+        tree.verify_hash(last.state.track_number.0, &last.proof, hash_leaf(&[]))
+            .unwrap_or(false)
+    }
+}
+
+/// Advance `proof` past the removal of each track in `removed`, whose proofs
+/// were current when it was removed. Tracks on other tapes leave it untouched.
+fn advance_past(
+    proof: &mut CompressedTrackProof,
+    removed: &[CompressedTrackProof],
+) -> Result<(), TapedriveError> {
+    let tape = proof.state.tape;
+    let index = proof.state.track_number.0;
+    for removed in removed.iter().filter(|removed| removed.state.tape == tape) {
+        let path = compute_path(
+            &removed.proof,
+            hash_leaf(&[]),
+            removed.state.track_number.0,
+            TRACK_TREE_HEIGHT,
+        );
+        advance_proof(&mut proof.proof, index, removed.state.track_number.0, &path)
+            .map_err(|error| TapedriveError::InvalidArgument(format!("track proof: {error:?}")))?;
+    }
+    Ok(())
 }
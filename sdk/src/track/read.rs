@@ -107,7 +107,7 @@ pub async fn read_track<Blockchain: Rpc, Cluster: Api>(
         let download = client.timer(operation, Phase::Download);
 
         let slices = downloader
-            .download_enough_slices(client.api.as_ref())
+            .download_enough_slices(client.slices.as_ref())
             .await
             .map_err(ClientError::Download);
 
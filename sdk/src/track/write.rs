@@ -12,6 +12,7 @@ use tape_api::instruction::{build_certify_track_ix, build_track_write_ix, track_
 use solana_instruction::Instruction;
 use tape_blocks::{parse_event_data, TapedriveEvent};
 use tape_core::bft::min_correct;
+use tape_core::encoding::LeafScheme;
 use tape_core::erasure::GROUP_SIZE;
 use tape_core::prelude::{
     BlobEncoding, CompressedTrack, EncodingProfile, EpochNumber, GroupIndex, StorageUnits,
@@ -20,7 +21,7 @@ use tape_core::prelude::{
 use tape_core::track::data::{track_key, BlobData, BlobDataSlice, BlobInfo, TrackObjectInfo};
 use tape_core::track::mirror::ArchiveMirror;
 use tape_core::track::types::CompressedTrackProof;
-use tape_core::types::ContentType;
+use tape_core::types::{ContentType, ObjectHeaders, MAX_OBJECT_HEADERS_LEN};
 use tape_crypto::prelude::{Address, Hash};
 use tape_crypto::tx::Txid;
 use tape_protocol::Api;
@@ -37,6 +38,7 @@ use crate::error::TapedriveError;
 use crate::keys::operator::TapeOperator;
 use crate::keys::tape_key::TapeKey;
 use crate::metrics::{Operation, Phase};
+use crate::stream::write::ChunkTransform;
 use crate::tapedrive::Tapedrive;
 use crate::track::{bootstrap_network_state, query};
 use crate::transfer::certify::{CertificationCollector, CollectedSignatures};
@@ -109,10 +111,10 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         &self,
         tape_key: &TapeKey,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
         data: &[u8],
     ) -> Result<CompressedTrack, TapedriveError> {
-        self.write_named_track_as(tape_key, name, content_type, data)
+        self.write_named_track_as(tape_key, name, headers, data)
             .await
     }
 
@@ -121,14 +123,14 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         &self,
         operator: &impl TapeOperator,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
         data: &[u8],
     ) -> Result<CompressedTrack, TapedriveError> {
         write_track(
             self,
             operator,
             name.as_ref(),
-            content_type,
+            &headers.into(),
             data,
         )
         .await
@@ -156,11 +158,12 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         &self,
         tape_key: &TapeKey,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
         raw: &[u8],
     ) -> Result<CompressedTrack, TapedriveError> {
         let name = name.as_ref();
-        if !inline_write_fits(name, raw.len()) {
+        let headers = headers.into();
+        if !inline_write_fits(name, &headers, raw.len()) {
             return Err(TapedriveError::InvalidArgument(format!(
                 "raw inline write exceeds SDK transaction limit; use write_track() or write_blob()"
             )));
@@ -174,7 +177,7 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
             self,
             tape_key,
             name,
-            content_type,
+            &headers,
             raw,
             Operation::WriteRaw
         ).await;
@@ -207,7 +210,7 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
         &self,
         tape_key: &TapeKey,
         name: impl AsRef<[u8]>,
-        content_type: ContentType,
+        headers: impl Into<ObjectHeaders>,
         data: &[u8],
     ) -> Result<(WrittenTrack, UploadPlan), TapedriveError> {
         let timer = self
@@ -218,7 +221,7 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
             self,
             tape_key,
             name.as_ref(),
-            content_type,
+            &headers.into(),
             data,
             Operation::WriteBlob
         ).await;
@@ -272,7 +275,7 @@ impl<Blockchain: Rpc, Cluster: Api> Tapedrive<Blockchain, Cluster> {
 
 fn prepare_plan(data: Vec<u8>) -> Result<UploadPlan, TapedriveError> {
     let data_len = data.len();
-    let profile = EncodingProfile::clay_default();
+    let profile = EncodingProfile::clay_default().with_leaf_scheme(LeafScheme::Segmented);
     let mut encoder = BlobEncoder::with_profile(profile);
     let (slices, merkle_root, leaves) = encoder
         .encode_with_leaves(data)
@@ -307,12 +310,38 @@ pub(crate) async fn encode_blob<Blockchain: Rpc, Cluster: Api>(
     result
 }
 
+/// Transform chunk `index` of a stream (compress, then seal) and encode the
+/// result into its upload plan, both on one blocking thread.
+pub(crate) async fn encode_stream_chunk<Blockchain: Rpc, Cluster: Api>(
+    client: &Tapedrive<Blockchain, Cluster>,
+    data: Vec<u8>,
+    transform: ChunkTransform,
+    index: u64,
+    operation: Operation,
+) -> Result<UploadPlan, TapedriveError> {
+    let encode_timer = client
+        .timer(operation, Phase::Encode)
+        .bytes(data.len() as u64);
+    let transform_and_encode = move || {
+        let stored = transform
+            .apply(index, data)
+            .map_err(|error| TapedriveError::Stream(error.to_string()))?;
+        prepare_plan(stored)
+    };
+    let result = match tokio::task::spawn_blocking(transform_and_encode).await {
+        Ok(plan) => plan,
+        Err(join) => Err(TapedriveError::Encoding(format!("encode task failed: {join}"))),
+    };
+    encode_timer.finish_result(&result);
+    result
+}
+
 /// Register an already-encoded blob on-chain.
 pub(crate) async fn register_blob<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
     plan: UploadPlan,
     operation: Operation,
@@ -321,25 +350,32 @@ pub(crate) async fn register_blob<Blockchain: Rpc, Cluster: Api>(
         .timer(operation, Phase::Register)
         .bytes(plan.storage_units.to_bytes())
         .chunks(1);
-    let result = send_blob(client, tape_key, name, content_type, logical_size, plan).await;
+    let result = send_blob(client, tape_key, name, headers, logical_size, plan).await;
     register_timer.finish_result(&result);
     result
 }
 
 fn track_object(
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
-) -> Option<TrackObjectInfo> {
+) -> Result<Option<TrackObjectInfo>, TapedriveError> {
     if name.is_empty() {
-        None
-    } else {
-        Some(TrackObjectInfo {
-            name: name.to_vec(),
-            content_type,
-            logical_size,
-        })
+        return Ok(None);
     }
+
+    let (content_type, headers) = headers.to_wire().ok_or_else(|| {
+        TapedriveError::InvalidArgument(format!(
+            "object headers take {} bytes, above the {MAX_OBJECT_HEADERS_LEN} byte limit",
+            headers.encoded_len()
+        ))
+    })?;
+    Ok(Some(TrackObjectInfo {
+        name: name.to_vec(),
+        content_type,
+        logical_size,
+        headers,
+    }))
 }
 
 fn inline_write_data_limit() -> usize {
@@ -347,10 +383,17 @@ fn inline_write_data_limit() -> usize {
         .expect("unnamed inline track write size should fit usize")
 }
 
-pub(crate) fn inline_write_fits(name: &[u8], payload_len: usize) -> bool {
-    let object_name_len = (!name.is_empty()).then_some(name.len());
+pub(crate) fn inline_write_fits(name: &[u8], headers: &ObjectHeaders, payload_len: usize) -> bool {
+    let object_len = if name.is_empty() {
+        None
+    } else {
+        match headers.to_wire() {
+            Some((_, trailer)) => Some(name.len() + trailer.len()),
+            None => return false,
+        }
+    };
 
-    track_write_ix_len(payload_len, object_name_len)
+    track_write_ix_len(payload_len, object_len)
         .is_some_and(|len| len <= inline_write_data_limit())
 }
 
@@ -358,7 +401,7 @@ async fn submit_raw<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     raw: &[u8],
     operation: Operation,
 ) -> Result<WrittenTrack, TapedriveError> {
@@ -366,7 +409,7 @@ async fn submit_raw<Blockchain: Rpc, Cluster: Api>(
         client,
         tape_key,
         name,
-        content_type,
+        headers,
         StorageUnits::from_bytes(raw.len() as u64),
         raw,
         operation,
@@ -378,7 +421,7 @@ pub(crate) async fn submit_raw_with_logical_size<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
     raw: &[u8],
     operation: Operation,
@@ -388,7 +431,7 @@ pub(crate) async fn submit_raw_with_logical_size<Blockchain: Rpc, Cluster: Api>(
         .bytes(raw.len() as u64)
         .chunks(1);
 
-    let result = send_raw(client, tape_key, name, content_type, logical_size, raw).await;
+    let result = send_raw(client, tape_key, name, headers, logical_size, raw).await;
 
     timer.finish_result(&result);
     result
@@ -398,7 +441,7 @@ async fn send_raw<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
     raw: &[u8],
 ) -> Result<WrittenTrack, TapedriveError> {
@@ -406,7 +449,7 @@ async fn send_raw<Blockchain: Rpc, Cluster: Api>(
     let tape_signer = tape_key.keypair();
     let data = BlobDataSlice::Inline(raw);
     let key = track_key(name, &data);
-    let object = track_object(name, content_type, logical_size);
+    let object = track_object(name, headers, logical_size)?;
 
     let write_ix = build_track_write_ix(
         payer.pubkey().into(),
@@ -457,7 +500,7 @@ pub(crate) async fn submit_blob<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     data: &[u8],
     operation: Operation,
 ) -> Result<(WrittenTrack, UploadPlan), TapedriveError> {
@@ -465,7 +508,7 @@ pub(crate) async fn submit_blob<Blockchain: Rpc, Cluster: Api>(
         client,
         tape_key,
         name,
-        content_type,
+        headers,
         StorageUnits::from_bytes(data.len() as u64),
         data,
         operation,
@@ -477,13 +520,13 @@ pub(crate) async fn submit_blob_with_logical_size<Blockchain: Rpc, Cluster: Api>
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
     data: &[u8],
     operation: Operation,
 ) -> Result<(WrittenTrack, UploadPlan), TapedriveError> {
     let plan = encode_blob(client, data.to_vec(), operation).await?;
-    register_blob(client, tape_key, name, content_type, logical_size, plan, operation).await
+    register_blob(client, tape_key, name, headers, logical_size, plan, operation).await
 }
 
 /// A registered blob whose transaction has been sent but whose TrackWritten
@@ -499,21 +542,14 @@ fn build_blob_write(
     payer: Address,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
     plan: &UploadPlan,
 ) -> Result<(Instruction, BlobEncoding, Hash), TapedriveError> {
-    let blob = BlobEncoding {
-        size: plan.storage_units,
-        commitment: plan.commitment_hash,
-        profile: plan.profile,
-        stripe_size: StorageUnits::from_bytes(plan.stripe_size as u64),
-        stripe_count: StripeCount(plan.stripe_count as u64),
-        leaves: plan.leaves,
-    };
+    let blob = plan_blob(plan);
 
     let key = track_key(name, &BlobDataSlice::Coded(blob));
-    let object = track_object(name, content_type, logical_size);
+    let object = track_object(name, headers, logical_size)?;
     let write_ix = build_track_write_ix(
         payer,
         tape_key.pubkey().into(),
@@ -528,6 +564,26 @@ fn build_blob_write(
     Ok((write_ix, blob, key))
 }
 
+/// The blob encoding a plan registers.
+fn plan_blob(plan: &UploadPlan) -> BlobEncoding {
+    BlobEncoding {
+        size: plan.storage_units,
+        commitment: plan.commitment_hash,
+        profile: plan.profile,
+        stripe_size: StorageUnits::from_bytes(plan.stripe_size as u64),
+        stripe_count: StripeCount(plan.stripe_count as u64),
+        leaves: plan.leaves,
+    }
+}
+
+/// Value hash of the track a plan registers, without registering it.
+pub(crate) fn plan_value_hash(plan: &UploadPlan) -> Result<Hash, TapedriveError> {
+    BlobDataSlice::Coded(plan_blob(plan))
+        .meta()
+        .map(|meta| meta.value_hash)
+        .ok_or(TapedriveError::InvalidArgument("invalid blob commitment".into()))
+}
+
 /// Resolve a sent register transaction into its written track. Waits until
 /// the transaction is queryable, so this carries the confirmed-level wait for
 /// registers sent at processed level.
@@ -569,7 +625,7 @@ pub(crate) async fn register_blob_processed<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
     plan: UploadPlan,
     operation: Operation,
@@ -577,7 +633,7 @@ pub(crate) async fn register_blob_processed<Blockchain: Rpc, Cluster: Api>(
     let payer = client.payer()?;
     let tape_signer = tape_key.keypair();
     let (write_ix, blob, key) =
-        build_blob_write(payer.pubkey().into(), tape_key, name, content_type, logical_size, &plan)?;
+        build_blob_write(payer.pubkey().into(), tape_key, name, headers, logical_size, &plan)?;
 
     let register_timer = client
         .timer(operation, Phase::Register)
@@ -604,14 +660,14 @@ async fn send_blob<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     logical_size: StorageUnits,
     plan: UploadPlan,
 ) -> Result<(WrittenTrack, UploadPlan), TapedriveError> {
     let payer = client.payer()?;
     let tape_signer = tape_key.keypair();
     let (write_ix, blob, key) =
-        build_blob_write(payer.pubkey().into(), tape_key, name, content_type, logical_size, &plan)?;
+        build_blob_write(payer.pubkey().into(), tape_key, name, headers, logical_size, &plan)?;
 
     let signature = client
         .rpc()
@@ -653,7 +709,7 @@ async fn upload_once<Blockchain: Rpc, Cluster: Api>(
         .chunks(chunks);
 
     let result = uploader
-        .upload_all(client.api.clone())
+        .upload_all(client.slices.clone())
         .await
         .map_err(TapedriveError::Upload);
 
@@ -998,7 +1054,7 @@ pub async fn write_track<Blockchain: Rpc, Cluster: Api>(
     client: &Tapedrive<Blockchain, Cluster>,
     tape_key: &impl TapeOperator,
     name: &[u8],
-    content_type: ContentType,
+    headers: &ObjectHeaders,
     data: &[u8],
 ) -> Result<CompressedTrack, TapedriveError> {
     let timer = client
@@ -1010,7 +1066,7 @@ pub async fn write_track<Blockchain: Rpc, Cluster: Api>(
                 client,
                 tape_key,
                 name,
-                content_type,
+                headers,
                 data,
                 Operation::WriteTrack,
             )
@@ -1029,7 +1085,7 @@ pub async fn write_track<Blockchain: Rpc, Cluster: Api>(
             client,
             tape_key,
             name,
-            content_type,
+            headers,
             StorageUnits::from_bytes(data.len() as u64),
             plan,
             Operation::WriteTrack,
@@ -1209,6 +1265,7 @@ mod tests {
     use super::should_retry_certification;
     use super::{inline_write_fits, SDK_INLINE_RAW_MAX_BYTES};
     use tape_api::instruction::TRACK_WRITE_MAX_BYTES;
+    use tape_core::types::{ContentType, ObjectHeaders};
 
     // The SDK inline write limit must always remain below the program limit.
     #[test]
@@ -1220,16 +1277,25 @@ mod tests {
     #[test]
     fn inline_write_budget_accounts_for_object_trailer() {
         let name = b"object/name";
+        let untyped = ObjectHeaders::default();
         let max_named_payload = (0..=SDK_INLINE_RAW_MAX_BYTES)
             .rev()
-            .find(|payload_len| inline_write_fits(name, *payload_len))
+            .find(|payload_len| inline_write_fits(name, &untyped, *payload_len))
             .expect("named inline payload should have some capacity");
 
-        assert!(inline_write_fits(b"", SDK_INLINE_RAW_MAX_BYTES));
-        assert!(!inline_write_fits(name, SDK_INLINE_RAW_MAX_BYTES));
+        assert!(inline_write_fits(b"", &untyped, SDK_INLINE_RAW_MAX_BYTES));
+        assert!(!inline_write_fits(name, &untyped, SDK_INLINE_RAW_MAX_BYTES));
         assert!(max_named_payload < SDK_INLINE_RAW_MAX_BYTES);
-        assert!(inline_write_fits(name, max_named_payload));
-        assert!(!inline_write_fits(name, max_named_payload + 1));
+        assert!(inline_write_fits(name, &untyped, max_named_payload));
+        assert!(!inline_write_fits(name, &untyped, max_named_payload + 1));
+
+        // A canonical content type rides the hot enum; anything else costs trailer bytes.
+        let typed = ObjectHeaders::from(ContentType::TextCsv);
+        assert!(inline_write_fits(name, &typed, max_named_payload));
+
+        let mut custom = ObjectHeaders::with_content_type("application/x-parquet");
+        custom.insert_metadata("pipeline", "ingest-7");
+        assert!(!inline_write_fits(name, &custom, max_named_payload));
     }
 
     // Certification should retry when proof visibility lags behind peer state.
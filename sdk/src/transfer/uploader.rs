@@ -39,17 +39,32 @@ pub struct SliceWithProof {
     pub data: Arc<Vec<u8>>,
     pub leaf_hash: Hash,
     pub merkle_proof: SliceMerkleProof,
+    /// Leaf hashes of the slice's segments, which rebuild `leaf_hash` under
+    /// the blob's leaf scheme.
+    pub segments: Vec<Hash>,
 }
 
 impl SliceWithProof {
     /// Create a new slice with proof.
-    pub fn new(index: SpoolIndex, data: Vec<u8>, leaf_hash: Hash, merkle_proof: SliceMerkleProof) -> Self {
-        Self { index, data: Arc::new(data), leaf_hash, merkle_proof }
+    pub fn new(
+        index: SpoolIndex,
+        data: Vec<u8>,
+        leaf_hash: Hash,
+        merkle_proof: SliceMerkleProof,
+        segments: Vec<Hash>,
+    ) -> Self {
+        Self { index, data: Arc::new(data), leaf_hash, merkle_proof, segments }
     }
 
-    /// Convert to SlicePayload for network transmission.
-    pub fn to_payload(&self) -> SlicePayload {
-        SlicePayload::new(self.data.as_ref().clone(), self.leaf_hash, self.merkle_proof.to_vec())
+    /// Convert to SlicePayload for network transmission. The payload is
+    /// shared by every attempt of the upload.
+    pub fn to_payload(&self) -> Arc<SlicePayload> {
+        Arc::new(SlicePayload::new(
+            self.data.as_ref().clone(),
+            self.leaf_hash,
+            self.merkle_proof.to_vec(),
+            self.segments.clone(),
+        ))
     }
 }
 
@@ -416,6 +431,7 @@ mod tests {
                     vec![i as u8; 100],
                     Hash::default(),
                     [Hash::default(); SLICE_TREE_HEIGHT],
+                    Vec::new(),
                 )
             })
             .collect()
@@ -470,6 +486,7 @@ mod tests {
             vec![0xAB; 500],
             Hash::default(),
             [Hash::default(); SLICE_TREE_HEIGHT],
+            vec![Hash::from([0x5A; 32]); 2],
         );
 
         let payload = slice.to_payload();
@@ -477,5 +494,6 @@ mod tests {
         assert_eq!(payload.data, *slice.data);
         assert_eq!(payload.leaf_hash, slice.leaf_hash);
         assert_eq!(payload.merkle_proof, slice.merkle_proof);
+        assert_eq!(payload.segments, slice.segments);
     }
 }
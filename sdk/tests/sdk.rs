@@ -21,7 +21,9 @@ use tape_core::track::types::{
 use tape_core::types::coin::TAPE;
 use tape_core::types::network::NetworkAddress;
 use tape_core::types::tls::NetworkTlsPubkey;
-use tape_core::types::{ContentType, EpochNumber, SlotNumber, StorageUnits, TrackNumber};
+use tape_core::types::{
+    ContentType, EpochNumber, ObjectHeaders, SlotNumber, StorageUnits, TrackNumber,
+};
 use tape_crypto::{hash, Hash};
 use tape_crypto::address::Address;
 use tape_crypto::ed25519::Keypair;
@@ -59,6 +61,7 @@ fn unexpected_peer_response(request: &PeerReq) -> PeerRes {
         PeerReq::SyncSlices(_) => PeerRes::SyncSlices(Err(unexpected_error())),
         PeerReq::SyncTracks(_) => PeerRes::SyncTracks(Err(unexpected_error())),
         PeerReq::Repair(_) => PeerRes::Repair(Err(unexpected_error())),
+        PeerReq::Challenge(_) => PeerRes::Challenge(Err(unexpected_error())),
         PeerReq::Certify(_) => PeerRes::Certify(Err(unexpected_error())),
         PeerReq::Vote(_) => PeerRes::Vote(Err(unexpected_error())),
         PeerReq::Invalidate(_) => PeerRes::Invalidate(Err(unexpected_error())),
@@ -66,6 +69,8 @@ fn unexpected_peer_response(request: &PeerReq) -> PeerRes {
         PeerReq::GetStats(_) => PeerRes::GetStats(Err(unexpected_error())),
         PeerReq::PutSlice(_) => PeerRes::PutSlice(Err(unexpected_error())),
         PeerReq::GetSlice(_) => PeerRes::GetSlice(Err(unexpected_error())),
+        PeerReq::PutSlices(_) => PeerRes::PutSlices(Err(unexpected_error())),
+        PeerReq::GetSlices(_) => PeerRes::GetSlices(Err(unexpected_error())),
     }
 }
 
@@ -236,6 +241,7 @@ fn setup() -> Fixture {
             PeerReq::SyncSlices(_) => unexpected_peer_response(&req),
             PeerReq::SyncTracks(_) => unexpected_peer_response(&req),
             PeerReq::Repair(_) => unexpected_peer_response(&req),
+            PeerReq::Challenge(_) => unexpected_peer_response(&req),
             PeerReq::Certify(_) => unexpected_peer_response(&req),
             PeerReq::Vote(_) => unexpected_peer_response(&req),
             PeerReq::Invalidate(_) => unexpected_peer_response(&req),
@@ -243,6 +249,8 @@ fn setup() -> Fixture {
             PeerReq::GetStats(_) => unexpected_peer_response(&req),
             PeerReq::PutSlice(_) => unexpected_peer_response(&req),
             PeerReq::GetSlice(_) => unexpected_peer_response(&req),
+            PeerReq::PutSlices(_) => unexpected_peer_response(&req),
+            PeerReq::GetSlices(_) => unexpected_peer_response(&req),
         }
     }));
 
@@ -357,6 +365,7 @@ fn object_item(name: &[u8], track_number: TrackNumber) -> ObjectListItem {
         track_number,
         kind: TrackKind::Inline as u64,
         content_type: ContentType::TextPlain,
+        headers: ObjectHeaders::from(ContentType::TextPlain),
     }
 }
 
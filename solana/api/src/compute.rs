@@ -12,6 +12,7 @@ pub const FINALIZE_SNAPSHOT_CU: u32 = 400_000;
 pub const VOTE_SNAPSHOT_CU:    u32    = 400_000;
 pub const VOTE_ASSIGNMENT_CU:  u32    = 400_000;
 pub const VOTE_EVICTION_CU:    u32    = 400_000;
+pub const SLASH_POOL_CU:       u32    = 400_000;
 
 // Iteration-heavy: AdvanceEpoch scans Committee(N+1) x PeerSet for
 // preference aggregation.
@@ -36,6 +37,7 @@ pub const PROPOSE_SNAPSHOT_CU:    u32 = 100_000;
 pub const PROPOSE_ASSIGNMENT_CU:  u32 = 100_000;
 pub const PROPOSE_EVICTION_CU:    u32 = 100_000;
 pub const RESIZE_ARCHIVE_CU:      u32 =  30_000;
+pub const RENEW_TAPE_CU:          u32 =  50_000;
 
 // Lightweight: small mutations + scheduled-state writes.
 pub const REQUEST_STAKE_UNLOCK_CU: u32 = 25_000;
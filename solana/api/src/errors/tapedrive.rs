@@ -27,6 +27,8 @@ pub enum TapeError {
     NotExpired = 0x23,
     #[error("not empty")]
     NotEmpty = 0x24,
+    #[error("not renewable")]
+    NotRenewable = 0x25,
 
     // Epoch
     #[error("bad epoch state")]
@@ -88,6 +90,8 @@ pub enum TapeError {
     ZeroShares = 0x66,
     #[error("pool accounting failed")]
     PoolAccountingFailed = 0x67,
+    #[error("already slashed")]
+    AlreadySlashed = 0x68,
 
     // Commitment
     #[error("bad proof")]
@@ -166,6 +170,7 @@ impl TapeError {
             Self::TapeExpired => "Tape has expired",
             Self::NotExpired => "Tape has not expired yet",
             Self::NotEmpty => "Tape is not empty",
+            Self::NotRenewable => "Tape is not in its renewal window",
             Self::BadEpochState => "Epoch is not in the expected phase",
             Self::TooSoon => "Please wait - epoch duration has not elapsed",
             Self::BadSchedule => "Invalid schedule",
@@ -193,6 +198,7 @@ impl TapeError {
             Self::EpochNotReached => "Target epoch not reached",
             Self::ZeroShares => "Cannot operate on zero shares",
             Self::PoolAccountingFailed => "Pool accounting failed during advance",
+            Self::AlreadySlashed => "Pool was already slashed this epoch",
             Self::BadProof => "Invalid proof",
             Self::ListFull => "Blacklist is full",
             Self::InvalidCommitment => "Leaf hashes do not match commitment root",
@@ -26,6 +26,13 @@ pub enum EventType {
     TapeReserved = 0x20,
     TapeDestroyed = 0x21,
     TapeExtended = 0x22,
+    TapeTransferred = 0x23,
+    TapeEscrowFunded = 0x24,
+    TapeEscrowWithdrawn = 0x25,
+    TapeEscrowLow = 0x26,
+    TapeVersioningSet = 0x27,
+    DeleteMarkerPlaced = 0x28,
+    DeleteMarkerRemoved = 0x29,
 
     // Node
     NodeRegistered = 0x30,
@@ -46,6 +53,7 @@ pub enum EventType {
     StakeDeposited = 0x50,
     StakeUnlockRequested = 0x51,
     StakeWithdrawn = 0x52,
+    StakeSlashed = 0x53,
 
     // Commission
     CommissionClaimed = 0x60,
@@ -218,6 +226,133 @@ pub struct TapeExtended {
 
 tape_solana::event!(EventType, TapeExtended);
 
+/// Emitted when a tape's new authority accepts a transfer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TapeTransferred {
+    /// Tape account address
+    pub tape: Address,
+
+    /// Owner before the transfer
+    pub previous_authority: Address,
+
+    /// Owner after the transfer
+    pub authority: Address,
+}
+
+tape_solana::event!(EventType, TapeTransferred);
+
+/// Emitted when TAPE is added to a tape's renewal escrow.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TapeEscrowFunded {
+    /// Tape account address
+    pub tape: Address,
+
+    /// Escrow account address
+    pub escrow: Address,
+
+    /// Token source owner who paid
+    pub payer: Address,
+
+    /// TAPE flux units added
+    pub amount: Coin<TAPE>,
+
+    /// Escrow balance after the deposit
+    pub balance: Coin<TAPE>,
+}
+
+tape_solana::event!(EventType, TapeEscrowFunded);
+
+/// Emitted when the escrow authority takes TAPE back out of a renewal escrow.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TapeEscrowWithdrawn {
+    /// Tape account address
+    pub tape: Address,
+
+    /// Escrow account address
+    pub escrow: Address,
+
+    /// Escrow authority
+    pub authority: Address,
+
+    /// TAPE flux units withdrawn
+    pub amount: Coin<TAPE>,
+
+    /// Escrow balance after the withdrawal
+    pub balance: Coin<TAPE>,
+}
+
+tape_solana::event!(EventType, TapeEscrowWithdrawn);
+
+/// Emitted by a renewal crank when the escrow cannot pay for the next renewal.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TapeEscrowLow {
+    /// Tape account address
+    pub tape: Address,
+
+    /// Escrow account address
+    pub escrow: Address,
+
+    /// Escrow balance
+    pub balance: Coin<TAPE>,
+
+    /// Cost of the next renewal at the current storage price
+    pub required: Coin<TAPE>,
+
+    /// Tape expiration epoch
+    pub expiry_epoch: EpochNumber,
+}
+
+tape_solana::event!(EventType, TapeEscrowLow);
+
+/// Emitted when a tape operator enables or suspends object versioning.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TapeVersioningSet {
+    /// Tape account address
+    pub tape: Address,
+
+    /// New versioning status, a `TapeVersioning` value
+    pub status: u64,
+}
+
+tape_solana::event!(EventType, TapeVersioningSet);
+
+/// Emitted when a tape operator deletes a name in a versioned tape.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct DeleteMarkerPlaced {
+    /// Tape account address
+    pub tape: Address,
+
+    /// Hash of the object name
+    pub key: Hash,
+}
+
+tape_solana::event!(EventType, DeleteMarkerPlaced);
+
+/// Emitted when a tape operator removes a delete marker version.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct DeleteMarkerRemoved {
+    /// Tape account address
+    pub tape: Address,
+
+    /// Hash of the object name
+    pub key: Hash,
+
+    /// Track the marker was stacked on
+    pub track_number: TrackNumber,
+
+    /// Marker sequence above that track
+    pub marker: u64,
+}
+
+tape_solana::event!(EventType, DeleteMarkerRemoved);
+
 /// Emitted when a storage node registers.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
@@ -475,6 +610,32 @@ pub struct StakeWithdrawn {
 
 tape_solana::event!(EventType, StakeWithdrawn);
 
+/// Emitted when a pool's stake is slashed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct StakeSlashed {
+    /// Pool (node account) address
+    pub pool: Address,
+
+    /// Epoch the slash landed in
+    pub epoch: EpochNumber,
+
+    /// Why the pool was slashed (see `SlashReason`)
+    pub reason: u64,
+
+    /// Hash of the signed challenge failure message (default for evictions)
+    pub evidence: Hash,
+
+    /// Stake cut from the pool, burned from the archive in full
+    pub amount: Coin<TAPE>,
+
+    /// Part of the cut the archive fronted beyond the pool's rewards, repaid
+    /// by stakers out of their principal at unstake
+    pub fronted: Coin<TAPE>,
+}
+
+tape_solana::event!(EventType, StakeSlashed);
+
 /// Emitted when a node operator claims commission.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
@@ -606,10 +767,15 @@ mod tests {
         assert_eq!(EventType::TrackCertified as u8, 0x13);
         assert_eq!(EventType::TapeReserved as u8, 0x20);
         assert_eq!(EventType::TapeExtended as u8, 0x22);
+        assert_eq!(EventType::TapeTransferred as u8, 0x23);
+        assert_eq!(EventType::TapeEscrowLow as u8, 0x26);
+        assert_eq!(EventType::TapeVersioningSet as u8, 0x27);
+        assert_eq!(EventType::DeleteMarkerRemoved as u8, 0x29);
         assert_eq!(EventType::NodeRegistered as u8, 0x30);
         assert_eq!(EventType::EpochCommitted as u8, 0x40);
         assert_eq!(EventType::EpochAdvanced as u8, 0x41);
         assert_eq!(EventType::StakeDeposited as u8, 0x50);
+        assert_eq!(EventType::StakeSlashed as u8, 0x53);
         assert_eq!(EventType::CommissionClaimed as u8, 0x60);
         assert_eq!(EventType::VoteProposed as u8, 0x70);
         assert_eq!(EventType::VoteRecorded as u8, 0x71);
@@ -624,11 +790,14 @@ mod tests {
         assert!(TrackDeleted::size_of() < 1024);
         assert!(TapeReserved::size_of() < 1024);
         assert!(TapeExtended::size_of() < 1024);
+        assert!(TapeEscrowLow::size_of() < 1024);
+        assert!(DeleteMarkerRemoved::size_of() < 1024);
         assert!(EpochCommitted::size_of() < 1024);
         assert!(EpochAdvanced::size_of() < 1024);
         assert!(SpoolSynced::size_of() < 1024);
         assert!(PoolAdvanced::size_of() < 1024);
         assert!(StakeDeposited::size_of() < 1024);
+        assert!(StakeSlashed::size_of() < 1024);
         assert!(VoteProposed::size_of() < 1024);
         assert!(VoteRecorded::size_of() < 1024);
         assert!(SnapshotFinalized::size_of() < 1024);
@@ -5,7 +5,7 @@ use tape_core::types::coin::{Coin, TAPE};
 
 use crate::program::token::ONE_TAPE;
 use crate::program::tapedrive::{
-    DEFAULT_BURN_FEE_BPS, DEFAULT_STORAGE_CAPACITY, DEFAULT_STORAGE_PRICE,
+    DEFAULT_BURN_FEE_BPS, DEFAULT_SLASH_BPS, DEFAULT_STORAGE_CAPACITY, DEFAULT_STORAGE_PRICE,
     DEFAULT_SUBSIDY_DECAY_BPS,
 };
 
@@ -21,6 +21,7 @@ pub struct GenesisConfig {
     pub storage_price: Coin<TAPE>,
     pub burn_fee_bps: BasisPoints,
     pub subsidy_decay_bps: BasisPoints,
+    pub slash_bps: BasisPoints,
     pub subsidy_amount: Coin<TAPE>,
 }
 
@@ -60,6 +61,7 @@ const MAINNET: GenesisConfig = GenesisConfig {
     storage_price: DEFAULT_STORAGE_PRICE,
     burn_fee_bps: DEFAULT_BURN_FEE_BPS,
     subsidy_decay_bps: DEFAULT_SUBSIDY_DECAY_BPS,
+    slash_bps: DEFAULT_SLASH_BPS,
     subsidy_amount: TAPE(0),
 };
 
@@ -106,6 +106,7 @@ pub fn build_advance_epoch_ix(
             AccountMeta::new(subsidy_ata.into(), false),
             AccountMeta::new_readonly(mint_address.into(), false),
             AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
         ],
         data: AdvanceEpoch {}.to_bytes(),
     }
@@ -77,6 +77,7 @@ pub enum TapeInstruction {
     ResizePeerSet,
     StageGenesisNode,
     StartNetwork,
+    UpgradeArchive,
 
     // Epoch
     SyncSpool = 0x50,
@@ -113,6 +114,8 @@ pub enum TapeInstruction {
     UnstakeFromPool,
     SplitPoolStake,
     MergePoolStake,
+    SlashPool,
+    UpgradeNode,
 
     // Tape
     ReserveTape = 0xA0,
@@ -121,6 +124,14 @@ pub enum TapeInstruction {
     ExtendTapeExpiry,
     SetTapeDelegate,
     RevokeTapeDelegate,
+    ProposeTapeTransfer,
+    AcceptTapeTransfer,
+    FundTapeEscrow,
+    WithdrawTapeEscrow,
+    RenewTape,
+    SetTapeVersioning,
+    PlaceDeleteMarker,
+    RemoveDeleteMarker,
 
     // Track
     TrackWrite = 0xB0,
@@ -165,6 +176,7 @@ tape_solana::instruction!(TapeInstruction, ResizeCommittee);
 tape_solana::instruction!(TapeInstruction, ResizePeerSet);
 tape_solana::instruction!(TapeInstruction, StageGenesisNode);
 tape_solana::instruction!(TapeInstruction, StartNetwork);
+tape_solana::instruction!(TapeInstruction, UpgradeArchive);
 
 tape_solana::instruction!(TapeInstruction, SyncSpool);
 tape_solana::instruction!(TapeInstruction, CommitEpoch);
@@ -176,6 +188,8 @@ tape_solana::instruction!(TapeInstruction, RequestStakeUnlock);
 tape_solana::instruction!(TapeInstruction, UnstakeFromPool);
 tape_solana::instruction!(TapeInstruction, SplitPoolStake);
 tape_solana::instruction!(TapeInstruction, MergePoolStake);
+tape_solana::instruction!(TapeInstruction, SlashPool);
+tape_solana::instruction!(TapeInstruction, UpgradeNode);
 
 tape_solana::instruction!(TapeInstruction, RegisterNode);
 tape_solana::instruction!(TapeInstruction, JoinCommittee);
@@ -204,6 +218,14 @@ tape_solana::instruction!(TapeInstruction, ExtendTapeCapacity);
 tape_solana::instruction!(TapeInstruction, ExtendTapeExpiry);
 tape_solana::instruction!(TapeInstruction, SetTapeDelegate);
 tape_solana::instruction!(TapeInstruction, RevokeTapeDelegate);
+tape_solana::instruction!(TapeInstruction, ProposeTapeTransfer);
+tape_solana::instruction!(TapeInstruction, AcceptTapeTransfer);
+tape_solana::instruction!(TapeInstruction, FundTapeEscrow);
+tape_solana::instruction!(TapeInstruction, WithdrawTapeEscrow);
+tape_solana::instruction!(TapeInstruction, RenewTape);
+tape_solana::instruction!(TapeInstruction, SetTapeVersioning);
+tape_solana::instruction!(TapeInstruction, PlaceDeleteMarker);
+tape_solana::instruction!(TapeInstruction, RemoveDeleteMarker);
 
 tape_solana::instruction!(TapeInstruction, TrackWrite);
 tape_solana::instruction!(TapeInstruction, DeleteTrack);
@@ -1,7 +1,9 @@
 use tape_solana::*;
 use tape_crypto::address::Address;
+use tape_core::bls::BlsSignature;
+use tape_core::spooler::GroupIndex;
 use tape_core::staking::PoolRate;
-use tape_core::types::EpochNumber;
+use tape_core::types::{EpochNumber, SpoolBitmap};
 use tape_core::types::coin::{Coin, TAPE};
 use crate::program::{staking, tapedrive};
 use crate::utils::ata;
@@ -39,6 +41,19 @@ pub struct SplitPoolStake {
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct MergePoolStake {}
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SlashPool {
+    pub node: Address,
+    pub group: GroupIndex,
+    pub bitmap: SpoolBitmap,
+    pub signature: BlsSignature,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct UpgradeNode {}
+
 pub fn build_advance_pool_ix(
     fee_payer: Address,
     pool: Address,
@@ -239,3 +254,58 @@ pub fn build_merge_pool_stake_ix(
         data: MergePoolStake {}.to_bytes(),
     }
 }
+
+pub fn build_slash_pool_ix(
+    fee_payer: Address,
+    current_epoch: EpochNumber,
+    node: Address,
+    group: GroupIndex,
+    bitmap: SpoolBitmap,
+    signature: BlsSignature,
+) -> Instruction {
+    let (system_address, _)  = system_pda();
+    let (epoch_address, _)   = epoch_pda(current_epoch);
+    let (group_address, _)   = group_pda(current_epoch, group);
+    let (archive_address, _) = archive_pda();
+    let (archive_ata, _)     = archive_ata();
+    let (mint_address, _)    = mint_pda();
+
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: vec![
+            AccountMeta::new(fee_payer.into(), true),
+
+            AccountMeta::new_readonly(system_address.into(), false),
+            AccountMeta::new_readonly(epoch_address.into(), false),
+            AccountMeta::new_readonly(group_address.into(), false),
+            AccountMeta::new(node.into(), false),
+
+            AccountMeta::new_readonly(archive_address.into(), false),
+            AccountMeta::new(archive_ata.into(), false),
+            AccountMeta::new(mint_address.into(), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: SlashPool {
+            node,
+            group,
+            bitmap,
+            signature,
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn build_upgrade_node_ix(
+    fee_payer: Address,
+    node: Address,
+) -> Instruction {
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: vec![
+            AccountMeta::new(fee_payer.into(), true),
+            AccountMeta::new(node.into(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: UpgradeNode {}.to_bytes(),
+    }
+}
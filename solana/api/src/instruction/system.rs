@@ -29,6 +29,7 @@ pub struct CreateArchive {
     pub storage_price: Coin<TAPE>,
     pub burn_fee_bps: BasisPoints,
     pub subsidy_decay_bps: BasisPoints,
+    pub slash_bps: BasisPoints,
 }
 
 #[repr(C)]
@@ -46,6 +47,10 @@ pub struct StartNetwork {
     pub subsidy_amount: Coin<TAPE>,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct UpgradeArchive {}
+
 
 pub fn build_create_system_ix(
     fee_payer: Address,
@@ -109,6 +114,7 @@ pub fn build_create_archive_ix(
             storage_price: config.storage_price,
             burn_fee_bps: config.burn_fee_bps,
             subsidy_decay_bps: config.subsidy_decay_bps,
+            slash_bps: config.slash_bps,
         }.to_bytes(),
     }
 }
@@ -186,3 +192,19 @@ pub fn build_start_network_ix(
         }.to_bytes(),
     }
 }
+
+pub fn build_upgrade_archive_ix(
+    fee_payer: Address,
+) -> Instruction {
+    let (archive_address, _) = archive_pda();
+
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: vec![
+            AccountMeta::new(fee_payer.into(), true),
+            AccountMeta::new(archive_address.into(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: UpgradeArchive {}.to_bytes(),
+    }
+}
@@ -1,6 +1,12 @@
+use core::mem::size_of;
+
 use tape_solana::*;
 use tape_crypto::address::Address;
 use tape_core::prelude::*;
+use tape_core::tape::TapeVersioning;
+use tape_core::types::coin::{Coin, TAPE};
+use crate::helpers::read_instruction_pod;
+use crate::instruction::MAX_NAME_LEN;
 use crate::utils::ata;
 use crate::program::tapedrive;
 use crate::program::tapedrive::*;
@@ -40,6 +46,67 @@ pub struct SetTapeDelegate {
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct RevokeTapeDelegate {}
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ProposeTapeTransfer {
+    pub new_authority: Address,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AcceptTapeTransfer {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct FundTapeEscrow {
+    pub amount: Coin<TAPE>,
+    /// Epochs per renewal, only read when the escrow is opened.
+    pub renewal_epochs: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct WithdrawTapeEscrow {
+    pub amount: Coin<TAPE>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct RenewTape {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetTapeVersioning {
+    /// A [`TapeVersioning`] value.
+    pub status: u64,
+}
+
+/// Followed by the object name.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct PlaceDeleteMarker {
+    pub name_len: [u8; 2],
+}
+
+/// Followed by the object name.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct RemoveDeleteMarker {
+    pub track_number: [u8; 8],
+    pub marker: [u8; 4],
+    pub name_len: [u8; 2],
+}
+
+impl RemoveDeleteMarker {
+    pub fn track_number(&self) -> TrackNumber {
+        TrackNumber(u64::from_le_bytes(self.track_number))
+    }
+
+    pub fn marker(&self) -> u32 {
+        u32::from_le_bytes(self.marker)
+    }
+}
+
 pub fn build_reserve_tape_ix(
     fee_payer: Address,
     authority: Address,
@@ -136,8 +203,8 @@ fn extend_tape_accounts(
 pub fn build_destroy_tape_ix(
     fee_payer: Address,
     authority: Address,
+    tape_address: Address,
 ) -> Instruction {
-    let (tape_address, _) = tape_pda(authority);
     let (system_address, _) = system_pda();
 
     Instruction {
@@ -187,3 +254,259 @@ pub fn build_revoke_tape_delegate_ix(
         data: RevokeTapeDelegate {}.to_bytes(),
     }
 }
+
+pub fn build_propose_tape_transfer_ix(
+    fee_payer: Address,
+    authority: Address,
+    tape: Address,
+    new_authority: Address,
+) -> Instruction {
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: vec![
+            AccountMeta::new(fee_payer.into(), true),
+            AccountMeta::new_readonly(authority.into(), true),
+            AccountMeta::new_readonly(tape.into(), false),
+            AccountMeta::new(tape_transfer_pda(tape).0.into(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: ProposeTapeTransfer { new_authority }.to_bytes(),
+    }
+}
+
+pub fn build_accept_tape_transfer_ix(
+    fee_payer: Address,
+    new_authority: Address,
+    tape: Address,
+) -> Instruction {
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: vec![
+            AccountMeta::new(fee_payer.into(), true),
+            AccountMeta::new_readonly(new_authority.into(), true),
+            AccountMeta::new(tape.into(), false),
+            AccountMeta::new(tape_transfer_pda(tape).0.into(), false),
+        ],
+        data: AcceptTapeTransfer {}.to_bytes(),
+    }
+}
+
+pub fn build_fund_tape_escrow_ix(
+    fee_payer: Address,
+    payer: Address,
+    tape: Address,
+    amount: Coin<TAPE>,
+    renewal_epochs: u64,
+) -> Instruction {
+    let payer_ata = ata(&payer);
+    let (escrow_address, _) = escrow_pda(tape);
+    let (archive_ata, _) = archive_ata();
+
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: vec![
+            AccountMeta::new(fee_payer.into(), true),
+            AccountMeta::new_readonly(payer.into(), true),
+            AccountMeta::new(payer_ata.into(), false),
+
+            AccountMeta::new_readonly(tape.into(), false),
+            AccountMeta::new(escrow_address.into(), false),
+            AccountMeta::new(archive_ata.into(), false),
+
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: FundTapeEscrow { amount, renewal_epochs }.to_bytes(),
+    }
+}
+
+pub fn build_withdraw_tape_escrow_ix(
+    fee_payer: Address,
+    authority: Address,
+    tape: Address,
+    amount: Coin<TAPE>,
+) -> Instruction {
+    let authority_ata = ata(&authority);
+    let (escrow_address, _) = escrow_pda(tape);
+    let (archive_address, _) = archive_pda();
+    let (archive_ata, _) = archive_ata();
+
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: vec![
+            AccountMeta::new(fee_payer.into(), true),
+            AccountMeta::new_readonly(authority.into(), true),
+            AccountMeta::new(authority_ata.into(), false),
+
+            AccountMeta::new(escrow_address.into(), false),
+            AccountMeta::new_readonly(archive_address.into(), false),
+            AccountMeta::new(archive_ata.into(), false),
+
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: WithdrawTapeEscrow { amount }.to_bytes(),
+    }
+}
+
+pub fn build_renew_tape_ix(fee_payer: Address, tape: Address) -> Instruction {
+    let (escrow_address, _) = escrow_pda(tape);
+    let (system_address, _) = system_pda();
+    let (archive_address, _) = archive_pda();
+    let (archive_ata, _) = archive_ata();
+    let (mint_address, _) = mint_pda();
+
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: vec![
+            AccountMeta::new(fee_payer.into(), true),
+
+            AccountMeta::new(tape.into(), false),
+            AccountMeta::new(escrow_address.into(), false),
+            AccountMeta::new_readonly(system_address.into(), false),
+            AccountMeta::new(archive_address.into(), false),
+            AccountMeta::new(archive_ata.into(), false),
+            AccountMeta::new(mint_address.into(), false),
+
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: RenewTape {}.to_bytes(),
+    }
+}
+
+pub fn build_set_tape_versioning_ix(
+    fee_payer: Address,
+    signer: Address,
+    tape: Address,
+    status: TapeVersioning,
+) -> Instruction {
+    Instruction {
+        program_id: tapedrive::ID,
+        accounts: tape_operator_accounts(fee_payer, signer, tape),
+        data: SetTapeVersioning { status: status.into() }.to_bytes(),
+    }
+}
+
+pub fn build_place_delete_marker_ix(
+    fee_payer: Address,
+    signer: Address,
+    tape: Address,
+    name: &[u8],
+) -> Result<Instruction, ProgramError> {
+    let mut data = PlaceDeleteMarker {
+        name_len: name_len(name)?,
+    }
+    .to_bytes();
+    data.extend_from_slice(name);
+
+    Ok(Instruction {
+        program_id: tapedrive::ID,
+        accounts: tape_operator_accounts(fee_payer, signer, tape),
+        data,
+    })
+}
+
+pub fn build_remove_delete_marker_ix(
+    fee_payer: Address,
+    signer: Address,
+    tape: Address,
+    name: &[u8],
+    track_number: TrackNumber,
+    marker: u32,
+) -> Result<Instruction, ProgramError> {
+    let mut data = RemoveDeleteMarker {
+        track_number: track_number.0.to_le_bytes(),
+        marker: marker.to_le_bytes(),
+        name_len: name_len(name)?,
+    }
+    .to_bytes();
+    data.extend_from_slice(name);
+
+    Ok(Instruction {
+        program_id: tapedrive::ID,
+        accounts: tape_operator_accounts(fee_payer, signer, tape),
+        data,
+    })
+}
+
+fn tape_operator_accounts(
+    fee_payer: Address,
+    signer: Address,
+    tape: Address,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(fee_payer.into(), true),
+        AccountMeta::new_readonly(signer.into(), true),
+        AccountMeta::new_readonly(tape.into(), false),
+    ]
+}
+
+pub fn parse_place_delete_marker(data: &[u8]) -> Result<(PlaceDeleteMarker, &[u8]), ProgramError> {
+    let (args, name) = split_named::<PlaceDeleteMarker>(data)?;
+    check_marker_name(name, args.name_len)?;
+
+    Ok((args, name))
+}
+
+pub fn parse_remove_delete_marker(
+    data: &[u8],
+) -> Result<(RemoveDeleteMarker, &[u8]), ProgramError> {
+    let (args, name) = split_named::<RemoveDeleteMarker>(data)?;
+    check_marker_name(name, args.name_len)?;
+
+    Ok((args, name))
+}
+
+fn split_named<T: Pod + Zeroable>(data: &[u8]) -> Result<(T, &[u8]), ProgramError> {
+    let (header, name) = data
+        .split_at_checked(size_of::<T>())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    Ok((read_instruction_pod::<T>(header)?, name))
+}
+
+fn check_marker_name(name: &[u8], name_len: [u8; 2]) -> Result<(), ProgramError> {
+    if name.len() != u16::from_le_bytes(name_len) as usize
+        || name.is_empty()
+        || name.len() > MAX_NAME_LEN
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+fn name_len(name: &[u8]) -> Result<[u8; 2], ProgramError> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok((name.len() as u16).to_le_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delete_marker_roundtrip() {
+        let tape = Address::new_unique();
+        let ix = build_place_delete_marker_ix(tape, tape, tape, b"photos/a.jpg").unwrap();
+        let (_, name) = parse_place_delete_marker(&ix.data[1..]).unwrap();
+        assert_eq!(name, b"photos/a.jpg");
+
+        let ix = build_remove_delete_marker_ix(tape, tape, tape, b"k", TrackNumber(7), 2).unwrap();
+        let (args, name) = parse_remove_delete_marker(&ix.data[1..]).unwrap();
+        assert_eq!((args.track_number(), args.marker(), name), (TrackNumber(7), 2, &b"k"[..]));
+    }
+
+    #[test]
+    fn delete_marker_rejects_bad_names() {
+        let tape = Address::new_unique();
+        assert!(build_place_delete_marker_ix(tape, tape, tape, b"").is_err());
+        assert!(build_place_delete_marker_ix(tape, tape, tape, &[b'a'; MAX_NAME_LEN + 1]).is_err());
+
+        let ix = build_place_delete_marker_ix(tape, tape, tape, b"abc").unwrap();
+        assert!(parse_place_delete_marker(&ix.data[1..ix.data.len() - 1]).is_err());
+        assert!(parse_place_delete_marker(&[ix.data[1..].to_vec(), vec![0]].concat()).is_err());
+    }
+}
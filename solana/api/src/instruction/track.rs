@@ -6,7 +6,10 @@ use tape_core::track::data::{
     BlobData, BlobDataSlice, BlobInfo, BlobInfoSlice, TrackObjectInfoSlice,
 };
 use tape_core::track::types::{CompressedTrackProof, TrackKind};
-use tape_core::types::{ContentType, EpochNumber, SpoolBitmap, StorageUnits, StripeCount};
+use tape_core::types::{
+    ContentType, EpochNumber, ObjectHeaders, SpoolBitmap, StorageUnits, StripeCount,
+    MAX_OBJECT_HEADERS_LEN,
+};
 use tape_crypto::address::Address;
 use tape_crypto::Hash;
 use tape_solana::*;
@@ -201,18 +204,20 @@ struct TrackWriteObject {
     pub logical_size: [u8; 8],
 }
 
+/// Instruction data length for a track write. `object_len` covers the object
+/// name plus its encoded headers.
 pub fn track_write_ix_len(
     payload_len: usize,
-    object_name_len: Option<usize>,
+    object_len: Option<usize>,
 ) -> Option<usize> {
     let mut len = 1usize
         .checked_add(size_of::<TrackWrite>())?
         .checked_add(payload_len)?;
 
-    if let Some(object_name_len) = object_name_len {
+    if let Some(object_len) = object_len {
         len = len
             .checked_add(size_of::<TrackWriteObject>())?
-            .checked_add(object_name_len)?;
+            .checked_add(object_len)?;
     }
 
     Some(len)
@@ -227,11 +232,17 @@ fn parse_object(trailer: &[u8]) -> Result<Option<TrackObjectInfoSlice<'_>>, Prog
         return Err(ProgramError::InvalidInstructionData);
     }
 
-    let (object_header, object_name) = trailer.split_at(size_of::<TrackWriteObject>());
+    let (object_header, rest) = trailer.split_at(size_of::<TrackWriteObject>());
     let object_header = read_instruction_pod::<TrackWriteObject>(object_header)?;
     let object_name_len = u16::from_le_bytes(object_header.name_len) as usize;
 
+    // Encoded object headers follow the name; writes without them end there.
+    let (object_name, headers) = rest
+        .split_at_checked(object_name_len)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
     check_name(object_name, object_name_len)?;
+    check_headers(headers)?;
 
     let content_type = ContentType::try_from(u16::from_le_bytes(object_header.content_type))
         .map_err(|_| ProgramError::InvalidInstructionData)?;
@@ -241,6 +252,7 @@ fn parse_object(trailer: &[u8]) -> Result<Option<TrackObjectInfoSlice<'_>>, Prog
         name: object_name,
         content_type,
         logical_size,
+        headers,
     }))
 }
 
@@ -252,6 +264,14 @@ fn check_name(name: &[u8], expected_len: usize) -> Result<(), ProgramError> {
     Ok(())
 }
 
+fn check_headers(headers: &[u8]) -> Result<(), ProgramError> {
+    if headers.len() > MAX_OBJECT_HEADERS_LEN || ObjectHeaders::from_bytes(headers).is_none() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
 #[inline(always)]
 fn make_track_write(blob: BlobInfo) -> Result<Vec<u8>, ProgramError> {
     if blob
@@ -262,6 +282,10 @@ fn make_track_write(blob: BlobInfo) -> Result<Vec<u8>, ProgramError> {
         return Err(ProgramError::InvalidInstructionData);
     }
 
+    if let Some(object) = blob.object.as_ref() {
+        check_headers(&object.headers)?;
+    }
+
     let BlobInfo { object, data } = blob;
     let (kind, payload) = match data {
         BlobData::Inline(bytes) => {
@@ -306,6 +330,7 @@ fn make_track_write(blob: BlobInfo) -> Result<Vec<u8>, ProgramError> {
         }));
 
         out.extend_from_slice(&object.name);
+        out.extend_from_slice(&object.headers);
     }
     Ok(out)
 }
@@ -346,6 +371,7 @@ mod tests {
                     name: name.clone(),
                     content_type: ContentType::ImageJpeg,
                     logical_size,
+                    headers: Vec::new(),
                 }),
                 data: BlobData::Coded(blob),
             },
@@ -412,4 +438,59 @@ mod tests {
         let err = parse_track_write(&ix.data[1..]).expect_err("partial object trailer");
         assert_eq!(err, ProgramError::InvalidInstructionData);
     }
+
+    #[test]
+    fn track_write_roundtrips_object_headers() {
+        let name = b"lake/part-0.parquet".to_vec();
+        let mut headers = ObjectHeaders::with_content_type("application/x-parquet");
+        headers.insert_metadata("pipeline", "ingest-7");
+        let (content_type, encoded) = headers.to_wire().expect("headers fit");
+
+        let ix = build_track_write_ix(
+            Address::new_unique(),
+            Address::new_unique(),
+            Address::new_unique(),
+            BlobInfo {
+                object: Some(TrackObjectInfo {
+                    name: name.clone(),
+                    content_type,
+                    logical_size: StorageUnits::from_bytes(3),
+                    headers: encoded.clone(),
+                }),
+                data: BlobData::Inline(b"row".to_vec()),
+            },
+        )
+        .expect("track write instruction");
+        assert_eq!(
+            track_write_ix_len(3, Some(name.len() + encoded.len())),
+            Some(ix.data.len())
+        );
+
+        let (_header, parsed) = parse_track_write(&ix.data[1..]).expect("parse track write");
+        let object = parsed.object.expect("object metadata");
+        assert_eq!(object.name, name.as_slice());
+        assert_eq!(object.decode_headers(), headers);
+    }
+
+    #[test]
+    fn track_write_rejects_malformed_headers() {
+        let object = TrackObjectInfo {
+            name: b"obj".to_vec(),
+            content_type: ContentType::Unknown,
+            logical_size: StorageUnits::from_bytes(3),
+            headers: vec![0xff, 0x00],
+        };
+
+        let err = build_track_write_ix(
+            Address::new_unique(),
+            Address::new_unique(),
+            Address::new_unique(),
+            BlobInfo {
+                object: Some(object),
+                data: BlobData::Inline(b"row".to_vec()),
+            },
+        )
+        .expect_err("malformed headers");
+        assert_eq!(err, ProgramError::InvalidInstructionData);
+    }
 }
@@ -11,9 +11,10 @@ use tape_crypto::Hash;
 
 use crate::program::tapedrive;
 use crate::program::tapedrive::{
-    assignment_vote_pda, committee_pda, epoch_pda, eviction_vote_pda, group_pda, peer_set_pda,
-    snapshot_tape_pda, snapshot_vote_pda, system_pda,
+    archive_ata, archive_pda, assignment_vote_pda, committee_pda, epoch_pda, eviction_vote_pda,
+    group_pda, peer_set_pda, snapshot_tape_pda, snapshot_vote_pda, system_pda,
 };
+use crate::program::token::mint_pda;
 use crate::state::Tape;
 
 #[repr(C)]
@@ -298,6 +299,9 @@ pub fn build_vote_eviction_ix(
     let (curr_group_address, _) = group_pda(current_epoch, group);
     let (vote_address, _) = eviction_vote_pda(current_epoch, target_epoch, node);
     let (committee_address, _) = committee_pda(target_epoch);
+    let (archive_address, _) = archive_pda();
+    let (archive_ata, _) = archive_ata();
+    let (mint_address, _) = mint_pda();
 
     Instruction {
         program_id: tapedrive::ID,
@@ -310,6 +314,10 @@ pub fn build_vote_eviction_ix(
             AccountMeta::new(vote_address.into(), false),
             AccountMeta::new(node.into(), false),
             AccountMeta::new(committee_address.into(), false),
+            AccountMeta::new_readonly(archive_address.into(), false),
+            AccountMeta::new(archive_ata.into(), false),
+            AccountMeta::new(mint_address.into(), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
         ],
         data: VoteEviction {
             node,
@@ -17,6 +17,8 @@ pub const DEFAULT_STORAGE_PRICE:               TAPE = TAPE(954); // ~1 TAPE / Ti
 pub const DEFAULT_BURN_FEE_BPS:         BasisPoints = BasisPoints(1_000);
 pub const DEFAULT_SUBSIDY_DECAY_BPS:    BasisPoints = BasisPoints(100);
 pub const MAX_SUBSIDY_DECAY_BPS:        BasisPoints = BasisPoints(500);
+pub const DEFAULT_SLASH_BPS:           BasisPoints = BasisPoints(500);
+pub const MAX_SLASH_BPS:               BasisPoints = BasisPoints(5_000);
 
 pub const EPOCH_VALUES:           usize = 4;    // Epoch N, N+1, N+2, N+3
 pub const FUTURE_EPOCHS:          usize = 256;  // ~5 years at 1 week epochs
@@ -49,6 +51,8 @@ pub const VOTE_SNAPSHOT:      &[u8] = b"snapshot";
 pub const VOTE_ASSIGNMENT:    &[u8] = b"assignment";
 pub const VOTE_EVICTION:      &[u8] = b"eviction";
 pub const SNAPSHOT_TAPE:      &[u8] = b"snapshot_tape";
+pub const ESCROW:             &[u8] = b"escrow";
+pub const TAPE_TRANSFER:      &[u8] = b"tape_transfer";
 pub const EVENT:              &[u8] = b"event";
 
 pub const SYSTEM_ADDRESS: Address =
@@ -262,6 +266,16 @@ pub fn tape_pda(authority: Address) -> (Address, u8) {
     Address::find_program_address(&[CASSETTE, authority.as_ref()], id())
 }
 
+#[inline(always)]
+pub fn escrow_pda(tape: Address) -> (Address, u8) {
+    Address::find_program_address(&[ESCROW, tape.as_ref()], id())
+}
+
+#[inline(always)]
+pub fn tape_transfer_pda(tape: Address) -> (Address, u8) {
+    Address::find_program_address(&[TAPE_TRANSFER, tape.as_ref()], id())
+}
+
 #[inline(always)]
 pub fn track_pda(tape: Address, track_number: TrackNumber) -> (Address, u8) {
     Address::find_program_address(&[TRACK, tape.as_ref(), &track_number.pack()], id())
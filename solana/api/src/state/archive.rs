@@ -34,6 +34,31 @@ pub struct Archive {
 
     /// The rewards paid out so far in this epoch.
     pub rewards_paid: Coin<TAPE>,
+
+    /// The share of a pool's stake cut by a slash, in basis points. Set at
+    /// genesis, or by `UpgradeArchive` for an archive created before slashing.
+    pub slash_bps: BasisPoints,
+}
+
+impl Archive {
+    /// Account size of an archive created before slashing, which ends at
+    /// `rewards_paid`. `UpgradeArchive` grows such an account to
+    /// [`Archive::get_size`].
+    pub const PRE_SLASH_SIZE: usize = Self::get_size() - core::mem::size_of::<BasisPoints>();
+
+    /// Decode an archive account in either layout. An archive that predates
+    /// slashing reads with slashing off, as it behaves until upgraded.
+    pub fn read_account(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() == Self::PRE_SLASH_SIZE {
+            let mut upgraded = data.to_vec();
+            upgraded.resize(Self::get_size(), 0);
+            return Self::unpack_with_discriminator(&upgraded).map(|archive| *archive);
+        }
+        if data.len() < Self::get_size() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::unpack_with_discriminator(data).map(|archive| *archive)
+    }
 }
 
 tape_solana::state!(AccountType, Archive);
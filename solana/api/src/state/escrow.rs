@@ -0,0 +1,29 @@
+use tape_crypto::address::Address;
+use tape_solana::*;
+use tape_core::types::EpochNumber;
+use tape_core::types::coin::{Coin, TAPE};
+
+use super::AccountType;
+
+/// Prepaid renewals for a tape. The balance is held in the archive token
+/// account and spent by `RenewTape` as the tape nears expiry.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Escrow {
+    /// The tape this escrow renews.
+    pub tape: Address,
+
+    /// The authority that opened the escrow and may withdraw from it.
+    pub authority: Address,
+
+    /// TAPE flux units left for renewals.
+    pub balance: Coin<TAPE>,
+
+    /// Epochs added to the tape's expiry by each renewal.
+    pub renewal_epochs: u64,
+
+    /// The epoch of the last renewal, zero if never renewed.
+    pub last_renewal: EpochNumber,
+}
+
+tape_solana::state!(AccountType, Escrow);
@@ -1,5 +1,6 @@
 mod archive;
 mod committee;
+mod escrow;
 mod epoch;
 mod exchange;
 mod group;
@@ -8,11 +9,13 @@ mod peer;
 mod stake;
 mod system;
 mod tape;
+mod transfer;
 mod treasury;
 mod vote;
 
 pub use archive::*;
 pub use committee::*;
+pub use escrow::*;
 pub use epoch::*;
 pub use exchange::*;
 pub use group::*;
@@ -21,6 +24,7 @@ pub use peer::*;
 pub use stake::*;
 pub use system::*;
 pub use tape::*;
+pub use transfer::*;
 pub use treasury::*;
 pub use vote::*;
 
@@ -42,4 +46,6 @@ pub enum AccountType {
     Tape,
     Treasury,
     Vote,
+    Escrow,
+    TapeTransfer,
 }
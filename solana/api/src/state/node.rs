@@ -1,6 +1,6 @@
 use tape_crypto::address::Address;
 use tape_solana::*;
-use tape_core::staking::{RateSpan, StakingPool};
+use tape_core::staking::{RateSpan, SlashState, StakingPool};
 use tape_core::system::{NodeMetadata, NodePreferences};
 use tape_core::types::EpochNumber;
 use tape_core::types::NodeId;
@@ -40,9 +40,31 @@ pub struct Node {
 
     /// Epoch through which this node is barred from joining a committee.
     pub suspended_until: EpochNumber,
+
+    /// Slashing bookkeeping for this node's pool.
+    pub slashing: SlashState,
 }
 
 impl Node {
+    /// Account size of a node registered before slashing, which ends at
+    /// `suspended_until`. `UpgradeNode` grows such an account to
+    /// [`Node::get_size`].
+    pub const PRE_SLASH_SIZE: usize = Self::get_size() - core::mem::size_of::<SlashState>();
+
+    /// Decode a node account in either layout. A node that predates slashing
+    /// has never been slashed, so its slashing state reads as unset.
+    pub fn read_account(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() == Self::PRE_SLASH_SIZE {
+            let mut upgraded = data.to_vec();
+            upgraded.resize(Self::get_size(), 0);
+            return Self::unpack_with_discriminator(&upgraded).map(|node| *node);
+        }
+        if data.len() < Self::get_size() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::unpack_with_discriminator(data).map(|node| *node)
+    }
+
     pub fn rate_span(&self, address: Address, current_epoch: EpochNumber) -> RateSpan {
         RateSpan {
             node: address,
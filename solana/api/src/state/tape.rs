@@ -109,6 +109,11 @@ impl Tape {
         TapeFlags::is_system(self.flags)
     }
 
+    #[inline(always)]
+    pub fn is_transferred(&self) -> bool {
+        TapeFlags::is_transferred(self.flags)
+    }
+
     #[inline(always)]
     pub fn is_operator(&self, signer: Address) -> bool {
         signer == self.authority
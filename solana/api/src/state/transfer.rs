@@ -0,0 +1,21 @@
+use tape_crypto::address::Address;
+use tape_solana::*;
+
+use super::AccountType;
+
+/// An open offer to hand a tape to a new authority. Kept beside the tape so
+/// the tape account keeps its size; `AcceptTapeTransfer` closes it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct TapeTransfer {
+    /// The tape on offer.
+    pub tape: Address,
+
+    /// The authority that made the offer.
+    pub authority: Address,
+
+    /// The authority that may accept it.
+    pub new_authority: Address,
+}
+
+tape_solana::state!(AccountType, TapeTransfer);
@@ -46,6 +46,7 @@ pub fn process_create_archive(accounts: &[AccountInfo<'_>], data: &[u8]) -> Prog
     let storage_price = args.storage_price;
     let burn_fee_bps = args.burn_fee_bps;
     let subsidy_decay_bps = args.subsidy_decay_bps;
+    let slash_bps = args.slash_bps;
 
     if storage_capacity.0 < MIN_STORAGE_CAPACITY as u64 {
         return Err(ProgramError::InvalidArgument);
@@ -59,6 +60,9 @@ pub fn process_create_archive(accounts: &[AccountInfo<'_>], data: &[u8]) -> Prog
     if subsidy_decay_bps > MAX_SUBSIDY_DECAY_BPS {
         return Err(ProgramError::InvalidArgument);
     }
+    if slash_bps > MAX_SLASH_BPS {
+        return Err(ProgramError::InvalidArgument);
+    }
 
     let (archive_address, _) = archive_pda();
     let (archive_ata_address, _) = archive_ata();
@@ -121,6 +125,7 @@ pub fn process_create_archive(accounts: &[AccountInfo<'_>], data: &[u8]) -> Prog
     archive.storage_price = storage_price;
     archive.burn_fee_bps = burn_fee_bps;
     archive.subsidy_decay_bps = subsidy_decay_bps;
+    archive.slash_bps = slash_bps;
     archive.schedule = EpochSchedule::new_at(EpochNumber(0));
 
     Ok(())
@@ -186,6 +191,7 @@ mod tests {
                         storage_price: config.storage_price,
                         burn_fee_bps: config.burn_fee_bps,
                         subsidy_decay_bps: config.subsidy_decay_bps,
+                        slash_bps: config.slash_bps,
                         schedule: EpochSchedule::new_at(EpochNumber(0)),
                         ..Archive::zeroed()
                     }.pack().as_ref()
@@ -1,3 +1,5 @@
 pub mod create;
+pub mod upgrade;
 
 pub use create::*;
+pub use upgrade::*;
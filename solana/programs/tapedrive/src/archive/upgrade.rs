@@ -0,0 +1,116 @@
+use tape_solana::*;
+use tape_api::program::prelude::*;
+
+/// Grow an archive created before slashing to the current layout.
+///
+/// Permissionless: the only new field is the slash rate, which there is no
+/// other way to set, so it takes the default a new archive starts with. An
+/// archive in the current layout is refused.
+pub fn process_upgrade_archive(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let _args = UpgradeArchive::try_from_bytes(data)?;
+    let [
+        fee_payer_info,
+        archive_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    system_program_info
+        .is_program(&system_program::ID)?;
+
+    archive_info
+        .is_writable()?
+        .is_archive()?;
+
+    if archive_info.data_len() != Archive::PRE_SLASH_SIZE {
+        return Err(TapeError::UnexpectedState.into());
+    }
+
+    grow_legacy_archive(archive_info, system_program_info, fee_payer_info)
+}
+
+/// Resize a pre-slashing archive in place and turn slashing on. Also run by
+/// `AdvanceEpoch`, so the archive is upgraded by the first epoch after deploy.
+pub fn grow_legacy_archive<'info>(
+    archive_info: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    fee_payer_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    resize_account(archive_info, system_program_info, fee_payer_info, Archive::get_size())?;
+    archive_info.try_borrow_mut_data()?[Archive::PRE_SLASH_SIZE..].fill(0);
+
+    let archive = archive_info.as_account_mut::<Archive>(&tapedrive::ID)?;
+    archive.slash_bps = DEFAULT_SLASH_BPS;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tape_test::*;
+
+    fn archive() -> Archive {
+        Archive {
+            storage_capacity: StorageUnits::mb(1000),
+            storage_price: TAPE(954),
+            burn_fee_bps: BasisPoints(1_000),
+            tape_count: 7,
+            ..Archive::zeroed()
+        }
+    }
+
+    #[test]
+    fn upgrade_archive() {
+        let fee_payer = Pubkey::new_unique();
+        let (archive_address, _) = archive_pda();
+        let mut legacy = archive().pack();
+        legacy.truncate(Archive::PRE_SLASH_SIZE);
+
+        let instruction = build_upgrade_archive_ix(fee_payer.into());
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &[
+                sol(fee_payer, 1_000_000_000),
+                pda(archive_address, legacy.clone(), tapedrive::ID),
+                system_program(),
+            ],
+            &[
+                Check::success(),
+                Check::account(&Pubkey::from(archive_address))
+                    .data(Archive { slash_bps: DEFAULT_SLASH_BPS, ..archive() }.pack().as_ref())
+                    .build(),
+            ],
+        );
+
+        // Clients decode the legacy layout with slashing off.
+        assert_eq!(Archive::read_account(&legacy).expect("legacy archive"), archive());
+    }
+
+    // an archive already in the current layout keeps its slash rate
+    #[test]
+    fn upgrade_rejects_current_layout() {
+        let fee_payer = Pubkey::new_unique();
+        let (archive_address, _) = archive_pda();
+
+        let instruction = build_upgrade_archive_ix(fee_payer.into());
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &[
+                sol(fee_payer, 1_000_000_000),
+                pda(archive_address, archive().pack(), tapedrive::ID),
+                system_program(),
+            ],
+            &[Check::err(TapeError::UnexpectedState.into())],
+        );
+    }
+}
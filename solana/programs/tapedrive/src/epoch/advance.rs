@@ -2,6 +2,8 @@ use tape_solana::*;
 use tape_api::program::prelude::*;
 use tape_api::event::EpochAdvanced;
 
+use crate::archive::grow_legacy_archive;
+
 pub fn process_advance_epoch(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     let _args = AdvanceEpoch::try_from_bytes(data)?;
     let [
@@ -23,6 +25,7 @@ pub fn process_advance_epoch(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progr
         subsidy_ata_info,
         mint_info,
         token_program_info,
+        system_program_info,
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -44,6 +47,15 @@ pub fn process_advance_epoch(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progr
         .is_writable()?
         .is_archive()?;
 
+    system_program_info
+        .is_program(&system_program::ID)?;
+
+    // An archive created before slashing is grown here, so the first epoch
+    // after deploy upgrades it without a separate instruction.
+    if archive_info.data_len() == Archive::PRE_SLASH_SIZE {
+        grow_legacy_archive(archive_info, system_program_info, fee_payer_info)?;
+    }
+
     let archive = archive_info.as_account_mut::<Archive>(&tapedrive::ID)?;
 
     archive_ata_info
@@ -395,6 +407,7 @@ mod tests {
             token(subsidy_ata_address, Pubkey::from(subsidy_address), 0),
             mint(0),
             token_program(),
+            system_program(),
         ];
 
         let expected_system = System {
@@ -571,6 +584,7 @@ mod tests {
             token(subsidy_ata_address, Pubkey::from(subsidy_address), 0),
             mint(0),
             token_program(),
+            system_program(),
         ];
 
         let expected_system = System {
@@ -593,4 +607,145 @@ mod tests {
             ],
         );
     }
+
+    // an archive created before slashing is grown on the first advance
+    #[test]
+    fn upgrades_legacy_archive() {
+        let fee_payer = Pubkey::new_unique();
+
+        let curr = EpochNumber(10);
+        let next = EpochNumber(11);
+        let target = EpochNumber(12);
+
+        let (system_address, _) = system_pda();
+        let (archive_address, _) = archive_pda();
+        let (archive_ata_address, _) = archive_ata();
+        let (curr_epoch_address, _) = epoch_pda(curr);
+        let (next_epoch_address, _) = epoch_pda(next);
+        let (next_committee_address, _) = committee_pda(next);
+        let (target_epoch_address, _) = epoch_pda(target);
+        let (target_committee_address, _) = committee_pda(target);
+        let (peer_set_address, _) = peer_set_pda();
+        let (subsidy_address, _) = subsidy_pda();
+        let (subsidy_ata_address, _) = subsidy_ata();
+
+        let prefs = pref(2_048, 950, COMMITTEE_SIZE, 50);
+        let next_members: Vec<Member> = (0..20)
+            .map(|i| {
+                let mut bytes = [0u8; 32];
+                bytes[0] = (i as u8) + 1;
+                Member {
+                    node: Address::new(bytes),
+                    stake: TAPE(1_000),
+                    assigned: StorageUnits::zero(),
+                    blacklisted: StorageUnits::zero(),
+                    spools: 50,
+                }
+            })
+            .collect();
+        let next_peers: Vec<Peer> = next_members
+            .iter()
+            .map(|m| Peer { node: m.node, preferences: prefs, ..Peer::zeroed() })
+            .collect();
+
+        let system = System {
+            current_epoch: curr,
+            committee_size: COMMITTEE_SIZE,
+            target_group_count: 50,
+            live_group_count: 50,
+            min_epoch_duration: TEST_MIN_EPOCH_DURATION,
+            max_epoch_duration: TEST_MAX_EPOCH_DURATION,
+            ..System::zeroed()
+        };
+
+        let archive = Archive {
+            schedule: EpochSchedule::new_at(curr),
+            ..Archive::zeroed()
+        };
+        let mut legacy_archive = archive.pack();
+        legacy_archive.truncate(Archive::PRE_SLASH_SIZE);
+
+        let curr_epoch = Epoch {
+            id: curr,
+            state: EpochState {
+                phase: EpochPhase::Closing as u64,
+                ..EpochState::zeroed()
+            },
+            ..Epoch::zeroed()
+        };
+        let next_epoch_data = Epoch {
+            id: next,
+            total_groups: 50,
+            assignment_hash: Hash::from([0x88; 32]),
+            preferences: prefs,
+            state: EpochState::zeroed(),
+            ..Epoch::zeroed()
+        };
+        let target_epoch_data = Epoch {
+            id: target,
+            state: EpochState::zeroed(),
+            ..Epoch::zeroed()
+        };
+
+        let next_committee_data = Committee {
+            epoch: next,
+            members: Tail::new(COMMITTEE_SIZE, next_members.len() as u64),
+        }
+        .pack_with(&next_members);
+        let target_committee_data = Committee {
+            epoch: target,
+            members: Tail::empty(prefs.committee_size),
+        }
+        .pack_with(&[]);
+        let peer_set_data = PeerSet {
+            peers: Tail::new(COMMITTEE_SIZE.saturating_mul(3), next_peers.len() as u64),
+        }
+            .pack_with(&next_peers);
+
+        let instruction = build_advance_epoch_ix(fee_payer.into(), curr);
+
+        let accounts = vec![
+            sol(fee_payer, 1_000_000_000),
+            pda(system_address, system.pack(), tapedrive::ID),
+            pda(archive_address, legacy_archive, tapedrive::ID),
+            token(archive_ata_address, Pubkey::from(archive_address), 0),
+            pda(curr_epoch_address, curr_epoch.pack(), tapedrive::ID),
+            pda(next_epoch_address, next_epoch_data.pack(), tapedrive::ID),
+            pda(next_committee_address, next_committee_data, tapedrive::ID),
+            pda(target_epoch_address, target_epoch_data.pack(), tapedrive::ID),
+            pda(target_committee_address, target_committee_data, tapedrive::ID),
+            pda(peer_set_address, peer_set_data, tapedrive::ID),
+            empty(subsidy_address),
+            token(subsidy_ata_address, Pubkey::from(subsidy_address), 0),
+            mint(0),
+            token_program(),
+            system_program(),
+        ];
+
+        let expected_archive = Archive {
+            slash_bps: DEFAULT_SLASH_BPS,
+            storage_capacity: StorageUnits::mb(2_048),
+            storage_price: TAPE(950),
+            burn_fee_bps: prefs.burn_fee_bps,
+            subsidy_decay_bps: prefs.subsidy_decay_bps,
+            schedule: {
+                let mut s = archive.schedule;
+                let _ = s.advance_epoch();
+                s
+            },
+            ..archive
+        };
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &accounts,
+            &[
+                Check::success(),
+                Check::account(&Pubkey::from(archive_address))
+                    .data(expected_archive.pack().as_ref())
+                    .build(),
+            ],
+        );
+    }
 }
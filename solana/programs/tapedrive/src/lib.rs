@@ -17,7 +17,7 @@ use tape_api::program::prelude::{TapeInstruction, parse_instruction};
 use tape_api::program::tapedrive;
 use tape_solana::{AccountInfo, ProgramError, ProgramResult, Pubkey, TryFromPrimitive, entrypoint};
 
-use crate::archive::process_create_archive;
+use crate::archive::{process_create_archive, process_upgrade_archive};
 use crate::system::{
     process_create_system,
     process_stage_genesis_node,
@@ -52,23 +52,33 @@ use crate::node::{
     process_set_storage_capacity,
     process_set_storage_price,
     process_set_subsidy_decay_bps,
+    process_upgrade_node,
 };
 use crate::peer::{process_create_peer_set, process_resize_peer_set};
 use crate::pool::{
     process_advance_pool,
     process_merge_pool_stake,
     process_request_stake_unlock,
+    process_slash_pool,
     process_split_pool_stake,
     process_stake_with_pool,
     process_unstake_from_pool,
 };
 use crate::tape::{
+    process_accept_tape_transfer,
     process_destroy_tape,
     process_extend_tape_capacity,
     process_extend_tape_expiry,
+    process_fund_tape_escrow,
+    process_place_delete_marker,
+    process_propose_tape_transfer,
+    process_remove_delete_marker,
+    process_renew_tape,
     process_revoke_tape_delegate,
     process_reserve_tape,
     process_set_tape_delegate,
+    process_set_tape_versioning,
+    process_withdraw_tape_escrow,
 };
 use crate::track::{
     process_certify_track,
@@ -118,6 +128,7 @@ pub fn process_instruction(
         TapeInstruction::ResizePeerSet => process_resize_peer_set(accounts, data)?,
         TapeInstruction::StageGenesisNode => process_stage_genesis_node(accounts, data)?,
         TapeInstruction::StartNetwork => process_start_network(accounts, data)?,
+        TapeInstruction::UpgradeArchive => process_upgrade_archive(accounts, data)?,
 
         // Epoch
         TapeInstruction::SyncSpool => process_sync_spool(accounts, data)?,
@@ -152,6 +163,8 @@ pub fn process_instruction(
         TapeInstruction::UnstakeFromPool => process_unstake_from_pool(accounts, data)?,
         TapeInstruction::SplitPoolStake => process_split_pool_stake(accounts, data)?,
         TapeInstruction::MergePoolStake => process_merge_pool_stake(accounts, data)?,
+        TapeInstruction::SlashPool => process_slash_pool(accounts, data)?,
+        TapeInstruction::UpgradeNode => process_upgrade_node(accounts, data)?,
 
         // Tape
         TapeInstruction::ReserveTape => process_reserve_tape(accounts, data)?,
@@ -160,6 +173,14 @@ pub fn process_instruction(
         TapeInstruction::ExtendTapeExpiry => process_extend_tape_expiry(accounts, data)?,
         TapeInstruction::SetTapeDelegate => process_set_tape_delegate(accounts, data)?,
         TapeInstruction::RevokeTapeDelegate => process_revoke_tape_delegate(accounts, data)?,
+        TapeInstruction::ProposeTapeTransfer => process_propose_tape_transfer(accounts, data)?,
+        TapeInstruction::AcceptTapeTransfer => process_accept_tape_transfer(accounts, data)?,
+        TapeInstruction::FundTapeEscrow => process_fund_tape_escrow(accounts, data)?,
+        TapeInstruction::WithdrawTapeEscrow => process_withdraw_tape_escrow(accounts, data)?,
+        TapeInstruction::RenewTape => process_renew_tape(accounts, data)?,
+        TapeInstruction::SetTapeVersioning => process_set_tape_versioning(accounts, data)?,
+        TapeInstruction::PlaceDeleteMarker => process_place_delete_marker(accounts, data)?,
+        TapeInstruction::RemoveDeleteMarker => process_remove_delete_marker(accounts, data)?,
 
         // Track
         TapeInstruction::TrackWrite => process_track_write(accounts, data)?,
@@ -15,6 +15,7 @@ pub mod set_spool_groups;
 pub mod set_storage_capacity;
 pub mod set_storage_price;
 pub mod set_subsidy_decay_bps;
+pub mod upgrade;
 
 pub use claim::*;
 pub use join::*;
@@ -33,3 +34,4 @@ pub use set_spool_groups::*;
 pub use set_storage_capacity::*;
 pub use set_storage_price::*;
 pub use set_subsidy_decay_bps::*;
+pub use upgrade::*;
@@ -0,0 +1,116 @@
+use tape_solana::*;
+use tape_api::program::prelude::*;
+
+/// Grow a node registered before slashing to the current layout.
+///
+/// Permissionless: the new slashing state starts unset, which is what a pool
+/// that was never slashed holds, so anyone may pay the extra rent. A node in
+/// the current layout is refused.
+pub fn process_upgrade_node(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let _args = UpgradeNode::try_from_bytes(data)?;
+    let [
+        fee_payer_info,
+        node_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    system_program_info
+        .is_program(&system_program::ID)?;
+
+    node_info
+        .is_writable()?
+        .is_type::<Node>(&tapedrive::ID)?;
+
+    if node_info.data_len() != Node::PRE_SLASH_SIZE {
+        return Err(TapeError::UnexpectedState.into());
+    }
+
+    resize_account(node_info, system_program_info, fee_payer_info, Node::get_size())?;
+    node_info.try_borrow_mut_data()?[Node::PRE_SLASH_SIZE..].fill(0);
+
+    // The grown account must load as a node again.
+    node_info.as_account::<Node>(&tapedrive::ID)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tape_test::*;
+
+    fn staked_node(authority: Pubkey) -> Node {
+        Node {
+            id: NodeId(3),
+            authority: authority.into(),
+            pool: StakingPool {
+                stake: TAPE(10_000),
+                shares: ShareAmount(9_000),
+                rewards: TAPE(1_000),
+                ..StakingPool::zeroed()
+            },
+            registered_epoch: EpochNumber(2),
+            latest_advance_epoch: EpochNumber(9),
+            suspended_until: EpochNumber(4),
+            ..Node::zeroed()
+        }
+    }
+
+    #[test]
+    fn upgrade_node() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (node_address, _) = node_pda(authority.into());
+        let node = staked_node(authority);
+        let mut legacy = node.pack();
+        legacy.truncate(Node::PRE_SLASH_SIZE);
+
+        let instruction = build_upgrade_node_ix(fee_payer.into(), node_address);
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &[
+                sol(fee_payer, 1_000_000_000),
+                pda(node_address, legacy.clone(), tapedrive::ID),
+                system_program(),
+            ],
+            &[
+                Check::success(),
+                Check::account(&Pubkey::from(node_address))
+                    .data(node.pack().as_ref())
+                    .build(),
+            ],
+        );
+
+        // Clients decode the legacy layout the same way.
+        assert_eq!(Node::read_account(&legacy).expect("legacy node"), node);
+    }
+
+    // a node already in the current layout is left alone
+    #[test]
+    fn upgrade_rejects_current_layout() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (node_address, _) = node_pda(authority.into());
+
+        let instruction = build_upgrade_node_ix(fee_payer.into(), node_address);
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &[
+                sol(fee_payer, 1_000_000_000),
+                pda(node_address, staked_node(authority).pack(), tapedrive::ID),
+                system_program(),
+            ],
+            &[Check::err(TapeError::UnexpectedState.into())],
+        );
+    }
+}
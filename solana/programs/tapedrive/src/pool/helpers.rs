@@ -37,3 +37,69 @@ pub fn resolve_rate(
 
     Ok(span.rate)
 }
+
+/// Slash a node's pool at the archive's configured rate and burn the whole
+/// cut from the archive. The pool's rewards cover what they can and the
+/// archive fronts the rest until the pool's stakers repay it out of their
+/// principal at unstake. Returns `None` when there is nothing to cut: the
+/// pool holds no stake, or it was already slashed this epoch.
+#[allow(clippy::too_many_arguments)]
+pub fn slash_pool<'info>(
+    node: &mut Node,
+    node_address: Address,
+    current_epoch: EpochNumber,
+    reason: SlashReason,
+    evidence: Hash,
+    archive_info: &AccountInfo<'info>,
+    archive_ata_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    token_program_info: &AccountInfo<'info>,
+) -> Result<Option<PoolSlash>, ProgramError> {
+    let slash_bps = archive_info
+        .is_archive()?
+        .as_account::<Archive>(&tapedrive::ID)?
+        .slash_bps;
+
+    let available: Coin<TAPE> = archive_ata_info
+        .is_writable()?
+        .is_archive_ata()?
+        .as_token_account()?
+        .amount()
+        .into();
+    mint_info
+        .is_writable()?
+        .is_mint()?;
+    token_program_info
+        .is_program(&spl_token::ID)?;
+
+    if node.pool.stake.is_zero() {
+        return Ok(None);
+    }
+
+    let cut = match node.pool.slash(&mut node.slashing, current_epoch, slash_bps, available) {
+        Ok(cut) => cut,
+        Err(PoolError::AlreadySlashed) => return Ok(None),
+        Err(_) => return Err(TapeError::StakingFailed.into()),
+    };
+
+    burn_signed(
+        archive_ata_info,
+        mint_info,
+        archive_info,
+        token_program_info,
+        cut.amount.into(),
+        &[ARCHIVE],
+    )?;
+
+    StakeSlashed {
+        pool: node_address,
+        epoch: current_epoch,
+        reason: reason.into(),
+        evidence,
+        amount: cut.amount,
+        fronted: cut.amount - cut.from_rewards,
+    }
+    .log();
+
+    Ok(Some(cut))
+}
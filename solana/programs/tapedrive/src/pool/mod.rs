@@ -4,6 +4,7 @@ pub mod unlock;
 pub mod unstake;
 pub mod split;
 pub mod merge;
+pub mod slash;
 pub mod helpers;
 
 pub use advance::*;
@@ -12,3 +13,4 @@ pub use unlock::*;
 pub use unstake::*;
 pub use split::*;
 pub use merge::*;
+pub use slash::*;
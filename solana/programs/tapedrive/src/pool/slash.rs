@@ -0,0 +1,262 @@
+use tape_api::program::prelude::*;
+use tape_crypto::bls12254::min_sig::*;
+use tape_crypto::hash::hash;
+
+use crate::pool::helpers::slash_pool;
+
+pub fn process_slash_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let args = SlashPool::try_from_bytes(data)?;
+    let [
+        fee_payer_info,
+
+        system_info,
+        epoch_info,
+        group_info,
+        node_info,
+
+        archive_info,
+        archive_ata_info,
+        mint_info,
+        token_program_info,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    let system = system_info
+        .is_system()?
+        .as_account::<System>(&tapedrive::ID)?;
+
+    let current = system.current_epoch;
+
+    let epoch = epoch_info
+        .is_epoch(current)?
+        .as_account::<Epoch>(&tapedrive::ID)?;
+
+    if args.group.0 >= epoch.total_groups {
+        return Err(TapeError::BadMember.into());
+    }
+
+    let group = group_info
+        .is_group(current, args.group)?
+        .as_account::<Group>(&tapedrive::ID)?;
+
+    // Only a group the node serves in can vouch for its misbehaviour.
+    if !group.spools.iter().any(|spool| spool.node == args.node) {
+        return Err(TapeError::BadMember.into());
+    }
+
+    let weight = args.bitmap.count_ones() as u64;
+    if !is_supermajority(weight, GROUP_SIZE as u64) {
+        return Err(TapeError::NoQuorum.into());
+    }
+
+    // The certificate is the one the group's members sign when the node
+    // fails their storage challenges. The node never signs against itself.
+    let indices = args.bitmap.indices();
+    let mut pubkeys = Vec::with_capacity(indices.len());
+    for spool_index in &indices {
+        let spool = group.spools.get(*spool_index).ok_or(TapeError::BadMember)?;
+        if spool.node == args.node {
+            return Err(TapeError::BadMember.into());
+        }
+        pubkeys.push(spool.bls_pubkey.0);
+    }
+
+    let signature = G1Point::try_from(&args.signature.0)
+        .map_err(|_| TapeError::BadSignature)?;
+
+    let message = ChallengeFailMessage::new(current, epoch.nonce, args.node).to_bytes();
+
+    verify_aggregate(&message, &pubkeys, &signature)
+        .map_err(|_| TapeError::BadSignature)?;
+
+    let node = node_info
+        .is_writable()?
+        .has_address(&args.node.into())?
+        .as_account_mut::<Node>(&tapedrive::ID)?;
+
+    slash_pool(
+        node,
+        args.node,
+        current,
+        SlashReason::Inconsistency,
+        hash(&message),
+        archive_info,
+        archive_ata_info,
+        mint_info,
+        token_program_info,
+    )?
+    .ok_or(TapeError::AlreadySlashed)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tape_test::*;
+
+    const EPOCH: EpochNumber = EpochNumber(12);
+    const NONCE: Hash = Hash([0x5A; 32]);
+    const SUPERMAJORITY: usize = 14;
+
+    fn challenge_failure(node: Address) -> Vec<u8> {
+        ChallengeFailMessage::new(EPOCH, NONCE, node).to_bytes().to_vec()
+    }
+
+    // Group 0 of EPOCH slashes `node`, with the spools in `signers` signing
+    // `message`.
+    fn run(
+        node: Address,
+        slashable_epoch: EpochNumber,
+        signers: &[usize],
+        message: &[u8],
+        checks: &[Check],
+    ) {
+        let fee_payer = Pubkey::new_unique();
+        let group_id = GroupIndex(0);
+        let (sks, group) = make_group(EPOCH, group_id);
+
+        let system = System {
+            current_epoch: EPOCH,
+            ..System::zeroed()
+        };
+        let epoch = Epoch {
+            id: EPOCH,
+            nonce: NONCE,
+            total_groups: 1,
+            ..Epoch::zeroed()
+        };
+        let archive = Archive {
+            slash_bps: DEFAULT_SLASH_BPS,
+            ..Archive::zeroed()
+        };
+        let node_account = Node {
+            authority: node,
+            pool: StakingPool {
+                stake: TAPE(10_000),
+                rewards: TAPE(200),
+                ..StakingPool::zeroed()
+            },
+            slashing: SlashState {
+                slashable_epoch,
+                ..SlashState::zeroed()
+            },
+            ..Node::zeroed()
+        };
+
+        let partials: Vec<BlsSignature> = signers
+            .iter()
+            .map(|&i| sks[i].sign(message).unwrap())
+            .collect();
+
+        let instruction = build_slash_pool_ix(
+            fee_payer.into(),
+            EPOCH,
+            node,
+            group_id,
+            SpoolBitmap::from_indices(signers),
+            BlsSignature::aggregate(&partials).unwrap(),
+        );
+
+        // The archive also holds rewards owed to other pools.
+        let accounts = vec![
+            sol(fee_payer, 1_000_000_000),
+            pda(system_pda().0, system.pack(), tapedrive::ID),
+            pda(epoch_pda(EPOCH).0, epoch.pack(), tapedrive::ID),
+            pda(group_pda(EPOCH, group_id).0, group.pack(), tapedrive::ID),
+            pda(node, node_account.pack(), tapedrive::ID),
+            pda(archive_pda().0, archive.pack(), tapedrive::ID),
+            token(archive_ata().0, archive_pda().0, 2_000),
+            mint(MAX_SUPPLY),
+            token_program(),
+        ];
+
+        test_env().process_instruction(&instruction, &accounts, checks);
+    }
+
+    // The last spool in make_group's group belongs to this node.
+    fn member() -> Address {
+        let mut bytes = [0u8; 32];
+        bytes[0] = GROUP_SIZE as u8;
+        Address::new(bytes)
+    }
+
+    fn others() -> Vec<usize> {
+        (0..SUPERMAJORITY).collect()
+    }
+
+    // 5% of 10_000 stake is burned in full. The pool's 200 rewards cover part
+    // of it and the archive fronts the other 300.
+    #[test]
+    fn slash_pool() {
+        let node = member();
+        let expected_node = Node {
+            authority: node,
+            pool: StakingPool {
+                stake: TAPE(9_500),
+                rewards: TAPE(0),
+                ..StakingPool::zeroed()
+            },
+            slashing: SlashState {
+                slashable_epoch: EPOCH.next(),
+                fronted: TAPE(300),
+            },
+            ..Node::zeroed()
+        };
+
+        run(node, EpochNumber(0), &others(), &challenge_failure(node), &[
+            Check::success(),
+            Check::account(&Pubkey::from(node))
+                .data(expected_node.pack().as_ref())
+                .build(),
+            Check::account(&Pubkey::from(archive_ata().0))
+                .data(token(archive_ata().0, archive_pda().0, 1_500).1.data.as_ref())
+                .build(),
+        ]);
+    }
+
+    #[test]
+    fn slash_pool_once_per_epoch() {
+        let node = member();
+        run(node, EPOCH.next(), &others(), &challenge_failure(node), &[
+            Check::err(TapeError::AlreadySlashed.into()),
+        ]);
+    }
+
+    // A group can only slash a node it serves with.
+    #[test]
+    fn slash_pool_not_in_group() {
+        let node = Address::new([0xEE; 32]);
+        run(node, EpochNumber(0), &others(), &challenge_failure(node), &[
+            Check::err(TapeError::BadMember.into()),
+        ]);
+    }
+
+    // The node's own spool cannot count towards a certificate against it.
+    #[test]
+    fn slash_pool_target_signed() {
+        let node = member();
+        let mut signers = others();
+        signers.push(GROUP_SIZE - 1);
+        run(node, EpochNumber(0), &signers, &challenge_failure(node), &[
+            Check::err(TapeError::BadMember.into()),
+        ]);
+    }
+
+    // Only a challenge failure certificate slashes; other group signatures
+    // over the node do not carry over.
+    #[test]
+    fn slash_pool_not_challenge_failure() {
+        let node = member();
+        let eviction = NodeEvictMessage::new(EPOCH, NONCE, node).to_bytes();
+        run(node, EpochNumber(0), &others(), &eviction, &[
+            Check::err(TapeError::BadSignature.into()),
+        ]);
+    }
+}
@@ -133,10 +133,25 @@ pub fn process_unstake_from_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> P
         .saturating_sub(staked_tape.amount.into());
 
     // Update pool accounting and stake state
-    let total_rewards = node.pool
+    let paid_rewards = node.pool
         .unstake_from_pool(staked_tape, current, owed_rewards.into())
         .map_err(|_| TapeError::StakingFailed)?;
 
+    // A slash burns rewards this staker may still be owed; the archive fronts
+    // them. It also leaves shares worth less than their principal; the staker
+    // hands that gap back to the archive. Stake that never activated held no
+    // shares and neither gains nor loses.
+    let fronted_rewards = node.pool.front_rewards(
+        &mut node.slashing,
+        TAPE(owed_rewards).saturating_sub(paid_rewards),
+    );
+    let repaid = if withdraw_epoch > staked_tape.activation_epoch {
+        node.pool.repay_slash(&mut node.slashing, staked_tape.amount, tokens_at_withdraw.into())
+    } else {
+        TAPE::zero()
+    };
+    let total_rewards = paid_rewards + fronted_rewards;
+
     solana_program::msg!(
         "Unstaking {} (owed rewards: {}, total rewards paid: {})",
         staked_tape.amount,
@@ -173,11 +188,23 @@ pub fn process_unstake_from_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> P
         STAKE_AUTHORITY_BUMP,
     )?;
 
+    // Hand the slashed part of the principal the vault just released to the
+    // archive, which burned it when the slash landed.
+    if !repaid.is_zero() {
+        transfer(
+            authority_info,
+            authority_ata_info,
+            archive_ata_info,
+            token_program_info,
+            repaid.into(),
+        )?;
+    }
+
     StakeWithdrawn {
         stake: stake_address,
         authority: (*authority_info.key).into(),
         pool: (*node_info.key).into(),
-        principal: staked_tape.amount,
+        principal: staked_tape.amount - repaid,
         rewards: total_rewards,
     }.log();
 
@@ -239,6 +266,45 @@ mod tests {
 
     #[test]
     fn unstake_from_pool() {
+        check_unstake(
+            ExchangeRate { tape: 1200, other: 8800 },
+            SlashState::default(),
+            0,
+            SlashState::default(),
+        );
+    }
+
+    fn slashed(fronted: u64) -> SlashState {
+        SlashState {
+            slashable_epoch: EpochNumber(44),
+            fronted: TAPE(fronted),
+        }
+    }
+
+    // The pool was slashed after activation, so the shares settle 100 below
+    // the principal. The staker hands that gap back to the archive, which
+    // fronted 250 of the burned cut.
+    #[test]
+    fn unstake_from_pool_slashed() {
+        check_unstake(ExchangeRate { tape: 900, other: 9000 }, slashed(250), 0, slashed(150));
+    }
+
+    // The slash took the pool's rewards before this staker left, so the pool
+    // is 200 short of what the shares settled at. The archive fronts it.
+    #[test]
+    fn unstake_from_pool_slashed_fronts_rewards() {
+        check_unstake(ExchangeRate { tape: 1200, other: 8800 }, slashed(0), 200, slashed(200));
+    }
+
+    // Unstake 1_000 staked at 1000/9000, settling at `withdraw_rate`, from a
+    // pool in `slashing` whose rewards are `unfunded` short of what is owed.
+    // The staker always leaves with what the shares settled at.
+    fn check_unstake(
+        withdraw_rate: ExchangeRate,
+        slashing: SlashState,
+        unfunded: u64,
+        expected_slashing: SlashState,
+    ) {
         let fee_payer = Pubkey::new_unique();
         let authority = Pubkey::new_unique();
         let pool_owner = Pubkey::new_unique();
@@ -258,7 +324,6 @@ mod tests {
         let e4: EpochNumber = e0 + EpochNumber(4); // withdraw epoch (== current)
 
         let activation_rate = ExchangeRate { tape: 1000, other: 9000 };
-        let withdraw_rate   = ExchangeRate { tape: 1200, other: 8800 };
 
         // Closed span covering [e0, e4) holds the withdraw settlement rate.
         let span = RateSpan {
@@ -291,6 +356,7 @@ mod tests {
 
         node.pool.stake = TAPE(withdraw_rate.tape);
         node.pool.shares = ShareAmount(withdraw_rate.other);
+        node.slashing = slashing;
 
         let principal: u64 = 1_000;
         let shares = activation_rate
@@ -300,7 +366,14 @@ mod tests {
         let reward = tokens_at_withdraw
             .saturating_sub(principal);
 
-        node.pool.rewards = reward.into();
+        node.pool.rewards = (reward - unfunded).into();
+
+        // A slashed staker repays what the shares lost against the principal.
+        let repaid = if slashing.is_slashed() {
+            principal.saturating_sub(tokens_at_withdraw)
+        } else {
+            0
+        };
 
         let stake = Stake {
             authority: authority.into(),
@@ -357,17 +430,18 @@ mod tests {
                     .closed()
                     .build(),
                 Check::account(&Pubkey::from(archive_ata)).data(
-                    token(archive_ata, archive_address, 0).1.data.as_ref()
+                    token(archive_ata, archive_address, repaid).1.data.as_ref()
                 ).build(),
                 Check::account(&Pubkey::from(authority_ata)).data(
-                    token(authority_ata, authority, principal + reward).1.data.as_ref()
+                    token(authority_ata, authority, principal + reward - repaid).1.data.as_ref()
                 ).build(),
                 Check::account(&Pubkey::from(pool_address)).data(
                     Node {
                         pool: StakingPool {
-                            rewards: node.pool.rewards - TAPE(reward),
+                            rewards: TAPE(0),
                             ..node.pool
                         },
+                        slashing: expected_slashing,
                         ..node
                     }.pack().as_ref()
                 ).build(),
@@ -79,7 +79,7 @@ mod tests {
             ..System::zeroed()
         };
 
-        let instruction = build_destroy_tape_ix(fee_payer.into(), authority.into());
+        let instruction = build_destroy_tape_ix(fee_payer.into(), authority.into(), tape_address);
 
         let accounts = vec![
             sol(fee_payer, 1_000_000_000),
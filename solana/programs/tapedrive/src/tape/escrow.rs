@@ -0,0 +1,593 @@
+use tape_solana::*;
+use tape_api::event::{TapeEscrowFunded, TapeEscrowLow, TapeEscrowWithdrawn, TapeExtended};
+use tape_api::program::prelude::*;
+use tape_core::tape::{in_renewal_window, tape_reservation_cost};
+
+use crate::tape::helpers::{authorize_tape_authority, schedule_capacity};
+
+pub fn process_fund_tape_escrow(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let args = FundTapeEscrow::try_from_bytes(data)?;
+    let [
+        fee_payer_info,
+        payer_info,
+        payer_ata_info,
+
+        tape_info,
+        escrow_info,
+        archive_ata_info,
+
+        token_program_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    payer_info
+        .is_signer()?;
+
+    payer_ata_info
+        .is_writable()?
+        .as_token_account()?
+        .assert(|t| t.owner() == *payer_info.key)?
+        .assert(|t| t.mint() == MINT_ADDRESS.into())?;
+
+    token_program_info
+        .is_program(&spl_token::ID)?;
+    system_program_info
+        .is_program(&system_program::ID)?;
+
+    archive_ata_info
+        .is_writable()?
+        .is_archive_ata()?;
+
+    if args.amount.is_zero() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let tape = tape_info.as_account::<Tape>(&tapedrive::ID)?;
+    let tape_address: Address = (*tape_info.key).into();
+    let payer: Address = (*payer_info.key).into();
+
+    let (escrow_address, _) = escrow_pda(tape_address);
+    escrow_info
+        .is_writable()?
+        .has_address(&escrow_address.into())?;
+
+    // Opening fixes who may withdraw and how far each renewal reaches, so it
+    // is left to the tape authority. Top-ups are open to anyone.
+    if escrow_info.data_is_empty() {
+        authorize_tape_authority(tape, payer)?;
+        if args.renewal_epochs == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        create_program_account::<Escrow>(
+            escrow_info,
+            system_program_info,
+            fee_payer_info,
+            &tapedrive::ID,
+            &[ESCROW, tape_info.key.as_ref()],
+        )?;
+
+        let escrow = escrow_info.as_account_mut::<Escrow>(&tapedrive::ID)?;
+        escrow.tape = tape_address;
+        escrow.authority = payer;
+        escrow.renewal_epochs = args.renewal_epochs;
+    }
+
+    let escrow = escrow_info.as_account_mut::<Escrow>(&tapedrive::ID)?;
+
+    // Escrowed TAPE sits in the archive token account, so a renewal only has
+    // to burn its policy share; the rest is already where rewards are paid from.
+    transfer(
+        payer_info,
+        payer_ata_info,
+        archive_ata_info,
+        token_program_info,
+        args.amount.as_u64(),
+    )?;
+
+    escrow.balance = escrow.balance
+        .checked_add(args.amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    TapeEscrowFunded {
+        tape: tape_address,
+        escrow: escrow_address,
+        payer,
+        amount: args.amount,
+        balance: escrow.balance,
+    }
+    .log();
+
+    Ok(())
+}
+
+pub fn process_withdraw_tape_escrow(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let args = WithdrawTapeEscrow::try_from_bytes(data)?;
+    let [
+        fee_payer_info,
+        authority_info,
+        authority_ata_info,
+
+        escrow_info,
+        archive_info,
+        archive_ata_info,
+
+        token_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    authority_info
+        .is_signer()?;
+
+    authority_ata_info
+        .is_writable()?
+        .as_token_account()?
+        .assert(|t| t.owner() == *authority_info.key)?
+        .assert(|t| t.mint() == MINT_ADDRESS.into())?;
+
+    archive_info
+        .is_archive()?;
+
+    archive_ata_info
+        .is_writable()?
+        .is_archive_ata()?;
+
+    token_program_info
+        .is_program(&spl_token::ID)?;
+
+    let escrow = escrow_info
+        .is_writable()?
+        .as_account_mut::<Escrow>(&tapedrive::ID)?;
+
+    let (escrow_address, _) = escrow_pda(escrow.tape);
+    escrow_info.has_address(&escrow_address.into())?;
+
+    // The escrow stays with whoever opened it, even after the tape changes
+    // hands or is destroyed.
+    let authority: Address = (*authority_info.key).into();
+    if escrow.authority != authority {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if args.amount.is_zero() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    escrow.balance = escrow.balance
+        .checked_sub(args.amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+
+    transfer_signed(
+        archive_info,
+        archive_ata_info,
+        authority_ata_info,
+        token_program_info,
+        args.amount.as_u64(),
+        &[ARCHIVE],
+    )?;
+
+    TapeEscrowWithdrawn {
+        tape: escrow.tape,
+        escrow: escrow_address,
+        authority,
+        amount: args.amount,
+        balance: escrow.balance,
+    }
+    .log();
+
+    // An emptied escrow is closed; funding the tape again reopens it.
+    if escrow.balance.is_zero() {
+        close_account(escrow_info, fee_payer_info)?;
+    }
+
+    Ok(())
+}
+
+pub fn process_renew_tape(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    let [
+        fee_payer_info,
+
+        tape_info,
+        escrow_info,
+        system_info,
+        archive_info,
+        archive_ata_info,
+        mint_info,
+
+        token_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    token_program_info
+        .is_program(&spl_token::ID)?;
+
+    let system = system_info
+        .is_system()?
+        .as_account::<System>(&tapedrive::ID)?;
+
+    archive_info
+        .is_writable()?
+        .is_archive()?;
+
+    let archive = archive_info.as_account_mut::<Archive>(&tapedrive::ID)?;
+
+    archive_ata_info
+        .is_writable()?
+        .is_archive_ata()?;
+
+    mint_info
+        .is_writable()?
+        .is_mint()?;
+
+    let tape = tape_info
+        .is_writable()?
+        .as_account_mut::<Tape>(&tapedrive::ID)?;
+
+    let tape_address: Address = (*tape_info.key).into();
+    let (escrow_address, _) = escrow_pda(tape_address);
+
+    let escrow = escrow_info
+        .is_writable()?
+        .has_address(&escrow_address.into())?
+        .as_account_mut::<Escrow>(&tapedrive::ID)?;
+
+    // Renewal is a permissionless crank, allowed once the tape is within the
+    // renewal window of its expiry. The window leaves nodes several epochs to
+    // get a renewal in, while a crank can still never push the tape further
+    // out than one renewal past the window.
+    let current = current_epoch(system);
+    if current >= tape.expiry_epoch {
+        return Err(TapeError::TapeExpired.into());
+    }
+    if !in_renewal_window(current, tape.expiry_epoch) {
+        return Err(TapeError::NotRenewable.into());
+    }
+
+    let renewal = EpochNumber(escrow.renewal_epochs);
+    let new_expiry_epoch = tape.expiry_epoch
+        .checked_add(renewal)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let required = tape_reservation_cost(archive.storage_price, tape.capacity, renewal.as_u64())
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    // Not enough to renew: report it and leave the tape as it is. This is not
+    // an error, so the event lands on chain for the owner to see, and it is
+    // raised again on every crank left in the window.
+    if escrow.balance < required {
+        TapeEscrowLow {
+            tape: tape_address,
+            escrow: escrow_address,
+            balance: escrow.balance,
+            required,
+            expiry_epoch: tape.expiry_epoch,
+        }
+        .log();
+        return Ok(());
+    }
+
+    let payment = schedule_capacity(
+        system,
+        archive,
+        tape.capacity,
+        tape.expiry_epoch,
+        new_expiry_epoch,
+    )?;
+
+    if !payment.burned.is_zero() {
+        burn_signed(
+            archive_ata_info,
+            mint_info,
+            archive_info,
+            token_program_info,
+            payment.burned.as_u64(),
+            &[ARCHIVE],
+        )?;
+    }
+
+    escrow.balance = escrow.balance
+        .checked_sub(payment.cost)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    escrow.last_renewal = current;
+    tape.expiry_epoch = new_expiry_epoch;
+
+    TapeExtended {
+        tape: tape_address,
+        payer: escrow_address,
+        capacity: tape.capacity,
+        active_epoch: tape.active_epoch,
+        expiry_epoch: tape.expiry_epoch,
+        cost: payment.cost,
+        burned: payment.burned,
+        scheduled: payment.scheduled,
+    }
+    .log();
+
+    // Warn a renewal ahead, while the owner still has a full term to top up.
+    if escrow.balance < required {
+        TapeEscrowLow {
+            tape: tape_address,
+            escrow: escrow_address,
+            balance: escrow.balance,
+            required,
+            expiry_epoch: tape.expiry_epoch,
+        }
+        .log();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tape_test::*;
+
+    const PRICE: u64 = 100;
+
+    fn user_tape(authority: Pubkey, expiry_epoch: EpochNumber) -> Tape {
+        Tape {
+            id: TapeNumber(1),
+            authority: authority.into(),
+            capacity: StorageUnits::mb(100),
+            active_epoch: EpochNumber(40),
+            expiry_epoch,
+            ..Tape::zeroed()
+        }
+    }
+
+    fn open_escrow(tape: Address, authority: Pubkey, balance: u64) -> Escrow {
+        Escrow {
+            tape,
+            authority: authority.into(),
+            balance: TAPE(balance),
+            renewal_epochs: 2,
+            last_renewal: EpochNumber(0),
+        }
+    }
+
+    #[test]
+    fn fund_tape_escrow_opens() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (escrow_address, _) = escrow_pda(tape_address);
+        let (archive_address, _) = archive_pda();
+        let (archive_ata, _) = archive_ata();
+        let authority_ata = ata_address(&authority);
+
+        let instruction = build_fund_tape_escrow_ix(
+            fee_payer.into(),
+            authority.into(),
+            tape_address,
+            TAPE(500),
+            2,
+        );
+        let accounts = vec![
+            sol(fee_payer, 1_000_000_000),
+            sol(authority, 0),
+            token(authority_ata, authority, 500),
+            pda(tape_address, user_tape(authority, EpochNumber(50)).pack(), tapedrive::ID),
+            empty(escrow_address),
+            token(archive_ata, archive_address, 0),
+            token_program(),
+            system_program(),
+        ];
+
+        let expected = open_escrow(tape_address, authority, 500);
+        test_env().process_instruction(&instruction, &accounts, &[
+            Check::success(),
+            Check::account(&Pubkey::from(escrow_address))
+                .data(expected.pack().as_ref())
+                .build(),
+            Check::account(&Pubkey::from(archive_ata))
+                .data(token(archive_ata, archive_address, 500).1.data.as_ref())
+                .build(),
+        ]);
+    }
+
+    // Only the tape authority can open an escrow and pick its renewal term.
+    #[test]
+    fn fund_tape_escrow_open_needs_authority() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (escrow_address, _) = escrow_pda(tape_address);
+        let (archive_address, _) = archive_pda();
+        let (archive_ata, _) = archive_ata();
+
+        let instruction = build_fund_tape_escrow_ix(
+            fee_payer.into(),
+            stranger.into(),
+            tape_address,
+            TAPE(500),
+            2,
+        );
+        let accounts = vec![
+            sol(fee_payer, 1_000_000_000),
+            sol(stranger, 0),
+            token(ata_address(&stranger), stranger, 500),
+            pda(tape_address, user_tape(authority, EpochNumber(50)).pack(), tapedrive::ID),
+            empty(escrow_address),
+            token(archive_ata, archive_address, 0),
+            token_program(),
+            system_program(),
+        ];
+
+        test_env().process_instruction(&instruction, &accounts, &[
+            Check::err(ProgramError::InvalidAccountData),
+        ]);
+    }
+
+    // Withdrawing the whole balance pays it out and closes the escrow.
+    #[test]
+    fn withdraw_tape_escrow_closes() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (escrow_address, _) = escrow_pda(tape_address);
+        let (archive_address, _) = archive_pda();
+        let (archive_ata, _) = archive_ata();
+        let authority_ata = ata_address(&authority);
+
+        let instruction = build_withdraw_tape_escrow_ix(
+            fee_payer.into(),
+            authority.into(),
+            tape_address,
+            TAPE(500),
+        );
+        let escrow = open_escrow(tape_address, authority, 500);
+        let accounts = vec![
+            sol(fee_payer, 1_000_000_000),
+            sol(authority, 0),
+            token(authority_ata, authority, 0),
+            pda(escrow_address, escrow.pack(), tapedrive::ID),
+            pda(archive_address, Archive::zeroed().pack(), tapedrive::ID),
+            token(archive_ata, archive_address, 500),
+            token_program(),
+        ];
+
+        test_env().process_instruction(&instruction, &accounts, &[
+            Check::success(),
+            Check::account(&Pubkey::from(escrow_address)).closed().build(),
+            Check::account(&Pubkey::from(authority_ata))
+                .data(token(authority_ata, authority, 500).1.data.as_ref())
+                .build(),
+        ]);
+    }
+
+    fn renew(
+        authority: Pubkey,
+        current: EpochNumber,
+        expiry_epoch: EpochNumber,
+        balance: u64,
+        checks: &[Check],
+    ) {
+        let fee_payer = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (escrow_address, _) = escrow_pda(tape_address);
+        let (archive_address, _) = archive_pda();
+        let (archive_ata, _) = archive_ata();
+
+        let system = System {
+            current_epoch: current,
+            ..System::zeroed()
+        };
+        let archive = Archive {
+            storage_capacity: StorageUnits::mb(1000),
+            storage_price: TAPE(PRICE),
+            schedule: EpochSchedule::new_at(current),
+            ..Archive::zeroed()
+        };
+        let tape = user_tape(authority, expiry_epoch);
+        let escrow = open_escrow(tape_address, authority, balance);
+
+        let instruction = build_renew_tape_ix(fee_payer.into(), tape_address);
+        let accounts = vec![
+            sol(fee_payer, 1_000_000_000),
+            pda(tape_address, tape.pack(), tapedrive::ID),
+            pda(escrow_address, escrow.pack(), tapedrive::ID),
+            pda(system_pda().0, system.pack(), tapedrive::ID),
+            pda(archive_address, archive.pack(), tapedrive::ID),
+            token(archive_ata, archive_address, balance),
+            mint(MAX_SUPPLY),
+            token_program(),
+        ];
+
+        test_env().process_instruction(&instruction, &accounts, checks);
+    }
+
+    // In the last epoch before expiry the escrow pays for two more epochs at
+    // the current price.
+    #[test]
+    fn renew_tape() {
+        let authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (escrow_address, _) = escrow_pda(tape_address);
+        let cost = PRICE * StorageUnits::mb(100).to_mb() * 2;
+
+        let expected_tape = user_tape(authority, EpochNumber(52));
+        let expected_escrow = Escrow {
+            last_renewal: EpochNumber(49),
+            ..open_escrow(tape_address, authority, 10)
+        };
+
+        renew(authority, EpochNumber(49), EpochNumber(50), cost + 10, &[
+            Check::success(),
+            Check::account(&Pubkey::from(tape_address))
+                .data(expected_tape.pack().as_ref())
+                .build(),
+            Check::account(&Pubkey::from(escrow_address))
+                .data(expected_escrow.pack().as_ref())
+                .build(),
+        ]);
+    }
+
+    // A short escrow leaves the tape alone and only reports the shortfall.
+    #[test]
+    fn renew_tape_low_balance() {
+        let authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (escrow_address, _) = escrow_pda(tape_address);
+
+        let tape = user_tape(authority, EpochNumber(50));
+        let escrow = open_escrow(tape_address, authority, 10);
+
+        renew(authority, EpochNumber(49), EpochNumber(50), 10, &[
+            Check::success(),
+            Check::account(&Pubkey::from(tape_address))
+                .data(tape.pack().as_ref())
+                .build(),
+            Check::account(&Pubkey::from(escrow_address))
+                .data(escrow.pack().as_ref())
+                .build(),
+        ]);
+    }
+
+    // Anywhere in the window the renewal goes through, ahead of the last epoch.
+    #[test]
+    fn renew_tape_ahead_of_expiry() {
+        let authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let cost = PRICE * StorageUnits::mb(100).to_mb() * 2;
+
+        let expected_tape = user_tape(authority, EpochNumber(52));
+        renew(authority, EpochNumber(46), EpochNumber(50), cost, &[
+            Check::success(),
+            Check::account(&Pubkey::from(tape_address))
+                .data(expected_tape.pack().as_ref())
+                .build(),
+        ]);
+    }
+
+    #[test]
+    fn renew_tape_too_early() {
+        renew(Pubkey::new_unique(), EpochNumber(45), EpochNumber(50), 1_000_000, &[
+            Check::err(TapeError::NotRenewable.into()),
+        ]);
+    }
+
+    #[test]
+    fn renew_tape_expired() {
+        renew(Pubkey::new_unique(), EpochNumber(50), EpochNumber(50), 1_000_000, &[
+            Check::err(TapeError::TapeExpired.into()),
+        ]);
+    }
+}
@@ -206,6 +206,13 @@ pub fn verified_tape_address(
     tape_info: &AccountInfo<'_>,
     tape: &Tape,
 ) -> Result<Address, ProgramError> {
+    // A transferred tape keeps the address derived from its first owner.
+    // The account was already loaded as a tapedrive-owned Tape, so its key
+    // is the tape address.
+    if tape.is_transferred() {
+        return Ok((*tape_info.key).into());
+    }
+
     let (tape_address, _) = tape_pda(tape.authority);
     if tape_address != (*tape_info.key).into() {
         return Err(ProgramError::InvalidAccountData);
@@ -1,10 +1,16 @@
 pub mod create;
 pub mod delegate;
 pub mod destroy;
+pub mod escrow;
 pub mod extend;
 pub mod helpers;
+pub mod transfer;
+pub mod versioning;
 
 pub use create::*;
 pub use delegate::*;
 pub use destroy::*;
+pub use escrow::*;
 pub use extend::*;
+pub use transfer::*;
+pub use versioning::*;
@@ -0,0 +1,410 @@
+use tape_api::event::TapeTransferred;
+use tape_api::program::prelude::*;
+
+use crate::tape::helpers::{
+    authorize_tape_authority,
+    verified_tape_address,
+};
+
+pub fn process_propose_tape_transfer(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let args = ProposeTapeTransfer::try_from_bytes(data)?;
+    let [
+        fee_payer_info,
+        authority_info,
+        tape_info,
+        transfer_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    authority_info
+        .is_signer()?;
+
+    system_program_info
+        .is_program(&system_program::ID)?;
+
+    let tape = tape_info.as_account::<Tape>(&tapedrive::ID)?;
+
+    if tape.is_system() {
+        return Err(TapeError::UnexpectedState.into());
+    }
+
+    let tape_address = verified_tape_address(tape_info, tape)?;
+    authorize_tape_authority(tape, (*authority_info.key).into())?;
+
+    // A tape cannot move to its own owner.
+    if args.new_authority == tape.authority {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (transfer_address, _) = tape_transfer_pda(tape_address);
+    transfer_info
+        .is_writable()?
+        .has_address(&transfer_address.into())?;
+
+    // Proposing the default address withdraws an earlier proposal.
+    if args.new_authority == Address::default() {
+        if !transfer_info.data_is_empty() {
+            close_account(transfer_info, fee_payer_info)?;
+        }
+        return Ok(());
+    }
+
+    // A new proposal replaces the open one.
+    if transfer_info.data_is_empty() {
+        create_program_account::<TapeTransfer>(
+            transfer_info,
+            system_program_info,
+            fee_payer_info,
+            &tapedrive::ID,
+            &[TAPE_TRANSFER, tape_info.key.as_ref()],
+        )?;
+    }
+
+    let transfer = transfer_info.as_account_mut::<TapeTransfer>(&tapedrive::ID)?;
+    transfer.tape = tape_address;
+    transfer.authority = tape.authority;
+    transfer.new_authority = args.new_authority;
+
+    Ok(())
+}
+
+pub fn process_accept_tape_transfer(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let _args = AcceptTapeTransfer::try_from_bytes(data)?;
+    let [
+        fee_payer_info,
+        new_authority_info,
+        tape_info,
+        transfer_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    new_authority_info
+        .is_signer()?;
+
+    let tape = tape_info
+        .is_writable()?
+        .as_account_mut::<Tape>(&tapedrive::ID)?;
+
+    if tape.is_system() {
+        return Err(TapeError::UnexpectedState.into());
+    }
+
+    let tape_address = verified_tape_address(tape_info, tape)?;
+
+    let (transfer_address, _) = tape_transfer_pda(tape_address);
+    let transfer = transfer_info
+        .is_writable()?
+        .has_address(&transfer_address.into())?
+        .as_account::<TapeTransfer>(&tapedrive::ID)?;
+
+    // The offer must come from the current owner and name the signer.
+    let new_authority: Address = (*new_authority_info.key).into();
+    if transfer.tape != tape_address
+        || transfer.authority != tape.authority
+        || transfer.new_authority != new_authority
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let previous_authority = tape.authority;
+    tape.authority = new_authority;
+    tape.flags |= TapeFlags::TRANSFERRED;
+
+    // The delegate was chosen by the previous owner and does not carry over.
+    tape.delegate = Address::default();
+
+    TapeTransferred {
+        tape: tape_address,
+        previous_authority,
+        authority: new_authority,
+    }
+    .log();
+
+    close_account(transfer_info, fee_payer_info)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tape_test::*;
+
+    fn user_tape(authority: Pubkey) -> Tape {
+        Tape {
+            authority: authority.into(),
+            capacity: StorageUnits::mb(1000),
+            ..Tape::zeroed()
+        }
+    }
+
+    fn offer(tape: Address, authority: Pubkey, new_authority: Pubkey) -> TapeTransfer {
+        TapeTransfer {
+            tape,
+            authority: authority.into(),
+            new_authority: new_authority.into(),
+        }
+    }
+
+    fn propose_accounts(
+        fee_payer: Pubkey,
+        signer: Pubkey,
+        tape_address: Address,
+        tape: &Tape,
+    ) -> Vec<(Pubkey, solana_account::Account)> {
+        let (transfer_address, _) = tape_transfer_pda(tape_address);
+        vec![
+            sol(fee_payer, 1_000_000_000),
+            sol(signer, 0),
+            pda(tape_address, tape.pack(), tapedrive::ID),
+            empty(transfer_address),
+            system_program(),
+        ]
+    }
+
+    fn accept_accounts(
+        fee_payer: Pubkey,
+        signer: Pubkey,
+        tape_address: Address,
+        tape: &Tape,
+        transfer: &TapeTransfer,
+    ) -> Vec<(Pubkey, solana_account::Account)> {
+        let (transfer_address, _) = tape_transfer_pda(tape_address);
+        vec![
+            sol(fee_payer, 1_000_000_000),
+            sol(signer, 0),
+            pda(tape_address, tape.pack(), tapedrive::ID),
+            pda(transfer_address, transfer.pack(), tapedrive::ID),
+        ]
+    }
+
+    #[test]
+    fn propose_tape_transfer() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (transfer_address, _) = tape_transfer_pda(tape_address);
+        let tape = user_tape(authority);
+
+        let instruction = build_propose_tape_transfer_ix(
+            fee_payer.into(),
+            authority.into(),
+            tape_address,
+            new_authority.into(),
+        );
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &propose_accounts(fee_payer, authority, tape_address, &tape),
+            &[
+                Check::success(),
+                Check::account(&Pubkey::from(tape_address))
+                    .data(tape.pack().as_ref())
+                    .build(),
+                Check::account(&Pubkey::from(transfer_address))
+                    .data(offer(tape_address, authority, new_authority).pack().as_ref())
+                    .build(),
+            ],
+        );
+    }
+
+    // proposing the default address withdraws the open offer
+    #[test]
+    fn propose_default_withdraws() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let proposed = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (transfer_address, _) = tape_transfer_pda(tape_address);
+        let tape = user_tape(authority);
+
+        let instruction = build_propose_tape_transfer_ix(
+            fee_payer.into(),
+            authority.into(),
+            tape_address,
+            Address::default(),
+        );
+
+        let mut accounts = propose_accounts(fee_payer, authority, tape_address, &tape);
+        accounts[3] = pda(
+            transfer_address,
+            offer(tape_address, authority, proposed).pack(),
+            tapedrive::ID,
+        );
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &accounts,
+            &[
+                Check::success(),
+                Check::account(&Pubkey::from(transfer_address)).closed().build(),
+            ],
+        );
+    }
+
+    // only the current authority may propose
+    #[test]
+    fn propose_rejects_non_authority() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let tape = user_tape(authority);
+
+        let instruction = build_propose_tape_transfer_ix(
+            fee_payer.into(),
+            stranger.into(),
+            tape_address,
+            stranger.into(),
+        );
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &propose_accounts(fee_payer, stranger, tape_address, &tape),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn accept_tape_transfer() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let (transfer_address, _) = tape_transfer_pda(tape_address);
+        let tape = Tape {
+            delegate: delegate.into(),
+            ..user_tape(authority)
+        };
+        let transfer = offer(tape_address, authority, new_authority);
+
+        let instruction = build_accept_tape_transfer_ix(
+            fee_payer.into(),
+            new_authority.into(),
+            tape_address,
+        );
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &accept_accounts(fee_payer, new_authority, tape_address, &tape, &transfer),
+            &[
+                Check::success(),
+                Check::account(&Pubkey::from(tape_address))
+                    .data(Tape {
+                        authority: new_authority.into(),
+                        delegate: Address::default(),
+                        flags: TapeFlags::TRANSFERRED,
+                        ..tape
+                    }.pack().as_ref())
+                    .build(),
+                Check::account(&Pubkey::from(transfer_address)).closed().build(),
+            ],
+        );
+    }
+
+    // a second transfer keeps the address bound to the first authority
+    #[test]
+    fn accept_repeated_transfer() {
+        let fee_payer = Pubkey::new_unique();
+        let original = Pubkey::new_unique();
+        let current = Pubkey::new_unique();
+        let next = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(original.into());
+        let tape = Tape {
+            flags: TapeFlags::TRANSFERRED,
+            ..user_tape(current)
+        };
+        let transfer = offer(tape_address, current, next);
+
+        let instruction = build_accept_tape_transfer_ix(
+            fee_payer.into(),
+            next.into(),
+            tape_address,
+        );
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &accept_accounts(fee_payer, next, tape_address, &tape, &transfer),
+            &[
+                Check::success(),
+                Check::account(&Pubkey::from(tape_address))
+                    .data(Tape {
+                        authority: next.into(),
+                        ..tape
+                    }.pack().as_ref())
+                    .build(),
+            ],
+        );
+    }
+
+    // accepting needs a matching proposal
+    #[test]
+    fn accept_rejects_unproposed_authority() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let proposed = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let tape = user_tape(authority);
+        let transfer = offer(tape_address, authority, proposed);
+
+        let instruction = build_accept_tape_transfer_ix(
+            fee_payer.into(),
+            stranger.into(),
+            tape_address,
+        );
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &accept_accounts(fee_payer, stranger, tape_address, &tape, &transfer),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    // an offer made by an earlier owner lapses with their ownership
+    #[test]
+    fn accept_rejects_stale_offer() {
+        let fee_payer = Pubkey::new_unique();
+        let original = Pubkey::new_unique();
+        let current = Pubkey::new_unique();
+        let proposed = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(original.into());
+        let tape = Tape {
+            flags: TapeFlags::TRANSFERRED,
+            ..user_tape(current)
+        };
+        let transfer = offer(tape_address, original, proposed);
+
+        let instruction = build_accept_tape_transfer_ix(
+            fee_payer.into(),
+            proposed.into(),
+            tape_address,
+        );
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &accept_accounts(fee_payer, proposed, tape_address, &tape, &transfer),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use tape_api::event::{DeleteMarkerPlaced, DeleteMarkerRemoved, TapeVersioningSet};
+use tape_api::program::prelude::*;
+use tape_core::tape::TapeVersioning;
+use tape_crypto::hash::hash;
+
+use crate::tape::helpers::{
+    authorize_tape_operator,
+    verified_tape_address,
+};
+
+/// Enable or suspend object versioning on a tape. Nodes replay the event into
+/// their object indexes; the tape account itself is unchanged.
+pub fn process_set_tape_versioning(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let args = SetTapeVersioning::try_from_bytes(data)?;
+    TapeVersioning::try_from(args.status)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let tape = operator_tape(accounts)?;
+
+    TapeVersioningSet {
+        tape,
+        status: args.status,
+    }
+    .log();
+
+    Ok(())
+}
+
+/// Hide a name in a versioned tape behind a delete marker.
+pub fn process_place_delete_marker(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let (_, name) = parse_place_delete_marker(data)?;
+    let tape = operator_tape(accounts)?;
+
+    DeleteMarkerPlaced {
+        tape,
+        key: hash(name),
+    }
+    .log();
+
+    Ok(())
+}
+
+/// Remove one delete marker version from a name's history.
+pub fn process_remove_delete_marker(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let (args, name) = parse_remove_delete_marker(data)?;
+    if args.marker() == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let tape = operator_tape(accounts)?;
+
+    DeleteMarkerRemoved {
+        tape,
+        key: hash(name),
+        track_number: args.track_number(),
+        marker: args.marker() as u64,
+    }
+    .log();
+
+    Ok(())
+}
+
+fn operator_tape(accounts: &[AccountInfo<'_>]) -> Result<Address, ProgramError> {
+    let [
+        fee_payer_info,
+        signer_info,
+        tape_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    fee_payer_info
+        .is_signer()?
+        .is_writable()?;
+
+    signer_info
+        .is_signer()?;
+
+    let tape = tape_info
+        .as_account::<Tape>(&tapedrive::ID)?;
+
+    if tape.is_system() {
+        return Err(TapeError::UnexpectedState.into());
+    }
+
+    let tape_address = verified_tape_address(tape_info, tape)?;
+    authorize_tape_operator(tape, (*signer_info.key).into())?;
+
+    Ok(tape_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tape_test::*;
+
+    fn user_tape(authority: Pubkey, delegate: Pubkey) -> Tape {
+        Tape {
+            authority: authority.into(),
+            delegate: delegate.into(),
+            capacity: StorageUnits::mb(1000),
+            ..Tape::zeroed()
+        }
+    }
+
+    fn operator_accounts(
+        fee_payer: Pubkey,
+        signer: Pubkey,
+        tape_address: Address,
+        tape: &Tape,
+    ) -> Vec<(Pubkey, solana_account::Account)> {
+        vec![
+            sol(fee_payer, 1_000_000_000),
+            sol(signer, 0),
+            pda(tape_address, tape.pack(), tapedrive::ID),
+        ]
+    }
+
+    #[test]
+    fn set_tape_versioning() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let tape = user_tape(authority, delegate);
+
+        let instruction = build_set_tape_versioning_ix(
+            fee_payer.into(),
+            delegate.into(),
+            tape_address,
+            TapeVersioning::Suspended,
+        );
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &operator_accounts(fee_payer, delegate, tape_address, &tape),
+            &[
+                Check::success(),
+                Check::account(&Pubkey::from(tape_address))
+                    .data(tape.pack().as_ref())
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn set_tape_versioning_rejects_unknown_status() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let tape = user_tape(authority, Pubkey::default());
+
+        let mut instruction = build_set_tape_versioning_ix(
+            fee_payer.into(),
+            authority.into(),
+            tape_address,
+            TapeVersioning::Enabled,
+        );
+        instruction.data = SetTapeVersioning { status: 0 }.to_bytes();
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &operator_accounts(fee_payer, authority, tape_address, &tape),
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    #[test]
+    fn place_and_remove_delete_marker() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let tape = user_tape(authority, Pubkey::default());
+        let accounts = operator_accounts(fee_payer, authority, tape_address, &tape);
+
+        let env = test_env();
+        let place = build_place_delete_marker_ix(
+            fee_payer.into(),
+            authority.into(),
+            tape_address,
+            b"photos/a.jpg",
+        )
+        .unwrap();
+        env.process_instruction(&place, &accounts, &[Check::success()]);
+
+        let remove = build_remove_delete_marker_ix(
+            fee_payer.into(),
+            authority.into(),
+            tape_address,
+            b"photos/a.jpg",
+            TrackNumber(4),
+            1,
+        )
+        .unwrap();
+        env.process_instruction(&remove, &accounts, &[Check::success()]);
+
+        // a marker sequence of zero names the object version itself
+        let object = build_remove_delete_marker_ix(
+            fee_payer.into(),
+            authority.into(),
+            tape_address,
+            b"photos/a.jpg",
+            TrackNumber(4),
+            0,
+        )
+        .unwrap();
+        env.process_instruction(
+            &object,
+            &accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    // only the tape authority or its delegate may place markers
+    #[test]
+    fn place_delete_marker_rejects_stranger() {
+        let fee_payer = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let (tape_address, _) = tape_pda(authority.into());
+        let tape = user_tape(authority, Pubkey::default());
+
+        let instruction = build_place_delete_marker_ix(
+            fee_payer.into(),
+            stranger.into(),
+            tape_address,
+            b"k",
+        )
+        .unwrap();
+
+        let env = test_env();
+        env.process_instruction(
+            &instruction,
+            &operator_accounts(fee_payer, stranger, tape_address, &tape),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+}
@@ -121,6 +121,7 @@ mod tests {
                     name,
                     content_type: ContentType::ImageJpeg,
                     logical_size: storage_units,
+                    headers: Vec::new(),
                 }),
                 data: BlobData::Coded(blob),
             },
@@ -4,6 +4,8 @@ use tape_core::cert::{NodeEvictMessage, eviction_vote_hash};
 use tape_core::system::apply_member_remove_slice;
 use tape_crypto::bls12254::min_sig::*;
 
+use crate::pool::helpers::slash_pool;
+
 pub fn process_vote_eviction(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     let args = VoteEviction::try_from_bytes(data)?;
     let [
@@ -15,6 +17,10 @@ pub fn process_vote_eviction(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progr
         vote_info,
         node_info,
         committee_info,
+        archive_info,
+        archive_ata_info,
+        mint_info,
+        token_program_info,
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -125,6 +131,20 @@ pub fn process_vote_eviction(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progr
             .as_account_mut::<Node>(&tapedrive::ID)?;
         node.suspended_until = target_epoch_id;
 
+        // The pool backing the node pays for the eviction. A pool already
+        // slashed this epoch is not cut twice, and the eviction still lands.
+        slash_pool(
+            node,
+            args.node,
+            voting_epoch_id,
+            SlashReason::Eviction,
+            Hash::zeroed(),
+            archive_info,
+            archive_ata_info,
+            mint_info,
+            token_program_info,
+        )?;
+
         // Remove the member from the next committee if it is seated. A missing
         // member is not an error (pre-emptive eviction). The peer set is left
         // untouched so next-epoch owners can still sync the node's spools.
@@ -184,6 +204,8 @@ mod tests {
         sign_nonce: Hash,
         vote_bitmap: Vec<u8>,
         seated: Address,
+        pool_stake: u64,
+        pool_rewards: u64,
     }
 
     impl Fixture {
@@ -210,6 +232,8 @@ mod tests {
                 sign_nonce: nonce,
                 vote_bitmap: vec![0u8; bytes_for_members(total_groups as usize)],
                 seated: node,
+                pool_stake: 0,
+                pool_rewards: 0,
             }
         }
 
@@ -270,8 +294,17 @@ mod tests {
             };
             let node_account = Node {
                 authority: self.node,
+                pool: StakingPool {
+                    stake: TAPE(self.pool_stake),
+                    rewards: TAPE(self.pool_rewards),
+                    ..StakingPool::zeroed()
+                },
                 ..Node::zeroed()
             };
+            let archive = Archive {
+                slash_bps: DEFAULT_SLASH_BPS,
+                ..Archive::zeroed()
+            };
 
             let signed_indices: Vec<usize> = (0..self.signers).collect();
             let bitmap = SpoolBitmap::from_indices(&signed_indices);
@@ -304,6 +337,10 @@ mod tests {
                     self.committee().pack_with(&self.members()),
                     tapedrive::ID,
                 ),
+                pda(archive_pda().0, archive.pack(), tapedrive::ID),
+                token(archive_ata().0, archive_pda().0, self.pool_rewards),
+                mint(MAX_SUPPLY),
+                token_program(),
             ];
 
             test_env().process_instruction(&instruction, &accounts, checks);
@@ -340,6 +377,40 @@ mod tests {
         ]);
     }
 
+    // Landing cuts the node's pool: 5% of 10_000 stake, burned from the
+    // archive and covered in full by the pool's 2_000 rewards.
+    #[test]
+    fn vote_eviction_slashes_pool() {
+        let mut fixture = Fixture::new();
+        fixture.pool_stake = 10_000;
+        fixture.pool_rewards = 2_000;
+
+        let expected_node = Node {
+            authority: fixture.node,
+            suspended_until: TARGET_EPOCH,
+            pool: StakingPool {
+                stake: TAPE(9_500),
+                rewards: TAPE(1_500),
+                ..StakingPool::zeroed()
+            },
+            slashing: SlashState {
+                slashable_epoch: TARGET_EPOCH,
+                fronted: TAPE(0),
+            },
+            ..Node::zeroed()
+        };
+
+        fixture.run(&[
+            Check::success(),
+            Check::account(&Pubkey::from(fixture.node))
+                .data(expected_node.pack().as_ref())
+                .build(),
+            Check::account(&Pubkey::from(archive_ata().0))
+                .data(token(archive_ata().0, archive_pda().0, 1_500).1.data.as_ref())
+                .build(),
+        ]);
+    }
+
     // Evict a node that is not seated in the next committee: the removal is a
     // no-op but the suspension still lands, blocking a later join.
     #[test]
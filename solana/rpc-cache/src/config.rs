@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{Context, Result, anyhow};
@@ -57,6 +57,13 @@ pub struct Config {
     #[serde(default = "default_slot_store_max_bytes")]
     pub slot_store_max_bytes: u64,
 
+    /// Directory for the durable block store. Every filtered block and
+    /// skip tombstone is also written here, so a restart skips slots it
+    /// already holds and serves them without asking upstream. Unset keeps
+    /// blocks in memory only.
+    #[serde(default)]
+    pub block_store_path: Option<PathBuf>,
+
     /// How many whole epochs of history to fill below the current epoch at
     /// bootstrap. 0 = current epoch only (from its start slot); 1 = also the
     /// previous epoch, etc. Widening this lets a reader that bootstraps from
@@ -178,6 +185,7 @@ api_key: "deadbeef"
         assert_eq!(config.min_429_delay, Duration::from_secs(10));
         assert!(config.log_submits);
         assert_eq!(config.api_key, "deadbeef");
+        assert!(config.block_store_path.is_none());
     }
 
     #[test]
@@ -0,0 +1,132 @@
+//! Durable slot store. Filtered blocks and skip tombstones are written to a
+//! local `store::Store` next to the in-memory slot store, so a restart
+//! serves what it already fetched instead of re-warming from upstream.
+//!
+//! Keys are big-endian slots, so store order is slot order and the oldest
+//! and newest persisted slots are one seek each.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use store::{Direction, Store};
+use store_rocks::RocksStore;
+
+use crate::server::CachedBlock;
+
+/// Column family holding one entry per persisted slot.
+pub const BLOCKS_CF: &str = "blocks";
+
+const TAG_SKIPPED: u8 = 0;
+const TAG_PRESENT: u8 = 1;
+
+pub struct DiskBlocks {
+    store: Arc<dyn Store>,
+}
+
+impl DiskBlocks {
+    /// Open (or create) a RocksDB block store under `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let rocks = RocksStore::open(path, &[BLOCKS_CF])
+            .with_context(|| format!("opening block store {}", path.display()))?;
+        Ok(Self::new(Arc::new(rocks)))
+    }
+
+    pub fn new(store: Arc<dyn Store>) -> Self {
+        Self { store }
+    }
+
+    pub fn get(&self, slot: u64) -> Result<Option<CachedBlock>> {
+        let Some(value) = self.store.get(BLOCKS_CF, &slot.to_be_bytes())? else {
+            return Ok(None);
+        };
+        Ok(decode(&value))
+    }
+
+    pub fn contains(&self, slot: u64) -> Result<bool> {
+        Ok(self.store.contains(BLOCKS_CF, &slot.to_be_bytes())?)
+    }
+
+    pub fn put(&self, slot: u64, block: &CachedBlock) -> Result<()> {
+        self.store.put(BLOCKS_CF, &slot.to_be_bytes(), &encode(block))?;
+        Ok(())
+    }
+
+    /// Lowest persisted slot, if any.
+    pub fn oldest_slot(&self) -> Result<Option<u64>> {
+        self.edge_slot(&0u64.to_be_bytes(), Direction::Asc)
+    }
+
+    /// Highest persisted slot, if any.
+    pub fn newest_slot(&self) -> Result<Option<u64>> {
+        self.edge_slot(&u64::MAX.to_be_bytes(), Direction::Desc)
+    }
+
+    fn edge_slot(&self, start: &[u8], direction: Direction) -> Result<Option<u64>> {
+        let mut entries = self.store.iter_from(BLOCKS_CF, start, direction)?;
+        Ok(entries.next().and_then(|(key, _)| slot_from_key(&key)))
+    }
+}
+
+fn encode(block: &CachedBlock) -> Vec<u8> {
+    match block {
+        CachedBlock::Skipped => vec![TAG_SKIPPED],
+        CachedBlock::Present(body) => {
+            let mut value = Vec::with_capacity(1 + body.len());
+            value.push(TAG_PRESENT);
+            value.extend_from_slice(body);
+            value
+        }
+    }
+}
+
+/// Decode a stored entry. Unknown tags read as missing so the slot is
+/// fetched again rather than served wrong.
+fn decode(value: &[u8]) -> Option<CachedBlock> {
+    match value.split_first()? {
+        (&TAG_SKIPPED, _) => Some(CachedBlock::Skipped),
+        (&TAG_PRESENT, body) => Some(CachedBlock::Present(Bytes::copy_from_slice(body))),
+        _ => None,
+    }
+}
+
+fn slot_from_key(key: &[u8]) -> Option<u64> {
+    Some(u64::from_be_bytes(key.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store_memory::MemoryStore;
+
+    fn disk() -> DiskBlocks {
+        DiskBlocks::new(Arc::new(MemoryStore::new()))
+    }
+
+    #[test]
+    fn round_trips_present_and_skipped() {
+        let disk = disk();
+        let body = Bytes::from_static(br#"{"blockhash":"abc"}"#);
+        disk.put(10, &CachedBlock::Present(body.clone())).unwrap();
+        disk.put(11, &CachedBlock::Skipped).unwrap();
+
+        assert!(matches!(disk.get(10).unwrap(), Some(CachedBlock::Present(b)) if b == body));
+        assert!(matches!(disk.get(11).unwrap(), Some(CachedBlock::Skipped)));
+        assert!(disk.get(12).unwrap().is_none());
+        assert!(disk.contains(11).unwrap());
+    }
+
+    #[test]
+    fn edges_follow_slot_order() {
+        let disk = disk();
+        assert_eq!(disk.oldest_slot().unwrap(), None);
+
+        // 256 sorts before 255 little-endian; big-endian keys keep slot order
+        for slot in [300, 255, 256, 7] {
+            disk.put(slot, &CachedBlock::Skipped).unwrap();
+        }
+        assert_eq!(disk.oldest_slot().unwrap(), Some(7));
+        assert_eq!(disk.newest_slot().unwrap(), Some(300));
+    }
+}
@@ -5,16 +5,19 @@
 //! a single upstream call per cache window. See [`docs/rpc-cache.md`].
 //!
 //! Caller-facing protocol is standard JSON-RPC over HTTP. Batch requests
-//! are passed through uncached (v1 simplification).
+//! are split, each element served as if it arrived alone, and the replies
+//! reassembled in order.
 //!
 //! `getBlock` takes a separate fast path: a slot-keyed in-memory store
 //! pre-warmed at boot with confirmed blocks from the current tape epoch
 //! start to the live edge, filtered down to tape-relevant data only.
-//! See [`runtime`] for the bootstrap/live-tail tasks and [`filter`] for
-//! the per-block reduction.
+//! With `block_store_path` set, those blocks are also persisted on disk
+//! (see [`disk`]). See [`runtime`] for the bootstrap/live-tail tasks and
+//! [`filter`] for the per-block reduction.
 
 pub mod cache;
 pub mod config;
+pub mod disk;
 pub mod filter;
 pub mod key;
 pub mod runtime;
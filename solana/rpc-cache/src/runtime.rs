@@ -24,19 +24,24 @@ use tape_core::types::EpochNumber;
 use tape_crypto::address::Address;
 use tokio::net::TcpListener;
 use tokio::task::JoinSet;
+use thiserror::Error;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 use crate::cache::{CacheStore, Policy};
 use crate::config::Config;
+use crate::disk::DiskBlocks;
 use crate::filter::filter_block;
 use crate::server::{AppState, CacheStats, CachedBlock};
 use crate::upstream::{Upstream, UpstreamError};
 
-/// Solana JSON-RPC error code returned for `getBlock` on a slot that
-/// was either skipped or has aged out of long-term storage. Both are
-/// permanent — cache as a tombstone and stop retrying.
+/// Solana JSON-RPC error code returned for `getBlock` on a skipped slot.
+/// Permanent once the slot is finalized — cache as a tombstone and stop
+/// retrying.
 const SKIPPED_SLOT_CODE: i64 = -32007;
+/// Returned for a slot that was skipped *or* is missing from upstream's
+/// long-term storage. The latter can be a transient gap, so it is served
+/// but never cached.
 const SKIPPED_OR_LTS_CODE: i64 = -32009;
 
 const BOOTSTRAP_CONCURRENCY: usize = 16;
@@ -57,14 +62,27 @@ const BLOCK_FETCH_CONFIG: RpcBlockConfig = RpcBlockConfig {
     max_supported_transaction_version: Some(0),
 };
 
-/// Status of a single upstream `getBlock` call.
+/// Status of a single upstream `getBlock` call. Skips carry upstream's
+/// error object so an unsettled one can be replayed verbatim.
 enum BlockOutcome {
     Present(UiConfirmedBlock),
-    Skipped,
+    Skipped(Value),
+    SkippedOrMissing(Value),
     Unavailable(String),
     Retriable(String),
 }
 
+/// Why a slot could not be settled into the slot store.
+#[derive(Debug, Error)]
+pub enum SlotFetchError {
+    /// Upstream reported the slot skipped or missing, but the skip is not
+    /// confirmed against a finalized slot. Served as-is, never cached.
+    #[error("unsettled skip: {0}")]
+    UnsettledSkip(Value),
+    #[error("{0}")]
+    Failed(String),
+}
+
 pub async fn run_application(config: Config) -> Result<()> {
     let state = build_state(&config)?;
     let cancel = CancellationToken::new();
@@ -164,6 +182,10 @@ fn build_state(config: &Config) -> Result<Arc<AppState>> {
     );
     let slot_store = build_slot_store(config.slot_store_max_bytes);
     let program_ids = parse_program_ids(&config.filter_program_ids)?;
+    let disk = config.block_store_path.as_deref().map(DiskBlocks::open).transpose()?;
+    if let Some(newest) = disk.as_ref().map(DiskBlocks::newest_slot).transpose()?.flatten() {
+        info!(newest, "block store opened");
+    }
     let stats = CacheStats::new();
 
     Ok(Arc::new(AppState {
@@ -173,6 +195,7 @@ fn build_state(config: &Config) -> Result<Arc<AppState>> {
         log_submits: config.log_submits,
         api_key: config.api_key.clone(),
         slot_store,
+        disk,
         program_ids,
         stats,
     }))
@@ -220,11 +243,12 @@ async fn bootstrap(
         .await;
     state.stats.bootstrap_fill_start_slot.store(fill_start, Ordering::Relaxed);
 
-    let live = fetch_live_slot(&state.upstream)
+    let live = fetch_slot(&state.upstream, "confirmed")
         .await
         .context("fetching live slot during bootstrap")?;
     state.stats.bootstrap_target_slot.store(live, Ordering::Relaxed);
     state.stats.last_observed_live_slot.store(live, Ordering::Relaxed);
+    refresh_finalized_slot(&state).await;
 
     info!(
         epoch_start_slot = epoch_start,
@@ -319,7 +343,7 @@ async fn live_tail(state: Arc<AppState>, cancel: CancellationToken) -> Result<()
             _ = tokio::time::sleep(LIVE_TAIL_POLL) => {}
         }
 
-        let live = match fetch_live_slot(&state.upstream).await {
+        let live = match fetch_slot(&state.upstream, "confirmed").await {
             Ok(s) => s,
             Err(e) => {
                 warn!(error = %e, "live tail: getSlot failed, will retry");
@@ -327,6 +351,7 @@ async fn live_tail(state: Arc<AppState>, cancel: CancellationToken) -> Result<()
             }
         };
         state.stats.last_observed_live_slot.store(live, Ordering::Relaxed);
+        refresh_finalized_slot(&state).await;
 
         let newest = state.stats.newest_cached_slot.load(Ordering::Relaxed);
         if live <= newest {
@@ -400,35 +425,60 @@ fn log_range_progress(slot: u64, done: u64, total: u64, started: Instant) {
 }
 
 async fn fetch_and_store(state: &AppState, slot: u64) {
+    // Already on disk from an earlier run; served from there on demand.
+    if state.is_persisted(slot) {
+        state.stats.slots_from_disk.fetch_add(1, Ordering::Relaxed);
+        state.stats.newest_cached_slot.fetch_max(slot, Ordering::Relaxed);
+        return;
+    }
+
     match fetch_slot_block(state, slot).await {
         Ok(cached) => {
-            state.slot_store.insert(slot, cached).await;
+            state.store_block(slot, cached).await;
             state.stats.newest_cached_slot.fetch_max(slot, Ordering::Relaxed);
         }
-        Err(msg) => {
-            warn!(slot, error = %msg, "block fetch failed; leaving slot uncached");
+        Err(SlotFetchError::UnsettledSkip(_)) => {
+            debug!(slot, "skip not settled against a finalized slot; leaving slot uncached");
+        }
+        Err(error) => {
+            warn!(slot, %error, "block fetch failed; leaving slot uncached");
         }
     }
 }
 
-pub async fn fetch_slot_block(state: &AppState, slot: u64) -> Result<CachedBlock, String> {
+/// Fetch one slot for the slot store. Only present blocks and skips at or
+/// below the finalized slot come back `Ok`; everything else is left for a
+/// later read to fetch again.
+pub async fn fetch_slot_block(
+    state: &AppState,
+    slot: u64,
+) -> Result<CachedBlock, SlotFetchError> {
     let mut attempt: u32 = 0;
     loop {
         state.stats.upstream_calls.fetch_add(1, Ordering::Relaxed);
         match fetch_block(&state.upstream, slot).await {
             BlockOutcome::Present(block) => {
-                let cached = build_present_block(state, slot, block)?;
+                let cached = build_present_block(state, slot, block)
+                    .map_err(SlotFetchError::Failed)?;
                 state.stats.slots_fetched.fetch_add(1, Ordering::Relaxed);
                 return Ok(cached);
             }
-            BlockOutcome::Skipped => {
+            BlockOutcome::Skipped(error) => {
+                // A confirmed slot can still be skipped on one fork and land on
+                // another; only a finalized skip is permanent.
+                if !is_finalized(state, slot).await {
+                    return Err(SlotFetchError::UnsettledSkip(error));
+                }
                 state.stats.slots_skipped.fetch_add(1, Ordering::Relaxed);
                 return Ok(CachedBlock::Skipped);
             }
+            BlockOutcome::SkippedOrMissing(error) => {
+                return Err(SlotFetchError::UnsettledSkip(error));
+            }
             BlockOutcome::Unavailable(msg) => {
                 // Permanently gone from this upstream but not a skip. Retrying the
                 // same endpoint won't help.
-                return Err(msg);
+                return Err(SlotFetchError::Failed(msg));
             }
             BlockOutcome::Retriable(msg) if attempt < MAX_BLOCK_FETCH_ATTEMPTS => {
                 let delay = retry_delay(attempt);
@@ -437,12 +487,33 @@ pub async fn fetch_slot_block(state: &AppState, slot: u64) -> Result<CachedBlock
                 attempt += 1;
             }
             BlockOutcome::Retriable(msg) => {
-                return Err(msg);
+                return Err(SlotFetchError::Failed(msg));
             }
         }
     }
 }
 
+/// Whether `slot` is at or below the finalized slot, asking upstream again
+/// when the last known finalized slot is behind it.
+async fn is_finalized(state: &AppState, slot: u64) -> bool {
+    slot <= state.stats.last_finalized_slot.load(Ordering::Relaxed)
+        || slot <= refresh_finalized_slot(state).await
+}
+
+/// Pull upstream's finalized slot into the stats and return the newest one
+/// known. A failed lookup keeps the previous value.
+async fn refresh_finalized_slot(state: &AppState) -> u64 {
+    match fetch_slot(&state.upstream, "finalized").await {
+        Ok(finalized) => {
+            state.stats.last_finalized_slot.fetch_max(finalized, Ordering::Relaxed);
+        }
+        Err(error) => {
+            debug!(%error, "getSlot finalized failed; keeping last finalized slot");
+        }
+    }
+    state.stats.last_finalized_slot.load(Ordering::Relaxed)
+}
+
 fn build_present_block(
     state: &AppState,
     slot: u64,
@@ -491,8 +562,10 @@ async fn fetch_block(upstream: &Upstream, slot: u64) -> BlockOutcome {
 
 fn classify_block_error(err: &Value) -> BlockOutcome {
     let code = err.get("code").and_then(Value::as_i64);
-    if matches!(code, Some(SKIPPED_SLOT_CODE) | Some(SKIPPED_OR_LTS_CODE)) {
-        return BlockOutcome::Skipped;
+    match code {
+        Some(SKIPPED_SLOT_CODE) => return BlockOutcome::Skipped(err.clone()),
+        Some(SKIPPED_OR_LTS_CODE) => return BlockOutcome::SkippedOrMissing(err.clone()),
+        _ => {}
     }
     let msg = err
         .get("message")
@@ -506,9 +579,10 @@ fn classify_block_error(err: &Value) -> BlockOutcome {
             msg
         ));
     }
-    // A genuine skip reported only by message (non-standard or missing code).
+    // A skip reported only by message (non-standard or missing code) can't
+    // be told apart from a storage gap, so it is never settled.
     if lower.contains("skipped") {
-        return BlockOutcome::Skipped;
+        return BlockOutcome::SkippedOrMissing(err.clone());
     }
     BlockOutcome::Retriable(format!(
         "code={} message={}",
@@ -521,12 +595,12 @@ fn upstream_to_outcome(e: &UpstreamError) -> BlockOutcome {
     BlockOutcome::Retriable(e.to_string())
 }
 
-async fn fetch_live_slot(upstream: &Upstream) -> Result<u64> {
+async fn fetch_slot(upstream: &Upstream, commitment: &str) -> Result<u64> {
     let body = json!({
         "jsonrpc": "2.0",
         "id": 1,
         "method": "getSlot",
-        "params": [{"commitment": "confirmed"}],
+        "params": [{"commitment": commitment}],
     });
     let env = upstream
         .forward(&body)
@@ -641,16 +715,26 @@ mod tests {
     #[test]
     fn classify_block_error_skipped_by_code() {
         let err = json!({"code": SKIPPED_SLOT_CODE, "message": "anything"});
-        assert!(matches!(classify_block_error(&err), BlockOutcome::Skipped));
+        assert!(matches!(classify_block_error(&err), BlockOutcome::Skipped(_)));
+    }
 
+    // -32009 may be a long-term storage gap rather than a skip
+    #[test]
+    fn classify_block_error_lts_gap_is_unsettled() {
         let err = json!({"code": SKIPPED_OR_LTS_CODE, "message": "anything"});
-        assert!(matches!(classify_block_error(&err), BlockOutcome::Skipped));
+        assert!(matches!(
+            classify_block_error(&err),
+            BlockOutcome::SkippedOrMissing(_)
+        ));
     }
 
     #[test]
     fn classify_block_error_skipped_by_message_text() {
         let err = json!({"code": -1, "message": "Slot 42 was Skipped or missing"});
-        assert!(matches!(classify_block_error(&err), BlockOutcome::Skipped));
+        assert!(matches!(
+            classify_block_error(&err),
+            BlockOutcome::SkippedOrMissing(_)
+        ));
     }
 
     #[test]
@@ -1,21 +1,25 @@
 //! Axum server glue. Two distinct request paths:
 //!
 //! - `getBlock` is served from the in-memory slot store (filled by
-//!   bootstrap + live tail). On miss, falls through to upstream without
-//!   inserting — the live tail owns slot-store writes.
+//!   bootstrap + live tail), backed by the durable block store when one
+//!   is configured. Confirmed misses are fetched once and fill both.
 //! - Everything else uses the original moka-based read-through cache
 //!   (per-method TTLs from `cache::Policy`). Submit methods are logged
 //!   and forwarded uncached.
 //!
-//! Batch requests (top-level JSON array) pass through unchanged.
+//! Batch requests (top-level JSON array) are split. `getBlock` elements take
+//! the slot-store path and cached reads are answered locally, so batched
+//! reads share the cache with single ones; whatever is left goes upstream
+//! as one batch. Replies are reassembled in request order.
 //!
 //! Two unauthed observability routes: `GET /v1/health` and
 //! `GET /v1/stats`. Everything JSON-RPC requires `?api=<key>`.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use axum::{
     Router,
@@ -25,6 +29,7 @@ use axum::{
     routing::{get, post},
 };
 use bytes::Bytes;
+use futures::stream::{self, StreamExt};
 use moka::future::Cache as MokaCache;
 use serde::Deserialize;
 use serde_json::{Value, json};
@@ -32,9 +37,11 @@ use tape_crypto::address::Address;
 use tracing::{info, warn};
 
 use crate::cache::{CacheStore, MethodKind, Policy};
+use crate::disk::DiskBlocks;
 use crate::key::CacheKey;
+use crate::runtime::SlotFetchError;
 use crate::submit_log;
-use crate::upstream::{Upstream, UpstreamError};
+use crate::upstream::{RpcEnvelope, Upstream, UpstreamError};
 
 /// Solana JSON-RPC error code for skipped or missing slots. Returned
 /// verbatim from upstream RPCs; we serve the same code from cached
@@ -42,13 +49,23 @@ use crate::upstream::{Upstream, UpstreamError};
 /// one.
 const SKIPPED_SLOT_ERROR_CODE: i32 = -32007;
 
+/// JSON-RPC error code for a batch element that is not a request object.
+const INVALID_REQUEST_ERROR_CODE: i32 = -32600;
+
+/// Most requests sent upstream in one batch. Larger client batches are cut
+/// into several; providers commonly reject batches much past this.
+const UPSTREAM_BATCH_MAX: usize = 100;
+
+/// Batch elements served locally, and upstream batches, in flight at once.
+const BATCH_CONCURRENCY: usize = 8;
+
 #[derive(Clone)]
 pub enum CachedBlock {
     /// Pre-serialized JSON-RPC `result` body for a confirmed,
     /// filtered block. Wrapped in an envelope at serve time.
     Present(Bytes),
-    /// The slot was skipped or never produced. Replays the standard
-    /// upstream error envelope.
+    /// The slot was skipped at or below a finalized slot. Replays the
+    /// standard upstream error envelope.
     Skipped,
 }
 
@@ -59,8 +76,13 @@ pub struct CacheStats {
     pub epoch_start_slot: AtomicU64,
     pub newest_cached_slot: AtomicU64,
     pub last_observed_live_slot: AtomicU64,
+    /// Newest finalized slot seen upstream. Skips at or below it are
+    /// permanent and safe to persist.
+    pub last_finalized_slot: AtomicU64,
     pub slot_store_hits: AtomicU64,
     pub slot_store_misses: AtomicU64,
+    pub block_store_hits: AtomicU64,
+    pub slots_from_disk: AtomicU64,
     pub slots_fetched: AtomicU64,
     pub slots_skipped: AtomicU64,
     pub upstream_calls: AtomicU64,
@@ -76,8 +98,11 @@ impl CacheStats {
             epoch_start_slot: AtomicU64::new(0),
             newest_cached_slot: AtomicU64::new(0),
             last_observed_live_slot: AtomicU64::new(0),
+            last_finalized_slot: AtomicU64::new(0),
             slot_store_hits: AtomicU64::new(0),
             slot_store_misses: AtomicU64::new(0),
+            block_store_hits: AtomicU64::new(0),
+            slots_from_disk: AtomicU64::new(0),
             slots_fetched: AtomicU64::new(0),
             slots_skipped: AtomicU64::new(0),
             upstream_calls: AtomicU64::new(0),
@@ -123,10 +148,92 @@ pub struct AppState {
     /// require this.
     pub api_key: String,
     pub slot_store: MokaCache<u64, CachedBlock>,
+    /// Durable copy of the slot store, when `block_store_path` is set.
+    pub disk: Option<DiskBlocks>,
     pub program_ids: Vec<Address>,
     pub stats: CacheStats,
 }
 
+impl AppState {
+    /// Look a slot up in memory, then on disk. Disk hits are promoted into
+    /// memory so the next read stays off the disk.
+    pub async fn cached_block(&self, slot: u64) -> Option<CachedBlock> {
+        if let Some(cached) = self.slot_store.get(&slot).await {
+            return Some(cached);
+        }
+        let cached = self.read_persisted(slot)?;
+        self.stats.block_store_hits.fetch_add(1, Ordering::Relaxed);
+        self.slot_store.insert(slot, cached.clone()).await;
+        Some(cached)
+    }
+
+    /// Keep a fetched slot in memory and, when configured, on disk.
+    pub async fn store_block(&self, slot: u64, block: CachedBlock) {
+        self.persist_block(slot, &block);
+        self.slot_store.insert(slot, block).await;
+    }
+
+    /// Write a slot to the block store. A failed write is logged and the
+    /// slot is still served from memory.
+    pub fn persist_block(&self, slot: u64, block: &CachedBlock) {
+        let Some(disk) = &self.disk else {
+            return;
+        };
+        if let Err(error) = disk.put(slot, block) {
+            warn!(slot, %error, "block store write failed");
+        }
+    }
+
+    /// Whether the block store already holds `slot`.
+    pub fn is_persisted(&self, slot: u64) -> bool {
+        self.disk
+            .as_ref()
+            .is_some_and(|disk| disk.contains(slot).unwrap_or(false))
+    }
+
+    /// Oldest slot in the block store, if there is one.
+    pub fn oldest_persisted_slot(&self) -> Option<u64> {
+        self.disk.as_ref()?.oldest_slot().ok().flatten()
+    }
+
+    fn read_persisted(&self, slot: u64) -> Option<CachedBlock> {
+        match self.disk.as_ref()?.get(slot) {
+            Ok(cached) => cached,
+            Err(error) => {
+                warn!(slot, %error, "block store read failed");
+                None
+            }
+        }
+    }
+}
+
+/// One serialized JSON-RPC reply. Kept as bytes so batch replies can be
+/// stitched together without re-parsing cached block bodies.
+struct Reply {
+    status: StatusCode,
+    body: Vec<u8>,
+}
+
+impl Reply {
+    fn json(status: StatusCode, value: &Value) -> Self {
+        Self {
+            status,
+            body: serde_json::to_vec(value).unwrap_or_default(),
+        }
+    }
+}
+
+impl IntoResponse for Reply {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            [(header::CONTENT_TYPE, "application/json")],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
 #[derive(Deserialize, Default)]
 struct AuthQuery {
     #[serde(default)]
@@ -180,11 +287,14 @@ async fn stats(State(state): State<Arc<AppState>>) -> impl IntoResponse {
         "bootstrap_eta_secs": eta_secs,
         "newest_cached_slot": newest,
         "last_observed_live_slot": live,
+        "last_finalized_slot": s.last_finalized_slot.load(Ordering::Relaxed),
         "lag_from_live_slot": lag,
         "slot_store_entries": entries,
         "slot_store_approximate_bytes": bytes,
         "slot_store_hits": s.slot_store_hits.load(Ordering::Relaxed),
         "slot_store_misses": s.slot_store_misses.load(Ordering::Relaxed),
+        "block_store_hits": s.block_store_hits.load(Ordering::Relaxed),
+        "slots_from_disk": s.slots_from_disk.load(Ordering::Relaxed),
         "slots_fetched": s.slots_fetched.load(Ordering::Relaxed),
         "slots_skipped": s.slots_skipped.load(Ordering::Relaxed),
         "upstream_calls": s.upstream_calls.load(Ordering::Relaxed),
@@ -210,22 +320,184 @@ async fn handle(
             .into_response();
     };
 
-    if req.is_array() {
-        return forward_raw(&state, &req).await;
+    let caller = addr.ip().to_string();
+    if let Some(items) = req.as_array() {
+        return handle_batch(&state, &caller, items).await.into_response();
     }
 
-    let Some(obj) = req.as_object() else {
+    if !req.is_object() {
         return (
             StatusCode::BAD_REQUEST,
             "expected JSON-RPC object".to_string(),
         )
             .into_response();
+    }
+
+    handle_single(&state, &caller, &req).await.into_response()
+}
+
+/// Where one batch element is answered from.
+enum BatchRoute {
+    /// Answered already: a cache hit or a malformed element.
+    Done(Reply),
+    /// Served through the single-request path (the slot store).
+    Local,
+    /// Sent upstream with the rest of the batch; reads carry the cache
+    /// entry their result fills.
+    Upstream(Option<(CacheKey, Duration)>),
+}
+
+/// Answer a batch with as few upstream calls as possible: cache hits are
+/// served in place, `getBlock` elements go through the slot store, and the
+/// remaining elements are forwarded as upstream batches. Both are bounded
+/// by [`BATCH_CONCURRENCY`]. Failures stay inside their element's envelope,
+/// so the batch itself always answers 200.
+async fn handle_batch(state: &Arc<AppState>, caller: &str, items: &[Value]) -> Reply {
+    if items.is_empty() {
+        return Reply::json(StatusCode::OK, &invalid_request());
+    }
+
+    let mut replies: Vec<Option<Reply>> = Vec::with_capacity(items.len());
+    let mut local = Vec::new();
+    let mut upstream = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        match route_batch_element(state, caller, item).await {
+            BatchRoute::Done(reply) => {
+                replies.push(Some(reply));
+                continue;
+            }
+            BatchRoute::Local => local.push(index),
+            BatchRoute::Upstream(fill) => upstream.push((index, fill)),
+        }
+        replies.push(None);
+    }
+
+    let local_replies = stream::iter(local)
+        .map(|index| async move { (index, handle_single(state, caller, &items[index]).await) })
+        .buffer_unordered(BATCH_CONCURRENCY)
+        .collect::<Vec<_>>();
+    let upstream_replies = stream::iter(upstream.chunks(UPSTREAM_BATCH_MAX))
+        .map(|chunk| forward_batch_chunk(state, items, chunk))
+        .buffer_unordered(BATCH_CONCURRENCY)
+        .flat_map(stream::iter)
+        .collect::<Vec<_>>();
+    let (local_replies, upstream_replies) =
+        futures::future::join(local_replies, upstream_replies).await;
+    for (index, reply) in local_replies.into_iter().chain(upstream_replies) {
+        replies[index] = Some(reply);
+    }
+
+    let replies = replies
+        .into_iter()
+        .map(|reply| reply.expect("every batch element answered"))
+        .collect::<Vec<_>>();
+    let len = replies.iter().map(|reply| reply.body.len() + 1).sum::<usize>() + 1;
+    let mut body = Vec::with_capacity(len);
+    body.push(b'[');
+    for (index, reply) in replies.into_iter().enumerate() {
+        if index > 0 {
+            body.push(b',');
+        }
+        body.extend_from_slice(&reply.body);
+    }
+    body.push(b']');
+
+    Reply {
+        status: StatusCode::OK,
+        body,
+    }
+}
+
+/// Decide how a batch element is answered, serving cache hits on the spot.
+async fn route_batch_element(state: &AppState, caller: &str, item: &Value) -> BatchRoute {
+    if !item.is_object() {
+        return BatchRoute::Done(Reply::json(StatusCode::OK, &invalid_request()));
+    }
+
+    let method = item.get("method").and_then(Value::as_str).unwrap_or("");
+    if method == "getBlock" || method == "getFirstAvailableBlock" {
+        return BatchRoute::Local;
+    }
+
+    let params = item.get("params").cloned().unwrap_or(Value::Null);
+    match state.policy.classify(method) {
+        MethodKind::Submit => {
+            if state.log_submits {
+                submit_log::record(caller, method, &params);
+            }
+            BatchRoute::Upstream(None)
+        }
+        MethodKind::Read { ttl } => {
+            let key = CacheKey::from_request(method, &params);
+            if let Some(cached) = state.cache.get(&key).await {
+                let id = item.get("id").cloned().unwrap_or(Value::Null);
+                return BatchRoute::Done(json_ok(id, (*cached).clone()));
+            }
+            BatchRoute::Upstream(Some((key, ttl)))
+        }
+        MethodKind::Unknown => {
+            warn!(%method, "unclassified method; passing through");
+            BatchRoute::Upstream(None)
+        }
+    }
+}
+
+/// Forward one slice of a batch upstream as a single batch call. Each
+/// element goes out with its batch index as the id, so replies are matched
+/// back regardless of upstream ordering or duplicate caller ids.
+async fn forward_batch_chunk(
+    state: &AppState,
+    items: &[Value],
+    chunk: &[(usize, Option<(CacheKey, Duration)>)],
+) -> Vec<(usize, Reply)> {
+    let requests = chunk
+        .iter()
+        .map(|(index, _)| {
+            let mut request = items[*index].clone();
+            request["id"] = json!(index);
+            request
+        })
+        .collect::<Vec<_>>();
+    let caller_id = |index: usize| items[index].get("id").cloned().unwrap_or(Value::Null);
+
+    state.stats.upstream_calls.fetch_add(1, Ordering::Relaxed);
+    let envelopes = match state.upstream.forward_batch(&requests).await {
+        Ok(envelopes) => envelopes,
+        Err(e) => {
+            return chunk
+                .iter()
+                .map(|(index, _)| (*index, upstream_err(caller_id(*index), &e)))
+                .collect();
+        }
     };
+    let mut by_index: HashMap<u64, RpcEnvelope> = envelopes
+        .into_iter()
+        .filter_map(|envelope| Some((envelope.id.as_u64()?, envelope)))
+        .collect();
+
+    let mut replies = Vec::with_capacity(chunk.len());
+    for (index, fill) in chunk {
+        let id = caller_id(*index);
+        let Some(envelope) = by_index.remove(&(*index as u64)) else {
+            let missing = UpstreamError::BadResponse("no reply for batch element".to_string());
+            replies.push((*index, upstream_err(id, &missing)));
+            continue;
+        };
+        if let (Some((key, ttl)), Some(result)) = (fill, &envelope.result) {
+            state
+                .cache
+                .insert(key.clone(), Arc::new(result.clone()), *ttl)
+                .await;
+        }
+        replies.push((*index, Reply::json(StatusCode::OK, &reshape_with_id(envelope, id))));
+    }
+    replies
+}
 
-    let method = obj.get("method").and_then(Value::as_str).unwrap_or("");
-    let params = obj.get("params").cloned().unwrap_or(Value::Null);
-    let id = obj.get("id").cloned().unwrap_or(Value::Null);
-    let caller = addr.ip().to_string();
+async fn handle_single(state: &Arc<AppState>, caller: &str, req: &Value) -> Reply {
+    let method = req.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = req.get("params").cloned().unwrap_or(Value::Null);
+    let id = req.get("id").cloned().unwrap_or(Value::Null);
 
     // Block path: separate confirmed slot-keyed store, populated by
     // bootstrap + live tail. Confirmed misses fill the same store, so
@@ -233,30 +505,36 @@ async fn handle(
     if method == "getBlock" {
         if is_confirmed_get_block(&params) {
             if let Some(slot) = parse_slot_param(&params) {
-                if let Some(cached) = state.slot_store.get(&slot).await {
+                if let Some(cached) = state.cached_block(slot).await {
                     state.stats.slot_store_hits.fetch_add(1, Ordering::Relaxed);
                     return serve_cached_block(id, cached);
                 }
                 state.stats.slot_store_misses.fetch_add(1, Ordering::Relaxed);
-                return fill_and_serve_cached_block(&state, id, slot).await;
+                return fill_and_serve_cached_block(state, id, slot).await;
             }
         } else if parse_slot_param(&params).is_some() {
             state.stats.slot_store_misses.fetch_add(1, Ordering::Relaxed);
         }
-        return forward_passthrough(&state, &req, id).await;
+        return forward_passthrough(state, req, id).await;
+    }
+
+    if method == "getFirstAvailableBlock" {
+        if let Some(oldest) = state.oldest_persisted_slot() {
+            return serve_first_available_block(state, req, id, oldest).await;
+        }
     }
 
     match state.policy.classify(method) {
         MethodKind::Submit => {
             if state.log_submits {
-                submit_log::record(&caller, method, &params);
+                submit_log::record(caller, method, &params);
             }
-            forward_raw(&state, &req).await
+            forward_raw(state, req).await
         }
         MethodKind::Read { ttl } => {
             let key = CacheKey::from_request(method, &params);
             if let Some(cached) = state.cache.get(&key).await {
-                return json_ok(id, (*cached).clone()).into_response();
+                return json_ok(id, (*cached).clone());
             }
 
             // Miss — forward and populate. moka's get_with would coalesce
@@ -265,7 +543,7 @@ async fn handle(
             // upstream call, which is cheap compared to the uncached
             // baseline. Keeps the error path straightforward.
             state.stats.upstream_calls.fetch_add(1, Ordering::Relaxed);
-            match state.upstream.forward(&req).await {
+            match state.upstream.forward(req).await {
                 Ok(envelope) => {
                     if let Some(result) = &envelope.result {
                         state
@@ -273,18 +551,14 @@ async fn handle(
                             .insert(key, Arc::new(result.clone()), ttl)
                             .await;
                     }
-                    (
-                        StatusCode::OK,
-                        axum::Json(reshape_with_id(envelope, id)),
-                    )
-                        .into_response()
+                    Reply::json(StatusCode::OK, &reshape_with_id(envelope, id))
                 }
-                Err(e) => upstream_err(id, &e).into_response(),
+                Err(e) => upstream_err(id, &e),
             }
         }
         MethodKind::Unknown => {
             warn!(%method, "unclassified method; passing through");
-            forward_raw(&state, &req).await
+            forward_raw(state, req).await
         }
     }
 }
@@ -306,7 +580,7 @@ fn get_block_commitment(params: &Value) -> Option<&str> {
     }
 }
 
-fn serve_cached_block(id: Value, cached: CachedBlock) -> Response {
+fn serve_cached_block(id: Value, cached: CachedBlock) -> Reply {
     match cached {
         CachedBlock::Present(bytes) => serve_present_block(id, bytes),
         CachedBlock::Skipped => serve_skipped_envelope(id),
@@ -317,24 +591,30 @@ async fn fill_and_serve_cached_block(
     state: &Arc<AppState>,
     id: Value,
     slot: u64,
-) -> Response {
+) -> Reply {
+    // Unsettled skips come back as errors so the slot store never keeps them.
     let fetch_state = Arc::clone(state);
     match state
         .slot_store
         .try_get_with(slot, async move {
-            crate::runtime::fetch_slot_block(fetch_state.as_ref(), slot).await
+            let cached = crate::runtime::fetch_slot_block(fetch_state.as_ref(), slot).await?;
+            fetch_state.persist_block(slot, &cached);
+            Ok::<_, SlotFetchError>(cached)
         })
         .await
     {
         Ok(cached) => serve_cached_block(id, cached),
-        Err(error) => slot_fill_err(id, error.as_str()).into_response(),
+        Err(error) => match error.as_ref() {
+            SlotFetchError::UnsettledSkip(upstream) => serve_upstream_error(id, upstream.clone()),
+            SlotFetchError::Failed(msg) => slot_fill_err(id, msg),
+        },
     }
 }
 
 /// Build the JSON-RPC envelope around a pre-serialized `result` body
 /// without re-parsing it. Saves a round-trip through serde for the
 /// (large) block body on the hot serve path.
-fn serve_present_block(id: Value, result_bytes: Bytes) -> Response {
+fn serve_present_block(id: Value, result_bytes: Bytes) -> Reply {
     let id_serialized = serde_json::to_vec(&id).unwrap_or_else(|_| b"null".to_vec());
     let mut buf = Vec::with_capacity(40 + id_serialized.len() + result_bytes.len());
     buf.extend_from_slice(br#"{"jsonrpc":"2.0","id":"#);
@@ -343,47 +623,74 @@ fn serve_present_block(id: Value, result_bytes: Bytes) -> Response {
     buf.extend_from_slice(&result_bytes);
     buf.push(b'}');
 
-    (
-        StatusCode::OK,
-        [(header::CONTENT_TYPE, "application/json")],
-        buf,
-    )
-        .into_response()
+    Reply {
+        status: StatusCode::OK,
+        body: buf,
+    }
 }
 
-fn serve_skipped_envelope(id: Value) -> Response {
-    (
+fn serve_skipped_envelope(id: Value) -> Reply {
+    Reply::json(
         StatusCode::OK,
-        axum::Json(json!({
+        &json!({
             "jsonrpc": "2.0",
             "id": id,
             "error": {
                 "code": SKIPPED_SLOT_ERROR_CODE,
                 "message": "Slot was skipped, or missing due to ledger jump to recent snapshot",
             },
-        })),
+        }),
+    )
+}
+
+/// Replay an upstream error object under the caller's id.
+fn serve_upstream_error(id: Value, error: Value) -> Reply {
+    Reply::json(
+        StatusCode::OK,
+        &json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": error,
+        }),
     )
-        .into_response()
+}
+
+/// Report the older of upstream's first available block and the oldest
+/// slot on disk, so a reader replaying from far back is not turned away
+/// by upstream's prune window.
+async fn serve_first_available_block(
+    state: &AppState,
+    req: &Value,
+    id: Value,
+    oldest: u64,
+) -> Reply {
+    state.stats.upstream_calls.fetch_add(1, Ordering::Relaxed);
+    let first = match state.upstream.forward(req).await {
+        Ok(envelope) => envelope
+            .result
+            .as_ref()
+            .and_then(Value::as_u64)
+            .map_or(oldest, |upstream| upstream.min(oldest)),
+        Err(error) => {
+            warn!(%error, "getFirstAvailableBlock upstream failed; serving block store floor");
+            oldest
+        }
+    };
+    json_ok(id, json!(first))
 }
 
 /// Forward the request upstream without caching the response. Used for
 /// non-confirmed `getBlock` requests and general passthrough paths.
-async fn forward_passthrough(state: &AppState, req: &Value, id: Value) -> Response {
+async fn forward_passthrough(state: &AppState, req: &Value, id: Value) -> Reply {
     state.stats.upstream_calls.fetch_add(1, Ordering::Relaxed);
     match state.upstream.forward(req).await {
-        Ok(envelope) => (
-            StatusCode::OK,
-            axum::Json(reshape_with_id(envelope, id)),
-        )
-            .into_response(),
-        Err(e) => upstream_err(id, &e).into_response(),
+        Ok(envelope) => Reply::json(StatusCode::OK, &reshape_with_id(envelope, id)),
+        Err(e) => upstream_err(id, &e),
     }
 }
 
-async fn forward_raw(state: &AppState, req: &Value) -> Response {
+async fn forward_raw(state: &AppState, req: &Value) -> Reply {
     state.stats.upstream_calls.fetch_add(1, Ordering::Relaxed);
-    // For batch requests, `id` is per-sub-request; nothing for us to
-    // preserve at the envelope level. Just forward and return verbatim.
     match state.upstream.forward(req).await {
         Ok(env) => {
             let wire = json!({
@@ -392,18 +699,18 @@ async fn forward_raw(state: &AppState, req: &Value) -> Response {
                 "result": env.result,
                 "error": env.error,
             });
-            (StatusCode::OK, axum::Json(strip_nulls(wire))).into_response()
+            Reply::json(StatusCode::OK, &strip_nulls(wire))
         }
         Err(e) => {
             let id = req.get("id").cloned().unwrap_or(Value::Null);
-            upstream_err(id, &e).into_response()
+            upstream_err(id, &e)
         }
     }
 }
 
 /// Rebuild a JSON-RPC response envelope substituting the caller's
 /// original `id`, which is what clients match on.
-fn reshape_with_id(env: crate::upstream::RpcEnvelope, id: Value) -> Value {
+fn reshape_with_id(env: RpcEnvelope, id: Value) -> Value {
     let mut out = json!({
         "jsonrpc": env.jsonrpc,
         "id": id,
@@ -417,44 +724,55 @@ fn reshape_with_id(env: crate::upstream::RpcEnvelope, id: Value) -> Value {
     out
 }
 
-fn json_ok(id: Value, result: Value) -> (StatusCode, axum::Json<Value>) {
-    (
+fn json_ok(id: Value, result: Value) -> Reply {
+    Reply::json(
         StatusCode::OK,
-        axum::Json(json!({
+        &json!({
             "jsonrpc": "2.0",
             "id": id,
             "result": result,
-        })),
+        }),
     )
 }
 
-fn upstream_err(id: Value, err: &UpstreamError) -> (StatusCode, axum::Json<Value>) {
+fn invalid_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": Value::Null,
+        "error": {
+            "code": INVALID_REQUEST_ERROR_CODE,
+            "message": "Invalid Request",
+        },
+    })
+}
+
+fn upstream_err(id: Value, err: &UpstreamError) -> Reply {
     info!(error = %err, "returning upstream-error envelope");
-    (
+    Reply::json(
         StatusCode::BAD_GATEWAY,
-        axum::Json(json!({
+        &json!({
             "jsonrpc": "2.0",
             "id": id,
             "error": {
                 "code": -32603,
                 "message": format!("upstream: {err}"),
             },
-        })),
+        }),
     )
 }
 
-fn slot_fill_err(id: Value, err: &str) -> (StatusCode, axum::Json<Value>) {
+fn slot_fill_err(id: Value, err: &str) -> Reply {
     info!(error = %err, "returning slot-fill-error envelope");
-    (
+    Reply::json(
         StatusCode::BAD_GATEWAY,
-        axum::Json(json!({
+        &json!({
             "jsonrpc": "2.0",
             "id": id,
             "error": {
                 "code": -32603,
                 "message": format!("upstream getBlock: {err}"),
             },
-        })),
+        }),
     )
 }
 
@@ -504,12 +822,150 @@ mod tests {
     fn present_block_envelope_serialization() {
         let result_bytes = Bytes::from_static(br#"{"blockhash":"abc","parentSlot":10}"#);
         let resp = serve_present_block(json!(7), result_bytes);
-        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.status, StatusCode::OK);
     }
 
     #[test]
     fn skipped_envelope_carries_solana_error_code() {
         let resp = serve_skipped_envelope(json!(42));
-        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.status, StatusCode::OK);
+    }
+
+    fn test_state(disk: Option<DiskBlocks>) -> Arc<AppState> {
+        test_state_with("http://127.0.0.1:1".to_string(), disk)
+    }
+
+    fn test_state_with(upstream: String, disk: Option<DiskBlocks>) -> Arc<AppState> {
+        Arc::new(AppState {
+            policy: Policy::new(Default::default()),
+            cache: CacheStore::new(16),
+            upstream: Upstream::new(
+                upstream,
+                std::time::Duration::from_millis(10),
+                Default::default(),
+            ),
+            log_submits: false,
+            api_key: "key".to_string(),
+            slot_store: MokaCache::builder().max_capacity(1 << 20).build(),
+            disk,
+            program_ids: Vec::new(),
+            stats: CacheStats::new(),
+        })
+    }
+
+    // batch elements are answered from memory and disk in request order,
+    // and a non-object element gets its own error envelope
+    #[tokio::test]
+    async fn batch_serves_elements_in_order() {
+        let disk = DiskBlocks::new(Arc::new(store_memory::MemoryStore::new()));
+        let on_disk = Bytes::from_static(br#"{"parentSlot":5}"#);
+        disk.put(6, &CachedBlock::Present(on_disk)).unwrap();
+        let state = test_state(Some(disk));
+        let in_memory = Bytes::from_static(br#"{"parentSlot":4}"#);
+        state.slot_store.insert(5, CachedBlock::Present(in_memory)).await;
+
+        let confirmed = json!({"commitment": "confirmed"});
+        let items = vec![
+            json!({"jsonrpc": "2.0", "id": 1, "method": "getBlock", "params": [5, confirmed]}),
+            json!(7),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "getBlock", "params": [6, confirmed]}),
+        ];
+        let reply = handle_batch(&state, "127.0.0.1", &items).await;
+        assert_eq!(reply.status, StatusCode::OK);
+
+        let replies: Vec<Value> = serde_json::from_slice(&reply.body).unwrap();
+        assert_eq!(replies.len(), 3);
+        assert_eq!(replies[0]["id"], 1);
+        assert_eq!(replies[0]["result"]["parentSlot"], 4);
+        assert_eq!(replies[1]["error"]["code"], INVALID_REQUEST_ERROR_CODE);
+        assert_eq!(replies[2]["id"], 3);
+        assert_eq!(replies[2]["result"]["parentSlot"], 5);
+
+        assert_eq!(state.stats.block_store_hits.load(Ordering::Relaxed), 1);
+        assert!(state.slot_store.get(&6).await.is_some(), "disk hit promoted to memory");
+    }
+
+    /// Loopback upstream answering each request with `reply`, batches in
+    /// reverse order. Returns its url and a count of HTTP calls.
+    async fn fake_upstream(reply: fn(&Value) -> Value) -> (String, Arc<AtomicU64>) {
+        let calls = Arc::new(AtomicU64::new(0));
+        let counter = calls.clone();
+        let router = Router::new().route(
+            "/",
+            post(move |axum::Json(body): axum::Json<Value>| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                let replies = match body.as_array() {
+                    Some(items) => Value::Array(items.iter().rev().map(reply).collect()),
+                    None => reply(&body),
+                };
+                async move { axum::Json(replies) }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (url, calls)
+    }
+
+    fn echo_param(req: &Value) -> Value {
+        json!({"jsonrpc": "2.0", "id": req["id"], "result": req["params"][0]})
+    }
+
+    // uncached reads share one upstream call, replies are matched back to
+    // their element even with duplicate ids, and the results fill the cache
+    #[tokio::test]
+    async fn batch_forwards_uncached_reads_together() {
+        let (url, calls) = fake_upstream(echo_param).await;
+        let state = test_state_with(url, None);
+
+        let items = vec![
+            json!({"jsonrpc": "2.0", "id": "a", "method": "getAccountInfo", "params": ["x"]}),
+            json!({"jsonrpc": "2.0", "id": "a", "method": "getAccountInfo", "params": ["y"]}),
+            json!({"jsonrpc": "2.0", "id": 9, "method": "getMultipleAccounts", "params": ["z"]}),
+        ];
+        for round in 0..2 {
+            let reply = handle_batch(&state, "127.0.0.1", &items).await;
+            let replies: Vec<Value> = serde_json::from_slice(&reply.body).unwrap();
+            assert_eq!(replies.len(), 3, "round {round}");
+            assert_eq!(replies[0]["id"], "a");
+            assert_eq!(replies[0]["result"], "x");
+            assert_eq!(replies[1]["id"], "a");
+            assert_eq!(replies[1]["result"], "y");
+            assert_eq!(replies[2]["id"], 9);
+            assert_eq!(replies[2]["result"], "z");
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 1, "second round served from cache");
+    }
+
+    fn skipping_upstream(req: &Value) -> Value {
+        let error = match req["method"].as_str() {
+            Some("getSlot") => return json!({"jsonrpc": "2.0", "id": req["id"], "result": 20}),
+            _ if req["params"][0] == 10 => json!({"code": -32009, "message": "missing"}),
+            _ => json!({"code": SKIPPED_SLOT_ERROR_CODE, "message": "skipped"}),
+        };
+        json!({"jsonrpc": "2.0", "id": req["id"], "error": error})
+    }
+
+    // only a -32007 skip at or below the finalized slot (20) is kept
+    #[tokio::test]
+    async fn only_finalized_skips_are_persisted() {
+        let (url, _) = fake_upstream(skipping_upstream).await;
+        let disk = DiskBlocks::new(Arc::new(store_memory::MemoryStore::new()));
+        let state = test_state_with(url, Some(disk));
+
+        for (slot, code, kept) in [(10, -32009, false), (15, -32007, true), (30, -32007, false)] {
+            let req = json!({
+                "jsonrpc": "2.0",
+                "id": slot,
+                "method": "getBlock",
+                "params": [slot, {"commitment": "confirmed"}],
+            });
+            let reply = handle_single(&state, "127.0.0.1", &req).await;
+            let reply: Value = serde_json::from_slice(&reply.body).unwrap();
+            assert_eq!(reply["id"], slot);
+            assert_eq!(reply["error"]["code"], code, "slot {slot}");
+            assert_eq!(state.is_persisted(slot), kept, "slot {slot}");
+            assert_eq!(state.slot_store.get(&slot).await.is_some(), kept, "slot {slot}");
+        }
     }
 }
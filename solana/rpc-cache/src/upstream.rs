@@ -7,6 +7,7 @@ use std::time::Duration;
 
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
 use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
@@ -94,6 +95,27 @@ impl Upstream {
     /// envelope. Handles 429 cool-off and general transient retries
     /// internally.
     pub async fn forward(&self, body: &Value) -> Result<RpcEnvelope, UpstreamError> {
+        let envelope: RpcEnvelope = self.send(body).await?;
+        debug!(
+            has_result = envelope.result.is_some(),
+            has_error = envelope.error.is_some(),
+            "upstream ok"
+        );
+        Ok(envelope)
+    }
+
+    /// Forward several requests as one JSON-RPC batch. Replies come back in
+    /// whatever order upstream chose; callers match them up by `id`.
+    pub async fn forward_batch(
+        &self,
+        items: &[Value],
+    ) -> Result<Vec<RpcEnvelope>, UpstreamError> {
+        let envelopes: Vec<RpcEnvelope> = self.send(&Value::Array(items.to_vec())).await?;
+        debug!(requests = items.len(), replies = envelopes.len(), "upstream batch ok");
+        Ok(envelopes)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, body: &Value) -> Result<T, UpstreamError> {
         let mut transient_attempts = 0u32;
 
         loop {
@@ -156,17 +178,11 @@ impl Upstream {
                 });
             }
 
-            // Success — parse envelope.
-            let envelope: RpcEnvelope = resp
+            // Success — parse envelope(s).
+            return resp
                 .json()
                 .await
-                .map_err(|e| UpstreamError::BadResponse(e.to_string()))?;
-            debug!(
-                has_result = envelope.result.is_some(),
-                has_error = envelope.error.is_some(),
-                "upstream ok"
-            );
-            return Ok(envelope);
+                .map_err(|e| UpstreamError::BadResponse(e.to_string()));
         }
     }
 }
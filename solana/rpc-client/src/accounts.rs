@@ -7,11 +7,11 @@ use rpc::{Rpc, RpcError};
 
 use tape_api::dynamic::DynamicState;
 use tape_api::state::{
-    AccountType, Archive, Committee, Epoch, Group, Node, PeerSet, Stake, System, Tape,
+    AccountType, Archive, Committee, Epoch, Escrow, Group, Node, PeerSet, Stake, System, Tape,
 };
 use tape_api::program::tapedrive::{
     self, SYSTEM_ADDRESS, ARCHIVE_ADDRESS, PEER_SET_ADDRESS,
-    committee_pda, epoch_pda, group_pda, history_pda, node_pda, stake_pda, tape_pda,
+    committee_pda, epoch_pda, escrow_pda, group_pda, history_pda, node_pda, stake_pda, tape_pda,
 };
 
 use tape_core::spooler::GroupIndex;
@@ -218,8 +218,7 @@ impl<R: Rpc> RpcClient<R> {
 
         let result = async {
             let account = self.rpc().get_account(&ARCHIVE_ADDRESS).await?;
-            Archive::unpack_with_discriminator(&account.data)
-                .map(|a| *a)
+            Archive::read_account(&account.data)
                 .map_err(|e| RpcError::Deserialization(e.to_string()))
         }
         .await;
@@ -360,8 +359,7 @@ impl<R: Rpc> RpcClient<R> {
         let result = async {
             let (address, _bump) = node_pda(*authority);
             let account = self.rpc().get_account(&address).await?;
-            Node::unpack_with_discriminator(&account.data)
-                .map(|n| *n)
+            Node::read_account(&account.data)
                 .map_err(|e| RpcError::Deserialization(e.to_string()))
         }
         .await;
@@ -380,8 +378,7 @@ impl<R: Rpc> RpcClient<R> {
     /// Fetch a Node account by its PDA address directly.
     pub async fn get_node_by_address(&self, address: &Address) -> Result<Node, RpcError> {
         let account = self.rpc().get_account(address).await?;
-        Node::unpack_with_discriminator(&account.data)
-            .map(|n| *n)
+        Node::read_account(&account.data)
             .map_err(|e| RpcError::Deserialization(e.to_string()))
     }
 
@@ -444,6 +441,31 @@ impl<R: Rpc> RpcClient<R> {
         result
     }
 
+    /// Fetch the renewal escrow attached to a tape
+    pub async fn get_escrow(&self, tape: &Address) -> Result<Escrow, RpcError> {
+        #[cfg(feature = "metrics")]
+        let timer = self.metrics.as_ref().map(|m| m.start_operation());
+
+        let result = async {
+            let (address, _bump) = escrow_pda(*tape);
+            let account = self.rpc().get_account(&address).await?;
+            Escrow::unpack_with_discriminator(&account.data)
+                .map(|e| *e)
+                .map_err(|e| RpcError::Deserialization(e.to_string()))
+        }
+        .await;
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            if let Some(timer) = &timer {
+                let status = if result.is_ok() { "success" } else { "error" };
+                metrics.record_account_fetch("escrow", status, timer);
+            }
+        }
+
+        result
+    }
+
     /// Fetch the history Tape account for a node.
     ///
     /// # Arguments
@@ -504,8 +526,7 @@ impl<R: Rpc> RpcClient<R> {
         accounts
             .into_iter()
             .map(|(pubkey, account)| {
-                let node = Node::unpack_with_discriminator(&account.data)
-                    .map(|n| *n)
+                let node = Node::read_account(&account.data)
                     .map_err(|e| RpcError::Deserialization(e.to_string()))?;
                 Ok((pubkey, node))
             })
@@ -600,8 +621,7 @@ impl<R: Rpc> RpcClient<R> {
             .into_iter()
             .next()
             .map(|(pubkey, account)| {
-                let node = Node::unpack_with_discriminator(&account.data)
-                    .map(|n| *n)
+                let node = Node::read_account(&account.data)
                     .map_err(|e| RpcError::Deserialization(e.to_string()))?;
                 Ok((pubkey, node))
             })
@@ -1,5 +1,7 @@
 use rpc_solana::{RpcConfig, SolanaRpc};
-use rpc::{EncodedConfirmedTransactionWithStatusMeta, Rpc, RpcError, UiConfirmedBlock};
+use rpc::{
+    BlockSubscription, EncodedConfirmedTransactionWithStatusMeta, Rpc, RpcError, UiConfirmedBlock,
+};
 use tape_crypto::tx::Txid;
 
 #[cfg(feature = "metrics")]
@@ -91,6 +93,11 @@ impl<R: Rpc> RpcClient<R> {
         self.rpc.get_block(slot).await
     }
 
+    /// Subscribe to confirmed blocks, or `None` when the backend only polls.
+    pub async fn subscribe_blocks(&self) -> Result<Option<BlockSubscription>, RpcError> {
+        self.rpc.subscribe_blocks().await
+    }
+
     /// Get the lowest slot the node still has a confirmed block for.
     pub async fn get_first_available_block(&self) -> Result<u64, RpcError> {
         self.rpc.get_first_available_block().await
@@ -11,6 +11,10 @@ use tape_api::compute::{MAX_COMPUTE_UNIT_LIMIT, MEASURED_CU_HEADROOM_PERCENT};
 use tape_crypto::signer::Signer as TapeSigner;
 use tape_crypto::tx::Txid;
 
+/// Largest serialized transaction the cluster accepts (a packet minus its
+/// network headers).
+const MAX_TRANSACTION_SIZE: usize = 1232;
+
 struct SolanaSignerAdapter<'a>(&'a dyn TapeSigner);
 
 impl SolanaSigner for SolanaSignerAdapter<'_> {
@@ -154,6 +158,29 @@ impl<R: Rpc> RpcClient<R> {
         .await
     }
 
+    /// Whether `instructions`, once capped with a compute budget instruction
+    /// the way `send_instructions_with_signers_and_compute_unit_limit` sends
+    /// them, fit in one transaction paid by `payer`.
+    ///
+    /// Used to pack batches of instructions into as few transactions as the
+    /// size limit allows.
+    pub fn fits_capped_transaction(
+        &self,
+        payer: &dyn TapeSigner,
+        instructions: &[Instruction],
+    ) -> bool {
+        let payer_pubkey: SolanaPubkey = payer.pubkey().into();
+        let mut capped = Vec::with_capacity(instructions.len() + 1);
+        capped.push(ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT));
+        capped.extend_from_slice(instructions);
+
+        let transaction = Transaction::new_with_payer(&capped, Some(&payer_pubkey));
+        let signatures = transaction.signatures.len();
+        // Compact-u16 signature count, then the signatures and the message.
+        let size = short_vec_len(signatures) + signatures * 64 + transaction.message_data().len();
+        size <= MAX_TRANSACTION_SIZE
+    }
+
     /// Send a transaction without waiting for confirmation
     ///
     /// Use this when you want to send the transaction and continue immediately
@@ -386,6 +413,15 @@ impl<R: Rpc> RpcClient<R> {
     }
 }
 
+/// Encoded length of a compact-u16 length prefix.
+fn short_vec_len(value: usize) -> usize {
+    match value {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -413,4 +449,16 @@ mod tests {
         // Expected to fail due to insufficient funds
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_fits_capped_transaction() {
+        let client = RpcClient::new(RpcConfig::default()).unwrap();
+        let solana_payer = SolanaKeypair::new();
+        let payer = Keypair::from_keypair_bytes(solana_payer.to_bytes()).expect("convert payer");
+        let payer_pubkey = payer.pubkey().into();
+        let transfer = system_instruction::transfer(&payer_pubkey, &Pubkey::new_unique(), 1000);
+
+        assert!(client.fits_capped_transaction(&payer, &[transfer.clone()]));
+        assert!(!client.fits_capped_transaction(&payer, &vec![transfer; 64]));
+    }
 }
@@ -13,7 +13,8 @@ use block::{RecordedTransaction, SlotData};
 use convert::{tx_result_to_status_result, tx_result_to_transaction_status};
 use litesvm::types::TransactionResult;
 use litesvm::LiteSVM;
-use rpc::{Rpc, RpcError, SimulationResult};
+use futures::stream;
+use rpc::{BlockSubscription, PushedBlock, Rpc, RpcError, SimulationResult};
 use solana_account::{Account, ReadableAccount as SvmReadableAccount};
 use solana_client::rpc_config::RpcProgramAccountsConfig;
 use solana_client::rpc_filter::RpcFilterType;
@@ -30,10 +31,14 @@ use solana_transaction_status::{
 };
 use tape_crypto::address::Address;
 use tape_crypto::tx::Txid;
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 
 const DEFAULT_FINALIZED_LAG_SLOTS: u64 = 1;
 
+/// Confirmed blocks buffered per subscriber before it starts missing them.
+const BLOCK_FEED_CAPACITY: usize = 256;
+
 struct Inner {
     /// The in-memory SVM instance that processes transactions and maintains
     svm: LiteSVM,
@@ -61,6 +66,9 @@ struct Inner {
     /// lags confirmed slot by a small fixed amount so tests can exercise
     /// both commitment levels.
     finalized_tip_override: Option<u64>,
+
+    /// Pushes each block to subscribers as `confirmed_tip` passes it.
+    block_feed: broadcast::Sender<PushedBlock>,
 }
 
 /// LiteSVM-backed Rpc implementation with simulated block production.
@@ -98,6 +106,7 @@ impl LiteSvmRpc {
                 confirmed_tip: 0,
                 pending_slot: 1,
                 finalized_tip_override: None,
+                block_feed: broadcast::channel(BLOCK_FEED_CAPACITY).0,
             })),
         }
     }
@@ -137,9 +146,7 @@ impl LiteSvmRpc {
             .map_err(|e| RpcError::Internal(format!("mutex poisoned: {e}")))?;
         inner.svm.warp_to_slot(slot);
         Self::close_slot_locked(&mut inner, slot);
-        if slot > inner.confirmed_tip {
-            inner.confirmed_tip = slot;
-        }
+        Self::confirm_through_locked(&mut inner, slot);
         if inner.pending_slot <= slot {
             inner.pending_slot = slot + 1;
         }
@@ -158,7 +165,7 @@ impl LiteSvmRpc {
                 let slot = inner.pending_slot;
                 inner.svm.warp_to_slot(slot);
                 Self::close_slot_locked(&mut inner, slot);
-                inner.confirmed_tip = slot;
+                Self::confirm_through_locked(&mut inner, slot);
                 inner.pending_slot = inner.confirmed_tip + 1;
 
                 let mut clock = inner.svm.get_sysvar::<SvmClock>();
@@ -311,6 +318,37 @@ impl LiteSvmRpc {
         inner.svm.expire_blockhash();
     }
 
+    /// Raise `confirmed_tip` to `slot` and push the blocks it uncovers to
+    /// subscribers in slot order. Blocks are only encoded when someone is
+    /// listening.
+    fn confirm_through_locked(inner: &mut Inner, slot: Slot) {
+        let previous = inner.confirmed_tip;
+        if slot <= previous {
+            return;
+        }
+        inner.confirmed_tip = slot;
+
+        if inner.block_feed.receiver_count() == 0 {
+            return;
+        }
+        let mut uncovered: Vec<Slot> = inner
+            .slots
+            .keys()
+            .copied()
+            .filter(|recorded| *recorded > previous && *recorded <= slot)
+            .collect();
+        uncovered.sort_unstable();
+        for recorded in uncovered {
+            let Ok(block) = inner.slots[&recorded].to_ui_confirmed_block() else {
+                continue;
+            };
+            let _ = inner.block_feed.send(PushedBlock {
+                slot: recorded,
+                block,
+            });
+        }
+    }
+
     fn record_transaction_locked(
         inner: &mut Inner,
         tx: VersionedTransaction,
@@ -378,6 +416,28 @@ impl Rpc for LiteSvmRpc {
             .unwrap_or_else(|| inner.confirmed_tip.saturating_sub(DEFAULT_FINALIZED_LAG_SLOTS)))
     }
 
+    async fn subscribe_blocks(&self) -> Result<Option<BlockSubscription>, RpcError> {
+        let receiver = self
+            .inner
+            .lock()
+            .map_err(|e| RpcError::Internal(format!("mutex poisoned: {e}")))?
+            .block_feed
+            .subscribe();
+
+        // A lagging receiver skips ahead; the consumer sees the gap in the
+        // parent chain and fills it with get_block.
+        let blocks = stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(block) => return Some((Ok(block), receiver)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+        Ok(Some(Box::pin(blocks)))
+    }
+
     async fn get_first_available_block(&self) -> Result<u64, RpcError> {
         let inner = self
             .inner
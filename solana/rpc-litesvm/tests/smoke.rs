@@ -66,3 +66,26 @@ async fn send_transfer_records_block_and_status() {
     let recipient_account = rpc.get_account(&recipient_addr).await.expect("recipient account");
     assert_eq!(recipient_account.lamports, 1_000_000);
 }
+
+#[tokio::test]
+async fn block_subscription_pushes_confirmed_blocks() {
+    use futures::StreamExt;
+
+    let rpc = LiteSvmRpc::new();
+    let mut blocks = rpc
+        .subscribe_blocks()
+        .await
+        .expect("subscribe")
+        .expect("litesvm has a block feed");
+
+    let slot = rpc.get_slot().await.expect("slot available");
+    rpc.warp_to_slot(slot + 1).expect("close first slot");
+    rpc.warp_to_slot(slot + 2).expect("close second slot");
+
+    let first = blocks.next().await.expect("first block").expect("block ok");
+    let second = blocks.next().await.expect("second block").expect("block ok");
+    assert_eq!(first.slot, slot + 1);
+    assert_eq!(second.slot, slot + 2);
+    assert_eq!(second.block.parent_slot, first.slot);
+    assert_eq!(second.block.previous_blockhash, first.block.blockhash);
+}
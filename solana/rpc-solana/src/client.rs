@@ -18,7 +18,7 @@ use solana_transaction_status::{
 };
 use std::future::Future;
 use std::sync::{Arc, Mutex, PoisonError};
-use rpc::{Rpc, RpcError, SimulationResult};
+use rpc::{BlockSubscription, Rpc, RpcError, SimulationResult};
 use tape_crypto::address::Address;
 use tape_crypto::tx::Txid;
 
@@ -458,6 +458,13 @@ impl Rpc for SolanaRpc {
         .await
     }
 
+    async fn subscribe_blocks(&self) -> Result<Option<BlockSubscription>, RpcError> {
+        match &self.config.block_feed {
+            Some(url) => crate::feed::subscribe(url).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
     async fn get_first_available_block(&self) -> Result<u64, RpcError> {
         self.with_retry("getFirstAvailableBlock", |client| async move {
             client
@@ -29,6 +29,11 @@ pub struct RpcConfig {
     /// Retry policy configuration
     #[serde(default)]
     pub retry: RpcRetryConfig,
+
+    /// Websocket endpoint serving `blockSubscribe`. When unset the client
+    /// offers no block feed and callers poll `getBlock`.
+    #[serde(default)]
+    pub block_feed: Option<String>,
 }
 
 /// Retry and backoff configuration
@@ -79,6 +84,7 @@ impl Default for RpcConfig {
             commitment: default_commitment(),
             timeout: default_timeout(),
             retry: RpcRetryConfig::default(),
+            block_feed: None,
         }
     }
 }
@@ -184,6 +190,7 @@ mod tests {
                 max_endpoint_attempts: 2,
                 endpoint_cooldown: Duration::from_secs(30),
             },
+            block_feed: Some("wss://test.com".to_string()),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -194,6 +201,7 @@ mod tests {
         assert_eq!(config.commitment, deserialized.commitment);
         assert_eq!(config.timeout, deserialized.timeout);
         assert_eq!(config.retry.max_retries, deserialized.retry.max_retries);
+        assert_eq!(config.block_feed, deserialized.block_feed);
     }
 
     // every strategy name parses from its kebab-case form
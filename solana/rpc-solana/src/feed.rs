@@ -0,0 +1,87 @@
+//! Confirmed block feed over websocket `blockSubscribe`.
+
+use futures::stream::{self, StreamExt};
+use rpc::{BlockSubscription, PushedBlock, RpcError};
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::{RpcBlockSubscribeConfig, RpcBlockSubscribeFilter};
+use solana_commitment_config::{CommitmentConfig, CommitmentLevel};
+use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
+use tokio::sync::mpsc;
+
+/// Blocks held between the websocket task and a slow consumer. The feed
+/// ends once this fills, since a consumer that far behind polls anyway.
+const FEED_BUFFER: usize = 64;
+
+/// Matches the encoding `get_block` asks for, so pushed and polled blocks
+/// parse the same way.
+fn subscribe_config() -> RpcBlockSubscribeConfig {
+    RpcBlockSubscribeConfig {
+        commitment: Some(CommitmentConfig {
+            commitment: CommitmentLevel::Confirmed,
+        }),
+        encoding: Some(UiTransactionEncoding::Json),
+        transaction_details: Some(TransactionDetails::Full),
+        show_rewards: Some(false),
+        max_supported_transaction_version: Some(0),
+    }
+}
+
+/// Connect to `url` and stream every confirmed block it announces. The
+/// stream ends when the socket drops; callers resubscribe.
+pub(crate) async fn subscribe(url: &str) -> Result<BlockSubscription, RpcError> {
+    let client = PubsubClient::new(url)
+        .await
+        .map_err(|error| RpcError::Request(format!("block feed connect: {error}")))?;
+
+    let (sender, receiver) = mpsc::channel(FEED_BUFFER);
+    tokio::spawn(forward(client, sender));
+
+    let blocks = stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|item| (item, receiver))
+    });
+    Ok(Box::pin(blocks))
+}
+
+/// Pump notifications from the socket into `sender` until either side
+/// goes away, then close the socket.
+async fn forward(client: PubsubClient, sender: mpsc::Sender<Result<PushedBlock, RpcError>>) {
+    pump(&client, &sender).await;
+    let _ = client.shutdown().await;
+}
+
+/// Subscribe on `client` and forward blocks until the socket or the
+/// consumer drops. Every borrow of `client` ends when this returns.
+async fn pump(client: &PubsubClient, sender: &mpsc::Sender<Result<PushedBlock, RpcError>>) {
+    let subscribed = client
+        .block_subscribe(RpcBlockSubscribeFilter::All, Some(subscribe_config()))
+        .await;
+    let (mut updates, unsubscribe) = match subscribed {
+        Ok(subscription) => subscription,
+        Err(error) => {
+            let error = RpcError::Request(format!("blockSubscribe: {error}"));
+            let _ = sender.send(Err(error)).await;
+            return;
+        }
+    };
+
+    while let Some(update) = updates.next().await {
+        let update = update.value;
+        let item = match (update.block, update.err) {
+            (Some(block), _) => Ok(PushedBlock {
+                slot: update.slot,
+                block,
+            }),
+            (None, Some(error)) => Err(RpcError::Request(format!(
+                "blockSubscribe slot {}: {error}",
+                update.slot
+            ))),
+            (None, None) => continue,
+        };
+        if sender.try_send(item).is_err() {
+            break;
+        }
+    }
+
+    drop(updates);
+    unsubscribe().await;
+}
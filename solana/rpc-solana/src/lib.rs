@@ -49,6 +49,7 @@
 
 mod client;
 mod config;
+mod feed;
 mod selector;
 
 #[cfg(feature = "metrics")]
@@ -30,7 +30,7 @@ mod rpc;
 
 // Core exports
 pub use error::{RpcError, looks_like_transaction_error};
-pub use rpc::{Rpc, SimulationResult};
+pub use rpc::{BlockSubscription, PushedBlock, Rpc, SimulationResult};
 
 // Re-export async_trait for implementors
 pub use async_trait::async_trait;
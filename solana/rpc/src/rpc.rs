@@ -6,6 +6,7 @@
 //! - Test backends (for simulation/integration environments)
 
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use solana_account::Account;
 use solana_client::rpc_config::RpcProgramAccountsConfig;
 use solana_commitment_config::CommitmentLevel;
@@ -27,6 +28,21 @@ pub struct SimulationResult {
     pub units_consumed: Option<u64>,
 }
 
+/// A confirmed block delivered by a block subscription.
+#[derive(Debug, Clone)]
+pub struct PushedBlock {
+    /// Slot the block was produced in.
+    pub slot: u64,
+
+    /// The block, encoded the same way `get_block` returns it.
+    pub block: UiConfirmedBlock,
+}
+
+/// Confirmed blocks in ascending slot order. Skipped slots are absent, and
+/// a feed may drop blocks under load or across reconnects, so consumers
+/// compare each block's parent against what they last saw.
+pub type BlockSubscription = BoxStream<'static, Result<PushedBlock, RpcError>>;
+
 /// Core RPC trait for Solana operations
 ///
 /// This trait mirrors the Store pattern from `tapedrive/archive/store/`.
@@ -65,6 +81,13 @@ pub trait Rpc: Send + Sync {
     /// Get a confirmed block by slot number
     async fn get_block(&self, slot: u64) -> Result<UiConfirmedBlock, RpcError>;
 
+    /// Subscribe to confirmed blocks as they are produced. Returns `None`
+    /// when the backend has no push feed, leaving callers to poll
+    /// `get_block`.
+    async fn subscribe_blocks(&self) -> Result<Option<BlockSubscription>, RpcError> {
+        Ok(None)
+    }
+
     /// Get the lowest slot the node still has a confirmed block for. Slots
     /// below this have been pruned from the ledger and can never be fetched.
     async fn get_first_available_block(&self) -> Result<u64, RpcError>;
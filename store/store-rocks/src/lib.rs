@@ -47,7 +47,7 @@ mod split;
 
 pub use config::ColumnFamilyConfig;
 pub use rocks::RocksStore;
-pub use split::SplitStore;
+pub use split::{SplitStore, CHECKPOINT_BULK_DIR, CHECKPOINT_META_DIR};
 
 // Re-export commonly used RocksDB types for convenience
 pub use rocksdb::{ColumnFamilyDescriptor, Options};
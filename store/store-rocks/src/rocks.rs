@@ -4,6 +4,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use fs2::available_space;
+use rocksdb::checkpoint::Checkpoint;
 use rocksdb::{
     ColumnFamilyDescriptor, DBWithThreadMode, IteratorMode, MultiThreaded, Options,
     WriteBatch as RocksWriteBatch,
@@ -871,6 +872,14 @@ impl Store for RocksStore {
         Ok(usage)
     }
 
+    fn checkpoint(&self, path: &Path) -> Result<()> {
+        // RocksDB flushes the memtables and pins the live file set itself, so
+        // the checkpoint is the database as of that flush.
+        Checkpoint::new(&self.db)
+            .and_then(|checkpoint| checkpoint.create_checkpoint(path))
+            .map_err(|e| Error::Database(e.to_string()))
+    }
+
     fn reclaim_space(&self) -> Result<()> {
         self.flush()?;
         self.compact_column_family("default");
@@ -1483,4 +1492,22 @@ mod tests {
             assert_eq!(entries, 0, "{cf} memtable not flushed");
         }
     }
+
+    // a checkpoint opens as its own database and ignores later writes
+    #[test]
+    fn checkpoint() {
+        let dir = tempdir().unwrap();
+        let store = RocksStore::open(dir.path().join("live"), &["test"]).unwrap();
+        store.put("test", b"kept", b"1").unwrap();
+
+        let path = dir.path().join("checkpoint");
+        store.checkpoint(&path).unwrap();
+        store.put("test", b"later", b"2").unwrap();
+        // The target must not exist yet.
+        assert!(store.checkpoint(&path).is_err());
+
+        let copy = RocksStore::open(&path, &["test"]).unwrap();
+        assert_eq!(copy.get("test", b"kept").unwrap(), Some(b"1".to_vec()));
+        assert_eq!(copy.get("test", b"later").unwrap(), None);
+    }
 }
@@ -9,12 +9,24 @@
 //! Pointing both instances at the same device gives the old single-device
 //! layout, so a one-drive box needs no special handling.
 
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::{PoisonError, RwLock};
+
 use store::{
-    BatchOp, CfDiskUsage, DiskVolume, Direction, Result, Store, StoreIter, StoreVolume, WriteBatch,
+    BatchOp, CfDiskUsage, DiskVolume, Direction, Error, Result, Store, StoreIter, StoreVolume,
+    WriteBatch,
 };
 
 use crate::RocksStore;
 
+/// Subdirectory of a checkpoint holding the metadata instance
+pub const CHECKPOINT_META_DIR: &str = "meta";
+
+/// Subdirectory of a checkpoint holding the bulk instance
+pub const CHECKPOINT_BULK_DIR: &str = "bulk";
+
 /// Store backed by two RocksDB instances split by column family
 ///
 /// The named bulk column families are served by the bulk store; everything
@@ -23,6 +35,9 @@ pub struct SplitStore {
     meta: RocksStore,
     bulk: RocksStore,
     bulk_cfs: Vec<String>,
+    // Held shared by every write and exclusively by a checkpoint, so no write
+    // lands on one instance between the two instance checkpoints
+    write_gate: RwLock<()>,
 }
 
 impl SplitStore {
@@ -36,6 +51,7 @@ impl SplitStore {
             meta,
             bulk,
             bulk_cfs,
+            write_gate: RwLock::new(()),
         }
     }
 
@@ -80,10 +96,12 @@ impl Store for SplitStore {
     }
 
     fn put(&self, cf: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        let _gate = self.write_gate.read().unwrap_or_else(PoisonError::into_inner);
         self.route(cf).put(cf, key, value)
     }
 
     fn delete(&self, cf: &str, key: &[u8]) -> Result<()> {
+        let _gate = self.write_gate.read().unwrap_or_else(PoisonError::into_inner);
         self.route(cf).delete(cf, key)
     }
 
@@ -96,6 +114,7 @@ impl Store for SplitStore {
             return Ok(());
         }
 
+        let _gate = self.write_gate.read().unwrap_or_else(PoisonError::into_inner);
         let mut any_bulk = false;
         let mut any_meta = false;
         for op in batch.iter() {
@@ -149,6 +168,7 @@ impl Store for SplitStore {
     }
 
     fn delete_range(&self, cf: &str, start: &[u8], end: &[u8]) -> Result<()> {
+        let _gate = self.write_gate.read().unwrap_or_else(PoisonError::into_inner);
         self.route(cf).delete_range(cf, start, end)
     }
 
@@ -196,6 +216,40 @@ impl Store for SplitStore {
         self.route(cf).key_count_estimate(cf)
     }
 
+    /// Checkpoints the metadata instance into a meta subdirectory and the
+    /// bulk instance into a bulk subdirectory of `path`, the layout a split
+    /// store opens from under one root. Writes wait while the two instance
+    /// checkpoints run, so both reflect the same last write.
+    ///
+    /// Files hard-link only within a filesystem; on a two-device box, place
+    /// `path` on the bulk device so only the small metadata instance is
+    /// copied while writes wait.
+    fn checkpoint(&self, path: &Path) -> Result<()> {
+        if path.exists() {
+            return Err(Error::Io(std::io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!("checkpoint target {} already exists", path.display()),
+            )));
+        }
+
+        // Flush ahead of the gate so the flush each instance checkpoint runs
+        // while writes wait only covers what arrived since.
+        self.flush()?;
+        fs::create_dir_all(path)?;
+
+        let result = {
+            let _gate = self.write_gate.write().unwrap_or_else(PoisonError::into_inner);
+            self.meta
+                .checkpoint(&path.join(CHECKPOINT_META_DIR))
+                .and_then(|()| self.bulk.checkpoint(&path.join(CHECKPOINT_BULK_DIR)))
+        };
+        if result.is_err() {
+            // A half-written checkpoint is not restorable; do not leave one.
+            let _ = fs::remove_dir_all(path);
+        }
+        result
+    }
+
     fn reclaim_space(&self) -> Result<()> {
         self.meta.reclaim_space()?;
         self.bulk.reclaim_space()
@@ -325,6 +379,25 @@ mod tests {
         assert_eq!(volumes[1].volume, StoreVolume::Bulk);
     }
 
+    // a checkpoint captures both instances and opens as a split store
+    #[test]
+    fn checkpoint_both_volumes() {
+        let dir = tempdir().unwrap();
+        let store = split(&dir.path().join("meta"), &dir.path().join("bulk"));
+        store.put("meta", b"k", b"m").unwrap();
+        store.put("slice", b"k", b"s").unwrap();
+
+        let path = dir.path().join("checkpoint");
+        store.checkpoint(&path).unwrap();
+        store.put("meta", b"later", b"m").unwrap();
+        assert!(store.checkpoint(&path).is_err());
+
+        let copy = split(&path.join(CHECKPOINT_META_DIR), &path.join(CHECKPOINT_BULK_DIR));
+        assert_eq!(copy.get("meta", b"k").unwrap(), Some(b"m".to_vec()));
+        assert_eq!(copy.get("slice", b"k").unwrap(), Some(b"s".to_vec()));
+        assert_eq!(copy.get("meta", b"later").unwrap(), None);
+    }
+
     // iteration is scoped to the owning instance
     #[test]
     fn iterates() {
@@ -16,4 +16,7 @@ pub enum Error {
 
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Unsupported operation: {0}")]
+    Unsupported(String),
 }
@@ -1,6 +1,8 @@
 //! Core storage trait defining the key-value store interface
 
-use crate::{Result, WriteBatch};
+use std::path::Path;
+
+use crate::{Error, Result, WriteBatch};
 
 /// Iterator direction for scanning (lexicographic order)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -156,6 +158,18 @@ pub trait Store: Send + Sync {
         Ok(())
     }
 
+    /// Write a consistent, openable copy of the store under `path` while it
+    /// keeps serving reads and writes.
+    ///
+    /// `path` must not exist yet. Persistent backends hard-link immutable files
+    /// where the filesystem allows and copy the rest, so a checkpoint on the
+    /// store's own device costs little space until the live store moves on. A
+    /// backend split across volumes captures every volume at the same write.
+    /// Backends without a checkpoint primitive return an error.
+    fn checkpoint(&self, _path: &Path) -> Result<()> {
+        Err(Error::Unsupported("checkpoint".into()))
+    }
+
     /// Best-effort disk usage per physical volume.
     ///
     /// Backends split across devices report one entry per volume. The default
@@ -0,0 +1,16 @@
+//! Per-bucket read access column family.
+
+use store::Column;
+use tape_crypto::address::Address;
+
+use crate::types::BucketAccess;
+
+/// Read access per bucket, keyed by bucket tape address. A bucket with no row
+/// is public.
+pub struct BucketAccessCol;
+
+impl Column for BucketAccessCol {
+    const CF_NAME: &'static str = "bucket_access";
+    type Key = Address;
+    type Value = BucketAccess;
+}
@@ -0,0 +1,16 @@
+//! Per-bucket lifecycle column family.
+
+use store::Column;
+use tape_crypto::address::Address;
+
+use crate::types::BucketLifecycle;
+
+/// Lifecycle rules per bucket, keyed by bucket tape address. A bucket with no
+/// row has no lifecycle configuration.
+pub struct BucketLifecycleCol;
+
+impl Column for BucketLifecycleCol {
+    const CF_NAME: &'static str = "bucket_lifecycle";
+    type Key = Address;
+    type Value = BucketLifecycle;
+}
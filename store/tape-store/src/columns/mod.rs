@@ -5,6 +5,8 @@
 //! ## Metadata Columns
 //! - `meta`: Node configuration and metadata (String -> Vec<u8>)
 //! - `tape`: Tape metadata (Address -> TapeInfo)
+//! - `tape_owner`: Current tape owner authority (Address -> Address)
+//! - `owner_tape`: Tapes held per owner authority (OwnerTapeKey -> ())
 //! - `track`: Canonical compressed-track catalog (Address -> PackedTrack)
 //! - `track_lookup`: Tape-local ordered index ((tape, track_number, key) -> ())
 //! - `track_data`: Local track payload data (Address -> BlobData)
@@ -20,10 +22,13 @@
 //! - `spool_pending_repair`: Pending repair (SliceKey -> ())
 //! - `spool_pending_recovery`: Pending recovery (SliceKey -> ())
 //! - `spool_sync_cursor`: Sync cursor (SpoolIndexKey -> Address)
+//! - `spool_scrub_cursor`: Scrub cursor (SpoolIndexKey -> Address)
+//! - `spool_quarantine`: Quarantined corrupt slices (SliceKey -> EpochNumber)
 //!
 //! ## Slice Data Column (BlobDB)
 //! - `slice`: Slice data (SliceKey -> Vec<u8>)
 //! - `slice_size`: Slice payload lengths (SliceKey -> u64)
+//! - `slice_segment`: Verified segments of in-flight uploads (SliceSegmentKey -> Vec<u8>)
 //!
 //! ## Event Log Column
 //! - `event_log`: Per-epoch replayable events (EventLogKey -> CapturedEvent)
@@ -42,23 +47,39 @@
 //! - `ledger`: Per-principal accounting ledger (Address -> LedgerEntry)
 //! - `ledger_reservation`: Outstanding budget reservations (LedgerReservationKey -> LedgerReservation)
 //! - `s3_multipart_upload`: In-flight multipart upload metadata (String -> MultipartUpload)
-//! - `s3_multipart_part`: Buffered multipart part metadata (MultipartPartKey -> MultipartPart)
+//! - `s3_multipart_part`: Multipart part metadata (MultipartPartKey -> MultipartPart)
+//! - `s3_multipart_part_chunks`: Multipart part chunk tracks (MultipartPartKey -> MultipartPartChunks)
 //! - `s3_multipart_part_data`: Buffered multipart part payloads (MultipartPartKey -> MultipartPartData)
+//!
+//! ## S3 Versioning Columns
+//! - `bucket_versioning`: Per-bucket versioning status (Address -> VersioningStatus)
+//! - `object_versions`: Per-key version history (ObjectVersionKey -> ObjectVersion)
+//! - `object_null_versions`: Per-key null version (ObjectListKey -> ObjectVersionId)
+//!
+//! ## S3 Lifecycle Columns
+//! - `bucket_lifecycle`: Per-bucket lifecycle rules (Address -> BucketLifecycle)
+//!
+//! ## S3 Access Columns
+//! - `bucket_access`: Per-bucket read access (Address -> BucketAccess)
 
 pub mod audit_log;
 pub mod auth_state;
+pub mod bucket_access;
 pub mod credential;
 pub mod event_log;
 pub mod gc;
 pub mod ledger;
+pub mod lifecycle;
 pub mod meta;
 pub mod object_info;
 pub mod object_list;
 pub mod object_metadata;
+pub mod object_version;
 pub mod policy;
 pub mod s3_multipart;
 pub mod snapshot;
 pub mod slice;
+pub mod slice_segment;
 pub mod slice_size;
 pub mod spool;
 pub mod sync_cursor;
@@ -71,24 +92,31 @@ pub mod vote;
 // Re-export all column types
 pub use audit_log::AuditLogCol;
 pub use auth_state::AuthStateCol;
+pub use bucket_access::BucketAccessCol;
 pub use credential::CredentialCol;
 pub use event_log::EventLogCol;
 pub use gc::GcCol;
 pub use ledger::{LedgerCol, LedgerReservationCol};
+pub use lifecycle::BucketLifecycleCol;
 pub use meta::MetaCol;
 pub use object_info::ObjectInfoCol;
 pub use object_list::ObjectListCol;
 pub use object_metadata::ObjectMetadataCol;
+pub use object_version::{BucketVersioningCol, ObjectNullVersionCol, ObjectVersionCol};
 pub use policy::PolicyRuleCol;
-pub use s3_multipart::{S3MultipartPartCol, S3MultipartPartDataCol, S3MultipartUploadCol};
+pub use s3_multipart::{
+    S3MultipartPartChunksCol, S3MultipartPartCol, S3MultipartPartDataCol, S3MultipartUploadCol,
+};
 pub use snapshot::SnapshotArtifactCol;
 pub use slice::SliceCol;
+pub use slice_segment::SliceSegmentCol;
 pub use slice_size::SliceSizeCol;
 pub use spool::{
-    SpoolPendingRecoveryCol, SpoolPendingRepairCol, SpoolStatusCol, SpoolSyncCursorCol,
+    SpoolPendingRecoveryCol, SpoolPendingRepairCol, SpoolQuarantineCol, SpoolScrubCursorCol,
+    SpoolStatusCol, SpoolSyncCursorCol,
 };
 pub use sync_cursor::SyncCursorCol;
-pub use tape::TapeCol;
+pub use tape::{OwnerTapeCol, TapeCol, TapeOwnerCol};
 pub use track::TrackCol;
 pub use track_data::TrackDataCol;
 pub use track_lookup::TrackLookupCol;
@@ -111,7 +139,10 @@ pub const ALL_COLUMN_FAMILIES: &[&str] = &[
     "spool_pending_recovery",
     "slice",
     "slice_size",
+    "slice_segment",
     "spool_sync_cursor",
+    "spool_scrub_cursor",
+    "spool_quarantine",
     "event_log",
     "vote_sig",
     "snapshot_artifact",
@@ -123,5 +154,13 @@ pub const ALL_COLUMN_FAMILIES: &[&str] = &[
     "ledger_reservation",
     "s3_multipart_upload",
     "s3_multipart_part",
+    "s3_multipart_part_chunks",
     "s3_multipart_part_data",
+    "bucket_versioning",
+    "object_versions",
+    "object_null_versions",
+    "bucket_lifecycle",
+    "bucket_access",
+    "tape_owner",
+    "owner_tape",
 ];
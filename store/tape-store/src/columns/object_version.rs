@@ -0,0 +1,46 @@
+//! Per-bucket versioning column families.
+
+use store::Column;
+use tape_crypto::address::Address;
+
+use crate::types::{
+    ObjectListKey, ObjectVersion, ObjectVersionId, ObjectVersionKey, VersioningStatus,
+};
+
+/// Versioning status per bucket, keyed by bucket tape address. A bucket with
+/// no row is unversioned.
+pub struct BucketVersioningCol;
+
+impl Column for BucketVersioningCol {
+    const CF_NAME: &'static str = "bucket_versioning";
+    type Key = Address;
+    type Value = VersioningStatus;
+}
+
+/// Version history of every key in a versioned bucket.
+///
+/// Key: `ObjectVersionKey` (`[bucket 32B][name][0x00][version 12B]`), names in
+/// lexicographic order and each name's versions newest first.
+/// Value: `ObjectVersion` (the listing entry of a written version, or a delete
+/// marker).
+pub struct ObjectVersionCol;
+
+impl Column for ObjectVersionCol {
+    const CF_NAME: &'static str = "object_versions";
+    type Key = ObjectVersionKey;
+    type Value = ObjectVersion;
+}
+
+/// The null version of each key in a versioned bucket: the object that
+/// predates versioning, or the object or delete marker last written while
+/// versioning was suspended. S3 clients address it as version `null`.
+///
+/// Key: `ObjectListKey` (`[bucket 32B][name]`).
+/// Value: the id of that version in `object_versions`.
+pub struct ObjectNullVersionCol;
+
+impl Column for ObjectNullVersionCol {
+    const CF_NAME: &'static str = "object_null_versions";
+    type Key = ObjectListKey;
+    type Value = ObjectVersionId;
+}
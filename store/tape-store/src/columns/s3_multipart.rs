@@ -2,7 +2,9 @@
 
 use store::Column;
 
-use crate::types::{MultipartPart, MultipartPartData, MultipartPartKey, MultipartUpload};
+use crate::types::{
+    MultipartPart, MultipartPartChunks, MultipartPartData, MultipartPartKey, MultipartUpload,
+};
 
 /// In-flight multipart uploads, keyed by opaque upload id.
 pub struct S3MultipartUploadCol;
@@ -13,7 +15,7 @@ impl Column for S3MultipartUploadCol {
     type Value = MultipartUpload;
 }
 
-/// Buffered multipart part metadata, keyed by `(upload, part_number)` so an
+/// Multipart part metadata, keyed by `(upload, part_number)` so an
 /// upload's parts scan together without reading any payload bytes.
 pub struct S3MultipartPartCol;
 
@@ -23,7 +25,18 @@ impl Column for S3MultipartPartCol {
     type Value = MultipartPart;
 }
 
+/// Chunk tracks each multipart part was written to, keyed identically to the
+/// part's metadata.
+pub struct S3MultipartPartChunksCol;
+
+impl Column for S3MultipartPartChunksCol {
+    const CF_NAME: &'static str = "s3_multipart_part_chunks";
+    type Key = MultipartPartKey;
+    type Value = MultipartPartChunks;
+}
+
 /// Buffered multipart part payloads, keyed identically to their metadata.
+/// Only parts uploaded before parts went straight to chunk tracks have one.
 pub struct S3MultipartPartDataCol;
 
 impl Column for S3MultipartPartDataCol {
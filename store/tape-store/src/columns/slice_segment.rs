@@ -0,0 +1,19 @@
+//! Staged slice segments
+//!
+//! Key structure: (spool_id, track_address, segment) - prefixed by the slice key
+
+use crate::types::{SliceSegmentKey, SliceValue};
+use store::Column;
+
+/// Verified segments of a slice still being uploaded, folded into `slice`
+/// once the last one lands
+///
+/// Key: SliceSegmentKey (36 bytes: spool_id BE + track_address + segment BE)
+/// Value: SliceValue (raw segment data)
+pub struct SliceSegmentCol;
+
+impl Column for SliceSegmentCol {
+    const CF_NAME: &'static str = "slice_segment";
+    type Key = SliceSegmentKey;
+    type Value = SliceValue;
+}
@@ -4,10 +4,13 @@
 //! - SpoolPendingRepairCol: (spool_id, track_address) -> ()
 //! - SpoolPendingRecoveryCol: (spool_id, track_address) -> ()
 //! - SpoolSyncCursorCol: spool_id -> Address (last synced track)
+//! - SpoolScrubCursorCol: spool_id -> Address (last scrubbed track)
+//! - SpoolQuarantineCol: (spool_id, track_address) -> EpochNumber
 
 use store::Column;
 use tape_crypto::address::Address;
 use tape_core::system::SpoolState;
+use tape_core::types::EpochNumber;
 
 use crate::types::{SliceKey, SpoolIndexKey};
 
@@ -58,3 +61,27 @@ impl Column for SpoolSyncCursorCol {
     type Key = SpoolIndexKey;
     type Value = Address;
 }
+
+/// Spool scrub cursor tracking
+///
+/// Key: SpoolIndexKey (2 bytes: spool_id BE)
+/// Value: Address (last scrubbed track address)
+pub struct SpoolScrubCursorCol;
+
+impl Column for SpoolScrubCursorCol {
+    const CF_NAME: &'static str = "spool_scrub_cursor";
+    type Key = SpoolIndexKey;
+    type Value = Address;
+}
+
+/// Slices dropped by the scrubber after failing verification
+///
+/// Key: SliceKey (34 bytes: spool_id BE + track_address)
+/// Value: EpochNumber (epoch the corruption was found in)
+pub struct SpoolQuarantineCol;
+
+impl Column for SpoolQuarantineCol {
+    const CF_NAME: &'static str = "spool_quarantine";
+    type Key = SliceKey;
+    type Value = EpochNumber;
+}
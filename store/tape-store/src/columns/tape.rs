@@ -3,7 +3,7 @@
 use store::Column;
 use tape_crypto::address::Address;
 
-use crate::types::TapeInfo;
+use crate::types::{OwnerTapeKey, TapeInfo, UnitKey};
 
 /// Tape info indexed by tape address
 ///
@@ -16,3 +16,27 @@ impl Column for TapeCol {
     type Key = Address;
     type Value = TapeInfo;
 }
+
+/// Current owner authority per tape, following transfers.
+///
+/// Key: Address (tape_address, 32 bytes)
+/// Value: Address (owner authority)
+pub struct TapeOwnerCol;
+
+impl Column for TapeOwnerCol {
+    const CF_NAME: &'static str = "tape_owner";
+    type Key = Address;
+    type Value = Address;
+}
+
+/// Owner-first index of the tapes each authority holds.
+///
+/// Key: OwnerTapeKey (owner, tape)
+/// Value: UnitKey (marker only; the owner of record lives in `tape_owner`)
+pub struct OwnerTapeCol;
+
+impl Column for OwnerTapeCol {
+    const CF_NAME: &'static str = "owner_tape";
+    type Key = OwnerTapeKey;
+    type Value = UnitKey;
+}
@@ -41,6 +41,8 @@ use rocksdb;
 /// - `spool_pending_repair` - 34-byte SliceKey with 2-byte spool prefix
 /// - `spool_pending_recovery` - 34-byte SliceKey with 2-byte spool prefix
 /// - `spool_sync_cursor` - 2-byte SpoolIndexKey
+/// - `spool_scrub_cursor` - 2-byte SpoolIndexKey
+/// - `spool_quarantine` - 34-byte SliceKey with 2-byte spool prefix
 ///
 /// ## Slice Data Column (BlobDB)
 /// - `slice` - 34-byte SliceKey, large (~1MB) values (BlobDB with 2-byte prefix)
@@ -149,11 +151,31 @@ pub fn create_tape_store_configs() -> Vec<ColumnFamilyDescriptor> {
             .with_prefix_extractor(2)
             .build(),
 
+        // Staged slice segments - 36-byte SliceSegmentKey, segment payloads
+        // 2-byte spool prefix for iteration by spool
+        // Short-lived, so kept inline rather than in blob files
+        ColumnFamilyConfig::new("slice_segment")
+            .with_block_based()
+            .with_prefix_extractor(2)
+            .build(),
+
         // Spool sync progress - 2-byte SpoolIndexKey
         ColumnFamilyConfig::new("spool_sync_cursor")
             .with_block_based()
             .build(),
 
+        // Spool scrub progress - 2-byte SpoolIndexKey
+        ColumnFamilyConfig::new("spool_scrub_cursor")
+            .with_block_based()
+            .build(),
+
+        // Quarantined slices - 34-byte SliceKey
+        // 2-byte spool prefix for iteration by spool
+        ColumnFamilyConfig::new("spool_quarantine")
+            .with_block_based()
+            .with_prefix_extractor(2)
+            .build(),
+
         // Event log - 20-byte EventLogKey (epoch 8B + slot 8B + seq 4B)
         // 8-byte epoch prefix for efficient per-epoch scanning and deletion
         ColumnFamilyConfig::new("event_log")
@@ -218,12 +240,61 @@ pub fn create_tape_store_configs() -> Vec<ColumnFamilyDescriptor> {
             .with_prefix_extractor(32)
             .build(),
 
+        // S3 multipart part chunk tracks keyed identically to their metadata;
+        // small values.
+        ColumnFamilyConfig::new("s3_multipart_part_chunks")
+            .with_block_based()
+            .with_prefix_extractor(32)
+            .build(),
+
         // S3 multipart part payloads keyed identically to their metadata; large
         // values held only until completion/abort.
         ColumnFamilyConfig::new("s3_multipart_part_data")
             .with_blob_db(256 * 1024)
             .with_prefix_extractor(32)
             .build(),
+
+        // Bucket versioning - per-bucket status keyed by 32-byte tape Address.
+        ColumnFamilyConfig::new("bucket_versioning")
+            .with_block_based()
+            .build(),
+
+        // Object versions - per-key version history
+        // ([bucket 32B][name var][0x00][version 12B]); 32-byte bucket prefix
+        // for per-bucket scans, like object_list.
+        ColumnFamilyConfig::new("object_versions")
+            .with_block_based()
+            .with_prefix_extractor(32)
+            .build(),
+
+        // Object null versions - per-key null version id ([bucket 32B][name
+        // var]); 32-byte bucket prefix, like object_list.
+        ColumnFamilyConfig::new("object_null_versions")
+            .with_block_based()
+            .with_prefix_extractor(32)
+            .build(),
+
+        // Bucket lifecycle - per-bucket rules keyed by 32-byte tape Address.
+        ColumnFamilyConfig::new("bucket_lifecycle")
+            .with_block_based()
+            .build(),
+
+        // Bucket access - per-bucket read access keyed by 32-byte tape Address.
+        ColumnFamilyConfig::new("bucket_access")
+            .with_block_based()
+            .build(),
+
+        // Tape owner - current owner authority keyed by 32-byte tape Address.
+        ColumnFamilyConfig::new("tape_owner")
+            .with_block_based()
+            .build(),
+
+        // Owner tape - tapes per owner ([owner 32B][tape 32B]); 32-byte owner
+        // prefix for per-owner scans.
+        ColumnFamilyConfig::new("owner_tape")
+            .with_block_based()
+            .with_prefix_extractor(32)
+            .build(),
     ]
 }
 
@@ -237,10 +308,11 @@ pub const BULK_SUBDIR: &str = "bulk";
 ///
 /// The slice and snapshot families use key-value separation; track data is
 /// stored inline but can be large. Everything else is small metadata that
-/// stays on the fast volume. The slice size index is small, but it rides along
-/// on the bulk volume because a write batch cannot span the two databases.
+/// stays on the fast volume. The slice size index and staged segments are
+/// small, but they ride along on the bulk volume because a write batch cannot
+/// span the two databases.
 pub const BULK_COLUMN_FAMILIES: &[&str] =
-    &["track_data", "slice", "slice_size", "snapshot_artifact"];
+    &["track_data", "slice", "slice_size", "slice_segment", "snapshot_artifact"];
 
 /// Column family configurations for the metadata (fast volume) store
 pub fn create_metadata_store_configs() -> Vec<ColumnFamilyDescriptor> {
@@ -316,7 +388,7 @@ mod tests {
     #[test]
     fn test_config_count() {
         let configs = create_tape_store_configs();
-        assert_eq!(configs.len(), 28);
+        assert_eq!(configs.len(), 37);
     }
 
     #[test]
@@ -340,7 +412,10 @@ mod tests {
             "spool_pending_recovery",
             "slice",
             "slice_size",
+            "slice_segment",
             "spool_sync_cursor",
+            "spool_scrub_cursor",
+            "spool_quarantine",
             "event_log",
             "vote_sig",
             "snapshot_artifact",
@@ -352,7 +427,15 @@ mod tests {
             "ledger_reservation",
             "s3_multipart_upload",
             "s3_multipart_part",
+            "s3_multipart_part_chunks",
             "s3_multipart_part_data",
+            "bucket_versioning",
+            "object_versions",
+            "object_null_versions",
+            "bucket_lifecycle",
+            "bucket_access",
+            "tape_owner",
+            "owner_tape",
         ];
 
         assert_eq!(names, expected);
@@ -378,7 +461,10 @@ mod tests {
 
         // The two volumes partition every column family with no overlap.
         assert_eq!(meta.len() + bulk.len(), create_tape_store_configs().len());
-        assert_eq!(bulk, vec!["track_data", "slice", "slice_size", "snapshot_artifact"]);
+        assert_eq!(
+            bulk,
+            vec!["track_data", "slice", "slice_size", "slice_segment", "snapshot_artifact"]
+        );
         assert!(meta.iter().all(|cf| !BULK_COLUMN_FAMILIES.contains(&cf.as_str())));
     }
 }
@@ -38,6 +38,14 @@ pub enum TapeStoreError {
     /// Serialization error
     #[error("Serialization error: {0}")]
     Serialization(String),
+
+    /// The store was written under a newer schema than this build reads
+    #[error("Store schema version {found} is newer than supported version {supported}")]
+    SchemaTooNew { found: u32, supported: u32 },
+
+    /// A checkpoint or store belongs to a different node or cluster
+    #[error("Store identity mismatch: {0}")]
+    IdentityMismatch(String),
 }
 
 /// Result type for tape-store operations
@@ -28,6 +28,8 @@
 //! - `spool_pending_repair`: Pending repair queue
 //! - `spool_pending_recovery`: Pending recovery queue
 //! - `spool_sync_cursor`: Sync cursor
+//! - `spool_scrub_cursor`: Scrub cursor
+//! - `spool_quarantine`: Quarantined corrupt slices
 //!
 //! ## Slice Data Column (BlobDB)
 //! - `slice`: Erasure-coded slice data
@@ -35,13 +37,14 @@
 pub mod columns;
 pub mod config;
 pub mod error;
+pub mod migration;
 pub mod ops;
+pub mod restore;
 pub mod stats;
 pub mod types;
 
 use std::path::Path;
 
-use ops::SliceOps;
 use store::{Store, TypedStore};
 use store_rocks::{RocksStore, SplitStore};
 
@@ -125,6 +128,24 @@ impl TapeStore<SplitStore> {
         meta_dir: P,
         bulk_dir: P,
         compaction_rate_limit_mb_per_sec: u64,
+    ) -> Result<Self, store::Error> {
+        Self::open_primary_split_with_progress(
+            meta_dir,
+            bulk_dir,
+            compaction_rate_limit_mb_per_sec,
+            &mut |_| {},
+        )
+    }
+
+    /// Open a primary split store, reporting the progress of any schema
+    /// migration the open runs
+    ///
+    /// Fails on a store stamped with a schema version newer than this build's.
+    pub fn open_primary_split_with_progress<P: AsRef<Path>>(
+        meta_dir: P,
+        bulk_dir: P,
+        compaction_rate_limit_mb_per_sec: u64,
+        progress: &mut dyn FnMut(&migration::MigrationProgress),
     ) -> Result<Self, store::Error> {
         std::fs::create_dir_all(meta_dir.as_ref())?;
         std::fs::create_dir_all(bulk_dir.as_ref())?;
@@ -145,9 +166,7 @@ impl TapeStore<SplitStore> {
         )?;
         let store = Self::new(split_store(meta, bulk));
 
-        // A store written before the size index existed reports no slice totals
-        // until the index is laid down.
-        store.ensure_slice_size_index().map_err(|err| match err {
+        migration::migrate(&store, progress).map_err(|err| match err {
             error::TapeStoreError::Store(err) => err,
             other => store::Error::Database(other.to_string()),
         })?;
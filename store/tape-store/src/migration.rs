@@ -0,0 +1,739 @@
+//! Versioned schema migrations
+//!
+//! The meta column records the schema version a store was last brought up to.
+//! Opening a primary store runs every registered migration above that version,
+//! in order, and stamps the version as each one completes. A migration works
+//! in bounded steps and hands back a cursor after each; the cursor is saved in
+//! the meta column, so a node stopped mid-migration resumes from the last step
+//! rather than starting over.
+//!
+//! A step stages its writes into a batch rather than applying them, and the
+//! runner commits them together with the saved cursor, or with the version
+//! stamp on the last step. On one volume a step and its cursor therefore land
+//! or vanish as one. A split store commits each volume separately, so steps
+//! writing bulk columns must stay safe to replay.
+//!
+//! Stores written before versioning carry no version and start from 0. A store
+//! stamped with a version above [`SCHEMA_VERSION`] was written by a newer node
+//! and is refused, since this build cannot read its layout.
+
+use serde::{Deserialize, Serialize};
+use store::{Column, Direction, Store, WriteBatch};
+use tape_core::types::{ContentType, ObjectHeaders, SlotNumber, StorageUnits, TrackNumber};
+use tape_crypto::address::Address;
+use tape_crypto::Hash;
+use wincode_derive::{SchemaRead, SchemaWrite};
+
+use crate::columns::{
+    CredentialCol, ObjectListCol, ObjectMetadataCol, PolicyRuleCol, S3MultipartUploadCol,
+};
+use crate::error::{Result, TapeStoreError};
+use crate::ops::{
+    clear_slice_size_index, index_slice_sizes, slice_size_index_intact, stage_migration_cursor,
+    stage_schema_version, MetaOps,
+};
+use crate::types::{
+    Credential, CredentialCaps, CredentialScope, CredentialStatus, MultipartUpload, ObjectListEntry,
+    ObjectMetadata, PolicyAction, PolicyConditions, PolicyEffect, PolicyRule,
+};
+use crate::TapeStore;
+
+/// Schema version this build reads and writes
+pub const SCHEMA_VERSION: u32 = 4;
+
+/// Credentials rewritten per step of the v2 migration
+const CREDENTIAL_BATCH_LEN: usize = 1024;
+
+/// Policy rules rewritten per step of the v3 migration
+const POLICY_RULE_BATCH_LEN: usize = 1024;
+
+/// Object records rewritten per step of the v4 migration
+const OBJECT_HEADERS_BATCH_LEN: usize = 1024;
+
+/// Outcome of one migration step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationStep {
+    /// More work is left; resume after `cursor`
+    Continue { cursor: Vec<u8>, processed: u64 },
+    /// The migration is complete
+    Done { processed: u64 },
+}
+
+/// One registered migration
+pub struct Migration<S: Store> {
+    /// Schema version the store is at once this migration completes
+    pub version: u32,
+    /// Name shown in progress reports
+    pub name: &'static str,
+    /// Stage one bounded step into the batch, resuming after the cursor of
+    /// the previous one
+    pub step: fn(&TapeStore<S>, Option<&[u8]>, &mut WriteBatch) -> Result<MigrationStep>,
+}
+
+/// Progress report handed to the caller after every step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationProgress {
+    pub version: u32,
+    pub name: &'static str,
+    /// Items handled so far, across restarts
+    pub processed: u64,
+    pub done: bool,
+}
+
+/// Saved position of the migration in flight
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+struct MigrationCursor {
+    version: u32,
+    processed: u64,
+    cursor: Vec<u8>,
+}
+
+/// Every migration, ordered by the version it brings the store to
+///
+/// The last entry's version must equal [`SCHEMA_VERSION`].
+pub fn migrations<S: Store>() -> Vec<Migration<S>> {
+    vec![
+        Migration {
+            version: 1,
+            name: "slice size index",
+            step: slice_size_index,
+        },
+        Migration {
+            version: 2,
+            name: "credential read caps",
+            step: credential_read_caps,
+        },
+        Migration {
+            version: 3,
+            name: "policy rule conditions",
+            step: policy_rule_conditions,
+        },
+        Migration {
+            version: 4,
+            name: "object headers",
+            step: object_headers,
+        },
+    ]
+}
+
+/// Bring `store` up to [`SCHEMA_VERSION`], reporting progress after each step
+///
+/// Returns the version the store was at before.
+pub fn migrate<S: Store>(
+    store: &TapeStore<S>,
+    progress: &mut dyn FnMut(&MigrationProgress),
+) -> Result<u32> {
+    run_migrations(store, &migrations(), progress)
+}
+
+fn run_migrations<S: Store>(
+    store: &TapeStore<S>,
+    migrations: &[Migration<S>],
+    progress: &mut dyn FnMut(&MigrationProgress),
+) -> Result<u32> {
+    let supported = migrations.last().map_or(0, |migration| migration.version);
+    let found = store.get_schema_version()?.unwrap_or(0);
+    if found > supported {
+        return Err(TapeStoreError::SchemaTooNew { found, supported });
+    }
+
+    for migration in migrations.iter().filter(|migration| migration.version > found) {
+        // A cursor left by an earlier migration, or by one that completed
+        // just before its cursor was dropped, does not apply here.
+        let saved = match store.get_migration_cursor()? {
+            Some(bytes) => Some(decode_cursor(&bytes)?),
+            None => None,
+        }
+        .filter(|saved| saved.version == migration.version);
+
+        let mut processed = saved.as_ref().map_or(0, |saved| saved.processed);
+        let mut cursor = saved.map(|saved| saved.cursor);
+        loop {
+            let mut batch = WriteBatch::new();
+            let (next, done) = match (migration.step)(store, cursor.as_deref(), &mut batch)? {
+                MigrationStep::Continue { cursor, processed: step } => {
+                    processed += step;
+                    (Some(cursor), false)
+                }
+                MigrationStep::Done { processed: step } => {
+                    processed += step;
+                    (None, true)
+                }
+            };
+
+            match &next {
+                Some(next) => {
+                    let saved = encode_cursor(&MigrationCursor {
+                        version: migration.version,
+                        processed,
+                        cursor: next.clone(),
+                    })?;
+                    stage_migration_cursor(&mut batch, Some(saved))?;
+                }
+                None => {
+                    stage_schema_version(&mut batch, migration.version)?;
+                    stage_migration_cursor(&mut batch, None)?;
+                }
+            }
+            store.inner().inner().write_batch(batch)?;
+            progress(&MigrationProgress {
+                version: migration.version,
+                name: migration.name,
+                processed,
+                done,
+            });
+
+            if done {
+                break;
+            }
+            cursor = next;
+        }
+    }
+
+    Ok(found)
+}
+
+fn encode_cursor(cursor: &MigrationCursor) -> Result<Vec<u8>> {
+    wincode::serialize(cursor)
+        .map_err(|e| TapeStoreError::Serialization(format!("migration cursor: {}", e)))
+}
+
+fn decode_cursor(bytes: &[u8]) -> Result<MigrationCursor> {
+    wincode::deserialize(bytes)
+        .map_err(|e| TapeStoreError::Serialization(format!("migration cursor: {}", e)))
+}
+
+/// v1: lay down the slice size index on stores written before it existed
+///
+/// The first step clears whatever partial index is present and indexes the
+/// first batch of slices; later steps index one batch each. Rewriting a size
+/// entry is harmless, so a replayed step is too.
+fn slice_size_index<S: Store>(
+    store: &TapeStore<S>,
+    after: Option<&[u8]>,
+    batch: &mut WriteBatch,
+) -> Result<MigrationStep> {
+    if after.is_none() {
+        if slice_size_index_intact(store)? {
+            return Ok(MigrationStep::Done { processed: 0 });
+        }
+        clear_slice_size_index(store, batch)?;
+    }
+
+    Ok(match index_slice_sizes(store, after, batch)? {
+        (Some(cursor), processed) => MigrationStep::Continue { cursor, processed },
+        (None, processed) => MigrationStep::Done { processed },
+    })
+}
+
+/// Caps as stored before credentials could carry read caps
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+struct LegacyCredentialCaps {
+    can_put: bool,
+    can_delete: bool,
+    can_multipart: bool,
+}
+
+/// Credential layout before v2
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+struct LegacyCredential {
+    secret_hmac: [u8; 32],
+    principal: Address,
+    scope: CredentialScope,
+    caps: LegacyCredentialCaps,
+    status: CredentialStatus,
+    not_after: Option<i64>,
+    grade: Option<String>,
+}
+
+/// v2: re-encode credentials with the read caps added to [`CredentialCaps`]
+///
+/// Every bucket was public-read before, so existing keys get both read caps
+/// and keep reading what they could. Each step rewrites one batch of
+/// credentials; the credential column sits on the metadata volume, so a step
+/// commits with its cursor and is never replayed over re-encoded rows.
+fn credential_read_caps<S: Store>(
+    store: &TapeStore<S>,
+    after: Option<&[u8]>,
+    batch: &mut WriteBatch,
+) -> Result<MigrationStep> {
+    let raw = store.inner().inner();
+    let entries = match after {
+        Some(after) => raw.iter_from(CredentialCol::CF_NAME, after, Direction::Asc)?,
+        None => raw.iter(CredentialCol::CF_NAME)?,
+    };
+
+    let mut last = None;
+    let mut processed = 0u64;
+    for (key_bytes, value_bytes) in entries {
+        if after == Some(key_bytes.as_slice()) {
+            continue;
+        }
+        let legacy: LegacyCredential = wincode::deserialize(&value_bytes)
+            .map_err(|e| TapeStoreError::Serialization(format!("legacy credential: {}", e)))?;
+        let credential = Credential {
+            secret_hmac: legacy.secret_hmac,
+            principal: legacy.principal,
+            scope: legacy.scope,
+            caps: CredentialCaps {
+                can_put: legacy.caps.can_put,
+                can_delete: legacy.caps.can_delete,
+                can_multipart: legacy.caps.can_multipart,
+                can_get: true,
+                can_list: true,
+            },
+            status: legacy.status,
+            not_after: legacy.not_after,
+            grade: legacy.grade,
+        };
+        let value_bytes = wincode::serialize(&credential)
+            .map_err(|e| TapeStoreError::Serialization(format!("credential: {}", e)))?;
+        last = Some(key_bytes.clone());
+        batch.put_owned(CredentialCol::CF_NAME, key_bytes, value_bytes);
+        processed += 1;
+        if processed == CREDENTIAL_BATCH_LEN as u64 {
+            break;
+        }
+    }
+
+    Ok(match last {
+        Some(cursor) => MigrationStep::Continue { cursor, processed },
+        None => MigrationStep::Done { processed },
+    })
+}
+
+/// Policy rule layout before v3
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+struct LegacyPolicyRule {
+    principal: Option<Address>,
+    bucket: Option<Address>,
+    action: PolicyAction,
+    effect: PolicyEffect,
+    reason: String,
+}
+
+/// v3: re-encode policy rules with the empty [`PolicyConditions`]
+///
+/// A rule with no conditions matches exactly what it matched before. Like
+/// v2, the rule column is on the metadata volume, so each step lands with its
+/// cursor.
+fn policy_rule_conditions<S: Store>(
+    store: &TapeStore<S>,
+    after: Option<&[u8]>,
+    batch: &mut WriteBatch,
+) -> Result<MigrationStep> {
+    let raw = store.inner().inner();
+    let entries = match after {
+        Some(after) => raw.iter_from(PolicyRuleCol::CF_NAME, after, Direction::Asc)?,
+        None => raw.iter(PolicyRuleCol::CF_NAME)?,
+    };
+
+    let mut last = None;
+    let mut processed = 0u64;
+    for (key_bytes, value_bytes) in entries {
+        if after == Some(key_bytes.as_slice()) {
+            continue;
+        }
+        let legacy: LegacyPolicyRule = wincode::deserialize(&value_bytes)
+            .map_err(|e| TapeStoreError::Serialization(format!("legacy policy rule: {}", e)))?;
+        let rule = PolicyRule {
+            principal: legacy.principal,
+            bucket: legacy.bucket,
+            action: legacy.action,
+            effect: legacy.effect,
+            reason: legacy.reason,
+            conditions: PolicyConditions::default(),
+        };
+        let value_bytes = wincode::serialize(&rule)
+            .map_err(|e| TapeStoreError::Serialization(format!("policy rule: {}", e)))?;
+        last = Some(key_bytes.clone());
+        batch.put_owned(PolicyRuleCol::CF_NAME, key_bytes, value_bytes);
+        processed += 1;
+        if processed == POLICY_RULE_BATCH_LEN as u64 {
+            break;
+        }
+    }
+
+    Ok(match last {
+        Some(cursor) => MigrationStep::Continue { cursor, processed },
+        None => MigrationStep::Done { processed },
+    })
+}
+
+/// Object listing entry layout before v4
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+struct LegacyObjectListEntry {
+    size: StorageUnits,
+    etag: Hash,
+    block_time: Option<i64>,
+    slot: SlotNumber,
+    data_tape: Address,
+    track_number: TrackNumber,
+    kind: u64,
+    content_type: ContentType,
+}
+
+/// Object metadata layout before v4
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+struct LegacyObjectMetadata {
+    name: Vec<u8>,
+    content_type: ContentType,
+}
+
+/// Multipart upload layout before v4, when only the hot content type was kept
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+struct LegacyMultipartUpload {
+    bucket: Address,
+    key: String,
+    content_type: ContentType,
+    initiated: i64,
+    principal: Address,
+}
+
+/// Columns the v4 migration rewrites, in the order it walks them
+const OBJECT_HEADER_COLUMNS: [&str; 3] = [
+    ObjectListCol::CF_NAME,
+    ObjectMetadataCol::CF_NAME,
+    S3MultipartUploadCol::CF_NAME,
+];
+
+/// v4: re-encode object listings, object metadata and multipart uploads with
+/// [`ObjectHeaders`]
+///
+/// Each record gets the headers a write of its hot content type stores today,
+/// so reads serve the same `Content-Type` as before. The three columns are
+/// walked in turn, and the cursor is the column's position followed by the
+/// last key rewritten there. All three sit on the metadata volume, so each
+/// step lands with its cursor.
+fn object_headers<S: Store>(
+    store: &TapeStore<S>,
+    after: Option<&[u8]>,
+    batch: &mut WriteBatch,
+) -> Result<MigrationStep> {
+    let (mut column, mut after) = match after {
+        Some([column, key @ ..]) => (*column as usize, Some(key)),
+        _ => (0, None),
+    };
+
+    while let Some(&cf) = OBJECT_HEADER_COLUMNS.get(column) {
+        let (last, processed) = match column {
+            0 => reencode_rows(store, cf, after, batch, |legacy: LegacyObjectListEntry| {
+                ObjectListEntry {
+                    size: legacy.size,
+                    etag: legacy.etag,
+                    block_time: legacy.block_time,
+                    slot: legacy.slot,
+                    data_tape: legacy.data_tape,
+                    track_number: legacy.track_number,
+                    kind: legacy.kind,
+                    content_type: legacy.content_type,
+                    headers: ObjectHeaders::from(legacy.content_type),
+                }
+            })?,
+            1 => reencode_rows(store, cf, after, batch, |legacy: LegacyObjectMetadata| {
+                ObjectMetadata {
+                    name: legacy.name,
+                    content_type: legacy.content_type,
+                    headers: ObjectHeaders::from(legacy.content_type),
+                }
+            })?,
+            _ => reencode_rows(store, cf, after, batch, |legacy: LegacyMultipartUpload| {
+                MultipartUpload {
+                    bucket: legacy.bucket,
+                    key: legacy.key,
+                    headers: ObjectHeaders::from(legacy.content_type),
+                    initiated: legacy.initiated,
+                    principal: legacy.principal,
+                }
+            })?,
+        };
+
+        if let Some(last) = last {
+            let mut cursor = vec![column as u8];
+            cursor.extend_from_slice(&last);
+            return Ok(MigrationStep::Continue { cursor, processed });
+        }
+        column += 1;
+        after = None;
+    }
+
+    Ok(MigrationStep::Done { processed: 0 })
+}
+
+/// Stage up to one batch of `cf` rows after `after`, decoded as `Legacy` and
+/// written back as `Current`
+///
+/// Returns the last key staged, `None` once the column is exhausted, and how
+/// many rows were staged.
+fn reencode_rows<S, Legacy, Current>(
+    store: &TapeStore<S>,
+    cf: &'static str,
+    after: Option<&[u8]>,
+    batch: &mut WriteBatch,
+    convert: impl Fn(Legacy) -> Current,
+) -> Result<(Option<Vec<u8>>, u64)>
+where
+    S: Store,
+    Legacy: for<'de> wincode::SchemaRead<'de, Dst = Legacy>,
+    Current: wincode::SchemaWrite<Src = Current>,
+{
+    let raw = store.inner().inner();
+    let entries = match after {
+        Some(after) => raw.iter_from(cf, after, Direction::Asc)?,
+        None => raw.iter(cf)?,
+    };
+
+    let mut last = None;
+    let mut processed = 0u64;
+    for (key_bytes, value_bytes) in entries {
+        if after == Some(key_bytes.as_slice()) {
+            continue;
+        }
+        let legacy: Legacy = wincode::deserialize(&value_bytes)
+            .map_err(|e| TapeStoreError::Serialization(format!("legacy {}: {}", cf, e)))?;
+        let value_bytes = wincode::serialize(&convert(legacy))
+            .map_err(|e| TapeStoreError::Serialization(format!("{}: {}", cf, e)))?;
+        last = Some(key_bytes.clone());
+        batch.put_owned(cf, key_bytes, value_bytes);
+        processed += 1;
+        if processed == OBJECT_HEADERS_BATCH_LEN as u64 {
+            break;
+        }
+    }
+
+    Ok((last, processed))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+    use crate::ops::{
+        CredentialOps, MultipartOps, ObjectListOps, ObjectMetadataOps, PolicyOps, SliceOps,
+    };
+    use crate::types::{ObjectListKey, PolicyRuleKey};
+    use store_memory::MemoryStore;
+    use tape_core::types::SpoolIndex;
+
+    fn test_store() -> TapeStore<MemoryStore> {
+        TapeStore::new(MemoryStore::new())
+    }
+
+    fn drop_size_index(store: &TapeStore<MemoryStore>) {
+        let mut batch = WriteBatch::new();
+        clear_slice_size_index(store, &mut batch).unwrap();
+        store.inner().inner().write_batch(batch).unwrap();
+        assert_eq!(store.slice_totals().unwrap(), (0, StorageUnits(0)));
+    }
+
+    // an unversioned store is brought up to date and stamped
+    #[test]
+    fn migrates_unversioned() {
+        let store = test_store();
+        store.put_slice(SpoolIndex(1), Address::new_unique(), vec![0; 100]).unwrap();
+        drop_size_index(&store);
+
+        let mut reports = Vec::new();
+        let found = migrate(&store, &mut |report| reports.push(*report)).unwrap();
+
+        assert_eq!(found, 0);
+        assert_eq!(store.get_schema_version().unwrap(), Some(SCHEMA_VERSION));
+        assert_eq!(store.get_migration_cursor().unwrap(), None);
+        assert_eq!(store.slice_totals().unwrap(), (1, StorageUnits(100)));
+        assert!(reports.last().unwrap().done);
+
+        // Nothing is left to run on the next open.
+        reports.clear();
+        let found = migrate(&store, &mut |report| reports.push(*report)).unwrap();
+        assert_eq!(found, SCHEMA_VERSION);
+        assert!(reports.is_empty());
+    }
+
+    // a store stamped by a newer build is refused
+    #[test]
+    fn refuses_newer() {
+        let store = test_store();
+        store.set_schema_version(SCHEMA_VERSION + 1).unwrap();
+
+        let result = migrate(&store, &mut |_| {});
+        assert!(matches!(result, Err(TapeStoreError::SchemaTooNew { .. })));
+    }
+
+    // a migration stopped partway resumes from its saved cursor
+    #[test]
+    fn resumes() {
+        static INTERRUPT: AtomicBool = AtomicBool::new(true);
+
+        // Steps through cursors 1..=3, failing once on the way to 2.
+        fn counting(
+            _: &TapeStore<MemoryStore>,
+            after: Option<&[u8]>,
+            _: &mut WriteBatch,
+        ) -> Result<MigrationStep> {
+            let next = after.map_or(0, |after| after[0]) + 1;
+            if next == 2 && INTERRUPT.swap(false, Ordering::SeqCst) {
+                return Err(TapeStoreError::Serialization("interrupted".into()));
+            }
+            Ok(match next {
+                4 => MigrationStep::Done { processed: 1 },
+                _ => MigrationStep::Continue {
+                    cursor: vec![next],
+                    processed: 1,
+                },
+            })
+        }
+
+        let store = test_store();
+        let registry = vec![Migration {
+            version: 1,
+            name: "counting",
+            step: counting,
+        }];
+        assert!(run_migrations(&store, &registry, &mut |_| {}).is_err());
+        assert_eq!(store.get_schema_version().unwrap(), None);
+
+        let mut reports = Vec::new();
+        run_migrations(&store, &registry, &mut |report| reports.push(*report)).unwrap();
+
+        // The first step after the restart is the second overall.
+        assert_eq!(reports.first().unwrap().processed, 2);
+        assert_eq!(reports.last().unwrap().processed, 4);
+        assert!(reports.last().unwrap().done);
+        assert_eq!(store.get_schema_version().unwrap(), Some(1));
+    }
+
+    // credentials stored before read caps keep their write caps and gain reads
+    #[test]
+    fn credential_read_caps_added() {
+        let store = test_store();
+        let legacy = LegacyCredential {
+            secret_hmac: [0x11; 32],
+            principal: Address::new_unique(),
+            scope: CredentialScope::AnyOwned,
+            caps: LegacyCredentialCaps {
+                can_put: true,
+                can_delete: false,
+                can_multipart: true,
+            },
+            status: CredentialStatus::Active,
+            not_after: Some(99),
+            grade: None,
+        };
+        let key = wincode::serialize(&"AKIDLEGACY".to_string()).unwrap();
+        let value = wincode::serialize(&legacy).unwrap();
+        store.inner().inner().put(CredentialCol::CF_NAME, &key, &value).unwrap();
+        store.set_schema_version(1).unwrap();
+
+        assert_eq!(migrate(&store, &mut |_| {}).unwrap(), 1);
+        let credential = store.get_credential("AKIDLEGACY").unwrap().unwrap();
+        assert_eq!(
+            credential.caps,
+            CredentialCaps {
+                can_put: true,
+                can_delete: false,
+                can_multipart: true,
+                can_get: true,
+                can_list: true,
+            }
+        );
+        assert_eq!(credential.principal, legacy.principal);
+        assert_eq!(credential.not_after, Some(99));
+        assert_eq!(store.get_schema_version().unwrap(), Some(SCHEMA_VERSION));
+    }
+
+    // policy rules stored before conditions keep their match and gain none
+    #[test]
+    fn policy_rule_conditions_added() {
+        let store = test_store();
+        let legacy = LegacyPolicyRule {
+            principal: Some(Address::new_unique()),
+            bucket: None,
+            action: PolicyAction::Put,
+            effect: PolicyEffect::Deny,
+            reason: "frozen".to_string(),
+        };
+        let key = PolicyRuleKey::new(5, 1);
+        let key_bytes = wincode::serialize(&key).unwrap();
+        let value = wincode::serialize(&legacy).unwrap();
+        store.inner().inner().put(PolicyRuleCol::CF_NAME, &key_bytes, &value).unwrap();
+        store.set_schema_version(2).unwrap();
+
+        assert_eq!(migrate(&store, &mut |_| {}).unwrap(), 2);
+        let rules = store.list_policy_rules().unwrap();
+        assert_eq!(rules.len(), 1);
+        let (listed, rule) = &rules[0];
+        assert_eq!(*listed, key);
+        assert_eq!(rule.principal, legacy.principal);
+        assert_eq!(rule.effect, PolicyEffect::Deny);
+        assert_eq!(rule.reason, "frozen");
+        assert_eq!(rule.conditions, PolicyConditions::default());
+        assert_eq!(store.get_schema_version().unwrap(), Some(SCHEMA_VERSION));
+    }
+
+    // object records stored with only the hot content type gain its headers
+    #[test]
+    fn object_headers_added() {
+        let store = test_store();
+        let raw = store.inner().inner();
+        let bucket = Address::new_unique();
+        let track = Address::new_unique();
+
+        // More listings than one step rewrites, so the walk crosses steps
+        // within a column as well as between columns.
+        let entries = OBJECT_HEADERS_BATCH_LEN + 1;
+        for n in 0..entries {
+            let legacy = LegacyObjectListEntry {
+                size: StorageUnits(n as u64),
+                etag: Hash::default(),
+                block_time: Some(7),
+                slot: SlotNumber(9),
+                data_tape: bucket,
+                track_number: TrackNumber(n as u64),
+                kind: 1,
+                content_type: ContentType::ImageJpeg,
+            };
+            let key = wincode::serialize(&ObjectListKey::new(bucket, format!("{n:05}"))).unwrap();
+            raw.put(ObjectListCol::CF_NAME, &key, &wincode::serialize(&legacy).unwrap()).unwrap();
+        }
+
+        let legacy = LegacyObjectMetadata {
+            name: b"photos/cat.jpg".to_vec(),
+            content_type: ContentType::Unknown,
+        };
+        let key = wincode::serialize(&track).unwrap();
+        raw.put(ObjectMetadataCol::CF_NAME, &key, &wincode::serialize(&legacy).unwrap()).unwrap();
+
+        let legacy = LegacyMultipartUpload {
+            bucket,
+            key: "obj".to_string(),
+            content_type: ContentType::TextPlain,
+            initiated: 1_000,
+            principal: Address::new_unique(),
+        };
+        let key = wincode::serialize(&"upload-1".to_string()).unwrap();
+        let value = wincode::serialize(&legacy).unwrap();
+        raw.put(S3MultipartUploadCol::CF_NAME, &key, &value).unwrap();
+        store.set_schema_version(3).unwrap();
+
+        let mut reports = Vec::new();
+        assert_eq!(migrate(&store, &mut |report| reports.push(*report)).unwrap(), 3);
+        assert_eq!(reports.last().unwrap().processed, entries as u64 + 2);
+        assert_eq!(store.get_schema_version().unwrap(), Some(SCHEMA_VERSION));
+
+        let entry = store.get_object_entry(bucket, format!("{:05}", entries - 1).as_bytes());
+        let entry = entry.unwrap().unwrap();
+        assert_eq!(entry.track_number, TrackNumber(entries as u64 - 1));
+        assert_eq!(entry.content_type, ContentType::ImageJpeg);
+        assert_eq!(entry.headers, ObjectHeaders::with_content_type("image/jpeg"));
+
+        let metadata = store.get_object_metadata(track).unwrap().unwrap();
+        assert_eq!(metadata.name, b"photos/cat.jpg");
+        assert_eq!(metadata.headers, ObjectHeaders::default());
+
+        let upload = store.get_multipart_upload("upload-1").unwrap().unwrap();
+        assert_eq!(upload.key, "obj");
+        assert_eq!(upload.initiated, 1_000);
+        assert_eq!(upload.headers, ObjectHeaders::with_content_type("text/plain"));
+    }
+}
@@ -0,0 +1,66 @@
+//! Per-bucket S3 read access.
+
+use store::Store;
+use tape_crypto::address::Address;
+
+use crate::columns::BucketAccessCol;
+use crate::error::Result;
+use crate::types::BucketAccess;
+use crate::TapeStore;
+
+/// Operations for bucket read access
+pub trait BucketAccessOps {
+    /// Fetch the read access of `bucket`; `None` when never set, which reads
+    /// as public.
+    fn get_bucket_access(&self, bucket: Address) -> Result<Option<BucketAccess>>;
+
+    /// Set the read access of `bucket`.
+    fn put_bucket_access(&self, bucket: Address, access: BucketAccess) -> Result<()>;
+
+    /// Forget the read access of `bucket`, leaving it public.
+    ///
+    /// Returns `true` when an access setting existed.
+    fn delete_bucket_access(&self, bucket: Address) -> Result<bool>;
+}
+
+impl<S: Store> BucketAccessOps for TapeStore<S> {
+    fn get_bucket_access(&self, bucket: Address) -> Result<Option<BucketAccess>> {
+        Ok(self.get::<BucketAccessCol>(&bucket)?)
+    }
+
+    fn put_bucket_access(&self, bucket: Address, access: BucketAccess) -> Result<()> {
+        self.put::<BucketAccessCol>(&bucket, &access)?;
+        Ok(())
+    }
+
+    fn delete_bucket_access(&self, bucket: Address) -> Result<bool> {
+        if self.contains::<BucketAccessCol>(&bucket)? {
+            self.delete::<BucketAccessCol>(&bucket)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store_memory::MemoryStore;
+
+    #[test]
+    fn put_get_delete() {
+        let s = TapeStore::new(MemoryStore::new());
+        let b = Address::new_unique();
+        assert!(s.get_bucket_access(b).unwrap().is_none());
+
+        s.put_bucket_access(b, BucketAccess::Private).unwrap();
+        assert_eq!(s.get_bucket_access(b).unwrap(), Some(BucketAccess::Private));
+        s.put_bucket_access(b, BucketAccess::Public).unwrap();
+        assert_eq!(s.get_bucket_access(b).unwrap(), Some(BucketAccess::Public));
+
+        assert!(s.delete_bucket_access(b).unwrap());
+        assert!(!s.delete_bucket_access(b).unwrap());
+        assert!(s.get_bucket_access(b).unwrap().is_none());
+    }
+}
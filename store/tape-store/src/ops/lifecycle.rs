@@ -0,0 +1,107 @@
+//! Per-bucket S3 lifecycle configuration.
+
+use store::Store;
+use tape_crypto::address::Address;
+
+use crate::columns::BucketLifecycleCol;
+use crate::error::Result;
+use crate::types::BucketLifecycle;
+use crate::TapeStore;
+
+/// Operations for bucket lifecycle rules
+pub trait LifecycleOps {
+    /// Fetch the lifecycle configuration of `bucket`; `None` when never set.
+    fn get_bucket_lifecycle(&self, bucket: Address) -> Result<Option<BucketLifecycle>>;
+
+    /// Replace the lifecycle configuration of `bucket`.
+    fn put_bucket_lifecycle(&self, bucket: Address, lifecycle: &BucketLifecycle) -> Result<()>;
+
+    /// Remove the lifecycle configuration of `bucket`.
+    ///
+    /// Returns `true` when a configuration existed.
+    fn delete_bucket_lifecycle(&self, bucket: Address) -> Result<bool>;
+
+    /// Every configured bucket with its rules, in bucket address order.
+    fn list_bucket_lifecycles(&self) -> Result<Vec<(Address, BucketLifecycle)>>;
+}
+
+impl<S: Store> LifecycleOps for TapeStore<S> {
+    fn get_bucket_lifecycle(&self, bucket: Address) -> Result<Option<BucketLifecycle>> {
+        Ok(self.get::<BucketLifecycleCol>(&bucket)?)
+    }
+
+    fn put_bucket_lifecycle(&self, bucket: Address, lifecycle: &BucketLifecycle) -> Result<()> {
+        self.put::<BucketLifecycleCol>(&bucket, lifecycle)?;
+        Ok(())
+    }
+
+    fn delete_bucket_lifecycle(&self, bucket: Address) -> Result<bool> {
+        if self.contains::<BucketLifecycleCol>(&bucket)? {
+            self.delete::<BucketLifecycleCol>(&bucket)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn list_bucket_lifecycles(&self) -> Result<Vec<(Address, BucketLifecycle)>> {
+        Ok(self.iter::<BucketLifecycleCol>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store_memory::MemoryStore;
+
+    use crate::types::LifecycleRule;
+
+    fn lifecycle(days: u32) -> BucketLifecycle {
+        BucketLifecycle {
+            rules: vec![LifecycleRule {
+                id: "expire".to_string(),
+                enabled: true,
+                prefix: String::new(),
+                tags: Vec::new(),
+                expiration_days: Some(days),
+                noncurrent_days: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn put_get_delete() {
+        let s = TapeStore::new(MemoryStore::new());
+        let b = Address::new_unique();
+        assert!(s.get_bucket_lifecycle(b).unwrap().is_none());
+
+        s.put_bucket_lifecycle(b, &lifecycle(7)).unwrap();
+        assert_eq!(s.get_bucket_lifecycle(b).unwrap(), Some(lifecycle(7)));
+        s.put_bucket_lifecycle(b, &lifecycle(9)).unwrap();
+        assert_eq!(s.get_bucket_lifecycle(b).unwrap(), Some(lifecycle(9)));
+
+        assert!(s.delete_bucket_lifecycle(b).unwrap());
+        assert!(!s.delete_bucket_lifecycle(b).unwrap());
+        assert!(s.get_bucket_lifecycle(b).unwrap().is_none());
+    }
+
+    #[test]
+    fn lists_every_bucket() {
+        let s = TapeStore::new(MemoryStore::new());
+        let first = Address::new_unique();
+        let second = Address::new_unique();
+        s.put_bucket_lifecycle(first, &lifecycle(1)).unwrap();
+        s.put_bucket_lifecycle(second, &lifecycle(2)).unwrap();
+
+        let mut buckets: Vec<Address> = s
+            .list_bucket_lifecycles()
+            .unwrap()
+            .into_iter()
+            .map(|(bucket, _)| bucket)
+            .collect();
+        buckets.sort();
+        let mut expected = vec![first, second];
+        expected.sort();
+        assert_eq!(buckets, expected);
+    }
+}
@@ -5,13 +5,14 @@
 //! - Cluster genesis hash
 //! - Chain epoch number
 //! - Node address
+//! - Schema version and in-flight migration progress
 //! - Sync cursor (last processed slot)
 //! - GC progress (started/completed epochs)
 
 use crate::columns::{GcCol, MetaCol, SyncCursorCol};
 use crate::error::{Result, TapeStoreError};
 use crate::TapeStore;
-use store::Store;
+use store::{Column, Store, WriteBatch};
 use tape_core::types::{EpochNumber, NodeId, SlotNumber};
 use tape_crypto::address::Address;
 use tape_crypto::Hash;
@@ -25,6 +26,8 @@ const NODE_ID_KEY: &str = "node_id";
 const SNAPSHOT_BOOTSTRAP_TARGET_EPOCH_KEY: &str = "snapshot_bootstrap_target_epoch";
 const OBSERVE_LAST_EPOCH_KEY: &str = "observe_last_epoch";
 const OBSERVE_LIFETIME_KEY: &str = "observe_lifetime";
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+const MIGRATION_CURSOR_KEY: &str = "schema_migration_cursor";
 
 // GC keys
 const GC_STARTED_KEY: &str = "started";
@@ -44,6 +47,13 @@ pub trait MetaOps {
     fn get_node_id(&self) -> Result<Option<NodeId>>;
     fn set_node_id(&self, id: NodeId) -> Result<()>;
 
+    // On-disk schema version, absent on stores written before versioning
+    fn get_schema_version(&self) -> Result<Option<u32>>;
+    fn set_schema_version(&self, version: u32) -> Result<()>;
+
+    // Progress of the migration in flight
+    fn get_migration_cursor(&self) -> Result<Option<Vec<u8>>>;
+
     // Sync cursor
     fn get_sync_cursor(&self) -> Result<Option<SlotNumber>>;
     fn set_sync_cursor(&self, slot: SlotNumber) -> Result<()>;
@@ -151,6 +161,34 @@ impl<S: Store> MetaOps for TapeStore<S> {
         Ok(())
     }
 
+    fn get_schema_version(&self) -> Result<Option<u32>> {
+        let key = SCHEMA_VERSION_KEY.to_string();
+        match self.get::<MetaCol>(&key)? {
+            Some(bytes) => {
+                if bytes.len() != 4 {
+                    return Err(TapeStoreError::InvalidDataLength {
+                        expected: 4,
+                        actual: bytes.len(),
+                    });
+                }
+                let mut version_bytes = [0u8; 4];
+                version_bytes.copy_from_slice(&bytes);
+                Ok(Some(u32::from_le_bytes(version_bytes)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn set_schema_version(&self, version: u32) -> Result<()> {
+        let key = SCHEMA_VERSION_KEY.to_string();
+        self.put::<MetaCol>(&key, &version.to_le_bytes().to_vec())?;
+        Ok(())
+    }
+
+    fn get_migration_cursor(&self) -> Result<Option<Vec<u8>>> {
+        Ok(self.get::<MetaCol>(&MIGRATION_CURSOR_KEY.to_string())?)
+    }
+
     fn get_sync_cursor(&self) -> Result<Option<SlotNumber>> {
         Ok(self.get::<SyncCursorCol>(&UnitKey)?)
     }
@@ -300,6 +338,34 @@ impl<S: Store> MetaOps for TapeStore<S> {
     }
 }
 
+/// Stage a schema version stamp, so it commits with the migration step that
+/// completes it
+pub(crate) fn stage_schema_version(batch: &mut WriteBatch, version: u32) -> Result<()> {
+    stage_meta(batch, SCHEMA_VERSION_KEY, Some(version.to_le_bytes().to_vec()))
+}
+
+/// Stage the saved migration cursor, or its removal, with the step it follows
+pub(crate) fn stage_migration_cursor(
+    batch: &mut WriteBatch,
+    bytes: Option<Vec<u8>>,
+) -> Result<()> {
+    stage_meta(batch, MIGRATION_CURSOR_KEY, bytes)
+}
+
+fn stage_meta(batch: &mut WriteBatch, key: &str, value: Option<Vec<u8>>) -> Result<()> {
+    let key_bytes = wincode::serialize(&key.to_string())
+        .map_err(|e| TapeStoreError::Serialization(format!("meta key: {}", e)))?;
+    match value {
+        Some(value) => {
+            let value_bytes = wincode::serialize(&value)
+                .map_err(|e| TapeStoreError::Serialization(format!("meta value: {}", e)))?;
+            batch.put_owned(MetaCol::CF_NAME, key_bytes, value_bytes);
+        }
+        None => batch.delete_owned(MetaCol::CF_NAME, key_bytes),
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,6 +410,15 @@ mod tests {
         assert_eq!(store.get_node_id().unwrap(), Some(id));
     }
 
+    #[test]
+    fn schema_version_roundtrip() {
+        let store = test_store();
+        assert_eq!(store.get_schema_version().unwrap(), None);
+
+        store.set_schema_version(3).unwrap();
+        assert_eq!(store.get_schema_version().unwrap(), Some(3));
+    }
+
     #[test]
     fn test_sync_cursor_roundtrip() {
         let store = test_store();
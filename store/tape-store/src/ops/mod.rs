@@ -16,16 +16,22 @@
 //! - `AuditOps`: Append-only write-authorization audit log (append/scan)
 //! - `LedgerOps`: Per-principal accounting ledger (atomic reserve/commit/refund + TTL sweep)
 //! - `MultipartOps`: Durable S3 multipart upload state (upload + part CRUD)
+//! - `ObjectVersionOps`: S3 bucket versioning status and per-key version history
+//! - `LifecycleOps`: S3 bucket lifecycle rules
+//! - `BucketAccessOps`: S3 bucket read access (public or private)
 
 mod audit_log;
 mod auth_state;
+mod bucket_access;
 mod credential;
 mod event_log;
 mod ledger;
+mod lifecycle;
 mod meta;
 mod object_info;
 mod object_list;
 mod object_metadata;
+mod object_version;
 mod policy;
 mod s3_multipart;
 mod snapshot;
@@ -39,17 +45,22 @@ mod vote;
 // Re-export operation traits
 pub use audit_log::AuditOps;
 pub use auth_state::AuthStateOps;
+pub use bucket_access::BucketAccessOps;
 pub use credential::CredentialOps;
 pub use event_log::EventLogOps;
 pub use ledger::{LedgerOps, ReserveOutcome, ReserveRequest};
+pub use lifecycle::LifecycleOps;
 pub use meta::MetaOps;
+pub(crate) use meta::{stage_migration_cursor, stage_schema_version};
 pub use object_info::ObjectInfoOps;
 pub use object_list::{ObjectListOps, ObjectListPage};
 pub use object_metadata::ObjectMetadataOps;
+pub use object_version::{ObjectVersionOps, ObjectVersionPage, ObjectVersionRow};
 pub use policy::{PolicyDecision, PolicyOps};
 pub use s3_multipart::MultipartOps;
 pub use snapshot::SnapshotOps;
 pub use slice::SliceOps;
+pub(crate) use slice::{clear_slice_size_index, index_slice_sizes, slice_size_index_intact};
 pub use spool::SpoolOps;
 pub use tape::TapeOps;
 pub use track::TrackOps;
@@ -170,7 +170,7 @@ fn decode_entry(bytes: &[u8]) -> Result<ObjectListEntry> {
 
 /// First byte string strictly greater than every string having `prefix` as a
 /// prefix, or `None` when `prefix` is all `0xFF` (no successor exists).
-fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+pub(crate) fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
     let mut v = prefix.to_vec();
     while let Some(&last) = v.last() {
         if last != u8::MAX {
@@ -193,7 +193,7 @@ fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
 mod tests {
     use super::*;
     use store_memory::MemoryStore;
-    use tape_core::types::{ContentType, SlotNumber, StorageUnits, TrackNumber};
+    use tape_core::types::{ContentType, ObjectHeaders, SlotNumber, StorageUnits, TrackNumber};
     use tape_crypto::Hash;
 
     fn store() -> TapeStore<MemoryStore> {
@@ -210,6 +210,7 @@ mod tests {
             track_number: TrackNumber(n),
             kind: 1,
             content_type: ContentType::Unknown,
+            headers: ObjectHeaders::default(),
         }
     }
 
@@ -39,7 +39,7 @@ impl<S: Store> ObjectMetadataOps for TapeStore<S> {
 #[cfg(test)]
 mod tests {
     use store_memory::MemoryStore;
-    use tape_core::types::ContentType;
+    use tape_core::types::{ContentType, ObjectHeaders};
 
     use super::*;
 
@@ -55,6 +55,7 @@ mod tests {
         let metadata = ObjectMetadata {
             name: b"photos/cat.jpg".to_vec(),
             content_type: ContentType::ImageJpeg,
+            headers: ObjectHeaders::default(),
         };
 
         store
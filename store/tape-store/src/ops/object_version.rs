@@ -0,0 +1,711 @@
+//! Per-bucket versioning status and object version history for S3 versioning.
+//!
+//! The `object_list` entry of a key stays the single current version that
+//! reads and listings see. In a versioned bucket every version is also kept
+//! here, and the current entry is re-derived from the newest version whenever
+//! the history changes.
+//!
+//! Each key holds at most one null version, S3's version `null`: the object
+//! that predates versioning, or whatever was last written or deleted while
+//! versioning was suspended. Writing a new null version drops the old one.
+
+use store::{Column, Direction, Store, WriteBatch};
+use tape_crypto::address::Address;
+
+use crate::columns::{BucketVersioningCol, ObjectNullVersionCol, ObjectVersionCol};
+use crate::error::{Result, TapeStoreError};
+use crate::ops::object_list::{prefix_successor, ObjectListOps};
+use crate::types::{
+    ObjectListKey, ObjectVersion, ObjectVersionId, ObjectVersionKey, VersioningStatus,
+};
+use crate::TapeStore;
+
+/// Objects recorded per listing page when versioning is first configured.
+const CONFIGURE_PAGE_SIZE: usize = 1000;
+
+/// One version returned by a version listing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectVersionRow {
+    /// Object name the version belongs to
+    pub name: Vec<u8>,
+    /// The version's id within its name
+    pub version: ObjectVersionId,
+    /// The written object, or a delete marker
+    pub value: ObjectVersion,
+    /// True for its name's null version
+    pub is_null: bool,
+    /// True for the newest version of its name
+    pub is_latest: bool,
+}
+
+/// One page of a version listing scan.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectVersionPage {
+    /// Versions in name order, each name's versions newest first.
+    pub versions: Vec<ObjectVersionRow>,
+    /// Exclusive resume position `(name, version)` when `is_truncated`.
+    pub next: Option<(Vec<u8>, ObjectVersionId)>,
+    /// True when more versions remain beyond this page.
+    pub is_truncated: bool,
+}
+
+/// Operations for bucket versioning and the per-key version history.
+pub trait ObjectVersionOps {
+    /// Fetch the versioning status of `bucket`; `None` when never configured.
+    fn get_bucket_versioning(&self, bucket: Address) -> Result<Option<VersioningStatus>>;
+
+    /// Set the versioning status of `bucket`.
+    fn put_bucket_versioning(&self, bucket: Address, status: VersioningStatus) -> Result<()>;
+
+    /// Set the versioning status of `bucket`, first recording every object
+    /// already in it as its key's null version when it had no status before.
+    fn configure_bucket_versioning(&self, bucket: Address, status: VersioningStatus)
+        -> Result<()>;
+
+    /// Insert or overwrite one version of `(bucket, name)`.
+    fn put_object_version(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+        value: &ObjectVersion,
+    ) -> Result<()>;
+
+    /// Fetch one version of `(bucket, name)`.
+    fn get_object_version(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+    ) -> Result<Option<ObjectVersion>>;
+
+    /// Record `value` as the null version of `(bucket, name)`, dropping the
+    /// null version it replaces.
+    fn put_null_version(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+        value: &ObjectVersion,
+    ) -> Result<()>;
+
+    /// The id of the null version of `(bucket, name)`, if it has one.
+    fn get_null_version(&self, bucket: Address, name: &[u8]) -> Result<Option<ObjectVersionId>>;
+
+    /// Remove one version of `(bucket, name)`, returning whether it existed.
+    fn delete_object_version(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+    ) -> Result<bool>;
+
+    /// Every version of `(bucket, name)`, newest first.
+    fn object_versions(
+        &self,
+        bucket: Address,
+        name: &[u8],
+    ) -> Result<Vec<(ObjectVersionId, ObjectVersion)>>;
+
+    /// Record the current listing entry of `(bucket, name)` as a version when
+    /// that version is not recorded yet, as for an object written before
+    /// versioning was enabled. Such an object becomes the key's null version
+    /// unless it already has one.
+    fn preserve_current_version(&self, bucket: Address, name: &[u8]) -> Result<()>;
+
+    /// Point the current listing entry of `(bucket, name)` at its newest
+    /// version, or remove it when the newest version is a delete marker or no
+    /// version is left.
+    fn restore_latest_version(&self, bucket: Address, name: &[u8]) -> Result<()>;
+
+    /// Hide `(bucket, name)` behind a new delete marker, newer than every
+    /// version it has, and drop its current entry. A `null` marker replaces
+    /// the key's null version, as a delete does while versioning is suspended.
+    /// Returns the marker's id, or `None` when the key has no version to hide.
+    fn place_delete_marker(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        time: i64,
+        null: bool,
+    ) -> Result<Option<ObjectVersionId>>;
+
+    /// Drop the versioning status and every version of `bucket`, as when its
+    /// tape is destroyed and the address may later be reserved afresh.
+    fn clear_bucket_versions(&self, bucket: Address) -> Result<()>;
+
+    /// List the versions under `bucket`, S3 `ListObjectVersions`-style.
+    ///
+    /// - `prefix` filters to names starting with it (empty = whole bucket).
+    /// - `after` resumes after a position: `(name, Some(version))` continues
+    ///   past that version, `(name, None)` past every version of `name`.
+    /// - `max_versions` caps the number of returned versions.
+    fn list_object_versions(
+        &self,
+        bucket: Address,
+        prefix: &[u8],
+        after: Option<(&[u8], Option<ObjectVersionId>)>,
+        max_versions: usize,
+    ) -> Result<ObjectVersionPage>;
+}
+
+impl<S: Store> ObjectVersionOps for TapeStore<S> {
+    fn get_bucket_versioning(&self, bucket: Address) -> Result<Option<VersioningStatus>> {
+        Ok(self.get::<BucketVersioningCol>(&bucket)?)
+    }
+
+    fn put_bucket_versioning(&self, bucket: Address, status: VersioningStatus) -> Result<()> {
+        self.put::<BucketVersioningCol>(&bucket, &status)?;
+        Ok(())
+    }
+
+    fn configure_bucket_versioning(
+        &self,
+        bucket: Address,
+        status: VersioningStatus,
+    ) -> Result<()> {
+        if self.get_bucket_versioning(bucket)?.is_none() {
+            let mut start: Option<Vec<u8>> = None;
+            loop {
+                let page =
+                    self.list_objects(bucket, b"", None, start.as_deref(), CONFIGURE_PAGE_SIZE)?;
+                for (name, _) in &page.objects {
+                    self.preserve_current_version(bucket, name)?;
+                }
+                match page.next {
+                    Some(next) if page.is_truncated => start = Some(next),
+                    _ => break,
+                }
+            }
+        }
+        self.put_bucket_versioning(bucket, status)
+    }
+
+    fn put_object_version(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+        value: &ObjectVersion,
+    ) -> Result<()> {
+        let key = ObjectVersionKey::new(bucket, name.to_vec(), version);
+        self.put::<ObjectVersionCol>(&key, value)?;
+        Ok(())
+    }
+
+    fn get_object_version(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+    ) -> Result<Option<ObjectVersion>> {
+        let key = ObjectVersionKey::new(bucket, name.to_vec(), version);
+        Ok(self.get::<ObjectVersionCol>(&key)?)
+    }
+
+    fn put_null_version(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+        value: &ObjectVersion,
+    ) -> Result<()> {
+        if let Some(previous) = self.get_null_version(bucket, name)? {
+            if previous != version {
+                self.delete_object_version(bucket, name, previous)?;
+            }
+        }
+        self.put_object_version(bucket, name, version, value)?;
+        self.put::<ObjectNullVersionCol>(&ObjectListKey::new(bucket, name.to_vec()), &version)?;
+        Ok(())
+    }
+
+    fn get_null_version(&self, bucket: Address, name: &[u8]) -> Result<Option<ObjectVersionId>> {
+        Ok(self.get::<ObjectNullVersionCol>(&ObjectListKey::new(bucket, name.to_vec()))?)
+    }
+
+    fn delete_object_version(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        version: ObjectVersionId,
+    ) -> Result<bool> {
+        let key = ObjectVersionKey::new(bucket, name.to_vec(), version);
+        if !self.contains::<ObjectVersionCol>(&key)? {
+            return Ok(false);
+        }
+        self.delete::<ObjectVersionCol>(&key)?;
+        if self.get_null_version(bucket, name)? == Some(version) {
+            self.delete::<ObjectNullVersionCol>(&ObjectListKey::new(bucket, name.to_vec()))?;
+        }
+        Ok(true)
+    }
+
+    fn object_versions(
+        &self,
+        bucket: Address,
+        name: &[u8],
+    ) -> Result<Vec<(ObjectVersionId, ObjectVersion)>> {
+        let prefix = ObjectVersionKey::name_prefix(bucket, name);
+        let iter = self
+            .inner()
+            .inner()
+            .iter_prefix(ObjectVersionCol::CF_NAME, &prefix)?;
+
+        let mut versions = Vec::new();
+        for (key_bytes, value_bytes) in iter {
+            // A longer name that happens to continue with `0x00` shares the
+            // prefix; only an exact version tail belongs to `name`.
+            if key_bytes.len() != prefix.len() + ObjectVersionId::SIZE {
+                continue;
+            }
+            let key = decode_key(&key_bytes)?;
+            versions.push((key.version, decode_version(&value_bytes)?));
+        }
+        Ok(versions)
+    }
+
+    fn preserve_current_version(&self, bucket: Address, name: &[u8]) -> Result<()> {
+        let Some(entry) = self.get_object_entry(bucket, name)? else {
+            return Ok(());
+        };
+        let version = ObjectVersionId::track(entry.track_number);
+        if self.get_object_version(bucket, name, version)?.is_some() {
+            return Ok(());
+        }
+        let value = ObjectVersion::Object(entry);
+        if self.get_null_version(bucket, name)?.is_none() {
+            self.put_null_version(bucket, name, version, &value)
+        } else {
+            self.put_object_version(bucket, name, version, &value)
+        }
+    }
+
+    fn restore_latest_version(&self, bucket: Address, name: &[u8]) -> Result<()> {
+        match self.object_versions(bucket, name)?.into_iter().next() {
+            Some((_, ObjectVersion::Object(entry))) => self.put_object_entry(bucket, name, entry),
+            Some((_, ObjectVersion::DeleteMarker { .. })) | None => {
+                self.delete_object_entry(bucket, name)
+            }
+        }
+    }
+
+    fn place_delete_marker(
+        &self,
+        bucket: Address,
+        name: &[u8],
+        time: i64,
+        null: bool,
+    ) -> Result<Option<ObjectVersionId>> {
+        self.preserve_current_version(bucket, name)?;
+        let Some((newest, _)) = self.object_versions(bucket, name)?.into_iter().next() else {
+            return Ok(None);
+        };
+
+        // Markers share the newest track's number and sort ahead of it, so the
+        // next write (a higher track number) still sorts ahead of the marker.
+        let marker = ObjectVersionId {
+            track_number: newest.track_number,
+            marker: newest.marker + 1,
+        };
+        let value = ObjectVersion::DeleteMarker { time };
+        if null {
+            self.put_null_version(bucket, name, marker, &value)?;
+        } else {
+            self.put_object_version(bucket, name, marker, &value)?;
+        }
+        self.restore_latest_version(bucket, name)?;
+        Ok(Some(marker))
+    }
+
+    fn clear_bucket_versions(&self, bucket: Address) -> Result<()> {
+        let keys = self
+            .inner()
+            .inner()
+            .iter_keys_prefix(ObjectVersionCol::CF_NAME, &bucket.to_bytes())?;
+        let null_keys = self
+            .inner()
+            .inner()
+            .iter_keys_prefix(ObjectNullVersionCol::CF_NAME, &bucket.to_bytes())?;
+        let mut batch = WriteBatch::new();
+        for key in &keys {
+            batch.delete(ObjectVersionCol::CF_NAME, key);
+        }
+        for key in &null_keys {
+            batch.delete(ObjectNullVersionCol::CF_NAME, key);
+        }
+        self.inner().inner().write_batch(batch)?;
+        self.delete::<BucketVersioningCol>(&bucket)?;
+        Ok(())
+    }
+
+    fn list_object_versions(
+        &self,
+        bucket: Address,
+        prefix: &[u8],
+        after: Option<(&[u8], Option<ObjectVersionId>)>,
+        max_versions: usize,
+    ) -> Result<ObjectVersionPage> {
+        let bucket_prefix = bucket.to_bytes();
+        let mut range_start = Vec::with_capacity(32 + prefix.len());
+        range_start.extend_from_slice(&bucket_prefix);
+        range_start.extend_from_slice(prefix);
+
+        // Where to start scanning, the name whose versions the scan may begin
+        // part-way through, and an exact key to step over.
+        let (seek, mut previous_name, skip) = match after {
+            Some((name, Some(version))) => {
+                let key = ObjectVersionKey::new(bucket, name.to_vec(), version);
+                let key_bytes = wincode::serialize(&key).map_err(|e| {
+                    TapeStoreError::Serialization(format!("object version key: {}", e))
+                })?;
+                (key_bytes.clone(), Some(name.to_vec()), Some(key_bytes))
+            }
+            Some((name, None)) => {
+                let past_name = prefix_successor(&ObjectVersionKey::name_prefix(bucket, name))
+                    .unwrap_or_default();
+                (past_name, None, None)
+            }
+            None => (range_start.clone(), None, None),
+        };
+        let seek = seek.max(range_start);
+
+        let iter = self
+            .inner()
+            .inner()
+            .iter_from(ObjectVersionCol::CF_NAME, &seek, Direction::Asc)?;
+
+        let mut page = ObjectVersionPage::default();
+        let mut null_version = match previous_name.as_deref() {
+            Some(name) => self.get_null_version(bucket, name)?,
+            None => None,
+        };
+        for (key_bytes, value_bytes) in iter {
+            // Left this bucket, or past the prefix range; keys are sorted.
+            if key_bytes.len() < 32 || key_bytes[..32] != bucket_prefix {
+                break;
+            }
+            if !key_bytes[32..].starts_with(prefix) {
+                break;
+            }
+            if skip.as_deref() == Some(key_bytes.as_slice()) {
+                continue;
+            }
+
+            if page.versions.len() >= max_versions {
+                page.is_truncated = true;
+                page.next = page
+                    .versions
+                    .last()
+                    .map(|row| (row.name.clone(), row.version));
+                break;
+            }
+
+            let key = decode_key(&key_bytes)?;
+            let is_latest = previous_name.as_deref() != Some(key.name.as_slice());
+            if is_latest {
+                null_version = self.get_null_version(bucket, &key.name)?;
+            }
+            previous_name = Some(key.name.clone());
+            page.versions.push(ObjectVersionRow {
+                is_null: null_version == Some(key.version),
+                name: key.name,
+                version: key.version,
+                value: decode_version(&value_bytes)?,
+                is_latest,
+            });
+        }
+
+        Ok(page)
+    }
+}
+
+fn decode_key(bytes: &[u8]) -> Result<ObjectVersionKey> {
+    wincode::deserialize(bytes)
+        .map_err(|e| TapeStoreError::Serialization(format!("object version key: {}", e)))
+}
+
+fn decode_version(bytes: &[u8]) -> Result<ObjectVersion> {
+    wincode::deserialize(bytes)
+        .map_err(|e| TapeStoreError::Serialization(format!("object version: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store_memory::MemoryStore;
+    use tape_core::types::{ContentType, ObjectHeaders, SlotNumber, StorageUnits, TrackNumber};
+    use tape_crypto::Hash;
+
+    use crate::types::ObjectListEntry;
+
+    fn store() -> TapeStore<MemoryStore> {
+        TapeStore::new(MemoryStore::new())
+    }
+
+    fn entry(n: u64) -> ObjectListEntry {
+        ObjectListEntry {
+            size: StorageUnits(n),
+            etag: Hash::new_unique(),
+            block_time: Some(1_700_000_000 + n as i64),
+            slot: SlotNumber(n),
+            data_tape: Address::new_unique(),
+            track_number: TrackNumber(n),
+            kind: 1,
+            content_type: ContentType::Unknown,
+            headers: ObjectHeaders::default(),
+        }
+    }
+
+    fn track(n: u64) -> ObjectVersionId {
+        ObjectVersionId::track(TrackNumber(n))
+    }
+
+    fn marker(n: u64, sequence: u32) -> ObjectVersionId {
+        ObjectVersionId {
+            track_number: TrackNumber(n),
+            marker: sequence,
+        }
+    }
+
+    fn put(s: &TapeStore<MemoryStore>, b: Address, name: &[u8], version: ObjectVersionId) {
+        let value = if version.is_delete_marker() {
+            ObjectVersion::DeleteMarker { time: 1_700_000_000 }
+        } else {
+            ObjectVersion::Object(entry(version.track_number.0))
+        };
+        s.put_object_version(b, name, version, &value).unwrap();
+    }
+
+    #[test]
+    fn bucket_status_roundtrip() {
+        let s = store();
+        let b = Address::new_unique();
+        assert!(s.get_bucket_versioning(b).unwrap().is_none());
+        s.put_bucket_versioning(b, VersioningStatus::Enabled).unwrap();
+        assert_eq!(s.get_bucket_versioning(b).unwrap(), Some(VersioningStatus::Enabled));
+        s.put_bucket_versioning(b, VersioningStatus::Suspended).unwrap();
+        assert_eq!(s.get_bucket_versioning(b).unwrap(), Some(VersioningStatus::Suspended));
+    }
+
+    #[test]
+    fn versions_newest_first() {
+        let s = store();
+        let b = Address::new_unique();
+        put(&s, b, b"k", track(2));
+        put(&s, b, b"k", track(7));
+        put(&s, b, b"k", marker(7, 1));
+        put(&s, b, b"k\0x", track(9));
+
+        let ids: Vec<ObjectVersionId> =
+            s.object_versions(b, b"k").unwrap().into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![marker(7, 1), track(7), track(2)]);
+    }
+
+    #[test]
+    fn delete_reports_presence() {
+        let s = store();
+        let b = Address::new_unique();
+        put(&s, b, b"k", track(1));
+        assert!(s.delete_object_version(b, b"k", track(1)).unwrap());
+        assert!(!s.delete_object_version(b, b"k", track(1)).unwrap());
+        assert!(s.get_object_version(b, b"k", track(1)).unwrap().is_none());
+    }
+
+    // the current entry follows the newest version through markers and removals
+    #[test]
+    fn restore_follows_history() {
+        let s = store();
+        let b = Address::new_unique();
+        put(&s, b, b"k", track(1));
+        put(&s, b, b"k", track(4));
+        s.restore_latest_version(b, b"k").unwrap();
+        assert_eq!(s.get_object_entry(b, b"k").unwrap().unwrap().track_number, TrackNumber(4));
+
+        put(&s, b, b"k", marker(4, 1));
+        s.restore_latest_version(b, b"k").unwrap();
+        assert!(s.get_object_entry(b, b"k").unwrap().is_none());
+
+        s.delete_object_version(b, b"k", marker(4, 1)).unwrap();
+        s.delete_object_version(b, b"k", track(4)).unwrap();
+        s.restore_latest_version(b, b"k").unwrap();
+        assert_eq!(s.get_object_entry(b, b"k").unwrap().unwrap().track_number, TrackNumber(1));
+
+        s.delete_object_version(b, b"k", track(1)).unwrap();
+        s.restore_latest_version(b, b"k").unwrap();
+        assert!(s.get_object_entry(b, b"k").unwrap().is_none());
+    }
+
+    // a delete marker stacks above the newest version and hides the key
+    #[test]
+    fn delete_marker_placement() {
+        let s = store();
+        let b = Address::new_unique();
+        assert_eq!(s.place_delete_marker(b, b"k", 1, false).unwrap(), None);
+
+        s.put_object_entry(b, b"k", entry(5)).unwrap();
+        assert_eq!(s.place_delete_marker(b, b"k", 1, false).unwrap(), Some(marker(5, 1)));
+        assert!(s.get_object_entry(b, b"k").unwrap().is_none());
+        assert_eq!(s.place_delete_marker(b, b"k", 2, false).unwrap(), Some(marker(5, 2)));
+
+        let versions: Vec<ObjectVersionId> = s
+            .object_versions(b, b"k")
+            .unwrap()
+            .into_iter()
+            .map(|(version, _)| version)
+            .collect();
+        assert_eq!(versions, vec![marker(5, 2), marker(5, 1), track(5)]);
+    }
+
+    // an unversioned current entry joins the history once, without duplicates
+    #[test]
+    fn preserve_current() {
+        let s = store();
+        let b = Address::new_unique();
+        s.preserve_current_version(b, b"k").unwrap();
+        assert!(s.object_versions(b, b"k").unwrap().is_empty());
+
+        let current = entry(6);
+        s.put_object_entry(b, b"k", current.clone()).unwrap();
+        s.preserve_current_version(b, b"k").unwrap();
+        s.preserve_current_version(b, b"k").unwrap();
+        assert_eq!(
+            s.object_versions(b, b"k").unwrap(),
+            vec![(track(6), ObjectVersion::Object(current))]
+        );
+    }
+
+    // configuring versioning turns existing objects into null versions once
+    #[test]
+    fn configure_records_null_versions() {
+        let s = store();
+        let b = Address::new_unique();
+        s.put_object_entry(b, b"a", entry(1)).unwrap();
+        s.put_object_entry(b, b"b", entry(2)).unwrap();
+
+        s.configure_bucket_versioning(b, VersioningStatus::Enabled).unwrap();
+        assert_eq!(s.get_bucket_versioning(b).unwrap(), Some(VersioningStatus::Enabled));
+        assert_eq!(s.get_null_version(b, b"a").unwrap(), Some(track(1)));
+        assert_eq!(s.get_null_version(b, b"b").unwrap(), Some(track(2)));
+
+        // A later status change leaves the history alone.
+        put(&s, b, b"a", track(4));
+        s.restore_latest_version(b, b"a").unwrap();
+        s.configure_bucket_versioning(b, VersioningStatus::Suspended).unwrap();
+        assert_eq!(s.get_bucket_versioning(b).unwrap(), Some(VersioningStatus::Suspended));
+        assert_eq!(s.get_null_version(b, b"a").unwrap(), Some(track(1)));
+    }
+
+    // a suspended write or delete replaces the null version and keeps the rest
+    #[test]
+    fn null_version_replaced() {
+        let s = store();
+        let b = Address::new_unique();
+        s.put_null_version(b, b"k", track(1), &ObjectVersion::Object(entry(1))).unwrap();
+        put(&s, b, b"k", track(3));
+        s.put_null_version(b, b"k", track(5), &ObjectVersion::Object(entry(5))).unwrap();
+        s.restore_latest_version(b, b"k").unwrap();
+        assert_eq!(s.get_null_version(b, b"k").unwrap(), Some(track(5)));
+        assert_eq!(s.get_object_entry(b, b"k").unwrap().unwrap().track_number, TrackNumber(5));
+
+        assert_eq!(s.place_delete_marker(b, b"k", 1, true).unwrap(), Some(marker(5, 1)));
+        assert_eq!(s.get_null_version(b, b"k").unwrap(), Some(marker(5, 1)));
+        let versions: Vec<ObjectVersionId> = s
+            .object_versions(b, b"k")
+            .unwrap()
+            .into_iter()
+            .map(|(version, _)| version)
+            .collect();
+        assert_eq!(versions, vec![marker(5, 1), track(3)]);
+
+        let page = s.list_object_versions(b, b"", None, 10).unwrap();
+        let nulls: Vec<bool> = page.versions.iter().map(|row| row.is_null).collect();
+        assert_eq!(nulls, vec![true, false]);
+
+        // Removing the null version clears the pointer.
+        assert!(s.delete_object_version(b, b"k", marker(5, 1)).unwrap());
+        assert!(s.get_null_version(b, b"k").unwrap().is_none());
+    }
+
+    #[test]
+    fn lists_with_latest_flags() {
+        let s = store();
+        let b = Address::new_unique();
+        put(&s, b, b"a", track(1));
+        put(&s, b, b"a", track(3));
+        put(&s, b, b"b", track(2));
+        put(&s, b, b"c", track(5));
+        put(&s, Address::new_unique(), b"a", track(8));
+
+        let page = s.list_object_versions(b, b"", None, 100).unwrap();
+        let rows: Vec<(&[u8], ObjectVersionId, bool)> = page
+            .versions
+            .iter()
+            .map(|row| (row.name.as_slice(), row.version, row.is_latest))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (b"a".as_slice(), track(3), true),
+                (b"a".as_slice(), track(1), false),
+                (b"b".as_slice(), track(2), true),
+                (b"c".as_slice(), track(5), true),
+            ]
+        );
+        assert!(!page.is_truncated);
+
+        let page = s.list_object_versions(b, b"b", None, 100).unwrap();
+        assert_eq!(page.versions.len(), 1);
+    }
+
+    #[test]
+    fn clear_bucket() {
+        let s = store();
+        let b = Address::new_unique();
+        let other = Address::new_unique();
+        s.put_bucket_versioning(b, VersioningStatus::Enabled).unwrap();
+        put(&s, b, b"k", track(1));
+        put(&s, b, b"k", marker(1, 1));
+        put(&s, other, b"k", track(1));
+
+        s.put_null_version(b, b"n", track(2), &ObjectVersion::Object(entry(2))).unwrap();
+
+        s.clear_bucket_versions(b).unwrap();
+        assert!(s.get_bucket_versioning(b).unwrap().is_none());
+        assert!(s.get_null_version(b, b"n").unwrap().is_none());
+        assert!(s.object_versions(b, b"k").unwrap().is_empty());
+        assert_eq!(s.object_versions(other, b"k").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn pagination_resumes_mid_key() {
+        let s = store();
+        let b = Address::new_unique();
+        put(&s, b, b"a", track(1));
+        put(&s, b, b"a", track(3));
+        put(&s, b, b"b", track(2));
+
+        let first = s.list_object_versions(b, b"", None, 1).unwrap();
+        assert!(first.is_truncated);
+        assert_eq!(first.next, Some((b"a".to_vec(), track(3))));
+
+        let (name, version) = first.next.unwrap();
+        let second = s
+            .list_object_versions(b, b"", Some((&name, Some(version))), 10)
+            .unwrap();
+        let rows: Vec<(&[u8], ObjectVersionId, bool)> = second
+            .versions
+            .iter()
+            .map(|row| (row.name.as_slice(), row.version, row.is_latest))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![(b"a".as_slice(), track(1), false), (b"b".as_slice(), track(2), true)]
+        );
+
+        // A key marker alone skips every version of that key.
+        let past = s.list_object_versions(b, b"", Some((b"a", None)), 10).unwrap();
+        assert_eq!(past.versions.len(), 1);
+        assert_eq!(past.versions[0].name, b"b".to_vec());
+    }
+}
@@ -1,11 +1,10 @@
 //! Write-authorization policy-engine operations
 
 use store::Store;
-use tape_crypto::address::Address;
 
 use crate::columns::PolicyRuleCol;
 use crate::error::Result;
-use crate::types::{PolicyAction, PolicyEffect, PolicyRule, PolicyRuleKey};
+use crate::types::{PolicyEffect, PolicyRequest, PolicyRule, PolicyRuleKey};
 use crate::TapeStore;
 
 /// The outcome of a policy evaluation:.
@@ -15,6 +14,8 @@ pub struct PolicyDecision {
     pub is_allowed: bool,
     /// Operator-facing reason code for the decision.
     pub reason: String,
+    /// The rule that decided, in evaluation order; `None` when the default did
+    pub rule: Option<PolicyRuleKey>,
 }
 
 /// Operations for the durable write-authorization policy engine
@@ -30,12 +31,10 @@ pub trait PolicyOps {
     /// List every policy rule as `(key, rule)`, in priority order
     fn list_policy_rules(&self) -> Result<Vec<(PolicyRuleKey, PolicyRule)>>;
 
-    /// Evaluate the ruleset for a concrete `(principal, bucket, action)` request.
+    /// Evaluate the ruleset, in key order, for a concrete request.
     fn evaluate_policy(
         &self,
-        principal: &Address,
-        bucket: &Address,
-        action: PolicyAction,
+        request: &PolicyRequest<'_>,
         is_default_allow: bool,
     ) -> Result<PolicyDecision>;
 }
@@ -61,14 +60,12 @@ impl<Backend: Store> PolicyOps for TapeStore<Backend> {
 
     fn evaluate_policy(
         &self,
-        principal: &Address,
-        bucket: &Address,
-        action: PolicyAction,
+        request: &PolicyRequest<'_>,
         is_default_allow: bool,
     ) -> Result<PolicyDecision> {
-        let mut allow_reason: Option<String> = None;
-        for (_key, rule) in self.iter::<PolicyRuleCol>()? {
-            if !rule.matches(principal, bucket, action) {
+        let mut first_allow: Option<(PolicyRuleKey, String)> = None;
+        for (key, rule) in self.iter::<PolicyRuleCol>()? {
+            if !rule.matches(request) {
                 continue;
             }
             match rule.effect {
@@ -76,20 +73,22 @@ impl<Backend: Store> PolicyOps for TapeStore<Backend> {
                     return Ok(PolicyDecision {
                         is_allowed: false,
                         reason: rule.reason,
+                        rule: Some(key),
                     });
                 }
                 PolicyEffect::Allow => {
-                    if allow_reason.is_none() {
-                        allow_reason = Some(rule.reason);
+                    if first_allow.is_none() {
+                        first_allow = Some((key, rule.reason));
                     }
                 }
             }
         }
 
-        if let Some(reason) = allow_reason {
+        if let Some((key, reason)) = first_allow {
             return Ok(PolicyDecision {
                 is_allowed: true,
                 reason,
+                rule: Some(key),
             });
         }
 
@@ -97,11 +96,13 @@ impl<Backend: Store> PolicyOps for TapeStore<Backend> {
             PolicyDecision {
                 is_allowed: true,
                 reason: "default-allow".to_string(),
+                rule: None,
             }
         } else {
             PolicyDecision {
                 is_allowed: false,
                 reason: "default-deny".to_string(),
+                rule: None,
             }
         })
     }
@@ -110,8 +111,10 @@ impl<Backend: Store> PolicyOps for TapeStore<Backend> {
 #[cfg(test)]
 mod tests {
     use store_memory::MemoryStore;
+    use tape_crypto::address::Address;
 
     use super::*;
+    use crate::types::{KeyPattern, PolicyAction, PolicyConditions};
 
     fn store() -> TapeStore<MemoryStore> {
         TapeStore::new(MemoryStore::new())
@@ -130,6 +133,23 @@ mod tests {
             action,
             effect,
             reason: reason.to_string(),
+            conditions: PolicyConditions::default(),
+        }
+    }
+
+    fn request(
+        principal: Address,
+        bucket: Address,
+        action: PolicyAction,
+    ) -> PolicyRequest<'static> {
+        PolicyRequest {
+            principal,
+            bucket,
+            action,
+            key: None,
+            source_ip: None,
+            size: None,
+            now: 0,
         }
     }
 
@@ -141,13 +161,13 @@ mod tests {
         let b = Address::new_unique();
 
         let deny = s
-            .evaluate_policy(&p, &b, PolicyAction::Put, false)
+            .evaluate_policy(&request(p, b, PolicyAction::Put), false)
             .expect("evaluate policy");
         assert!(!deny.is_allowed);
         assert_eq!(deny.reason, "default-deny");
 
         let allow = s
-            .evaluate_policy(&p, &b, PolicyAction::Put, true)
+            .evaluate_policy(&request(p, b, PolicyAction::Put), true)
             .expect("evaluate policy");
         assert!(allow.is_allowed);
         assert_eq!(allow.reason, "default-allow");
@@ -166,14 +186,14 @@ mod tests {
         .expect("put rule");
 
         let decision = s
-            .evaluate_policy(&p, &b, PolicyAction::Put, false)
+            .evaluate_policy(&request(p, b, PolicyAction::Put), false)
             .expect("evaluate policy");
         assert!(decision.is_allowed);
         assert_eq!(decision.reason, "owner ok");
 
         // A different principal is not matched, so the default decides.
         let other = s
-            .evaluate_policy(&Address::new_unique(), &b, PolicyAction::Put, false)
+            .evaluate_policy(&request(Address::new_unique(), b, PolicyAction::Put), false)
             .expect("evaluate policy");
         assert!(!other.is_allowed);
     }
@@ -198,17 +218,45 @@ mod tests {
         .expect("put rule");
 
         let decision = s
-            .evaluate_policy(&p, &b, PolicyAction::Delete, true)
+            .evaluate_policy(&request(p, b, PolicyAction::Delete), true)
             .expect("evaluate policy");
         assert!(!decision.is_allowed, "deny must win over allow");
         assert_eq!(decision.reason, "no deletes");
 
         // A Put on the same subject is only matched by the broad allow.
         let put = s
-            .evaluate_policy(&p, &b, PolicyAction::Put, false)
+            .evaluate_policy(&request(p, b, PolicyAction::Put), false)
             .expect("evaluate policy");
         assert!(put.is_allowed);
         assert_eq!(put.reason, "broad allow");
+        assert_eq!(put.rule, Some(PolicyRuleKey::new(1, 1)));
+    }
+
+    // a conditioned allow admits only the requests meeting its conditions
+    #[test]
+    fn conditioned_allow() {
+        let s = store();
+        let p = Address::new_unique();
+        let b = Address::new_unique();
+        let mut ci = rule(Some(p), Some(b), PolicyAction::Put, PolicyEffect::Allow, "ci artifacts");
+        ci.conditions.key = Some(KeyPattern::Prefix("artifacts/ci/".to_string()));
+        s.put_policy_rule(PolicyRuleKey::new(10, 1), &ci).expect("put rule");
+
+        let inside = PolicyRequest {
+            key: Some("artifacts/ci/build.tar"),
+            ..request(p, b, PolicyAction::Put)
+        };
+        let decision = s.evaluate_policy(&inside, false).expect("evaluate policy");
+        assert!(decision.is_allowed);
+        assert_eq!(decision.rule, Some(PolicyRuleKey::new(10, 1)));
+
+        let outside = PolicyRequest {
+            key: Some("releases/build.tar"),
+            ..inside
+        };
+        let decision = s.evaluate_policy(&outside, false).expect("evaluate policy");
+        assert!(!decision.is_allowed);
+        assert_eq!(decision.rule, None);
     }
 
     // a rule can be added, listed, and deleted
@@ -3,9 +3,11 @@
 use store::{Column, Store, WriteBatch};
 use tape_crypto::hash::{hash, Hash};
 
-use crate::columns::{S3MultipartPartCol, S3MultipartPartDataCol, S3MultipartUploadCol};
+use crate::columns::{
+    S3MultipartPartChunksCol, S3MultipartPartCol, S3MultipartPartDataCol, S3MultipartUploadCol,
+};
 use crate::error::{Result, TapeStoreError};
-use crate::types::{MultipartPart, MultipartPartData, MultipartPartKey, MultipartUpload};
+use crate::types::{MultipartPart, MultipartPartChunks, MultipartPartKey, MultipartUpload};
 use crate::TapeStore;
 
 /// Digest of an opaque upload id, used as the fixed-width key prefix shared by
@@ -37,25 +39,36 @@ pub trait MultipartOps {
     fn get_multipart_upload(&self, upload_id: &str) -> Result<Option<MultipartUpload>>;
 
     /// Insert or overwrite one part of `upload_id` (re-upload overwrites). The
-    /// metadata and payload are written together in one atomic batch.
+    /// metadata and chunk tracks are written together in one atomic batch,
+    /// which also drops a buffered payload left by an older part. Returns the
+    /// chunk tracks of the part it replaced, which nothing references anymore.
     fn put_multipart_part(
         &self,
         upload_id: &str,
         part: &MultipartPart,
-        data: Vec<u8>,
-    ) -> Result<()>;
+        chunks: &MultipartPartChunks,
+    ) -> Result<Option<MultipartPartChunks>>;
 
-    /// Every buffered part's metadata for `upload_id`, in ascending part-number
-    /// order, without reading any payload bytes
+    /// Every part's metadata for `upload_id`, in ascending part-number order,
+    /// without reading where any part's bytes live
     fn list_multipart_parts(&self, upload_id: &str) -> Result<Vec<MultipartPart>>;
 
-    /// The buffered payload of one part of `upload_id`, if present
+    /// The chunk tracks one part of `upload_id` was written to, if present
+    fn get_multipart_part_chunks(
+        &self,
+        upload_id: &str,
+        part_number: u32,
+    ) -> Result<Option<MultipartPartChunks>>;
+
+    /// The buffered payload of one part of `upload_id`, if present; only parts
+    /// uploaded before parts were written to chunk tracks have one
     fn get_multipart_part_data(&self, upload_id: &str, part_number: u32) -> Result<Option<Vec<u8>>>;
 
     /// Every in-flight upload as `(upload_id, metadata)`, for ListMultipartUploads
     fn list_multipart_uploads(&self) -> Result<Vec<(String, MultipartUpload)>>;
 
-    /// Delete `upload_id` and all of its buffered parts (Complete or Abort)
+    /// Delete `upload_id` and all of its parts' records (Complete or Abort);
+    /// the parts' chunk tracks are the caller's to delete or link
     fn delete_multipart_upload(&self, upload_id: &str) -> Result<()>;
 }
 
@@ -73,17 +86,19 @@ impl<Backend: Store> MultipartOps for TapeStore<Backend> {
         &self,
         upload_id: &str,
         part: &MultipartPart,
-        data: Vec<u8>,
-    ) -> Result<()> {
+        chunks: &MultipartPartChunks,
+    ) -> Result<Option<MultipartPartChunks>> {
+        let replaced = self.get_multipart_part_chunks(upload_id, part.part_number)?;
         let key = encode(&part_key(upload_id, part.part_number), "multipart part key")?;
         let metadata = encode(part, "multipart part metadata")?;
-        let payload = encode(&MultipartPartData { data }, "multipart part payload")?;
+        let chunk_tracks = encode(chunks, "multipart part chunks")?;
 
         let mut batch = WriteBatch::new();
         batch.put(S3MultipartPartCol::CF_NAME, &key, &metadata);
-        batch.put(S3MultipartPartDataCol::CF_NAME, &key, &payload);
+        batch.put(S3MultipartPartChunksCol::CF_NAME, &key, &chunk_tracks);
+        batch.delete(S3MultipartPartDataCol::CF_NAME, &key);
         self.inner().inner().write_batch(batch)?;
-        Ok(())
+        Ok(replaced)
     }
 
     fn list_multipart_parts(&self, upload_id: &str) -> Result<Vec<MultipartPart>> {
@@ -105,6 +120,14 @@ impl<Backend: Store> MultipartOps for TapeStore<Backend> {
         Ok(parts)
     }
 
+    fn get_multipart_part_chunks(
+        &self,
+        upload_id: &str,
+        part_number: u32,
+    ) -> Result<Option<MultipartPartChunks>> {
+        Ok(self.get::<S3MultipartPartChunksCol>(&part_key(upload_id, part_number))?)
+    }
+
     fn get_multipart_part_data(&self, upload_id: &str, part_number: u32) -> Result<Option<Vec<u8>>> {
         Ok(self
             .get::<S3MultipartPartDataCol>(&part_key(upload_id, part_number))?
@@ -119,15 +142,15 @@ impl<Backend: Store> MultipartOps for TapeStore<Backend> {
         let raw = self.inner().inner();
         let prefix = MultipartPartKey::upload_prefix(upload_digest(upload_id));
 
-        let metadata_keys = raw.iter_keys_prefix(S3MultipartPartCol::CF_NAME, &prefix)?;
-        let payload_keys = raw.iter_keys_prefix(S3MultipartPartDataCol::CF_NAME, &prefix)?;
-
         let mut batch = WriteBatch::new();
-        for key in &metadata_keys {
-            batch.delete(S3MultipartPartCol::CF_NAME, key);
-        }
-        for key in &payload_keys {
-            batch.delete(S3MultipartPartDataCol::CF_NAME, key);
+        for cf in [
+            S3MultipartPartCol::CF_NAME,
+            S3MultipartPartChunksCol::CF_NAME,
+            S3MultipartPartDataCol::CF_NAME,
+        ] {
+            for key in &raw.iter_keys_prefix(cf, &prefix)? {
+                batch.delete(cf, key);
+            }
         }
         raw.write_batch(batch)?;
 
@@ -139,11 +162,12 @@ impl<Backend: Store> MultipartOps for TapeStore<Backend> {
 #[cfg(test)]
 mod tests {
     use store_memory::MemoryStore;
-    use tape_core::types::ContentType;
+    use tape_core::types::{ObjectHeaders, TrackNumber};
     use tape_crypto::address::Address;
     use tape_crypto::hash::hash;
 
     use super::*;
+    use crate::types::{MultipartPartData, PartChunk};
 
     fn store() -> TapeStore<MemoryStore> {
         TapeStore::new(MemoryStore::new())
@@ -153,22 +177,53 @@ mod tests {
         MultipartUpload {
             bucket: Address::new_unique(),
             key: "obj".to_string(),
-            content_type: ContentType::TextPlain,
+            headers: ObjectHeaders::with_content_type("text/plain"),
             initiated: 1_000,
             principal: Address::new_unique(),
         }
     }
 
-    fn put_part(store: &TapeStore<MemoryStore>, upload_id: &str, part_number: u32, data: &[u8]) {
+    fn chunks(track_number: u64, size: u64) -> MultipartPartChunks {
+        MultipartPartChunks {
+            chunks: vec![PartChunk {
+                track_number: TrackNumber(track_number),
+                size,
+            }],
+        }
+    }
+
+    fn put_part(
+        store: &TapeStore<MemoryStore>,
+        upload_id: &str,
+        part_number: u32,
+        data: &[u8],
+    ) -> Option<MultipartPartChunks> {
         let part = MultipartPart {
             part_number,
             etag: hash(data),
             last_modified: 2_000,
             size: data.len() as u64,
         };
+        let track_number = u64::from(part_number) * 100 + data.len() as u64;
         store
-            .put_multipart_part(upload_id, &part, data.to_vec())
-            .expect("put part");
+            .put_multipart_part(upload_id, &part, &chunks(track_number, part.size))
+            .expect("put part")
+    }
+
+    // a part buffered before parts went to chunk tracks, as it was stored
+    fn put_buffered_part(store: &TapeStore<MemoryStore>, upload_id: &str, part_number: u32) {
+        let data = b"buffered".to_vec();
+        let part = MultipartPart {
+            part_number,
+            etag: hash(&data),
+            last_modified: 2_000,
+            size: data.len() as u64,
+        };
+        let key = part_key(upload_id, part_number);
+        store.put::<S3MultipartPartCol>(&key, &part).expect("put part");
+        store
+            .put::<S3MultipartPartDataCol>(&key, &MultipartPartData { data })
+            .expect("put payload");
     }
 
     // an upload's metadata reads back unchanged
@@ -210,34 +265,51 @@ mod tests {
         assert_eq!(store.list_multipart_parts("u2").expect("list").len(), 1);
     }
 
-    // part metadata records its payload size, and the payload reads back separately
+    // part metadata records its size, and its chunk tracks read back separately
     #[test]
-    fn part_size_and_payload() {
+    fn part_size_and_chunks() {
         let store = store();
-        put_part(&store, "u1", 1, b"hello");
+        assert!(put_part(&store, "u1", 1, b"hello").is_none());
 
         let parts = store.list_multipart_parts("u1").expect("list");
         assert_eq!(parts[0].size, 5);
         assert_eq!(
-            store.get_multipart_part_data("u1", 1).expect("data"),
-            Some(b"hello".to_vec())
+            store.get_multipart_part_chunks("u1", 1).expect("chunks"),
+            Some(chunks(105, 5))
         );
-        assert!(store.get_multipart_part_data("u1", 2).expect("data").is_none());
+        assert!(store.get_multipart_part_chunks("u1", 2).expect("chunks").is_none());
+        assert!(store.get_multipart_part_data("u1", 1).expect("data").is_none());
     }
 
-    // re-uploading a part number overwrites it
+    // re-uploading a part number overwrites it and hands back the old chunks
     #[test]
     fn part_overwrite() {
         let store = store();
         put_part(&store, "u1", 1, b"old");
-        put_part(&store, "u1", 1, b"new");
+        let replaced = put_part(&store, "u1", 1, b"newer");
 
         let parts = store.list_multipart_parts("u1").expect("list parts");
         assert_eq!(parts.len(), 1);
+        assert_eq!(replaced, Some(chunks(103, 3)));
+        assert_eq!(
+            store.get_multipart_part_chunks("u1", 1).expect("chunks"),
+            Some(chunks(105, 5))
+        );
+    }
+
+    // a buffered part still reads back, and overwriting it drops the payload
+    #[test]
+    fn buffered_part() {
+        let store = store();
+        put_buffered_part(&store, "u1", 1);
         assert_eq!(
             store.get_multipart_part_data("u1", 1).expect("data"),
-            Some(b"new".to_vec())
+            Some(b"buffered".to_vec())
         );
+        assert!(store.get_multipart_part_chunks("u1", 1).expect("chunks").is_none());
+
+        assert!(put_part(&store, "u1", 1, b"new").is_none());
+        assert!(store.get_multipart_part_data("u1", 1).expect("data").is_none());
     }
 
     // delete removes the upload and all of its parts, leaving others intact
@@ -246,7 +318,7 @@ mod tests {
         let store = store();
         store.put_multipart_upload("u1", &upload()).expect("put upload");
         put_part(&store, "u1", 1, b"a");
-        put_part(&store, "u1", 2, b"b");
+        put_buffered_part(&store, "u1", 2);
         store.put_multipart_upload("u2", &upload()).expect("put upload");
         put_part(&store, "u2", 1, b"c");
 
@@ -254,10 +326,11 @@ mod tests {
 
         assert!(store.get_multipart_upload("u1").expect("get").is_none());
         assert!(store.list_multipart_parts("u1").expect("list").is_empty());
-        assert!(store.get_multipart_part_data("u1", 1).expect("data").is_none());
+        assert!(store.get_multipart_part_chunks("u1", 1).expect("chunks").is_none());
+        assert!(store.get_multipart_part_data("u1", 2).expect("data").is_none());
         assert!(store.get_multipart_upload("u2").expect("get").is_some());
         assert_eq!(store.list_multipart_parts("u2").expect("list").len(), 1);
-        assert!(store.get_multipart_part_data("u2", 1).expect("data").is_some());
+        assert!(store.get_multipart_part_chunks("u2", 1).expect("chunks").is_some());
     }
 
     // listing returns every in-flight upload
@@ -1,12 +1,12 @@
 //! Slice data operations (merged primary + recovery)
 
-use store::{Column, Store, WriteBatch};
+use store::{Column, Direction, Store, WriteBatch};
 use tape_core::types::{SpoolIndex, StorageUnits};
 use tape_crypto::address::Address;
 
-use crate::columns::{SliceCol, SliceSizeCol};
+use crate::columns::{SliceCol, SliceSegmentCol, SliceSizeCol};
 use crate::error::{Result, TapeStoreError};
-use crate::types::{SliceKey, SliceValue};
+use crate::types::{SliceKey, SliceSegmentKey, SliceValue};
 use crate::TapeStore;
 
 /// Entries staged before a rebuild flushes its batch
@@ -20,6 +20,30 @@ pub trait SliceOps {
     /// Store slice data
     fn put_slice(&self, spool_id: SpoolIndex, track_address: Address, data: Vec<u8>) -> Result<()>;
 
+    /// Stage one verified segment of a slice still being uploaded
+    fn put_slice_segment(
+        &self,
+        spool_id: SpoolIndex,
+        track_address: Address,
+        segment: u16,
+        data: Vec<u8>,
+    ) -> Result<()>;
+
+    /// Fold the staged segments of a slice, in order, into its stored data
+    ///
+    /// The slice, its size and the removal of the staged segments land in one
+    /// batch. Refuses, staging nothing, when the segments do not add up to
+    /// `len` bytes, as when another upload of the slice committed first.
+    fn commit_slice_segments(
+        &self,
+        spool_id: SpoolIndex,
+        track_address: Address,
+        len: usize,
+    ) -> Result<()>;
+
+    /// Drop the staged segments of an abandoned upload
+    fn discard_slice_segments(&self, spool_id: SpoolIndex, track_address: Address) -> Result<()>;
+
     /// Delete slice data
     fn delete_slice(&self, spool_id: SpoolIndex, track_address: Address) -> Result<()>;
 
@@ -88,6 +112,75 @@ impl<S: Store> SliceOps for TapeStore<S> {
         Ok(())
     }
 
+    fn put_slice_segment(
+        &self,
+        spool_id: SpoolIndex,
+        track_address: Address,
+        segment: u16,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let key = SliceSegmentKey::new(SliceKey::new(spool_id, track_address), segment);
+        let key_bytes = wincode::serialize(&key)
+            .map_err(|e| TapeStoreError::Serialization(format!("slice segment key: {}", e)))?;
+        let value_bytes = wincode::serialize(&SliceValue(data))
+            .map_err(|e| TapeStoreError::Serialization(format!("slice segment: {}", e)))?;
+
+        let mut batch = WriteBatch::new();
+        batch.put_owned(SliceSegmentCol::CF_NAME, key_bytes, value_bytes);
+        self.inner().inner().write_batch(batch)?;
+        Ok(())
+    }
+
+    fn commit_slice_segments(
+        &self,
+        spool_id: SpoolIndex,
+        track_address: Address,
+        len: usize,
+    ) -> Result<()> {
+        let key = SliceKey::new(spool_id, track_address);
+        let key_bytes = serialize_slice_key(&key)?;
+        let staged = self
+            .inner()
+            .inner()
+            .iter_prefix(SliceSegmentCol::CF_NAME, &key_bytes)?;
+
+        // Segment keys sort by index, so appending in scan order rebuilds the
+        // slice.
+        let mut batch = WriteBatch::new();
+        let mut data = Vec::with_capacity(len);
+        for (segment_key, segment_bytes) in staged {
+            let segment: SliceValue = wincode::deserialize(&segment_bytes)
+                .map_err(|e| TapeStoreError::Serialization(format!("slice segment: {}", e)))?;
+            data.extend_from_slice(&segment.0);
+            batch.delete_owned(SliceSegmentCol::CF_NAME, segment_key);
+        }
+        if data.len() != len {
+            return Err(TapeStoreError::InvalidDataLength {
+                expected: len,
+                actual: data.len(),
+            });
+        }
+
+        let size_bytes = serialize_size(StorageUnits(len as u64))?;
+        let value_bytes = wincode::serialize(&SliceValue(data))
+            .map_err(|e| TapeStoreError::Serialization(format!("slice value: {}", e)))?;
+        batch.put_owned(SliceCol::CF_NAME, key_bytes.clone(), value_bytes);
+        batch.put_owned(SliceSizeCol::CF_NAME, key_bytes, size_bytes);
+        self.inner().inner().write_batch(batch)?;
+        Ok(())
+    }
+
+    fn discard_slice_segments(&self, spool_id: SpoolIndex, track_address: Address) -> Result<()> {
+        let key_bytes = serialize_slice_key(&SliceKey::new(spool_id, track_address))?;
+        let raw = self.inner().inner();
+        let mut batch = WriteBatch::new();
+        for segment_key in raw.iter_keys_prefix(SliceSegmentCol::CF_NAME, &key_bytes)? {
+            batch.delete_owned(SliceSegmentCol::CF_NAME, segment_key);
+        }
+        raw.write_batch(batch)?;
+        Ok(())
+    }
+
     fn delete_slice(&self, spool_id: SpoolIndex, track_address: Address) -> Result<()> {
         let key = SliceKey::new(spool_id, track_address);
         let key_bytes = serialize_slice_key(&key)?;
@@ -255,40 +348,82 @@ impl<S: Store> SliceOps for TapeStore<S> {
     }
 
     fn ensure_slice_size_index(&self) -> Result<bool> {
-        let raw = self.inner().inner();
-
-        let slices = raw.iter_keys_prefix(SliceCol::CF_NAME, &[])?;
-        let sizes = raw.iter_keys_prefix(SliceSizeCol::CF_NAME, &[])?;
-        if slices.len() == sizes.len() {
+        if slice_size_index_intact(self)? {
             return Ok(false);
         }
 
         // Interrupting the rebuild leaves a short index, which the same count
         // check catches on the next open.
+        let raw = self.inner().inner();
         let mut batch = WriteBatch::new();
-        for key in &sizes {
-            batch.delete(SliceSizeCol::CF_NAME, key);
-        }
+        clear_slice_size_index(self, &mut batch)?;
         raw.write_batch(batch)?;
 
-        let mut batch = WriteBatch::new();
-        let mut staged = 0usize;
-        for (key_bytes, value_bytes) in raw.iter(SliceCol::CF_NAME)? {
-            let value: SliceValue = wincode::deserialize(&value_bytes)
-                .map_err(|e| TapeStoreError::Serialization(format!("slice value: {}", e)))?;
-            let size_bytes = serialize_size(StorageUnits(value.0.len() as u64))?;
-            batch.put_owned(SliceSizeCol::CF_NAME, key_bytes, size_bytes);
-            staged += 1;
-            if staged == REBUILD_BATCH_LEN {
-                raw.write_batch(std::mem::replace(&mut batch, WriteBatch::new()))?;
-                staged = 0;
+        let mut after = None;
+        loop {
+            let mut batch = WriteBatch::new();
+            let (last, _) = index_slice_sizes(self, after.as_deref(), &mut batch)?;
+            raw.write_batch(batch)?;
+            match last {
+                Some(last) => after = Some(last),
+                None => return Ok(true),
             }
         }
-        if !batch.is_empty() {
-            raw.write_batch(batch)?;
+    }
+}
+
+/// Whether the size index holds one entry per stored slice
+pub(crate) fn slice_size_index_intact<S: Store>(store: &TapeStore<S>) -> Result<bool> {
+    let raw = store.inner().inner();
+    let slices = raw.iter_keys_prefix(SliceCol::CF_NAME, &[])?;
+    let sizes = raw.iter_keys_prefix(SliceSizeCol::CF_NAME, &[])?;
+    Ok(slices.len() == sizes.len())
+}
+
+/// Stage the deletion of every size index entry ahead of a rebuild
+pub(crate) fn clear_slice_size_index<S: Store>(
+    store: &TapeStore<S>,
+    batch: &mut WriteBatch,
+) -> Result<()> {
+    for key in store.inner().inner().iter_keys_prefix(SliceSizeCol::CF_NAME, &[])? {
+        batch.delete_owned(SliceSizeCol::CF_NAME, key);
+    }
+    Ok(())
+}
+
+/// Stage size index entries for up to one rebuild batch of slices after the
+/// slice key `after`
+///
+/// Returns the last slice key staged, `None` once no slices are left, and how
+/// many slices were staged.
+pub(crate) fn index_slice_sizes<S: Store>(
+    store: &TapeStore<S>,
+    after: Option<&[u8]>,
+    batch: &mut WriteBatch,
+) -> Result<(Option<Vec<u8>>, u64)> {
+    let raw = store.inner().inner();
+    let entries = match after {
+        Some(after) => raw.iter_from(SliceCol::CF_NAME, after, Direction::Asc)?,
+        None => raw.iter(SliceCol::CF_NAME)?,
+    };
+
+    let mut last = None;
+    let mut staged = 0u64;
+    for (key_bytes, value_bytes) in entries {
+        if after == Some(key_bytes.as_slice()) {
+            continue;
+        }
+        let value: SliceValue = wincode::deserialize(&value_bytes)
+            .map_err(|e| TapeStoreError::Serialization(format!("slice value: {}", e)))?;
+        let size_bytes = serialize_size(StorageUnits(value.0.len() as u64))?;
+        last = Some(key_bytes.clone());
+        batch.put_owned(SliceSizeCol::CF_NAME, key_bytes, size_bytes);
+        staged += 1;
+        if staged == REBUILD_BATCH_LEN as u64 {
+            break;
         }
-        Ok(true)
     }
+    Ok((last, staged))
 }
 
 fn serialize_slice_key(key: &SliceKey) -> Result<Vec<u8>> {
@@ -347,6 +482,42 @@ mod tests {
         assert_eq!(retrieved, data);
     }
 
+    // staged segments fold into the slice in index order and leave nothing behind
+    #[test]
+    fn slice_segments_commit() {
+        let store = test_store();
+        let spool_id = SpoolIndex(7);
+        let track = Address::new_unique();
+
+        store.put_slice_segment(spool_id, track, 1, vec![2; 3]).unwrap();
+        store.put_slice_segment(spool_id, track, 0, vec![1; 2]).unwrap();
+        assert!(!store.has_slice(spool_id, track).unwrap());
+
+        assert!(store.commit_slice_segments(spool_id, track, 4).is_err());
+        store.commit_slice_segments(spool_id, track, 5).unwrap();
+        assert_eq!(store.get_slice(spool_id, track).unwrap().unwrap(), vec![1, 1, 2, 2, 2]);
+        assert_eq!(store.slice_totals_by_spool(spool_id).unwrap(), (1, StorageUnits(5)));
+
+        let raw = store.inner().inner();
+        assert!(raw.iter_keys_prefix(SliceSegmentCol::CF_NAME, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn slice_segments_discard() {
+        let store = test_store();
+        let spool_id = SpoolIndex(7);
+        let track = Address::new_unique();
+        let other = Address::new_unique();
+
+        store.put_slice_segment(spool_id, track, 0, vec![1; 4]).unwrap();
+        store.put_slice_segment(spool_id, other, 0, vec![9; 4]).unwrap();
+        store.discard_slice_segments(spool_id, track).unwrap();
+
+        let raw = store.inner().inner();
+        assert_eq!(raw.iter_keys_prefix(SliceSegmentCol::CF_NAME, &[]).unwrap().len(), 1);
+        assert!(!store.has_slice(spool_id, track).unwrap());
+    }
+
     #[test]
     fn test_delete_slice() {
         let store = test_store();
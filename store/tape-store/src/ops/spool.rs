@@ -1,12 +1,13 @@
 //! Spool operations
 
 use tape_core::system::SpoolState;
-use tape_core::types::SpoolIndex;
+use tape_core::types::{EpochNumber, SpoolIndex};
 use tape_crypto::address::Address;
 use store::{Column, Store};
 
 use crate::columns::{
-    SpoolPendingRecoveryCol, SpoolPendingRepairCol, SpoolStatusCol, SpoolSyncCursorCol,
+    SliceCol, SpoolPendingRecoveryCol, SpoolPendingRepairCol, SpoolQuarantineCol,
+    SpoolScrubCursorCol, SpoolStatusCol, SpoolSyncCursorCol,
 };
 use crate::error::{Result, TapeStoreError};
 use crate::types::{SliceKey, SpoolIndexKey};
@@ -43,11 +44,27 @@ pub trait SpoolOps {
     fn set_spool_sync_cursor( &self, spool_id: SpoolIndex, last_synced_track: Address,) -> Result<()>;
     fn remove_spool_sync_cursor(&self, spool_id: SpoolIndex) -> Result<()>;
 
+    // Scrub progress
+    fn get_spool_scrub_cursor(&self, spool_id: SpoolIndex) -> Result<Option<Address>>;
+    fn set_spool_scrub_cursor(&self, spool_id: SpoolIndex, last_scrubbed_track: Address) -> Result<()>;
+    fn remove_spool_scrub_cursor(&self, spool_id: SpoolIndex) -> Result<()>;
+
+    // Quarantined slices (kept in place but not served after failing
+    // verification, until a repair overwrites them)
+    fn quarantine_slice(&self, spool_id: SpoolIndex, track_address: Address, epoch: EpochNumber) -> Result<()>;
+    fn release_quarantined_slice(&self, spool_id: SpoolIndex, track_address: Address) -> Result<()>;
+    fn is_slice_quarantined(&self, spool_id: SpoolIndex, track_address: Address) -> Result<bool>;
+    fn count_quarantined_slices(&self, spool_id: SpoolIndex) -> Result<usize>;
+    fn has_usable_slice(&self, spool_id: SpoolIndex, track_address: Address) -> Result<bool>;
+
     // Bulk clear all pending repairs for a spool
     fn clear_all_pending_repairs(&self, spool_id: SpoolIndex) -> Result<()>;
 
     // Bulk clear all pending recoveries for a spool
     fn clear_all_pending_recoveries(&self, spool_id: SpoolIndex) -> Result<()>;
+
+    // Bulk clear all quarantine entries for a spool
+    fn clear_all_quarantined_slices(&self, spool_id: SpoolIndex) -> Result<()>;
 }
 
 impl<S: Store> SpoolOps for TapeStore<S> {
@@ -155,6 +172,67 @@ impl<S: Store> SpoolOps for TapeStore<S> {
         Ok(())
     }
 
+    fn get_spool_scrub_cursor(&self, spool_id: SpoolIndex) -> Result<Option<Address>> {
+        let key = SpoolIndexKey::new(spool_id);
+        Ok(self.get::<SpoolScrubCursorCol>(&key)?)
+    }
+
+    fn set_spool_scrub_cursor(
+        &self,
+        spool_id: SpoolIndex,
+        last_scrubbed_track: Address,
+    ) -> Result<()> {
+        let key = SpoolIndexKey::new(spool_id);
+        self.put::<SpoolScrubCursorCol>(&key, &last_scrubbed_track)?;
+        Ok(())
+    }
+
+    fn remove_spool_scrub_cursor(&self, spool_id: SpoolIndex) -> Result<()> {
+        let key = SpoolIndexKey::new(spool_id);
+        self.delete::<SpoolScrubCursorCol>(&key)?;
+        Ok(())
+    }
+
+    fn quarantine_slice(
+        &self,
+        spool_id: SpoolIndex,
+        track_address: Address,
+        epoch: EpochNumber,
+    ) -> Result<()> {
+        let key = SliceKey::new(spool_id, track_address);
+        self.put::<SpoolQuarantineCol>(&key, &epoch)?;
+        Ok(())
+    }
+
+    fn release_quarantined_slice(&self, spool_id: SpoolIndex, track_address: Address) -> Result<()> {
+        let key = SliceKey::new(spool_id, track_address);
+        self.delete::<SpoolQuarantineCol>(&key)?;
+        Ok(())
+    }
+
+    fn is_slice_quarantined(&self, spool_id: SpoolIndex, track_address: Address) -> Result<bool> {
+        let key = SliceKey::new(spool_id, track_address);
+        Ok(self.contains::<SpoolQuarantineCol>(&key)?)
+    }
+
+    fn count_quarantined_slices(&self, spool_id: SpoolIndex) -> Result<usize> {
+        let prefix = SliceKey::spool_prefix(spool_id);
+        Ok(self
+            .inner()
+            .inner()
+            .iter_keys_prefix(SpoolQuarantineCol::CF_NAME, &prefix)?
+            .len())
+    }
+
+    fn has_usable_slice(&self, spool_id: SpoolIndex, track_address: Address) -> Result<bool> {
+        let key = SliceKey::new(spool_id, track_address);
+        Ok(self.contains::<SliceCol>(&key)? && !self.contains::<SpoolQuarantineCol>(&key)?)
+    }
+
+    fn clear_all_quarantined_slices(&self, spool_id: SpoolIndex) -> Result<()> {
+        clear_all_pending_by_spool(self, SpoolQuarantineCol::CF_NAME, spool_id)
+    }
+
 }
 
 fn iter_pending_by_spool<S: Store>(
@@ -375,4 +453,63 @@ mod tests {
         store.remove_spool_sync_cursor(spool_id).unwrap();
         assert!(store.get_spool_sync_cursor(spool_id).unwrap().is_none());
     }
+
+    #[test]
+    fn scrub_cursor_roundtrip() {
+        let store = test_store();
+        let spool_id = SpoolIndex(9);
+        let track = Address::new_unique();
+
+        assert!(store.get_spool_scrub_cursor(spool_id).unwrap().is_none());
+
+        store.set_spool_scrub_cursor(spool_id, track).unwrap();
+        assert_eq!(store.get_spool_scrub_cursor(spool_id).unwrap(), Some(track));
+
+        store.remove_spool_scrub_cursor(spool_id).unwrap();
+        assert!(store.get_spool_scrub_cursor(spool_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn quarantine_per_spool() {
+        let store = test_store();
+        let spool_id = SpoolIndex(9);
+        let other = SpoolIndex(10);
+        let a = Address::new_unique();
+        let b = Address::new_unique();
+
+        store.quarantine_slice(spool_id, a, EpochNumber(3)).unwrap();
+        store.quarantine_slice(spool_id, b, EpochNumber(3)).unwrap();
+        store.quarantine_slice(other, a, EpochNumber(3)).unwrap();
+
+        assert!(store.is_slice_quarantined(spool_id, a).unwrap());
+        assert_eq!(store.count_quarantined_slices(spool_id).unwrap(), 2);
+
+        store.release_quarantined_slice(spool_id, a).unwrap();
+        assert!(!store.is_slice_quarantined(spool_id, a).unwrap());
+        assert_eq!(store.count_quarantined_slices(spool_id).unwrap(), 1);
+
+        store.clear_all_quarantined_slices(spool_id).unwrap();
+        assert_eq!(store.count_quarantined_slices(spool_id).unwrap(), 0);
+        assert!(store.is_slice_quarantined(other, a).unwrap());
+    }
+
+    #[test]
+    fn quarantined_slice_is_not_usable() {
+        use crate::ops::SliceOps;
+
+        let store = test_store();
+        let spool_id = SpoolIndex(9);
+        let a = Address::new_unique();
+
+        assert!(!store.has_usable_slice(spool_id, a).unwrap());
+        store.put_slice(spool_id, a, vec![1, 2, 3]).unwrap();
+        assert!(store.has_usable_slice(spool_id, a).unwrap());
+
+        store.quarantine_slice(spool_id, a, EpochNumber(3)).unwrap();
+        assert!(store.has_slice(spool_id, a).unwrap());
+        assert!(!store.has_usable_slice(spool_id, a).unwrap());
+
+        store.release_quarantined_slice(spool_id, a).unwrap();
+        assert!(store.has_usable_slice(spool_id, a).unwrap());
+    }
 }
@@ -1,11 +1,11 @@
-//! TapeInfo operations for tape metadata
+//! TapeInfo operations for tape metadata and tape ownership
 
-use store::Store;
+use store::{Column, Store};
 use tape_crypto::address::Address;
 
-use crate::columns::TapeCol;
-use crate::error::Result;
-use crate::types::TapeInfo;
+use crate::columns::{OwnerTapeCol, TapeCol, TapeOwnerCol};
+use crate::error::{Result, TapeStoreError};
+use crate::types::{OwnerTapeKey, TapeInfo, UnitKey};
 use crate::TapeStore;
 
 /// Operations for tape info
@@ -21,6 +21,19 @@ pub trait TapeOps {
 
     /// Iterate all stored tapes
     fn iter_all_tapes(&self) -> Result<Vec<(Address, TapeInfo)>>;
+
+    /// Get the current owner authority of a tape, if known
+    fn get_tape_owner(&self, tape_address: Address) -> Result<Option<Address>>;
+
+    /// Record `owner` as the tape's owner, moving it out of the previous
+    /// owner's index
+    fn set_tape_owner(&self, tape_address: Address, owner: Address) -> Result<()>;
+
+    /// Forget a tape's owner
+    fn delete_tape_owner(&self, tape_address: Address) -> Result<()>;
+
+    /// List the tapes an authority currently owns
+    fn list_owned_tapes(&self, owner: Address) -> Result<Vec<Address>>;
 }
 
 impl<S: Store> TapeOps for TapeStore<S> {
@@ -41,6 +54,44 @@ impl<S: Store> TapeOps for TapeStore<S> {
     fn iter_all_tapes(&self) -> Result<Vec<(Address, TapeInfo)>> {
         Ok(self.iter::<TapeCol>()?.into_iter().collect())
     }
+
+    fn get_tape_owner(&self, tape_address: Address) -> Result<Option<Address>> {
+        Ok(self.get::<TapeOwnerCol>(&tape_address)?)
+    }
+
+    fn set_tape_owner(&self, tape_address: Address, owner: Address) -> Result<()> {
+        if let Some(previous) = self.get_tape_owner(tape_address)? {
+            if previous == owner {
+                return Ok(());
+            }
+            self.delete::<OwnerTapeCol>(&OwnerTapeKey::new(previous, tape_address))?;
+        }
+        self.put::<TapeOwnerCol>(&tape_address, &owner)?;
+        self.put::<OwnerTapeCol>(&OwnerTapeKey::new(owner, tape_address), &UnitKey)?;
+        Ok(())
+    }
+
+    fn delete_tape_owner(&self, tape_address: Address) -> Result<()> {
+        if let Some(owner) = self.get_tape_owner(tape_address)? {
+            self.delete::<OwnerTapeCol>(&OwnerTapeKey::new(owner, tape_address))?;
+            self.delete::<TapeOwnerCol>(&tape_address)?;
+        }
+        Ok(())
+    }
+
+    fn list_owned_tapes(&self, owner: Address) -> Result<Vec<Address>> {
+        let keys = self
+            .inner()
+            .inner()
+            .iter_keys_prefix(OwnerTapeCol::CF_NAME, &OwnerTapeKey::owner_prefix(owner))?;
+        keys.iter()
+            .map(|key| {
+                wincode::deserialize::<OwnerTapeKey>(key)
+                    .map(|key| key.tape)
+                    .map_err(|e| TapeStoreError::Serialization(format!("owner tape key: {e}")))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -92,6 +143,29 @@ mod tests {
         assert!(addresses.contains(&tape2));
     }
 
+    #[test]
+    fn test_tape_owner_follows_transfer() {
+        let store = test_store();
+        let tape = Address::new_unique();
+        let first = Address::new_unique();
+        let second = Address::new_unique();
+
+        assert!(store.get_tape_owner(tape).unwrap().is_none());
+
+        store.set_tape_owner(tape, first).unwrap();
+        assert_eq!(store.get_tape_owner(tape).unwrap(), Some(first));
+        assert_eq!(store.list_owned_tapes(first).unwrap(), vec![tape]);
+
+        store.set_tape_owner(tape, second).unwrap();
+        assert_eq!(store.get_tape_owner(tape).unwrap(), Some(second));
+        assert!(store.list_owned_tapes(first).unwrap().is_empty());
+        assert_eq!(store.list_owned_tapes(second).unwrap(), vec![tape]);
+
+        store.delete_tape_owner(tape).unwrap();
+        assert!(store.get_tape_owner(tape).unwrap().is_none());
+        assert!(store.list_owned_tapes(second).unwrap().is_empty());
+    }
+
     #[test]
     fn test_tape_delete() {
         let store = test_store();
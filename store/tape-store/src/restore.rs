@@ -0,0 +1,233 @@
+//! Restoring a node store from a checkpoint
+//!
+//! A checkpoint written by [`store::Store::checkpoint`] on a split store holds
+//! the metadata instance and the bulk instance under one root, in the same
+//! meta and bulk subdirectories a single-root store uses. Restoring moves the
+//! two instances into the node's configured directories, after checking that
+//! the checkpoint was taken by the same node identity the live store holds.
+//!
+//! The live directories are renamed aside rather than deleted, so an operator
+//! can roll the restore back by hand. The checkpoint itself is consumed.
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use store_rocks::RocksStore;
+use tape_crypto::address::Address;
+use tape_crypto::Hash;
+
+use crate::config;
+use crate::error::{Result, TapeStoreError};
+use crate::ops::MetaOps;
+use crate::TapeStore;
+
+/// Suffix given to the live directories a restore replaces
+pub const PRE_RESTORE_SUFFIX: &str = ".pre-restore";
+
+/// Node identity recorded in a store's metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreIdentity {
+    pub node_address: Option<Address>,
+    pub cluster_hash: Option<Hash>,
+}
+
+impl StoreIdentity {
+    fn read<S: store::Store>(store: &TapeStore<S>) -> Result<Self> {
+        Ok(Self {
+            node_address: store.get_node_address()?,
+            cluster_hash: store.get_cluster_hash()?,
+        })
+    }
+}
+
+/// Read the identity of the checkpoint under `checkpoint`
+///
+/// Opens both volumes read-only, so a checkpoint missing either one fails
+/// here rather than after the live store was moved aside.
+pub fn checkpoint_identity(checkpoint: &Path) -> Result<StoreIdentity> {
+    let store = TapeStore::open_read_only(checkpoint)?;
+    StoreIdentity::read(&store)
+}
+
+/// Replace the store at `meta_dir` and `bulk_dir` with the checkpoint under
+/// `checkpoint`
+///
+/// Refuses unless the checkpoint records `node_address`, and, when a live
+/// store exists, unless its node address and cluster hash agree with the
+/// checkpoint's. The live metadata store is opened as a primary for the check,
+/// so a restore against a running node fails on the database lock.
+pub fn restore_checkpoint(
+    checkpoint: &Path,
+    meta_dir: &Path,
+    bulk_dir: &Path,
+    node_address: Address,
+) -> Result<StoreIdentity> {
+    let restored = checkpoint_identity(checkpoint)?;
+    match restored.node_address {
+        Some(address) if address == node_address => {}
+        Some(address) => {
+            return Err(TapeStoreError::IdentityMismatch(format!(
+                "checkpoint belongs to node {address}, not {node_address}"
+            )));
+        }
+        None => {
+            return Err(TapeStoreError::IdentityMismatch(
+                "checkpoint records no node address".into(),
+            ));
+        }
+    }
+
+    if meta_dir.exists() {
+        let live = live_identity(meta_dir)?;
+        check_live_identity(&live, &restored)?;
+    }
+
+    let meta_aside = aside_path(meta_dir);
+    let bulk_aside = aside_path(bulk_dir);
+    for aside in [&meta_aside, &bulk_aside] {
+        if aside.exists() {
+            let error = std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{} is left from an earlier restore; remove it first", aside.display()),
+            );
+            return Err(store::Error::Io(error).into());
+        }
+    }
+
+    for (dir, aside) in [(meta_dir, &meta_aside), (bulk_dir, &bulk_aside)] {
+        if dir.exists() {
+            fs::rename(dir, aside).map_err(store::Error::from)?;
+        }
+    }
+    move_dir(&checkpoint.join(config::META_SUBDIR), meta_dir)?;
+    move_dir(&checkpoint.join(config::BULK_SUBDIR), bulk_dir)?;
+    // Only the emptied checkpoint root is left; failing to drop it is harmless.
+    let _ = fs::remove_dir(checkpoint);
+    Ok(restored)
+}
+
+/// Open only the live metadata instance, as a primary, and read its identity
+fn live_identity(meta_dir: &Path) -> Result<StoreIdentity> {
+    let meta = RocksStore::open_with_cf_config(
+        meta_dir,
+        config::create_db_options(),
+        config::create_metadata_store_configs(),
+    )?;
+    StoreIdentity::read(&TapeStore::new(meta))
+}
+
+fn check_live_identity(live: &StoreIdentity, restored: &StoreIdentity) -> Result<()> {
+    if let (Some(live), Some(restored)) = (live.node_address, restored.node_address) {
+        if live != restored {
+            return Err(TapeStoreError::IdentityMismatch(format!(
+                "live store belongs to node {live}, checkpoint to {restored}"
+            )));
+        }
+    }
+    if let (Some(live), Some(restored)) = (live.cluster_hash, restored.cluster_hash) {
+        if live != restored {
+            return Err(TapeStoreError::IdentityMismatch(format!(
+                "live store is on cluster {live}, checkpoint on {restored}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn aside_path(dir: &Path) -> PathBuf {
+    let mut name = OsString::from(dir.as_os_str());
+    name.push(PRE_RESTORE_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Rename `from` to `to`, copying instead when they sit on different devices
+fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_dir(from, to).map_err(store::Error::from)?;
+    fs::remove_dir_all(from).map_err(store::Error::from)?;
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(not(miri))]
+mod tests {
+    use super::*;
+    use store::Store;
+    use tempfile::tempdir;
+
+    fn checkpointed(root: &Path, checkpoint: &Path, node: Address, cluster: Hash) {
+        let store = TapeStore::open_primary(root).unwrap();
+        store.set_node_address(node).unwrap();
+        store.set_cluster_hash(cluster).unwrap();
+        store.inner().inner().checkpoint(checkpoint).unwrap();
+    }
+
+    // a checkpoint restores over the live store and the live store is kept aside
+    #[test]
+    fn restore() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("store");
+        let checkpoint = dir.path().join("checkpoint");
+        let node = Address::new_unique();
+        let cluster = Hash::new_unique();
+        checkpointed(&root, &checkpoint, node, cluster);
+        {
+            let store = TapeStore::open_primary(&root).unwrap();
+            store.set_sync_cursor(tape_core::types::SlotNumber(9)).unwrap();
+        }
+
+        let meta_dir = root.join(config::META_SUBDIR);
+        let bulk_dir = root.join(config::BULK_SUBDIR);
+        let identity = restore_checkpoint(&checkpoint, &meta_dir, &bulk_dir, node).unwrap();
+        assert_eq!(identity.node_address, Some(node));
+        assert_eq!(identity.cluster_hash, Some(cluster));
+        assert!(aside_path(&meta_dir).exists());
+        assert!(!checkpoint.exists());
+
+        let store = TapeStore::open_primary(&root).unwrap();
+        assert_eq!(store.get_node_address().unwrap(), Some(node));
+        assert_eq!(store.get_sync_cursor().unwrap(), None);
+    }
+
+    // another node's checkpoint, or one from another cluster, is refused
+    #[test]
+    fn identity_mismatch() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("store");
+        let checkpoint = dir.path().join("checkpoint");
+        let node = Address::new_unique();
+        checkpointed(&root, &checkpoint, node, Hash::new_unique());
+
+        let meta_dir = root.join(config::META_SUBDIR);
+        let bulk_dir = root.join(config::BULK_SUBDIR);
+        let other = Address::new_unique();
+        let result = restore_checkpoint(&checkpoint, &meta_dir, &bulk_dir, other);
+        assert!(matches!(result, Err(TapeStoreError::IdentityMismatch(_))));
+
+        {
+            let store = TapeStore::open_primary(&root).unwrap();
+            store.set_cluster_hash(Hash::new_unique()).unwrap();
+        }
+        let result = restore_checkpoint(&checkpoint, &meta_dir, &bulk_dir, node);
+        assert!(matches!(result, Err(TapeStoreError::IdentityMismatch(_))));
+        assert!(checkpoint.exists());
+        assert!(!aside_path(&meta_dir).exists());
+    }
+}
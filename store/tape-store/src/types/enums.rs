@@ -1,5 +1,7 @@
 //! Enum types for tape-store
 
+use std::net::IpAddr;
+
 use serde::{Deserialize, Serialize};
 use wincode_derive::{SchemaRead, SchemaWrite};
 
@@ -81,6 +83,12 @@ pub enum AuditOp {
     Abort,
     /// An admin control-plane mutation.
     Admin,
+    /// A bucket configuration change (e.g. `PutBucketVersioning`)
+    Configure,
+    /// `GetObject` or `HeadObject`
+    Get,
+    /// A listing (`ListObjects`, `ListObjectVersions`, bucket config reads)
+    List,
 }
 
 /// The effect a policy rule asserts when it matches a write request.
@@ -103,6 +111,124 @@ pub enum PolicyAction {
     Delete,
     /// Matches the multipart-upload lifecycle (create / upload-part / complete)
     Multipart,
+    /// Matches bucket configuration changes (e.g. `PutBucketVersioning`)
+    Configure,
+    /// Matches object reads (`GetObject` / `HeadObject`)
+    Get,
+    /// Matches bucket listings
+    List,
+}
+
+impl PolicyAction {
+    /// Whether this is a read action, which `Any` does not cover
+    pub fn is_read(self) -> bool {
+        matches!(self, PolicyAction::Get | PolicyAction::List)
+    }
+}
+
+/// The object keys a policy rule condition admits.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub enum KeyPattern {
+    /// Keys starting with this prefix
+    Prefix(String),
+    /// Keys matching a glob, where `*` matches any run of characters
+    /// (including `/`) and `?` matches exactly one
+    Glob(String),
+}
+
+impl KeyPattern {
+    /// Whether `key` is admitted by this pattern
+    pub fn matches(&self, key: &str) -> bool {
+        match self {
+            KeyPattern::Prefix(prefix) => key.starts_with(prefix.as_str()),
+            KeyPattern::Glob(glob) => glob_matches(glob, key),
+        }
+    }
+}
+
+/// Iterative glob match that backtracks only to the most recent `*`
+fn glob_matches(glob: &str, key: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let key: Vec<char> = key.chars().collect();
+    let (mut g, mut k) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while k < key.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g, k));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == key[k] => {
+                g += 1;
+                k += 1;
+            }
+            _ => match star {
+                Some((star_g, star_k)) => {
+                    g = star_g + 1;
+                    k = star_k + 1;
+                    star = Some((star_g, star_k + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+/// A source network a policy rule condition admits.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub enum CidrBlock {
+    V4 { network: [u8; 4], prefix_len: u8 },
+    V6 { network: [u8; 16], prefix_len: u8 },
+}
+
+impl CidrBlock {
+    /// The block of `prefix_len` leading bits of `address`; `None` when the
+    /// prefix is longer than the address.
+    pub fn new(address: IpAddr, prefix_len: u8) -> Option<Self> {
+        match address {
+            IpAddr::V4(v4) if prefix_len <= 32 => Some(CidrBlock::V4 {
+                network: v4.octets(),
+                prefix_len,
+            }),
+            IpAddr::V6(v6) if prefix_len <= 128 => Some(CidrBlock::V6 {
+                network: v6.octets(),
+                prefix_len,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether `address` falls in this block. An IPv4-mapped IPv6 address
+    /// is matched as IPv4.
+    pub fn contains(&self, address: IpAddr) -> bool {
+        let address = match address {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(address, IpAddr::V4),
+            IpAddr::V4(_) => address,
+        };
+        match (self, address) {
+            (CidrBlock::V4 { network, prefix_len }, IpAddr::V4(v4)) => {
+                prefix_matches(network, &v4.octets(), *prefix_len)
+            }
+            (CidrBlock::V6 { network, prefix_len }, IpAddr::V6(v6)) => {
+                prefix_matches(network, &v6.octets(), *prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_matches(network: &[u8], address: &[u8], prefix_len: u8) -> bool {
+    let full = usize::from(prefix_len / 8);
+    if network[..full] != address[..full] {
+        return false;
+    }
+    let rest = prefix_len % 8;
+    if rest == 0 {
+        return true;
+    }
+    let mask = 0xffu8 << (8 - rest);
+    network[full] & mask == address[full] & mask
 }
 
 /// The outcome an audit entry records for a write-authorization decision
@@ -114,6 +240,27 @@ pub enum AuditDecision {
     Deny,
 }
 
+/// Read access of an S3 bucket. A bucket that was never configured has no
+/// stored access and is public.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub enum BucketAccess {
+    /// Anyone may read and list the bucket
+    Public,
+    /// Reads and listings need a credential with the matching cap and scope
+    Private,
+}
+
+/// Versioning state of an S3 bucket. A bucket that was never configured has
+/// no stored status and is unversioned.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub enum VersioningStatus {
+    /// Every write is kept as a version; deletes place delete markers
+    Enabled,
+    /// Versions already kept stay; new writes and deletes replace the key's
+    /// null version instead of adding one
+    Suspended,
+}
+
 impl ObjectInfo {
     pub fn is_certified(&self) -> bool {
         matches!(
@@ -226,6 +373,9 @@ mod tests {
             AuditOp::CompleteMultipart,
             AuditOp::Abort,
             AuditOp::Admin,
+            AuditOp::Configure,
+            AuditOp::Get,
+            AuditOp::List,
         ] {
             let bytes = wincode::serialize(&op).expect("serialize");
             assert_eq!(wincode::deserialize::<AuditOp>(&bytes).expect("deserialize"), op);
@@ -249,6 +399,9 @@ mod tests {
             PolicyAction::Put,
             PolicyAction::Delete,
             PolicyAction::Multipart,
+            PolicyAction::Configure,
+            PolicyAction::Get,
+            PolicyAction::List,
         ] {
             let bytes = wincode::serialize(&action).expect("serialize");
             assert_eq!(
@@ -256,5 +409,58 @@ mod tests {
                 action
             );
         }
+        for status in [VersioningStatus::Enabled, VersioningStatus::Suspended] {
+            let bytes = wincode::serialize(&status).expect("serialize");
+            assert_eq!(
+                wincode::deserialize::<VersioningStatus>(&bytes).expect("deserialize"),
+                status
+            );
+        }
+        for pattern in [KeyPattern::Prefix("ci/".into()), KeyPattern::Glob("*.tar".into())] {
+            let bytes = wincode::serialize(&pattern).expect("serialize");
+            assert_eq!(
+                wincode::deserialize::<KeyPattern>(&bytes).expect("deserialize"),
+                pattern
+            );
+        }
+        for access in [BucketAccess::Public, BucketAccess::Private] {
+            let bytes = wincode::serialize(&access).expect("serialize");
+            assert_eq!(
+                wincode::deserialize::<BucketAccess>(&bytes).expect("deserialize"),
+                access
+            );
+        }
+    }
+
+    // prefixes and globs admit the keys they describe
+    #[test]
+    fn key_pattern() {
+        let prefix = KeyPattern::Prefix("artifacts/ci/".into());
+        assert!(prefix.matches("artifacts/ci/build.tar"));
+        assert!(!prefix.matches("artifacts/release/build.tar"));
+
+        let glob = KeyPattern::Glob("artifacts/*/build-?.tar".into());
+        assert!(glob.matches("artifacts/ci/build-1.tar"));
+        assert!(glob.matches("artifacts/ci/nightly/build-2.tar"));
+        assert!(!glob.matches("artifacts/ci/build-10.tar"));
+        assert!(!glob.matches("artifacts/ci/build-1.tar.gz"));
+        assert!(KeyPattern::Glob("*".into()).matches(""));
+    }
+
+    // a block admits addresses sharing its prefix, v4-mapped v6 included
+    #[test]
+    fn cidr_block() {
+        let block = CidrBlock::new("10.1.0.0".parse().unwrap(), 20).unwrap();
+        assert!(block.contains("10.1.15.9".parse().unwrap()));
+        assert!(!block.contains("10.1.16.0".parse().unwrap()));
+        assert!(block.contains("::ffff:10.1.2.3".parse().unwrap()));
+        assert!(!block.contains("2001:db8::1".parse().unwrap()));
+
+        let v6 = CidrBlock::new("2001:db8::".parse().unwrap(), 32).unwrap();
+        assert!(v6.contains("2001:db8:ffff::1".parse().unwrap()));
+        assert!(CidrBlock::new("10.0.0.0".parse().unwrap(), 33).is_none());
+        assert!(CidrBlock::new("0.0.0.0".parse().unwrap(), 0)
+            .unwrap()
+            .contains("192.0.2.1".parse().unwrap()));
     }
 }
@@ -5,9 +5,12 @@
 //! - UnitKey: empty (0 bytes)
 //! - SpoolIndexKey: spool_id BE (2 bytes)
 //! - SliceKey: (spool_id BE, track_address) (34 bytes)
+//! - SliceSegmentKey: (spool_id BE, track_address, segment BE) (36 bytes)
 //! - TrackLookupKey: (tape, track_number BE, key) (72 bytes)
 //! - SnapshotArtifactKey: (epoch BE, group BE, chunk BE) (24 bytes)
 //! - VoteSigKey: (voting_epoch BE, kind BE, target_epoch BE, hash, group BE, signer) (96 bytes)
+//! - ObjectVersionKey: (bucket, name, 0x00, !track_number BE, !marker BE) (45+ bytes)
+//! - OwnerTapeKey: (owner, tape) (64 bytes)
 
 use std::mem::MaybeUninit;
 
@@ -267,6 +270,55 @@ impl<'de> SchemaRead<'de> for SliceKey {
     }
 }
 
+/// Key for a staged slice segment (36 bytes)
+///
+/// Format: [spool_id BE 2 bytes][track_address 32 bytes][segment BE 2 bytes]
+///
+/// Prefixed by the slice key so one slice's segments iterate in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SliceSegmentKey {
+    pub slice: SliceKey,
+    pub segment: u16,
+}
+
+impl SliceSegmentKey {
+    pub const SIZE: usize = SliceKey::SIZE + 2;
+
+    pub fn new(slice: SliceKey, segment: u16) -> Self {
+        Self { slice, segment }
+    }
+}
+
+impl SchemaWrite for SliceSegmentKey {
+    type Src = Self;
+
+    fn size_of(_src: &Self::Src) -> WriteResult<usize> {
+        Ok(Self::SIZE)
+    }
+
+    fn write(writer: &mut Writer, src: &Self::Src) -> WriteResult<()> {
+        SliceKey::write(writer, &src.slice)?;
+        writer.write_exact(&src.segment.to_be_bytes())?;
+        Ok(())
+    }
+}
+
+impl<'de> SchemaRead<'de> for SliceSegmentKey {
+    type Dst = Self;
+
+    fn read(reader: &mut Reader<'de>, dst: &mut MaybeUninit<SliceSegmentKey>) -> ReadResult<()> {
+        let spool_bytes: [u8; 2] = unsafe { reader.get_t()? };
+        let track_bytes: [u8; 32] = unsafe { reader.get_t()? };
+        let segment_bytes: [u8; 2] = unsafe { reader.get_t()? };
+        let spool_id = u16::from_be_bytes(spool_bytes);
+        dst.write(SliceSegmentKey {
+            slice: SliceKey::new(SpoolIndex(spool_id as u64), Address::from(track_bytes)),
+            segment: u16::from_be_bytes(segment_bytes),
+        });
+        Ok(())
+    }
+}
+
 /// Key for per-chunk snapshot build artifacts (24 bytes)
 ///
 /// Format: [epoch BE 8 bytes][group BE 8 bytes][chunk BE 8 bytes]
@@ -547,6 +599,151 @@ impl<'de> SchemaRead<'de> for ObjectListKey {
     }
 }
 
+/// One version of an object within its key.
+///
+/// Object versions are the object's own track, so they carry its track number
+/// and `marker == 0`. Delete markers have no track: they take the track number
+/// of the newest version they hide and a `marker` sequence of at least 1, so a
+/// marker always orders after the version it was placed over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ObjectVersionId {
+    pub track_number: TrackNumber,
+    pub marker: u32,
+}
+
+impl ObjectVersionId {
+    /// Encoded size of a version id in bytes.
+    pub const SIZE: usize = 12;
+
+    /// The version held by the object track `track_number`.
+    pub fn track(track_number: TrackNumber) -> Self {
+        Self {
+            track_number,
+            marker: 0,
+        }
+    }
+
+    /// Whether this version is a delete marker rather than a track.
+    pub fn is_delete_marker(&self) -> bool {
+        self.marker != 0
+    }
+
+    /// Bitwise-inverted big-endian encoding, so newer versions sort first.
+    pub fn to_key_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[..8].copy_from_slice(&(!self.track_number.0).to_be_bytes());
+        bytes[8..].copy_from_slice(&(!self.marker).to_be_bytes());
+        bytes
+    }
+
+    /// Inverse of `to_key_bytes`.
+    pub fn from_key_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        let mut track_number = [0u8; 8];
+        let mut marker = [0u8; 4];
+        track_number.copy_from_slice(&bytes[..8]);
+        marker.copy_from_slice(&bytes[8..]);
+        Self {
+            track_number: TrackNumber(!u64::from_be_bytes(track_number)),
+            marker: !u32::from_be_bytes(marker),
+        }
+    }
+}
+
+impl SchemaWrite for ObjectVersionId {
+    type Src = Self;
+
+    fn size_of(_src: &Self::Src) -> WriteResult<usize> {
+        Ok(Self::SIZE)
+    }
+
+    fn write(writer: &mut Writer, src: &Self::Src) -> WriteResult<()> {
+        writer.write_exact(&src.to_key_bytes())?;
+        Ok(())
+    }
+}
+
+impl<'de> SchemaRead<'de> for ObjectVersionId {
+    type Dst = Self;
+
+    fn read(reader: &mut Reader<'de>, dst: &mut MaybeUninit<ObjectVersionId>) -> ReadResult<()> {
+        // SAFETY: get_t reads a fixed 12-byte array.
+        let bytes: [u8; ObjectVersionId::SIZE] = unsafe { reader.get_t()? };
+        dst.write(ObjectVersionId::from_key_bytes(bytes));
+        Ok(())
+    }
+}
+
+/// Key for the per-bucket object version history (variable length).
+///
+/// Format: `[bucket 32 bytes][name raw bytes][0x00][version 12 bytes]`. Names
+/// sort as in `ObjectListKey`; the `0x00` separator keeps a name's versions
+/// ahead of any longer name, and the inverted version id orders each key's
+/// versions newest first. Decoding takes the version from the fixed-width tail.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ObjectVersionKey {
+    pub bucket: Address,
+    pub name: Vec<u8>,
+    pub version: ObjectVersionId,
+}
+
+impl ObjectVersionKey {
+    pub fn new(bucket: Address, name: impl Into<Vec<u8>>, version: ObjectVersionId) -> Self {
+        Self {
+            bucket,
+            name: name.into(),
+            version,
+        }
+    }
+
+    /// Prefix bytes for iterating every version of `name` in `bucket`.
+    pub fn name_prefix(bucket: Address, name: &[u8]) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(32 + name.len() + 1);
+        prefix.extend_from_slice(bucket.as_ref());
+        prefix.extend_from_slice(name);
+        prefix.push(0);
+        prefix
+    }
+}
+
+impl SchemaWrite for ObjectVersionKey {
+    type Src = Self;
+
+    fn size_of(src: &Self::Src) -> WriteResult<usize> {
+        Ok(32 + src.name.len() + 1 + ObjectVersionId::SIZE)
+    }
+
+    fn write(writer: &mut Writer, src: &Self::Src) -> WriteResult<()> {
+        writer.write_exact(src.bucket.as_ref())?;
+        writer.write_exact(&src.name)?;
+        writer.write_exact(&[0])?;
+        writer.write_exact(&src.version.to_key_bytes())?;
+        Ok(())
+    }
+}
+
+impl<'de> SchemaRead<'de> for ObjectVersionKey {
+    type Dst = Self;
+
+    fn read(reader: &mut Reader<'de>, dst: &mut MaybeUninit<ObjectVersionKey>) -> ReadResult<()> {
+        // SAFETY: get_t reads a fixed 32-byte array for the Pod bucket field.
+        let bucket: [u8; 32] = unsafe { reader.get_t()? };
+        let remaining = reader.as_slice().len();
+        let name = reader
+            .read_borrowed(remaining.saturating_sub(1 + ObjectVersionId::SIZE))?
+            .to_vec();
+        // SAFETY: get_t reads fixed-size byte arrays for the separator and the
+        // version tail; a key too short to hold them fails the read instead.
+        let _separator: [u8; 1] = unsafe { reader.get_t()? };
+        let version: [u8; ObjectVersionId::SIZE] = unsafe { reader.get_t()? };
+        dst.write(ObjectVersionKey {
+            bucket: Address::from(bucket),
+            name,
+            version: ObjectVersionId::from_key_bytes(version),
+        });
+        Ok(())
+    }
+}
+
 /// Key for the append-only write-authorization audit log (16 bytes).
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct AuditKey {
@@ -779,6 +976,63 @@ impl<'de> SchemaRead<'de> for MultipartPartKey {
     }
 }
 
+/// Key for the owner-to-tape index (64 bytes).
+///
+/// Format: `[owner 32 bytes][tape 32 bytes]`. The owner is a fixed 32-byte
+/// prefix, so one authority's tapes scan together.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct OwnerTapeKey {
+    /// Authority that owns the tape.
+    pub owner: Address,
+    /// Tape account address.
+    pub tape: Address,
+}
+
+impl OwnerTapeKey {
+    /// Encoded size of the key in bytes.
+    pub const SIZE: usize = 64;
+
+    /// Create an index key for `tape` under `owner`.
+    pub fn new(owner: Address, tape: Address) -> Self {
+        Self { owner, tape }
+    }
+
+    /// Prefix bytes for scanning every tape of one owner (32 bytes).
+    pub fn owner_prefix(owner: Address) -> [u8; 32] {
+        owner.to_bytes()
+    }
+}
+
+impl SchemaWrite for OwnerTapeKey {
+    type Src = Self;
+
+    fn size_of(_src: &Self::Src) -> WriteResult<usize> {
+        Ok(Self::SIZE)
+    }
+
+    fn write(writer: &mut Writer, src: &Self::Src) -> WriteResult<()> {
+        writer.write_exact(src.owner.as_ref())?;
+        writer.write_exact(src.tape.as_ref())?;
+        Ok(())
+    }
+}
+
+impl<'de> SchemaRead<'de> for OwnerTapeKey {
+    type Dst = Self;
+
+    fn read(reader: &mut Reader<'de>, dst: &mut MaybeUninit<OwnerTapeKey>) -> ReadResult<()> {
+        // SAFETY: get_t reads fixed 32-byte arrays for the two Pod addresses; the key is a
+        // known fixed width, so the source buffer is guaranteed to hold these bytes.
+        let owner: [u8; 32] = unsafe { reader.get_t()? };
+        let tape: [u8; 32] = unsafe { reader.get_t()? };
+        dst.write(OwnerTapeKey {
+            owner: Address::from(owner),
+            tape: Address::from(tape),
+        });
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -950,6 +1204,37 @@ mod tests {
         assert!(b < other);
     }
 
+    #[test]
+    fn object_version_key_roundtrip() {
+        let version = ObjectVersionId {
+            track_number: TrackNumber(42),
+            marker: 3,
+        };
+        let key = ObjectVersionKey::new(Address::new([0x11; 32]), b"photos/cat.jpg".to_vec(), version);
+        let bytes = wincode::serialize(&key).unwrap();
+        let decoded: ObjectVersionKey = wincode::deserialize(&bytes).unwrap();
+        assert_eq!(key, decoded);
+        assert!(bytes.starts_with(&ObjectVersionKey::name_prefix(key.bucket, &key.name)));
+    }
+
+    // versions of a key sort newest first, and all of them ahead of longer names
+    #[test]
+    fn object_version_key_order() {
+        let bucket = Address::new([0x11; 32]);
+        let encode = |name: &[u8], track: u64, marker: u32| {
+            let version = ObjectVersionId {
+                track_number: TrackNumber(track),
+                marker,
+            };
+            wincode::serialize(&ObjectVersionKey::new(bucket, name.to_vec(), version)).unwrap()
+        };
+        let marker = encode(b"a", 9, 1);
+        let newer = encode(b"a", 9, 0);
+        let older = encode(b"a", 2, 0);
+        let longer = encode(b"ab", 1, 0);
+        assert!(marker < newer && newer < older && older < longer);
+    }
+
     // the audit key serializes to its fixed size
     #[test]
     fn audit_length() {
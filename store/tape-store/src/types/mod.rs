@@ -2,7 +2,8 @@
 //!
 //! This module provides all the types used throughout the tape-store crate:
 //! - Enums: NodeStatus, SpoolState, ObjectInfo
-//! - Keys: EpochKey, UnitKey, SpoolIndexKey, SliceKey, TrackLookupKey, vote/snapshot keys
+//! - Keys: EpochKey, UnitKey, SpoolIndexKey, SliceKey, SliceSegmentKey, TrackLookupKey,
+//!   vote/snapshot keys
 //! - Values: TapeInfo, PackedTrack, snapshot artifacts
 mod enums;
 pub mod keys;
@@ -10,19 +11,22 @@ mod values;
 
 // Re-export enum types
 pub use enums::{
-    AuditDecision, AuditOp, CredentialScope, CredentialStatus, ObjectInfo, PolicyAction,
-    PolicyEffect, SystemObjectKind,
+    AuditDecision, AuditOp, BucketAccess, CidrBlock, CredentialScope, CredentialStatus,
+    KeyPattern, ObjectInfo, PolicyAction, PolicyEffect, SystemObjectKind, VersioningStatus,
 };
 
 // Re-export key types
 pub use keys::{
     AuditKey, EpochKey, EventLogKey, LedgerReservationKey, MultipartPartKey, ObjectListKey,
-    PolicyRuleKey, SliceKey, SnapshotArtifactKey, SpoolIndexKey, TrackLookupKey, UnitKey, VoteSigKey,
+    ObjectVersionId, ObjectVersionKey, OwnerTapeKey, PolicyRuleKey, SliceKey, SliceSegmentKey,
+    SnapshotArtifactKey, SpoolIndexKey, TrackLookupKey, UnitKey, VoteSigKey,
 };
 
 // Re-export value types
 pub use values::{
-    AuditEntry, AuthState, BudgetLimits, Credential, CredentialCaps, InvalidationProof,
-    LedgerEntry, LedgerReservation, MultipartPart, MultipartPartData, MultipartUpload,
-    ObjectListEntry, ObjectMetadata, PolicyRule, SliceValue, SnapshotArtifact, TapeInfo,
+    AuditEntry, AuthState, BucketLifecycle, BudgetLimits, Credential, CredentialCaps,
+    InvalidationProof, LedgerEntry, LedgerReservation, LifecycleRule, MultipartPart,
+    MultipartPartChunks, MultipartPartData, MultipartUpload, ObjectListEntry, ObjectMetadata,
+    ObjectVersion, PartChunk, PolicyConditions, PolicyRequest, PolicyRule, SliceValue,
+    SnapshotArtifact, TapeInfo, TimeWindow,
 };
@@ -1,10 +1,13 @@
 //! Value types for tape-store columns
 
+use std::net::IpAddr;
+
 use serde::{Deserialize, Serialize};
 use tape_core::bls::BlsSignature;
 use tape_core::track::blob::BlobEncoding;
 use tape_core::types::{
-    ContentType, EpochNumber, SlotNumber, SpoolIndex, StorageUnits, TapeNumber, TrackNumber,
+    ContentType, EpochNumber, MetadataEntry, ObjectHeaders, SlotNumber, SpoolIndex, StorageUnits,
+    TapeNumber, TrackNumber,
 };
 use tape_crypto::address::Address;
 use tape_crypto::Hash;
@@ -13,7 +16,8 @@ use wincode::len::BincodeLen;
 use wincode_derive::{SchemaRead, SchemaWrite};
 
 use super::enums::{
-    AuditDecision, AuditOp, CredentialScope, CredentialStatus, PolicyAction, PolicyEffect,
+    AuditDecision, AuditOp, CidrBlock, CredentialScope, CredentialStatus, KeyPattern,
+    PolicyAction, PolicyEffect,
 };
 
 const SLICE_BYTES_LIMIT: usize = 10 * 1024 * 1024;
@@ -96,8 +100,56 @@ pub struct ObjectListEntry {
     pub track_number: TrackNumber,
     /// Storage kind discriminator (`TrackKind::Inline` / `TrackKind::Coded`)
     pub kind: u64,
-    /// Hot content type; precise custom strings are deferred to the data plane
+    /// Hot content type
     pub content_type: ContentType,
+    /// Exact content type and user metadata, returned by metadata listings
+    pub headers: ObjectHeaders,
+}
+
+/// One entry in a key's version history, keyed in `object_versions` by
+/// `[bucket][name][version]`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub enum ObjectVersion {
+    /// A written object, listed exactly as it was when current
+    Object(ObjectListEntry),
+    /// A delete marker hiding the versions before it
+    DeleteMarker {
+        /// Unix seconds the delete was requested at
+        time: i64,
+    },
+}
+
+/// Lifecycle configuration of an S3 bucket, keyed in `bucket_lifecycle` by
+/// the bucket's tape address.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub struct BucketLifecycle {
+    /// Rules in the order they were configured
+    pub rules: Vec<LifecycleRule>,
+}
+
+/// One S3 lifecycle rule: which objects it covers and when they expire.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub struct LifecycleRule {
+    /// Operator-facing rule id
+    pub id: String,
+    /// Disabled rules are kept but never act
+    pub enabled: bool,
+    /// Key prefix the rule covers (empty covers the whole bucket)
+    pub prefix: String,
+    /// Tags an object must all carry, matched against its user metadata
+    pub tags: Vec<MetadataEntry>,
+    /// Days after its write that a current object expires
+    pub expiration_days: Option<u32>,
+    /// Days after it stops being current that a prior version is deleted
+    pub noncurrent_days: Option<u32>,
+}
+
+impl LifecycleRule {
+    /// Whether the rule covers an object named `name` carrying `headers`.
+    pub fn matches(&self, name: &[u8], headers: &ObjectHeaders) -> bool {
+        name.starts_with(self.prefix.as_bytes())
+            && self.tags.iter().all(|tag| headers.user_metadata.contains(tag))
+    }
 }
 
 /// Name metadata keyed by object track address
@@ -107,9 +159,11 @@ pub struct ObjectMetadata {
     pub name: Vec<u8>,
     /// Hot content type
     pub content_type: ContentType,
+    /// Free-form headers served with the object on reads
+    pub headers: ObjectHeaders,
 }
 
-/// The write operations a credential is permitted to perform. Fail-closed: a cap
+/// The operations a credential is permitted to perform. Fail-closed: a cap
 /// that is `false` denies that operation.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
 pub struct CredentialCaps {
@@ -119,24 +173,32 @@ pub struct CredentialCaps {
     pub can_delete: bool,
     /// May perform the multipart-upload lifecycle
     pub can_multipart: bool,
+    /// May perform `GetObject` and `HeadObject` on private buckets
+    pub can_get: bool,
+    /// May list the objects of private buckets
+    pub can_list: bool,
 }
 
 impl CredentialCaps {
-    /// Caps granting every write operation
+    /// Caps granting every operation
     pub fn all() -> Self {
         Self {
             can_put: true,
             can_delete: true,
             can_multipart: true,
+            can_get: true,
+            can_list: true,
         }
     }
 
-    /// Caps granting no write operation (the fail-closed default)
+    /// Caps granting no operation (the fail-closed default)
     pub fn none() -> Self {
         Self {
             can_put: false,
             can_delete: false,
             can_multipart: false,
+            can_get: false,
+            can_list: false,
         }
     }
 }
@@ -149,9 +211,9 @@ pub struct Credential {
     pub secret_hmac: [u8; 32],
     /// Owner authority pubkey this credential acts on behalf of
     pub principal: Address,
-    /// Which buckets this credential may write to
+    /// Which buckets this credential may write to, or read when private
     pub scope: CredentialScope,
-    /// Which write operations this credential may perform
+    /// Which operations this credential may perform
     pub caps: CredentialCaps,
     /// Active or revoked. A revoked credential never authorizes
     pub status: CredentialStatus,
@@ -178,10 +240,12 @@ impl Credential {
             && self.not_after.map_or(true, |not_after| now < not_after)
     }
 
-    /// Whether this credential's scope admits writes to `bucket`.
-    pub fn allows_bucket(&self, bucket: &Address) -> bool {
+    /// Whether this credential's scope admits `bucket`, whose current owner
+    /// of record is `owner`. An `AnyOwned` key only reaches buckets its
+    /// principal owns; a bucket with no known owner is outside it.
+    pub fn allows_bucket(&self, bucket: &Address, owner: Option<Address>) -> bool {
         match &self.scope {
-            CredentialScope::AnyOwned => true,
+            CredentialScope::AnyOwned => owner == Some(self.principal),
             CredentialScope::Buckets(buckets) => buckets.contains(bucket),
         }
     }
@@ -220,18 +284,121 @@ pub struct PolicyRule {
     /// Operator-facing reason code recorded in the audit log on every decision
     /// this rule drives.
     pub reason: String,
+    /// Further constraints on the request; the default constrains nothing
+    pub conditions: PolicyConditions,
 }
 
 impl PolicyRule {
-    /// Whether this rule matches a concrete `(principal, bucket, action)` request.
-    /// A `None` subject is a wildcard; an Any rule matches every action.
-    pub fn matches(&self, principal: &Address, bucket: &Address, action: PolicyAction) -> bool {
-        self.principal.map_or(true, |rule_principal| rule_principal == *principal)
-            && self.bucket.map_or(true, |rule_bucket| rule_bucket == *bucket)
-            && (matches!(self.action, PolicyAction::Any) || self.action == action)
+    /// Whether this rule matches a concrete request.
+    /// A `None` subject is a wildcard; an Any rule matches every write action,
+    /// so rules written before reads were policed never catch a read.
+    pub fn matches(&self, request: &PolicyRequest<'_>) -> bool {
+        let action_matches = match self.action {
+            PolicyAction::Any => !request.action.is_read(),
+            rule_action => rule_action == request.action,
+        };
+        self.principal.map_or(true, |rule_principal| rule_principal == request.principal)
+            && self.bucket.map_or(true, |rule_bucket| rule_bucket == request.bucket)
+            && action_matches
+            && self.conditions.admit(request)
+    }
+}
+
+/// Conditions a policy rule puts on the requests it matches. Every condition
+/// that is set must hold. A condition on something the request does not carry
+/// (a key on a bucket configuration change, a size on a delete) never holds,
+/// so an allow rule stays fail-closed.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub struct PolicyConditions {
+    /// Object keys the request must target
+    pub key: Option<KeyPattern>,
+    /// Networks the caller address must fall in; empty admits any caller
+    pub source_cidrs: Vec<CidrBlock>,
+    /// UTC window the request must arrive in
+    pub time_window: Option<TimeWindow>,
+    /// Largest object size, in bytes, the request may write
+    pub max_object_size: Option<u64>,
+}
+
+impl PolicyConditions {
+    /// Whether `request` satisfies every set condition
+    pub fn admit(&self, request: &PolicyRequest<'_>) -> bool {
+        let key_holds = match (&self.key, request.key) {
+            (None, _) => true,
+            (Some(pattern), Some(key)) => pattern.matches(key),
+            (Some(_), None) => false,
+        };
+        let source_holds = self.source_cidrs.is_empty()
+            || request
+                .source_ip
+                .map_or(false, |ip| self.source_cidrs.iter().any(|block| block.contains(ip)));
+        let time_holds = self.time_window.map_or(true, |window| window.contains(request.now));
+        let size_holds = match (self.max_object_size, request.size) {
+            (None, _) => true,
+            (Some(max), Some(size)) => size <= max,
+            (Some(_), None) => false,
+        };
+        key_holds && source_holds && time_holds && size_holds
     }
 }
 
+/// A recurring UTC time-of-day window on selected weekdays.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub struct TimeWindow {
+    /// Opening minute after UTC midnight (0..1440)
+    pub start_minute: u16,
+    /// Closing minute after UTC midnight, exclusive. A window that closes
+    /// before it opens runs past midnight.
+    pub end_minute: u16,
+    /// Days the window opens on, bit 0 for Monday through bit 6 for Sunday
+    pub weekdays: u8,
+}
+
+impl TimeWindow {
+    /// Every day of the week
+    pub const ALL_DAYS: u8 = 0x7f;
+
+    /// Whether the unix time `now` falls in the window. The part of an
+    /// overnight window after midnight belongs to the day it opened on.
+    pub fn contains(&self, now: i64) -> bool {
+        const MINUTES_PER_DAY: i64 = 24 * 60;
+        let minutes = now.div_euclid(60);
+        let day = minutes.div_euclid(MINUTES_PER_DAY);
+        let minute = minutes.rem_euclid(MINUTES_PER_DAY);
+        let (start, end) = (i64::from(self.start_minute), i64::from(self.end_minute));
+
+        let opened_on = if start <= end {
+            (start <= minute && minute < end).then_some(day)
+        } else if minute >= start {
+            Some(day)
+        } else if minute < end {
+            Some(day - 1)
+        } else {
+            None
+        };
+        // 1970-01-01 was a Thursday, weekday 3 counting from Monday.
+        opened_on.map_or(false, |day| self.weekdays & (1 << (day + 3).rem_euclid(7)) != 0)
+    }
+}
+
+/// A concrete request evaluated against the policy ruleset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PolicyRequest<'a> {
+    /// Acting principal (owner authority); `Address::default()` when unknown
+    pub principal: Address,
+    /// The bucket tape the request targets
+    pub bucket: Address,
+    pub action: PolicyAction,
+    /// Object key, when the request targets one
+    pub key: Option<&'a str>,
+    /// Caller address, resolved through the trusted proxies
+    pub source_ip: Option<IpAddr>,
+    /// Object bytes the request writes, when it writes any
+    pub size: Option<u64>,
+    /// Request time (unix seconds)
+    pub now: i64,
+}
+
 /// Per-principal write budgets.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
 pub struct BudgetLimits {
@@ -333,8 +500,9 @@ pub struct MultipartUpload {
     pub bucket: Address,
     /// Object key (the on-chain track name) the assembled object will take
     pub key: String,
-    /// Content type captured at CreateMultipartUpload, applied to the object
-    pub content_type: ContentType,
+    /// Headers and user metadata captured at CreateMultipartUpload, applied
+    /// to the object
+    pub headers: ObjectHeaders,
     /// Initiation time (unix seconds)
     pub initiated: i64,
     /// Owner authority that opened the upload; bounds the per-principal
@@ -342,8 +510,8 @@ pub struct MultipartUpload {
     pub principal: Address,
 }
 
-/// One uploaded multipart part's metadata, kept apart from its payload so
-/// listing an upload's parts never reads the buffered bytes.
+/// One uploaded multipart part's metadata, kept apart from where its bytes
+/// live so listing an upload's parts reads neither.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
 pub struct MultipartPart {
     /// Part number (1..=10000)
@@ -352,12 +520,31 @@ pub struct MultipartPart {
     pub etag: Hash,
     /// Upload time (unix seconds)
     pub last_modified: i64,
-    /// Size of the buffered payload in bytes
+    /// Size of the part in bytes
+    pub size: u64,
+}
+
+/// The chunk tracks one multipart part was written to at UploadPart, in
+/// order; completion links every part's chunks under one manifest.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub struct MultipartPartChunks {
+    /// The part's chunk tracks on the upload's bucket, in order
+    pub chunks: Vec<PartChunk>,
+}
+
+/// One chunk track of a multipart part.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
+pub struct PartChunk {
+    /// Track number on the bucket's tape
+    pub track_number: TrackNumber,
+    /// Part bytes the chunk holds
     pub size: u64,
 }
 
 /// The buffered bytes of one multipart part, stored in its own column so part
 /// metadata (ListParts, completion validation) loads without the payload.
+/// Parts are no longer buffered; this only reads back parts uploaded before
+/// they were written straight to chunk tracks.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, SchemaRead, SchemaWrite, Serialize)]
 pub struct MultipartPartData {
     /// Raw part bytes
@@ -392,6 +579,7 @@ mod tests {
             track_number: TrackNumber(3),
             kind: 1,
             content_type: ContentType::ImageJpeg,
+            headers: ObjectHeaders::default(),
         };
         let bytes = wincode::serialize(&entry).unwrap();
         let decoded: ObjectListEntry = wincode::deserialize(&bytes).unwrap();
@@ -401,9 +589,13 @@ mod tests {
     // object metadata round-trips through serialization
     #[test]
     fn object_metadata() {
+        let mut headers = ObjectHeaders::with_content_type("image/jpeg; q=0.9");
+        headers.cache_control = Some("max-age=60".into());
+        headers.insert_metadata("camera", "x100");
         let metadata = ObjectMetadata {
             name: b"photos/cat.jpg".to_vec(),
             content_type: ContentType::ImageJpeg,
+            headers,
         };
 
         let bytes = wincode::serialize(&metadata).unwrap();
@@ -411,6 +603,34 @@ mod tests {
         assert_eq!(metadata, decoded);
     }
 
+    // a lifecycle rule round-trips and matches on prefix and every tag
+    #[test]
+    fn lifecycle_rule() {
+        let rule = LifecycleRule {
+            id: "expire-logs".to_string(),
+            enabled: true,
+            prefix: "logs/".to_string(),
+            tags: vec![MetadataEntry {
+                key: "tier".to_string(),
+                value: "scratch".to_string(),
+            }],
+            expiration_days: Some(30),
+            noncurrent_days: None,
+        };
+        let lifecycle = BucketLifecycle {
+            rules: vec![rule.clone()],
+        };
+        let bytes = wincode::serialize(&lifecycle).unwrap();
+        let decoded: BucketLifecycle = wincode::deserialize(&bytes).unwrap();
+        assert_eq!(lifecycle, decoded);
+
+        let mut tagged = ObjectHeaders::default();
+        tagged.insert_metadata("tier", "scratch");
+        assert!(rule.matches(b"logs/a.txt", &tagged));
+        assert!(!rule.matches(b"data/a.txt", &tagged));
+        assert!(!rule.matches(b"logs/a.txt", &ObjectHeaders::default()));
+    }
+
     // a credential round-trips through serialization
     #[test]
     fn credential() {
@@ -533,14 +753,16 @@ mod tests {
             not_after: None,
             grade: None,
         };
-        assert!(any.allows_bucket(&bucket));
+        assert!(any.allows_bucket(&bucket, Some(any.principal)));
+        assert!(!any.allows_bucket(&bucket, Some(Address::new([2u8; 32]))));
+        assert!(!any.allows_bucket(&bucket, None));
 
         let scoped = Credential {
             scope: CredentialScope::Buckets(vec![bucket]),
             ..any
         };
-        assert!(scoped.allows_bucket(&bucket));
-        assert!(!scoped.allows_bucket(&other));
+        assert!(scoped.allows_bucket(&bucket, None));
+        assert!(!scoped.allows_bucket(&other, Some(scoped.principal)));
     }
 
     // a policy rule round-trips and matches the right requests
@@ -555,6 +777,7 @@ mod tests {
                 action: PolicyAction::Put,
                 effect: PolicyEffect::Allow,
                 reason: "owner may put".to_string(),
+                conditions: PolicyConditions::default(),
             },
             PolicyRule {
                 principal: None,
@@ -562,6 +785,16 @@ mod tests {
                 action: PolicyAction::Any,
                 effect: PolicyEffect::Deny,
                 reason: "default deny".to_string(),
+                conditions: PolicyConditions {
+                    key: Some(KeyPattern::Prefix("ci/".to_string())),
+                    source_cidrs: vec![CidrBlock::new([10, 0, 0, 0].into(), 8).unwrap()],
+                    time_window: Some(TimeWindow {
+                        start_minute: 9 * 60,
+                        end_minute: 17 * 60,
+                        weekdays: 0x1f,
+                    }),
+                    max_object_size: Some(1 << 20),
+                },
             },
         ];
         for rule in &rules {
@@ -570,14 +803,76 @@ mod tests {
             assert_eq!(*rule, decoded);
         }
 
+        let request = |principal, bucket, action| PolicyRequest {
+            principal,
+            bucket,
+            action,
+            key: None,
+            source_ip: None,
+            size: None,
+            now: 0,
+        };
         // Exact match.
-        assert!(rules[0].matches(&principal, &bucket, PolicyAction::Put));
+        assert!(rules[0].matches(&request(principal, bucket, PolicyAction::Put)));
         // Wrong action does not match a specific-action rule.
-        assert!(!rules[0].matches(&principal, &bucket, PolicyAction::Delete));
+        assert!(!rules[0].matches(&request(principal, bucket, PolicyAction::Delete)));
         // Wrong bucket does not match.
-        assert!(!rules[0].matches(&principal, &Address::new([9u8; 32]), PolicyAction::Put));
-        // The wildcard rule matches anything.
-        assert!(rules[1].matches(&Address::new([7u8; 32]), &Address::new([8u8; 32]), PolicyAction::Delete));
+        let other_bucket = Address::new([9u8; 32]);
+        assert!(!rules[0].matches(&request(principal, other_bucket, PolicyAction::Put)));
+
+        // The wildcard rule matches any write meeting its conditions: a key
+        // under ci/, from 10/8, on a weekday between 09:00 and 17:00, at most
+        // 1 MiB. 2024-01-01 was a Monday.
+        let monday_noon = 1_704_110_400;
+        let conditioned = PolicyRequest {
+            key: Some("ci/build.tar"),
+            source_ip: Some([10, 2, 3, 4].into()),
+            size: Some(1024),
+            now: monday_noon,
+            ..request(Address::new([7u8; 32]), Address::new([8u8; 32]), PolicyAction::Delete)
+        };
+        assert!(rules[1].matches(&conditioned));
+        assert!(!rules[1].matches(&PolicyRequest {
+            key: Some("release/build.tar"),
+            ..conditioned
+        }));
+        assert!(!rules[1].matches(&PolicyRequest {
+            source_ip: Some([192, 0, 2, 1].into()),
+            ..conditioned
+        }));
+        assert!(!rules[1].matches(&PolicyRequest {
+            now: monday_noon + 6 * 3600,
+            ..conditioned
+        }));
+        assert!(!rules[1].matches(&PolicyRequest {
+            now: monday_noon + 5 * 86_400,
+            ..conditioned
+        }));
+        assert!(!rules[1].matches(&PolicyRequest {
+            size: None,
+            ..conditioned
+        }));
+        // ... but never a read, which needs a rule naming it.
+        assert!(!rules[1].matches(&PolicyRequest {
+            action: PolicyAction::Get,
+            ..conditioned
+        }));
+    }
+
+    // an overnight window counts its early hours toward the day it opened
+    #[test]
+    fn overnight_window() {
+        // Opens 22:00 Friday only, closes 02:00.
+        let window = TimeWindow {
+            start_minute: 22 * 60,
+            end_minute: 2 * 60,
+            weekdays: 1 << 4,
+        };
+        let friday = 1_704_412_800; // 2024-01-05 00:00 UTC
+        assert!(window.contains(friday + 23 * 3600));
+        assert!(window.contains(friday + 25 * 3600));
+        assert!(!window.contains(friday + 3600));
+        assert!(!window.contains(friday + 27 * 3600));
     }
 
     // auth state round-trips and has the expected default